@@ -73,3 +73,25 @@ impl From<std::io::Error> for OapError {
         OapError::Encode(OapEncodeError::Io(err))
     }
 }
+
+#[derive(Debug, Error)]
+pub enum OapSessionError {
+    #[error("session closed")]
+    Closed,
+    #[error("stream reset by peer")]
+    Reset,
+    #[error("peer exceeded stream credits on corr_id {0:#x}")]
+    CreditOverrun(u64),
+    #[error("stream body exceeds {limit} bytes")]
+    TooLarge { limit: usize },
+    #[error("capability length exceeds u16")]
+    CapOutOfBounds,
+    #[error("invalid session config: {0}")]
+    Config(&'static str),
+    #[error("protocol violation: {0}")]
+    Protocol(&'static str),
+    #[error(transparent)]
+    Decode(#[from] OapDecodeError),
+    #[error(transparent)]
+    Encode(#[from] OapEncodeError),
+}
@@ -1,4 +1,4 @@
-//! RO:WHAT — OAP/1 constants, flags, header & frame types, and a Tokio codec (Encoder/Decoder).
+//! RO:WHAT — OAP/1 constants, flags, header & frame types, a Tokio codec (Encoder/Decoder), and a streaming session layer.
 //! RO:WHY  — Pillar 7 (App BFF & SDK); Concerns: SEC/RES/PERF/DX. Stable envelopes for interop.
 //! RO:INTERACTS — bytes, tokio-util::codec; optional ron-proto DTOs; consumed by ron-app-sdk/omnigate.
 //! RO:INVARIANTS — OAP/1 max_frame=1MiB; stream chunk≈64KiB (storage); no lock across .await in codec.
@@ -25,12 +25,13 @@ pub mod seq;
 
 // Stream helpers (as per TODO folders)
 pub mod parser;
+pub mod session;
 pub mod writer;
 
 // Core exports
 pub use codec::{OapDecoder, OapEncoder};
 pub use constants::*;
-pub use error::{OapDecodeError, OapEncodeError, OapError, StatusCode};
+pub use error::{OapDecodeError, OapEncodeError, OapError, OapSessionError, StatusCode};
 pub use flags::Flags;
 pub use frame::Frame;
 pub use header::Header;
@@ -49,4 +50,5 @@ pub use seq::Seq;
 
 // Parser/Writer facades
 pub use parser::{ParserConfig, ParserState};
+pub use session::{InboundRequest, InboundStream, Incoming, Responder, Session, SessionConfig};
 pub use writer::{OapWriter, WriterConfig};
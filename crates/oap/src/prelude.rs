@@ -9,7 +9,7 @@ pub use crate::{
         hello_reply_default, hello_request, is_fire_and_forget, is_terminal, wants_ack, Capability,
         FrameBuilder,
     },
-    error::{OapDecodeError, OapEncodeError, OapError, StatusCode},
+    error::{OapDecodeError, OapEncodeError, OapError, OapSessionError, StatusCode},
     flags::Flags,
     frame::Frame,
     header::Header,
@@ -18,4 +18,5 @@ pub use crate::{
         outcome_from_status, reason, OutcomeClass,
     },
     seq::Seq,
+    session::{InboundRequest, InboundStream, Incoming, Responder, Session, SessionConfig},
 };
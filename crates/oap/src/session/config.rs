@@ -0,0 +1,86 @@
+//! RO:WHAT — Session configuration (in-flight cap, per-stream credit window, chunk size).
+//! RO:WHY  — Flow-control tunables live in one place and derive from the negotiated HELLO reply.
+//! RO:INTERACTS — Used by `Session`; `HelloReply` supplies `max_inflight`/`max_frame`.
+//! RO:INVARIANTS — All knobs > 0; a START chunk plus a full u16 cap always fits in one frame; writer queues derive from the knobs.
+
+use tokio::sync::Semaphore;
+
+use crate::{
+    constants::{MAX_FRAME_BYTES, STREAM_CHUNK_SIZE},
+    error::OapSessionError,
+    hello::HelloReply,
+    Header,
+};
+
+/// Default credit window granted to each inbound stream.
+pub const DEFAULT_STREAM_CREDITS: u32 = 8;
+
+/// Session configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SessionConfig {
+    /// Max concurrent requests awaiting a response, per direction.
+    /// Outbound requests wait for a slot; inbound requests over the cap get 429.
+    pub max_inflight: u16,
+    /// Chunks a peer may send on one stream beyond START before it needs an ACK.
+    pub stream_credits: u32,
+    /// Payload bytes per chunk frame.
+    pub chunk_size: usize,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            max_inflight: 64,
+            stream_credits: DEFAULT_STREAM_CREDITS,
+            chunk_size: STREAM_CHUNK_SIZE,
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Adopt the limits a server advertised in its HELLO reply.
+    pub fn from_hello(reply: &HelloReply) -> Self {
+        let max_frame = reply.max_frame.min(MAX_FRAME_BYTES) as usize;
+        let room = max_frame.saturating_sub(Header::WIRE_SIZE + u16::MAX as usize);
+        Self {
+            max_inflight: reply.max_inflight,
+            chunk_size: STREAM_CHUNK_SIZE.min(room),
+            ..Self::default()
+        }
+    }
+
+    /// Capacity of the writer's control queue (credits, resets, status-only replies).
+    ///
+    /// Each stream open in either direction (at most `max_inflight` per direction) can need
+    /// its opening grant, one grant per credited chunk, and a reset or status reply.
+    /// A peer that makes us queue more than this is not reading and gets disconnected.
+    pub fn control_queue(&self) -> usize {
+        let per_stream = (self.stream_credits as usize).saturating_add(2);
+        (2 * self.max_inflight as usize)
+            .saturating_mul(per_stream)
+            .min(Semaphore::MAX_PERMITS)
+    }
+
+    /// Capacity of the writer's data queue; chunk senders wait while it is full.
+    pub fn data_queue(&self) -> usize {
+        2 * self.max_inflight as usize
+    }
+
+    pub fn validate(&self) -> Result<(), OapSessionError> {
+        if self.max_inflight == 0 {
+            return Err(OapSessionError::Config("max_inflight must be > 0"));
+        }
+        if self.stream_credits == 0 {
+            return Err(OapSessionError::Config("stream_credits must be > 0"));
+        }
+        if self.chunk_size == 0 {
+            return Err(OapSessionError::Config("chunk_size must be > 0"));
+        }
+        if Header::WIRE_SIZE + u16::MAX as usize + self.chunk_size > MAX_FRAME_BYTES as usize {
+            return Err(OapSessionError::Config(
+                "chunk_size leaves no room for header and cap",
+            ));
+        }
+        Ok(())
+    }
+}
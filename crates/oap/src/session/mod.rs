@@ -0,0 +1,576 @@
+//! RO:WHAT — Streaming OAP/1 session: multiplexes request/response streams by `corr_id` over one framed connection.
+//! RO:WHY  — svc-overlay, the SDK and micronode all need START…END chunking, in-flight caps and backpressure; do it once.
+//! RO:INTERACTS — `OapDecoder`/`OapEncoder` via tokio-util `FramedRead`/`FramedWrite`; `HelloReply` for negotiated limits.
+//! RO:INVARIANTS — HELLO happens before the session; ≤ max_inflight open requests per direction (excess inbound → 429);
+//!                 a stream sends START freely and one credit per later chunk; ACK_REQ marks chunks that return a credit;
+//!                 no lock across `.await`; data chunks wait for room in a bounded writer queue; control frames and
+//!                 status-only replies go through a second queue bounded by `SessionConfig::control_queue`, and
+//!                 overflowing it closes the connection; a START reusing an open corr_id closes it with `Protocol`.
+//! RO:METRICS — none here; callers wrap request/respond with RED metrics.
+//! RO:CONFIG — `SessionConfig` (max_inflight, stream_credits, chunk_size).
+//! RO:SECURITY — capabilities ride only on START and stay opaque; peers that overrun credits get their stream reset.
+//! RO:TEST — tests/session_streaming.rs.
+//!
+//! Wire conventions (all frames are ordinary OAP/1 frames):
+//! - data: `REQ` or `RESP` plus `START` on the first chunk and `END` on the last; middle chunks carry `ACK_REQ`.
+//! - credit: `app_proto_id = 0`, `EVENT | REQ|RESP`, payload = big-endian u32 credits for that direction's stream.
+//! - reset: `app_proto_id = 0`, `EVENT | END | REQ|RESP`, no payload; the receiver stops sending that stream.
+
+pub mod config;
+pub mod stream;
+
+use std::{
+    collections::{HashMap, HashSet},
+    future::poll_fn,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    sync::{
+        mpsc::{self, error::TrySendError},
+        oneshot, Semaphore,
+    },
+    task::AbortHandle,
+};
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+use crate::{
+    codec::{OapDecoder, OapEncoder},
+    error::{OapSessionError, StatusCode},
+    flags::Flags,
+    seq::Seq,
+    Frame, Header, OAP_VERSION,
+};
+
+pub use config::{SessionConfig, DEFAULT_STREAM_CREDITS};
+pub use stream::{InboundRequest, InboundStream, Responder};
+
+use stream::{send_stream, Chunk};
+
+/// `app_proto_id` used for session control (credit and reset) frames; shared with HELLO.
+pub const CONTROL_APP_PROTO_ID: u16 = 0;
+
+/// Which way a stream's data flows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Dir {
+    Req,
+    Resp,
+}
+
+impl Dir {
+    fn of(flags: Flags) -> Option<Self> {
+        match (flags.contains(Flags::REQ), flags.contains(Flags::RESP)) {
+            (true, false) => Some(Self::Req),
+            (false, true) => Some(Self::Resp),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn flag(self) -> Flags {
+        match self {
+            Self::Req => Flags::REQ,
+            Self::Resp => Flags::RESP,
+        }
+    }
+}
+
+type StreamKey = (Dir, u64);
+
+/// Sending halves of the writer queues.
+struct Outbox {
+    /// Credits, resets and status-only replies; never awaited, overflow closes the connection.
+    control: mpsc::Sender<Frame>,
+    /// Stream chunks; senders wait for room.
+    data: mpsc::Sender<Frame>,
+}
+
+#[derive(Default)]
+pub(crate) struct Inner {
+    closed: bool,
+    out: Option<Outbox>,
+    /// Reader and writer tasks, aborted when the connection is torn down.
+    tasks: Vec<AbortHandle>,
+    /// Credit windows for streams this side is sending.
+    credits: HashMap<StreamKey, Arc<Semaphore>>,
+    /// Streams the peer is sending that are not finished yet.
+    inbound: HashMap<StreamKey, mpsc::Sender<Result<Chunk, OapSessionError>>>,
+    /// Outbound requests waiting for their response START.
+    pending: HashMap<u64, oneshot::Sender<InboundStream>>,
+    /// Inbound requests whose responder has not finished, by corr_id.
+    open_requests: HashSet<u64>,
+}
+
+pub(crate) struct Shared {
+    pub(crate) cfg: SessionConfig,
+    inner: Mutex<Inner>,
+    inflight: Arc<Semaphore>,
+    seq: Seq,
+}
+
+impl Shared {
+    pub(crate) fn inner(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Queue a control frame or status-only reply.
+    pub(crate) fn send(&self, frame: Frame) -> Result<(), OapSessionError> {
+        self.send_locked(&mut self.inner(), frame)
+    }
+
+    fn send_locked(&self, inner: &mut Inner, frame: Frame) -> Result<(), OapSessionError> {
+        let out = inner.out.as_ref().ok_or(OapSessionError::Closed)?;
+        match out.control.try_send(frame) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Closed(_)) => Err(OapSessionError::Closed),
+            Err(TrySendError::Full(_)) => {
+                // The peer is not reading what it makes us send; drop it rather than buffer.
+                tracing::debug!("oap session: control queue full, closing connection");
+                self.teardown(inner, None);
+                Err(OapSessionError::Closed)
+            }
+        }
+    }
+
+    /// Queue a stream chunk, waiting while the writer is behind.
+    pub(crate) async fn send_data(&self, frame: Frame) -> Result<(), OapSessionError> {
+        let data = self
+            .inner()
+            .out
+            .as_ref()
+            .map(|out| out.data.clone())
+            .ok_or(OapSessionError::Closed)?;
+        data.send(frame).await.map_err(|_| OapSessionError::Closed)
+    }
+
+    /// Register a credit window for a stream this side is about to send.
+    pub(crate) fn open_credits(&self, key: StreamKey) -> Result<Arc<Semaphore>, OapSessionError> {
+        let mut inner = self.inner();
+        if inner.closed {
+            return Err(OapSessionError::Closed);
+        }
+        let credits = Arc::new(Semaphore::new(0));
+        inner.credits.insert(key, credits.clone());
+        Ok(credits)
+    }
+
+    pub(crate) fn close_credits(&self, key: StreamKey) {
+        if let Some(credits) = self.inner().credits.remove(&key) {
+            credits.close();
+        }
+    }
+
+    /// Forget an inbound stream and tell the peer to stop sending it.
+    pub(crate) fn reset_inbound(&self, key: StreamKey, tenant_id: u128) {
+        let mut inner = self.inner();
+        if inner.inbound.remove(&key).is_some() {
+            let _ = self.send_locked(&mut inner, control_frame(key, tenant_id, Flags::END, None));
+        }
+    }
+
+    pub(crate) fn grant(&self, key: StreamKey, tenant_id: u128, credits: u32) {
+        let _ = self.send(credit_frame(key, tenant_id, credits));
+    }
+
+    pub(crate) fn finish_request(&self, corr_id: u64) {
+        self.inner().open_requests.remove(&corr_id);
+    }
+
+    /// Stop the session and let the writer flush what is already queued.
+    fn shutdown(&self) {
+        let mut inner = self.inner();
+        inner.closed = true;
+        inner.out = None;
+        for (_, credits) in inner.credits.drain() {
+            credits.close();
+        }
+        inner.inbound.clear();
+        inner.pending.clear();
+        drop(inner);
+        self.inflight.close();
+    }
+
+    /// Close the connection now: open inbound streams get `Protocol` (or `Closed`) and
+    /// queued frames are dropped unsent.
+    fn teardown(&self, inner: &mut Inner, protocol: Option<&'static str>) {
+        if let Some(reason) = protocol {
+            for tx in inner.inbound.values() {
+                let _ = tx.try_send(Err(OapSessionError::Protocol(reason)));
+            }
+        }
+        inner.closed = true;
+        inner.out = None;
+        for (_, credits) in inner.credits.drain() {
+            credits.close();
+        }
+        inner.inbound.clear();
+        inner.pending.clear();
+        for task in inner.tasks.drain(..) {
+            task.abort();
+        }
+        self.inflight.close();
+    }
+
+    /// Route one decoded frame. Never blocks: full or closed stream buffers are resolved in place.
+    fn dispatch(self: &Arc<Self>, frame: Frame, incoming: &mpsc::UnboundedSender<InboundRequest>) {
+        let header = frame.header;
+        let Some(dir) = Dir::of(header.flags) else {
+            tracing::trace!(
+                corr_id = header.corr_id,
+                "oap session: frame without direction"
+            );
+            return;
+        };
+        let key = (dir, header.corr_id);
+
+        if header.app_proto_id == CONTROL_APP_PROTO_ID && header.flags.contains(Flags::EVENT) {
+            let mut inner = self.inner();
+            if header.flags.contains(Flags::END) {
+                if let Some(credits) = inner.credits.remove(&key) {
+                    credits.close();
+                }
+            } else if let Some(credits) = inner.credits.get(&key) {
+                let n = frame
+                    .payload
+                    .as_deref()
+                    .and_then(|p| <[u8; 4]>::try_from(p).ok())
+                    .map_or(0, u32::from_be_bytes);
+                let room = Semaphore::MAX_PERMITS - credits.available_permits();
+                credits.add_permits((n as usize).min(room));
+            }
+            return;
+        }
+
+        let chunk = Chunk {
+            data: frame.payload.unwrap_or_default(),
+            ack: header.flags.contains(Flags::ACK_REQ),
+            end: header.flags.contains(Flags::END),
+        };
+
+        if !header.flags.contains(Flags::START) {
+            let mut inner = self.inner();
+            let Some(tx) = inner.inbound.get(&key) else {
+                return;
+            };
+            // One slot is always kept free so an overrun can be reported in-band.
+            let overrun = tx.capacity() <= 1;
+            let end = chunk.end;
+            let item = if overrun {
+                Err(OapSessionError::CreditOverrun(header.corr_id))
+            } else {
+                Ok(chunk)
+            };
+            let delivered = match tx.try_send(item) {
+                Ok(()) => !overrun,
+                Err(TrySendError::Full(_)) | Err(TrySendError::Closed(_)) => false,
+            };
+            if end || !delivered {
+                inner.inbound.remove(&key);
+            }
+            if overrun {
+                let _ = self.send_locked(
+                    &mut inner,
+                    control_frame(key, header.tenant_id, Flags::END, None),
+                );
+            }
+            return;
+        }
+
+        match dir {
+            Dir::Req => {
+                let mut inner = self.inner();
+                if inner.closed {
+                    return;
+                }
+                if inner.open_requests.contains(&header.corr_id) {
+                    tracing::debug!(
+                        corr_id = header.corr_id,
+                        "oap session: START reuses an open corr_id, closing connection"
+                    );
+                    self.teardown(&mut inner, Some("START reuses an open corr_id"));
+                    return;
+                }
+                if inner.open_requests.len() >= self.cfg.max_inflight as usize {
+                    let reject = data_frame(
+                        Dir::Resp,
+                        &header,
+                        StatusCode::TooManyRequests as u16,
+                        Flags::START | Flags::END,
+                        None,
+                        None,
+                    );
+                    let _ = self.send_locked(&mut inner, reject);
+                    return;
+                }
+                inner.open_requests.insert(header.corr_id);
+                let stream = self.open_inbound(&mut inner, key, header.clone(), frame.cap, chunk);
+                drop(inner);
+                let responder = Responder::new(self.clone(), &header);
+                let _ = incoming.send(InboundRequest { stream, responder });
+            }
+            Dir::Resp => {
+                let mut inner = self.inner();
+                let Some(waiter) = inner.pending.remove(&header.corr_id) else {
+                    return;
+                };
+                // A response has started; the rest of the request body is not needed.
+                if let Some(credits) = inner.credits.remove(&(Dir::Req, header.corr_id)) {
+                    credits.close();
+                }
+                let stream = self.open_inbound(&mut inner, key, header, frame.cap, chunk);
+                drop(inner);
+                let _ = waiter.send(stream);
+            }
+        }
+    }
+
+    fn open_inbound(
+        self: &Arc<Self>,
+        inner: &mut Inner,
+        key: StreamKey,
+        header: Header,
+        cap: Option<Bytes>,
+        first: Chunk,
+    ) -> InboundStream {
+        // START + the credit window + one slot reserved for an overrun error.
+        let (tx, rx) = mpsc::channel(self.cfg.stream_credits as usize + 2);
+        let end = first.end;
+        let tenant_id = header.tenant_id;
+        let _ = tx.try_send(Ok(first));
+        if !end {
+            inner.inbound.insert(key, tx);
+            let _ = self.send_locked(inner, credit_frame(key, tenant_id, self.cfg.stream_credits));
+        }
+        InboundStream::new(self.clone(), key, header, cap, rx)
+    }
+}
+
+pub(crate) fn data_frame(
+    dir: Dir,
+    like: &Header,
+    code: u16,
+    flags: Flags,
+    cap: Option<Bytes>,
+    payload: Option<Bytes>,
+) -> Frame {
+    Frame {
+        header: Header {
+            len: 0,
+            ver: OAP_VERSION,
+            flags: dir.flag() | flags,
+            code,
+            app_proto_id: like.app_proto_id,
+            tenant_id: like.tenant_id,
+            cap_len: 0,
+            corr_id: like.corr_id,
+        },
+        cap,
+        payload,
+    }
+}
+
+fn control_frame(key: StreamKey, tenant_id: u128, flags: Flags, payload: Option<Bytes>) -> Frame {
+    Frame {
+        header: Header {
+            len: 0,
+            ver: OAP_VERSION,
+            flags: key.0.flag() | Flags::EVENT | flags,
+            code: 0,
+            app_proto_id: CONTROL_APP_PROTO_ID,
+            tenant_id,
+            cap_len: 0,
+            corr_id: key.1,
+        },
+        cap: None,
+        payload,
+    }
+}
+
+fn credit_frame(key: StreamKey, tenant_id: u128, credits: u32) -> Frame {
+    let payload = Bytes::copy_from_slice(&credits.to_be_bytes());
+    control_frame(key, tenant_id, Flags::empty(), Some(payload))
+}
+
+/// Cloneable handle for issuing requests on a session.
+#[derive(Clone)]
+pub struct Session {
+    shared: Arc<Shared>,
+}
+
+/// Inbound requests opened by the peer.
+pub struct Incoming {
+    rx: mpsc::UnboundedReceiver<InboundRequest>,
+}
+
+impl Incoming {
+    /// Next inbound request, or `None` once the session is closed.
+    pub async fn accept(&mut self) -> Option<InboundRequest> {
+        self.rx.recv().await
+    }
+}
+
+impl Session {
+    /// Start a session over an already-negotiated connection (HELLO done).
+    ///
+    /// Spawns a reader and a writer task on the current Tokio runtime.
+    pub fn spawn<T>(io: T, cfg: SessionConfig) -> Result<(Self, Incoming), OapSessionError>
+    where
+        T: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        cfg.validate()?;
+        let (read_half, write_half) = tokio::io::split(io);
+        let (control_tx, control_rx) = mpsc::channel(cfg.control_queue());
+        let (data_tx, data_rx) = mpsc::channel(cfg.data_queue());
+        // Bounded by max_inflight: every accepted request holds an open_requests slot.
+        let (in_tx, in_rx) = mpsc::unbounded_channel();
+        let shared = Arc::new(Shared {
+            cfg,
+            inner: Mutex::new(Inner {
+                out: Some(Outbox {
+                    control: control_tx,
+                    data: data_tx,
+                }),
+                ..Inner::default()
+            }),
+            inflight: Arc::new(Semaphore::new(cfg.max_inflight as usize)),
+            seq: Seq::new(),
+        });
+
+        let writer = tokio::spawn(write_loop(
+            FramedWrite::new(write_half, OapEncoder),
+            control_rx,
+            data_rx,
+        ));
+        let reader = tokio::spawn(read_loop(
+            shared.clone(),
+            FramedRead::new(read_half, OapDecoder),
+            in_tx,
+        ));
+        let mut inner = shared.inner();
+        inner.tasks = vec![writer.abort_handle(), reader.abort_handle()];
+        if inner.closed {
+            // Torn down before the handles were stored.
+            for task in inner.tasks.drain(..) {
+                task.abort();
+            }
+        }
+        drop(inner);
+        Ok((Self { shared }, Incoming { rx: in_rx }))
+    }
+
+    /// Send `body` as one request stream and wait for the response stream.
+    ///
+    /// Waits for an in-flight slot first; the slot is held until the returned
+    /// response stream is dropped. A 429 from the peer arrives as a normal response.
+    pub async fn request(
+        &self,
+        app_proto_id: u16,
+        tenant_id: u128,
+        cap: Option<Bytes>,
+        body: Bytes,
+    ) -> Result<InboundStream, OapSessionError> {
+        if cap.as_ref().is_some_and(|c| c.len() > u16::MAX as usize) {
+            return Err(OapSessionError::CapOutOfBounds);
+        }
+        let permit = self
+            .shared
+            .inflight
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| OapSessionError::Closed)?;
+
+        let corr_id = self.shared.seq.next();
+        let key = (Dir::Req, corr_id);
+        let (tx, rx) = oneshot::channel();
+        let credits = self.shared.open_credits(key)?;
+        self.shared.inner().pending.insert(corr_id, tx);
+
+        let header = Header {
+            len: 0,
+            ver: OAP_VERSION,
+            flags: Flags::REQ,
+            code: 0,
+            app_proto_id,
+            tenant_id,
+            cap_len: 0,
+            corr_id,
+        };
+        let sent = send_stream(&self.shared, Dir::Req, &header, 0, cap, body, &credits).await;
+        self.shared.close_credits(key);
+        match sent {
+            Ok(()) | Err(OapSessionError::Reset) => {}
+            Err(err) => {
+                self.shared.inner().pending.remove(&corr_id);
+                return Err(err);
+            }
+        }
+
+        let mut response = rx.await.map_err(|_| OapSessionError::Closed)?;
+        response.hold(permit);
+        Ok(response)
+    }
+
+    /// Stop the session: open streams fail with `Closed` and the write side is shut down.
+    pub fn close(&self) {
+        self.shared.shutdown();
+    }
+}
+
+async fn write_loop<W: AsyncWrite + Unpin>(
+    mut sink: FramedWrite<W, OapEncoder>,
+    mut control: mpsc::Receiver<Frame>,
+    mut data: mpsc::Receiver<Frame>,
+) {
+    while let Some(frame) = poll_fn(|cx| poll_next_frame(cx, &mut control, &mut data)).await {
+        if let Err(err) = sink.feed(frame).await {
+            tracing::debug!(error = %err, "oap session: write failed");
+            return;
+        }
+        if control.is_empty() && data.is_empty() {
+            if let Err(err) = sink.flush().await {
+                tracing::debug!(error = %err, "oap session: flush failed");
+                return;
+            }
+        }
+    }
+    let _ = sink.close().await;
+}
+
+/// Next frame to write, control first: credits and resets are what unblock the peer's senders.
+/// `None` once both queues are closed and drained.
+fn poll_next_frame(
+    cx: &mut Context<'_>,
+    control: &mut mpsc::Receiver<Frame>,
+    data: &mut mpsc::Receiver<Frame>,
+) -> Poll<Option<Frame>> {
+    let control = control.poll_recv(cx);
+    if let Poll::Ready(Some(frame)) = control {
+        return Poll::Ready(Some(frame));
+    }
+    match data.poll_recv(cx) {
+        Poll::Ready(Some(frame)) => Poll::Ready(Some(frame)),
+        Poll::Ready(None) if control.is_ready() => Poll::Ready(None),
+        _ => Poll::Pending,
+    }
+}
+
+async fn read_loop<R: AsyncRead + Unpin>(
+    shared: Arc<Shared>,
+    mut frames: FramedRead<R, OapDecoder>,
+    incoming: mpsc::UnboundedSender<InboundRequest>,
+) {
+    while let Some(next) = frames.next().await {
+        match next {
+            Ok(frame) => shared.dispatch(frame, &incoming),
+            Err(err) => {
+                tracing::debug!(error = %err, "oap session: decode failed");
+                break;
+            }
+        }
+    }
+    shared.shutdown();
+}
@@ -0,0 +1,261 @@
+//! RO:WHAT — Per-stream halves of a session: chunked sender, inbound stream reader, and request responder.
+//! RO:WHY  — Keep credit accounting next to the code that spends and returns credits.
+//! RO:INTERACTS — `session::Shared` for routing and the writer queue; `Frame`/`Header`/`Flags`.
+//! RO:INVARIANTS — START is sent without credit; every later chunk waits for one; consuming an ACK_REQ chunk returns one;
+//!                 dropping an unfinished inbound stream resets it; dropping an unanswered responder replies 500.
+//! RO:METRICS — none.
+//! RO:CONFIG — `SessionConfig::{stream_credits, chunk_size}`.
+//! RO:SECURITY — caps are attached only to START; `collect` is bounded by the caller's limit.
+//! RO:TEST — tests/session_streaming.rs.
+
+use std::sync::Arc;
+
+use bytes::{Bytes, BytesMut};
+use tokio::sync::{mpsc, OwnedSemaphorePermit, Semaphore};
+
+use super::{data_frame, Dir, Shared, StreamKey};
+use crate::{
+    error::{OapSessionError, StatusCode},
+    flags::Flags,
+    Header,
+};
+
+/// One received chunk as routed by the session reader.
+#[derive(Debug)]
+pub(crate) struct Chunk {
+    pub(crate) data: Bytes,
+    pub(crate) ack: bool,
+    pub(crate) end: bool,
+}
+
+/// Send `body` as START…END chunks, spending one credit per chunk after START.
+///
+/// Returns `Reset` if the peer (or a started response) closed the credit window early.
+pub(crate) async fn send_stream(
+    shared: &Shared,
+    dir: Dir,
+    like: &Header,
+    code: u16,
+    mut cap: Option<Bytes>,
+    body: Bytes,
+    credits: &Semaphore,
+) -> Result<(), OapSessionError> {
+    let chunk_size = shared.cfg.chunk_size;
+    let mut offset = 0;
+    loop {
+        let first = offset == 0;
+        let end = (offset + chunk_size).min(body.len());
+        let last = end == body.len();
+        if !first {
+            match credits.acquire().await {
+                Ok(permit) => permit.forget(),
+                Err(_) if shared.inner().closed => return Err(OapSessionError::Closed),
+                Err(_) => return Err(OapSessionError::Reset),
+            }
+        }
+
+        let mut flags = Flags::empty();
+        if first {
+            flags |= Flags::START;
+        }
+        if last {
+            flags |= Flags::END;
+        } else if !first {
+            flags |= Flags::ACK_REQ;
+        }
+        let payload = (end > offset).then(|| body.slice(offset..end));
+        let cap = if first { cap.take() } else { None };
+        shared
+            .send_data(data_frame(dir, like, code, flags, cap, payload))
+            .await?;
+
+        if last {
+            return Ok(());
+        }
+        offset = end;
+    }
+}
+
+/// A stream the peer is sending: a request body (server side) or a response (client side).
+pub struct InboundStream {
+    shared: Arc<Shared>,
+    key: StreamKey,
+    header: Header,
+    cap: Option<Bytes>,
+    rx: mpsc::Receiver<Result<Chunk, OapSessionError>>,
+    finished: bool,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl InboundStream {
+    pub(crate) fn new(
+        shared: Arc<Shared>,
+        key: StreamKey,
+        header: Header,
+        cap: Option<Bytes>,
+        rx: mpsc::Receiver<Result<Chunk, OapSessionError>>,
+    ) -> Self {
+        Self {
+            shared,
+            key,
+            header,
+            cap,
+            rx,
+            finished: false,
+            _permit: None,
+        }
+    }
+
+    /// Keep an in-flight slot for as long as this stream lives.
+    pub(crate) fn hold(&mut self, permit: OwnedSemaphorePermit) {
+        self._permit = Some(permit);
+    }
+
+    /// Header of the START frame.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    pub fn corr_id(&self) -> u64 {
+        self.header.corr_id
+    }
+
+    /// Status code carried on START (0 for requests).
+    pub fn code(&self) -> u16 {
+        self.header.code
+    }
+
+    /// Capability bytes carried on START.
+    pub fn cap(&self) -> Option<&Bytes> {
+        self.cap.as_ref()
+    }
+
+    /// Next non-empty chunk; `None` after END.
+    pub async fn next_chunk(&mut self) -> Option<Result<Bytes, OapSessionError>> {
+        while !self.finished {
+            let chunk = match self.rx.recv().await {
+                Some(Ok(chunk)) => chunk,
+                Some(Err(err)) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+                None => {
+                    self.finished = true;
+                    return Some(Err(OapSessionError::Closed));
+                }
+            };
+            self.finished = chunk.end;
+            if chunk.ack {
+                self.shared.grant(self.key, self.header.tenant_id, 1);
+            }
+            if !chunk.data.is_empty() {
+                return Some(Ok(chunk.data));
+            }
+        }
+        None
+    }
+
+    /// Read the whole stream, failing with `TooLarge` past `limit` bytes.
+    pub async fn collect(mut self, limit: usize) -> Result<Bytes, OapSessionError> {
+        let mut out = BytesMut::new();
+        while let Some(chunk) = self.next_chunk().await {
+            let chunk = chunk?;
+            if out.len() + chunk.len() > limit {
+                return Err(OapSessionError::TooLarge { limit });
+            }
+            out.extend_from_slice(&chunk);
+        }
+        Ok(out.freeze())
+    }
+}
+
+impl Drop for InboundStream {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.shared.reset_inbound(self.key, self.header.tenant_id);
+        }
+    }
+}
+
+impl std::fmt::Debug for InboundStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InboundStream")
+            .field("header", &self.header)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A request opened by the peer, with the handle used to answer it.
+#[derive(Debug)]
+pub struct InboundRequest {
+    pub stream: InboundStream,
+    pub responder: Responder,
+}
+
+/// Answers one inbound request. Dropping it unanswered replies 500.
+pub struct Responder {
+    shared: Arc<Shared>,
+    header: Header,
+    answered: bool,
+}
+
+impl Responder {
+    pub(crate) fn new(shared: Arc<Shared>, request: &Header) -> Self {
+        Self {
+            shared,
+            header: request.clone(),
+            answered: false,
+        }
+    }
+
+    pub fn corr_id(&self) -> u64 {
+        self.header.corr_id
+    }
+
+    /// Stream `body` back as the response, honouring the peer's credits.
+    ///
+    /// Fails with `Reset` if the peer dropped the response stream part-way.
+    pub async fn respond(mut self, code: StatusCode, body: Bytes) -> Result<(), OapSessionError> {
+        self.answered = true;
+        let key = (Dir::Resp, self.header.corr_id);
+        let credits = self.shared.open_credits(key)?;
+        let sent = send_stream(
+            &self.shared,
+            Dir::Resp,
+            &self.header,
+            code as u16,
+            None,
+            body,
+            &credits,
+        )
+        .await;
+        self.shared.close_credits(key);
+        sent
+    }
+}
+
+impl Drop for Responder {
+    fn drop(&mut self) {
+        if !self.answered {
+            let _ = self.shared.send(data_frame(
+                Dir::Resp,
+                &self.header,
+                StatusCode::Internal as u16,
+                Flags::START | Flags::END,
+                None,
+                None,
+            ));
+        }
+        self.shared.finish_request(self.header.corr_id);
+    }
+}
+
+impl std::fmt::Debug for Responder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Responder")
+            .field("corr_id", &self.header.corr_id)
+            .field("answered", &self.answered)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,388 @@
+//! Session layer: corr_id multiplexing, chunked START…END bodies, credit ACKs, max_inflight, resets.
+
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures_util::{SinkExt, StreamExt};
+use oap::{
+    prelude::*,
+    session::{CONTROL_APP_PROTO_ID, DEFAULT_STREAM_CREDITS},
+    HelloReply,
+};
+use tokio::time::timeout;
+use tokio_util::codec::{FramedRead, FramedWrite};
+
+const APP: u16 = 7;
+const TENANT: u128 = 0xABCD;
+const LIMIT: usize = 16 * 1024 * 1024;
+
+fn small_chunks(max_inflight: u16, stream_credits: u32) -> SessionConfig {
+    SessionConfig {
+        max_inflight,
+        stream_credits,
+        chunk_size: 1024,
+    }
+}
+
+fn body(seed: u8, len: usize) -> Bytes {
+    (0..len)
+        .map(|i| (i as u8).wrapping_mul(31).wrapping_add(seed))
+        .collect::<Vec<u8>>()
+        .into()
+}
+
+/// Serve every request by echoing its body reversed, with the request cap appended.
+fn spawn_echo(mut incoming: Incoming) {
+    tokio::spawn(async move {
+        while let Some(req) = incoming.accept().await {
+            tokio::spawn(async move {
+                let cap = req.stream.cap().cloned().unwrap_or_default();
+                let mut data = req.stream.collect(LIMIT).await.unwrap().to_vec();
+                data.reverse();
+                data.extend_from_slice(&cap);
+                req.responder
+                    .respond(StatusCode::Ok, data.into())
+                    .await
+                    .unwrap();
+            });
+        }
+    });
+}
+
+#[tokio::test]
+async fn concurrent_streams_multiplex_by_corr_id() {
+    let (a, b) = tokio::io::duplex(8 * 1024);
+    let (client, _client_incoming) = Session::spawn(a, small_chunks(8, 2)).unwrap();
+    let (_server, server_incoming) = Session::spawn(b, small_chunks(8, 2)).unwrap();
+    spawn_echo(server_incoming);
+
+    let mut tasks = Vec::new();
+    for i in 0..6_u8 {
+        let client = client.clone();
+        tasks.push(tokio::spawn(async move {
+            let sent = body(i, 1024 * (10 + i as usize * 7) + i as usize);
+            let cap = Bytes::from(format!("cap-{i}"));
+            let resp = client
+                .request(APP, TENANT, Some(cap.clone()), sent.clone())
+                .await
+                .unwrap();
+            assert_eq!(resp.code(), StatusCode::Ok as u16);
+            assert_eq!(resp.header().app_proto_id, APP);
+            let got = resp.collect(LIMIT).await.unwrap();
+            let mut expected = sent.to_vec();
+            expected.reverse();
+            expected.extend_from_slice(&cap);
+            assert_eq!(got.as_ref(), expected.as_slice());
+        }));
+    }
+    for task in tasks {
+        timeout(Duration::from_secs(10), task)
+            .await
+            .expect("no deadlock")
+            .unwrap();
+    }
+
+    // Empty bodies are a single START|END frame each way.
+    let resp = client
+        .request(APP, TENANT, None, Bytes::new())
+        .await
+        .unwrap();
+    assert!(resp.collect(LIMIT).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn sender_waits_for_credit_acks_on_the_wire() {
+    let (a, b) = tokio::io::duplex(1024 * 1024);
+    let (client, _incoming) = Session::spawn(a, small_chunks(4, 2)).unwrap();
+    let (read, write) = tokio::io::split(b);
+    let mut frames = FramedRead::new(read, OapDecoder);
+    let mut sink = FramedWrite::new(write, OapEncoder);
+
+    let request = tokio::spawn(async move {
+        let resp = client
+            .request(APP, TENANT, None, body(1, 10 * 1024))
+            .await
+            .unwrap();
+        resp.collect(LIMIT).await.unwrap()
+    });
+
+    // START goes out without credit; nothing else until the peer grants some.
+    let start = frames.next().await.unwrap().unwrap();
+    let corr_id = start.header.corr_id;
+    assert!(start.header.flags.contains(Flags::REQ | Flags::START));
+    assert!(!start.header.flags.contains(Flags::ACK_REQ));
+    assert!(timeout(Duration::from_millis(100), frames.next())
+        .await
+        .is_err());
+
+    let credit = |n: u32| {
+        let mut frame = FrameBuilder::request(CONTROL_APP_PROTO_ID, TENANT, corr_id)
+            .payload(Bytes::copy_from_slice(&n.to_be_bytes()))
+            .build();
+        frame.header.flags |= Flags::EVENT;
+        frame
+    };
+    sink.send(credit(3)).await.unwrap();
+    for _ in 0..3 {
+        let chunk = frames.next().await.unwrap().unwrap();
+        assert_eq!(chunk.header.corr_id, corr_id);
+        assert_eq!(chunk.payload_len(), 1024);
+        assert!(chunk.header.flags.contains(Flags::ACK_REQ));
+    }
+    assert!(timeout(Duration::from_millis(100), frames.next())
+        .await
+        .is_err());
+
+    sink.send(credit(100)).await.unwrap();
+    let mut received = 4;
+    loop {
+        let chunk = frames.next().await.unwrap().unwrap();
+        received += 1;
+        if chunk.header.flags.contains(Flags::END) {
+            assert!(!chunk.header.flags.contains(Flags::ACK_REQ));
+            break;
+        }
+    }
+    assert_eq!(received, 10);
+
+    let mut done = FrameBuilder::response(APP, TENANT, corr_id, StatusCode::Ok).build();
+    done.header.flags |= Flags::START | Flags::END;
+    done.payload = Some(Bytes::from_static(b"done"));
+    sink.send(done).await.unwrap();
+    assert_eq!(request.await.unwrap().as_ref(), b"done");
+}
+
+#[tokio::test]
+async fn max_inflight_is_enforced_both_ways() {
+    // Server allows one open request; the client is configured more loosely.
+    let (a, b) = tokio::io::duplex(64 * 1024);
+    let (client, _client_incoming) = Session::spawn(a, small_chunks(4, 2)).unwrap();
+    let (_server, mut server_incoming) = Session::spawn(b, small_chunks(1, 2)).unwrap();
+
+    let first = {
+        let client = client.clone();
+        tokio::spawn(async move { client.request(APP, TENANT, None, body(1, 100)).await })
+    };
+    let held = server_incoming.accept().await.unwrap();
+
+    let rejected = client
+        .request(APP, TENANT, None, body(2, 8 * 1024))
+        .await
+        .unwrap();
+    assert_eq!(rejected.code(), StatusCode::TooManyRequests as u16);
+    drop(rejected);
+
+    held.responder
+        .respond(StatusCode::Ok, Bytes::from_static(b"ok"))
+        .await
+        .unwrap();
+    let resp = first.await.unwrap().unwrap();
+    assert_eq!(resp.collect(LIMIT).await.unwrap().as_ref(), b"ok");
+
+    // Client side: with one slot, a second request waits until the first response is dropped.
+    let (a, b) = tokio::io::duplex(64 * 1024);
+    let (client, _client_incoming) = Session::spawn(a, small_chunks(1, 2)).unwrap();
+    let (_server, server_incoming) = Session::spawn(b, small_chunks(4, 2)).unwrap();
+    spawn_echo(server_incoming);
+
+    let first = client
+        .request(APP, TENANT, None, body(3, 10))
+        .await
+        .unwrap();
+    let second = {
+        let client = client.clone();
+        tokio::spawn(async move { client.request(APP, TENANT, None, body(4, 10)).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!second.is_finished());
+    drop(first);
+    let second = timeout(Duration::from_secs(5), second)
+        .await
+        .expect("slot released")
+        .unwrap()
+        .unwrap();
+    assert_eq!(second.code(), StatusCode::Ok as u16);
+}
+
+#[tokio::test]
+async fn dropped_streams_reset_the_sender() {
+    let (a, b) = tokio::io::duplex(64 * 1024);
+    let (client, _client_incoming) = Session::spawn(a, small_chunks(4, 1)).unwrap();
+    let (_server, mut server_incoming) = Session::spawn(b, small_chunks(4, 1)).unwrap();
+
+    let request = {
+        let client = client.clone();
+        tokio::spawn(async move { client.request(APP, TENANT, None, body(5, 10)).await })
+    };
+    let req = server_incoming.accept().await.unwrap();
+    let responder = tokio::spawn(async move {
+        req.responder
+            .respond(StatusCode::Ok, body(6, 64 * 1024))
+            .await
+    });
+
+    let mut resp = request.await.unwrap().unwrap();
+    assert!(resp.next_chunk().await.unwrap().is_ok());
+    drop(resp);
+    let outcome = timeout(Duration::from_secs(5), responder)
+        .await
+        .expect("sender unblocked")
+        .unwrap();
+    assert!(matches!(outcome, Err(OapSessionError::Reset)));
+
+    // An unanswered responder turns into a 500 rather than a hung client.
+    let request = {
+        let client = client.clone();
+        tokio::spawn(async move { client.request(APP, TENANT, None, body(7, 10)).await })
+    };
+    drop(server_incoming.accept().await.unwrap());
+    let resp = request.await.unwrap().unwrap();
+    assert_eq!(resp.code(), StatusCode::Internal as u16);
+}
+
+#[tokio::test]
+async fn closing_fails_open_streams_and_bounds_collect() {
+    let (a, b) = tokio::io::duplex(64 * 1024);
+    let (client, _client_incoming) = Session::spawn(a, small_chunks(4, 2)).unwrap();
+    let (server, mut server_incoming) = Session::spawn(b, small_chunks(4, 2)).unwrap();
+
+    let request = {
+        let client = client.clone();
+        tokio::spawn(async move { client.request(APP, TENANT, None, body(8, 8 * 1024)).await })
+    };
+    let req = server_incoming.accept().await.unwrap();
+    assert!(matches!(
+        req.stream.collect(2048).await,
+        Err(OapSessionError::TooLarge { limit: 2048 })
+    ));
+    drop(req.responder);
+    assert_eq!(
+        request.await.unwrap().unwrap().code(),
+        StatusCode::Internal as u16
+    );
+
+    server.close();
+    let err = timeout(
+        Duration::from_secs(5),
+        client.request(APP, TENANT, None, body(9, 10)),
+    )
+    .await
+    .expect("close propagates")
+    .unwrap_err();
+    assert!(matches!(err, OapSessionError::Closed));
+    assert!(server_incoming.accept().await.is_none());
+}
+
+/// A request START from a raw peer; `end` makes it a complete one-frame request.
+fn raw_start(corr_id: u64, end: bool) -> Frame {
+    let mut frame = FrameBuilder::request(APP, TENANT, corr_id)
+        .payload(Bytes::from_static(b"part"))
+        .build();
+    frame.header.flags |= Flags::START;
+    if end {
+        frame.header.flags |= Flags::END;
+    }
+    frame
+}
+
+#[tokio::test]
+async fn reused_corr_id_is_a_protocol_error() {
+    let (a, b) = tokio::io::duplex(64 * 1024);
+    let (_server, mut server_incoming) = Session::spawn(b, small_chunks(4, 2)).unwrap();
+    let (read, write) = tokio::io::split(a);
+    let mut frames = FramedRead::new(read, OapDecoder);
+    let mut sink = FramedWrite::new(write, OapEncoder);
+
+    sink.send(raw_start(1, false)).await.unwrap();
+    let mut req = server_incoming.accept().await.unwrap();
+    assert_eq!(
+        req.stream.next_chunk().await.unwrap().unwrap().as_ref(),
+        b"part"
+    );
+
+    sink.send(raw_start(1, false)).await.unwrap();
+    assert!(matches!(
+        req.stream.next_chunk().await,
+        Some(Err(OapSessionError::Protocol(_)))
+    ));
+    assert!(server_incoming.accept().await.is_none());
+
+    // The connection is gone: only the first stream's credit grant ever went out.
+    let mut seen = 0;
+    while let Some(frame) = timeout(Duration::from_secs(5), frames.next())
+        .await
+        .expect("connection closed")
+    {
+        assert!(frame.unwrap().header.flags.contains(Flags::EVENT));
+        seen += 1;
+    }
+    assert_eq!(seen, 1);
+}
+
+#[tokio::test]
+async fn peer_that_stops_reading_is_disconnected() {
+    let (a, b) = tokio::io::duplex(4 * 1024);
+    let cfg = small_chunks(1, 1);
+    let (_server, mut server_incoming) = Session::spawn(b, cfg).unwrap();
+    let (_read, write) = tokio::io::split(a);
+    let mut sink = FramedWrite::new(write, OapEncoder);
+
+    // One request holds the only slot; everything after it earns a 429 nobody reads.
+    sink.send(raw_start(1, false)).await.unwrap();
+    let mut held = server_incoming.accept().await.unwrap();
+    assert!(held.stream.next_chunk().await.unwrap().is_ok());
+    let flood = tokio::spawn(async move {
+        for corr_id in 2..20_000 {
+            if sink.send(raw_start(corr_id, true)).await.is_err() {
+                return corr_id;
+            }
+        }
+        u64::MAX
+    });
+
+    assert!(matches!(
+        timeout(Duration::from_secs(5), held.stream.next_chunk())
+            .await
+            .expect("session torn down"),
+        Some(Err(OapSessionError::Closed))
+    ));
+    assert!(server_incoming.accept().await.is_none());
+    let stopped_at = flood.await.unwrap();
+    assert!(stopped_at < u64::MAX, "server stopped reading");
+    assert!(stopped_at > cfg.control_queue() as u64);
+}
+
+#[test]
+fn config_follows_hello_and_validates() {
+    let reply = HelloReply::default_for_server();
+    let cfg = SessionConfig::from_hello(&reply);
+    assert_eq!(cfg.max_inflight, reply.max_inflight);
+    assert_eq!(cfg.stream_credits, DEFAULT_STREAM_CREDITS);
+    assert_eq!(cfg.chunk_size, STREAM_CHUNK_SIZE);
+    cfg.validate().unwrap();
+
+    let tiny = HelloReply {
+        max_frame: 80 * 1024,
+        ..reply
+    };
+    let cfg = SessionConfig::from_hello(&tiny);
+    assert!(cfg.chunk_size < STREAM_CHUNK_SIZE);
+    cfg.validate().unwrap();
+
+    for bad in [
+        SessionConfig {
+            max_inflight: 0,
+            ..SessionConfig::default()
+        },
+        SessionConfig {
+            stream_credits: 0,
+            ..SessionConfig::default()
+        },
+        SessionConfig {
+            chunk_size: MAX_FRAME_BYTES as usize,
+            ..SessionConfig::default()
+        },
+    ] {
+        assert!(matches!(bad.validate(), Err(OapSessionError::Config(_))));
+    }
+}
@@ -1 +1,5 @@
-/// STUBBED - FINISH SVC-PASSPORT THEN COME BACK
\ No newline at end of file
+//! RO:WHAT   Gateway-side auth helpers.
+//! RO:LAYOUT revocation (mirror of svc-passport's signed revocation list).
+//!           capability.rs / passport_client.rs are still placeholders and are not compiled.
+
+pub mod revocation;
@@ -1 +1,277 @@
-/// STUBBED - FINISH SVC-PASSPORT THEN COME BACK
\ No newline at end of file
+//! RO:WHAT   Local mirror of svc-passport's revocation list (revoked token ids + KIDs).
+//! RO:WHY    Requests carrying a leaked passport must be refused here too, without a call per request.
+//! RO:INTERACTS svc-passport `GET /v1/keys`, `GET /v1/revocations?since=N` (signed Envelope),
+//!             `GET /v1/revocations/stream` (SSE wake-ups).
+//!           Presented passports arrive base64url(JSON envelope) in `x-ron-passport-token`.
+//! RO:INVARS Only signed lists mutate the cache (SSE payloads are hints); versions never go backwards;
+//!           entries are honoured until their own expiry; token ids are recomputed from the signed
+//!           bytes, never taken from the envelope.
+
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, bail, Context as _};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use ed25519_dalek::{Signature, VerifyingKey};
+use parking_lot::RwLock;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::{debug, warn};
+
+/// Signed transport shape returned by `GET /v1/revocations`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Envelope {
+    pub alg: String,
+    pub kid: String,
+    pub msg_b64: String,
+    pub sig_b64: String,
+}
+
+/// Body of a revocation list (the signed `msg_b64` bytes).
+#[derive(Debug, Clone, Deserialize)]
+pub struct RevocationList {
+    pub issuer: String,
+    pub version: u64,
+    pub since: u64,
+    pub generated_at_s: u64,
+    pub entries: Vec<RevocationEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RevocationEntry {
+    pub version: u64,
+    /// `"token"` or `"kid"`; unknown kinds are ignored.
+    pub kind: String,
+    pub id: String,
+    pub expires_at_s: u64,
+}
+
+#[derive(Default)]
+struct State {
+    version: u64,
+    tokens: HashMap<String, u64>,
+    kids: HashMap<String, u64>,
+}
+
+/// Revoked token ids / KIDs with their expiry. Cheap to query from request handlers.
+#[derive(Default)]
+pub struct RevocationCache {
+    state: RwLock<State>,
+}
+
+impl RevocationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highest list version applied; send it back as `since`.
+    pub fn version(&self) -> u64 {
+        self.state.read().version
+    }
+
+    pub fn is_token_revoked(&self, jti: &str, now_s: u64) -> bool {
+        self.state
+            .read()
+            .tokens
+            .get(jti)
+            .is_some_and(|exp| *exp > now_s)
+    }
+
+    pub fn is_kid_revoked(&self, kid: &str, now_s: u64) -> bool {
+        self.state
+            .read()
+            .kids
+            .get(kid)
+            .is_some_and(|exp| *exp > now_s)
+    }
+
+    /// Whether a presented passport (base64url JSON envelope) is revoked by token id or signing KID.
+    pub fn is_presented_revoked(&self, token: &str) -> anyhow::Result<bool> {
+        let env: Envelope = serde_json::from_slice(
+            &URL_SAFE_NO_PAD
+                .decode(token.trim())
+                .context("passport token is not base64url")?,
+        )
+        .context("passport token is not an envelope")?;
+        let msg = STANDARD
+            .decode(&env.msg_b64)
+            .context("passport msg_b64 is not base64")?;
+        let now = now_s();
+        Ok(self.is_kid_revoked(&env.kid, now) || self.is_token_revoked(&token_id(&msg), now))
+    }
+
+    /// Merge a verified list. Lists older than what we hold are ignored; returns entries applied.
+    pub fn apply(&self, list: &RevocationList, now_s: u64) -> usize {
+        let mut st = self.state.write();
+        if list.version < st.version {
+            return 0;
+        }
+        let mut applied = 0;
+        for e in &list.entries {
+            let map = match e.kind.as_str() {
+                "token" => &mut st.tokens,
+                "kid" => &mut st.kids,
+                _ => continue,
+            };
+            let exp = map.entry(e.id.clone()).or_insert(0);
+            *exp = (*exp).max(e.expires_at_s);
+            applied += 1;
+        }
+        st.version = list.version;
+        st.tokens.retain(|_, exp| *exp > now_s);
+        st.kids.retain(|_, exp| *exp > now_s);
+        applied
+    }
+}
+
+/// Token id svc-passport assigns to a signed message: lowercase hex BLAKE3 of the exact bytes.
+pub fn token_id(msg: &[u8]) -> String {
+    blake3::hash(msg).to_hex().to_string()
+}
+
+/// Verify `env` against a JWKS (`/v1/keys`) and decode the list it carries.
+pub fn verify_list(env: &Envelope, jwks: &Value) -> anyhow::Result<RevocationList> {
+    if env.alg != "Ed25519" {
+        bail!("unsupported alg {}", env.alg);
+    }
+    let x = jwks
+        .get("keys")
+        .and_then(Value::as_array)
+        .and_then(|keys| {
+            keys.iter()
+                .find(|k| k.get("kid") == Some(&Value::from(env.kid.as_str())))
+        })
+        .and_then(|k| k.get("x"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("unknown kid {}", env.kid))?;
+    let vk_bytes: [u8; 32] = URL_SAFE_NO_PAD
+        .decode(x)?
+        .try_into()
+        .map_err(|_| anyhow!("bad key length"))?;
+    let vk = VerifyingKey::from_bytes(&vk_bytes)?;
+    let msg = STANDARD.decode(&env.msg_b64)?;
+    let sig = Signature::from_slice(&STANDARD.decode(&env.sig_b64)?)?;
+    vk.verify_strict(&msg, &sig)
+        .map_err(|_| anyhow!("revocation list signature invalid"))?;
+    Ok(serde_json::from_slice(&msg)?)
+}
+
+/// Keeps a `RevocationCache` in sync with one svc-passport instance.
+#[derive(Clone)]
+pub struct RevocationSync {
+    base_url: String,
+    client: reqwest::Client,
+    cache: Arc<RevocationCache>,
+}
+
+impl RevocationSync {
+    pub fn new(base_url: impl Into<String>, cache: Arc<RevocationCache>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_owned(),
+            client: reqwest::Client::new(),
+            cache,
+        }
+    }
+
+    /// Fetch and apply everything newer than the cache's version; returns entries applied.
+    pub async fn refresh(&self) -> anyhow::Result<usize> {
+        let jwks: Value = self
+            .client
+            .get(format!("{}/v1/keys", self.base_url))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let env: Envelope = self
+            .client
+            .get(format!(
+                "{}/v1/revocations?since={}",
+                self.base_url,
+                self.cache.version()
+            ))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let list = verify_list(&env, &jwks).context("verify revocation list")?;
+        Ok(self.cache.apply(&list, now_s()))
+    }
+
+    /// Refresh now, then on every SSE event and at least every `poll`; reconnects after errors.
+    pub fn spawn(self, poll: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = self.refresh().await {
+                    warn!(error = %e, "revocation refresh failed");
+                }
+                if let Err(e) = self.follow_stream(poll).await {
+                    debug!(error = %e, "revocation stream ended");
+                    tokio::time::sleep(poll).await;
+                }
+            }
+        })
+    }
+
+    /// Read the SSE stream, refreshing on each `revoked`/`resync` event. Returns after `poll` of silence.
+    async fn follow_stream(&self, poll: Duration) -> anyhow::Result<()> {
+        let mut resp = self
+            .client
+            .get(format!("{}/v1/revocations/stream", self.base_url))
+            .send()
+            .await?
+            .error_for_status()?;
+        let mut buf = String::new();
+        loop {
+            let chunk = match tokio::time::timeout(poll, resp.chunk()).await {
+                Ok(chunk) => chunk?,
+                Err(_) => return Ok(()), // silent too long: fall back to a poll
+            };
+            let Some(chunk) = chunk else {
+                bail!("stream closed");
+            };
+            buf.push_str(&String::from_utf8_lossy(&chunk));
+            if take_sse_events(&mut buf)
+                .iter()
+                .any(|ev| ev == "revoked" || ev == "resync")
+            {
+                if let Err(e) = self.refresh().await {
+                    warn!(error = %e, "revocation refresh failed");
+                }
+            }
+        }
+    }
+}
+
+/// Drain complete SSE blocks from `buf`, returning their `event:` names (`message` when unnamed).
+pub fn take_sse_events(buf: &mut String) -> Vec<String> {
+    let normalized = buf.replace("\r\n", "\n");
+    let Some(end) = normalized.rfind("\n\n") else {
+        *buf = normalized;
+        return Vec::new();
+    };
+    let events = normalized[..end]
+        .split("\n\n")
+        .filter(|block| block.lines().any(|l| !l.starts_with(':') && !l.is_empty()))
+        .map(|block| {
+            block
+                .lines()
+                .find_map(|l| l.strip_prefix("event:"))
+                .map_or_else(|| "message".to_owned(), |name| name.trim().to_owned())
+        })
+        .collect();
+    *buf = normalized[end + 2..].to_owned();
+    events
+}
+
+fn now_s() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -20,7 +20,12 @@ pub async fn serve(
     // Axum/Hyper server with graceful shutdown on Ctrl-C.
     // (Main still holds the JoinHandle and can abort on top; this just ensures
     //  a clean drain when Ctrl-C is delivered to the process.)
-    let http = axum::serve(listener, router).with_graceful_shutdown(async {
+    // Peer addresses feed the policy context's client IP.
+    let http = axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async {
         // Best-effort: if ctrl_c fails, just keep serving.
         if let Err(e) = tokio::signal::ctrl_c().await {
             // Log once; we don't bubble this up because we want the server to continue.
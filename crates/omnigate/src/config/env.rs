@@ -2,7 +2,8 @@
 //! RO:WHY — 12 Pillars hardening: explicit/typed config; Concerns: GOV/SEC.
 //! RO:INTERACTS — `config::Config`, HTTP admission body/decompression guards, server bind config.
 //! RO:INVARIANTS — OAP frame caps remain separate; HTTP body caps are bounded; malformed env fails closed.
-//! RO:CONFIG — OMNIGATE_BIND, OMNIGATE_METRICS_ADDR, OMNIGATE_AMNESIA, OMNIGATE_MAX_BODY_BYTES.
+//! RO:CONFIG — OMNIGATE_BIND, OMNIGATE_METRICS_ADDR, OMNIGATE_AMNESIA, OMNIGATE_MAX_BODY_BYTES,
+//!             OMNIGATE_PASSPORT_REVOCATION_URL.
 //! RO:SECURITY — no secret material is read here; env only tunes local operator/runtime limits.
 
 use super::Config;
@@ -49,6 +50,11 @@ pub fn apply_env_overrides(cfg: &mut Config) -> Result<()> {
         cfg.admission.body.max_content_length = value;
     }
 
+    if let Ok(v) = env::var("OMNIGATE_PASSPORT_REVOCATION_URL") {
+        let v = v.trim();
+        cfg.passport.revocation_url = (!v.is_empty()).then(|| v.to_owned());
+    }
+
     Ok(())
 }
 
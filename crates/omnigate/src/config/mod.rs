@@ -17,6 +17,8 @@ pub struct Config {
     pub admission: Admission,
     pub policy: Policy,
     pub readiness: Readiness,
+    #[serde(default)]
+    pub passport: Passport,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -190,6 +192,10 @@ pub struct Policy {
     /// "deny" or "allow" on evaluator failure (kept for future use).
     #[serde(default = "Policy::default_fail_mode")]
     pub fail_mode: String,
+    /// Proxy CIDRs whose X-Forwarded-For names the client IP for `cidr_any` rules.
+    /// Empty (default): the TCP peer is the client and X-Forwarded-For is ignored.
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
 }
 
 impl Policy {
@@ -201,6 +207,31 @@ impl Policy {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct Passport {
+    /// svc-passport base URL whose signed revocation list is mirrored; `None` skips the check.
+    #[serde(default)]
+    pub revocation_url: Option<String>,
+    /// Refresh at least this often when the revocation stream is quiet.
+    #[serde(default = "Passport::default_revocation_poll_secs")]
+    pub revocation_poll_secs: u64,
+}
+
+impl Passport {
+    fn default_revocation_poll_secs() -> u64 {
+        30
+    }
+}
+
+impl Default for Passport {
+    fn default() -> Self {
+        Self {
+            revocation_url: None,
+            revocation_poll_secs: Self::default_revocation_poll_secs(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Readiness {
     pub max_inflight_threshold: u64,
@@ -240,6 +271,7 @@ impl Config {
                 enabled: false,
                 bundle_path: "policy.bundle.json".into(),
                 fail_mode: "deny".into(),
+                trusted_proxies: Vec::new(),
             },
             readiness: Readiness {
                 max_inflight_threshold: 1_800,
@@ -247,6 +279,7 @@ impl Config {
                 window_secs: 10,
                 hold_for_secs: 30,
             },
+            passport: Passport::default(),
         };
         env::apply_env_overrides(&mut cfg)?;
         validate::validate(&cfg)?;
@@ -4,7 +4,12 @@
 
 use super::Config;
 
-pub fn validate(_cfg: &Config) -> anyhow::Result<()> {
+pub fn validate(cfg: &Config) -> anyhow::Result<()> {
     // Add concrete checks as data-plane routes land (body caps, timeouts, inflight).
+    crate::middleware::TrustedProxies::parse(&cfg.policy.trusted_proxies)?;
+    anyhow::ensure!(
+        cfg.passport.revocation_poll_secs > 0,
+        "passport.revocation_poll_secs must be > 0"
+    );
     Ok(())
 }
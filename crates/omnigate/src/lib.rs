@@ -2,6 +2,7 @@
 #![allow(clippy::needless_return)]
 
 pub mod admission;
+pub mod auth;
 pub mod bootstrap;
 pub mod config;
 pub mod errors;
@@ -28,6 +29,8 @@ use serde_json::Map;
 pub struct App {
     pub router: Router,
     pub admin_addr: SocketAddr,
+    /// Mirror of svc-passport's revocation list; `None` when `passport.revocation_url` is unset.
+    pub revocations: Option<Arc<auth::revocation::RevocationCache>>,
 }
 
 impl App {
@@ -121,6 +124,21 @@ impl App {
         // then admission (quotas/fair-queue) — these are INNER layers.
         app_router = middleware::apply_with_cfg(app_router, &cfg.admission)
             .layer(observability::http_trace_layer());
+
+        // -------------------- PASSPORT REVOCATION --------------------
+        // Synced in the background; the layer only reads the in-memory cache.
+        let revocations = cfg.passport.revocation_url.as_ref().map(|url| {
+            let cache = Arc::new(auth::revocation::RevocationCache::new());
+            auth::revocation::RevocationSync::new(url.clone(), cache.clone()).spawn(
+                std::time::Duration::from_secs(cfg.passport.revocation_poll_secs),
+            );
+            info!(%url, "passport revocation sync started");
+            cache
+        });
+        if let Some(cache) = &revocations {
+            app_router = app_router.layer(middleware::revocation::layer(cache.clone()));
+        }
+
         app_router = crate::admission::attach_with_cfg(app_router, &cfg.admission);
 
         // -------------------- POLICY BUNDLE (OUTERMOST so inner policy layer can see it) --------------------
@@ -129,11 +147,19 @@ impl App {
         // be layered AFTER apply_with_cfg/admission.
         let mut have_bundle = false;
         if cfg.policy.enabled {
+            // Who may speak for the client IP in the policy context (also checked by config validation).
+            let trusted = middleware::TrustedProxies::parse(&cfg.policy.trusted_proxies)?;
+            app_router = app_router.layer(Extension(Arc::new(trusted)));
             match std::fs::read_to_string(&cfg.policy.bundle_path) {
                 Ok(json) => match serde_json::from_str::<PolicyBundle>(&json) {
                     Ok(bundle) => {
                         crate::metrics::registry::POLICY_BUNDLE_LOADED_TOTAL.inc();
                         info!(path=%cfg.policy.bundle_path, "policy bundle loaded and inserted");
+                        // The PolicyLayer builds an Evaluator per request, which re-validates:
+                        // an invalid bundle means policy requests fail closed (503).
+                        if let Err(e) = ron_policy::parse::validate::validate(&bundle) {
+                            warn!(path=%cfg.policy.bundle_path, error=%e, "policy bundle rejected; policy requests will fail closed");
+                        }
                         app_router = app_router.layer(Extension(Arc::new(bundle)));
                         have_bundle = true;
                     }
@@ -196,6 +222,7 @@ impl App {
         Ok(Self {
             router: app_router,
             admin_addr,
+            revocations,
         })
     }
 }
@@ -9,8 +9,11 @@ mod corr_id;
 pub mod decompress_guard;
 pub mod inflight;
 mod policy;
+pub mod revocation;
 mod slow_loris;
 
+pub use policy::{PolicyAttrs, TrustedProxies};
+
 use crate::config::Admission;
 use axum::Router;
 
@@ -2,25 +2,90 @@
 //! RO:WHY    Centralize allow/deny; keep business handlers policy-agnostic.
 //! RO:INVARS If no evaluator is present, act as a no-op (safe pass-through).
 //!           When denying, emit stable JSON envelopes and bounded-label metrics.
+//!           The context carries the request's headers, `PolicyAttrs`, and client IP; X-Forwarded-For
+//!           is only believed when the TCP peer is a `TrustedProxies` entry, so it cannot be spoofed
+//!           past `cidr_any` rules. Bodies of unknown length (chunked) count as over any body cap.
 
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
+    net::{IpAddr, SocketAddr},
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 
 use axum::{
-    extract::Request,
-    http::StatusCode,
+    body::HttpBody as _,
+    extract::{
+        connect_info::{ConnectInfo, MockConnectInfo},
+        Request,
+    },
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
 use futures_util::future::BoxFuture;
+use ron_policy::engine::matchers::Cidr;
 use tower::{Layer, Service};
 
 use crate::errors::GateError;
 // IMPORTANT: pull counters from the gates module to match registration on the default registry.
 use crate::metrics::gates::POLICY_MIDDLEWARE_SHORTCIRCUITS_TOTAL;
 
+/// Proxies whose X-Forwarded-For is believed (request extension; `policy.trusted_proxies`).
+#[derive(Clone, Debug, Default)]
+pub struct TrustedProxies(Vec<Cidr>);
+
+impl TrustedProxies {
+    /// Parse CIDRs (a bare address is a single host).
+    pub fn parse<S: AsRef<str>>(cidrs: &[S]) -> anyhow::Result<Self> {
+        cidrs
+            .iter()
+            .map(|c| {
+                let c = c.as_ref();
+                Cidr::parse(c)
+                    .ok_or_else(|| anyhow::anyhow!("policy.trusted_proxies: invalid CIDR {c:?}"))
+            })
+            .collect::<anyhow::Result<_>>()
+            .map(Self)
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        self.0.iter().any(|c| c.contains(ip))
+    }
+
+    /// The client behind `peer`: the peer itself, or — when the peer is trusted — the nearest
+    /// X-Forwarded-For hop that is not.
+    fn client_ip(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let peer = peer?.to_canonical();
+        if !self.contains(peer) {
+            return Some(peer);
+        }
+        let mut client = peer;
+        for hop in headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .rev()
+        {
+            match hop.trim().parse::<IpAddr>() {
+                Ok(ip) => {
+                    client = ip.to_canonical();
+                    if !self.contains(client) {
+                        break;
+                    }
+                }
+                // An unparsable hop ends the chain we can vouch for.
+                Err(_) => break,
+            }
+        }
+        Some(client)
+    }
+}
+
+/// Numeric facts for `attrs` rules, inserted by earlier layers (request extension).
+#[derive(Clone, Debug, Default)]
+pub struct PolicyAttrs(pub BTreeMap<String, f64>);
+
 #[derive(Clone)]
 pub struct PolicyLayer;
 
@@ -88,13 +153,36 @@ where
                         let region = String::new();
                         let tenant = "default".to_string();
 
+                        // Same lookup as the `ConnectInfo` extractor (tests use `MockConnectInfo`).
+                        let peer = req
+                            .extensions()
+                            .get::<ConnectInfo<SocketAddr>>()
+                            .map(|ci| ci.0.ip())
+                            .or_else(|| {
+                                req.extensions()
+                                    .get::<MockConnectInfo<SocketAddr>>()
+                                    .map(|ci| ci.0.ip())
+                            });
+                        let client_ip = req.extensions().get::<Arc<TrustedProxies>>().map_or_else(
+                            || TrustedProxies::default().client_ip(peer, req.headers()),
+                            |t| t.client_ip(peer, req.headers()),
+                        );
+
                         let ctx = ron_policy::Context {
                             now_ms,
-                            body_bytes: 0, // unknown at admission time
+                            body_bytes: body_bytes(&req),
                             method,
                             region,
                             tags,
                             tenant,
+                            path: req.uri().path().to_owned(),
+                            headers: policy_headers(req.headers()),
+                            attrs: req
+                                .extensions()
+                                .get::<PolicyAttrs>()
+                                .map(|a| a.0.clone())
+                                .unwrap_or_default(),
+                            client_ip,
                         };
 
                         match eval.evaluate(&ctx) {
@@ -153,3 +241,30 @@ where
         })
     }
 }
+
+/// Body size for the policy context, without reading the body: the declared `Content-Length` (the
+/// server enforces it as framing) or an exact size hint; a streamed body of unknown length counts as
+/// `u64::MAX`, so `max_body_bytes` caps fail closed instead of seeing 0.
+fn body_bytes(req: &Request) -> u64 {
+    req.headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .or_else(|| req.body().size_hint().exact())
+        .unwrap_or(u64::MAX)
+}
+
+/// Lowercased names; repeated headers are joined with ", "; non-UTF-8 values are skipped.
+fn policy_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::<String, String>::new();
+    for (name, value) in headers {
+        let Ok(value) = value.to_str() else { continue };
+        out.entry(name.as_str().to_owned())
+            .and_modify(|v| {
+                v.push_str(", ");
+                v.push_str(value);
+            })
+            .or_insert_with(|| value.to_owned());
+    }
+    out
+}
@@ -0,0 +1,84 @@
+//! RO:WHAT   Refuse requests presenting a passport that svc-passport has revoked.
+//! RO:WHY    A leaked passport must stop working at the edge as soon as the revocation list lands.
+//! RO:INTERACTS auth::revocation::RevocationCache (kept fresh by RevocationSync, spawned in App::build).
+//! RO:INVARS Requests without `x-ron-passport-token` pass through; revoked or unparseable tokens get 401;
+//!           lookups are in-memory only (no call to svc-passport per request).
+
+use std::sync::Arc;
+
+use axum::{
+    extract::Request,
+    http::HeaderName,
+    response::{IntoResponse, Response},
+};
+use futures_util::future::BoxFuture;
+use tower::{Layer, Service};
+
+use crate::auth::revocation::RevocationCache;
+use crate::errors::{http_map::to_response, Reason};
+
+static HDR_PASSPORT: HeaderName = HeaderName::from_static("x-ron-passport-token");
+
+#[derive(Clone)]
+pub struct RevocationLayer {
+    cache: Arc<RevocationCache>,
+}
+
+pub fn layer(cache: Arc<RevocationCache>) -> RevocationLayer {
+    RevocationLayer { cache }
+}
+
+impl<S> Layer<S> for RevocationLayer {
+    type Service = RevocationService<S>;
+    fn layer(&self, inner: S) -> Self::Service {
+        RevocationService {
+            inner,
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RevocationService<S> {
+    inner: S,
+    cache: Arc<RevocationCache>,
+}
+
+impl<S> Service<Request> for RevocationService<S>
+where
+    S: Service<Request> + Clone + Send + 'static,
+    S::Response: IntoResponse + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<Box<dyn std::error::Error + Send + Sync>> + Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let verdict = req.headers().get(&HDR_PASSPORT).map(|v| {
+            v.to_str()
+                .map_err(anyhow::Error::from)
+                .and_then(|t| self.cache.is_presented_revoked(t))
+        });
+        match verdict {
+            Some(Ok(true)) => {
+                Box::pin(async { Ok(to_response(Reason::Unauthorized, "passport revoked")) })
+            }
+            Some(Err(_)) => {
+                Box::pin(async { Ok(to_response(Reason::Unauthorized, "malformed passport")) })
+            }
+            None | Some(Ok(false)) => {
+                let mut inner = self.inner.clone();
+                Box::pin(async move { Ok(inner.call(req).await?.into_response()) })
+            }
+        }
+    }
+}
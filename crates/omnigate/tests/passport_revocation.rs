@@ -0,0 +1,207 @@
+//! passport_revocation.rs — Omnigate mirror of svc-passport's signed revocation list.
+//!
+//! RO:WHAT — Dummy svc-passport serving /v1/keys + a signed /v1/revocations; assert sync, signature
+//!           checks, version monotonicity, SSE event framing, and that App refuses revoked passports.
+//! RO:INVARIANTS — only signed lists mutate the cache; tampered lists are rejected.
+//! RO:TEST — cargo test -p omnigate --test passport_revocation.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{extract::Query, routing::get, Json, Router};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use ed25519_dalek::{Signer, SigningKey};
+use omnigate::auth::revocation::{
+    take_sse_events, token_id, verify_list, Envelope, RevocationCache, RevocationSync,
+};
+use omnigate::{config, App};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+const KID: &str = "ed25519/default/v1";
+const REVOKED_KID: &str = "ed25519/default/v0";
+const FAR: u64 = 4_000_000_000;
+const REVOKED_MSG: &[u8] = br#"{"sub":"leaked","exp":4000000000}"#;
+
+fn key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn jwks() -> Value {
+    json!({ "keys": [{ "kid": KID, "x": URL_SAFE_NO_PAD.encode(key().verifying_key().to_bytes()) }] })
+}
+
+fn signed(list: &Value) -> Value {
+    let msg = serde_json::to_vec(list).unwrap();
+    let sig = key().sign(&msg);
+    json!({
+        "alg": "Ed25519",
+        "kid": KID,
+        "msg_b64": STANDARD.encode(&msg),
+        "sig_b64": STANDARD.encode(sig.to_bytes()),
+    })
+}
+
+fn list(since: u64) -> Value {
+    let entries: Vec<Value> = [
+        json!({ "version": 1, "kind": "token", "id": token_id(REVOKED_MSG), "revoked_at_s": 1, "expires_at_s": FAR }),
+        json!({ "version": 2, "kind": "kid", "id": REVOKED_KID, "revoked_at_s": 1, "expires_at_s": FAR }),
+    ]
+    .into_iter()
+    .filter(|e| e["version"].as_u64().unwrap() > since)
+    .collect();
+    json!({ "issuer": "svc-passport", "version": 2, "since": since, "generated_at_s": 1, "entries": entries })
+}
+
+async fn dummy_passport() -> SocketAddr {
+    async fn revocations(Query(q): Query<std::collections::HashMap<String, u64>>) -> Json<Value> {
+        Json(signed(&list(q.get("since").copied().unwrap_or(0))))
+    }
+    let app = Router::new()
+        .route("/v1/keys", get(|| async { Json(jwks()) }))
+        .route("/v1/revocations", get(revocations));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    addr
+}
+
+#[tokio::test]
+async fn refresh_applies_signed_list_incrementally() {
+    let addr = dummy_passport().await;
+    let cache = Arc::new(RevocationCache::new());
+    let sync = RevocationSync::new(format!("http://{addr}"), cache.clone());
+
+    assert_eq!(sync.refresh().await.unwrap(), 2);
+    assert_eq!(cache.version(), 2);
+    assert!(cache.is_token_revoked(&token_id(REVOKED_MSG), 10));
+    assert!(cache.is_kid_revoked(REVOKED_KID, 10));
+    assert!(!cache.is_token_revoked("bb", 10));
+    assert!(
+        !cache.is_token_revoked(&token_id(REVOKED_MSG), FAR),
+        "entries lapse at their expiry"
+    );
+
+    // Next fetch asks since=2 and gets nothing new.
+    assert_eq!(sync.refresh().await.unwrap(), 0);
+}
+
+#[test]
+fn tampered_or_unknown_key_lists_are_rejected() {
+    let mut env: Envelope = serde_json::from_value(signed(&list(0))).unwrap();
+    assert!(verify_list(&env, &jwks()).is_ok());
+
+    let mut body: Value = serde_json::from_slice(&STANDARD.decode(&env.msg_b64).unwrap()).unwrap();
+    body["entries"] = json!([]);
+    let original_msg = std::mem::replace(
+        &mut env.msg_b64,
+        STANDARD.encode(serde_json::to_vec(&body).unwrap()),
+    );
+    assert!(verify_list(&env, &jwks()).is_err());
+
+    env.msg_b64 = original_msg;
+    env.kid = "ed25519/default/v9".into();
+    assert!(verify_list(&env, &jwks()).is_err());
+}
+
+#[test]
+fn older_lists_never_roll_back() {
+    let cache = RevocationCache::new();
+    let newer: omnigate::auth::revocation::RevocationList =
+        serde_json::from_value(list(0)).unwrap();
+    assert_eq!(cache.apply(&newer, 10), 2);
+    let mut stale = newer.clone();
+    stale.version = 1;
+    stale.entries.clear();
+    assert_eq!(cache.apply(&stale, 10), 0);
+    assert_eq!(cache.version(), 2);
+}
+
+#[test]
+fn sse_framing_yields_complete_events_only() {
+    let mut buf = String::from(":heartbeat\n\nevent: revoked\nid: 3\ndata: {}\n\nevent: res");
+    assert_eq!(take_sse_events(&mut buf), vec!["revoked".to_string()]);
+    buf.push_str("ync\ndata: 4\r\n\r\n");
+    assert_eq!(take_sse_events(&mut buf), vec!["resync".to_string()]);
+    assert!(buf.is_empty());
+}
+
+/// A passport as presented to omnigate: base64url of the issued JSON envelope.
+fn presented(kid: &str, msg: &[u8]) -> String {
+    let env = json!({
+        "alg": "Ed25519",
+        "kid": kid,
+        "jti": token_id(msg),
+        "msg_b64": STANDARD.encode(msg),
+        "sig_b64": STANDARD.encode(key().sign(msg).to_bytes()),
+    });
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(&env).unwrap())
+}
+
+fn gateway_cfg(passport: SocketAddr) -> config::Config {
+    config::Config {
+        server: config::Server {
+            bind: "127.0.0.1:0".parse().unwrap(),
+            metrics_addr: "127.0.0.1:0".parse().unwrap(),
+            amnesia: true,
+        },
+        oap: config::Oap {
+            max_frame_bytes: 1_048_576,
+            stream_chunk_bytes: 65_536,
+        },
+        admission: config::Admission::default(),
+        policy: config::Policy {
+            enabled: false,
+            bundle_path: String::new(),
+            fail_mode: "deny".into(),
+            trusted_proxies: vec![],
+        },
+        readiness: config::Readiness {
+            max_inflight_threshold: 1_800,
+            error_rate_429_503_pct: 2.0,
+            window_secs: 10,
+            hold_for_secs: 30,
+        },
+        passport: config::Passport {
+            revocation_url: Some(format!("http://{passport}")),
+            revocation_poll_secs: 1,
+        },
+    }
+}
+
+#[tokio::test]
+async fn gateway_refuses_revoked_passports() {
+    let passport = dummy_passport().await;
+    let app = App::build(gateway_cfg(passport)).await.unwrap();
+    let cache = app.revocations.clone().expect("revocation sync configured");
+    for _ in 0..100 {
+        if cache.version() == 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert_eq!(cache.version(), 2, "bootstrap sync applied the signed list");
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = app.router;
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let client = reqwest::Client::new();
+    let status = |token: Option<String>| {
+        let mut req = client.get(format!("http://{addr}/versionz"));
+        if let Some(t) = token {
+            req = req.header("x-ron-passport-token", t);
+        }
+        async move { req.send().await.unwrap().status().as_u16() }
+    };
+
+    assert_eq!(status(None).await, 200);
+    assert_eq!(status(Some(presented(KID, b"{\"sub\":\"ok\"}"))).await, 200);
+    assert_eq!(status(Some(presented(KID, REVOKED_MSG))).await, 401);
+    assert_eq!(
+        status(Some(presented(REVOKED_KID, b"{\"sub\":\"ok\"}"))).await,
+        401
+    );
+    assert_eq!(status(Some("not-a-passport".into())).await, 401);
+}
@@ -98,3 +98,118 @@ async fn policy_correct_layering_yields_403_put() {
         "GET should be allowed by policy"
     );
 }
+
+#[tokio::test]
+async fn cidr_deny_sees_the_real_client_ip() {
+    use axum::extract::connect_info::MockConnectInfo;
+    use omnigate::middleware::TrustedProxies;
+    use std::net::SocketAddr;
+
+    let json = r#"
+    {
+      "version": 1,
+      "defaults": { "default_action": "allow" },
+      "rules": [
+        { "id": "block-net", "when": { "cidr_any": ["203.0.113.0/24"] }, "action": "deny" },
+        { "id": "need-header", "when": { "method": "POST", "not": { "headers_present": ["x-api-key"] } }, "action": "deny" }
+      ]
+    }"#;
+    let bundle = Arc::new(serde_json::from_str::<PolicyBundle>(json).unwrap());
+    let app = |peer: &str| {
+        let peer: SocketAddr = peer.parse().unwrap();
+        omnigate::middleware::apply(Router::new().route("/v1/ping", get(ping).post(ping)))
+            .layer(axum::Extension(bundle.clone()))
+            .layer(axum::Extension(Arc::new(
+                TrustedProxies::parse(&["10.0.0.0/8"]).unwrap(),
+            )))
+            .layer(MockConnectInfo(peer))
+    };
+    let get_from = |xff: Option<&str>| {
+        let mut req = Request::builder().method("GET").uri("/v1/ping");
+        if let Some(xff) = xff {
+            req = req.header("x-forwarded-for", xff);
+        }
+        req.body(Body::empty()).unwrap()
+    };
+    let status =
+        |app: Router, req: Request<Body>| async move { app.oneshot(req).await.unwrap().status() };
+
+    // Direct peer in the denied range.
+    assert_eq!(
+        status(app("203.0.113.7:4000"), get_from(None)).await,
+        StatusCode::FORBIDDEN
+    );
+    // Behind a trusted proxy, the forwarded client decides.
+    assert_eq!(
+        status(
+            app("10.1.2.3:4000"),
+            get_from(Some("203.0.113.7, 10.9.9.9"))
+        )
+        .await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(app("10.1.2.3:4000"), get_from(Some("198.51.100.1"))).await,
+        StatusCode::OK
+    );
+    // An untrusted peer cannot launder itself through X-Forwarded-For.
+    assert_eq!(
+        status(app("203.0.113.7:4000"), get_from(Some("198.51.100.1"))).await,
+        StatusCode::FORBIDDEN
+    );
+
+    // Headers reach the context too.
+    let post = |key: Option<&str>| {
+        let mut req = Request::builder().method("POST").uri("/v1/ping");
+        if let Some(key) = key {
+            req = req.header("x-api-key", key);
+        }
+        req.body(Body::empty()).unwrap()
+    };
+    assert_eq!(
+        status(app("198.51.100.1:4000"), post(None)).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        status(app("198.51.100.1:4000"), post(Some("k"))).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn body_cap_counts_streamed_bodies_as_oversized() {
+    let json = r#"
+    {
+      "version": 1,
+      "defaults": { "default_action": "allow", "max_body_bytes": 16 },
+      "rules": []
+    }"#;
+    let bundle = Arc::new(serde_json::from_str::<PolicyBundle>(json).unwrap());
+    let router = omnigate::middleware::apply(Router::new().route("/v1/ping", get(ping).post(ping)))
+        .layer(axum::Extension(bundle));
+    let post = |body: Body| {
+        Request::builder()
+            .method("POST")
+            .uri("/v1/ping")
+            .body(body)
+            .unwrap()
+    };
+
+    // Known small body passes.
+    let resp = router
+        .clone()
+        .oneshot(post(Body::from("tiny")))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Chunked body with no Content-Length cannot slip under the cap.
+    let chunks = futures::stream::iter(
+        (0..4).map(|_| Ok::<_, std::io::Error>(bytes::Bytes::from_static(b"0123456789"))),
+    );
+    let resp = router
+        .oneshot(post(Body::from_stream(chunks)))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
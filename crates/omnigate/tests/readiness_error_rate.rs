@@ -73,6 +73,7 @@ fn cfg_error_rate_via_fair_queue() -> config::Config {
             enabled: false, // keep policy out of the picture for this test
             bundle_path: "crates/omnigate/configs/policy.bundle.json".into(),
             fail_mode: "deny".into(),
+            trusted_proxies: vec![],
         },
         readiness: config::Readiness {
             max_inflight_threshold: 1_000_000, // make inflight path irrelevant
@@ -80,6 +81,7 @@ fn cfg_error_rate_via_fair_queue() -> config::Config {
             window_secs: 2,                    // short window => quick sampler ticks
             hold_for_secs: 3,
         },
+        passport: config::Passport::default(),
     }
 }
 
@@ -76,6 +76,7 @@ fn cfg_inflight_low_threshold() -> config::Config {
             enabled: true,
             bundle_path: "crates/omnigate/configs/policy.bundle.json".into(),
             fail_mode: "deny".into(),
+            trusted_proxies: vec![],
         },
         // Low thresholds so laptops trip quickly
         readiness: config::Readiness {
@@ -84,6 +85,7 @@ fn cfg_inflight_low_threshold() -> config::Config {
             window_secs: 5,
             hold_for_secs: 6,
         },
+        passport: config::Passport::default(),
     }
 }
 
@@ -104,12 +104,12 @@ fn make_token_heavy(keys: &impl MacKeyProvider) -> String {
         .caveat(Caveat::BytesLe(1_048_576))
         .caveat(Caveat::Amnesia(false));
 
-    // pad to ~30 caveats with no-op customs (ignored by evaluator)
+    // pad to ~30 caveats with advisory customs (unregistered, `crit: false` => skipped)
     for i in 0..6 {
         builder = builder.caveat(Caveat::Custom {
             name: format!("x{}", i),
             ns: "demo".into(),
-            cbor: Value::Null,
+            cbor: Value::Map([(Value::Text("crit".into()), Value::Bool(false))].into()),
         });
     }
 
@@ -1 +1,82 @@
-// caveats::builtin placeholder
\ No newline at end of file
+//! RO:WHAT  Ready-made custom caveat evaluators over `RequestCtx::extras` (a CBOR map).
+//! RO:WHY   Common domain limits (max spend, bucket id) without each host re-writing CBOR plumbing.
+//! RO:INVARIANTS Missing or mistyped request values deny; payloads are plain scalars (always critical).
+
+use super::custom::{CustomCaveat, MALFORMED_ARGS};
+use crate::errors::DenyReason;
+use crate::types::RequestCtx;
+use serde_cbor::Value;
+
+/// Deny unless `extras[key]` is an unsigned integer `<=` the caveat's integer payload.
+///
+/// e.g. `register("wallet", "max_spend", extras_le("amount"))`; denies with `<key>_exceeded`.
+pub fn extras_le(key: &str) -> ExtrasLe {
+    ExtrasLe {
+        key: key.to_owned(),
+        reason: format!("{key}_exceeded"),
+    }
+}
+
+/// Deny unless `extras[key]` equals the caveat's payload (text, integer, bytes or bool).
+///
+/// e.g. `register("storage", "bucket", extras_eq("bucket"))`; denies with `<key>_mismatch`.
+pub fn extras_eq(key: &str) -> ExtrasEq {
+    ExtrasEq {
+        key: key.to_owned(),
+        reason: format!("{key}_mismatch"),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtrasLe {
+    key: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExtrasEq {
+    key: String,
+    reason: String,
+}
+
+fn extra<'a>(ctx: &'a RequestCtx, key: &str) -> Option<&'a Value> {
+    match &ctx.extras {
+        Value::Map(m) => m.iter().find_map(|(k, v)| match k {
+            Value::Text(s) if s == key => Some(v),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn as_u128(v: &Value) -> Option<u128> {
+    match v {
+        Value::Integer(i) => u128::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+impl CustomCaveat for ExtrasLe {
+    fn eval(&self, args: &Value, ctx: &RequestCtx) -> Result<(), DenyReason> {
+        let max = as_u128(args).ok_or_else(|| DenyReason::Custom(MALFORMED_ARGS.into()))?;
+        match extra(ctx, &self.key).and_then(as_u128) {
+            Some(n) if n <= max => Ok(()),
+            _ => Err(DenyReason::Custom(self.reason.clone())),
+        }
+    }
+}
+
+impl CustomCaveat for ExtrasEq {
+    fn eval(&self, args: &Value, ctx: &RequestCtx) -> Result<(), DenyReason> {
+        if !matches!(
+            args,
+            Value::Text(_) | Value::Integer(_) | Value::Bytes(_) | Value::Bool(_)
+        ) {
+            return Err(DenyReason::Custom(MALFORMED_ARGS.into()));
+        }
+        match extra(ctx, &self.key) {
+            Some(v) if v == args => Ok(()),
+            _ => Err(DenyReason::Custom(self.reason.clone())),
+        }
+    }
+}
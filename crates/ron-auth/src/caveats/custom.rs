@@ -1 +1,71 @@
-// caveats::custom placeholder
\ No newline at end of file
+//! RO:WHAT  Evaluator trait for `Caveat::Custom`, a serde-typed adapter, and criticality.
+//! RO:WHY   Hosts write `fn(&Args, &RequestCtx)` instead of hand-walking CBOR values.
+//! RO:INVARIANTS Decode failures deny; a custom caveat is critical unless its args map says `"crit": false`.
+
+use crate::errors::DenyReason;
+use crate::types::RequestCtx;
+use serde::de::DeserializeOwned;
+use serde_cbor::Value;
+use std::marker::PhantomData;
+
+/// Deny detail for a critical custom caveat with no registered evaluator.
+pub const UNKNOWN_CRITICAL: &str = "unknown_critical_caveat";
+/// Deny detail for custom caveat arguments the evaluator cannot decode.
+pub const MALFORMED_ARGS: &str = "custom_caveat_malformed";
+
+/// Evaluates one registered `(ns, name)` custom caveat. Must be cheap, pure and thread-safe:
+/// the same evaluator runs on the streaming, SoA and parallel batch paths.
+pub trait CustomCaveat: Send + Sync {
+    /// `args` is the caveat's raw `cbor` payload. `Err` becomes one deny reason.
+    fn eval(&self, args: &Value, ctx: &RequestCtx) -> Result<(), DenyReason>;
+}
+
+/// Whether a custom caveat must be understood to be honoured.
+///
+/// Critical by default; issuers mark purely advisory caveats with a map payload holding
+/// `"crit": false`. The flag is covered by the MAC like every other caveat byte.
+pub fn is_critical(args: &Value) -> bool {
+    match args {
+        Value::Map(m) => !matches!(m.get(&Value::Text("crit".into())), Some(Value::Bool(false))),
+        _ => true,
+    }
+}
+
+/// Adapter that decodes the payload into `T` before calling `f`. Build with [`typed`].
+pub struct TypedCaveat<T, F> {
+    f: F,
+    _args: PhantomData<fn() -> T>,
+}
+
+/// Wrap `f` so it receives the payload decoded as `T` (via serde); undecodable payloads deny.
+pub fn typed<T, F>(f: F) -> TypedCaveat<T, F>
+where
+    T: DeserializeOwned,
+    F: Fn(&T, &RequestCtx) -> Result<(), DenyReason> + Send + Sync,
+{
+    TypedCaveat {
+        f,
+        _args: PhantomData,
+    }
+}
+
+impl<T, F> CustomCaveat for TypedCaveat<T, F>
+where
+    T: DeserializeOwned,
+    F: Fn(&T, &RequestCtx) -> Result<(), DenyReason> + Send + Sync,
+{
+    fn eval(&self, args: &Value, ctx: &RequestCtx) -> Result<(), DenyReason> {
+        let args: T = serde_cbor::value::from_value(args.clone())
+            .map_err(|_| DenyReason::Custom(MALFORMED_ARGS.into()))?;
+        (self.f)(&args, ctx)
+    }
+}
+
+impl<F> CustomCaveat for F
+where
+    F: Fn(&Value, &RequestCtx) -> Result<(), DenyReason> + Send + Sync,
+{
+    fn eval(&self, args: &Value, ctx: &RequestCtx) -> Result<(), DenyReason> {
+        self(args, ctx)
+    }
+}
@@ -1 +1,11 @@
-// caveats::mod placeholder
\ No newline at end of file
+//! RO:WHAT  Host-extensible caveats: registry of typed evaluators for `Caveat::Custom`.
+//! RO:WHY   Services (storage, wallet, ...) need domain caveats without forking the core set.
+//! RO:INTERACTS  verify::{streaming, soa_eval} via `VerifyEnv::with_caveats`; types::Caveat::Custom.
+//! RO:INVARIANTS Pure and sync; unknown *critical* custom caveats deny (fail closed).
+
+pub mod builtin; // ready-made evaluators over RequestCtx::extras
+pub mod custom; // evaluator trait, typed adapter, criticality
+pub mod registry; // (ns, name) -> evaluator map
+
+pub use custom::{is_critical, typed, CustomCaveat, TypedCaveat};
+pub use registry::{CaveatRegistry, RegistryError};
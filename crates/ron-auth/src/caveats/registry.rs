@@ -1 +1,114 @@
-// caveats::registry placeholder
\ No newline at end of file
+//! RO:WHAT  `CaveatRegistry`: (namespace, name) -> evaluator for `Caveat::Custom`.
+//! RO:WHY   One immutable table, built at startup, shared by every verification path.
+//! RO:INTERACTS  caveats::custom (trait, criticality), verify::{streaming, soa_eval}.
+//! RO:INVARIANTS Registration is explicit and duplicate-free; lookups never allocate.
+
+use super::custom::{is_critical, CustomCaveat, UNKNOWN_CRITICAL};
+use crate::errors::DenyReason;
+use crate::types::RequestCtx;
+use serde_cbor::Value;
+use std::collections::HashMap;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RegistryError {
+    #[error("custom caveat {ns}/{name} already registered")]
+    Duplicate { ns: String, name: String },
+    #[error("custom caveat namespace and name must be non-empty")]
+    EmptyName,
+}
+
+/// Typed evaluators for host-defined caveats, keyed by namespace then name.
+#[derive(Default)]
+pub struct CaveatRegistry {
+    by_ns: HashMap<String, HashMap<String, Box<dyn CustomCaveat>>>,
+}
+
+impl CaveatRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `eval` for `ns`/`name`. Each pair may be registered once.
+    pub fn register(
+        &mut self,
+        ns: impl Into<String>,
+        name: impl Into<String>,
+        eval: impl CustomCaveat + 'static,
+    ) -> Result<(), RegistryError> {
+        let (ns, name) = (ns.into(), name.into());
+        if ns.is_empty() || name.is_empty() {
+            return Err(RegistryError::EmptyName);
+        }
+        let names = self.by_ns.entry(ns.clone()).or_default();
+        if names.contains_key(&name) {
+            return Err(RegistryError::Duplicate { ns, name });
+        }
+        names.insert(name, Box::new(eval));
+        Ok(())
+    }
+
+    /// Builder-style `register`.
+    pub fn with(
+        mut self,
+        ns: impl Into<String>,
+        name: impl Into<String>,
+        eval: impl CustomCaveat + 'static,
+    ) -> Result<Self, RegistryError> {
+        self.register(ns, name, eval)?;
+        Ok(self)
+    }
+
+    pub fn contains(&self, ns: &str, name: &str) -> bool {
+        self.get(ns, name).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_ns.values().map(HashMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, ns: &str, name: &str) -> Option<&dyn CustomCaveat> {
+        self.by_ns.get(ns)?.get(name).map(|b| &**b)
+    }
+}
+
+impl std::fmt::Debug for CaveatRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<String> = self
+            .by_ns
+            .iter()
+            .flat_map(|(ns, m)| m.keys().map(move |n| format!("{ns}/{n}")))
+            .collect();
+        names.sort_unstable();
+        f.debug_struct("CaveatRegistry")
+            .field("caveats", &names)
+            .finish()
+    }
+}
+
+/// Evaluate one custom caveat against `reg` (shared by every evaluator path).
+///
+/// Registered: the evaluator decides. Unregistered (or no registry): critical caveats push
+/// `unknown_critical_caveat`, advisory ones are skipped.
+pub(crate) fn eval_custom(
+    reg: Option<&CaveatRegistry>,
+    ns: &str,
+    name: &str,
+    args: &Value,
+    ctx: &RequestCtx,
+    out: &mut Vec<DenyReason>,
+) {
+    match reg.and_then(|r| r.get(ns, name)) {
+        Some(eval) => {
+            if let Err(reason) = eval.eval(args, ctx) {
+                out.push(reason);
+            }
+        }
+        None if is_critical(args) => out.push(DenyReason::Custom(UNKNOWN_CRITICAL.into())),
+        None => {}
+    }
+}
@@ -36,6 +36,6 @@ pub enum DenyReason {
     IpNotAllowed,
     TenantMismatch,
     BytesExceed,
-    RateExceeded, // placeholder for future rate caveat eval
+    RateExceeded, // token's Rate caveat bucket is empty (see verify::rate)
     Custom(String),
 }
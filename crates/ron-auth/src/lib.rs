@@ -3,6 +3,7 @@
 //! RO:INTERACTS  Delegates to `verify` module; no I/O or globals.
 //! RO:INVARIANTS  No panics; propagate typed errors; keep generics simple and zero-IO.
 
+pub mod caveats;
 pub mod cbor;
 pub mod errors;
 pub mod mac;
@@ -10,10 +11,12 @@ pub mod metrics;
 pub mod types;
 pub mod verify;
 
+pub use caveats::{CaveatRegistry, CustomCaveat};
 pub use errors::{AuthError, DenyReason};
 pub use types::{
     Capability, Caveat, Decision, MacKey, MacKeyProvider, RequestCtx, Scope, VerifierConfig,
 };
+pub use verify::{InMemoryRateStore, RateStore, VerifyEnv};
 
 /// Verify a single Base64URL-encoded token.
 #[inline]
@@ -26,6 +29,21 @@ pub fn verify_token<K: MacKeyProvider>(
     verify::verify_token(cfg, token_b64url, ctx, keys)
 }
 
+/// Verify a single token, consulting host state in `env` (a `RateStore`, a `CaveatRegistry`).
+///
+/// Tokens carrying `Caveat::Rate` are denied by the env-less entry points, since
+/// nothing would track their usage.
+#[inline]
+pub fn verify_token_with<K: MacKeyProvider>(
+    cfg: &VerifierConfig,
+    token_b64url: &str,
+    ctx: &RequestCtx,
+    keys: &K,
+    env: &VerifyEnv<'_>,
+) -> Result<Decision, AuthError> {
+    verify::verify_token_with(cfg, token_b64url, ctx, keys, env)
+}
+
 /// Verify many tokens; amortizes internal buffers and may parallelize
 /// when built with `--features parallel`.
 #[inline]
@@ -50,6 +68,31 @@ pub fn verify_many_into<K: MacKeyProvider + Sync>(
     verify::verify_many_into(cfg, tokens_b64url, ctx, keys, out)
 }
 
+/// Same as `verify_many`, consulting host state in `env`. Buckets are shared across the batch.
+#[inline]
+pub fn verify_many_with<K: MacKeyProvider + Sync>(
+    cfg: &VerifierConfig,
+    tokens_b64url: &[String],
+    ctx: &RequestCtx,
+    keys: &K,
+    env: &VerifyEnv<'_>,
+) -> Result<Vec<Decision>, AuthError> {
+    verify::verify_many_with(cfg, tokens_b64url, ctx, keys, env)
+}
+
+/// Same as `verify_many_into`, consulting host state in `env`.
+#[inline]
+pub fn verify_many_into_with<K: MacKeyProvider + Sync>(
+    cfg: &VerifierConfig,
+    tokens_b64url: &[String],
+    ctx: &RequestCtx,
+    keys: &K,
+    env: &VerifyEnv<'_>,
+    out: &mut Vec<Decision>,
+) -> Result<(), AuthError> {
+    verify::verify_many_into_with(cfg, tokens_b64url, ctx, keys, env, out)
+}
+
 // ===== Bench/Test-friendly helpers (stable, zero I/O) ========================
 
 /// Builder to assemble a Capability and sign/encode it for benches/tests.
@@ -0,0 +1,41 @@
+//! RO:WHAT  Host-supplied verification state (`VerifyEnv`): stores the pure pipeline consults.
+//! RO:WHY   Keep ron-auth zero-I/O and global-free while letting hosts plug in stateful checks.
+//! RO:INTERACTS  verify::pipeline (threads it through), verify::rate (RateStore), caveats::registry.
+//! RO:INVARIANTS Borrowed only; an empty env fails closed on caveats that need state or a registry.
+
+use super::rate::RateStore;
+use crate::caveats::CaveatRegistry;
+
+/// Stateful hooks for one verification call. `VerifyEnv::default()` has none.
+#[derive(Clone, Copy, Default)]
+pub struct VerifyEnv<'a> {
+    pub(crate) rates: Option<&'a dyn RateStore>,
+    pub(crate) caveats: Option<&'a CaveatRegistry>,
+}
+
+impl<'a> VerifyEnv<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enforce `Caveat::Rate` against `store`.
+    pub fn with_rates(mut self, store: &'a dyn RateStore) -> Self {
+        self.rates = Some(store);
+        self
+    }
+
+    /// Evaluate `Caveat::Custom` with the evaluators in `registry`.
+    pub fn with_caveats(mut self, registry: &'a CaveatRegistry) -> Self {
+        self.caveats = Some(registry);
+        self
+    }
+}
+
+impl std::fmt::Debug for VerifyEnv<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VerifyEnv")
+            .field("rates", &self.rates.is_some())
+            .field("caveats", &self.caveats)
+            .finish()
+    }
+}
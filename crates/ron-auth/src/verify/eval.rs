@@ -2,6 +2,7 @@
 //! RO:WHY   Fast path for small caveat sets; accumulates soft mismatches.
 
 use crate::{
+    caveats::{registry::eval_custom, CaveatRegistry},
     errors::{AuthError, DenyReason},
     types::{Caveat, RequestCtx, VerifierConfig},
 };
@@ -14,6 +15,7 @@ pub fn eval_caveats_streaming(
     cfg: &VerifierConfig,
     ctx: &RequestCtx,
     caveats: &[Caveat],
+    custom: Option<&CaveatRegistry>,
     out: &mut Vec<DenyReason>,
 ) -> Result<(), AuthError> {
     let now = ctx.now_unix_s as i64;
@@ -61,7 +63,7 @@ pub fn eval_caveats_streaming(
                 }
             }
             Caveat::Rate { .. } => {
-                // informational placeholder
+                // stateful: pipeline enforces via verify::rate once the rest pass
             }
             Caveat::Tenant(t) => {
                 if t != &ctx.tenant {
@@ -78,9 +80,7 @@ pub fn eval_caveats_streaming(
                     out.push(DenyReason::Custom("gov_policy_digest_mismatch".into()));
                 }
             }
-            Caveat::Custom { .. } => {
-                // host-defined; no-op
-            }
+            Caveat::Custom { ns, name, cbor } => eval_custom(custom, ns, name, cbor, ctx, out),
         }
     }
     Ok(())
@@ -1,14 +1,21 @@
 //! RO:WHAT    Verification module split into pipeline + evaluators.
-//! RO:LAYOUT  pipeline (API) | streaming (small sets) | soa (columns) | soa_eval | parse tests.
+//! RO:LAYOUT  pipeline (API) | streaming (small sets) | soa (columns) | soa_eval | rate (buckets) | env | parse tests.
 
+pub mod env; // host-supplied state for stateful caveats
 pub mod parse; // tests/utilities (kept)
+pub mod rate; // Rate caveat token buckets
 pub mod soa; // CaveatsSoA columnar representation
 pub mod soa_eval;
 pub mod streaming; // eval for small caveat sets (early short-circuit)
 
 mod pipeline; // main API (private module)
 
-pub use pipeline::{verify_many, verify_many_into, verify_token};
+pub use env::VerifyEnv;
+pub use pipeline::{
+    verify_many, verify_many_into, verify_many_into_with, verify_many_with, verify_token,
+    verify_token_with,
+};
+pub use rate::{InMemoryRateStore, RateKey, RateLimit, RateStore};
 
 #[cfg(feature = "bench-eval-modes")]
 pub use pipeline::{
@@ -2,7 +2,10 @@
 //! RO:WHY   Keep early short-circuit cost for common tiny tokens; SoA for larger sets;
 //!          add feature-gated parallelism for big batches while preserving order.
 //! RO:INVARIANTS No I/O; strict bounds; constant-time MAC compare; BLAKE3 only.
+//!               Host state only via `VerifyEnv`: custom caveats in both evaluators, rate buckets last.
 
+use super::env::VerifyEnv;
+use super::rate;
 use super::{soa::CaveatsSoA, soa_eval::eval_caveats_soa, streaming::eval_caveats_streaming};
 use crate::cbor::decode_b64url_cbor_capability_with_buf;
 use crate::errors::{AuthError, DenyReason};
@@ -69,10 +72,22 @@ pub fn verify_token<K: MacKeyProvider>(
     token_b64url: &str,
     ctx: &RequestCtx,
     keys: &K,
+) -> Result<Decision, AuthError> {
+    verify_token_with(cfg, token_b64url, ctx, keys, &VerifyEnv::default())
+}
+
+/// Same as `verify_token`, consulting host state in `env` (e.g. rate buckets).
+pub fn verify_token_with<K: MacKeyProvider>(
+    cfg: &VerifierConfig,
+    token_b64url: &str,
+    ctx: &RequestCtx,
+    keys: &K,
+    env: &VerifyEnv<'_>,
 ) -> Result<Decision, AuthError> {
     let mut scratch = Vec::with_capacity(1024);
     verify_one_with_buf_thresh(
         cfg,
+        env,
         cfg.soa_threshold,
         token_b64url,
         ctx,
@@ -88,9 +103,20 @@ pub fn verify_many<K: MacKeyProvider + Sync>(
     tokens_b64url: &[String],
     ctx: &RequestCtx,
     keys: &K,
+) -> Result<Vec<Decision>, AuthError> {
+    verify_many_with(cfg, tokens_b64url, ctx, keys, &VerifyEnv::default())
+}
+
+/// Same as `verify_many`, consulting host state in `env` (e.g. rate buckets).
+pub fn verify_many_with<K: MacKeyProvider + Sync>(
+    cfg: &VerifierConfig,
+    tokens_b64url: &[String],
+    ctx: &RequestCtx,
+    keys: &K,
+    env: &VerifyEnv<'_>,
 ) -> Result<Vec<Decision>, AuthError> {
     let mut out = Vec::with_capacity(tokens_b64url.len());
-    verify_many_into(cfg, tokens_b64url, ctx, keys, &mut out)?;
+    verify_many_into_with(cfg, tokens_b64url, ctx, keys, env, &mut out)?;
     Ok(out)
 }
 
@@ -105,6 +131,18 @@ pub fn verify_many_into<K: MacKeyProvider + Sync>(
     ctx: &RequestCtx,
     keys: &K,
     out: &mut Vec<Decision>,
+) -> Result<(), AuthError> {
+    verify_many_into_with(cfg, tokens_b64url, ctx, keys, &VerifyEnv::default(), out)
+}
+
+/// Same as `verify_many_into`, consulting host state in `env` (e.g. rate buckets).
+pub fn verify_many_into_with<K: MacKeyProvider + Sync>(
+    cfg: &VerifierConfig,
+    tokens_b64url: &[String],
+    ctx: &RequestCtx,
+    keys: &K,
+    env: &VerifyEnv<'_>,
+    out: &mut Vec<Decision>,
 ) -> Result<(), AuthError> {
     out.clear();
     out.reserve(tokens_b64url.len());
@@ -130,6 +168,7 @@ pub fn verify_many_into<K: MacKeyProvider + Sync>(
                     let mut scratch = Vec::with_capacity(2048);
                     verify_one_with_buf_thresh(
                         cfg,
+                        env,
                         effective_threshold,
                         tok,
                         ctx,
@@ -187,9 +226,15 @@ pub fn verify_many_into<K: MacKeyProvider + Sync>(
 
         // Evaluator may return Expired / NotYetValid (hard errors) or push soft reasons.
         let eval_res = if cap.caveats.len() <= effective_threshold {
-            eval_caveats_streaming(cfg, ctx, &cap.caveats, &mut reasons)
+            eval_caveats_streaming(cfg, ctx, &cap.caveats, env.caveats, &mut reasons)
         } else {
-            eval_caveats_soa(cfg, ctx, CaveatsSoA::from_slice(&cap.caveats), &mut reasons)
+            eval_caveats_soa(
+                cfg,
+                ctx,
+                CaveatsSoA::from_slice(&cap.caveats),
+                env.caveats,
+                &mut reasons,
+            )
         };
 
         if let Err(e @ (AuthError::Expired | AuthError::NotYetValid | AuthError::PolicyDeny)) =
@@ -199,6 +244,10 @@ pub fn verify_many_into<K: MacKeyProvider + Sync>(
             return Err(e);
         }
 
+        if reasons.is_empty() {
+            rate::enforce(env.rates, &cap, ctx.now_unix_s, &mut reasons);
+        }
+
         if reasons.is_empty() {
             m::counter_inc(m::C_ALLOW);
             out.push(Decision::Allow { scope: cap.scope });
@@ -215,6 +264,7 @@ pub fn verify_many_into<K: MacKeyProvider + Sync>(
 
 fn verify_one_with_buf_thresh<K: MacKeyProvider>(
     cfg: &VerifierConfig,
+    env: &VerifyEnv<'_>,
     threshold: usize,
     token_b64url: &str,
     ctx: &RequestCtx,
@@ -257,9 +307,15 @@ fn verify_one_with_buf_thresh<K: MacKeyProvider>(
 
     let mut reasons: Vec<DenyReason> = Vec::new();
     let eval_res = if cap.caveats.len() <= threshold {
-        eval_caveats_streaming(cfg, ctx, &cap.caveats, &mut reasons)
+        eval_caveats_streaming(cfg, ctx, &cap.caveats, env.caveats, &mut reasons)
     } else {
-        eval_caveats_soa(cfg, ctx, CaveatsSoA::from_slice(&cap.caveats), &mut reasons)
+        eval_caveats_soa(
+            cfg,
+            ctx,
+            CaveatsSoA::from_slice(&cap.caveats),
+            env.caveats,
+            &mut reasons,
+        )
     };
 
     if let Err(e @ (AuthError::Expired | AuthError::NotYetValid | AuthError::PolicyDeny)) = eval_res
@@ -268,6 +324,10 @@ fn verify_one_with_buf_thresh<K: MacKeyProvider>(
         return Err(e);
     }
 
+    if reasons.is_empty() {
+        rate::enforce(env.rates, &cap, ctx.now_unix_s, &mut reasons);
+    }
+
     if reasons.is_empty() {
         m::counter_inc(m::C_ALLOW);
         Ok(Decision::Allow { scope: cap.scope })
@@ -310,11 +370,15 @@ pub fn verify_token_streaming_only<K: MacKeyProvider>(
     }
     let mut reasons = Vec::new();
     if let Err(e @ (AuthError::Expired | AuthError::NotYetValid | AuthError::PolicyDeny)) =
-        eval_caveats_streaming(cfg, ctx, &cap.caveats, &mut reasons)
+        eval_caveats_streaming(cfg, ctx, &cap.caveats, None, &mut reasons)
     {
         m::bump_error(&e);
         return Err(e);
     }
+    if reasons.is_empty() {
+        // Bench modes carry no host state: Rate and critical Custom caveats fail closed.
+        rate::enforce(None, &cap, ctx.now_unix_s, &mut reasons);
+    }
     if reasons.is_empty() {
         m::counter_inc(m::C_ALLOW);
         Ok(Decision::Allow { scope: cap.scope })
@@ -357,11 +421,21 @@ pub fn verify_token_soa_only<K: MacKeyProvider>(
     }
     let mut reasons = Vec::new();
     if let Err(e @ (AuthError::Expired | AuthError::NotYetValid | AuthError::PolicyDeny)) =
-        eval_caveats_soa(cfg, ctx, CaveatsSoA::from_slice(&cap.caveats), &mut reasons)
+        eval_caveats_soa(
+            cfg,
+            ctx,
+            CaveatsSoA::from_slice(&cap.caveats),
+            None,
+            &mut reasons,
+        )
     {
         m::bump_error(&e);
         return Err(e);
     }
+    if reasons.is_empty() {
+        // Bench modes carry no host state: Rate and critical Custom caveats fail closed.
+        rate::enforce(None, &cap, ctx.now_unix_s, &mut reasons);
+    }
     if reasons.is_empty() {
         m::counter_inc(m::C_ALLOW);
         Ok(Decision::Allow { scope: cap.scope })
@@ -412,11 +486,14 @@ pub fn verify_many_streaming_only<K: MacKeyProvider>(
         }
         reasons.clear();
         if let Err(e @ (AuthError::Expired | AuthError::NotYetValid | AuthError::PolicyDeny)) =
-            eval_caveats_streaming(cfg, ctx, &cap.caveats, &mut reasons)
+            eval_caveats_streaming(cfg, ctx, &cap.caveats, None, &mut reasons)
         {
             m::bump_error(&e);
             return Err(e);
         }
+        if reasons.is_empty() {
+            rate::enforce(None, &cap, ctx.now_unix_s, &mut reasons);
+        }
         if reasons.is_empty() {
             m::counter_inc(m::C_ALLOW);
             out.push(Decision::Allow { scope: cap.scope });
@@ -471,11 +548,20 @@ pub fn verify_many_soa_only<K: MacKeyProvider>(
         }
         reasons.clear();
         if let Err(e @ (AuthError::Expired | AuthError::NotYetValid | AuthError::PolicyDeny)) =
-            eval_caveats_soa(cfg, ctx, CaveatsSoA::from_slice(&cap.caveats), &mut reasons)
+            eval_caveats_soa(
+                cfg,
+                ctx,
+                CaveatsSoA::from_slice(&cap.caveats),
+                None,
+                &mut reasons,
+            )
         {
             m::bump_error(&e);
             return Err(e);
         }
+        if reasons.is_empty() {
+            rate::enforce(None, &cap, ctx.now_unix_s, &mut reasons);
+        }
         if reasons.is_empty() {
             m::counter_inc(m::C_ALLOW);
             out.push(Decision::Allow { scope: cap.scope });
@@ -0,0 +1,206 @@
+//! RO:WHAT  Rate caveat enforcement: pluggable bucket store + in-memory token bucket.
+//! RO:WHY   `Caveat::Rate { burst, per_s }` must bound how often one token is used, not just be parsed.
+//! RO:INTERACTS  verify::pipeline calls `enforce` after streaming/SoA eval; hosts supply a `RateStore` via `VerifyEnv`.
+//! RO:INVARIANTS Buckets keyed by token identity (BLAKE3 of the MAC, never the MAC itself) + caveat ordinal;
+//!               consumed only when every other caveat passed; a token's buckets are drawn all-or-nothing;
+//!               no store => fail closed.
+
+use crate::errors::DenyReason;
+use crate::types::{Capability, Caveat};
+use smallvec::SmallVec;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Deny detail used when a token declares a rate but the verifier has no store to track it.
+pub const RATE_UNENFORCED: &str = "rate_unenforced";
+
+/// One bucket: which token, and which of its Rate caveats (0-based among Rate caveats).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RateKey {
+    pub token: [u8; 32],
+    pub ordinal: u16,
+}
+
+impl RateKey {
+    /// Key for the `ordinal`-th Rate caveat of the token whose MAC is `mac`.
+    pub fn for_token(mac: &[u8], ordinal: u16) -> Self {
+        Self {
+            token: *blake3::hash(mac).as_bytes(),
+            ordinal,
+        }
+    }
+}
+
+/// Limits declared by a Rate caveat: up to `burst` uses at once, refilled at `per_s` per second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub burst: u32,
+    pub per_s: u32,
+}
+
+/// Host-provided bucket store (in-memory, shared cache, ...). Must be cheap and non-blocking.
+pub trait RateStore: Send + Sync {
+    /// Take one use from every bucket in `buckets` at `now_unix_s`, or from none of them;
+    /// `false` means at least one limit is exhausted.
+    fn try_acquire(&self, buckets: &[(RateKey, RateLimit)], now_unix_s: u64) -> bool;
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: u64,
+    last_s: u64,
+}
+
+impl Bucket {
+    fn refill(&mut self, limit: RateLimit, now_s: u64) {
+        let elapsed = now_s.saturating_sub(self.last_s);
+        if elapsed > 0 {
+            self.tokens = self
+                .tokens
+                .saturating_add(elapsed.saturating_mul(u64::from(limit.per_s)))
+                .min(u64::from(limit.burst));
+            self.last_s = now_s;
+        }
+    }
+}
+
+/// Process-local token buckets behind one mutex; bounded by `max_buckets`.
+///
+/// When full, buckets idle long enough to have refilled are dropped first, then the least recently used.
+#[derive(Debug)]
+pub struct InMemoryRateStore {
+    max_buckets: usize,
+    buckets: Mutex<HashMap<RateKey, (RateLimit, Bucket)>>,
+}
+
+impl Default for InMemoryRateStore {
+    fn default() -> Self {
+        Self::new(100_000)
+    }
+}
+
+impl InMemoryRateStore {
+    pub fn new(max_buckets: usize) -> Self {
+        Self {
+            max_buckets: max_buckets.max(1),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Buckets currently tracked.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<RateKey, (RateLimit, Bucket)>> {
+        // A poisoned map only holds counters; keep serving rather than failing every request.
+        self.buckets.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Make room for `missing` new buckets, never dropping one of `keep`.
+    fn evict(
+        map: &mut HashMap<RateKey, (RateLimit, Bucket)>,
+        max: usize,
+        missing: usize,
+        keep: &[(RateKey, RateLimit)],
+        now_s: u64,
+    ) {
+        let kept = |k: &RateKey| keep.iter().any(|(key, _)| key == k);
+        map.retain(|k, (limit, b)| {
+            let mut b = *b;
+            b.refill(*limit, now_s);
+            kept(k) || b.tokens < u64::from(limit.burst)
+        });
+        while map.len() + missing > max {
+            let Some(oldest) = map
+                .iter()
+                .filter(|(k, _)| !kept(k))
+                .min_by_key(|(_, (_, b))| b.last_s)
+                .map(|(k, _)| *k)
+            else {
+                break;
+            };
+            map.remove(&oldest);
+        }
+    }
+}
+
+impl RateStore for InMemoryRateStore {
+    fn try_acquire(&self, buckets: &[(RateKey, RateLimit)], now_unix_s: u64) -> bool {
+        let mut map = self.lock();
+        let missing = buckets.iter().filter(|(k, _)| !map.contains_key(k)).count();
+        if missing > 0 && map.len() + missing > self.max_buckets {
+            Self::evict(&mut map, self.max_buckets, missing, buckets, now_unix_s);
+        }
+        // Check every bucket before drawing from any, under the one lock.
+        let mut ok = true;
+        for (key, limit) in buckets {
+            let (stored, bucket) = map.entry(*key).or_insert((
+                *limit,
+                Bucket {
+                    tokens: u64::from(limit.burst),
+                    last_s: now_unix_s,
+                },
+            ));
+            *stored = *limit;
+            bucket.refill(*limit, now_unix_s);
+            ok &= bucket.tokens > 0;
+        }
+        if ok {
+            for (key, _) in buckets {
+                if let Some((_, bucket)) = map.get_mut(key) {
+                    bucket.tokens -= 1;
+                }
+            }
+        }
+        ok
+    }
+}
+
+/// Consult `store` for every Rate caveat of `cap`; pushes at most one reason.
+///
+/// Call only once every other caveat has passed, so denied requests never drain a bucket.
+pub(crate) fn enforce(
+    store: Option<&dyn RateStore>,
+    cap: &Capability,
+    now_unix_s: u64,
+    out: &mut Vec<DenyReason>,
+) {
+    let limits: SmallVec<[RateLimit; 2]> = cap
+        .caveats
+        .iter()
+        .filter_map(|c| match c {
+            Caveat::Rate { per_s, burst } => Some(RateLimit {
+                burst: *burst,
+                per_s: *per_s,
+            }),
+            _ => None,
+        })
+        .collect();
+    if limits.is_empty() {
+        return;
+    }
+    let Some(store) = store else {
+        out.push(DenyReason::Custom(RATE_UNENFORCED.into()));
+        return;
+    };
+    let token = *blake3::hash(&cap.mac).as_bytes();
+    let buckets: SmallVec<[(RateKey, RateLimit); 2]> = limits
+        .iter()
+        .enumerate()
+        .map(|(ordinal, limit)| {
+            let key = RateKey {
+                token,
+                ordinal: u16::try_from(ordinal).unwrap_or(u16::MAX),
+            };
+            (key, *limit)
+        })
+        .collect();
+    if !store.try_acquire(&buckets, now_unix_s) {
+        out.push(DenyReason::RateExceeded);
+    }
+}
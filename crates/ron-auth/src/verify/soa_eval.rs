@@ -1,6 +1,7 @@
 //! Evaluator over SoA columns.
 
 use super::soa::CaveatsSoA;
+use crate::caveats::{registry::eval_custom, CaveatRegistry};
 use crate::errors::{AuthError, DenyReason};
 use crate::types::{RequestCtx, VerifierConfig};
 
@@ -8,6 +9,7 @@ pub fn eval_caveats_soa<'a>(
     cfg: &VerifierConfig,
     ctx: &RequestCtx,
     soa: CaveatsSoA<'a>,
+    custom: Option<&CaveatRegistry>,
     out: &mut Vec<DenyReason>,
 ) -> Result<(), AuthError> {
     let now = ctx.now_unix_s as i64;
@@ -66,10 +68,7 @@ pub fn eval_caveats_soa<'a>(
         }
     }
 
-    // Rate (placeholder)
-    for (_burst, _per_s) in soa.rate.iter() {
-        // host policy may enforce elsewhere
-    }
+    // Rate: stateful, enforced by the pipeline via verify::rate once everything else passes.
 
     // Tenant
     for t in soa.tenant.iter() {
@@ -92,8 +91,10 @@ pub fn eval_caveats_soa<'a>(
         }
     }
 
-    // Custom caveats are host-defined; keep informational
-    let _ = soa.custom;
+    // Custom: host-registered evaluators; unknown critical ones deny.
+    for (ns, name, cbor) in soa.custom.iter() {
+        eval_custom(custom, ns, name, cbor, ctx, out);
+    }
 
     Ok(())
 }
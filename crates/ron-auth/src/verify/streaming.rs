@@ -1,5 +1,6 @@
 //! Streaming evaluator with early short-circuit for Exp/Nbf.
 
+use crate::caveats::{registry::eval_custom, CaveatRegistry};
 use crate::errors::{AuthError, DenyReason};
 use crate::types::{Caveat, RequestCtx, VerifierConfig};
 use ipnet::IpNet;
@@ -10,6 +11,7 @@ pub fn eval_caveats_streaming(
     cfg: &VerifierConfig,
     ctx: &RequestCtx,
     caveats: &[Caveat],
+    custom: Option<&CaveatRegistry>,
     out: &mut Vec<DenyReason>,
 ) -> Result<(), AuthError> {
     let now = ctx.now_unix_s as i64;
@@ -56,7 +58,7 @@ pub fn eval_caveats_streaming(
                     }
                 }
             }
-            Caveat::Rate { .. } => {} // informational
+            Caveat::Rate { .. } => {} // stateful: pipeline enforces via verify::rate once the rest pass
             Caveat::Tenant(t) => {
                 if t != &ctx.tenant {
                     out.push(DenyReason::TenantMismatch);
@@ -72,7 +74,7 @@ pub fn eval_caveats_streaming(
                     out.push(DenyReason::Custom("gov_policy_digest_mismatch".into()));
                 }
             }
-            Caveat::Custom { .. } => {}
+            Caveat::Custom { ns, name, cbor } => eval_custom(custom, ns, name, cbor, ctx, out),
         }
     }
     Ok(())
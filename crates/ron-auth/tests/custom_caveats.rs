@@ -0,0 +1,217 @@
+//! Custom caveats: registry-dispatched evaluators on the streaming, SoA and batch paths.
+use ron_auth::caveats::{builtin, typed, RegistryError};
+use ron_auth::{
+    verify_many_with, verify_token, verify_token_with, CapabilityBuilder, Caveat, CaveatRegistry,
+    Decision, DenyReason, MacKey, MacKeyProvider, RequestCtx, Scope, VerifierConfig, VerifyEnv,
+};
+use serde::Deserialize;
+use serde_cbor::Value;
+use std::collections::BTreeMap;
+
+#[derive(Clone)]
+struct StaticKeys;
+impl MacKeyProvider for StaticKeys {
+    fn key_for(&self, kid: &str, tid: &str) -> Option<MacKey> {
+        if kid == "k1" && tid == "tenant-a" {
+            Some(MacKey(*b"0123456789abcdef0123456789abcdef"))
+        } else {
+            None
+        }
+    }
+}
+
+fn ctx(amount: u64, bucket: &str) -> RequestCtx {
+    let mut extras = BTreeMap::new();
+    extras.insert(Value::Text("amount".into()), Value::Integer(amount.into()));
+    extras.insert(Value::Text("bucket".into()), Value::Text(bucket.into()));
+    RequestCtx {
+        now_unix_s: 1_700_000_000,
+        method: "PUT".into(),
+        path: "/o/abc".into(),
+        peer_ip: None,
+        object_addr: None,
+        tenant: "tenant-a".into(),
+        amnesia: false,
+        policy_digest_hex: None,
+        extras: Value::Map(extras),
+    }
+}
+
+fn cfg(soa_threshold: usize) -> VerifierConfig {
+    VerifierConfig {
+        max_token_bytes: 4096,
+        max_caveats: 128,
+        clock_skew_secs: 60,
+        soa_threshold,
+    }
+}
+
+fn custom(ns: &str, name: &str, cbor: Value) -> Caveat {
+    Caveat::Custom {
+        ns: ns.into(),
+        name: name.into(),
+        cbor,
+    }
+}
+
+fn token(caveats: Vec<Caveat>) -> String {
+    let scope = Scope {
+        prefix: Some("/o/".into()),
+        methods: vec!["PUT".into()],
+        max_bytes: None,
+    };
+    let cap = CapabilityBuilder::new(scope, "tenant-a", "k1")
+        .extend(caveats)
+        .build();
+    ron_auth::sign_and_encode_b64url(&cap, &StaticKeys).unwrap()
+}
+
+fn reasons(d: Decision) -> Vec<DenyReason> {
+    match d {
+        Decision::Allow { .. } => Vec::new(),
+        Decision::Deny { reasons } => reasons,
+    }
+}
+
+#[derive(Deserialize)]
+struct Window {
+    from: u64,
+    to: u64,
+}
+
+fn registry() -> CaveatRegistry {
+    CaveatRegistry::new()
+        .with("wallet", "max_spend", builtin::extras_le("amount"))
+        .unwrap()
+        .with("storage", "bucket", builtin::extras_eq("bucket"))
+        .unwrap()
+        .with(
+            "demo",
+            "window",
+            typed(|w: &Window, ctx: &RequestCtx| {
+                if (w.from..w.to).contains(&ctx.now_unix_s) {
+                    Ok(())
+                } else {
+                    Err(DenyReason::Custom("outside_window".into()))
+                }
+            }),
+        )
+        .unwrap()
+}
+
+fn window(from: u64, to: u64) -> Value {
+    let mut m = BTreeMap::new();
+    m.insert(Value::Text("from".into()), Value::Integer(from.into()));
+    m.insert(Value::Text("to".into()), Value::Integer(to.into()));
+    Value::Map(m)
+}
+
+#[test]
+fn registered_evaluators_allow_and_deny_on_both_paths() {
+    let reg = registry();
+    let env = VerifyEnv::new().with_caveats(&reg);
+    let tok = token(vec![
+        custom("wallet", "max_spend", Value::Integer(500)),
+        custom("storage", "bucket", Value::Text("photos".into())),
+        custom("demo", "window", window(1_600_000_000, 1_800_000_000)),
+    ]);
+
+    // 8 => streaming, 0 => SoA
+    for threshold in [8, 0] {
+        let cfg = cfg(threshold);
+        let ok = verify_token_with(&cfg, &tok, &ctx(500, "photos"), &StaticKeys, &env).unwrap();
+        assert!(reasons(ok).is_empty(), "threshold {threshold}");
+
+        let d = verify_token_with(&cfg, &tok, &ctx(501, "docs"), &StaticKeys, &env).unwrap();
+        assert_eq!(
+            reasons(d),
+            vec![
+                DenyReason::Custom("amount_exceeded".into()),
+                DenyReason::Custom("bucket_mismatch".into()),
+            ],
+            "threshold {threshold}"
+        );
+    }
+}
+
+#[test]
+fn unknown_critical_fails_closed_and_advisory_is_skipped() {
+    let reg = registry();
+    let env = VerifyEnv::new().with_caveats(&reg);
+    let mut advisory = BTreeMap::new();
+    advisory.insert(Value::Text("crit".into()), Value::Bool(false));
+
+    let critical = token(vec![custom("acme", "geo", Value::Text("eu".into()))]);
+    let skipped = token(vec![custom("acme", "hint", Value::Map(advisory))]);
+    for threshold in [8, 0] {
+        let cfg = cfg(threshold);
+        let d = verify_token_with(&cfg, &critical, &ctx(1, "b"), &StaticKeys, &env).unwrap();
+        assert_eq!(
+            reasons(d),
+            vec![DenyReason::Custom("unknown_critical_caveat".into())]
+        );
+        let d = verify_token_with(&cfg, &skipped, &ctx(1, "b"), &StaticKeys, &env).unwrap();
+        assert!(reasons(d).is_empty());
+    }
+
+    // No registry at all: even caveats a host could evaluate are unknown here.
+    let known = token(vec![custom("wallet", "max_spend", Value::Integer(500))]);
+    let d = verify_token(&cfg(8), &known, &ctx(1, "b"), &StaticKeys).unwrap();
+    assert_eq!(
+        reasons(d),
+        vec![DenyReason::Custom("unknown_critical_caveat".into())]
+    );
+}
+
+#[test]
+fn malformed_payload_denies() {
+    let reg = registry();
+    let env = VerifyEnv::new().with_caveats(&reg);
+    let tok = token(vec![
+        custom("wallet", "max_spend", Value::Text("lots".into())),
+        custom("demo", "window", Value::Null),
+    ]);
+    let d = verify_token_with(&cfg(8), &tok, &ctx(1, "b"), &StaticKeys, &env).unwrap();
+    assert_eq!(
+        reasons(d),
+        vec![DenyReason::Custom("custom_caveat_malformed".into()); 2]
+    );
+}
+
+#[test]
+fn batch_path_uses_registry_in_order() {
+    // 80 tokens crosses the parallel kickoff when built with `--features parallel`.
+    let reg = registry();
+    let env = VerifyEnv::new().with_caveats(&reg);
+    let toks: Vec<String> = (0..80u64)
+        .map(|i| {
+            token(vec![custom(
+                "wallet",
+                "max_spend",
+                Value::Integer(i.into()),
+            )])
+        })
+        .collect();
+    let out = verify_many_with(&cfg(8), &toks, &ctx(40, "b"), &StaticKeys, &env).unwrap();
+    for (i, d) in out.into_iter().enumerate() {
+        assert_eq!(reasons(d).is_empty(), i >= 40, "token {i}");
+    }
+}
+
+#[test]
+fn registration_rejects_duplicates_and_empty_names() {
+    let mut reg = registry();
+    assert_eq!(reg.len(), 3);
+    assert_eq!(
+        reg.register("wallet", "max_spend", builtin::extras_le("amount")),
+        Err(RegistryError::Duplicate {
+            ns: "wallet".into(),
+            name: "max_spend".into()
+        })
+    );
+    assert_eq!(
+        reg.register("", "x", builtin::extras_le("amount")),
+        Err(RegistryError::EmptyName)
+    );
+    assert!(reg.contains("storage", "bucket"));
+}
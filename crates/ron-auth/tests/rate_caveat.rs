@@ -0,0 +1,174 @@
+//! Rate caveats: token-bucket enforcement through `VerifyEnv`, on both evaluator paths.
+use ron_auth::verify::RateKey;
+use ron_auth::{
+    verify_many_with, verify_token, verify_token_with, CapabilityBuilder, Caveat, Decision,
+    DenyReason, InMemoryRateStore, MacKey, MacKeyProvider, RateStore, RequestCtx, Scope,
+    VerifierConfig, VerifyEnv,
+};
+use serde_cbor::Value;
+
+#[derive(Clone)]
+struct StaticKeys;
+impl MacKeyProvider for StaticKeys {
+    fn key_for(&self, kid: &str, tid: &str) -> Option<MacKey> {
+        if kid == "k1" && tid == "tenant-a" {
+            Some(MacKey(*b"0123456789abcdef0123456789abcdef"))
+        } else {
+            None
+        }
+    }
+}
+
+const T0: u64 = 1_700_000_000;
+
+fn ctx(now: u64) -> RequestCtx {
+    RequestCtx {
+        now_unix_s: now,
+        method: "GET".into(),
+        path: "/index/abc".into(),
+        peer_ip: None,
+        object_addr: None,
+        tenant: "tenant-a".into(),
+        amnesia: false,
+        policy_digest_hex: None,
+        extras: Value::Null,
+    }
+}
+
+fn cfg(soa_threshold: usize) -> VerifierConfig {
+    VerifierConfig {
+        max_token_bytes: 4096,
+        max_caveats: 128,
+        clock_skew_secs: 60,
+        soa_threshold,
+    }
+}
+
+fn token(burst: u32, per_s: u32, tenant_caveat: &str) -> String {
+    let scope = Scope {
+        prefix: Some("/index/".into()),
+        methods: vec!["GET".into()],
+        max_bytes: None,
+    };
+    let cap = CapabilityBuilder::new(scope, "tenant-a", "k1")
+        .caveat(Caveat::Tenant(tenant_caveat.into()))
+        .caveat(Caveat::Rate { per_s, burst })
+        .build();
+    ron_auth::sign_and_encode_b64url(&cap, &StaticKeys).unwrap()
+}
+
+fn is_allow(d: &Decision) -> bool {
+    matches!(d, Decision::Allow { .. })
+}
+
+fn is_rate_deny(d: &Decision) -> bool {
+    matches!(d, Decision::Deny { reasons } if reasons == &[DenyReason::RateExceeded])
+}
+
+#[test]
+fn burst_then_deny_then_refill_streaming_and_soa() {
+    // soa_threshold 8 => streaming for this 2-caveat token; 0 => SoA.
+    for threshold in [8, 0] {
+        let store = InMemoryRateStore::default();
+        let env = VerifyEnv::new().with_rates(&store);
+        let tok = token(2, 1, "tenant-a");
+        let cfg = cfg(threshold);
+
+        for _ in 0..2 {
+            let d = verify_token_with(&cfg, &tok, &ctx(T0), &StaticKeys, &env).unwrap();
+            assert!(is_allow(&d), "threshold {threshold}: {d:?}");
+        }
+        let d = verify_token_with(&cfg, &tok, &ctx(T0), &StaticKeys, &env).unwrap();
+        assert!(is_rate_deny(&d), "threshold {threshold}: {d:?}");
+
+        // One second refills one use, not more.
+        let d = verify_token_with(&cfg, &tok, &ctx(T0 + 1), &StaticKeys, &env).unwrap();
+        assert!(is_allow(&d));
+        let d = verify_token_with(&cfg, &tok, &ctx(T0 + 1), &StaticKeys, &env).unwrap();
+        assert!(is_rate_deny(&d));
+    }
+}
+
+#[test]
+fn buckets_are_per_token_and_denied_requests_do_not_consume() {
+    let store = InMemoryRateStore::default();
+    let env = VerifyEnv::new().with_rates(&store);
+    let cfg = cfg(8);
+    let a = token(1, 1, "tenant-a");
+    let b = token(1, 2, "tenant-a");
+    let wrong_tenant = token(1, 1, "tenant-b");
+
+    // A request already denied for another reason leaves the bucket alone.
+    let d = verify_token_with(&cfg, &wrong_tenant, &ctx(T0), &StaticKeys, &env).unwrap();
+    assert!(matches!(d, Decision::Deny { reasons } if reasons == [DenyReason::TenantMismatch]));
+    assert!(store.is_empty());
+
+    let many = verify_many_with(&cfg, &[a.clone(), b, a], &ctx(T0), &StaticKeys, &env).unwrap();
+    assert!(is_allow(&many[0]));
+    assert!(is_allow(&many[1]));
+    assert!(is_rate_deny(&many[2]));
+    assert_eq!(store.len(), 2);
+}
+
+#[test]
+fn rate_caveat_without_store_fails_closed() {
+    let tok = token(100, 100, "tenant-a");
+    let d = verify_token(&cfg(8), &tok, &ctx(T0), &StaticKeys).unwrap();
+    match d {
+        Decision::Deny { reasons } => {
+            assert_eq!(reasons, vec![DenyReason::Custom("rate_unenforced".into())])
+        }
+        other => panic!("expected deny, got {other:?}"),
+    }
+}
+
+#[test]
+fn in_memory_store_is_bounded_and_zero_burst_denies() {
+    let store = InMemoryRateStore::new(2);
+    let limit = ron_auth::verify::RateLimit { burst: 1, per_s: 1 };
+    for i in 0u8..5 {
+        let key = RateKey::for_token(&[i], 0);
+        assert!(store.try_acquire(&[(key, limit)], T0 + u64::from(i)));
+        assert!(store.len() <= 2);
+    }
+
+    let zero = ron_auth::verify::RateLimit {
+        burst: 0,
+        per_s: 10,
+    };
+    let key = RateKey::for_token(b"z", 0);
+    assert!(!store.try_acquire(&[(key, zero)], T0));
+    assert!(!store.try_acquire(&[(key, zero)], T0 + 60));
+}
+
+#[test]
+fn a_denied_request_draws_from_none_of_its_buckets() {
+    let store = InMemoryRateStore::default();
+    let env = VerifyEnv::new().with_rates(&store);
+    let cfg = cfg(8);
+    // Two uses ever (no refill), but at most one per second.
+    let scope = Scope {
+        prefix: Some("/index/".into()),
+        methods: vec!["GET".into()],
+        max_bytes: None,
+    };
+    let cap = CapabilityBuilder::new(scope, "tenant-a", "k1")
+        .caveat(Caveat::Rate { per_s: 0, burst: 2 })
+        .caveat(Caveat::Rate { per_s: 1, burst: 1 })
+        .build();
+    let tok = ron_auth::sign_and_encode_b64url(&cap, &StaticKeys).unwrap();
+    let verify = |now| verify_token_with(&cfg, &tok, &ctx(now), &StaticKeys, &env).unwrap();
+
+    assert!(is_allow(&verify(T0)));
+    // The per-second bucket is empty; these denials must not spend the lifetime budget.
+    for _ in 0..5 {
+        let d = verify(T0);
+        assert!(is_rate_deny(&d), "{d:?}");
+    }
+    let d = verify(T0 + 1);
+    assert!(
+        is_allow(&d),
+        "second lifetime use was drained by denials: {d:?}"
+    );
+    assert!(is_rate_deny(&verify(T0 + 2)));
+}
@@ -0,0 +1,180 @@
+//! RO:WHAT — Cross-process RPC DTOs: the `Envelope` carried over UDS plus typed request/response enums per service
+//! RO:WHY  — Pillar 1 (Kernel & Orchestration); Concerns: SEC/DX. Local control tools (ronctl) and services share one schema
+//! RO:INTERACTS — uds (framing + correlation); svc-index / svc-storage / svc-overlay servers; tools/ronctl
+//! RO:INVARIANTS — payload is MessagePack of the service's Req/Resp enum; replies echo service, method and corr_id;
+//!                 a reply with `error` set carries no payload (the request could not be handled);
+//!                 enums are #[non_exhaustive] (additive growth); no secrets in payloads, token is opaque bytes
+//! RO:SECURITY — `token` is never logged or interpreted here; servers verify it
+//! RO:TEST — Integration: tests/uds_rpc.rs (feature = "uds")
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::uds::UdsError;
+
+/// Service name for svc-index.
+pub const SVC_INDEX: &str = "svc.index";
+/// Service name for svc-storage.
+pub const SVC_STORAGE: &str = "svc.storage";
+/// Service name for svc-overlay.
+pub const SVC_OVERLAY: &str = "svc.overlay";
+
+/// One RPC message on the wire (request or reply).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Target service, e.g. [`SVC_INDEX`].
+    pub service: String,
+    /// Versioned method, e.g. `"v1.resolve"`.
+    pub method: String,
+    /// Caller-chosen id echoed by the reply.
+    pub corr_id: u64,
+    /// Opaque capability token (empty when the socket policy alone authorizes).
+    pub token: Vec<u8>,
+    /// MessagePack-encoded request or response body.
+    pub payload: Vec<u8>,
+    /// Set on replies whose request failed before a typed response could be built; `payload` is then empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Envelope {
+    /// Build a request carrying `body`.
+    pub fn request<T: Serialize>(
+        service: impl Into<String>,
+        method: impl Into<String>,
+        corr_id: u64,
+        body: &T,
+    ) -> Result<Self, UdsError> {
+        Ok(Self {
+            service: service.into(),
+            method: method.into(),
+            corr_id,
+            token: Vec::new(),
+            payload: encode(body)?,
+            error: None,
+        })
+    }
+
+    /// Attach a capability token.
+    pub fn with_token(mut self, token: Vec<u8>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Reply to this request with `body` (same service, method and corr_id; no token).
+    pub fn reply<T: Serialize>(&self, body: &T) -> Result<Self, UdsError> {
+        Ok(Self {
+            service: self.service.clone(),
+            method: self.method.clone(),
+            corr_id: self.corr_id,
+            token: Vec::new(),
+            payload: encode(body)?,
+            error: None,
+        })
+    }
+
+    /// Error reply to this request (same service, method and corr_id; empty payload).
+    pub fn error_reply(&self, message: impl Into<String>) -> Self {
+        Self {
+            service: self.service.clone(),
+            method: self.method.clone(),
+            corr_id: self.corr_id,
+            token: Vec::new(),
+            payload: Vec::new(),
+            error: Some(message.into()),
+        }
+    }
+
+    /// Decode the payload as `T`.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, UdsError> {
+        rmp_serde::from_slice(&self.payload).map_err(|e| UdsError::Decode(e.to_string()))
+    }
+}
+
+fn encode<T: Serialize>(body: &T) -> Result<Vec<u8>, UdsError> {
+    // Named (map) encoding so fields can be added without breaking older peers.
+    rmp_serde::to_vec_named(body).map_err(|e| UdsError::Encode(e.to_string()))
+}
+
+/// svc-index requests.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndexReq {
+    /// Liveness probe.
+    Health,
+    /// Resolve a content address (`b3:<hex>.ext`) to its bundle directory.
+    Resolve { addr: String },
+    /// Insert or overwrite an address → directory mapping.
+    PutAddress { addr: String, dir: String },
+}
+
+/// svc-index responses.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IndexResp {
+    /// Reply to `Health`.
+    HealthOk,
+    /// Reply to `Resolve`.
+    Resolved { dir: String },
+    /// Address unknown.
+    NotFound,
+    /// Reply to `PutAddress`.
+    PutOk,
+    /// Request failed; human-readable reason.
+    Err { err: String },
+}
+
+/// svc-storage requests.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageReq {
+    /// Liveness probe.
+    Health,
+    /// Read `rel` inside bundle directory `dir`.
+    ReadFile { dir: String, rel: String },
+    /// Write `bytes` to `rel` inside bundle directory `dir`.
+    WriteFile {
+        dir: String,
+        rel: String,
+        bytes: Vec<u8>,
+    },
+}
+
+/// svc-storage responses.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StorageResp {
+    /// Reply to `Health`.
+    HealthOk,
+    /// Reply to `ReadFile`.
+    File { bytes: Vec<u8> },
+    /// Reply to `WriteFile`.
+    Written,
+    /// File or directory unknown.
+    NotFound,
+    /// Request failed; human-readable reason.
+    Err { err: String },
+}
+
+/// svc-overlay requests.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverlayReq {
+    /// Liveness probe.
+    Health,
+    /// Fetch `rel` of the bundle at content address `addr`.
+    Get { addr: String, rel: String },
+}
+
+/// svc-overlay responses.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverlayResp {
+    /// Reply to `Health`.
+    HealthOk,
+    /// Reply to `Get`.
+    Bytes { data: Vec<u8> },
+    /// Address or file unknown.
+    NotFound,
+    /// Request failed; human-readable reason.
+    Err { err: String },
+}
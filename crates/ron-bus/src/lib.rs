@@ -1,10 +1,12 @@
 //! RO:WHAT — Public surface for the in-process broadcast bus (bounded, lossy, observable-by-host)
 //! RO:WHY  — Pillar 1 (Kernel & Orchestration); Concerns: RES/PERF (bounded backpressure, no locks across .await)
-//! RO:INTERACTS — internal::channel (tokio::broadcast wrapper); public: Bus, BusConfig, Event, BusError
+//! RO:INTERACTS — internal::channel (tokio::broadcast wrapper); public: Bus, BusConfig, Event, BusError;
+//!                feature "uds": api (Envelope + per-service Req/Resp) and uds (cross-process framing)
 //! RO:INVARIANTS — bounded channel; one receiver per task; no background tasks; no secrets/PII on bus
 //! RO:METRICS — none inside crate (host updates counters/gauges in recv loop)
 //! RO:CONFIG — capacity fixed at construction; cutover by constructing a new Bus
-//! RO:SECURITY — no network/disk I/O; no payload logging; secret-free surface
+//! RO:SECURITY — the bus itself does no network/disk I/O; only the opt-in "uds" feature touches sockets;
+//!               no payload logging; secret-free surface
 //! RO:TEST — integration tests in tests/*; loom model optional (cfg(loom))
 
 #![forbid(unsafe_code)]
@@ -20,6 +22,11 @@ pub mod prelude;
 
 pub mod internal; // kept small; still non-public APIs within it
 
+#[cfg(feature = "uds")]
+pub mod api;
+#[cfg(feature = "uds")]
+pub mod uds;
+
 pub use bus::Bus;
 pub use config::BusConfig;
 pub use errors::BusError;
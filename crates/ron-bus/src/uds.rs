@@ -0,0 +1,304 @@
+//! RO:WHAT — Unix-socket RPC transport: length-prefixed MessagePack `Envelope` frames, corr_id correlation, peer checks
+//! RO:WHY  — Pillar 1 (Kernel & Orchestration); Concerns: SEC/RES. Local tools and services talk without HTTP or TCP
+//! RO:INTERACTS — api::Envelope; std::os::unix::net; rustix (SO_PEERCRED); tools/ronctl; service UDS listeners
+//! RO:INVARIANTS — frame = u32 big-endian length + MessagePack(Envelope); length checked against the cap before
+//!                 allocating; one reply per request, same corr_id (an error frame when the handler fails);
+//!                 clean EOF only at a frame boundary
+//! RO:CONFIG — `MAX_FRAME_BYTES` default cap; `PeerPolicy` allowlist (default: same uid as this process)
+//! RO:SECURITY — socket dir 0700 and socket 0600 from `listen`; PEERCRED checked before any frame is read;
+//!               tokens and payloads are never logged
+//! RO:TEST — Integration: tests/uds_rpc.rs (feature = "uds")
+
+use std::{
+    fmt, fs,
+    io::{self, Read, Write},
+    os::unix::{
+        fs::PermissionsExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::Path,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::api::Envelope;
+
+/// Default frame cap (1 MiB, the OAP/1 frame size).
+pub const MAX_FRAME_BYTES: usize = 1024 * 1024;
+
+/// Errors from the UDS transport.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum UdsError {
+    /// Socket I/O failed (including a frame cut short by the peer).
+    Io(io::Error),
+    /// Peer closed the connection between frames.
+    Closed,
+    /// Frame length above the configured cap.
+    FrameTooLarge { len: usize, max: usize },
+    /// Envelope or body could not be encoded.
+    Encode(String),
+    /// Envelope or body could not be decoded.
+    Decode(String),
+    /// Reply carried a different corr_id than the request.
+    CorrelationMismatch { expected: u64, got: u64 },
+    /// Peer uid not allowed by the socket policy.
+    PeerRejected { uid: u32 },
+    /// Server answered with an error frame instead of a response.
+    Remote(String),
+}
+
+impl fmt::Display for UdsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "uds io: {e}"),
+            Self::Closed => f.write_str("uds peer closed the connection"),
+            Self::FrameTooLarge { len, max } => {
+                write!(f, "uds frame of {len} bytes exceeds cap of {max}")
+            }
+            Self::Encode(e) => write!(f, "uds encode: {e}"),
+            Self::Decode(e) => write!(f, "uds decode: {e}"),
+            Self::CorrelationMismatch { expected, got } => {
+                write!(
+                    f,
+                    "uds reply corr_id {got} does not match request {expected}"
+                )
+            }
+            Self::PeerRejected { uid } => write!(f, "uds peer uid {uid} not allowed"),
+            Self::Remote(e) => write!(f, "uds server error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for UdsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for UdsError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Write one envelope frame.
+pub fn send<W: Write>(w: &mut W, env: &Envelope) -> Result<(), UdsError> {
+    send_with_limit(w, env, MAX_FRAME_BYTES)
+}
+
+/// Write one envelope frame, refusing to send more than `max` bytes.
+pub fn send_with_limit<W: Write>(w: &mut W, env: &Envelope, max: usize) -> Result<(), UdsError> {
+    let body = rmp_serde::to_vec_named(env).map_err(|e| UdsError::Encode(e.to_string()))?;
+    if body.len() > max {
+        return Err(UdsError::FrameTooLarge {
+            len: body.len(),
+            max,
+        });
+    }
+    let mut frame = Vec::with_capacity(4 + body.len());
+    frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&body);
+    w.write_all(&frame)?;
+    w.flush()?;
+    Ok(())
+}
+
+/// Read one envelope frame.
+pub fn recv<R: Read>(r: &mut R) -> Result<Envelope, UdsError> {
+    recv_with_limit(r, MAX_FRAME_BYTES)
+}
+
+/// Read one envelope frame of at most `max` bytes.
+pub fn recv_with_limit<R: Read>(r: &mut R, max: usize) -> Result<Envelope, UdsError> {
+    let mut len = [0_u8; 4];
+    let mut filled = 0;
+    while filled < len.len() {
+        match r.read(&mut len[filled..]) {
+            Ok(0) if filled == 0 => return Err(UdsError::Closed),
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let len = u32::from_be_bytes(len) as usize;
+    if len > max {
+        return Err(UdsError::FrameTooLarge { len, max });
+    }
+    let mut body = vec![0_u8; len];
+    r.read_exact(&mut body)?;
+    rmp_serde::from_slice(&body).map_err(|e| UdsError::Decode(e.to_string()))
+}
+
+/// Send `req` and read its reply, checking the corr_id; an error frame becomes `UdsError::Remote`.
+pub fn call(stream: &mut UnixStream, req: &Envelope) -> Result<Envelope, UdsError> {
+    send(stream, req)?;
+    let reply = recv(stream)?;
+    if reply.corr_id != req.corr_id {
+        return Err(UdsError::CorrelationMismatch {
+            expected: req.corr_id,
+            got: reply.corr_id,
+        });
+    }
+    if let Some(err) = reply.error {
+        return Err(UdsError::Remote(err));
+    }
+    Ok(reply)
+}
+
+/// Typed client for one service over one connection; assigns corr_ids.
+#[derive(Debug)]
+pub struct Client {
+    stream: UnixStream,
+    service: String,
+    token: Vec<u8>,
+    next_corr: u64,
+}
+
+impl Client {
+    /// Connect to the socket at `path` serving `service`.
+    pub fn connect(path: impl AsRef<Path>, service: impl Into<String>) -> Result<Self, UdsError> {
+        Ok(Self::new(UnixStream::connect(path)?, service))
+    }
+
+    /// Wrap an already-connected stream.
+    pub fn new(stream: UnixStream, service: impl Into<String>) -> Self {
+        Self {
+            stream,
+            service: service.into(),
+            token: Vec::new(),
+            next_corr: 1,
+        }
+    }
+
+    /// Send `token` with every request.
+    pub fn with_token(mut self, token: Vec<u8>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Call `method` with `req` and decode the reply as `Resp`.
+    pub fn call<Req: Serialize, Resp: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        req: &Req,
+    ) -> Result<Resp, UdsError> {
+        let corr_id = self.next_corr;
+        self.next_corr = self.next_corr.wrapping_add(1);
+        let env = Envelope::request(self.service.clone(), method, corr_id, req)?
+            .with_token(self.token.clone());
+        call(&mut self.stream, &env)?.decode()
+    }
+}
+
+/// Credentials of the process on the other end of a socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerCred {
+    /// Peer process id.
+    pub pid: i32,
+    /// Peer effective user id.
+    pub uid: u32,
+    /// Peer effective group id.
+    pub gid: u32,
+}
+
+/// Credentials of the peer of `stream` (SO_PEERCRED).
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn peer_cred(stream: &UnixStream) -> io::Result<PeerCred> {
+    let cred = rustix::net::sockopt::socket_peercred(stream)?;
+    Ok(PeerCred {
+        pid: cred.pid.as_raw_nonzero().get(),
+        uid: cred.uid.as_raw(),
+        gid: cred.gid.as_raw(),
+    })
+}
+
+/// Credentials of the peer of `stream` (unsupported on this platform).
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn peer_cred(_stream: &UnixStream) -> io::Result<PeerCred> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "peer credentials are only available on Linux",
+    ))
+}
+
+/// Which peers a server accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerPolicy {
+    allow_uids: Vec<u32>,
+}
+
+impl Default for PeerPolicy {
+    /// Only the uid this process runs as.
+    fn default() -> Self {
+        Self {
+            allow_uids: vec![rustix::process::getuid().as_raw()],
+        }
+    }
+}
+
+impl PeerPolicy {
+    /// Accept exactly these uids.
+    pub fn allow_uids(uids: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            allow_uids: uids.into_iter().collect(),
+        }
+    }
+
+    /// Check the peer of `stream`; fails closed when credentials are unavailable.
+    pub fn check(&self, stream: &UnixStream) -> Result<PeerCred, UdsError> {
+        let cred = peer_cred(stream)?;
+        if !self.allow_uids.contains(&cred.uid) {
+            return Err(UdsError::PeerRejected { uid: cred.uid });
+        }
+        Ok(cred)
+    }
+}
+
+/// Bind a service socket at `path`: parent dir created 0700, stale socket replaced, socket 0600.
+pub fn listen(path: impl AsRef<Path>) -> io::Result<UnixListener> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        if !dir.exists() {
+            fs::create_dir_all(dir)?;
+            fs::set_permissions(dir, fs::Permissions::from_mode(0o700))?;
+        }
+    }
+    match fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let listener = UnixListener::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// Serve one connection: check the peer, then answer each request with `handler` until the peer hangs up.
+///
+/// Replies are forced to carry the request's corr_id. A handler error is answered with an error frame and
+/// the connection stays open; only transport errors end it. Returns `Ok(())` on a clean close.
+pub fn serve_conn<F>(
+    mut stream: UnixStream,
+    policy: &PeerPolicy,
+    mut handler: F,
+) -> Result<(), UdsError>
+where
+    F: FnMut(&Envelope) -> Result<Envelope, UdsError>,
+{
+    policy.check(&stream)?;
+    loop {
+        let req = match recv(&mut stream) {
+            Ok(req) => req,
+            Err(UdsError::Closed) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let mut reply = handler(&req).unwrap_or_else(|e| req.error_reply(e.to_string()));
+        reply.corr_id = req.corr_id;
+        send(&mut stream, &reply)?;
+    }
+}
@@ -0,0 +1,158 @@
+// RO:WHAT — UDS RPC transport: framing round-trip, typed client/server, frame caps, corr_id checks, peer policy.
+// RO:WHY  — ronctl and local services depend on this wire format; lock it in.
+// RO:INTERACTS — api::{Envelope, IndexReq, IndexResp}, uds::{send, recv, Client, listen, serve_conn, PeerPolicy}.
+// RO:INVARIANTS — u32 BE length + MessagePack; cap checked before allocation; replies echo corr_id.
+#![cfg(feature = "uds")]
+
+use std::{
+    io::{Cursor, Write},
+    os::unix::{fs::PermissionsExt, net::UnixStream},
+    thread,
+};
+
+use ron_bus::api::{Envelope, IndexReq, IndexResp, SVC_INDEX};
+use ron_bus::uds::{self, Client, PeerPolicy, UdsError};
+
+fn index_handler(req: &Envelope) -> Result<Envelope, UdsError> {
+    let resp = match req.decode::<IndexReq>()? {
+        IndexReq::Health => IndexResp::HealthOk,
+        IndexReq::Resolve { addr } if addr == "b3:00.txt" => IndexResp::Resolved {
+            dir: "/bundles/00".into(),
+        },
+        IndexReq::Resolve { .. } => IndexResp::NotFound,
+        IndexReq::PutAddress { .. } => IndexResp::PutOk,
+        _ => IndexResp::Err {
+            err: "unsupported".into(),
+        },
+    };
+    req.reply(&resp)
+}
+
+#[test]
+fn envelope_roundtrips_over_a_socket_pair() {
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    let req = Envelope::request(SVC_INDEX, "v1.health", 7, &IndexReq::Health)
+        .unwrap()
+        .with_token(vec![1, 2, 3]);
+    uds::send(&mut a, &req).unwrap();
+    let got = uds::recv(&mut b).unwrap();
+    assert_eq!(got, req);
+    assert_eq!(got.decode::<IndexReq>().unwrap(), IndexReq::Health);
+}
+
+#[test]
+fn client_talks_to_a_listening_server() {
+    let dir = tempfile::tempdir().unwrap();
+    let sock = dir.path().join("run").join("svc-index.sock");
+    let listener = uds::listen(&sock).unwrap();
+    assert_eq!(
+        std::fs::metadata(&sock).unwrap().permissions().mode() & 0o777,
+        0o600
+    );
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        uds::serve_conn(stream, &PeerPolicy::default(), index_handler)
+    });
+
+    let mut client = Client::connect(&sock, SVC_INDEX).unwrap();
+    let health: IndexResp = client.call("v1.health", &IndexReq::Health).unwrap();
+    assert_eq!(health, IndexResp::HealthOk);
+    let resolved: IndexResp = client
+        .call(
+            "v1.resolve",
+            &IndexReq::Resolve {
+                addr: "b3:00.txt".into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        resolved,
+        IndexResp::Resolved {
+            dir: "/bundles/00".into()
+        }
+    );
+    drop(client);
+    // Hanging up between frames is a clean close.
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn oversized_frames_are_refused_before_reading_the_body() {
+    let mut wire = Vec::new();
+    wire.extend_from_slice(&(64_u32 * 1024 * 1024).to_be_bytes());
+    match uds::recv(&mut Cursor::new(wire)) {
+        Err(UdsError::FrameTooLarge { len, max }) => {
+            assert_eq!(len, 64 * 1024 * 1024);
+            assert_eq!(max, uds::MAX_FRAME_BYTES);
+        }
+        other => panic!("expected FrameTooLarge, got {other:?}"),
+    }
+
+    let big = Envelope::request(SVC_INDEX, "v1.put_address", 1, &vec![0_u8; 256]).unwrap();
+    assert!(matches!(
+        uds::send_with_limit(&mut Vec::new(), &big, 64),
+        Err(UdsError::FrameTooLarge { max: 64, .. })
+    ));
+}
+
+#[test]
+fn replies_with_the_wrong_corr_id_are_rejected() {
+    let (mut client, mut server) = UnixStream::pair().unwrap();
+    let t = thread::spawn(move || {
+        let req = uds::recv(&mut server).unwrap();
+        let mut reply = req.reply(&IndexResp::HealthOk).unwrap();
+        reply.corr_id += 1;
+        uds::send(&mut server, &reply).unwrap();
+    });
+    let req = Envelope::request(SVC_INDEX, "v1.health", 41, &IndexReq::Health).unwrap();
+    match uds::call(&mut client, &req) {
+        Err(UdsError::CorrelationMismatch { expected, got }) => {
+            assert_eq!((expected, got), (41, 42));
+        }
+        other => panic!("expected CorrelationMismatch, got {other:?}"),
+    }
+    t.join().unwrap();
+}
+
+#[test]
+fn handler_errors_become_error_frames_and_the_connection_stays_open() {
+    let (client, server) = UnixStream::pair().unwrap();
+    let server =
+        thread::spawn(move || uds::serve_conn(server, &PeerPolicy::default(), index_handler));
+
+    let mut client = Client::new(client, SVC_INDEX);
+    // A body that is not an `IndexReq` fails to decode inside the handler.
+    match client.call::<_, IndexResp>("v1.resolve", &"not a request") {
+        Err(UdsError::Remote(msg)) => assert!(msg.contains("decode"), "{msg}"),
+        other => panic!("expected Remote, got {other:?}"),
+    }
+    let health: IndexResp = client.call("v1.health", &IndexReq::Health).unwrap();
+    assert_eq!(health, IndexResp::HealthOk);
+    drop(client);
+    server.join().unwrap().unwrap();
+}
+
+#[test]
+fn peers_outside_the_policy_are_rejected() {
+    let (server, _client) = UnixStream::pair().unwrap();
+    let me = uds::peer_cred(&server).unwrap();
+    assert_eq!(me.uid, PeerPolicy::default().check(&server).unwrap().uid);
+
+    let other = me.uid.wrapping_add(1);
+    let err = uds::serve_conn(server, &PeerPolicy::allow_uids([other]), index_handler).unwrap_err();
+    assert!(matches!(err, UdsError::PeerRejected { uid } if uid == me.uid));
+}
+
+#[test]
+fn eof_mid_frame_is_an_error_but_eof_between_frames_is_closed() {
+    assert!(matches!(
+        uds::recv(&mut Cursor::new(Vec::new())),
+        Err(UdsError::Closed)
+    ));
+
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    a.write_all(&[0, 0, 0, 10, 1, 2]).unwrap();
+    drop(a);
+    assert!(matches!(uds::recv(&mut b), Err(UdsError::Io(_))));
+}
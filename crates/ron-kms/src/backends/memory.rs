@@ -126,6 +126,7 @@ impl Keystore for MemoryKeystore {
             current_version: root.current_version,
             versions,
             created_ms: root.created_ms,
+            public_key: root.vk.to_bytes().to_vec(),
         })
     }
 }
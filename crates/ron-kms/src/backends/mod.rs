@@ -20,6 +20,11 @@ pub use crate::backends::fast_ring::{
 pub mod memory;
 pub use memory::MemoryKeystore;
 
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
+#[cfg(feature = "pkcs11")]
+pub use pkcs11::Pkcs11Keystore;
+
 /// Stable adapter so benches/tests can `use ron_kms::backends::ed25519`.
 /// It forwards to the currently selected backend (dalek or ring).
 pub mod ed25519 {
@@ -1 +1,368 @@
-// PKCS#11 backend scaffold (feature-gated in real impl)
+// RO:WHAT  PKCS#11 keystore: key custody on any Cryptoki token (HSM, YubiHSM, SoftHSM for dev/CI).
+// RO:INV   Private keys are generated on-token as sensitive + non-extractable and never leave it;
+//          only the latest version can sign; every retained public key can verify.
+// RO:NOTES Object layout per key root `<tenant>/<purpose>/<alg>/<uuid>`:
+//          - key pair per version: CKA_ID = root, CKA_LABEL = full `KeyId` (`…#vN`);
+//          - rotation generates vN+1 and destroys the vN private key (public keys stay for verify);
+//          - one CKO_DATA object (CKA_APPLICATION = "ron-kms", CKA_LABEL = root) holds `created_ms`.
+//          The token is the source of truth, so keys survive process restarts.
+
+use cryptoki::{
+    context::{CInitializeArgs, CInitializeFlags, Pkcs11},
+    error::{Error, RvError},
+    mechanism::{
+        eddsa::{EddsaParams, EddsaSignatureScheme},
+        Mechanism,
+    },
+    object::{Attribute, AttributeType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    slot::Slot,
+    types::AuthPin,
+};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    config::Pkcs11Config,
+    error::KmsError,
+    traits::pubkey::PubkeyProvider,
+    traits::{Keystore, Signer, Verifier},
+    types::{Alg, KeyId, KeyMeta},
+    util::time::now_utc_ms,
+};
+
+/// DER OID 1.3.101.112 (id-Ed25519) for `CKA_EC_PARAMS`.
+const ED25519_PARAMS: &[u8] = &[0x06, 0x03, 0x2b, 0x65, 0x70];
+/// DER OID 1.2.840.10045.3.1.7 (prime256v1) for `CKA_EC_PARAMS`.
+const P256_PARAMS: &[u8] = &[0x06, 0x08, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+/// `CKA_APPLICATION` of the per-root metadata object.
+const META_APP: &[u8] = b"ron-kms";
+
+pub struct Pkcs11Keystore {
+    // Sessions are Send but not Sync; one logged-in R/W session serializes token calls.
+    session: Mutex<Session>,
+}
+
+impl Pkcs11Keystore {
+    /// Load the module, pick the token labelled `slot_label`, and log in with the PIN from `pin_env`.
+    pub fn open(cfg: &Pkcs11Config) -> Result<Self, KmsError> {
+        let pin = cfg.pin()?;
+        let ctx = Pkcs11::new(&cfg.module_path)
+            .map_err(|_| KmsError::Internal("pkcs11 module load failed"))?;
+        match ctx.initialize(CInitializeArgs::new(CInitializeFlags::OS_LOCKING_OK)) {
+            // Another keystore in this process already initialized the module.
+            Ok(()) | Err(Error::Pkcs11(RvError::CryptokiAlreadyInitialized, _)) => {}
+            Err(e) => return Err(map_err(e)),
+        }
+        let slot = find_slot(&ctx, &cfg.slot_label)?;
+        let session = ctx.open_rw_session(slot).map_err(map_err)?;
+        let auth = AuthPin::from(pin.as_str());
+        match session.login(UserType::User, Some(&auth)) {
+            // Login state is per application, shared by all sessions on the token.
+            Ok(()) | Err(Error::Pkcs11(RvError::UserAlreadyLoggedIn, _)) => {}
+            Err(Error::Pkcs11(
+                RvError::PinIncorrect | RvError::PinLocked | RvError::PinExpired,
+                _,
+            )) => return Err(KmsError::CapabilityMissing),
+            Err(e) => return Err(map_err(e)),
+        }
+        Ok(Self {
+            session: Mutex::new(session),
+        })
+    }
+
+    /// Public key bytes for `kid`'s version: raw 32 bytes (Ed25519) or SEC1 uncompressed (P-256).
+    pub fn public_key(&self, kid: &KeyId) -> Result<Vec<u8>, KmsError> {
+        let s = self.session.lock();
+        let h = public_handle(&s, kid)?;
+        ec_point(&s, h, kid.alg)
+    }
+
+    fn root_id(kid: &KeyId) -> Vec<u8> {
+        format!("{}/{}/{}/{}", kid.tenant, kid.purpose, kid.alg, kid.uuid).into_bytes()
+    }
+}
+
+impl Keystore for Pkcs11Keystore {
+    fn create_ed25519(&self, tenant: &str, purpose: &str) -> Result<KeyId, KmsError> {
+        self.create(tenant, purpose, Alg::Ed25519)
+    }
+
+    fn create(&self, tenant: &str, purpose: &str, alg: Alg) -> Result<KeyId, KmsError> {
+        let kid = KeyId::new(tenant, purpose, alg);
+        let root = Self::root_id(&kid);
+        let s = self.session.lock();
+        let (public, private) = generate(&s, &kid, &root)?;
+        let meta = s.create_object(&[
+            Attribute::Class(ObjectClass::DATA),
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Application(META_APP.to_vec()),
+            Attribute::Label(root),
+            Attribute::Value(now_utc_ms().to_be_bytes().to_vec()),
+        ]);
+        if let Err(e) = meta {
+            // Without its metadata the pair is unreachable (`created_ms` fails); don't leave it on
+            // the token. A failed destroy here is ignored in favour of the original error.
+            let _ = s.destroy_object(private);
+            let _ = s.destroy_object(public);
+            return Err(map_err(e));
+        }
+        Ok(kid)
+    }
+
+    fn rotate(&self, kid: &KeyId) -> Result<KeyId, KmsError> {
+        let root = Self::root_id(kid);
+        let s = self.session.lock();
+        let (current, old) = signing_handle(&s, &root)?;
+        let mut new = kid.clone();
+        new.version = current.saturating_add(1);
+        generate(&s, &new, &root)?;
+        // A failed destroy leaves two private keys; `signing_handle` still picks the newest.
+        s.destroy_object(old).map_err(map_err)?;
+        Ok(new)
+    }
+
+    fn alg(&self, kid: &KeyId) -> Result<Alg, KmsError> {
+        let s = self.session.lock();
+        if public_versions(&s, &Self::root_id(kid))?.is_empty() {
+            return Err(KmsError::NoSuchKey);
+        }
+        Ok(kid.alg)
+    }
+
+    fn meta(&self, kid: &KeyId) -> Result<KeyMeta, KmsError> {
+        let root = Self::root_id(kid);
+        let s = self.session.lock();
+        let (current_version, _) = signing_handle(&s, &root)?;
+        let publics = public_versions(&s, &root)?;
+        let current_pub = publics
+            .iter()
+            .find(|(v, _)| *v == current_version)
+            .map(|(_, h)| *h)
+            .ok_or(KmsError::Internal("pkcs11 public key missing"))?;
+        Ok(KeyMeta {
+            alg: kid.alg,
+            current_version,
+            versions: publics.iter().map(|(v, _)| *v).collect(),
+            created_ms: created_ms(&s, &root)?,
+            public_key: ec_point(&s, current_pub, kid.alg)?,
+        })
+    }
+}
+
+impl Signer for Pkcs11Keystore {
+    fn sign(&self, kid: &KeyId, msg: &[u8]) -> Result<Vec<u8>, KmsError> {
+        let s = self.session.lock();
+        let (current, h) = signing_handle(&s, &Self::root_id(kid))?;
+        // Only the latest version can sign: rotation destroyed every older private key.
+        if kid.version != current {
+            return Err(KmsError::NoSuchKey);
+        }
+        match kid.alg {
+            Alg::Ed25519 => s.sign(&eddsa(), h, msg),
+            Alg::EcdsaP256 => s.sign(&Mechanism::Ecdsa, h, &Sha256::digest(msg)),
+        }
+        .map_err(map_err)
+    }
+}
+
+impl Verifier for Pkcs11Keystore {
+    fn verify(&self, kid: &KeyId, msg: &[u8], sig: &[u8]) -> Result<bool, KmsError> {
+        let s = self.session.lock();
+        let h = public_handle(&s, kid)?;
+        let res = match kid.alg {
+            Alg::Ed25519 => s.verify(&eddsa(), h, msg, sig),
+            Alg::EcdsaP256 => s.verify(&Mechanism::Ecdsa, h, &Sha256::digest(msg), sig),
+        };
+        match res {
+            Ok(()) => Ok(true),
+            Err(Error::Pkcs11(RvError::SignatureInvalid | RvError::SignatureLenRange, _)) => {
+                Ok(false)
+            }
+            Err(e) => Err(map_err(e)),
+        }
+    }
+}
+
+impl PubkeyProvider for Pkcs11Keystore {
+    fn verifying_key_bytes(&self, kid: &KeyId) -> Result<[u8; 32], KmsError> {
+        if kid.alg != Alg::Ed25519 {
+            return Err(KmsError::AlgUnavailable);
+        }
+        let pk = self.public_key(kid)?;
+        pk.try_into()
+            .map_err(|_| KmsError::Internal("pkcs11 ed25519 point length"))
+    }
+}
+
+fn find_slot(ctx: &Pkcs11, label: &str) -> Result<Slot, KmsError> {
+    for slot in ctx.get_slots_with_token().map_err(map_err)? {
+        if ctx
+            .get_token_info(slot)
+            .map_err(map_err)?
+            .label()
+            .trim_end()
+            == label
+        {
+            return Ok(slot);
+        }
+    }
+    Err(KmsError::Internal("pkcs11 token label not found"))
+}
+
+fn eddsa() -> Mechanism<'static> {
+    // Null parameters = pure Ed25519; the most widely supported CKM_EDDSA form.
+    Mechanism::Eddsa(EddsaParams::new(EddsaSignatureScheme::Pure))
+}
+
+/// Generate `kid`'s key pair under `root`; returns the (public, private) handles.
+fn generate(
+    s: &Session,
+    kid: &KeyId,
+    root: &[u8],
+) -> Result<(ObjectHandle, ObjectHandle), KmsError> {
+    let (mech, params) = match kid.alg {
+        Alg::Ed25519 => (Mechanism::EccEdwardsKeyPairGen, ED25519_PARAMS),
+        Alg::EcdsaP256 => (Mechanism::EccKeyPairGen, P256_PARAMS),
+    };
+    let label = kid.to_string().into_bytes();
+    let public = [
+        Attribute::Token(true),
+        Attribute::Private(false),
+        Attribute::Verify(true),
+        Attribute::EcParams(params.to_vec()),
+        Attribute::Id(root.to_vec()),
+        Attribute::Label(label.clone()),
+    ];
+    let private = [
+        Attribute::Token(true),
+        Attribute::Private(true),
+        Attribute::Sensitive(true),
+        Attribute::Extractable(false),
+        Attribute::Sign(true),
+        Attribute::Id(root.to_vec()),
+        Attribute::Label(label),
+    ];
+    s.generate_key_pair(&mech, &public, &private)
+        .map_err(map_err)
+}
+
+/// Version parsed from an object's `CKA_LABEL` (`KeyId` display form).
+fn version_of(s: &Session, h: ObjectHandle) -> Result<u32, KmsError> {
+    let attrs = s
+        .get_attributes(h, &[AttributeType::Label])
+        .map_err(map_err)?;
+    let Some(Attribute::Label(label)) = attrs.into_iter().next() else {
+        return Err(KmsError::Internal("pkcs11 object without label"));
+    };
+    let kid: KeyId = std::str::from_utf8(&label)
+        .ok()
+        .and_then(|l| l.parse().ok())
+        .ok_or(KmsError::Internal("pkcs11 foreign object label"))?;
+    Ok(kid.version)
+}
+
+fn versions_of(
+    s: &Session,
+    class: ObjectClass,
+    root: &[u8],
+) -> Result<Vec<(u32, ObjectHandle)>, KmsError> {
+    let handles = s
+        .find_objects(&[Attribute::Class(class), Attribute::Id(root.to_vec())])
+        .map_err(map_err)?;
+    let mut out = handles
+        .into_iter()
+        .map(|h| version_of(s, h).map(|v| (v, h)))
+        .collect::<Result<Vec<_>, _>>()?;
+    out.sort_unstable_by_key(|(v, _)| *v);
+    Ok(out)
+}
+
+/// All retained public keys of a root, ascending by version.
+fn public_versions(s: &Session, root: &[u8]) -> Result<Vec<(u32, ObjectHandle)>, KmsError> {
+    versions_of(s, ObjectClass::PUBLIC_KEY, root)
+}
+
+/// The newest private key of a root.
+fn signing_handle(s: &Session, root: &[u8]) -> Result<(u32, ObjectHandle), KmsError> {
+    versions_of(s, ObjectClass::PRIVATE_KEY, root)?
+        .pop()
+        .ok_or(KmsError::NoSuchKey)
+}
+
+fn public_handle(s: &Session, kid: &KeyId) -> Result<ObjectHandle, KmsError> {
+    s.find_objects(&[
+        Attribute::Class(ObjectClass::PUBLIC_KEY),
+        Attribute::Label(kid.to_string().into_bytes()),
+    ])
+    .map_err(map_err)?
+    .into_iter()
+    .next()
+    .ok_or(KmsError::NoSuchKey)
+}
+
+fn created_ms(s: &Session, root: &[u8]) -> Result<i128, KmsError> {
+    let h = s
+        .find_objects(&[
+            Attribute::Class(ObjectClass::DATA),
+            Attribute::Application(META_APP.to_vec()),
+            Attribute::Label(root.to_vec()),
+        ])
+        .map_err(map_err)?
+        .into_iter()
+        .next()
+        .ok_or(KmsError::Internal("pkcs11 key metadata missing"))?;
+    let attrs = s
+        .get_attributes(h, &[AttributeType::Value])
+        .map_err(map_err)?;
+    match attrs.into_iter().next() {
+        Some(Attribute::Value(v)) => v
+            .try_into()
+            .map(i128::from_be_bytes)
+            .map_err(|_| KmsError::Internal("pkcs11 key metadata malformed")),
+        _ => Err(KmsError::Internal("pkcs11 key metadata malformed")),
+    }
+}
+
+/// `CKA_EC_POINT` with the DER OCTET STRING wrapper removed (tokens differ on whether they add it).
+fn ec_point(s: &Session, h: ObjectHandle, alg: Alg) -> Result<Vec<u8>, KmsError> {
+    let attrs = s
+        .get_attributes(h, &[AttributeType::EcPoint])
+        .map_err(map_err)?;
+    let Some(Attribute::EcPoint(point)) = attrs.into_iter().next() else {
+        return Err(KmsError::Internal("pkcs11 public key without EC point"));
+    };
+    let len = match alg {
+        Alg::Ed25519 => 32,
+        Alg::EcdsaP256 => 65,
+    };
+    match point.len() {
+        n if n == len => Ok(point),
+        n if n == len + 2 && point[0] == 0x04 && usize::from(point[1]) == len => {
+            Ok(point[2..].to_vec())
+        }
+        _ => Err(KmsError::Internal("pkcs11 EC point length")),
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)] // `.map_err(map_err)` adapter
+fn map_err(e: Error) -> KmsError {
+    match e {
+        Error::Pkcs11(
+            RvError::MechanismInvalid | RvError::CurveNotSupported | RvError::DomainParamsInvalid,
+            _,
+        ) => KmsError::AlgUnavailable,
+        Error::Pkcs11(RvError::RandomNoRng, _) => KmsError::Entropy,
+        Error::Pkcs11(RvError::UserNotLoggedIn, _) => KmsError::CapabilityMissing,
+        Error::Pkcs11(
+            RvError::DeviceRemoved
+            | RvError::DeviceError
+            | RvError::TokenNotPresent
+            | RvError::SessionClosed
+            | RvError::SessionHandleInvalid,
+            _,
+        ) => KmsError::Internal("pkcs11 token unavailable"),
+        _ => KmsError::Internal("pkcs11 call failed"),
+    }
+}
@@ -1,5 +1,81 @@
-// Config scaffold (no IO yet)
-pub struct KmsConfig;
+// RO:WHAT  Backend configuration: `[backends.*]` sections and their `RON_KMS_*` env overrides.
+// RO:INV   Secrets are never config values — the PKCS#11 PIN is read from the env var named by
+//          `pin_env` at open time and zeroized after login.
+
+use std::{env, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::error::KmsError;
+
+/// Default token label looked up when no slot is configured explicitly.
+pub const DEFAULT_SLOT_LABEL: &str = "RON-KMS";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KmsConfig {
+    #[serde(default)]
+    pub backends: Backends,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Backends {
+    /// Present when a PKCS#11 module is configured.
+    #[serde(default)]
+    pub pkcs11: Option<Pkcs11Config>,
+}
+
+/// `[backends.pkcs11]` — see docs/CONFIG.MD §3.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pkcs11Config {
+    /// Vendor module, e.g. `/usr/lib/softhsm/libsofthsm2.so`.
+    pub module_path: PathBuf,
+    /// Label of the token to use (the first slot whose token carries it).
+    #[serde(default = "default_slot_label")]
+    pub slot_label: String,
+    /// Name of the environment variable holding the user PIN.
+    #[serde(default)]
+    pub pin_env: String,
+}
+
+fn default_slot_label() -> String {
+    DEFAULT_SLOT_LABEL.to_string()
+}
+
 impl KmsConfig {
-    pub fn load() -> Self { Self }
+    /// Build from `RON_KMS_*` env vars; the PKCS#11 backend is configured iff
+    /// `RON_KMS_PKCS11_MODULE_PATH` is set and non-empty.
+    #[must_use]
+    pub fn load() -> Self {
+        let pkcs11 = non_empty_var("RON_KMS_PKCS11_MODULE_PATH").map(|module_path| Pkcs11Config {
+            module_path: PathBuf::from(module_path),
+            slot_label: non_empty_var("RON_KMS_PKCS11_SLOT_LABEL")
+                .unwrap_or_else(default_slot_label),
+            pin_env: non_empty_var("RON_KMS_PKCS11_PIN_ENV").unwrap_or_default(),
+        });
+        Self {
+            backends: Backends { pkcs11 },
+        }
+    }
+}
+
+impl Pkcs11Config {
+    /// Read the user PIN from the env var named by `pin_env` (fail-closed when unset).
+    pub fn pin(&self) -> Result<Zeroizing<String>, KmsError> {
+        if self.pin_env.is_empty() {
+            return Err(KmsError::Internal("pkcs11 pin_env not configured"));
+        }
+        env::var(&self.pin_env)
+            .ok()
+            .filter(|p| !p.is_empty())
+            .map(Zeroizing::new)
+            .ok_or(KmsError::Internal("pkcs11 PIN env var unset"))
+    }
+}
+
+fn non_empty_var(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|v| !v.is_empty())
 }
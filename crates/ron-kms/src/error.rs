@@ -16,10 +16,14 @@ pub enum KmsError {
     CapabilityMissing,
     #[error("rotation in progress; try again")]
     Busy,
+    #[error("decapsulation or unwrap failed")]
+    UnwrapFailed,
     #[error("internal error: {0}")]
     Internal(&'static str),
 }
 
+pub type KmResult<T> = Result<T, KmsError>;
+
 impl KmsError {
     #[must_use]
     pub fn kind(&self) -> &'static str {
@@ -31,6 +35,7 @@ impl KmsError {
             KmsError::VerifyFailed => "VerifyFailed",
             KmsError::CapabilityMissing => "CapabilityMissing",
             KmsError::Busy => "Busy",
+            KmsError::UnwrapFailed => "UnwrapFailed",
             KmsError::Internal(_) => "Internal",
         }
     }
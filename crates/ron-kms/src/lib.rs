@@ -3,8 +3,10 @@
 #![allow(clippy::missing_errors_doc, clippy::module_name_repetitions)]
 
 pub mod backends;
+pub mod config;
 pub mod error;
 pub mod ops;
+pub mod pq;
 pub mod traits;
 pub mod types;
 pub mod util;
@@ -0,0 +1,183 @@
+// RO:WHAT  Hybrid X25519 + ML-KEM-768 KEM and DEK wrapping (feature `pq-hybrid`).
+// RO:WHY   Confidentiality holds while either X25519 or ML-KEM stays unbroken (harvest-now-decrypt-later).
+// RO:INV   KEK = BLAKE3-derive_key(ctx, x25519_ss || mlkem_ss || kem_ct || recipient_pub); wrap = KEM ct || soft-seal.
+// RO:FORMAT public = [X25519:32][ML-KEM-768 ek:1184]; kem ct = [EPH_X25519:32][ML-KEM ct:1088].
+// RO:SECRET [X25519 secret:32][ML-KEM seed:64]; wiped on drop.
+
+use rand::rngs::OsRng;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+use super::mlkem::{self, MlKem768Keypair};
+use crate::error::{KmResult, KmsError};
+use crate::traits::{Hybrid, Kem};
+
+/// BLAKE3 `derive_key` context for the combined shared secret.
+const KDF_CONTEXT: &str = "ron-kms 2025 hybrid x25519+mlkem768 kek v1";
+
+/// Encoded hybrid public key length.
+pub const PUBLIC_KEY_LEN: usize = 32 + mlkem::PUBLIC_KEY_LEN;
+/// Hybrid KEM ciphertext length.
+pub const CIPHERTEXT_LEN: usize = 32 + mlkem::CIPHERTEXT_LEN;
+/// Encoded secret key length.
+pub const SECRET_KEY_LEN: usize = 32 + mlkem::SEED_LEN;
+
+/// Public half of a hybrid key, safe to hand to writers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HybridPublicKey {
+    pub x25519: [u8; 32],
+    pub mlkem: Vec<u8>,
+}
+
+impl HybridPublicKey {
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(PUBLIC_KEY_LEN);
+        out.extend_from_slice(&self.x25519);
+        out.extend_from_slice(&self.mlkem);
+        out
+    }
+
+    pub fn from_bytes(raw: &[u8]) -> KmResult<Self> {
+        if raw.len() != PUBLIC_KEY_LEN {
+            return Err(KmsError::UnwrapFailed);
+        }
+        let (x, m) = raw.split_at(32);
+        let mut x25519 = [0u8; 32];
+        x25519.copy_from_slice(x);
+        Ok(Self {
+            x25519,
+            mlkem: m.to_vec(),
+        })
+    }
+
+    /// Stable BLAKE3 fingerprint of the encoded public key (used as a key hint).
+    #[must_use]
+    pub fn fingerprint(&self) -> [u8; 32] {
+        *blake3::hash(&self.to_bytes()).as_bytes()
+    }
+}
+
+/// Hybrid X25519 + ML-KEM-768 keypair.
+pub struct HybridKeypair {
+    x_secret: StaticSecret,
+    mlkem: MlKem768Keypair,
+    public: HybridPublicKey,
+}
+
+impl HybridKeypair {
+    #[must_use]
+    pub fn generate() -> Self {
+        Self::assemble(
+            StaticSecret::random_from_rng(OsRng),
+            MlKem768Keypair::generate(),
+        )
+    }
+
+    /// Restore a keypair from `secret_bytes()` output.
+    pub fn from_secret_bytes(raw: &[u8]) -> KmResult<Self> {
+        if raw.len() != SECRET_KEY_LEN {
+            return Err(KmsError::UnwrapFailed);
+        }
+        let mut x = Zeroizing::new([0u8; 32]);
+        x.copy_from_slice(&raw[..32]);
+        let mut seed = Zeroizing::new([0u8; mlkem::SEED_LEN]);
+        seed.copy_from_slice(&raw[32..]);
+        Ok(Self::assemble(
+            StaticSecret::from(*x),
+            MlKem768Keypair::from_seed(&seed),
+        ))
+    }
+
+    fn assemble(x_secret: StaticSecret, mlkem: MlKem768Keypair) -> Self {
+        let public = HybridPublicKey {
+            x25519: PublicKey::from(&x_secret).to_bytes(),
+            mlkem: mlkem.public_bytes(),
+        };
+        Self {
+            x_secret,
+            mlkem,
+            public,
+        }
+    }
+
+    /// Secret key encoding; keep it out of logs and off shared disks.
+    #[must_use]
+    pub fn secret_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut out = Zeroizing::new(Vec::with_capacity(SECRET_KEY_LEN));
+        out.extend_from_slice(self.x_secret.as_bytes());
+        out.extend_from_slice(self.mlkem.seed());
+        out
+    }
+
+    #[must_use]
+    pub fn public_key(&self) -> &HybridPublicKey {
+        &self.public
+    }
+}
+
+fn combine(x_ss: &[u8], m_ss: &[u8], ct: &[u8], recipient: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut hasher = blake3::Hasher::new_derive_key(KDF_CONTEXT);
+    hasher.update(x_ss);
+    hasher.update(m_ss);
+    hasher.update(ct);
+    hasher.update(recipient);
+    Zeroizing::new(*hasher.finalize().as_bytes())
+}
+
+impl Kem for HybridKeypair {
+    fn encap(&self, peer_pub: &[u8]) -> KmResult<(Vec<u8>, Vec<u8>)> {
+        let peer = HybridPublicKey::from_bytes(peer_pub)?;
+        let eph = EphemeralSecret::random_from_rng(OsRng);
+        let eph_pub = PublicKey::from(&eph);
+        let x_ss = eph.diffie_hellman(&PublicKey::from(peer.x25519));
+        let (m_ct, m_ss) = self.mlkem.encap(&peer.mlkem)?;
+        let m_ss = Zeroizing::new(m_ss);
+
+        let mut ct = Vec::with_capacity(CIPHERTEXT_LEN);
+        ct.extend_from_slice(eph_pub.as_bytes());
+        ct.extend_from_slice(&m_ct);
+        let kek = combine(x_ss.as_bytes(), &m_ss, &ct, peer_pub);
+        Ok((ct, kek.to_vec()))
+    }
+
+    fn decap(&self, ct: &[u8]) -> KmResult<Vec<u8>> {
+        if ct.len() != CIPHERTEXT_LEN {
+            return Err(KmsError::UnwrapFailed);
+        }
+        let mut eph = [0u8; 32];
+        eph.copy_from_slice(&ct[..32]);
+        let x_ss = self.x_secret.diffie_hellman(&PublicKey::from(eph));
+        let m_ss = Zeroizing::new(self.mlkem.decap(&ct[32..])?);
+        let kek = combine(x_ss.as_bytes(), &m_ss, ct, &self.public.to_bytes());
+        Ok(kek.to_vec())
+    }
+}
+
+impl Hybrid for HybridKeypair {
+    /// `[KEM ct || soft-seal(KEK, pt, aad = KEM ct)]`, addressed to this keypair.
+    fn wrap(&self, pt: &[u8]) -> KmResult<Vec<u8>> {
+        let (mut out, kek) = self.encap(&self.public.to_bytes())?;
+        let kek = Zeroizing::new(kek);
+        let key: &[u8; 32] = kek
+            .as_slice()
+            .try_into()
+            .map_err(|_| KmsError::Internal("kek length"))?;
+        let sealed = crate::sealed::seal(key, pt, &out);
+        out.extend_from_slice(&sealed);
+        Ok(out)
+    }
+
+    fn unwrap_(&self, ct: &[u8]) -> KmResult<Vec<u8>> {
+        if ct.len() <= CIPHERTEXT_LEN {
+            return Err(KmsError::UnwrapFailed);
+        }
+        let (kem_ct, sealed) = ct.split_at(CIPHERTEXT_LEN);
+        let kek = Zeroizing::new(self.decap(kem_ct)?);
+        let key: &[u8; 32] = kek
+            .as_slice()
+            .try_into()
+            .map_err(|_| KmsError::Internal("kek length"))?;
+        crate::sealed::unseal(key, sealed, kem_ct).map_err(|_| KmsError::UnwrapFailed)
+    }
+}
@@ -1 +1,79 @@
-// ML-KEM scaffold (feature: mlkem)
+// RO:WHAT ML-KEM-768 (FIPS 203) keypair behind the `Kem` seam (feature `mlkem`).
+// RO:INV  Secret material is the 64-byte (d, z) seed, wiped on drop; keys and ciphertexts are fixed-size.
+// RO:INV  Decapsulation uses implicit rejection: a bad ciphertext yields an unrelated secret, never an error oracle.
+
+use ml_kem::array::Array;
+use ml_kem::kem::{Decapsulate, Encapsulate};
+use ml_kem::{Ciphertext, EncodedSizeUser, KemCore, MlKem768, B32};
+use rand::rngs::OsRng;
+use rand_core::RngCore;
+use zeroize::Zeroizing;
+
+use crate::error::{KmResult, KmsError};
+use crate::traits::Kem;
+
+type DecapKey = <MlKem768 as KemCore>::DecapsulationKey;
+type EncapKey = <MlKem768 as KemCore>::EncapsulationKey;
+
+/// Encoded encapsulation (public) key length.
+pub const PUBLIC_KEY_LEN: usize = 1184;
+/// Ciphertext length.
+pub const CIPHERTEXT_LEN: usize = 1088;
+/// Secret seed length (d || z).
+pub const SEED_LEN: usize = 64;
+
+pub struct MlKem768Keypair {
+    seed: Zeroizing<[u8; SEED_LEN]>,
+    dk: DecapKey,
+    ek: EncapKey,
+}
+
+impl MlKem768Keypair {
+    #[must_use]
+    pub fn generate() -> Self {
+        let mut seed = Zeroizing::new([0u8; SEED_LEN]);
+        OsRng.fill_bytes(&mut *seed);
+        Self::from_seed(&seed)
+    }
+
+    /// Deterministically expand a keypair from its (d, z) seed.
+    #[must_use]
+    pub fn from_seed(seed: &[u8; SEED_LEN]) -> Self {
+        let d = B32::from_fn(|i| seed[i]);
+        let z = B32::from_fn(|i| seed[32 + i]);
+        let (dk, ek) = MlKem768::generate_deterministic(&d, &z);
+        Self {
+            seed: Zeroizing::new(*seed),
+            dk,
+            ek,
+        }
+    }
+
+    #[must_use]
+    pub fn seed(&self) -> &[u8; SEED_LEN] {
+        &self.seed
+    }
+
+    #[must_use]
+    pub fn public_bytes(&self) -> Vec<u8> {
+        self.ek.as_bytes().to_vec()
+    }
+}
+
+impl Kem for MlKem768Keypair {
+    fn encap(&self, peer_pub: &[u8]) -> KmResult<(Vec<u8>, Vec<u8>)> {
+        let encoded = Array::try_from(peer_pub).map_err(|_| KmsError::UnwrapFailed)?;
+        let ek = EncapKey::from_bytes(&encoded);
+        let (ct, ss) = ek.encapsulate(&mut OsRng).map_err(|()| KmsError::Entropy)?;
+        Ok((ct.to_vec(), ss.to_vec()))
+    }
+
+    fn decap(&self, ct: &[u8]) -> KmResult<Vec<u8>> {
+        let ct = Ciphertext::<MlKem768>::try_from(ct).map_err(|_| KmsError::UnwrapFailed)?;
+        let ss = self
+            .dk
+            .decapsulate(&ct)
+            .map_err(|()| KmsError::UnwrapFailed)?;
+        Ok(ss.to_vec())
+    }
+}
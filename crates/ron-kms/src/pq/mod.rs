@@ -1,4 +1,12 @@
-// PQ adapters scaffold
-pub mod mlkem;
+// PQ adapters (ML-KEM live behind `mlkem`; signatures still scaffolds)
+#[cfg(feature = "pq-hybrid")]
+pub mod hybrid;
 pub mod mldsa;
+#[cfg(feature = "mlkem")]
+pub mod mlkem;
 pub mod slhdsa;
+
+#[cfg(feature = "pq-hybrid")]
+pub use hybrid::{HybridKeypair, HybridPublicKey};
+#[cfg(feature = "mlkem")]
+pub use mlkem::MlKem768Keypair;
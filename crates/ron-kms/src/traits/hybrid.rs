@@ -1,7 +1,8 @@
-// Hybrid trait scaffold
+// RO:WHAT Wrap/unwrap seam for data-encryption keys under a (hybrid) KEM key.
+// RO:INV  Wrapped output is self-contained (KEM ciphertext + AEAD); tamper => UnwrapFailed.
 use crate::error::KmResult;
 
 pub trait Hybrid {
-    fn wrap(&self, _pt: &[u8]) -> KmResult<Vec<u8>>;
-    fn unwrap_(&self, _ct: &[u8]) -> KmResult<Vec<u8>>;
+    fn wrap(&self, pt: &[u8]) -> KmResult<Vec<u8>>;
+    fn unwrap_(&self, ct: &[u8]) -> KmResult<Vec<u8>>;
 }
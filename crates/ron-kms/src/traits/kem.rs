@@ -1,7 +1,10 @@
-// KEM trait scaffold
+// RO:WHAT Key-encapsulation seam (ML-KEM, hybrid X25519+ML-KEM).
+// RO:INV  Shared secrets are 32 bytes; decap never reveals why a ciphertext was rejected.
 use crate::error::KmResult;
 
 pub trait Kem {
-    fn encap(&self, _peer_pub: &[u8]) -> KmResult<(Vec<u8>, Vec<u8>)>;
-    fn decap(&self, _ct: &[u8]) -> KmResult<Vec<u8>>;
+    /// Encapsulate to `peer_pub`; returns `(ciphertext, shared_secret)`.
+    fn encap(&self, peer_pub: &[u8]) -> KmResult<(Vec<u8>, Vec<u8>)>;
+    /// Recover the shared secret from a ciphertext addressed to this key.
+    fn decap(&self, ct: &[u8]) -> KmResult<Vec<u8>>;
 }
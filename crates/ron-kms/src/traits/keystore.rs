@@ -6,6 +6,13 @@ use crate::{
 /// Custody lifecycle — create/rotate/get metadata/attest (subset for core boot).
 pub trait Keystore: Send + Sync {
     fn create_ed25519(&self, tenant: &str, purpose: &str) -> Result<KeyId, KmsError>;
+    /// Create a key root for `alg`; backends without that algorithm return `AlgUnavailable`.
+    fn create(&self, tenant: &str, purpose: &str, alg: Alg) -> Result<KeyId, KmsError> {
+        match alg {
+            Alg::Ed25519 => self.create_ed25519(tenant, purpose),
+            Alg::EcdsaP256 => Err(KmsError::AlgUnavailable),
+        }
+    }
     fn rotate(&self, kid: &KeyId) -> Result<KeyId, KmsError>;
     fn alg(&self, kid: &KeyId) -> Result<Alg, KmsError>;
     /// Public metadata about a key root.
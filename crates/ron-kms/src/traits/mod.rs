@@ -1,12 +1,15 @@
 //! KMS trait surfaces
 
+pub mod hybrid;
+pub mod kem;
 pub mod keystore;
+pub mod pubkey;
 pub mod signer;
 pub mod verifier;
 
-// Internal-only helper for batch verify fast path.
-pub(crate) mod pubkey;
-
+pub use hybrid::Hybrid;
+pub use kem::Kem;
 pub use keystore::Keystore;
+pub use pubkey::PubkeyProvider;
 pub use signer::Signer;
 pub use verifier::Verifier;
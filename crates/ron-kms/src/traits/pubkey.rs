@@ -1,10 +1,11 @@
-//! RO:WHAT  Internal trait to expose verifying keys for batch verify fast paths.
+//! RO:WHAT  Trait to expose verifying keys (batch verify fast paths, key publication).
 //! RO:WHY   Our public Verifier trait only exposes boolean checks; batch verify
-//!          needs access to the raw verifying keys to use dalek's batch API.
+//!          needs access to the raw verifying keys to use dalek's batch API, and
+//!          issuers need them to publish a JWKS ahead of rotation.
 
 use crate::{error::KmsError, types::KeyId};
 
-/// Internal-only surface to fetch verifying key bytes for a given `KeyId` version.
+/// Surface to fetch verifying key bytes for a given `KeyId` version.
 /// Implemented by in-crate backends (memory, file, pkcs11) as needed.
 pub trait PubkeyProvider {
     /// Returns the raw verifying key bytes (Ed25519, 32 bytes) for this `KeyId`/version.
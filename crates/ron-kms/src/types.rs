@@ -7,6 +7,9 @@ use uuid::Uuid;
 #[serde(rename_all = "lowercase")]
 pub enum Alg {
     Ed25519,
+    /// ECDSA over NIST P-256 with SHA-256; HSM-backed issuers only (PKCS#11).
+    #[serde(rename = "ecdsa-p256")]
+    EcdsaP256,
     // Future: MlDsa, SlhDsa, X25519, MlKem...
 }
 
@@ -15,6 +18,7 @@ impl Alg {
     pub const fn as_str(self) -> &'static str {
         match self {
             Alg::Ed25519 => "ed25519",
+            Alg::EcdsaP256 => "ecdsa-p256",
         }
     }
 }
@@ -23,6 +27,7 @@ impl fmt::Display for Alg {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Alg::Ed25519 => write!(f, "Ed25519"),
+            Alg::EcdsaP256 => write!(f, "EcdsaP256"),
         }
     }
 }
@@ -32,6 +37,7 @@ impl FromStr for Alg {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "Ed25519" | "ed25519" => Ok(Self::Ed25519),
+            "EcdsaP256" | "ecdsa-p256" => Ok(Self::EcdsaP256),
             _ => Err("unknown alg"),
         }
     }
@@ -112,4 +118,7 @@ pub struct KeyMeta {
     pub current_version: u32,
     pub versions: Vec<u32>,
     pub created_ms: i128,
+    /// Public key of `current_version`: raw 32 bytes (Ed25519) or SEC1 uncompressed (P-256).
+    #[serde(default)]
+    pub public_key: Vec<u8>,
 }
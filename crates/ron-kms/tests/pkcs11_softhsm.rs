@@ -0,0 +1,86 @@
+//! PKCS#11 backend against a real token (SoftHSM locally / in CI).
+//!
+//! Skipped unless a module is configured, e.g.:
+//!   softhsm2-util --init-token --free --label RON-KMS --so-pin 0000 --pin 1234
+//!   RON_KMS_PKCS11_MODULE_PATH=/usr/lib/softhsm/libsofthsm2.so \
+//!   RON_KMS_PKCS11_PIN_ENV=KMS_PIN KMS_PIN=1234 \
+//!   cargo test -p ron-kms --features pkcs11 --test pkcs11_softhsm
+#![cfg(feature = "pkcs11")]
+
+use ron_kms::{
+    backends::Pkcs11Keystore, config::KmsConfig, traits::PubkeyProvider, Alg, Keystore, KmsError,
+    Signer, Verifier,
+};
+
+fn keystore() -> Option<Pkcs11Keystore> {
+    let Some(cfg) = KmsConfig::load().backends.pkcs11 else {
+        eprintln!("RON_KMS_PKCS11_MODULE_PATH unset; skipping PKCS#11 test");
+        return None;
+    };
+    Some(Pkcs11Keystore::open(&cfg).expect("open pkcs11 keystore"))
+}
+
+#[test]
+fn ed25519_sign_rotate_verify() {
+    let Some(ks) = keystore() else { return };
+    let v1 = ks.create_ed25519("tenantA", "passport").unwrap();
+    let msg = b"issuer-claims";
+    let sig1 = ks.sign(&v1, msg).unwrap();
+    assert!(ks.verify(&v1, msg, &sig1).unwrap());
+    assert!(!ks.verify(&v1, b"tampered", &sig1).unwrap());
+
+    // The exported key verifies outside the token.
+    let pk = ks.verifying_key_bytes(&v1).unwrap();
+    let vk = ed25519_dalek::VerifyingKey::from_bytes(&pk).unwrap();
+    let sig = ed25519_dalek::Signature::from_slice(&sig1).unwrap();
+    assert!(vk.verify_strict(msg, &sig).is_ok());
+    assert_eq!(ks.meta(&v1).unwrap().public_key, pk.to_vec());
+
+    let v2 = ks.rotate(&v1).unwrap();
+    assert_eq!(v2.version, 2);
+    assert!(matches!(ks.sign(&v1, msg), Err(KmsError::NoSuchKey)));
+    assert!(ks.verify(&v1, msg, &sig1).unwrap());
+    let sig2 = ks.sign(&v2, msg).unwrap();
+    assert!(ks.verify(&v2, msg, &sig2).unwrap());
+
+    let meta = ks.meta(&v2).unwrap();
+    assert_eq!(meta.current_version, 2);
+    assert_eq!(meta.versions, vec![1, 2]);
+    assert_eq!(
+        meta.public_key,
+        ks.verifying_key_bytes(&v2).unwrap().to_vec()
+    );
+}
+
+#[test]
+fn ecdsa_p256_sign_verify() {
+    let Some(ks) = keystore() else { return };
+    let kid = ks.create("tenantA", "passport", Alg::EcdsaP256).unwrap();
+    assert_eq!(ks.alg(&kid).unwrap(), Alg::EcdsaP256);
+    let sig = ks.sign(&kid, b"hello").unwrap();
+    assert_eq!(sig.len(), 64, "r || s");
+    assert!(ks.verify(&kid, b"hello", &sig).unwrap());
+    assert!(!ks.verify(&kid, b"other", &sig).unwrap());
+
+    let meta = ks.meta(&kid).unwrap();
+    assert_eq!(meta.public_key.len(), 65);
+    assert_eq!(meta.public_key[0], 0x04, "SEC1 uncompressed");
+    assert!(matches!(
+        ks.verifying_key_bytes(&kid),
+        Err(KmsError::AlgUnavailable)
+    ));
+}
+
+#[test]
+fn keys_survive_reopen() {
+    let Some(ks) = keystore() else { return };
+    let kid = ks.create_ed25519("tenantB", "passport").unwrap();
+    let created = ks.meta(&kid).unwrap().created_ms;
+    drop(ks);
+
+    let ks = keystore().unwrap();
+    let meta = ks.meta(&kid).unwrap();
+    assert_eq!(meta.created_ms, created);
+    let sig = ks.sign(&kid, b"after-restart").unwrap();
+    assert!(ks.verify(&kid, b"after-restart", &sig).unwrap());
+}
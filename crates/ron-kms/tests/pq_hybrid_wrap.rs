@@ -0,0 +1,47 @@
+#![cfg(feature = "pq-hybrid")]
+
+use ron_kms::pq::HybridKeypair;
+use ron_kms::traits::{Hybrid, Kem};
+
+#[test]
+fn hybrid_kem_agrees_and_wrap_roundtrips() {
+    let recipient = HybridKeypair::generate();
+    let sender = HybridKeypair::generate();
+
+    let (ct, ss) = sender
+        .encap(&recipient.public_key().to_bytes())
+        .expect("encap");
+    assert_eq!(ss.len(), 32);
+    assert_eq!(recipient.decap(&ct).expect("decap"), ss);
+    assert_ne!(sender.decap(&ct).expect("decap"), ss, "wrong key");
+
+    let dek = [7u8; 32];
+    let wrapped = recipient.wrap(&dek).expect("wrap");
+    assert_eq!(recipient.unwrap_(&wrapped).expect("unwrap"), dek);
+}
+
+#[test]
+fn hybrid_secret_bytes_restore_same_key() {
+    let kp = HybridKeypair::generate();
+    let wrapped = kp.wrap(b"dek").expect("wrap");
+
+    let restored = HybridKeypair::from_secret_bytes(&kp.secret_bytes()).expect("restore");
+    assert_eq!(restored.public_key(), kp.public_key());
+    assert_eq!(restored.unwrap_(&wrapped).expect("unwrap"), b"dek");
+}
+
+#[test]
+fn hybrid_unwrap_rejects_tamper_and_foreign_keys() {
+    let kp = HybridKeypair::generate();
+    let mut wrapped = kp.wrap(b"dek").expect("wrap");
+
+    assert!(HybridKeypair::generate().unwrap_(&wrapped).is_err());
+
+    // Flip a bit in the X25519 ephemeral key, then in the ML-KEM ciphertext.
+    for at in [0, 100] {
+        wrapped[at] ^= 0x01;
+        assert!(kp.unwrap_(&wrapped).is_err(), "tamper at {at}");
+        wrapped[at] ^= 0x01;
+    }
+    assert!(kp.unwrap_(&wrapped[..40]).is_err());
+}
@@ -1,7 +1,7 @@
 //! RO:WHAT — Typed ledger configuration for batching, limits, checkpoint cadence, engine profile, and PQ seam flags.
 //! RO:WHY  — Pillar 12; Concerns: ECON/RES/GOV. Keep runtime knobs explicit and validated without dragging in service config loaders.
 //! RO:INTERACTS — crate::engine, crate::api validation, tests, examples.
-//! RO:INVARIANTS — batch cap > 0; queue/checkpoint knobs non-zero; amnesia and persistent modes stay explicit.
+//! RO:INVARIANTS — batch cap > 0; queue/checkpoint/idempotency-window knobs non-zero; amnesia and persistent modes stay explicit.
 //! RO:METRICS — none directly; service wrappers can surface config-derived gauges if desired.
 //! RO:CONFIG — this file is the config contract.
 //! RO:SECURITY — limits prevent pathological payloads; pq mode is only a seam, not custody.
@@ -29,15 +29,19 @@ impl Default for EngineMode {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AccumulatorKind {
-    /// BLAKE3 chained accumulator.
+    /// Legacy BLAKE3 hash chain over records (not a Merkle tree, despite the name): each root
+    /// commits to the previous one, so there are no inclusion or consistency proofs. Kept so
+    /// ledgers persisted under it keep replaying.
     Merkle,
-    /// Future seam for a different accumulator.
+    /// Reserved seam for a vector-commitment accumulator; currently the same hash chain as `Merkle`.
     Verkle,
+    /// BLAKE3 Merkle mountain range; roots support inclusion and consistency proofs. The default.
+    Mmr,
 }
 
 impl Default for AccumulatorKind {
     fn default() -> Self {
-        Self::Merkle
+        Self::Mmr
     }
 }
 
@@ -91,6 +95,14 @@ pub struct LedgerConfig {
     pub accumulator_kind: AccumulatorKind,
     /// PQ seam for future wrappers.
     pub pq_mode: PqMode,
+    /// Entry ids and batch `idem_id`s stay deduplicated for this many sequences;
+    /// older ones are dropped from memory and snapshots.
+    #[serde(default = "default_idempotency_window")]
+    pub idempotency_window: u64,
+}
+
+fn default_idempotency_window() -> u64 {
+    1_000_000
 }
 
 impl Default for LedgerConfig {
@@ -99,8 +111,9 @@ impl Default for LedgerConfig {
             limits: Limits::default(),
             checkpoint_interval: 10_000,
             engine_mode: EngineMode::Amnesia,
-            accumulator_kind: AccumulatorKind::Merkle,
+            accumulator_kind: AccumulatorKind::default(),
             pq_mode: PqMode::Off,
+            idempotency_window: default_idempotency_window(),
         }
     }
 }
@@ -132,6 +145,12 @@ impl LedgerConfig {
                 "checkpoint_interval must be > 0",
             ));
         }
+        if self.idempotency_window == 0 {
+            return Err(LedgerError::reject(
+                RejectReason::Invalid,
+                "idempotency_window must be > 0",
+            ));
+        }
         Ok(())
     }
 }
@@ -1,20 +1,28 @@
-//! RO:WHAT — Deterministic chained accumulator for append-only entry records.
-//! RO:WHY  — Pillar 12; Concerns: ECON/RES. The ledger needs a stable root function for replay equality and audit.
-//! RO:INTERACTS — crate::types::EntryRecord, crate::engine::replay, crate::engine::ledger.
-//! RO:INVARIANTS — same ordered records always produce the same root; hash input must exclude self-referential new_root bytes.
+//! RO:WHAT — Append-only accumulators for entry records: the BLAKE3 chained hash and a BLAKE3 Merkle mountain range.
+//! RO:WHY  — Pillar 12; Concerns: ECON/RES/SEC. Roots must be replay-stable, and MMR roots must let clients prove one record without replaying all.
+//! RO:INTERACTS — crate::types::EntryRecord, crate::engine::{replay,ledger,snapshot,proof,checkpoint}.
+//! RO:INVARIANTS — same ordered records always produce the same root; hash input must exclude self-referential new_root bytes;
+//!                 MMR leaf/node/root hashes are domain-separated; the MMR root binds the leaf count; only peaks live in memory.
 //! RO:METRICS — none directly.
-//! RO:CONFIG — LedgerConfig::accumulator_kind chooses the algorithm seam, currently mapped to the same deterministic path.
-//! RO:SECURITY — root is integrity-only; no secret material or signatures participate here.
-//! RO:TEST — replay_recovery.rs and idempotency_prop.rs prove determinism.
+//! RO:CONFIG — LedgerConfig::accumulator_kind: Mmr (default) = mountain range with inclusion/consistency
+//!             proofs; Merkle/Verkle = legacy chained hash, no proofs.
+//! RO:SECURITY — roots are integrity-only; no secret material or signatures participate here.
+//! RO:TEST — replay_recovery.rs and idempotency_prop.rs prove determinism; merkle_inclusion.rs and checkpoint_consistency.rs cover proofs.
 
 use crate::{
     config::AccumulatorKind,
-    error::LedgerError,
-    types::{EntryRecord, Root},
+    error::{LedgerError, RejectReason},
+    types::{Entry, EntryRecord, Root, Seq},
 };
 use serde::Serialize;
 
-/// Canonical hash input for a record.
+use super::snapshot::LedgerSnapshot;
+
+const LEAF_TAG: u8 = 0x00;
+const NODE_TAG: u8 = 0x01;
+const ROOT_TAG: u8 = 0x02;
+
+/// Canonical hash input for a chained record.
 ///
 /// This intentionally excludes `new_root`, because `new_root` is the value being
 /// derived. Including it makes the accumulator self-referential and breaks replay:
@@ -23,28 +31,292 @@ use serde::Serialize;
 #[derive(Debug, Serialize)]
 struct CanonicalRecordForHash<'a> {
     seq: u64,
-    entry: &'a crate::types::Entry,
+    entry: &'a Entry,
     prev_root_hex: String,
 }
 
-/// Compute the next root for a record.
-pub fn next_root(
-    kind: AccumulatorKind,
-    prev_root: Root,
-    record: &EntryRecord,
-) -> Result<Root, LedgerError> {
+/// Canonical hash input for an MMR leaf: the committed entry at its sequence.
+#[derive(Debug, Serialize)]
+struct CanonicalLeafForHash<'a> {
+    seq: u64,
+    entry: &'a Entry,
+}
+
+/// MMR leaf hash for an entry committed at `seq`.
+pub fn leaf_hash(seq: Seq, entry: &Entry) -> Result<Root, LedgerError> {
+    let encoded = serde_json::to_vec(&CanonicalLeafForHash {
+        seq: seq.get(),
+        entry,
+    })?;
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[LEAF_TAG]);
+    hasher.update(&encoded);
+    Ok(Root::from_bytes(*hasher.finalize().as_bytes()))
+}
+
+pub(crate) fn node_hash(left: &Root, right: &Root) -> Root {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[NODE_TAG]);
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    Root::from_bytes(*hasher.finalize().as_bytes())
+}
+
+/// Bag the peaks (left to right) of a range holding `size` leaves into one root.
+pub(crate) fn bag_peaks(size: u64, peaks: &[Root]) -> Root {
+    if size == 0 {
+        return Root::zero();
+    }
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&[ROOT_TAG]);
+    hasher.update(&size.to_le_bytes());
+    for peak in peaks {
+        hasher.update(peak.as_bytes());
+    }
+    Root::from_bytes(*hasher.finalize().as_bytes())
+}
+
+/// Perfect subtrees of a range holding `size` leaves, left to right, as `(first leaf index, height)`.
+pub(crate) fn peak_layout(size: u64) -> impl Iterator<Item = (u64, u32)> {
+    let mut offset = 0_u64;
+    (0..u64::BITS).rev().filter_map(move |height| {
+        if size & (1 << height) == 0 {
+            return None;
+        }
+        let first = offset;
+        offset += 1 << height;
+        Some((first, height))
+    })
+}
+
+fn chained_root(prev_root: Root, record: &EntryRecord) -> Result<Root, LedgerError> {
     let canonical = CanonicalRecordForHash {
         seq: record.seq.get(),
         entry: &record.entry,
         prev_root_hex: prev_root.to_hex(),
     };
-
-    let encoded = match kind {
-        AccumulatorKind::Merkle | AccumulatorKind::Verkle => serde_json::to_vec(&canonical)?,
-    };
-
+    let encoded = serde_json::to_vec(&canonical)?;
     let mut hasher = blake3::Hasher::new();
     hasher.update(prev_root.as_bytes());
     hasher.update(&encoded);
     Ok(Root::from_bytes(*hasher.finalize().as_bytes()))
 }
+
+/// Reject proof requests against a chained accumulator.
+pub(crate) fn require_mmr(kind: AccumulatorKind, what: &str) -> Result<(), LedgerError> {
+    match kind {
+        AccumulatorKind::Mmr => Ok(()),
+        AccumulatorKind::Merkle | AccumulatorKind::Verkle => Err(LedgerError::reject(
+            RejectReason::Invalid,
+            format!("{what} proofs require the mmr accumulator"),
+        )),
+    }
+}
+
+/// Running accumulator state for one ledger.
+///
+/// For `Mmr` only the peaks are kept (one hash per set bit of the size); proofs are
+/// rebuilt from the log through [`LogTree`].
+#[derive(Debug, Clone)]
+pub struct Accumulator {
+    kind: AccumulatorKind,
+    size: u64,
+    root: Root,
+    /// Mountain range peaks, left to right; empty for the chained kinds.
+    peaks: Vec<Root>,
+}
+
+impl Accumulator {
+    /// Empty accumulator at genesis.
+    pub fn new(kind: AccumulatorKind) -> Self {
+        Self {
+            kind,
+            size: 0,
+            root: Root::zero(),
+            peaks: Vec::new(),
+        }
+    }
+
+    /// Restore an accumulator covering `size` records with the given head root.
+    ///
+    /// `Mmr` needs the peaks at `size` and rejects any mismatch with `root`; the chained
+    /// kinds only need the head root.
+    pub fn restore(
+        kind: AccumulatorKind,
+        size: u64,
+        root: Root,
+        peaks: &[Root],
+    ) -> Result<Self, LedgerError> {
+        let mut acc = Self::new(kind);
+        acc.size = size;
+        acc.root = root;
+        if kind == AccumulatorKind::Mmr {
+            if peaks.len() != peak_layout(size).count() {
+                return Err(LedgerError::reject(
+                    RejectReason::Conflict,
+                    format!("accumulator has {} peaks for {size} records", peaks.len()),
+                ));
+            }
+            if bag_peaks(size, peaks) != root {
+                return Err(LedgerError::reject(
+                    RejectReason::Conflict,
+                    "restored accumulator root mismatch",
+                ));
+            }
+            acc.peaks = peaks.to_vec();
+        }
+        Ok(acc)
+    }
+
+    /// Accumulator kind.
+    pub const fn kind(&self) -> AccumulatorKind {
+        self.kind
+    }
+
+    /// Number of records accumulated.
+    pub const fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Current root.
+    pub const fn root(&self) -> Root {
+        self.root
+    }
+
+    /// Mountain range peaks, left to right; empty for the chained kinds.
+    pub fn peaks(&self) -> &[Root] {
+        &self.peaks
+    }
+
+    /// Root after appending `record`, without mutating the accumulator.
+    ///
+    /// Lets the writer persist a record before advancing in-memory state.
+    pub fn next_root(&self, record: &EntryRecord) -> Result<Root, LedgerError> {
+        self.check_next(record)?;
+        match self.kind {
+            AccumulatorKind::Mmr => {
+                let mut peaks = self.peaks.clone();
+                push_leaf(&mut peaks, self.size, leaf_hash(record.seq, &record.entry)?);
+                Ok(bag_peaks(self.size + 1, &peaks))
+            }
+            AccumulatorKind::Merkle | AccumulatorKind::Verkle => chained_root(self.root, record),
+        }
+    }
+
+    /// Append the record committed at the next sequence and return the new root.
+    pub fn append(&mut self, record: &EntryRecord) -> Result<Root, LedgerError> {
+        self.check_next(record)?;
+        match self.kind {
+            AccumulatorKind::Mmr => {
+                push_leaf(
+                    &mut self.peaks,
+                    self.size,
+                    leaf_hash(record.seq, &record.entry)?,
+                );
+                self.root = bag_peaks(self.size + 1, &self.peaks);
+            }
+            AccumulatorKind::Merkle | AccumulatorKind::Verkle => {
+                self.root = chained_root(self.root, record)?;
+            }
+        }
+        self.size += 1;
+        Ok(self.root)
+    }
+
+    fn check_next(&self, record: &EntryRecord) -> Result<(), LedgerError> {
+        if record.seq.get() != self.size + 1 {
+            return Err(LedgerError::reject(
+                RejectReason::Conflict,
+                format!(
+                    "accumulator expected seq {}, got {}",
+                    self.size + 1,
+                    record.seq.get()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Append `leaf` to the peaks of a range holding `size` leaves, merging equal-height peaks.
+fn push_leaf(peaks: &mut Vec<Root>, size: u64, leaf: Root) {
+    let mut carry = leaf;
+    let mut height = 0;
+    while size & (1 << height) != 0 {
+        if let Some(left) = peaks.pop() {
+            carry = node_hash(&left, &carry);
+        }
+        height += 1;
+    }
+    peaks.push(carry);
+}
+
+/// Mountain range nodes for proofs, rebuilt from the records still in the log plus the
+/// peaks of the latest snapshot.
+///
+/// A node is either one of those peaks or hashed up from logged leaves, so a proof that
+/// needs a record compaction already dropped is refused.
+#[derive(Debug)]
+pub(crate) struct LogTree {
+    anchor: Vec<((u64, u32), Root)>,
+    first_leaf: u64,
+    leaves: Vec<Root>,
+}
+
+impl LogTree {
+    /// Tree over the first `size` records.
+    pub(crate) fn new(
+        snapshot: Option<&LedgerSnapshot>,
+        records: &[EntryRecord],
+        size: u64,
+    ) -> Result<Self, LedgerError> {
+        let anchor = match snapshot {
+            Some(s) if s.peaks.len() == peak_layout(s.seq.get()).count() => {
+                peak_layout(s.seq.get())
+                    .zip(s.peaks.iter().copied())
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        let first_leaf = records.first().map_or(size, |r| r.seq.get() - 1);
+        let mut leaves = Vec::new();
+        for record in records.iter().take_while(|r| r.seq.get() <= size) {
+            if record.seq.get() != first_leaf + leaves.len() as u64 + 1 {
+                return Err(LedgerError::reject(
+                    RejectReason::Conflict,
+                    format!("log has a gap before seq {}", record.seq.get()),
+                ));
+            }
+            leaves.push(leaf_hash(record.seq, &record.entry)?);
+        }
+        Ok(Self {
+            anchor,
+            first_leaf,
+            leaves,
+        })
+    }
+
+    /// Root of the perfect subtree of `2^height` leaves starting at leaf index `first`.
+    pub(crate) fn node(&self, first: u64, height: u32) -> Result<Root, LedgerError> {
+        if let Some((_, peak)) = self.anchor.iter().find(|(at, _)| *at == (first, height)) {
+            return Ok(*peak);
+        }
+        if height == 0 {
+            return first
+                .checked_sub(self.first_leaf)
+                .and_then(|i| self.leaves.get(i as usize))
+                .copied()
+                .ok_or_else(|| {
+                    LedgerError::reject(
+                        RejectReason::Invalid,
+                        format!("record at seq {} is no longer in the log", first + 1),
+                    )
+                });
+        }
+        let half = 1_u64 << (height - 1);
+        Ok(node_hash(
+            &self.node(first, height - 1)?,
+            &self.node(first + half, height - 1)?,
+        ))
+    }
+}
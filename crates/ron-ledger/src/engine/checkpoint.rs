@@ -1,15 +1,182 @@
-//! RO:WHAT — Checkpoint helper for producing durable `(seq, root, ts)` snapshots from the commit path.
-//! RO:WHY  — Pillar 12; Concerns: ECON/RES. Checkpoints bound replay work and anchor deterministic recovery.
-//! RO:INTERACTS — crate::types::CheckpointRecord, crate::engine::storage, crate::engine::ledger.
-//! RO:INVARIANTS — checkpoint cadence is config-driven; checkpoint data is append-only and never rewrites history.
+//! RO:WHAT — Checkpoint helper for producing durable `(seq, root, ts)` snapshots from the commit path, plus append-only consistency proofs between them.
+//! RO:WHY  — Pillar 12; Concerns: ECON/RES/SEC. Checkpoints bound replay work and anchor deterministic recovery; consistency proofs let outsiders detect history rewrites.
+//! RO:INTERACTS — crate::types::CheckpointRecord, crate::engine::{storage,ledger,accumulator}.
+//! RO:INVARIANTS — checkpoint cadence is config-driven; checkpoint data is append-only and never rewrites history;
+//!                 a consistency proof rebuilds the newer root from the older root's peaks plus subtrees appended after it.
 //! RO:METRICS — future wrappers can turn checkpoint events into counters/latency metrics through observers.
-//! RO:CONFIG — LedgerConfig::checkpoint_interval.
-//! RO:SECURITY — checkpoint records contain no secrets.
-//! RO:TEST — replay_recovery.rs ensures checkpoint + WAL replay yields the same root.
+//! RO:CONFIG — LedgerConfig::checkpoint_interval; consistency proofs require AccumulatorKind::Mmr.
+//! RO:SECURITY — checkpoint records and proofs contain no secrets; verification runs offline from two records.
+//! RO:TEST — replay_recovery.rs ensures checkpoint + WAL replay yields the same root; checkpoint_consistency.rs covers proofs.
 
-use crate::types::{CheckpointRecord, Root, Seq};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{LedgerError, RejectReason},
+    types::{CheckpointRecord, Root, Seq},
+};
+
+use super::accumulator::{bag_peaks, node_hash, peak_layout, LogTree};
 
 /// Build a checkpoint record.
 pub fn build_checkpoint(seq: Seq, root: Root, ts: u64) -> CheckpointRecord {
     CheckpointRecord { seq, root, ts }
 }
+
+/// Proof that the root at `to` extends the root at `from` without rewriting any record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConsistencyProof {
+    /// Sequence of the older checkpoint.
+    pub from: Seq,
+    /// Sequence of the newer checkpoint.
+    pub to: Seq,
+    /// Peaks of the mountain range at `from`, left to right.
+    pub old_peaks: Vec<Root>,
+    /// Roots of the subtrees appended after `from`, in the order the verifier consumes them.
+    pub nodes: Vec<Root>,
+}
+
+/// Consistency proof that the root at `to` extends the root at `from`, with nodes taken from `tree`.
+pub(crate) fn prove_consistency(
+    tree: &LogTree,
+    from: Seq,
+    to: Seq,
+) -> Result<ConsistencyProof, LedgerError> {
+    check_range(from, to)?;
+    let old_peaks = peak_layout(from.get())
+        .map(|(first, height)| tree.node(first, height))
+        .collect::<Result<Vec<Root>, LedgerError>>()?;
+    let mut nodes = Vec::new();
+    extend_peaks(from.get(), to.get(), &old_peaks, &mut |first, height| {
+        let node = tree.node(first, height)?;
+        nodes.push(node);
+        Ok(node)
+    })?;
+    Ok(ConsistencyProof {
+        from,
+        to,
+        old_peaks,
+        nodes,
+    })
+}
+
+/// Verify that checkpoint `new` extends checkpoint `old`.
+pub fn verify_consistency(
+    old: &CheckpointRecord,
+    new: &CheckpointRecord,
+    proof: &ConsistencyProof,
+) -> Result<(), LedgerError> {
+    if proof.from != old.seq || proof.to != new.seq {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            format!(
+                "proof covers {}..{}, checkpoints are {}..{}",
+                proof.from.get(),
+                proof.to.get(),
+                old.seq.get(),
+                new.seq.get()
+            ),
+        ));
+    }
+    check_range(old.seq, new.seq)?;
+    if proof.old_peaks.len() != peak_layout(old.seq.get()).count() {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            "proof peak count does not match the older checkpoint",
+        ));
+    }
+    if bag_peaks(old.seq.get(), &proof.old_peaks) != old.root {
+        return Err(LedgerError::reject(
+            RejectReason::Conflict,
+            "proof peaks do not match the older checkpoint root",
+        ));
+    }
+
+    let mut nodes = proof.nodes.iter();
+    let new_peaks = extend_peaks(
+        old.seq.get(),
+        new.seq.get(),
+        &proof.old_peaks,
+        &mut |_, _| {
+            nodes.next().copied().ok_or_else(|| {
+                LedgerError::reject(RejectReason::Invalid, "consistency proof is too short")
+            })
+        },
+    )?;
+    if nodes.next().is_some() {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            "consistency proof has unused nodes",
+        ));
+    }
+    if bag_peaks(new.seq.get(), &new_peaks) != new.root {
+        return Err(LedgerError::reject(
+            RejectReason::Conflict,
+            "newer checkpoint does not extend the older one",
+        ));
+    }
+    Ok(())
+}
+
+fn check_range(from: Seq, to: Seq) -> Result<(), LedgerError> {
+    if from.get() == 0 || from > to {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            format!(
+                "consistency needs 1 <= from <= to, got {}..{}",
+                from.get(),
+                to.get()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Peaks of the range at `new_size`, built from the peaks at `old_size` plus the roots of
+/// perfect subtrees lying entirely past `old_size`, requested left to right from `fresh`.
+///
+/// Prover and verifier share this walk so they agree on which nodes a proof carries.
+fn extend_peaks(
+    old_size: u64,
+    new_size: u64,
+    old_peaks: &[Root],
+    fresh: &mut dyn FnMut(u64, u32) -> Result<Root, LedgerError>,
+) -> Result<Vec<Root>, LedgerError> {
+    let old_layout: Vec<(u64, u32)> = peak_layout(old_size).collect();
+    peak_layout(new_size)
+        .map(|(first, height)| subtree(first, height, old_size, &old_layout, old_peaks, fresh))
+        .collect()
+}
+
+fn subtree(
+    first: u64,
+    height: u32,
+    old_size: u64,
+    old_layout: &[(u64, u32)],
+    old_peaks: &[Root],
+    fresh: &mut dyn FnMut(u64, u32) -> Result<Root, LedgerError>,
+) -> Result<Root, LedgerError> {
+    if let Some(i) = old_layout.iter().position(|p| *p == (first, height)) {
+        return Ok(old_peaks[i]);
+    }
+    if first >= old_size {
+        return fresh(first, height);
+    }
+    if height == 0 {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            "consistency walk reached an uncovered leaf",
+        ));
+    }
+    let half = 1_u64 << (height - 1);
+    let left = subtree(first, height - 1, old_size, old_layout, old_peaks, fresh)?;
+    let right = subtree(
+        first + half,
+        height - 1,
+        old_size,
+        old_layout,
+        old_peaks,
+        fresh,
+    )?;
+    Ok(node_hash(&left, &right))
+}
@@ -1,11 +1,12 @@
 //! RO:WHAT — Single-writer append-only ledger engine that validates batches, appends records, computes roots, and emits checkpoints.
 //! RO:WHY  — Pillar 12; Concerns: ECON/RES/GOV. This is the deterministic truth path that wallet/service wrappers will build on.
-//! RO:INTERACTS — crate::api, crate::config, crate::error, crate::types, crate::engine::{storage,replay,accumulator,checkpoint,observer}.
-//! RO:INVARIANTS — append-only commits; deterministic validation order; single mutation lock; replay equals live root; no floats; non-negative balances.
+//! RO:INTERACTS — crate::api, crate::config, crate::error, crate::types, crate::engine::{storage,replay,snapshot,accumulator,proof,checkpoint,observer}.
+//! RO:INVARIANTS — append-only commits; deterministic validation order; single mutation lock; replay equals live root; no floats; non-negative balances;
+//!                 idempotency maps are bounded by idempotency_window and snapshotted at every checkpoint.
 //! RO:METRICS — none directly; observer hooks expose committed/rejected/replayed/checkpointed events.
-//! RO:CONFIG — LedgerConfig controls batch cap, checkpoint cadence, and accumulator kind.
+//! RO:CONFIG — LedgerConfig controls batch cap, checkpoint cadence, idempotency window, and accumulator kind.
 //! RO:SECURITY — capability/KID values are stored as IDs only; external verification belongs outside this crate.
-//! RO:TEST — idempotency_prop.rs, replay_recovery.rs, reject_taxonomy.rs, interop_vectors.rs, benches/micro.rs.
+//! RO:TEST — idempotency_prop.rs, replay_recovery.rs, snapshot_compaction.rs, merkle_inclusion.rs, checkpoint_consistency.rs, reject_taxonomy.rs, interop_vectors.rs, benches/micro.rs.
 
 use std::{collections::HashMap, sync::Arc};
 
@@ -15,13 +16,16 @@ use crate::{
     api::{IngestRequest, IngestResponse, RejectItem, RootItem, RootsResponse},
     config::LedgerConfig,
     error::{LedgerError, RejectReason},
-    types::{AccountId, Entry, EntryRecord, Root, Seq},
+    types::{AccountId, CheckpointRecord, Entry, EntryRecord, Root, Seq},
 };
 
 use super::{
-    checkpoint::build_checkpoint,
+    accumulator::{require_mmr, Accumulator, LogTree},
+    checkpoint::{build_checkpoint, prove_consistency, ConsistencyProof},
     observer::{LedgerEvent, NoopObserver, Observer},
-    replay::{apply_entry, replay_records},
+    proof::{prove_inclusion, InclusionProof},
+    replay::{apply_entry, replay_from_snapshot, replay_records},
+    snapshot::{LedgerSnapshot, SnapshotBalance, SnapshotBatch, SnapshotEntryId, SNAPSHOT_VERSION},
     storage::Storage,
 };
 
@@ -40,17 +44,64 @@ struct State {
     seen_entry_ids: HashMap<String, Seq>,
     batch_idem: HashMap<String, StoredBatchResponse>,
     roots: Vec<RootItem>,
+    accumulator: Accumulator,
+    /// Seq of the latest checkpoint + snapshot; the next one is due `checkpoint_interval` entries later.
+    last_checkpoint_seq: u64,
 }
 
-impl Default for State {
-    fn default() -> Self {
-        Self {
-            next_seq: 1,
-            head_root: Root::zero(),
-            balances: HashMap::new(),
-            seen_entry_ids: HashMap::new(),
-            batch_idem: HashMap::new(),
-            roots: Vec::new(),
+impl State {
+    /// Drop entry ids and batch responses that fell out of the idempotency window.
+    fn prune_idempotency(&mut self, window: u64) {
+        let floor = (self.next_seq - 1).saturating_sub(window);
+        self.seen_entry_ids.retain(|_, seq| seq.get() > floor);
+        self.batch_idem
+            .retain(|_, stored| stored.seq_end.get() > floor);
+    }
+
+    fn snapshot(&self, ts: u64) -> LedgerSnapshot {
+        let mut balances: Vec<SnapshotBalance> = self
+            .balances
+            .iter()
+            .filter(|(_, balance)| **balance > 0)
+            .map(|(account, balance)| SnapshotBalance {
+                account: account.clone(),
+                balance: *balance,
+            })
+            .collect();
+        balances.sort_by(|a, b| a.account.as_str().cmp(b.account.as_str()));
+
+        let mut entry_ids: Vec<SnapshotEntryId> = self
+            .seen_entry_ids
+            .iter()
+            .map(|(id, seq)| SnapshotEntryId {
+                id: id.clone(),
+                seq: *seq,
+            })
+            .collect();
+        entry_ids.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut batches: Vec<SnapshotBatch> = self
+            .batch_idem
+            .iter()
+            .map(|(idem_id, stored)| SnapshotBatch {
+                idem_id: idem_id.clone(),
+                seq_start: stored.seq_start,
+                seq_end: stored.seq_end,
+                new_root: stored.new_root,
+            })
+            .collect();
+        batches.sort_by(|a, b| a.idem_id.cmp(&b.idem_id));
+
+        LedgerSnapshot {
+            v: SNAPSHOT_VERSION,
+            seq: Seq(self.next_seq - 1),
+            head_root: self.head_root,
+            ts,
+            balances,
+            entry_ids,
+            batches,
+            accumulator: self.accumulator.kind(),
+            peaks: self.accumulator.peaks().to_vec(),
         }
     }
 }
@@ -78,9 +129,35 @@ impl<S: Storage> Ledger<S> {
         config.validate()?;
         let storage = Arc::new(storage);
         let observer: Arc<dyn Observer> = Arc::new(observer);
-        let records = storage.load_records()?;
+        let snapshot = storage.load_snapshot()?;
         let checkpoints = storage.load_checkpoints()?;
-        let replayed = replay_records(config.accumulator_kind, &records)?;
+        // Restart from the latest snapshot plus the tail when the backend has one.
+        let (replayed, records, batch_idem) = match &snapshot {
+            Some(snapshot) => {
+                let tail = storage.load_records_after(snapshot.seq)?;
+                let replayed = replay_from_snapshot(config.accumulator_kind, snapshot, &tail)?;
+                let batch_idem = snapshot
+                    .batches
+                    .iter()
+                    .map(|b| {
+                        (
+                            b.idem_id.clone(),
+                            StoredBatchResponse {
+                                seq_start: b.seq_start,
+                                seq_end: b.seq_end,
+                                new_root: b.new_root,
+                            },
+                        )
+                    })
+                    .collect();
+                (replayed, tail.len(), batch_idem)
+            }
+            None => {
+                let records = storage.load_records()?;
+                let replayed = replay_records(config.accumulator_kind, &records)?;
+                (replayed, records.len(), HashMap::new())
+            }
+        };
 
         let mut roots: Vec<RootItem> = checkpoints
             .iter()
@@ -102,22 +179,35 @@ impl<S: Storage> Ledger<S> {
         }
 
         observer.on_event(&LedgerEvent::Replayed {
-            entries: records.len(),
+            entries: records,
             new_root: replayed.head_root,
         });
 
+        let last_checkpoint_seq = snapshot
+            .as_ref()
+            .map(|s| s.seq.get())
+            .into_iter()
+            .chain(checkpoints.last().map(|cp| cp.seq.get()))
+            .max()
+            .unwrap_or(0);
+
+        let mut state = State {
+            next_seq: replayed.next_seq,
+            head_root: replayed.head_root,
+            balances: replayed.balances,
+            seen_entry_ids: replayed.seen_entry_ids,
+            batch_idem,
+            roots,
+            accumulator: replayed.accumulator,
+            last_checkpoint_seq,
+        };
+        state.prune_idempotency(config.idempotency_window);
+
         Ok(Self {
             config,
             storage,
             observer,
-            state: Mutex::new(State {
-                next_seq: replayed.next_seq,
-                head_root: replayed.head_root,
-                balances: replayed.balances,
-                seen_entry_ids: replayed.seen_entry_ids,
-                batch_idem: HashMap::new(),
-                roots,
-            }),
+            state: Mutex::new(state),
         })
     }
 
@@ -179,13 +269,10 @@ impl<S: Storage> Ledger<S> {
                 prev_root,
                 new_root: Root::zero(),
             };
-            let new_root = crate::engine::accumulator::next_root(
-                self.config.accumulator_kind,
-                prev_root,
-                &record,
-            )?;
+            let new_root = state.accumulator.next_root(&record)?;
             record.new_root = new_root;
             self.storage.append_record(&record)?;
+            state.accumulator.append(&record)?;
             apply_entry(
                 &mut state.balances,
                 &entry.account,
@@ -206,16 +293,6 @@ impl<S: Storage> Ledger<S> {
             ts,
         });
 
-        let committed_count = last_seq.get();
-        if committed_count % self.config.checkpoint_interval == 0 {
-            let checkpoint = build_checkpoint(last_seq, prev_root, ts);
-            self.storage.append_checkpoint(&checkpoint)?;
-            self.observer.on_event(&LedgerEvent::Checkpointed {
-                seq: checkpoint.seq,
-                root: checkpoint.root,
-            });
-        }
-
         if let Some(idem_id) = request.idem_id {
             state.batch_idem.insert(
                 idem_id,
@@ -227,6 +304,19 @@ impl<S: Storage> Ledger<S> {
             );
         }
 
+        // Distance, not `seq % interval`: batches rarely end exactly on a multiple.
+        if last_seq.get() - state.last_checkpoint_seq >= self.config.checkpoint_interval {
+            let checkpoint = build_checkpoint(last_seq, prev_root, ts);
+            self.storage.append_checkpoint(&checkpoint)?;
+            state.prune_idempotency(self.config.idempotency_window);
+            self.storage.write_snapshot(&state.snapshot(ts))?;
+            state.last_checkpoint_seq = last_seq.get();
+            self.observer.on_event(&LedgerEvent::Checkpointed {
+                seq: checkpoint.seq,
+                root: checkpoint.root,
+            });
+        }
+
         self.observer.on_event(&LedgerEvent::BatchCommitted {
             seq_start,
             seq_end: last_seq,
@@ -264,6 +354,54 @@ impl<S: Storage> Ledger<S> {
         })
     }
 
+    /// Inclusion proof for the entry committed at `seq` against the root published at `root_seq`.
+    ///
+    /// Any root from [`Ledger::roots_since`] (or the head) can be used; clients check the
+    /// result with [`crate::engine::proof::verify_inclusion`]. Requires the MMR accumulator,
+    /// and the entry plus its siblings must still be in the log or the latest snapshot.
+    pub fn inclusion_proof(&self, seq: Seq, root_seq: Seq) -> Result<InclusionProof, LedgerError> {
+        let tree = self.log_tree("inclusion", root_seq)?;
+        prove_inclusion(&tree, seq, root_seq)
+    }
+
+    /// Fetch durable checkpoints after a given sequence (exclusive).
+    pub fn checkpoints_since(&self, since: u64) -> Result<Vec<CheckpointRecord>, LedgerError> {
+        let mut checkpoints = self.storage.load_checkpoints()?;
+        checkpoints.retain(|cp| cp.seq.get() > since);
+        Ok(checkpoints)
+    }
+
+    /// Consistency proof that the root at `to` extends the root at `from`.
+    ///
+    /// Meant for pairs of published checkpoints; auditors check the result offline with
+    /// [`crate::engine::checkpoint::verify_consistency`]. Requires the MMR accumulator.
+    pub fn consistency_proof(&self, from: Seq, to: Seq) -> Result<ConsistencyProof, LedgerError> {
+        let tree = self.log_tree("consistency", to)?;
+        prove_consistency(&tree, from, to)
+    }
+
+    /// MMR nodes for the first `size` records, read back from storage.
+    ///
+    /// Held under the state lock so a checkpoint cannot compact the log between
+    /// reading the records and reading the snapshot whose peaks cover what it dropped.
+    fn log_tree(&self, what: &str, size: Seq) -> Result<LogTree, LedgerError> {
+        let state = self.state.lock();
+        require_mmr(state.accumulator.kind(), what)?;
+        if size.get() > state.accumulator.size() {
+            return Err(LedgerError::reject(
+                RejectReason::Invalid,
+                format!(
+                    "seq {} is past the head {}",
+                    size.get(),
+                    state.accumulator.size()
+                ),
+            ));
+        }
+        let records = self.storage.load_records()?;
+        let snapshot = self.storage.load_snapshot()?;
+        LogTree::new(snapshot.as_ref(), &records, size.get())
+    }
+
     fn validate_request(&self, request: &IngestRequest) -> Result<(), LedgerError> {
         if request.batch.is_empty() {
             return Err(LedgerError::reject(
@@ -0,0 +1,133 @@
+//! RO:WHAT — MMR inclusion proofs for committed entries, built from the log, and a standalone verifier against published roots.
+//! RO:WHY  — Pillar 12; Concerns: ECON/SEC. Wallet receipts must be checkable by clients without trusting the ledger host.
+//! RO:INTERACTS — crate::engine::accumulator (hashing, peak layout, LogTree), crate::api::RootItem, crate::types::Entry.
+//! RO:INVARIANTS — verification needs only the entry, the proof, and a RootItem; shape mismatches are Invalid, hash mismatches Conflict.
+//! RO:METRICS — none.
+//! RO:CONFIG — proofs exist only for AccumulatorKind::Mmr.
+//! RO:SECURITY — a proof is public data; trust comes from the RootItem the client obtained independently.
+//! RO:TEST — merkle_inclusion.rs.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::RootItem,
+    error::{LedgerError, RejectReason},
+    types::{Entry, Root, Seq},
+};
+
+use super::accumulator::{bag_peaks, leaf_hash, node_hash, peak_layout, LogTree};
+
+/// Proof that the entry committed at `seq` is included in the root published at `root_seq`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InclusionProof {
+    /// Sequence of the proven entry.
+    pub seq: Seq,
+    /// Sequence of the root the proof is against.
+    pub root_seq: Seq,
+    /// Sibling hashes from the leaf up to its peak.
+    pub siblings: Vec<Root>,
+    /// The other peaks of the mountain range, left to right.
+    pub peaks: Vec<Root>,
+}
+
+/// Inclusion proof for `seq` against the root at `root_seq`, with nodes taken from `tree`.
+pub(crate) fn prove_inclusion(
+    tree: &LogTree,
+    seq: Seq,
+    root_seq: Seq,
+) -> Result<InclusionProof, LedgerError> {
+    if seq.get() == 0 || seq > root_seq {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            format!(
+                "cannot prove seq {} against root seq {}",
+                seq.get(),
+                root_seq.get()
+            ),
+        ));
+    }
+
+    let index = seq.get() - 1;
+    let mut siblings = Vec::new();
+    let mut peaks = Vec::new();
+    for (first, height) in peak_layout(root_seq.get()) {
+        if (first..first + (1 << height)).contains(&index) {
+            for level in 0..height {
+                let sibling = ((index >> level) ^ 1) << level;
+                siblings.push(tree.node(sibling, level)?);
+            }
+        } else {
+            peaks.push(tree.node(first, height)?);
+        }
+    }
+    Ok(InclusionProof {
+        seq,
+        root_seq,
+        siblings,
+        peaks,
+    })
+}
+
+/// Verify that `entry`, committed at `proof.seq`, is included in `root`.
+pub fn verify_inclusion(
+    entry: &Entry,
+    proof: &InclusionProof,
+    root: &RootItem,
+) -> Result<(), LedgerError> {
+    if proof.root_seq != root.seq {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            format!(
+                "proof is for root seq {}, not {}",
+                proof.root_seq.get(),
+                root.seq.get()
+            ),
+        ));
+    }
+    let size = root.seq.get();
+    if proof.seq.get() == 0 || proof.seq.get() > size {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            "proof seq outside the root's range",
+        ));
+    }
+
+    let index = proof.seq.get() - 1;
+    let layout: Vec<(u64, u32)> = peak_layout(size).collect();
+    let Some(position) = layout
+        .iter()
+        .position(|(first, height)| index >= *first && index - first < 1 << height)
+    else {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            "proof seq outside the root's range",
+        ));
+    };
+    let height = layout[position].1;
+    if proof.siblings.len() != height as usize || proof.peaks.len() != layout.len() - 1 {
+        return Err(LedgerError::reject(
+            RejectReason::Invalid,
+            "proof shape does not match the root's size",
+        ));
+    }
+
+    let mut node = leaf_hash(proof.seq, entry)?;
+    for (level, sibling) in proof.siblings.iter().enumerate() {
+        node = if (index >> level) & 1 == 0 {
+            node_hash(&node, sibling)
+        } else {
+            node_hash(sibling, &node)
+        };
+    }
+
+    let mut peaks = proof.peaks.clone();
+    peaks.insert(position, node);
+    if bag_peaks(size, &peaks) != root.root {
+        return Err(LedgerError::reject(
+            RejectReason::Conflict,
+            "inclusion proof does not match root",
+        ));
+    }
+    Ok(())
+}
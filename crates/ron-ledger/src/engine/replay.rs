@@ -1,11 +1,12 @@
 //! RO:WHAT — Replay helpers that rebuild balances, roots, and indexes from append-only records and checkpoints.
 //! RO:WHY  — Pillar 12; Concerns: ECON/RES. Recovery must produce the same state every time from the same history.
-//! RO:INTERACTS — crate::engine::storage, crate::engine::accumulator, crate::engine::ledger, crate::types.
-//! RO:INVARIANTS — records replay in sequence order; prev_root/new_root continuity is checked; balance rules are identical to live commit.
+//! RO:INTERACTS — crate::engine::storage, crate::engine::accumulator, crate::engine::ledger, crate::engine::snapshot, crate::types.
+//! RO:INVARIANTS — records replay in sequence order; prev_root/new_root continuity is checked; balance rules are identical to live commit;
+//!                 a snapshot tail must start right after the snapshot seq and chain from its head root; the snapshot's accumulator must match config.
 //! RO:METRICS — observer emits a replay-complete event; no metrics coupling here.
 //! RO:CONFIG — accumulator kind influences root verification.
 //! RO:SECURITY — replay validates identifiers and roots but never re-verifies external capability secrets.
-//! RO:TEST — replay_recovery.rs and interop_vectors.rs.
+//! RO:TEST — replay_recovery.rs, interop_vectors.rs, snapshot_compaction.rs.
 
 use std::collections::HashMap;
 
@@ -15,6 +16,8 @@ use crate::{
     types::{AccountId, EntryKind, EntryRecord, Root, Seq},
 };
 
+use super::{accumulator::Accumulator, snapshot::LedgerSnapshot};
+
 /// Rebuilt state after replay.
 #[derive(Debug, Clone)]
 pub struct ReplayedState {
@@ -26,6 +29,8 @@ pub struct ReplayedState {
     pub balances: HashMap<AccountId, u128>,
     /// Record ids already present.
     pub seen_entry_ids: HashMap<String, Seq>,
+    /// Accumulator state at the head.
+    pub accumulator: Accumulator,
 }
 
 /// Rebuild state from ordered records.
@@ -33,10 +38,66 @@ pub fn replay_records(
     kind: AccumulatorKind,
     records: &[EntryRecord],
 ) -> Result<ReplayedState, LedgerError> {
-    let mut head_root = Root::zero();
-    let mut next_seq = 1_u64;
-    let mut balances: HashMap<AccountId, u128> = HashMap::new();
-    let mut seen_entry_ids: HashMap<String, Seq> = HashMap::new();
+    let genesis = ReplayedState {
+        head_root: Root::zero(),
+        next_seq: 1,
+        balances: HashMap::new(),
+        seen_entry_ids: HashMap::new(),
+        accumulator: Accumulator::new(kind),
+    };
+    replay_onto(genesis, records)
+}
+
+/// Rebuild state from a snapshot plus the records appended after it.
+pub fn replay_from_snapshot(
+    kind: AccumulatorKind,
+    snapshot: &LedgerSnapshot,
+    tail: &[EntryRecord],
+) -> Result<ReplayedState, LedgerError> {
+    // v1 snapshots predate the field and were always chained.
+    if snapshot.v >= 2 && snapshot.accumulator != kind {
+        return Err(LedgerError::reject(
+            RejectReason::Conflict,
+            format!(
+                "snapshot was taken with the {:?} accumulator, config uses {kind:?}",
+                snapshot.accumulator
+            ),
+        ));
+    }
+    let start = ReplayedState {
+        head_root: snapshot.head_root,
+        next_seq: snapshot.seq.get() + 1,
+        balances: snapshot
+            .balances
+            .iter()
+            .map(|item| (item.account.clone(), item.balance))
+            .collect(),
+        seen_entry_ids: snapshot
+            .entry_ids
+            .iter()
+            .map(|item| (item.id.clone(), item.seq))
+            .collect(),
+        accumulator: Accumulator::restore(
+            kind,
+            snapshot.seq.get(),
+            snapshot.head_root,
+            &snapshot.peaks,
+        )?,
+    };
+    replay_onto(start, tail)
+}
+
+fn replay_onto(
+    start: ReplayedState,
+    records: &[EntryRecord],
+) -> Result<ReplayedState, LedgerError> {
+    let ReplayedState {
+        mut head_root,
+        mut next_seq,
+        mut balances,
+        mut seen_entry_ids,
+        mut accumulator,
+    } = start;
 
     for record in records {
         if record.seq.get() != next_seq {
@@ -55,7 +116,7 @@ pub fn replay_records(
                 "prev_root mismatch during replay",
             ));
         }
-        let expected = crate::engine::accumulator::next_root(kind, record.prev_root, record)?;
+        let expected = accumulator.append(record)?;
         if expected != record.new_root {
             return Err(LedgerError::reject(
                 RejectReason::Conflict,
@@ -79,6 +140,7 @@ pub fn replay_records(
         next_seq,
         balances,
         seen_entry_ids,
+        accumulator,
     })
 }
 
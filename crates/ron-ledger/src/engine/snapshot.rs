@@ -0,0 +1,119 @@
+//! RO:WHAT — Compacted ledger state (balances, head root, MMR peaks, bounded idempotency window) persisted at checkpoints.
+//! RO:WHY  — Pillar 12; Concerns: ECON/RES. Restart cost must track the tail since the last checkpoint, not all history.
+//! RO:INTERACTS — crate::engine::{ledger,replay,storage}, crate::types.
+//! RO:INVARIANTS — snapshot at seq N + records N+1.. replays to the same head root and balances as the full log; items are sorted so encoding is deterministic;
+//!                 a v2 snapshot names its accumulator and is only restored under the same kind.
+//! RO:METRICS — none directly.
+//! RO:CONFIG — LedgerConfig::{checkpoint_interval, idempotency_window}.
+//! RO:SECURITY — identifiers and balances only; a BLAKE3 checksum rejects torn or edited snapshot files.
+//! RO:TEST — snapshot_compaction.rs.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::AccumulatorKind,
+    error::{LedgerError, RejectReason},
+    types::{AccountId, Checksum, Root, Seq},
+};
+
+/// Snapshot schema version written by this build.
+///
+/// v2 adds `accumulator` and `peaks`; v1 snapshots (chained accumulator only) still load.
+pub const SNAPSHOT_VERSION: u16 = 2;
+
+/// Ledger state as of one checkpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LedgerSnapshot {
+    /// Snapshot schema version.
+    pub v: u16,
+    /// Last sequence covered by the snapshot.
+    pub seq: Seq,
+    /// Head root at `seq`.
+    pub head_root: Root,
+    /// Timestamp of the checkpoint that produced the snapshot.
+    pub ts: u64,
+    /// Non-zero balances, sorted by account.
+    pub balances: Vec<SnapshotBalance>,
+    /// Entry ids inside the idempotency window, sorted by id.
+    pub entry_ids: Vec<SnapshotEntryId>,
+    /// Batch idempotency responses inside the window, sorted by idem id.
+    pub batches: Vec<SnapshotBatch>,
+    /// Accumulator that produced `head_root`; v1 snapshots predate the field and were chained.
+    #[serde(default = "chained_accumulator")]
+    pub accumulator: AccumulatorKind,
+    /// MMR peaks at `seq`, left to right; the leaf count is `seq`. Empty for the chained kinds.
+    #[serde(default)]
+    pub peaks: Vec<Root>,
+}
+
+fn chained_accumulator() -> AccumulatorKind {
+    AccumulatorKind::Merkle
+}
+
+/// One account balance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotBalance {
+    /// Account id.
+    pub account: AccountId,
+    /// Balance in minor units.
+    pub balance: u128,
+}
+
+/// One committed entry id still inside the idempotency window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotEntryId {
+    /// Client-provided entry id.
+    pub id: String,
+    /// Sequence the entry was committed at.
+    pub seq: Seq,
+}
+
+/// One stored batch response still inside the idempotency window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotBatch {
+    /// Batch-level idempotency id.
+    pub idem_id: String,
+    /// First sequence of the batch.
+    pub seq_start: Seq,
+    /// Last sequence of the batch.
+    pub seq_end: Seq,
+    /// Root after the batch.
+    pub new_root: Root,
+}
+
+impl LedgerSnapshot {
+    /// Encode as `<checksum hex>\n<json>`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, LedgerError> {
+        let body = serde_json::to_vec(self)?;
+        let mut out = Checksum::of_bytes(&body).to_hex().into_bytes();
+        out.push(b'\n');
+        out.extend_from_slice(&body);
+        Ok(out)
+    }
+
+    /// Decode bytes written by [`LedgerSnapshot::to_bytes`], verifying the checksum and version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LedgerError> {
+        let split = bytes.iter().position(|&b| b == b'\n').ok_or_else(|| {
+            LedgerError::reject(RejectReason::Invalid, "snapshot missing checksum line")
+        })?;
+        let (checksum, body) = (&bytes[..split], &bytes[split + 1..]);
+        if checksum != Checksum::of_bytes(body).to_hex().as_bytes() {
+            return Err(LedgerError::reject(
+                RejectReason::Conflict,
+                "snapshot checksum mismatch",
+            ));
+        }
+        let snapshot: Self = serde_json::from_slice(body)?;
+        if snapshot.v == 0 || snapshot.v > SNAPSHOT_VERSION {
+            return Err(LedgerError::reject(
+                RejectReason::Invalid,
+                format!("unsupported snapshot version {}", snapshot.v),
+            ));
+        }
+        Ok(snapshot)
+    }
+}
@@ -1,11 +1,13 @@
-//! RO:WHAT — Storage trait plus in-memory and file-backed append-only implementations for records and checkpoints.
+//! RO:WHAT — Storage trait plus in-memory, file-backed, and segmented snapshot-compacting implementations for records and checkpoints.
 //! RO:WHY  — Pillar 12; Concerns: ECON/RES. The engine depends on a tiny storage seam instead of a specific DB.
-//! RO:INTERACTS — crate::engine::ledger, crate::engine::replay, crate::types::EntryRecord / CheckpointRecord.
-//! RO:INVARIANTS — append-only writes; durable backend never mutates history in place; amnesia backend leaves no disk artifacts.
+//! RO:INTERACTS — crate::engine::ledger, crate::engine::replay, crate::engine::snapshot, crate::types::EntryRecord / CheckpointRecord.
+//! RO:INVARIANTS — append-only writes; durable backends never mutate history in place; amnesia backend leaves no disk artifacts;
+//!                 a segment is only deleted once a durable snapshot covers every record in it;
+//!                 every record log starts with a `{"wal":N}` header, and headerless logs read as version 1.
 //! RO:METRICS — none directly; wrappers can instrument IO externally if needed.
-//! RO:CONFIG — EngineMode decides whether callers choose MemoryStorage or FileStorage.
+//! RO:CONFIG — EngineMode decides whether callers choose MemoryStorage or a file backend; SegmentedStorage::with_segment_records.
 //! RO:SECURITY — file backend stores only identifiers and ledger records; no secrets or raw capability material.
-//! RO:TEST — replay_recovery.rs exercises FileStorage; other tests use MemoryStorage.
+//! RO:TEST — replay_recovery.rs exercises FileStorage; snapshot_compaction.rs exercises SegmentedStorage; other tests use MemoryStorage.
 
 use std::{
     fs::{self, File, OpenOptions},
@@ -17,9 +19,11 @@ use parking_lot::Mutex;
 
 use crate::{
     error::{LedgerError, RejectReason},
-    types::{CheckpointRecord, EntryRecord},
+    types::{CheckpointRecord, EntryRecord, Seq},
 };
 
+use super::snapshot::LedgerSnapshot;
+
 /// Storage seam required by the engine.
 pub trait Storage: Send + Sync + 'static {
     /// Append a new entry record.
@@ -30,6 +34,25 @@ pub trait Storage: Send + Sync + 'static {
     fn load_records(&self) -> Result<Vec<EntryRecord>, LedgerError>;
     /// Load all checkpoints in append order.
     fn load_checkpoints(&self) -> Result<Vec<CheckpointRecord>, LedgerError>;
+
+    /// Load entry records with `seq > after`, in append order.
+    fn load_records_after(&self, after: Seq) -> Result<Vec<EntryRecord>, LedgerError> {
+        let mut records = self.load_records()?;
+        records.retain(|record| record.seq > after);
+        Ok(records)
+    }
+
+    /// Persist a snapshot taken at a checkpoint.
+    ///
+    /// Backends without snapshot support keep the full log and ignore it.
+    fn write_snapshot(&self, _snapshot: &LedgerSnapshot) -> Result<(), LedgerError> {
+        Ok(())
+    }
+
+    /// Load the latest persisted snapshot, if any.
+    fn load_snapshot(&self) -> Result<Option<LedgerSnapshot>, LedgerError> {
+        Ok(None)
+    }
 }
 
 /// In-memory storage for amnesia mode and tests.
@@ -75,7 +98,7 @@ impl FileStorage {
         let wal_path = dir.join("wal.jsonl");
         let checkpoint_path = dir.join("checkpoints.jsonl");
         if !wal_path.exists() {
-            File::create(&wal_path)?;
+            create_log(&wal_path)?;
         }
         if !checkpoint_path.exists() {
             File::create(&checkpoint_path)?;
@@ -91,53 +114,88 @@ impl FileStorage {
     pub fn dir(&self) -> &Path {
         &self.dir
     }
+}
 
-    fn append_json_line<T: serde::Serialize>(
-        &self,
-        path: &Path,
-        value: &T,
-    ) -> Result<(), LedgerError> {
-        let bytes = serde_json::to_vec(value)?;
-        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
-        file.write_all(&bytes)?;
-        file.write_all(b"\n")?;
-        file.flush()?;
-        Ok(())
-    }
+/// Record log format version written in the header line of new log files.
+pub const WAL_VERSION: u16 = 1;
 
-    fn read_json_lines<T: for<'de> serde::Deserialize<'de>>(
-        &self,
-        path: &Path,
-    ) -> Result<Vec<T>, LedgerError> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-        let mut out = Vec::new();
-        for line in reader.lines() {
-            let line = line?;
-            if line.trim().is_empty() {
+/// First line of a record log file.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WalHeader {
+    wal: u16,
+}
+
+/// Create `path` as an empty record log holding only the version header.
+fn create_log(path: &Path) -> Result<(), LedgerError> {
+    append_json_line(path, &WalHeader { wal: WAL_VERSION })
+}
+
+/// Read a record log, checking its version header.
+fn read_log(path: &Path) -> Result<Vec<EntryRecord>, LedgerError> {
+    let file = File::open(path)?;
+    let mut out = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if idx == 0 {
+            if let Ok(header) = serde_json::from_str::<WalHeader>(&line) {
+                if header.wal == 0 || header.wal > WAL_VERSION {
+                    return Err(LedgerError::reject(
+                        RejectReason::Invalid,
+                        format!("unsupported wal version {}", header.wal),
+                    ));
+                }
                 continue;
             }
-            out.push(serde_json::from_str(&line)?);
         }
-        Ok(out)
+        out.push(serde_json::from_str(&line)?);
+    }
+    Ok(out)
+}
+
+fn append_json_line<T: serde::Serialize>(path: &Path, value: &T) -> Result<(), LedgerError> {
+    let bytes = serde_json::to_vec(value)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&bytes)?;
+    file.write_all(b"\n")?;
+    file.flush()?;
+    Ok(())
+}
+
+fn read_json_lines<T: for<'de> serde::Deserialize<'de>>(
+    path: &Path,
+) -> Result<Vec<T>, LedgerError> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let mut out = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        out.push(serde_json::from_str(&line)?);
     }
+    Ok(out)
 }
 
 impl Storage for FileStorage {
     fn append_record(&self, record: &EntryRecord) -> Result<(), LedgerError> {
-        self.append_json_line(&self.wal_path, record)
+        append_json_line(&self.wal_path, record)
     }
 
     fn append_checkpoint(&self, checkpoint: &CheckpointRecord) -> Result<(), LedgerError> {
-        self.append_json_line(&self.checkpoint_path, checkpoint)
+        append_json_line(&self.checkpoint_path, checkpoint)
     }
 
     fn load_records(&self) -> Result<Vec<EntryRecord>, LedgerError> {
-        self.read_json_lines(&self.wal_path)
+        read_log(&self.wal_path)
     }
 
     fn load_checkpoints(&self) -> Result<Vec<CheckpointRecord>, LedgerError> {
-        self.read_json_lines(&self.checkpoint_path)
+        read_json_lines(&self.checkpoint_path)
     }
 }
 
@@ -153,3 +211,172 @@ impl FileStorage {
         Ok(())
     }
 }
+
+/// Default number of records per log segment.
+pub const DEFAULT_SEGMENT_RECORDS: u64 = 10_000;
+
+#[derive(Debug, Default)]
+struct ActiveSegment {
+    /// First sequence in the active segment (0 = no segment yet).
+    first_seq: u64,
+    records: u64,
+}
+
+/// File-backed storage that rolls the log into fixed-size segments, keeps the
+/// latest snapshot, and drops segments the snapshot fully covers.
+///
+/// Layout: `segments/<first seq>.jsonl`, `checkpoints.jsonl`, `snapshot.json`.
+/// After compaction `load_records` only returns the retained tail.
+#[derive(Debug)]
+pub struct SegmentedStorage {
+    dir: PathBuf,
+    segments_dir: PathBuf,
+    checkpoint_path: PathBuf,
+    snapshot_path: PathBuf,
+    segment_records: u64,
+    active: Mutex<ActiveSegment>,
+}
+
+impl SegmentedStorage {
+    /// Create or open a segmented storage directory.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, LedgerError> {
+        let dir = dir.as_ref().to_path_buf();
+        FileStorage::validate_dir(&dir)?;
+        let segments_dir = dir.join("segments");
+        fs::create_dir_all(&segments_dir)?;
+        let checkpoint_path = dir.join("checkpoints.jsonl");
+        if !checkpoint_path.exists() {
+            File::create(&checkpoint_path)?;
+        }
+        let storage = Self {
+            snapshot_path: dir.join("snapshot.json"),
+            dir,
+            segments_dir,
+            checkpoint_path,
+            segment_records: DEFAULT_SEGMENT_RECORDS,
+            active: Mutex::new(ActiveSegment::default()),
+        };
+
+        if let Some((first_seq, path)) = storage.segments()?.pop() {
+            let records = read_log(&path)?.len() as u64;
+            *storage.active.lock() = ActiveSegment { first_seq, records };
+        }
+        Ok(storage)
+    }
+
+    /// Records per segment before the log rolls to a new file.
+    pub fn with_segment_records(mut self, records: u64) -> Self {
+        self.segment_records = records.max(1);
+        self
+    }
+
+    /// Directory path used by this storage.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Segment files as `(first seq, path)`, oldest first.
+    pub fn segments(&self) -> Result<Vec<(u64, PathBuf)>, LedgerError> {
+        let mut out = Vec::new();
+        for entry in fs::read_dir(&self.segments_dir)? {
+            let path = entry?.path();
+            let first = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".jsonl"))
+                .and_then(|stem| stem.parse::<u64>().ok());
+            if let Some(first) = first {
+                out.push((first, path));
+            }
+        }
+        out.sort_by_key(|(first, _)| *first);
+        Ok(out)
+    }
+
+    fn segment_path(&self, first_seq: u64) -> PathBuf {
+        self.segments_dir.join(format!("{first_seq:020}.jsonl"))
+    }
+
+    /// Delete every segment whose records are all at or below `seq`.
+    ///
+    /// The newest segment is always kept; appends continue into it.
+    fn compact_through(&self, seq: Seq) -> Result<(), LedgerError> {
+        let segments = self.segments()?;
+        for pair in segments.windows(2) {
+            let (_, path) = &pair[0];
+            let (next_first, _) = pair[1];
+            if next_first > seq.get() + 1 {
+                break;
+            }
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Storage for SegmentedStorage {
+    fn append_record(&self, record: &EntryRecord) -> Result<(), LedgerError> {
+        let mut active = self.active.lock();
+        if active.first_seq == 0 || active.records >= self.segment_records {
+            *active = ActiveSegment {
+                first_seq: record.seq.get(),
+                records: 0,
+            };
+            create_log(&self.segment_path(active.first_seq))?;
+        }
+        append_json_line(&self.segment_path(active.first_seq), record)?;
+        active.records += 1;
+        Ok(())
+    }
+
+    fn append_checkpoint(&self, checkpoint: &CheckpointRecord) -> Result<(), LedgerError> {
+        append_json_line(&self.checkpoint_path, checkpoint)
+    }
+
+    fn load_records(&self) -> Result<Vec<EntryRecord>, LedgerError> {
+        self.load_records_after(Seq(0))
+    }
+
+    fn load_checkpoints(&self) -> Result<Vec<CheckpointRecord>, LedgerError> {
+        read_json_lines(&self.checkpoint_path)
+    }
+
+    fn load_records_after(&self, after: Seq) -> Result<Vec<EntryRecord>, LedgerError> {
+        let segments = self.segments()?;
+        let mut out = Vec::new();
+        for (idx, (_, path)) in segments.iter().enumerate() {
+            // Skip whole segments that end before the cut.
+            if segments
+                .get(idx + 1)
+                .is_some_and(|(next_first, _)| *next_first <= after.get() + 1)
+            {
+                continue;
+            }
+            let records = read_log(path)?;
+            out.extend(records.into_iter().filter(|record| record.seq > after));
+        }
+        Ok(out)
+    }
+
+    fn write_snapshot(&self, snapshot: &LedgerSnapshot) -> Result<(), LedgerError> {
+        let mut tmp = self.snapshot_path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(&snapshot.to_bytes()?)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &self.snapshot_path)?;
+        // Only drop history once the snapshot that replaces it is durable.
+        self.compact_through(snapshot.seq)
+    }
+
+    fn load_snapshot(&self) -> Result<Option<LedgerSnapshot>, LedgerError> {
+        match fs::read(&self.snapshot_path) {
+            Ok(bytes) => LedgerSnapshot::from_bytes(&bytes).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
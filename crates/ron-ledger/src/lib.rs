@@ -20,8 +20,9 @@ pub mod types;
 
 pub use crate::config::{AccumulatorKind, EngineMode, LedgerConfig, Limits, PqMode};
 pub use crate::engine::{
-    CheckpointRecord, FileStorage, Ledger, LedgerEvent, MemoryStorage, NoopObserver, Observer,
-    RootItem, Storage,
+    verify_consistency, verify_inclusion, CheckpointRecord, ConsistencyProof, FileStorage,
+    InclusionProof, Ledger, LedgerEvent, LedgerSnapshot, MemoryStorage, NoopObserver, Observer,
+    RootItem, SegmentedStorage, Storage,
 };
 pub use crate::error::{LedgerError, RejectReason};
 pub use crate::types::{
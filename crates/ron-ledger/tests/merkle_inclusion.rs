@@ -0,0 +1,257 @@
+//! RO:WHAT — MMR inclusion-proof tests: prove, verify, tamper, restart from a peaks-only snapshot, and chained-kind rejection.
+//! RO:WHY  — Pillar 12; Concerns: ECON/SEC. Wallet receipts must verify against a published root without trusting the host.
+//! RO:INTERACTS — ron_ledger::engine::{Ledger, InclusionProof, verify_inclusion, SegmentedStorage}, api::RootItem.
+//! RO:INVARIANTS — every committed seq still in the log proves against every root at or after it; any edited entry, seq, sibling, or root fails.
+//! RO:METRICS — none.
+//! RO:CONFIG — Mmr accumulator (the default); tiny checkpoint intervals and segments for the restart case.
+//! RO:SECURITY — proofs are public; verification needs only the entry, the proof, and a RootItem.
+//! RO:TEST — integration + property test.
+
+use proptest::prelude::*;
+use ron_ledger::{
+    api::{IngestRequest, RootItem},
+    config::{AccumulatorKind, LedgerConfig},
+    engine::{verify_inclusion, InclusionProof, Ledger, MemoryStorage, SegmentedStorage, Storage},
+    types::{AccountId, CapabilityRef, Entry, EntryKind, Kid, Nonce, Root, Seq},
+    RejectReason,
+};
+use tempfile::tempdir;
+
+fn mmr() -> LedgerConfig {
+    LedgerConfig {
+        accumulator_kind: AccumulatorKind::Mmr,
+        ..LedgerConfig::default()
+    }
+}
+
+fn mint(n: usize) -> Entry {
+    Entry::new(
+        format!("mint-{n}"),
+        n as u64,
+        EntryKind::Mint,
+        AccountId::new(format!("acct_{}", n % 4)).unwrap(),
+        10 + n as u64,
+        Nonce::from_base64("AAAAAAAAAAAAAAAAAAAAAA==").unwrap(),
+        Kid::new("kid-proof").unwrap(),
+        CapabilityRef::new("cap-proof").unwrap(),
+        1,
+    )
+    .unwrap()
+}
+
+/// Commit `sizes` batches of mints and return every committed entry in seq order.
+fn commit<S: ron_ledger::Storage>(ledger: &Ledger<S>, sizes: &[usize]) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for &size in sizes {
+        let n = entries.len();
+        let batch: Vec<Entry> = (0..size).map(|i| mint(n + i + 1)).collect();
+        entries.extend(batch.iter().cloned());
+        let resp = ledger
+            .ingest(IngestRequest {
+                batch,
+                idem_id: None,
+            })
+            .unwrap();
+        assert!(resp.accepted);
+        assert_eq!(resp.seq_start, Some(Seq(n as u64 + 1)));
+    }
+    entries
+}
+
+fn assert_all_proofs_verify<S: ron_ledger::Storage>(ledger: &Ledger<S>, entries: &[Entry]) {
+    assert_proofs_verify_after(ledger, entries, 0);
+}
+
+/// Every entry past `after` proves against every root at or after it.
+fn assert_proofs_verify_after<S: ron_ledger::Storage>(
+    ledger: &Ledger<S>,
+    entries: &[Entry],
+    after: u64,
+) {
+    let roots = ledger.roots_since(0).unwrap().roots;
+    assert!(!roots.is_empty());
+    for root in &roots {
+        for seq in after + 1..=root.seq.get() {
+            let proof = ledger.inclusion_proof(Seq(seq), root.seq).unwrap();
+            verify_inclusion(&entries[seq as usize - 1], &proof, root).unwrap();
+        }
+    }
+}
+
+#[test]
+fn every_entry_proves_against_every_later_root() {
+    let ledger = Ledger::new(MemoryStorage::default(), mmr()).unwrap();
+    let entries = commit(&ledger, &[1, 2, 3, 1, 5, 4, 1]);
+    assert_all_proofs_verify(&ledger, &entries);
+
+    // Proofs are plain DTOs a client can carry alongside a receipt.
+    let head = ledger.roots_since(0).unwrap().roots.pop().unwrap();
+    let proof = ledger.inclusion_proof(Seq(6), head.seq).unwrap();
+    let wire = serde_json::to_string(&proof).unwrap();
+    let decoded: InclusionProof = serde_json::from_str(&wire).unwrap();
+    verify_inclusion(&entries[5], &decoded, &head).unwrap();
+}
+
+#[test]
+fn default_config_proves_inclusion() {
+    assert_eq!(
+        LedgerConfig::default().accumulator_kind,
+        AccumulatorKind::Mmr
+    );
+    let ledger = Ledger::new(MemoryStorage::default(), LedgerConfig::default()).unwrap();
+    let entries = commit(&ledger, &[2, 3]);
+    assert_all_proofs_verify(&ledger, &entries);
+}
+
+#[test]
+fn tampered_inputs_fail_verification() {
+    let ledger = Ledger::new(MemoryStorage::default(), mmr()).unwrap();
+    let entries = commit(&ledger, &[4, 3, 6]);
+    let head = ledger.roots_since(0).unwrap().roots.pop().unwrap();
+    let proof = ledger.inclusion_proof(Seq(5), head.seq).unwrap();
+    verify_inclusion(&entries[4], &proof, &head).unwrap();
+
+    let reason = |entry: &Entry, proof: &InclusionProof, root: &RootItem| {
+        verify_inclusion(entry, proof, root)
+            .unwrap_err()
+            .reject_reason()
+    };
+
+    let mut edited = entries[4].clone();
+    edited.amount += 1;
+    assert_eq!(reason(&edited, &proof, &head), Some(RejectReason::Conflict));
+    assert_eq!(
+        reason(&entries[5], &proof, &head),
+        Some(RejectReason::Conflict)
+    );
+
+    let mut moved = proof.clone();
+    moved.seq = Seq(6);
+    assert_eq!(
+        reason(&entries[4], &moved, &head),
+        Some(RejectReason::Conflict)
+    );
+
+    let mut forged = proof.clone();
+    forged.siblings[0] = Root::from_bytes([9; 32]);
+    assert_eq!(
+        reason(&entries[4], &forged, &head),
+        Some(RejectReason::Conflict)
+    );
+
+    let mut truncated = proof.clone();
+    truncated.siblings.pop();
+    assert_eq!(
+        reason(&entries[4], &truncated, &head),
+        Some(RejectReason::Invalid)
+    );
+
+    let wrong_root = RootItem {
+        root: Root::from_bytes([7; 32]),
+        ..head.clone()
+    };
+    assert_eq!(
+        reason(&entries[4], &proof, &wrong_root),
+        Some(RejectReason::Conflict)
+    );
+
+    let earlier = ledger.roots_since(0).unwrap().roots[1].clone();
+    assert_eq!(
+        reason(&entries[4], &proof, &earlier),
+        Some(RejectReason::Invalid)
+    );
+}
+
+#[test]
+fn out_of_range_and_chained_requests_are_rejected() {
+    let ledger = Ledger::new(MemoryStorage::default(), mmr()).unwrap();
+    commit(&ledger, &[3]);
+    for (seq, root_seq) in [(0, 3), (4, 3), (2, 4)] {
+        let err = ledger.inclusion_proof(Seq(seq), Seq(root_seq)).unwrap_err();
+        assert_eq!(err.reject_reason(), Some(RejectReason::Invalid));
+    }
+
+    for kind in [AccumulatorKind::Merkle, AccumulatorKind::Verkle] {
+        let chained = Ledger::new(
+            MemoryStorage::default(),
+            LedgerConfig {
+                accumulator_kind: kind,
+                ..LedgerConfig::default()
+            },
+        )
+        .unwrap();
+        commit(&chained, &[2]);
+        let err = chained.inclusion_proof(Seq(1), Seq(2)).unwrap_err();
+        assert_eq!(err.reject_reason(), Some(RejectReason::Invalid));
+    }
+}
+
+#[test]
+fn proofs_after_restart_come_from_snapshot_peaks_and_the_log() {
+    let dir = tempdir().unwrap();
+    let open = |checkpoint_interval| {
+        Ledger::new(
+            SegmentedStorage::open(dir.path())
+                .unwrap()
+                .with_segment_records(3),
+            LedgerConfig {
+                checkpoint_interval,
+                ..mmr()
+            },
+        )
+        .unwrap()
+    };
+
+    let ledger = open(4);
+    let mut entries = commit(&ledger, &[3, 1, 4, 2]);
+    let before = ledger.roots_since(0).unwrap();
+    drop(ledger);
+
+    // The snapshot carries the peaks and nothing per leaf.
+    let snapshot = SegmentedStorage::open(dir.path())
+        .unwrap()
+        .load_snapshot()
+        .unwrap()
+        .expect("snapshot written");
+    assert_eq!(snapshot.seq, Seq(8));
+    assert_eq!(snapshot.accumulator, AccumulatorKind::Mmr);
+    assert_eq!(snapshot.peaks.len(), 1);
+
+    // No further checkpoint, so the snapshot above stays the proof anchor.
+    let ledger = open(100);
+    let after = ledger.roots_since(0).unwrap();
+    assert_eq!(before.next, after.next);
+    let (head_before, head_after) = (before.roots.last().unwrap(), after.roots.last().unwrap());
+    assert_eq!(
+        (head_before.seq, head_before.root),
+        (head_after.seq, head_after.root)
+    );
+
+    // Records past the snapshot prove from its peaks plus the log tail.
+    let more: Vec<Entry> = (0..5).map(|i| mint(100 + i)).collect();
+    ledger
+        .ingest(IngestRequest {
+            batch: more.clone(),
+            idem_id: None,
+        })
+        .unwrap();
+    entries.extend(more);
+    assert_proofs_verify_after(&ledger, &entries, snapshot.seq.get());
+
+    // Compacted records are refused rather than proven from memory.
+    let err = ledger.inclusion_proof(Seq(1), Seq(10)).unwrap_err();
+    assert_eq!(err.reject_reason(), Some(RejectReason::Invalid));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn random_histories_prove_every_entry(
+        sizes in proptest::collection::vec(1_usize..6, 1..20),
+    ) {
+        let ledger = Ledger::new(MemoryStorage::default(), mmr()).unwrap();
+        let entries = commit(&ledger, &sizes);
+        assert_all_proofs_verify(&ledger, &entries);
+    }
+}
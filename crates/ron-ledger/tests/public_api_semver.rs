@@ -20,7 +20,7 @@ fn public_surface_smoke() {
     ));
     assert!(matches!(
         cfg.accumulator_kind,
-        AccumulatorKind::Merkle | AccumulatorKind::Verkle
+        AccumulatorKind::Merkle | AccumulatorKind::Verkle | AccumulatorKind::Mmr
     ));
     assert!(matches!(cfg.pq_mode, PqMode::Off | PqMode::Hybrid));
 
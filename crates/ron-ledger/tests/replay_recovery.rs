@@ -1,7 +1,7 @@
 //! RO:WHAT — Replay recovery test for durable file storage.
 //! RO:WHY  — Pillar 12; Concerns: ECON/RES. Restarting from WAL/checkpoints must yield the same head root and balances.
 //! RO:INTERACTS — ron_ledger::engine::{Ledger, FileStorage}, api::IngestRequest.
-//! RO:INVARIANTS — replay is deterministic; no seq gaps; durable backend restores balances.
+//! RO:INVARIANTS — replay is deterministic; no seq gaps; durable backend restores balances; the WAL header version is enforced.
 //! RO:METRICS — none.
 //! RO:CONFIG — default config with amnesia storage replaced by FileStorage.
 //! RO:SECURITY — tempdir only; no secrets.
@@ -12,6 +12,7 @@ use ron_ledger::{
     config::LedgerConfig,
     engine::{FileStorage, Ledger},
     types::{AccountId, CapabilityRef, Entry, EntryKind, Kid, Nonce},
+    RejectReason,
 };
 use tempfile::tempdir;
 
@@ -62,3 +63,60 @@ fn file_backed_replay_restores_root_and_balance() {
     assert_eq!(before_balance, reopened.balance(&account).unwrap());
     assert_eq!(before.new_root, last.root);
 }
+
+#[test]
+fn wal_version_header_is_checked() {
+    let dir = tempdir().unwrap();
+    let account = AccountId::new("acct_wal").unwrap();
+    let ledger = Ledger::new(
+        FileStorage::open(dir.path()).unwrap(),
+        LedgerConfig::default(),
+    )
+    .unwrap();
+    let before = ledger
+        .ingest(IngestRequest {
+            batch: vec![Entry::new(
+                "wal-1",
+                1,
+                EntryKind::Mint,
+                account.clone(),
+                5,
+                Nonce::from_base64("AAAAAAAAAAAAAAAAAAAAAA==").unwrap(),
+                Kid::new("kid-w").unwrap(),
+                CapabilityRef::new("cap-w").unwrap(),
+                1,
+            )
+            .unwrap()],
+            idem_id: None,
+        })
+        .unwrap();
+    drop(ledger);
+
+    let wal = dir.path().join("wal.jsonl");
+    let log = std::fs::read_to_string(&wal).unwrap();
+    let (header, records) = log.split_once('\n').unwrap();
+    assert_eq!(header, r#"{"wal":1}"#);
+
+    // Logs written before the header existed still replay.
+    std::fs::write(&wal, records).unwrap();
+    let reopened = Ledger::new(
+        FileStorage::open(dir.path()).unwrap(),
+        LedgerConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(reopened.balance(&account).unwrap(), 5);
+    assert_eq!(
+        reopened.roots_since(0).unwrap().roots.last().unwrap().root,
+        before.new_root
+    );
+    drop(reopened);
+
+    std::fs::write(&wal, format!("{{\"wal\":99}}\n{records}")).unwrap();
+    let err = Ledger::new(
+        FileStorage::open(dir.path()).unwrap(),
+        LedgerConfig::default(),
+    )
+    .err()
+    .expect("a newer wal format must not load");
+    assert_eq!(err.reject_reason(), Some(RejectReason::Invalid));
+}
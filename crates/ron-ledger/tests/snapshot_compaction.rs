@@ -0,0 +1,348 @@
+//! RO:WHAT — Snapshot + segmented-log storage tests: compaction, restart from snapshot + tail, and replay equality.
+//! RO:WHY  — Pillar 12; Concerns: ECON/RES. Restart must not replay all history, yet must land on the full-log state.
+//! RO:INTERACTS — ron_ledger::engine::{Ledger, SegmentedStorage, FileStorage, replay, snapshot}, api::IngestRequest.
+//! RO:INVARIANTS — snapshot + tail == full-log replay (root, next seq, balances, roots); compaction drops only covered segments.
+//! RO:METRICS — none.
+//! RO:CONFIG — tiny checkpoint intervals, segment sizes, and idempotency windows.
+//! RO:SECURITY — tempdir only; no secrets.
+//! RO:TEST — integration + property test.
+
+use proptest::prelude::*;
+use ron_ledger::{
+    api::IngestRequest,
+    config::{AccumulatorKind, LedgerConfig},
+    engine::{
+        replay::{replay_from_snapshot, replay_records},
+        FileStorage, Ledger, SegmentedStorage, Storage,
+    },
+    types::{AccountId, CapabilityRef, Entry, EntryKind, Kid, Nonce, Seq},
+    RejectReason,
+};
+use tempfile::tempdir;
+
+fn config(checkpoint_interval: u64, idempotency_window: u64) -> LedgerConfig {
+    LedgerConfig {
+        checkpoint_interval,
+        idempotency_window,
+        ..LedgerConfig::default()
+    }
+}
+
+fn account(i: usize) -> AccountId {
+    AccountId::new(format!("acct_{i}")).unwrap()
+}
+
+fn mint(id: &str, acct: &AccountId, amount: u64) -> Entry {
+    Entry::new(
+        id,
+        amount,
+        EntryKind::Mint,
+        acct.clone(),
+        amount,
+        Nonce::from_base64("AAAAAAAAAAAAAAAAAAAAAA==").unwrap(),
+        Kid::new("kid-snap").unwrap(),
+        CapabilityRef::new("cap-snap").unwrap(),
+        1,
+    )
+    .unwrap()
+}
+
+fn burn(id: &str, acct: &AccountId, amount: u64) -> Entry {
+    Entry::new(
+        id,
+        amount,
+        EntryKind::Burn,
+        acct.clone(),
+        amount,
+        Nonce::from_base64("AQEBAQEBAQEBAQEBAQEBAQ==").unwrap(),
+        Kid::new("kid-snap").unwrap(),
+        CapabilityRef::new("cap-snap").unwrap(),
+        1,
+    )
+    .unwrap()
+}
+
+/// Deterministic workload: mints spread over a few accounts, with a burn every third batch.
+fn batches(sizes: &[usize]) -> Vec<IngestRequest> {
+    let mut n = 0;
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(b, &size)| {
+            let batch = (0..size)
+                .map(|_| {
+                    n += 1;
+                    let acct = account(n % 3);
+                    if b % 3 == 2 && n % 2 == 0 {
+                        burn(&format!("e-{n}"), &acct, 1)
+                    } else {
+                        mint(&format!("e-{n}"), &acct, 10 + n as u64)
+                    }
+                })
+                .collect();
+            IngestRequest {
+                batch,
+                idem_id: Some(format!("batch-{b}")),
+            }
+        })
+        .collect()
+}
+
+fn ingest_all<S: Storage>(ledger: &Ledger<S>, reqs: &[IngestRequest]) {
+    for req in reqs {
+        let resp = ledger.ingest(req.clone()).unwrap();
+        assert!(resp.accepted);
+    }
+}
+
+fn assert_same_state<A: Storage, B: Storage>(a: &Ledger<A>, b: &Ledger<B>) {
+    assert_eq!(a.roots_since(0).unwrap(), b.roots_since(0).unwrap());
+    for i in 0..3 {
+        assert_eq!(
+            a.balance(&account(i)).unwrap(),
+            b.balance(&account(i)).unwrap()
+        );
+    }
+}
+
+#[test]
+fn restart_from_snapshot_matches_full_log_and_compacts() {
+    let full_dir = tempdir().unwrap();
+    let seg_dir = tempdir().unwrap();
+    let cfg = config(4, 1_000);
+    let reqs = batches(&[2, 2, 3, 1, 2, 2, 4, 1, 1, 2]);
+
+    let full = Ledger::new(FileStorage::open(full_dir.path()).unwrap(), cfg.clone()).unwrap();
+    let seg = Ledger::new(
+        SegmentedStorage::open(seg_dir.path())
+            .unwrap()
+            .with_segment_records(3),
+        cfg.clone(),
+    )
+    .unwrap();
+    ingest_all(&full, &reqs);
+    ingest_all(&seg, &reqs);
+    assert_same_state(&full, &seg);
+    drop((full, seg));
+
+    let storage = SegmentedStorage::open(seg_dir.path()).unwrap();
+    let snapshot = storage.load_snapshot().unwrap().expect("snapshot written");
+    let retained = storage.load_records().unwrap();
+    assert!(
+        retained.first().unwrap().seq.get() > 1,
+        "covered segments were dropped"
+    );
+    assert!(retained.first().unwrap().seq.get() <= snapshot.seq.get() + 1);
+
+    // Snapshot + tail replays to exactly the full-log state.
+    let full_records = FileStorage::open(full_dir.path())
+        .unwrap()
+        .load_records()
+        .unwrap();
+    let from_full = replay_records(cfg.accumulator_kind, &full_records).unwrap();
+    let tail = storage.load_records_after(snapshot.seq).unwrap();
+    let from_snapshot = replay_from_snapshot(cfg.accumulator_kind, &snapshot, &tail).unwrap();
+    assert_eq!(from_full.head_root, from_snapshot.head_root);
+    assert_eq!(from_full.next_seq, from_snapshot.next_seq);
+    let nonzero = |s: &ron_ledger::engine::replay::ReplayedState| {
+        let mut v: Vec<_> = s
+            .balances
+            .iter()
+            .filter(|(_, b)| **b > 0)
+            .map(|(a, b)| (a.as_str().to_string(), *b))
+            .collect();
+        v.sort();
+        v
+    };
+    assert_eq!(nonzero(&from_full), nonzero(&from_snapshot));
+
+    let full = Ledger::new(FileStorage::open(full_dir.path()).unwrap(), cfg.clone()).unwrap();
+    let seg = Ledger::new(storage, cfg).unwrap();
+    assert_same_state(&full, &seg);
+
+    // Both keep committing identically after the restart.
+    let more = batches(&[2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3]);
+    let more: Vec<IngestRequest> = more
+        .into_iter()
+        .map(|mut r| {
+            for e in &mut r.batch {
+                e.id = format!("after-{}", e.id);
+            }
+            r.idem_id = r.idem_id.map(|id| format!("after-{id}"));
+            r
+        })
+        .collect();
+    ingest_all(&full, &more);
+    ingest_all(&seg, &more);
+    assert_same_state(&full, &seg);
+}
+
+#[test]
+fn checkpoints_fire_when_batches_straddle_the_interval() {
+    let dir = tempdir().unwrap();
+    let cfg = config(4, 1_000);
+    // Batches of three end at seqs 3, 6, 9, 12: only 12 is a multiple of 4.
+    let ledger = Ledger::new(FileStorage::open(dir.path()).unwrap(), cfg).unwrap();
+    ingest_all(&ledger, &batches(&[3, 3, 3, 3]));
+
+    let seqs: Vec<u64> = ledger
+        .checkpoints_since(0)
+        .unwrap()
+        .iter()
+        .map(|cp| cp.seq.get())
+        .collect();
+    assert_eq!(seqs, vec![6, 12]);
+}
+
+#[test]
+fn idempotency_window_survives_restart_and_stays_bounded() {
+    let dir = tempdir().unwrap();
+    let cfg = config(2, 4);
+    let acct = account(0);
+    let open = || {
+        Ledger::new(
+            SegmentedStorage::open(dir.path())
+                .unwrap()
+                .with_segment_records(2),
+            cfg.clone(),
+        )
+        .unwrap()
+    };
+
+    let ledger = open();
+    let first = IngestRequest {
+        batch: vec![mint("old", &acct, 5), mint("old-2", &acct, 5)],
+        idem_id: Some("old-batch".into()),
+    };
+    let recent = IngestRequest {
+        batch: vec![mint("recent", &acct, 7), mint("recent-2", &acct, 7)],
+        idem_id: Some("recent-batch".into()),
+    };
+    ingest_all(&ledger, std::slice::from_ref(&first));
+    for i in 0..2 {
+        ingest_all(
+            &ledger,
+            &[IngestRequest {
+                batch: vec![
+                    mint(&format!("fill-{i}"), &acct, 1),
+                    mint(&format!("fill-{i}b"), &acct, 1),
+                ],
+                idem_id: None,
+            }],
+        );
+    }
+    let recent_resp = ledger.ingest(recent.clone()).unwrap();
+    let balance = ledger.balance(&acct).unwrap();
+    drop(ledger);
+
+    let ledger = open();
+    assert_eq!(ledger.balance(&acct).unwrap(), balance);
+
+    // Inside the window: the batch response and entry ids are still remembered.
+    let retried = ledger.ingest(recent).unwrap();
+    assert_eq!(retried, recent_resp);
+    let dup = ledger
+        .ingest(IngestRequest {
+            batch: vec![mint("recent", &acct, 7)],
+            idem_id: None,
+        })
+        .unwrap();
+    assert!(!dup.accepted);
+    assert_eq!(dup.reasons[0].reason, RejectReason::Conflict);
+
+    // Outside the window: forgotten, so the old batch commits again.
+    let again = ledger.ingest(first).unwrap();
+    assert!(again.accepted);
+    assert_eq!(again.seq_start, Some(Seq(9)));
+}
+
+#[test]
+fn corrupt_snapshot_is_rejected() {
+    let dir = tempdir().unwrap();
+    let cfg = config(2, 100);
+    let ledger = Ledger::new(SegmentedStorage::open(dir.path()).unwrap(), cfg.clone()).unwrap();
+    ingest_all(&ledger, &batches(&[2, 2]));
+    drop(ledger);
+
+    let path = dir.path().join("snapshot.json");
+    let mut bytes = std::fs::read(&path).unwrap();
+    let last = bytes.len() - 2;
+    bytes[last] ^= 0x01;
+    std::fs::write(&path, bytes).unwrap();
+
+    let err = Ledger::new(SegmentedStorage::open(dir.path()).unwrap(), cfg)
+        .err()
+        .expect("tampered snapshot must not load");
+    assert_eq!(err.reject_reason(), Some(RejectReason::Conflict));
+}
+
+#[test]
+fn snapshot_from_another_accumulator_is_rejected() {
+    let dir = tempdir().unwrap();
+    let mmr = LedgerConfig {
+        accumulator_kind: AccumulatorKind::Mmr,
+        ..config(2, 100)
+    };
+    let ledger = Ledger::new(SegmentedStorage::open(dir.path()).unwrap(), mmr.clone()).unwrap();
+    ingest_all(&ledger, &batches(&[3, 2]));
+    let head = ledger.roots_since(0).unwrap().roots.pop().unwrap();
+    drop(ledger);
+
+    let snapshot = SegmentedStorage::open(dir.path())
+        .unwrap()
+        .load_snapshot()
+        .unwrap()
+        .expect("snapshot written");
+    assert_eq!(snapshot.accumulator, AccumulatorKind::Mmr);
+    assert_eq!(
+        snapshot.peaks.len(),
+        snapshot.seq.get().count_ones() as usize
+    );
+
+    let chained = LedgerConfig {
+        accumulator_kind: AccumulatorKind::Merkle,
+        ..config(2, 100)
+    };
+    let err = Ledger::new(SegmentedStorage::open(dir.path()).unwrap(), chained)
+        .err()
+        .expect("chained config must not resume an mmr snapshot");
+    assert_eq!(err.reject_reason(), Some(RejectReason::Conflict));
+
+    let reopened = Ledger::new(SegmentedStorage::open(dir.path()).unwrap(), mmr).unwrap();
+    let again = reopened.roots_since(0).unwrap().roots.pop().unwrap();
+    assert_eq!((head.seq, head.root), (again.seq, again.root));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(24))]
+
+    #[test]
+    fn snapshot_restart_equals_full_replay(
+        sizes in proptest::collection::vec(1_usize..5, 1..16),
+        checkpoint_interval in 1_u64..8,
+        segment_records in 1_u64..6,
+    ) {
+        let full_dir = tempdir().unwrap();
+        let seg_dir = tempdir().unwrap();
+        let cfg = config(checkpoint_interval, 1_000);
+        let reqs = batches(&sizes);
+
+        let full = Ledger::new(FileStorage::open(full_dir.path()).unwrap(), cfg.clone()).unwrap();
+        let seg = Ledger::new(
+            SegmentedStorage::open(seg_dir.path()).unwrap().with_segment_records(segment_records),
+            cfg.clone(),
+        )
+        .unwrap();
+        ingest_all(&full, &reqs);
+        ingest_all(&seg, &reqs);
+        drop((full, seg));
+
+        let full = Ledger::new(FileStorage::open(full_dir.path()).unwrap(), cfg.clone()).unwrap();
+        let seg = Ledger::new(
+            SegmentedStorage::open(seg_dir.path()).unwrap().with_segment_records(segment_records),
+            cfg,
+        )
+        .unwrap();
+        assert_same_state(&full, &seg);
+    }
+}
@@ -0,0 +1,158 @@
+//! RO:WHAT — Policy rollout CLI: dry-run a bundle, or diff two bundles, over recorded contexts.
+//! RO:WHY  — Review a policy change against real traffic before shipping it; DX helper only.
+//! RO:INTERACTS — `load_json`/`load_toml`, `parse::json::contexts_from_jsonl`, `explain::diff`
+//! RO:INVARIANTS — No network; reads files, writes stdout (validation warnings to stderr).
+//!                 `diff --exit-code` exits 1 when decisions change.
+
+#![cfg(feature = "cli")]
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context as _};
+use clap::{Parser, Subcommand};
+use ron_policy::explain::diff::{diff_bundles, dry_run, DecisionChange, Outcome};
+use ron_policy::{load_json, load_toml, parse, Context, PolicyBundle};
+
+/// policyctl — ron-policy rollout toolbox (dry-run, diff)
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Cli {
+    #[command(subcommand)]
+    cmd: Cmd,
+}
+
+#[derive(Subcommand, Debug)]
+enum Cmd {
+    /// Evaluate a bundle for every recorded context
+    DryRun {
+        /// Bundle (.json or .toml)
+        bundle: PathBuf,
+        /// Recorded contexts, one JSON object per line
+        #[arg(long)]
+        contexts: PathBuf,
+        /// Emit JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Report every recorded context whose effect, reason or obligations change
+    Diff {
+        /// Current bundle (.json or .toml)
+        before: PathBuf,
+        /// Proposed bundle (.json or .toml)
+        after: PathBuf,
+        /// Recorded contexts, one JSON object per line
+        #[arg(long)]
+        contexts: PathBuf,
+        /// Emit JSON instead of text
+        #[arg(long)]
+        json: bool,
+        /// Exit with status 1 when any decision changes
+        #[arg(long)]
+        exit_code: bool,
+    },
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    match cli.cmd {
+        Cmd::DryRun {
+            bundle,
+            contexts,
+            json,
+        } => {
+            let bundle = load_bundle(&bundle)?;
+            let contexts = load_contexts(&contexts)?;
+            let outcomes = dry_run(&bundle, &contexts)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&outcomes)?);
+            } else {
+                for (i, (ctx, out)) in contexts.iter().zip(&outcomes).enumerate() {
+                    println!("#{i} {}  {}", describe(ctx), outcome(out));
+                }
+            }
+        }
+        Cmd::Diff {
+            before,
+            after,
+            contexts,
+            json,
+            exit_code,
+        } => {
+            let before = load_bundle(&before)?;
+            let after = load_bundle(&after)?;
+            let contexts = load_contexts(&contexts)?;
+            let diff = diff_bundles(&before, &after, &contexts)?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&diff)?);
+            } else {
+                for change in &diff.changes {
+                    print_change(change);
+                }
+                println!(
+                    "{} of {} decisions changed",
+                    diff.changes.len(),
+                    diff.evaluated
+                );
+            }
+            if exit_code && !diff.is_empty() {
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn load_bundle(path: &Path) -> anyhow::Result<PolicyBundle> {
+    let bytes = std::fs::read(path).with_context(|| format!("read {}", path.display()))?;
+    let bundle = match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => load_json(&bytes),
+        Some("toml") => load_toml(&bytes),
+        _ => bail!("bundle must be .json or .toml: {}", path.display()),
+    };
+    bundle.with_context(|| format!("load {}", path.display()))
+}
+
+fn load_contexts(path: &Path) -> anyhow::Result<Vec<Context>> {
+    let bytes = std::fs::read(path).with_context(|| format!("read {}", path.display()))?;
+    parse::json::contexts_from_jsonl(&bytes).with_context(|| format!("load {}", path.display()))
+}
+
+fn describe(ctx: &Context) -> String {
+    format!(
+        "{} {} tenant={} region={}",
+        ctx.method, ctx.path, ctx.tenant, ctx.region
+    )
+}
+
+fn outcome(o: &Outcome) -> String {
+    let kinds: Vec<&str> = o.obligations.iter().map(|ob| ob.kind.as_str()).collect();
+    format!(
+        "{:?} reason={} obligations=[{}] rule={}",
+        o.effect,
+        o.reason.as_deref().unwrap_or("-"),
+        kinds.join(","),
+        o.rule.as_deref().unwrap_or("(defaults)")
+    )
+}
+
+fn print_change(c: &DecisionChange) {
+    let mut what = Vec::new();
+    if c.effect_changed {
+        what.push("effect");
+    }
+    if c.reason_changed {
+        what.push("reason");
+    }
+    if c.obligations_changed {
+        what.push("obligations");
+    }
+    println!(
+        "#{} {}  [{}]",
+        c.index,
+        describe(&c.context),
+        what.join(", ")
+    );
+    println!("  - {}", outcome(&c.before));
+    println!("  + {}", outcome(&c.after));
+}
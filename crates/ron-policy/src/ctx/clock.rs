@@ -19,3 +19,13 @@ impl Clock for SystemClock {
         u64::try_from(ms).unwrap_or(u64::MAX)
     }
 }
+
+/// Clock pinned to one instant (tests, replays of recorded contexts).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now_ms(&self) -> u64 {
+        self.0
+    }
+}
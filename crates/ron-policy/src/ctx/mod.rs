@@ -2,22 +2,53 @@
 //!
 //! RO:WHY  — Deterministic, testable evaluation independent of actual services.
 //!
-//! RO:INTERACTS — `engine::eval` (consumes `Context`), `ctx::{normalize,clock}`
+//! RO:INTERACTS — `engine::eval` (consumes `Context`), `ctx::{normalize,clock}`, `parse::json` (recorded corpora)
 
 pub mod clock;
 pub mod normalize;
 
-use std::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
 
 /// Minimal context the engine needs to decide.
-#[derive(Debug, Clone)]
+///
+/// Serializable so decisions can be recorded and replayed (`explain::diff`); omitted fields take the
+/// same defaults as `ContextBuilder`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Context {
+    #[serde(default = "any")]
     pub tenant: String,
+    #[serde(default = "any")]
     pub method: String,
+    #[serde(default = "any")]
     pub region: String,
+    #[serde(default)]
     pub body_bytes: u64,
+    #[serde(default)]
     pub tags: BTreeSet<String>,
+    #[serde(default)]
     pub now_ms: u64,
+    /// Request path ("/" when unknown).
+    #[serde(default = "root_path")]
+    pub path: String,
+    /// Header names are lowercased.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Numeric facts for `attrs` comparisons (e.g. risk scores, request rates).
+    #[serde(default)]
+    pub attrs: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub client_ip: Option<IpAddr>,
+}
+
+fn any() -> String {
+    "*".to_string()
+}
+
+fn root_path() -> String {
+    "/".to_string()
 }
 
 impl Context {
@@ -25,4 +56,22 @@ impl Context {
     pub fn builder() -> normalize::ContextBuilder {
         normalize::ContextBuilder::default()
     }
+
+    /// Apply the builder's casing rules to a context that did not come from the builder (e.g. a recording).
+    #[must_use]
+    pub fn normalized(mut self) -> Self {
+        self.method = self.method.to_ascii_uppercase();
+        self.tags = self
+            .tags
+            .into_iter()
+            .map(|t| t.to_ascii_lowercase())
+            .collect();
+        self.headers = self
+            .headers
+            .into_iter()
+            .map(|(k, v)| (k.to_ascii_lowercase(), v))
+            .collect();
+        self.client_ip = self.client_ip.map(|ip| ip.to_canonical());
+        self
+    }
 }
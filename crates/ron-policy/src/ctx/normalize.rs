@@ -4,7 +4,8 @@
 
 use super::clock::Clock;
 use super::Context;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::IpAddr;
 
 #[derive(Default)]
 pub struct ContextBuilder {
@@ -13,6 +14,10 @@ pub struct ContextBuilder {
     region: Option<String>,
     body_bytes: Option<u64>,
     tags: BTreeSet<String>,
+    path: Option<String>,
+    headers: BTreeMap<String, String>,
+    attrs: BTreeMap<String, f64>,
+    client_ip: Option<IpAddr>,
 }
 
 impl ContextBuilder {
@@ -46,6 +51,31 @@ impl ContextBuilder {
         self
     }
 
+    #[must_use]
+    pub fn path(mut self, p: impl Into<String>) -> Self {
+        self.path = Some(p.into());
+        self
+    }
+
+    #[must_use]
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers
+            .insert(name.into().to_ascii_lowercase(), value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn attr(mut self, key: impl Into<String>, value: f64) -> Self {
+        self.attrs.insert(key.into(), value);
+        self
+    }
+
+    #[must_use]
+    pub const fn client_ip(mut self, ip: IpAddr) -> Self {
+        self.client_ip = Some(ip);
+        self
+    }
+
     pub fn build<C: Clock>(self, clock: &C) -> Context {
         Context {
             tenant: self.tenant.unwrap_or_else(|| "*".to_string()),
@@ -56,6 +86,10 @@ impl ContextBuilder {
             body_bytes: self.body_bytes.unwrap_or(0),
             tags: self.tags,
             now_ms: clock.now_ms(),
+            path: self.path.unwrap_or_else(|| "/".to_string()),
+            headers: self.headers,
+            attrs: self.attrs,
+            client_ip: self.client_ip.map(|ip| ip.to_canonical()),
         }
     }
 }
@@ -4,7 +4,9 @@
 
 use std::time::Instant;
 
-use super::{index::RuleIndex, metrics, obligations::ObligationSet};
+use serde::Serialize;
+
+use super::{index::RuleIndex, matchers, metrics, obligations::ObligationSet};
 use crate::{
     errors::Error,
     explain::trace::{DecisionTrace, TraceStep},
@@ -12,7 +14,8 @@ use crate::{
     Context,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DecisionEffect {
     Allow,
     Deny,
@@ -128,39 +131,5 @@ impl<'a> Evaluator<'a> {
 }
 
 fn rule_matches(rule: &Rule, ctx: &Context) -> bool {
-    if let Some(tenant) = &rule.when.tenant {
-        if tenant != "*" && tenant != &ctx.tenant {
-            return false;
-        }
-    }
-
-    if let Some(method) = &rule.when.method {
-        if method != "*" && method.to_ascii_uppercase() != ctx.method {
-            return false;
-        }
-    }
-
-    if let Some(region) = &rule.when.region {
-        if region != "*" && region != &ctx.region {
-            return false;
-        }
-    }
-
-    if let Some(max_body_bytes) = rule.when.max_body_bytes {
-        if ctx.body_bytes > max_body_bytes {
-            return false;
-        }
-    }
-
-    if !rule.when.require_tags_all.is_empty()
-        && !rule
-            .when
-            .require_tags_all
-            .iter()
-            .all(|tag| ctx.tags.contains(&tag.to_ascii_lowercase()))
-    {
-        return false;
-    }
-
-    true
+    matchers::condition_matches(&rule.when, ctx)
 }
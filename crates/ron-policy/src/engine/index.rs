@@ -21,13 +21,7 @@ impl<'a> RuleIndex<'a> {
     pub fn build(bundle: &'a PolicyBundle) -> Self {
         let mut by_method: BTreeMap<String, Vec<&'a Rule>> = BTreeMap::new();
         for r in &bundle.rules {
-            // clippy(map_unwrap_or): use map_or_else
-            let key = r
-                .when
-                .method
-                .as_ref()
-                .map_or_else(|| "*".to_string(), |s| s.to_ascii_uppercase());
-            by_method.entry(key).or_default().push(r);
+            by_method.entry(index_key(r)).or_default().push(r);
         }
         Self { by_method }
     }
@@ -48,3 +42,12 @@ impl<'a> RuleIndex<'a> {
             )
     }
 }
+
+/// Index bucket for `rule`: its uppercased top-level method, or "*".
+pub(crate) fn index_key(rule: &Rule) -> String {
+    // clippy(map_unwrap_or): use map_or_else
+    rule.when
+        .method
+        .as_ref()
+        .map_or_else(|| "*".to_string(), |s| s.to_ascii_uppercase())
+}
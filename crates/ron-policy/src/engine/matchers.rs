@@ -0,0 +1,364 @@
+//! RO:WHAT — Condition matching: `all`/`any`/`not` trees plus leaf matchers (path globs, headers,
+//! time windows, numeric attrs, CIDR).
+//!
+//! RO:WHY — Keep `eval` about ordering/obligations; the matchers are pure functions of (condition, context).
+//!
+//! RO:INTERACTS — `model::{RuleCondition, TimeWindow, AttrCompare}`, `ctx::Context` (`now_ms` from `ctx::clock`),
+//! `parse::validate` (shares the `parse_*` helpers so validation and evaluation agree on syntax).
+//!
+//! RO:INVARIANTS — pure and deterministic; malformed leaves never match (validation rejects them up front);
+//! missing attrs / headers / client IP never match; times are UTC.
+
+use std::cmp::Ordering;
+use std::net::IpAddr;
+
+use crate::{
+    model::{AttrCompare, CmpOp, RuleCondition, TimeWindow},
+    Context,
+};
+
+const MS_PER_DAY: u64 = 86_400_000;
+const MS_PER_MINUTE: u64 = 60_000;
+
+/// True if `ctx` satisfies every part of `c` (see `RuleCondition` for the semantics).
+#[must_use]
+pub fn condition_matches(c: &RuleCondition, ctx: &Context) -> bool {
+    leaves_match(c, ctx)
+        && c.all.iter().all(|child| condition_matches(child, ctx))
+        && (c.any.is_empty() || c.any.iter().any(|child| condition_matches(child, ctx)))
+        && !c
+            .not
+            .as_deref()
+            .is_some_and(|child| condition_matches(child, ctx))
+}
+
+fn leaves_match(c: &RuleCondition, ctx: &Context) -> bool {
+    if let Some(tenant) = &c.tenant {
+        if tenant != "*" && tenant != &ctx.tenant {
+            return false;
+        }
+    }
+
+    if let Some(method) = &c.method {
+        if method != "*" && method.to_ascii_uppercase() != ctx.method {
+            return false;
+        }
+    }
+
+    if let Some(region) = &c.region {
+        if region != "*" && region != &ctx.region {
+            return false;
+        }
+    }
+
+    if let Some(max_body_bytes) = c.max_body_bytes {
+        if ctx.body_bytes > max_body_bytes {
+            return false;
+        }
+    }
+
+    if !c
+        .require_tags_all
+        .iter()
+        .all(|tag| ctx.tags.contains(&tag.to_ascii_lowercase()))
+    {
+        return false;
+    }
+
+    if let Some(pattern) = &c.path {
+        if !glob_matches(pattern, &ctx.path) {
+            return false;
+        }
+    }
+
+    if !c
+        .headers_present
+        .iter()
+        .all(|name| ctx.headers.contains_key(&name.to_ascii_lowercase()))
+    {
+        return false;
+    }
+
+    if !c.headers.iter().all(|(name, want)| {
+        ctx.headers
+            .get(&name.to_ascii_lowercase())
+            .is_some_and(|got| want == "*" || got == want)
+    }) {
+        return false;
+    }
+
+    if let Some(window) = &c.time {
+        if !time_matches(window, ctx.now_ms) {
+            return false;
+        }
+    }
+
+    if !c.attrs.iter().all(|cmp| attr_matches(cmp, ctx)) {
+        return false;
+    }
+
+    if !c.cidr_any.is_empty() {
+        let Some(ip) = ctx.client_ip else {
+            return false;
+        };
+        if !c
+            .cidr_any
+            .iter()
+            .filter_map(|s| Cidr::parse(s))
+            .any(|net| net.contains(ip))
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+// ---------- path globs ----------
+
+#[derive(Clone, Copy)]
+enum GlobTok {
+    Lit(u8),
+    One,
+    Star,
+    DoubleStar,
+}
+
+fn glob_tokens(pattern: &str) -> Vec<GlobTok> {
+    let bytes = pattern.as_bytes();
+    let mut toks = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'*' if bytes.get(i + 1) == Some(&b'*') => {
+                toks.push(GlobTok::DoubleStar);
+                i += 2;
+            }
+            b'*' => {
+                toks.push(GlobTok::Star);
+                i += 1;
+            }
+            b'?' => {
+                toks.push(GlobTok::One);
+                i += 1;
+            }
+            b => {
+                toks.push(GlobTok::Lit(b));
+                i += 1;
+            }
+        }
+    }
+    toks
+}
+
+/// `?` = one non-`/` byte, `*` = any run without `/`, `**` = any run. O(pattern × path), no backtracking.
+#[must_use]
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    let toks = glob_tokens(pattern);
+    let s = path.as_bytes();
+    // next[j]: does toks[i+1..] match s[j..]?
+    let mut next = vec![false; s.len() + 1];
+    next[s.len()] = true;
+    for tok in toks.iter().rev() {
+        let mut cur = vec![false; s.len() + 1];
+        for j in (0..=s.len()).rev() {
+            let here = s.get(j).copied();
+            cur[j] = match *tok {
+                GlobTok::Lit(b) => here == Some(b) && next[j + 1],
+                GlobTok::One => here.is_some_and(|b| b != b'/') && next[j + 1],
+                GlobTok::Star => next[j] || (here.is_some_and(|b| b != b'/') && cur[j + 1]),
+                GlobTok::DoubleStar => next[j] || (here.is_some() && cur[j + 1]),
+            };
+        }
+        next = cur;
+    }
+    next[0]
+}
+
+// ---------- time windows ----------
+
+fn time_matches(w: &TimeWindow, now_ms: u64) -> bool {
+    let days = i64::try_from(now_ms / MS_PER_DAY).unwrap_or(i64::MAX);
+    let minute = u32::try_from((now_ms % MS_PER_DAY) / MS_PER_MINUTE).unwrap_or(0);
+
+    let from = w.from.as_deref().map(parse_hhmm);
+    let until = w.until.as_deref().map(parse_hhmm);
+    let in_day = match (from, until) {
+        (Some(Some(f)), Some(Some(u))) if f <= u => f <= minute && minute < u,
+        (Some(Some(f)), Some(Some(u))) => minute >= f || minute < u,
+        (Some(Some(f)), None) => minute >= f,
+        (None, Some(Some(u))) => minute < u,
+        (None, None) => true,
+        _ => false,
+    };
+    if !in_day {
+        return false;
+    }
+
+    if !w.weekdays.is_empty() {
+        let today = weekday_of(days);
+        if !w.weekdays.iter().any(|d| parse_weekday(d) == Some(today)) {
+            return false;
+        }
+    }
+
+    let after_start = w
+        .date_from
+        .as_deref()
+        .map_or(true, |d| parse_date(d).is_some_and(|start| days >= start));
+    let before_end = w
+        .date_until
+        .as_deref()
+        .map_or(true, |d| parse_date(d).is_some_and(|end| days <= end));
+    after_start && before_end
+}
+
+/// "HH:MM" -> minutes since midnight.
+#[must_use]
+pub fn parse_hhmm(s: &str) -> Option<u32> {
+    let (h, m) = s.split_once(':')?;
+    if h.len() != 2 || m.len() != 2 {
+        return None;
+    }
+    let h: u32 = h.parse().ok()?;
+    let m: u32 = m.parse().ok()?;
+    (h < 24 && m < 60).then_some(h * 60 + m)
+}
+
+/// "mon".."sun" (case-insensitive) -> 0..=6.
+#[must_use]
+pub fn parse_weekday(s: &str) -> Option<u8> {
+    const DAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+    let s = s.to_ascii_lowercase();
+    DAYS.iter()
+        .position(|d| *d == s)
+        .and_then(|i| u8::try_from(i).ok())
+}
+
+/// "YYYY-MM-DD" -> days since 1970-01-01.
+#[must_use]
+pub fn parse_date(s: &str) -> Option<i64> {
+    let mut parts = s.splitn(3, '-');
+    let (y, m, d) = (parts.next()?, parts.next()?, parts.next()?);
+    if y.len() != 4 || m.len() != 2 || d.len() != 2 {
+        return None;
+    }
+    let y: i64 = y.parse().ok()?;
+    let m: i64 = m.parse().ok()?;
+    let d: i64 = d.parse().ok()?;
+    if !(1..=12).contains(&m) || d < 1 || d > days_in_month(y, m) {
+        return None;
+    }
+    Some(days_from_civil(y, m, d))
+}
+
+const fn days_in_month(y: i64, m: i64) -> i64 {
+    match m {
+        2 if (y % 4 == 0 && y % 100 != 0) || y % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Proleptic Gregorian date -> days since the Unix epoch.
+const fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// 0 = Monday (1970-01-01 was a Thursday).
+fn weekday_of(days: i64) -> u8 {
+    u8::try_from((days + 3).rem_euclid(7)).unwrap_or(0)
+}
+
+// ---------- numeric attrs ----------
+
+fn attr_matches(cmp: &AttrCompare, ctx: &Context) -> bool {
+    let Some(got) = ctx.attrs.get(&cmp.key) else {
+        return false;
+    };
+    if got.is_nan() {
+        return false;
+    }
+    cmp_holds(got.total_cmp(&cmp.value), cmp.op)
+}
+
+pub(crate) const fn cmp_holds(ord: Ordering, op: CmpOp) -> bool {
+    match op {
+        CmpOp::Eq => matches!(ord, Ordering::Equal),
+        CmpOp::Ne => !matches!(ord, Ordering::Equal),
+        CmpOp::Lt => matches!(ord, Ordering::Less),
+        CmpOp::Le => !matches!(ord, Ordering::Greater),
+        CmpOp::Gt => matches!(ord, Ordering::Greater),
+        CmpOp::Ge => !matches!(ord, Ordering::Less),
+    }
+}
+
+// ---------- CIDR ----------
+
+/// Parsed CIDR block (IPv4-mapped IPv6 is folded to IPv4).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    net: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    /// Parse `addr/prefix` or a bare address (full-length prefix).
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((a, p)) => (a, Some(p)),
+            None => (s, None),
+        };
+        let addr: IpAddr = addr.trim().parse().ok()?;
+        let mapped = matches!(addr, IpAddr::V6(_)) && addr.to_canonical().is_ipv4();
+        let net = addr.to_canonical();
+        let max = if net.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            None => max,
+            Some(p) => {
+                let p: u8 = p.trim().parse().ok()?;
+                // A mapped v6 prefix covers 96 bits of ::ffff:0:0/96 before the v4 part.
+                let p = if mapped { p.checked_sub(96)? } else { p };
+                if p > max {
+                    return None;
+                }
+                p
+            }
+        };
+        Some(Self { net, prefix })
+    }
+
+    /// True if `ip` is inside this block.
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.net, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(net) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(net) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+
+    /// True if every address in `other` is inside `self`.
+    #[must_use]
+    pub fn covers(&self, other: &Self) -> bool {
+        self.prefix <= other.prefix && self.contains(other.net)
+    }
+}
@@ -1,6 +1,7 @@
 //! RO:WHAT — Policy evaluation engine modules.
 pub mod eval;
 pub mod index;
+pub mod matchers;
 pub mod metrics;
 pub mod obligations;
 pub mod reason;
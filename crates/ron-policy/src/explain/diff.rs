@@ -0,0 +1,119 @@
+//! RO:WHAT — Dry-run a bundle over recorded contexts and diff two bundles' decisions.
+//!
+//! RO:WHY — Make rollouts reviewable: list every recorded request whose effect, reason or obligations
+//! would change, and which rule (from `DecisionTrace`) decided before and after.
+//!
+//! RO:INTERACTS — `engine::eval::Evaluator`, `explain::trace::DecisionTrace::decided_by`,
+//! `parse::json::contexts_from_jsonl` (corpus loader), `bin/policyctl` (CLI).
+//!
+//! RO:INVARIANTS — pure: contexts carry their own `now_ms`, so the same inputs give the same report;
+//! changes are reported in corpus order.
+
+use serde::Serialize;
+
+use crate::{
+    engine::eval::{Decision, DecisionEffect, Evaluator},
+    errors::Error,
+    model::{Obligation, PolicyBundle},
+    Context,
+};
+
+/// What one bundle decided for one context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Outcome {
+    pub effect: DecisionEffect,
+    pub reason: Option<String>,
+    pub obligations: Vec<Obligation>,
+    /// Rule that fired; `None` when the bundle defaults decided.
+    pub rule: Option<String>,
+}
+
+impl From<Decision> for Outcome {
+    fn from(d: Decision) -> Self {
+        Self {
+            effect: d.effect,
+            rule: d.trace.decided_by().map(str::to_string),
+            reason: d.reason,
+            obligations: d.obligations.items,
+        }
+    }
+}
+
+/// One recorded context whose decision differs between the two bundles.
+#[derive(Debug, Clone, Serialize)]
+pub struct DecisionChange {
+    /// Position in the corpus (0-based).
+    pub index: usize,
+    pub context: Context,
+    pub effect_changed: bool,
+    pub reason_changed: bool,
+    pub obligations_changed: bool,
+    pub before: Outcome,
+    pub after: Outcome,
+}
+
+/// Result of `diff_bundles`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BundleDiff {
+    /// Contexts evaluated.
+    pub evaluated: usize,
+    pub changes: Vec<DecisionChange>,
+}
+
+impl BundleDiff {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Evaluate `bundle` for every context, in order.
+///
+/// # Errors
+///
+/// Returns `Error::Validation` if the bundle is invalid, or `Error::Eval` if an evaluation fails.
+pub fn dry_run(bundle: &PolicyBundle, contexts: &[Context]) -> Result<Vec<Outcome>, Error> {
+    let ev = Evaluator::new(bundle)?;
+    contexts
+        .iter()
+        .map(|ctx| ev.evaluate(ctx).map(Outcome::from))
+        .collect()
+}
+
+/// Evaluate both bundles for every context and report the contexts whose effect, reason or
+/// obligations differ. A different deciding rule with an identical outcome is not a change.
+///
+/// # Errors
+///
+/// Returns `Error::Validation` if either bundle is invalid, or `Error::Eval` if an evaluation fails.
+pub fn diff_bundles(
+    before: &PolicyBundle,
+    after: &PolicyBundle,
+    contexts: &[Context],
+) -> Result<BundleDiff, Error> {
+    let old = Evaluator::new(before)?;
+    let new = Evaluator::new(after)?;
+    let mut diff = BundleDiff {
+        evaluated: contexts.len(),
+        changes: Vec::new(),
+    };
+    for (index, ctx) in contexts.iter().enumerate() {
+        let before = Outcome::from(old.evaluate(ctx)?);
+        let after = Outcome::from(new.evaluate(ctx)?);
+        let effect_changed = before.effect != after.effect;
+        let reason_changed = before.reason != after.reason;
+        let obligations_changed = before.obligations != after.obligations;
+        if effect_changed || reason_changed || obligations_changed {
+            diff.changes.push(DecisionChange {
+                index,
+                context: ctx.clone(),
+                effect_changed,
+                reason_changed,
+                obligations_changed,
+                before,
+                after,
+            });
+        }
+    }
+    Ok(diff)
+}
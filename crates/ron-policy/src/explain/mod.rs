@@ -1,2 +1,3 @@
-//! RO:WHAT — Explainability surface (trace).
+//! RO:WHAT — Explainability surface (trace, bundle dry-run/diff).
+pub mod diff;
 pub mod trace;
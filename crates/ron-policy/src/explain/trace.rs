@@ -21,6 +21,15 @@ impl DecisionTrace {
         });
         d
     }
+
+    /// Id of the rule that decided, if any (`None` when defaults decided).
+    #[must_use]
+    pub fn decided_by(&self) -> Option<&str> {
+        self.steps.iter().rev().find_map(|step| match step {
+            TraceStep::RuleHit { id, .. } => Some(id.as_str()),
+            _ => None,
+        })
+    }
 }
 
 impl TraceStep {
@@ -1,4 +1,4 @@
-//! RO:WHAT — Policy model (DTOs): `PolicyBundle`, Rule, Conditions, Actions, Obligations.
+//! RO:WHAT — Policy model (DTOs): `PolicyBundle`, Rule, Conditions (boolean trees + matchers), Actions, Obligations.
 //!
 //! RO:WHY  — DTO hygiene: `#[serde(deny_unknown_fields)]` so policies are explicit and auditable.
 //!
@@ -47,7 +47,9 @@ pub struct Rule {
     pub reason: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Conditions are conjunctive: every field that is set must hold, plus every `all` child, at least one
+/// `any` child (when non-empty), and the `not` child must not hold.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct RuleCondition {
     /// Tenant or "*" for any.
@@ -65,9 +67,77 @@ pub struct RuleCondition {
     /// Arbitrary tags (all must be present in context if specified).
     #[serde(default)]
     pub require_tags_all: Vec<String>,
+    /// Path glob: `?` one char, `*` any run within a segment, `**` anything (across `/`).
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Header names that must be present (case-insensitive).
+    #[serde(default)]
+    pub headers_present: Vec<String>,
+    /// Header name (case-insensitive) -> exact value, or "*" for any value.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// UTC time-of-day / weekday / date window, checked against `Context::now_ms`.
+    #[serde(default)]
+    pub time: Option<TimeWindow>,
+    /// Numeric comparisons on `Context::attrs`; a missing attribute never matches.
+    #[serde(default)]
+    pub attrs: Vec<AttrCompare>,
+    /// Client IP must fall inside one of these CIDRs (`10.0.0.0/8`, `2001:db8::/32`, bare IPs).
+    #[serde(default)]
+    pub cidr_any: Vec<String>,
+    /// Every child must match.
+    #[serde(default)]
+    pub all: Vec<Self>,
+    /// At least one child must match (ignored when empty).
+    #[serde(default)]
+    pub any: Vec<Self>,
+    /// Child must not match.
+    #[serde(default)]
+    pub not: Option<Box<Self>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// UTC window; every bound that is set must hold.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct TimeWindow {
+    /// Start of day, inclusive ("HH:MM").
+    #[serde(default)]
+    pub from: Option<String>,
+    /// End of day, exclusive ("HH:MM"); a window with `from` > `until` wraps midnight.
+    #[serde(default)]
+    pub until: Option<String>,
+    /// Allowed weekdays ("mon".."sun"); empty = every day.
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+    /// First allowed date, inclusive ("YYYY-MM-DD").
+    #[serde(default)]
+    pub date_from: Option<String>,
+    /// Last allowed date, inclusive ("YYYY-MM-DD").
+    #[serde(default)]
+    pub date_until: Option<String>,
+}
+
+/// `attrs[key] <op> value`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AttrCompare {
+    pub key: String,
+    pub op: CmpOp,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields)]
 pub struct Obligation {
     /// Name (e.g., "add-header", "mask-field", "log-audit")
@@ -1,8 +1,8 @@
-//! RO:WHAT — JSON loader for `PolicyBundle` (strict).
+//! RO:WHAT — JSON loaders: `PolicyBundle` (strict) and recorded `Context` corpora (JSON Lines).
 //!
-//! RO:INVARIANTS — `deny_unknown_fields` enforced by DTOs.
+//! RO:INVARIANTS — `deny_unknown_fields` enforced by DTOs; recorded contexts are normalized on load.
 
-use crate::{errors::Error, model::PolicyBundle};
+use crate::{ctx::Context, errors::Error, model::PolicyBundle};
 
 /// Parse a `PolicyBundle` from JSON bytes.
 ///
@@ -12,3 +12,23 @@ use crate::{errors::Error, model::PolicyBundle};
 pub fn from_slice(bytes: &[u8]) -> Result<PolicyBundle, Error> {
     serde_json::from_slice::<PolicyBundle>(bytes).map_err(|e| Error::Parse(e.to_string()))
 }
+
+/// Parse recorded contexts: one JSON object per line; blank lines and `#` comments are skipped.
+///
+/// # Errors
+///
+/// Returns `Error::Parse` naming the first line that is not valid UTF-8 or a valid `Context`.
+pub fn contexts_from_jsonl(bytes: &[u8]) -> Result<Vec<Context>, Error> {
+    let text = std::str::from_utf8(bytes).map_err(|e| Error::Parse(e.to_string()))?;
+    let mut out = Vec::new();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let ctx = serde_json::from_str::<Context>(line)
+            .map_err(|e| Error::Parse(format!("context line {}: {e}", n + 1)))?;
+        out.push(ctx.normalized());
+    }
+    Ok(out)
+}
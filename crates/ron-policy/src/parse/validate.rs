@@ -1,25 +1,51 @@
 //! RO:WHAT — Structural validation for `PolicyBundle`.
 //!
-//! RO:WHY — Keep policy declarative: deny ambiguous bundles, oversized caps, and
-//! authority-shaped conditions/obligations that could be mistaken for wallet/ledger truth.
+//! RO:WHY — Keep policy declarative: deny ambiguous bundles, oversized caps, unsatisfiable rules, rules
+//! shadowed by an earlier rule, and authority-shaped conditions/obligations that could be mistaken for
+//! wallet/ledger truth. A shadowed rule never fires, so the bundle does not do what its author wrote.
 //!
-//! RO:INTERACTS — `model::PolicyBundle`, `model::Obligation`, parse loaders.
+//! RO:INTERACTS — `model::PolicyBundle`, `model::Obligation`, `engine::matchers` (shared leaf syntax),
+//! `engine::index` (evaluation order), parse loaders.
 //!
-//! RO:INVARIANTS — deny-by-default; no paid-unlock authority; no receipt/balance/finality authority.
+//! RO:INVARIANTS — deny-by-default; no paid-unlock authority; no receipt/balance/finality authority;
+//! dead-rule detection is conservative (only provable cases are reported).
 
 use crate::{
+    engine::{
+        index::index_key,
+        matchers::{parse_date, parse_hhmm, parse_weekday, Cidr},
+    },
     errors::Error,
-    model::{Obligation, PolicyBundle},
+    model::{AttrCompare, CmpOp, Obligation, PolicyBundle, RuleCondition, TimeWindow},
 };
-use std::collections::BTreeSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
 
-/// Validate a `PolicyBundle` for basic invariants.
+/// Maximum nesting of `all`/`any`/`not` condition trees.
+pub const MAX_CONDITION_DEPTH: usize = 8;
+
+/// Validate a `PolicyBundle`: `validate_structure`, then reject rules shadowed by an earlier rule.
 ///
 /// # Errors
 ///
-/// Returns `Error::Validation` if the bundle violates invariants such as duplicate rule IDs,
-/// empty IDs, body caps over 1 MiB, authority-shaped condition tags, or authority-shaped obligations.
+/// Returns `Error::Validation` for anything `validate_structure` rejects, or for the first rule
+/// reported by `shadowed_rules`.
 pub fn validate(b: &PolicyBundle) -> Result<(), Error> {
+    validate_structure(b)?;
+    shadowed_rules(b)
+        .into_iter()
+        .next()
+        .map_or(Ok(()), |w| Err(Error::Validation(w)))
+}
+
+/// Per-rule invariants only (no shadowing check); for tools that list every finding of a draft.
+///
+/// # Errors
+///
+/// Returns `Error::Validation` if the bundle violates invariants such as duplicate rule IDs,
+/// empty IDs, body caps over 1 MiB, malformed or over-deep conditions, unsatisfiable rules,
+/// authority-shaped condition tags/attrs, or authority-shaped obligations.
+pub fn validate_structure(b: &PolicyBundle) -> Result<(), Error> {
     if b.version == 0 {
         return Err(Error::Validation("version must be ≥ 1".into()));
     }
@@ -32,19 +58,13 @@ pub fn validate(b: &PolicyBundle) -> Result<(), Error> {
         if !ids.insert(&r.id) {
             return Err(Error::Validation(format!("duplicate rule id: {}", r.id)));
         }
-        if let Some(n) = r.when.max_body_bytes {
-            if n > 1_048_576 {
-                return Err(Error::Validation(format!(
-                    "rule {} max_body_bytes > 1MiB",
-                    r.id
-                )));
-            }
-        }
 
-        validate_condition_tags(&r.id, &r.when.require_tags_all)?;
+        validate_condition(&r.id, &r.when, 1)?;
         validate_obligations(&r.id, &r.obligations)?;
     }
 
+    validate_satisfiable(b)?;
+
     if let Some(n) = b.defaults.max_body_bytes {
         if n > 1_048_576 {
             return Err(Error::Validation("defaults.max_body_bytes > 1MiB".into()));
@@ -54,6 +74,404 @@ pub fn validate(b: &PolicyBundle) -> Result<(), Error> {
     Ok(())
 }
 
+/// Rules fully covered by a rule evaluated before them (same index bucket earlier in the bundle, or a
+/// method bucket ahead of "*"), so they never fire.
+#[must_use]
+pub fn shadowed_rules(b: &PolicyBundle) -> Vec<String> {
+    let summaries: Vec<Summary<'_>> = b.rules.iter().map(|r| Summary::of(&r.when)).collect();
+    let keys: Vec<String> = b.rules.iter().map(index_key).collect();
+    let mut out = Vec::new();
+    for (j, later) in b.rules.iter().enumerate() {
+        let shadow = b.rules.iter().enumerate().find(|&(i, _)| {
+            let evaluated_first = if keys[i] == keys[j] {
+                i < j
+            } else {
+                keys[j] == "*"
+                    && summaries[j].methods.len() == 1
+                    && summaries[j].methods.contains(&keys[i])
+            };
+            evaluated_first && summaries[i].covers(&summaries[j])
+        });
+        if let Some((_, earlier)) = shadow {
+            out.push(format!(
+                "rule {} is shadowed by earlier rule {}",
+                later.id, earlier.id
+            ));
+        }
+    }
+    out
+}
+
+fn validate_condition(rule_id: &str, c: &RuleCondition, depth: usize) -> Result<(), Error> {
+    if depth > MAX_CONDITION_DEPTH {
+        return Err(Error::Validation(format!(
+            "rule {rule_id} condition nesting exceeds {MAX_CONDITION_DEPTH}"
+        )));
+    }
+
+    if let Some(n) = c.max_body_bytes {
+        if n > 1_048_576 {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} max_body_bytes > 1MiB"
+            )));
+        }
+    }
+
+    validate_condition_tags(rule_id, &c.require_tags_all)?;
+
+    if c.path.as_deref().is_some_and(|p| p.trim().is_empty()) {
+        return Err(Error::Validation(format!(
+            "rule {rule_id} path must be non-empty"
+        )));
+    }
+
+    if c.headers_present
+        .iter()
+        .chain(c.headers.keys())
+        .any(|name| name.trim().is_empty())
+    {
+        return Err(Error::Validation(format!(
+            "rule {rule_id} header names must be non-empty"
+        )));
+    }
+
+    if let Some(w) = &c.time {
+        validate_time_window(rule_id, w)?;
+    }
+
+    for (index, cmp) in c.attrs.iter().enumerate() {
+        if cmp.key.trim().is_empty() {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} attrs {index} key must be non-empty"
+            )));
+        }
+        if is_forbidden_authority_field_shape(&normalize_authority_token(&cmp.key)) {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} attrs {index} key looks like economic authority: {}",
+                cmp.key
+            )));
+        }
+        if !cmp.value.is_finite() {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} attrs {index} value must be finite"
+            )));
+        }
+    }
+
+    for cidr in &c.cidr_any {
+        if Cidr::parse(cidr).is_none() {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} cidr_any has invalid CIDR: {cidr}"
+            )));
+        }
+    }
+
+    for child in c.all.iter().chain(&c.any).chain(c.not.as_deref()) {
+        validate_condition(rule_id, child, depth + 1)?;
+    }
+
+    Ok(())
+}
+
+fn validate_time_window(rule_id: &str, w: &TimeWindow) -> Result<(), Error> {
+    for hhmm in w.from.iter().chain(&w.until) {
+        if parse_hhmm(hhmm).is_none() {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} time expects HH:MM, got {hhmm}"
+            )));
+        }
+    }
+    for day in &w.weekdays {
+        if parse_weekday(day).is_none() {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} time has unknown weekday: {day}"
+            )));
+        }
+    }
+    for date in w.date_from.iter().chain(&w.date_until) {
+        if parse_date(date).is_none() {
+            return Err(Error::Validation(format!(
+                "rule {rule_id} time expects YYYY-MM-DD, got {date}"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject rules that no context can satisfy on their own.
+fn validate_satisfiable(b: &PolicyBundle) -> Result<(), Error> {
+    for r in &b.rules {
+        if let Some(why) = Summary::of(&r.when).unsatisfiable() {
+            return Err(Error::Validation(format!(
+                "rule {} can never match: {why}",
+                r.id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Conjunctive facts of a condition (its leaves plus every `all` child, flattened).
+#[derive(Default)]
+struct Summary<'a> {
+    tenants: BTreeSet<&'a str>,
+    methods: BTreeSet<String>,
+    regions: BTreeSet<&'a str>,
+    max_body: Option<u64>,
+    tags: BTreeSet<String>,
+    paths: BTreeSet<&'a str>,
+    headers_present: BTreeSet<String>,
+    header_values: BTreeMap<String, BTreeSet<&'a str>>,
+    times: Vec<&'a TimeWindow>,
+    attrs: BTreeMap<&'a str, Range>,
+    cidr_sets: Vec<Vec<Cidr>>,
+    any_groups: Vec<&'a [RuleCondition]>,
+    nots: Vec<&'a RuleCondition>,
+}
+
+impl<'a> Summary<'a> {
+    fn of(c: &'a RuleCondition) -> Self {
+        let mut s = Self::default();
+        s.add(c);
+        s
+    }
+
+    fn add(&mut self, c: &'a RuleCondition) {
+        self.tenants.extend(specific(c.tenant.as_deref()));
+        self.methods
+            .extend(specific(c.method.as_deref()).map(str::to_ascii_uppercase));
+        self.regions.extend(specific(c.region.as_deref()));
+        if let Some(n) = c.max_body_bytes {
+            self.max_body = Some(self.max_body.map_or(n, |m| m.min(n)));
+        }
+        self.tags
+            .extend(c.require_tags_all.iter().map(|t| t.to_ascii_lowercase()));
+        self.paths.extend(c.path.as_deref());
+        self.headers_present
+            .extend(c.headers_present.iter().map(|h| h.to_ascii_lowercase()));
+        for (name, value) in &c.headers {
+            let name = name.to_ascii_lowercase();
+            self.headers_present.insert(name.clone());
+            if value != "*" {
+                self.header_values.entry(name).or_default().insert(value);
+            }
+        }
+        self.times.extend(c.time.as_ref());
+        for cmp in &c.attrs {
+            self.attrs.entry(&cmp.key).or_default().apply(cmp);
+        }
+        if !c.cidr_any.is_empty() {
+            self.cidr_sets
+                .push(c.cidr_any.iter().filter_map(|s| Cidr::parse(s)).collect());
+        }
+        if !c.any.is_empty() {
+            self.any_groups.push(&c.any);
+        }
+        self.nots.extend(c.not.as_deref());
+        for child in &c.all {
+            self.add(child);
+        }
+    }
+
+    /// Why no context can satisfy these facts, if that is provable.
+    fn unsatisfiable(&self) -> Option<String> {
+        for (field, n) in [
+            ("tenant", self.tenants.len()),
+            ("method", self.methods.len()),
+            ("region", self.regions.len()),
+        ] {
+            if n > 1 {
+                return Some(format!("conflicting {field} values"));
+            }
+        }
+        if let Some((name, _)) = self.header_values.iter().find(|(_, v)| v.len() > 1) {
+            return Some(format!("conflicting values for header {name}"));
+        }
+        if let Some((key, _)) = self.attrs.iter().find(|(_, r)| r.is_empty()) {
+            return Some(format!("attrs on {key} admit no value"));
+        }
+        for w in &self.times {
+            if w.from.is_some() && w.from == w.until {
+                return Some("time window from == until is empty".into());
+            }
+            let from = w.date_from.as_deref().and_then(parse_date);
+            let until = w.date_until.as_deref().and_then(parse_date);
+            if let (Some(f), Some(u)) = (from, until) {
+                if f > u {
+                    return Some("time date_from is after date_until".into());
+                }
+            }
+        }
+        if self
+            .any_groups
+            .iter()
+            .any(|group| group.iter().all(|c| Self::of(c).unsatisfiable().is_some()))
+        {
+            return Some("no `any` branch can match".into());
+        }
+        if self.nots.iter().any(|c| Self::of(c).is_unconstrained()) {
+            return Some("`not` of a condition that always matches".into());
+        }
+        None
+    }
+
+    fn is_unconstrained(&self) -> bool {
+        self.tenants.is_empty()
+            && self.methods.is_empty()
+            && self.regions.is_empty()
+            && self.max_body.is_none()
+            && self.tags.is_empty()
+            && self.paths.iter().all(|p| matches_any_path(p))
+            && self.headers_present.is_empty()
+            && self.times.iter().all(|w| **w == TimeWindow::default())
+            && self.attrs.is_empty()
+            && self.cidr_sets.is_empty()
+            && self.any_groups.is_empty()
+            && self.nots.is_empty()
+    }
+
+    /// True if every context matching `other` also matches `self` (only claimed when provable).
+    fn covers(&self, other: &Self) -> bool {
+        if !self.any_groups.is_empty() || !self.nots.is_empty() {
+            return false;
+        }
+        self.tenants.is_subset(&other.tenants)
+            && self.methods.is_subset(&other.methods)
+            && self.regions.is_subset(&other.regions)
+            && self
+                .max_body
+                .map_or(true, |m| other.max_body.is_some_and(|o| o <= m))
+            && self.tags.is_subset(&other.tags)
+            && self
+                .paths
+                .iter()
+                .all(|p| other.paths.contains(p) || matches_any_path(p))
+            && self.headers_present.is_subset(&other.headers_present)
+            && self.header_values.iter().all(|(name, values)| {
+                other
+                    .header_values
+                    .get(name)
+                    .is_some_and(|o| values.is_subset(o))
+            })
+            && self.times.iter().all(|w| other.times.contains(w))
+            && self
+                .attrs
+                .iter()
+                .all(|(key, range)| other.attrs.get(key).is_some_and(|o| o.implies(range)))
+            && self.cidr_sets.iter().all(|mine| {
+                other
+                    .cidr_sets
+                    .iter()
+                    .any(|theirs| theirs.iter().all(|t| mine.iter().any(|m| m.covers(t))))
+            })
+    }
+}
+
+fn specific(v: Option<&str>) -> Option<&str> {
+    v.filter(|v| *v != "*")
+}
+
+/// True for path globs that match every path ("**", "***", ...).
+fn matches_any_path(p: &str) -> bool {
+    p.len() > 1 && p.chars().all(|c| c == '*')
+}
+
+/// Values admitted by the `attrs` comparisons on one key: `lo`/`hi` are `(bound, inclusive)`.
+#[derive(Default)]
+struct Range {
+    lo: Option<(f64, bool)>,
+    hi: Option<(f64, bool)>,
+    ne: Vec<f64>,
+    ops: Vec<(CmpOp, f64)>,
+}
+
+impl Range {
+    fn apply(&mut self, cmp: &AttrCompare) {
+        let v = cmp.value;
+        self.ops.push((cmp.op, v));
+        match cmp.op {
+            CmpOp::Eq => {
+                self.raise_lo(v, true);
+                self.lower_hi(v, true);
+            }
+            CmpOp::Ne => self.ne.push(v),
+            CmpOp::Lt => self.lower_hi(v, false),
+            CmpOp::Le => self.lower_hi(v, true),
+            CmpOp::Gt => self.raise_lo(v, false),
+            CmpOp::Ge => self.raise_lo(v, true),
+        }
+    }
+
+    fn raise_lo(&mut self, v: f64, inclusive: bool) {
+        let tighter = self.lo.map_or(true, |(cur, inc)| match v.total_cmp(&cur) {
+            Ordering::Greater => true,
+            Ordering::Equal => inc && !inclusive,
+            Ordering::Less => false,
+        });
+        if tighter {
+            self.lo = Some((v, inclusive));
+        }
+    }
+
+    fn lower_hi(&mut self, v: f64, inclusive: bool) {
+        let tighter = self.hi.map_or(true, |(cur, inc)| match v.total_cmp(&cur) {
+            Ordering::Less => true,
+            Ordering::Equal => inc && !inclusive,
+            Ordering::Greater => false,
+        });
+        if tighter {
+            self.hi = Some((v, inclusive));
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        let (Some((lo, lo_inc)), Some((hi, hi_inc))) = (self.lo, self.hi) else {
+            return false;
+        };
+        match lo.total_cmp(&hi) {
+            Ordering::Greater => true,
+            Ordering::Equal => {
+                !(lo_inc && hi_inc) || self.ne.iter().any(|n| n.total_cmp(&lo).is_eq())
+            }
+            Ordering::Less => false,
+        }
+    }
+
+    /// True if every value admitted by `self` passes every comparison recorded in `other`.
+    fn implies(&self, other: &Self) -> bool {
+        other.ops.iter().all(|&(op, v)| self.implies_cmp(op, v))
+    }
+
+    fn implies_cmp(&self, op: CmpOp, v: f64) -> bool {
+        let below = |hi: Option<(f64, bool)>, strict: bool| {
+            hi.is_some_and(|(h, inc)| match h.total_cmp(&v) {
+                Ordering::Less => true,
+                Ordering::Equal => !(strict && inc),
+                Ordering::Greater => false,
+            })
+        };
+        let above = |lo: Option<(f64, bool)>, strict: bool| {
+            lo.is_some_and(|(l, inc)| match l.total_cmp(&v) {
+                Ordering::Greater => true,
+                Ordering::Equal => !(strict && inc),
+                Ordering::Less => false,
+            })
+        };
+        match op {
+            CmpOp::Lt => below(self.hi, true),
+            CmpOp::Le => below(self.hi, false),
+            CmpOp::Gt => above(self.lo, true),
+            CmpOp::Ge => above(self.lo, false),
+            CmpOp::Eq => above(self.lo, false) && below(self.hi, false),
+            CmpOp::Ne => {
+                self.ne.iter().any(|n| n.total_cmp(&v).is_eq())
+                    || below(self.hi, true)
+                    || above(self.lo, true)
+            }
+        }
+    }
+}
+
 fn validate_condition_tags(rule_id: &str, tags: &[String]) -> Result<(), Error> {
     for (index, tag) in tags.iter().enumerate() {
         if tag.trim().is_empty() {
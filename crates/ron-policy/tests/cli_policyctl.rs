@@ -0,0 +1,56 @@
+//! RO:WHAT — CLI contract tests for `policyctl`.
+//! RO:WHY  — Keep the rollout CLI output and exit codes stable. Only runs with `--features cli`.
+
+#![cfg(feature = "cli")]
+
+use assert_cmd::cargo::cargo_bin_cmd;
+
+const BEFORE: &str = "tests/vectors/rollout_before.json";
+const AFTER: &str = "tests/vectors/rollout_after.json";
+const CONTEXTS: &str = "tests/vectors/rollout_contexts.jsonl";
+
+#[test]
+fn diff_prints_changes_with_deciding_rules() {
+    let mut cmd = cargo_bin_cmd!("policyctl");
+    cmd.args(["diff", BEFORE, AFTER, "--contexts", CONTEXTS]);
+    let assert = cmd.assert().success();
+    let out = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(
+        out.contains("#1 GET /a tenant=t region=EU  [effect, reason]"),
+        "{out}"
+    );
+    assert!(out.contains("rule=allow-gets"), "{out}");
+    assert!(out.contains("rule=deny-eu-gets"), "{out}");
+    assert!(out.contains("obligations=[log-audit]"), "{out}");
+    assert!(out.contains("2 of 4 decisions changed"), "{out}");
+}
+
+#[test]
+fn diff_exit_code_flags_changes() {
+    cargo_bin_cmd!("policyctl")
+        .args(["diff", BEFORE, AFTER, "--contexts", CONTEXTS, "--exit-code"])
+        .assert()
+        .code(1);
+    cargo_bin_cmd!("policyctl")
+        .args([
+            "diff",
+            BEFORE,
+            BEFORE,
+            "--contexts",
+            CONTEXTS,
+            "--exit-code",
+        ])
+        .assert()
+        .success();
+}
+
+#[test]
+fn dry_run_emits_json() {
+    let mut cmd = cargo_bin_cmd!("policyctl");
+    cmd.args(["dry-run", AFTER, "--contexts", CONTEXTS, "--json"]);
+    let assert = cmd.assert().success();
+    let out = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v[1]["effect"], "deny");
+    assert_eq!(v[1]["rule"], "deny-eu-gets");
+}
@@ -0,0 +1,55 @@
+//! Dry-run and diff of two bundles over recorded contexts: only changed decisions are reported, with
+//! the deciding rule on each side.
+
+use ron_policy::explain::diff::{diff_bundles, dry_run};
+use ron_policy::{load_json, parse, DecisionEffect};
+
+fn corpus() -> Vec<ron_policy::Context> {
+    parse::json::contexts_from_jsonl(include_bytes!("vectors/rollout_contexts.jsonl")).unwrap()
+}
+
+#[test]
+fn diff_reports_effect_reason_and_obligation_changes() {
+    let before = load_json(include_bytes!("vectors/rollout_before.json")).unwrap();
+    let after = load_json(include_bytes!("vectors/rollout_after.json")).unwrap();
+    let diff = diff_bundles(&before, &after, &corpus()).unwrap();
+
+    assert_eq!(diff.evaluated, 4);
+    let indices: Vec<usize> = diff.changes.iter().map(|c| c.index).collect();
+    assert_eq!(indices, [1, 2], "US GET and DELETE are unchanged");
+
+    let eu = &diff.changes[0];
+    assert!(eu.effect_changed && eu.reason_changed && !eu.obligations_changed);
+    assert_eq!(eu.context.region, "EU");
+    assert_eq!(eu.before.effect, DecisionEffect::Allow);
+    assert_eq!(eu.before.rule.as_deref(), Some("allow-gets"));
+    assert_eq!(eu.after.effect, DecisionEffect::Deny);
+    assert_eq!(eu.after.rule.as_deref(), Some("deny-eu-gets"));
+    assert_eq!(eu.after.reason.as_deref(), Some("residency"));
+
+    let post = &diff.changes[1];
+    assert!(!post.effect_changed && !post.reason_changed && post.obligations_changed);
+    assert!(post.before.obligations.is_empty());
+    assert_eq!(post.after.obligations[0].kind, "log-audit");
+    assert_eq!(post.after.rule.as_deref(), Some("allow-us-posts"));
+}
+
+#[test]
+fn identical_bundles_do_not_differ_and_dry_run_names_defaults() {
+    let bundle = load_json(include_bytes!("vectors/rollout_before.json")).unwrap();
+    assert!(diff_bundles(&bundle, &bundle, &corpus())
+        .unwrap()
+        .is_empty());
+
+    let outcomes = dry_run(&bundle, &corpus()).unwrap();
+    // Recorded methods are normalized on load ("get" -> "GET").
+    assert_eq!(outcomes[0].rule.as_deref(), Some("allow-gets"));
+    assert_eq!(outcomes[3].rule, None);
+    assert_eq!(outcomes[3].reason.as_deref(), Some("default"));
+}
+
+#[test]
+fn corpus_errors_name_the_line() {
+    let err = parse::json::contexts_from_jsonl(b"{}\n\n{ \"nope\": 1 }\n").unwrap_err();
+    assert!(err.to_string().contains("context line 3"), "{err}");
+}
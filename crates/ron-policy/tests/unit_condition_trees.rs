@@ -0,0 +1,249 @@
+//! Condition trees (`all`/`any`/`not`), the richer matchers (path globs, headers, time windows,
+//! numeric attrs, CIDR), rejection of unsatisfiable rules, and shadowed rules.
+
+use ron_policy::ctx::clock::FixedClock;
+use ron_policy::engine::eval::{DecisionEffect, Evaluator};
+use ron_policy::engine::matchers::{glob_matches, Cidr};
+use ron_policy::parse::validate;
+use ron_policy::{load_json, Context};
+
+// 2026-10-14 is a Wednesday.
+const WED_10_30: FixedClock = FixedClock(1_791_973_800_000);
+const WED_22_00: FixedClock = FixedClock(1_792_015_200_000);
+const WED_03_00: FixedClock = FixedClock(1_791_946_800_000);
+const SAT_10_30: FixedClock = FixedClock(1_792_233_000_000);
+
+fn decide(ctx: &Context) -> (DecisionEffect, String) {
+    let b = load_json(include_bytes!("vectors/condition_trees.json")).unwrap();
+    let d = Evaluator::new(&b).unwrap().evaluate(ctx).unwrap();
+    (d.effect, d.reason.unwrap_or_default())
+}
+
+fn get(path: &str) -> ron_policy::ctx::normalize::ContextBuilder {
+    Context::builder()
+        .tenant("t")
+        .method("GET")
+        .region("US")
+        .path(path)
+}
+
+fn admin_post() -> ron_policy::ctx::normalize::ContextBuilder {
+    Context::builder()
+        .tenant("t")
+        .method("POST")
+        .region("US")
+        .path("/admin/users/42")
+        .header("x-admin-token", "opaque")
+}
+
+#[test]
+fn any_and_not_branches() {
+    let allow = (DecisionEffect::Allow, "public-read".to_string());
+    let default = (DecisionEffect::Deny, "default".to_string());
+
+    assert_eq!(decide(&get("/public/a").build(&WED_10_30)), allow);
+    // `*` stays within one segment.
+    assert_eq!(decide(&get("/public/a/b").build(&WED_10_30)), default);
+    // Second `any` branch: header value (names are case-insensitive).
+    assert_eq!(
+        decide(&get("/private").header("X-Tier", "gold").build(&WED_10_30)),
+        allow
+    );
+    assert_eq!(
+        decide(&get("/private").header("X-Tier", "silver").build(&WED_10_30)),
+        default
+    );
+    // `not` excludes a region even when an `any` branch matches.
+    assert_eq!(
+        decide(&get("/public/a").region("KP").build(&WED_10_30)),
+        default
+    );
+}
+
+#[test]
+fn cidr_matches_v4_v6_and_mapped_addresses() {
+    let blocked = (DecisionEffect::Deny, "blocked-network".to_string());
+    for ip in ["203.0.113.9", "::ffff:203.0.113.9", "2001:db8::1"] {
+        let ctx = get("/public/a")
+            .client_ip(ip.parse().unwrap())
+            .build(&WED_10_30);
+        assert_eq!(decide(&ctx), blocked, "{ip}");
+    }
+    let ctx = get("/public/a")
+        .client_ip("198.51.100.1".parse().unwrap())
+        .build(&WED_10_30);
+    assert_eq!(decide(&ctx).1, "public-read");
+    // No client IP never matches a CIDR condition.
+    assert_eq!(decide(&get("/public/a").build(&WED_10_30)).1, "public-read");
+}
+
+#[test]
+fn time_windows_headers_and_attrs() {
+    assert_eq!(
+        decide(&admin_post().build(&WED_10_30)),
+        (DecisionEffect::Allow, "admin-office-hours".to_string())
+    );
+    // Outside the weekday / hour window.
+    assert_eq!(decide(&admin_post().build(&SAT_10_30)).1, "default");
+    assert_eq!(decide(&admin_post().build(&WED_22_00)).1, "default");
+    // Header missing.
+    let no_token = Context::builder()
+        .tenant("t")
+        .method("POST")
+        .path("/admin/users/42")
+        .build(&WED_10_30);
+    assert_eq!(decide(&no_token).1, "default");
+    // Numeric attr comparison wins first; a missing attr never matches.
+    assert_eq!(
+        decide(&admin_post().attr("risk", 0.9).build(&WED_10_30)),
+        (DecisionEffect::Deny, "risk".to_string())
+    );
+    assert_eq!(
+        decide(&admin_post().attr("risk", 0.1).build(&WED_10_30)).1,
+        "admin-office-hours"
+    );
+}
+
+#[test]
+fn all_branches_and_windows_across_midnight() {
+    let ops = |clock: &FixedClock| {
+        Context::builder()
+            .tenant("ops")
+            .method("PUT")
+            .path("/x")
+            .build(clock)
+    };
+    assert_eq!(decide(&ops(&WED_22_00)).1, "maintenance-window");
+    assert_eq!(decide(&ops(&WED_03_00)).1, "default");
+    assert_eq!(decide(&ops(&WED_10_30)).1, "default");
+}
+
+#[test]
+fn glob_and_cidr_helpers() {
+    assert!(glob_matches("/a/*/c", "/a/b/c"));
+    assert!(!glob_matches("/a/*/c", "/a/b/x/c"));
+    assert!(glob_matches("/a/**/c", "/a/b/x/c"));
+    assert!(glob_matches("/v?/x", "/v1/x"));
+    assert!(!glob_matches("/v?/x", "/v//x"));
+    assert!(glob_matches("**", ""));
+
+    let wide = Cidr::parse("10.0.0.0/8").unwrap();
+    let narrow = Cidr::parse("10.1.0.0/16").unwrap();
+    assert!(wide.covers(&narrow) && !narrow.covers(&wide));
+    assert!(Cidr::parse("0.0.0.0/0")
+        .unwrap()
+        .contains("8.8.8.8".parse().unwrap()));
+    assert!(Cidr::parse("10.0.0.0/33").is_none());
+    assert!(Cidr::parse("not-an-ip").is_none());
+}
+
+fn rejected(rules: &str) -> String {
+    let json = format!(r#"{{ "version": 1, "rules": [{rules}] }}"#);
+    load_json(json.as_bytes()).unwrap_err().to_string()
+}
+
+#[test]
+fn unsatisfiable_rules_are_rejected() {
+    for (when, why) in [
+        (
+            r#"{ "method": "GET", "all": [{ "method": "PUT" }] }"#,
+            "conflicting method",
+        ),
+        (
+            r#"{ "attrs": [{ "key": "n", "op": "gt", "value": 5 }, { "key": "n", "op": "le", "value": 5 }] }"#,
+            "admit no value",
+        ),
+        (
+            r#"{ "any": [{ "region": "US", "all": [{ "region": "EU" }] }] }"#,
+            "no `any` branch",
+        ),
+        (r#"{ "not": { "method": "*" } }"#, "`not` of a condition"),
+        (
+            r#"{ "time": { "from": "10:00", "until": "10:00" } }"#,
+            "from == until",
+        ),
+        (
+            r#"{ "time": { "date_from": "2026-02-01", "date_until": "2026-01-31" } }"#,
+            "date_from is after",
+        ),
+    ] {
+        let err = rejected(&format!(
+            r#"{{ "id": "r", "when": {when}, "action": "allow" }}"#
+        ));
+        assert!(
+            err.contains("can never match") && err.contains(why),
+            "{when}: {err}"
+        );
+    }
+}
+
+/// Shadowed rules of a structurally valid bundle, and the loader's verdict.
+fn shadowing(rules: &str) -> (Vec<String>, Result<(), String>) {
+    let json = format!(r#"{{ "version": 1, "rules": [{rules}] }}"#);
+    let b = ron_policy::parse::json::from_slice(json.as_bytes()).unwrap();
+    validate::validate_structure(&b).unwrap();
+    let loaded = load_json(json.as_bytes())
+        .map(drop)
+        .map_err(|e| e.to_string());
+    (validate::shadowed_rules(&b), loaded)
+}
+
+#[test]
+fn shadowed_rules_are_rejected() {
+    // Same bucket, earlier and broader.
+    let (shadowed, loaded) = shadowing(
+        r#"{ "id": "a", "when": { "method": "GET" }, "action": "deny" },
+           { "id": "b", "when": { "method": "get", "path": "/x", "attrs": [{ "key": "n", "op": "gt", "value": 9 }] }, "action": "allow" }"#,
+    );
+    assert_eq!(shadowed, ["rule b is shadowed by earlier rule a"]);
+    assert!(
+        loaded
+            .unwrap_err()
+            .contains("rule b is shadowed by earlier rule a"),
+        "loaded"
+    );
+
+    // Method bucket is evaluated ahead of "*" rules, whatever the bundle order.
+    let (shadowed, loaded) = shadowing(
+        r#"{ "id": "wild", "when": { "all": [{ "method": "PUT" }], "cidr_any": ["10.1.0.0/16"] }, "action": "allow" },
+           { "id": "put", "when": { "method": "PUT", "cidr_any": ["10.0.0.0/8"] }, "action": "deny" }"#,
+    );
+    assert_eq!(shadowed, ["rule wild is shadowed by earlier rule put"]);
+    assert!(loaded.is_err());
+
+    // Narrower first, or an earlier rule with `any`/`not`, is fine.
+    let (shadowed, loaded) = shadowing(
+        r#"{ "id": "a", "when": { "method": "GET", "attrs": [{ "key": "n", "op": "gt", "value": 9 }] }, "action": "deny" },
+           { "id": "b", "when": { "method": "GET", "attrs": [{ "key": "n", "op": "gt", "value": 5 }] }, "action": "allow" },
+           { "id": "c", "when": { "not": { "region": "EU" } }, "action": "allow" },
+           { "id": "d", "when": { "region": "US" }, "action": "deny" }"#,
+    );
+    assert!(shadowed.is_empty(), "{shadowed:?}");
+    loaded.unwrap();
+}
+
+#[test]
+fn malformed_conditions_are_rejected() {
+    for (when, why) in [
+        (r#"{ "cidr_any": ["10.0.0.0/40"] }"#, "invalid CIDR"),
+        (r#"{ "time": { "from": "25:00" } }"#, "HH:MM"),
+        (
+            r#"{ "time": { "weekdays": ["someday"] } }"#,
+            "unknown weekday",
+        ),
+        (r#"{ "time": { "date_from": "2026-02-30" } }"#, "YYYY-MM-DD"),
+        (
+            r#"{ "attrs": [{ "key": "wallet_balance", "op": "gt", "value": 0 }] }"#,
+            "economic authority",
+        ),
+        (
+            r#"{ "not": { "not": { "not": { "not": { "not": { "not": { "not": { "not": { "method": "GET" } } } } } } } } }"#,
+            "nesting exceeds",
+        ),
+    ] {
+        let err = rejected(&format!(
+            r#"{{ "id": "r", "when": {when}, "action": "allow" }}"#
+        ));
+        assert!(err.contains(why), "{when}: {err}");
+    }
+}
@@ -5,9 +5,10 @@ use ron_policy::{Action, PolicyBundle, Rule, RuleCondition};
 
 #[test]
 fn first_match_wins_and_default_applies() {
-    // Bundle: two overlapping allows for GET; no catch-all rule.
+    // Bundle: two overlapping allows for GET, the narrower one first; no catch-all rule.
     // Expectations:
-    //  - GET hits the *first* allow rule and returns immediately ("first").
+    //  - GET /x matches both and hits the *first* allow rule ("first").
+    //  - Any other GET falls through to the second ("second").
     //  - POST has no matching rule and falls back to defaults ("default" deny).
     let b = PolicyBundle {
         version: 1,
@@ -17,11 +18,9 @@ fn first_match_wins_and_default_applies() {
             Rule {
                 id: "allow-1".into(),
                 when: RuleCondition {
-                    tenant: None,
                     method: Some("GET".into()),
-                    region: None,
-                    max_body_bytes: None,
-                    require_tags_all: vec![],
+                    path: Some("/x".into()),
+                    ..Default::default()
                 },
                 action: Action::Allow,
                 obligations: vec![],
@@ -30,11 +29,8 @@ fn first_match_wins_and_default_applies() {
             Rule {
                 id: "allow-2".into(),
                 when: RuleCondition {
-                    tenant: None,
                     method: Some("GET".into()),
-                    region: None,
-                    max_body_bytes: None,
-                    require_tags_all: vec![],
+                    ..Default::default()
                 },
                 action: Action::Allow,
                 obligations: vec![],
@@ -50,6 +46,13 @@ fn first_match_wins_and_default_applies() {
     let getc = Context::builder()
         .tenant("t")
         .method("GET")
+        .path("/x")
+        .region("US")
+        .build(&SystemClock);
+    let other_getc = Context::builder()
+        .tenant("t")
+        .method("GET")
+        .path("/y")
         .region("US")
         .build(&SystemClock);
     let postc = Context::builder()
@@ -62,7 +65,28 @@ fn first_match_wins_and_default_applies() {
     assert!(matches!(d_get.effect, DecisionEffect::Allow));
     assert_eq!(d_get.reason.as_deref(), Some("first"));
 
+    let d_other = ev.evaluate(&other_getc).unwrap();
+    assert!(matches!(d_other.effect, DecisionEffect::Allow));
+    assert_eq!(d_other.reason.as_deref(), Some("second"));
+
     let d_post = ev.evaluate(&postc).unwrap();
     assert!(matches!(d_post.effect, DecisionEffect::Deny));
     assert_eq!(d_post.reason.as_deref(), Some("default"));
 }
+
+#[test]
+fn shadowed_rule_is_rejected() {
+    // The broader rule first leaves the narrower one unreachable.
+    let err = ron_policy::load_json(
+        br#"{ "version": 1, "rules": [
+            { "id": "get", "when": { "method": "GET" }, "action": "allow" },
+            { "id": "get-x", "when": { "method": "GET", "path": "/x" }, "action": "deny" }
+        ] }"#,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(
+        err.contains("rule get-x is shadowed by earlier rule get"),
+        "{err}"
+    );
+}
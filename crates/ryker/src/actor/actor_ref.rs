@@ -0,0 +1,71 @@
+//! RO:WHAT — ActorRef<Req, Resp>: cloneable typed handle that asks an actor and awaits its reply.
+//! RO:WHY  — Request/response on top of the bounded mailbox without per-service oneshot glue.
+//! RO:INTERACTS — mailbox::{Mailbox, MailboxTx, MailboxRx}; actor::Request; observe hooks via MailboxTx.
+//! RO:INVARIANTS — enqueue is try_send (Busy, never waits for space); one deadline covers queueing + handling;
+//!                 a dropped reply handle surfaces as Closed, a missed deadline as Timeout.
+
+use super::request::Request;
+use crate::mailbox::{Mailbox, MailboxError, MailboxResult, MailboxRx, MailboxTx};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Typed handle to an actor that answers `Req` with `Resp`.
+pub struct ActorRef<Req, Resp> {
+    tx: MailboxTx<Request<Req, Resp>>,
+    deadline: Duration,
+}
+
+// Manual impl: cloning the handle must not require `Req: Clone` / `Resp: Clone`.
+impl<Req, Resp> Clone for ActorRef<Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            deadline: self.deadline,
+        }
+    }
+}
+
+impl<Req, Resp> ActorRef<Req, Resp> {
+    /// Split a request mailbox into a handle for callers and the inbox the actor pulls from.
+    /// The mailbox deadline becomes the default ask deadline.
+    pub fn from_mailbox(
+        mailbox: Mailbox<Request<Req, Resp>>,
+    ) -> (Self, MailboxRx<Request<Req, Resp>>) {
+        let deadline = mailbox.deadline();
+        let (tx, rx) = mailbox.split();
+        (Self { tx, deadline }, rx)
+    }
+
+    /// Actor name of the target mailbox.
+    pub fn actor(&self) -> &str {
+        self.tx.actor()
+    }
+
+    /// Default deadline applied by [`ask`](Self::ask).
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    /// Ask with the mailbox deadline.
+    pub async fn ask(&self, msg: Req) -> MailboxResult<Resp> {
+        self.ask_with_deadline(msg, self.deadline).await
+    }
+
+    /// Ask with an explicit deadline covering enqueue, handling and reply.
+    ///
+    /// Errors: `Busy` (mailbox full), `Closed` (actor gone or dropped the request),
+    /// `Timeout` (no reply within `deadline`).
+    pub async fn ask_with_deadline(&self, msg: Req, deadline: Duration) -> MailboxResult<Resp> {
+        let (req, rx) = Request::new(msg, Instant::now() + deadline);
+        let expires = req.deadline();
+        self.tx.try_send(req)?;
+        match tokio::time::timeout_at(expires, rx).await {
+            Ok(Ok(resp)) => Ok(resp),
+            Ok(Err(_)) => Err(MailboxError::Closed),
+            Err(_) => {
+                self.tx.note_timeout(deadline);
+                Err(MailboxError::Timeout)
+            }
+        }
+    }
+}
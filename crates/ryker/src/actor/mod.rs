@@ -0,0 +1,12 @@
+//! RO:WHAT — Typed request/response actors: `ActorRef::ask` over a bounded mailbox with a oneshot reply.
+//! RO:WHY  — Pillar 1 (Kernel & Orchestration); Concerns: RES/DX. One ask pattern instead of ad-hoc oneshot plumbing.
+//! RO:INTERACTS — mailbox::{Mailbox, MailboxTx, MailboxRx}; observe hooks (timeouts).
+//! RO:INVARIANTS — ask never blocks on a full mailbox (Busy); the mailbox deadline bounds the whole round trip
+//!                 and travels with the request so the actor can skip or abort stale work.
+#![allow(clippy::module_inception)]
+
+mod actor_ref;
+mod request;
+
+pub use actor_ref::ActorRef;
+pub use request::{ReplyTo, Request};
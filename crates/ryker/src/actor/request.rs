@@ -0,0 +1,86 @@
+//! RO:WHAT — Request<Req, Resp>: the message an ask enqueues (payload + deadline-stamped reply handle).
+//! RO:WHY  — The deadline set by the caller is visible to the actor; late replies are reported, not lost silently.
+//! RO:INTERACTS — actor_ref::ActorRef (creates), actor loops (consume via MailboxRx::pull).
+//! RO:INVARIANTS — at most one reply per request; replies after the deadline are refused with Timeout.
+
+use crate::mailbox::{MailboxError, MailboxResult};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+/// One ask in flight: the caller's message plus where to send the answer.
+#[derive(Debug)]
+pub struct Request<Req, Resp> {
+    msg: Req,
+    reply: ReplyTo<Resp>,
+}
+
+impl<Req, Resp> Request<Req, Resp> {
+    pub(crate) fn new(msg: Req, deadline: Instant) -> (Self, oneshot::Receiver<Resp>) {
+        let (tx, rx) = oneshot::channel();
+        let reply = ReplyTo { tx, deadline };
+        (Self { msg, reply }, rx)
+    }
+
+    /// The caller's message.
+    pub fn message(&self) -> &Req {
+        &self.msg
+    }
+
+    /// Instant after which the caller no longer waits.
+    pub fn deadline(&self) -> Instant {
+        self.reply.deadline
+    }
+
+    /// True once the caller's deadline has passed (work can be skipped).
+    pub fn is_expired(&self) -> bool {
+        self.reply.is_expired()
+    }
+
+    /// Split into the message and its reply handle (e.g. to answer from another task).
+    pub fn into_parts(self) -> (Req, ReplyTo<Resp>) {
+        (self.msg, self.reply)
+    }
+
+    /// Answer this request; see [`ReplyTo::send`].
+    pub fn reply(self, resp: Resp) -> MailboxResult<()> {
+        self.reply.send(resp)
+    }
+}
+
+/// Reply half of a request, stamped with the caller's deadline.
+#[derive(Debug)]
+pub struct ReplyTo<Resp> {
+    tx: oneshot::Sender<Resp>,
+    deadline: Instant,
+}
+
+impl<Resp> ReplyTo<Resp> {
+    /// Instant after which the caller no longer waits.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Time left before the deadline (zero once expired).
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// True once the caller's deadline has passed.
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+
+    /// True if the caller has gone away (timed out or dropped the ask).
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Deliver the response. `Timeout` if the deadline already passed, `Closed` if the caller is gone.
+    pub fn send(self, resp: Resp) -> MailboxResult<()> {
+        if self.is_expired() {
+            return Err(MailboxError::Timeout);
+        }
+        self.tx.send(resp).map_err(|_| MailboxError::Closed)
+    }
+}
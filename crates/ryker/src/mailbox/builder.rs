@@ -18,6 +18,8 @@ pub struct MailboxBuilder<T> {
     max_msg_bytes: Option<usize>,
     deadline: Option<Duration>,
     observer: Option<Observer>,
+    #[cfg(feature = "sim")]
+    sim: Option<crate::sim::SimHandle>,
     _phantom: std::marker::PhantomData<T>,
 }
 
@@ -30,6 +32,8 @@ impl<T> MailboxBuilder<T> {
             max_msg_bytes: None,
             deadline: None,
             observer: None,
+            #[cfg(feature = "sim")]
+            sim: None,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -60,6 +64,12 @@ impl<T> MailboxBuilder<T> {
         self
     }
 
+    #[cfg(feature = "sim")]
+    pub(crate) fn sim(mut self, sim: Option<crate::sim::SimHandle>) -> Self {
+        self.sim = sim;
+        self
+    }
+
     pub fn build(self) -> Mailbox<T> {
         let cap = self.capacity.unwrap_or(self.cfg.defaults.mailbox_capacity);
         let max = self
@@ -68,6 +78,12 @@ impl<T> MailboxBuilder<T> {
         let dl = self.deadline.unwrap_or(self.cfg.defaults.deadline);
         let obs = self.observer.unwrap_or_else(|| Arc::new(NoopObserver));
 
-        Mailbox::new(self.actor_name, cap, max, dl, obs)
+        let mailbox = Mailbox::new(self.actor_name, cap, max, dl, obs);
+        #[cfg(feature = "sim")]
+        let mailbox = match self.sim {
+            Some(sim) => mailbox.with_sim(sim),
+            None => mailbox,
+        };
+        mailbox
     }
 }
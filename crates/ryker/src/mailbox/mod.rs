@@ -7,10 +7,12 @@ mod builder;
 mod error;
 pub mod observer;
 mod queue;
+#[cfg(feature = "sim")]
+mod sim;
 
 pub use builder::MailboxBuilder;
 pub use error::{MailboxError, MailboxResult};
-pub use queue::Mailbox;
+pub use queue::{Mailbox, MailboxRx, MailboxTx};
 
 // Convenience re-exports so users can `use ryker::mailbox::*;`
 pub use observer::{DropReason, MailboxObserver, NoopObserver, Observer};
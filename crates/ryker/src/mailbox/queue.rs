@@ -7,6 +7,8 @@
 
 use super::error::{MailboxError, MailboxResult};
 use super::observer::{DropReason, Observer};
+#[cfg(feature = "sim")]
+use super::sim::SimQueue;
 use crate::observe::trace;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -21,6 +23,8 @@ pub struct Mailbox<T> {
     max_msg_bytes: usize,
     deadline: Duration,
     observer: Observer,
+    #[cfg(feature = "sim")]
+    sim: Option<SimQueue<T>>,
 }
 
 impl<T> Mailbox<T> {
@@ -40,9 +44,18 @@ impl<T> Mailbox<T> {
             max_msg_bytes,
             deadline,
             observer,
+            #[cfg(feature = "sim")]
+            sim: None,
         }
     }
 
+    /// Route pulls through the deterministic simulation.
+    #[cfg(feature = "sim")]
+    pub(crate) fn with_sim(mut self, sim: crate::sim::SimHandle) -> Self {
+        self.sim = Some(SimQueue::new(sim, self.tx.downgrade()));
+        self
+    }
+
     /// Non-blocking enqueue; returns Busy when full.
     pub fn try_send(&self, msg: T) -> MailboxResult<()> {
         match self.tx.try_send(msg) {
@@ -80,6 +93,12 @@ impl<T> Mailbox<T> {
 
     /// Pull one message, honoring the deadline as a receive timeout.
     pub async fn pull(&mut self) -> MailboxResult<T> {
+        #[cfg(feature = "sim")]
+        if let Some(sim) = self.sim.as_mut() {
+            return sim
+                .pull(&mut self.rx, &self.actor, self.deadline, &self.observer)
+                .await;
+        }
         match tokio::time::timeout(self.deadline, self.rx.recv()).await {
             Ok(Some(m)) => Ok(m),
             Ok(None) => Err(MailboxError::Closed),
@@ -96,6 +115,11 @@ impl<T> Mailbox<T> {
         self.capacity
     }
 
+    /// Configured per-message size cap (hosts enforce it for sized payloads).
+    pub fn max_msg_bytes(&self) -> usize {
+        self.max_msg_bytes
+    }
+
     /// Deadline getter.
     pub fn deadline(&self) -> Duration {
         self.deadline
@@ -112,6 +136,8 @@ impl<T> Mailbox<T> {
             max_msg_bytes: _,
             deadline,
             observer,
+            #[cfg(feature = "sim")]
+            sim,
         } = self;
         (
             MailboxTx {
@@ -124,6 +150,8 @@ impl<T> Mailbox<T> {
                 rx,
                 deadline,
                 observer,
+                #[cfg(feature = "sim")]
+                sim,
             },
         )
     }
@@ -136,7 +164,29 @@ pub struct MailboxTx<T> {
     observer: Observer,
 }
 
+// Manual impl: cloning the sender must not require `T: Clone`.
+impl<T> Clone for MailboxTx<T> {
+    fn clone(&self) -> Self {
+        Self {
+            actor: self.actor.clone(),
+            tx: self.tx.clone(),
+            observer: self.observer.clone(),
+        }
+    }
+}
+
 impl<T> MailboxTx<T> {
+    /// Actor name this mailbox belongs to.
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    /// Report a deadline miss on the send side (e.g. an unanswered ask).
+    pub(crate) fn note_timeout(&self, deadline: Duration) {
+        self.observer.on_timeout(&self.actor);
+        trace::span_handle(&self.actor, "timeout", deadline.as_millis() as u64);
+    }
+
     pub fn try_send(&self, msg: T) -> MailboxResult<()> {
         match self.tx.try_send(msg) {
             Ok(()) => {
@@ -166,10 +216,18 @@ pub struct MailboxRx<T> {
     rx: mpsc::Receiver<T>,
     deadline: Duration,
     observer: Observer,
+    #[cfg(feature = "sim")]
+    sim: Option<SimQueue<T>>,
 }
 
 impl<T> MailboxRx<T> {
     pub async fn pull(&mut self) -> MailboxResult<T> {
+        #[cfg(feature = "sim")]
+        if let Some(sim) = self.sim.as_mut() {
+            return sim
+                .pull(&mut self.rx, &self.actor, self.deadline, &self.observer)
+                .await;
+        }
         match tokio::time::timeout(self.deadline, self.rx.recv()).await {
             Ok(Some(m)) => Ok(m),
             Ok(None) => Err(MailboxError::Closed),
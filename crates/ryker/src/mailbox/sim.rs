@@ -0,0 +1,101 @@
+//! RO:WHAT — Simulated pull path (feature "sim"): seeded yields/faults, then a seeded pick among ready messages.
+//! RO:WHY  — Lets tests reorder deliveries reproducibly without touching the production pull path.
+//! RO:INTERACTS — sim::SimHandle (decisions + trace), queue::{Mailbox, MailboxRx} (delegate when simulated).
+//! RO:INVARIANTS — messages are never lost or duplicated, only reordered; deadline semantics match `pull`;
+//!                 every message drained into `ready` keeps its channel slot reserved until it is delivered,
+//!                 so senders still see `Busy` at the mailbox capacity.
+
+use super::error::{MailboxError, MailboxResult};
+use super::observer::Observer;
+use crate::observe::trace;
+use crate::sim::SimHandle;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, OwnedPermit, WeakSender};
+
+pub(super) struct SimQueue<T> {
+    sim: SimHandle,
+    /// Reserves slots for drained messages; weak so dropping every sender still closes the mailbox.
+    tx: WeakSender<T>,
+    ready: VecDeque<T>,
+    /// One per message in `ready`, short only when a sender refilled a freed slot first.
+    slots: Vec<OwnedPermit<T>>,
+}
+
+impl<T> SimQueue<T> {
+    pub(super) fn new(sim: SimHandle, tx: WeakSender<T>) -> Self {
+        Self {
+            sim,
+            tx,
+            ready: VecDeque::new(),
+            slots: Vec::new(),
+        }
+    }
+
+    pub(super) async fn pull(
+        &mut self,
+        rx: &mut mpsc::Receiver<T>,
+        actor: &str,
+        deadline: Duration,
+        observer: &Observer,
+    ) -> MailboxResult<T> {
+        self.sim.before_pull(actor).await;
+        self.drain(rx);
+        if self.ready.is_empty() {
+            match tokio::time::timeout(deadline, rx.recv()).await {
+                Ok(Some(m)) => {
+                    self.ready.push_back(m);
+                    self.reserve();
+                    self.drain(rx);
+                }
+                Ok(None) => return Err(MailboxError::Closed),
+                Err(_) => {
+                    observer.on_timeout(actor);
+                    trace::span_handle(actor, "timeout", deadline.as_millis() as u64);
+                    return Err(MailboxError::Timeout);
+                }
+            }
+        }
+        let idx = self.sim.pick(actor, self.ready.len());
+        let msg = self
+            .ready
+            .remove(idx)
+            .expect("ryker-sim: pick within ready queue");
+        // Delivered: hand its slot back to senders.
+        self.slots.truncate(self.ready.len());
+        Ok(msg)
+    }
+
+    /// Move waiting messages into `ready`, each swapping its channel slot for a reserved one.
+    ///
+    /// Stops when no slot can be reserved, leaving the rest in the channel, so `ready` plus the
+    /// channel stay within the mailbox capacity.
+    fn drain(&mut self, rx: &mut mpsc::Receiver<T>) {
+        loop {
+            let Some(tx) = self.tx.upgrade() else {
+                // No sender left to refill the channel; what remains is already bounded.
+                while let Ok(m) = rx.try_recv() {
+                    self.ready.push_back(m);
+                }
+                return;
+            };
+            let Ok(slot) = tx.try_reserve_owned() else {
+                return;
+            };
+            match rx.try_recv() {
+                Ok(m) => {
+                    self.ready.push_back(m);
+                    self.slots.push(slot);
+                }
+                Err(_) => return,
+            }
+        }
+    }
+
+    /// Reserve the slot freed by a blocking receive, if no sender has taken it yet.
+    fn reserve(&mut self) {
+        if let Some(slot) = self.tx.upgrade().and_then(|tx| tx.try_reserve_owned().ok()) {
+            self.slots.push(slot);
+        }
+    }
+}
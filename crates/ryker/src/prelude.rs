@@ -1,10 +1,11 @@
 //! RO:WHAT — Ergonomic re-exports for common ryker types.
 //! RO:WHY  — DX; fewer deep module paths for apps embedding ryker.
-//! RO:INTERACTS — re-exports from config, runtime, mailbox, supervisor.
+//! RO:INTERACTS — re-exports from actor, config, runtime, mailbox, supervisor.
 //! RO:INVARIANTS — re-export only stable, documented surface.
 
+pub use crate::actor::{ActorRef, ReplyTo, Request};
 pub use crate::config::RykerConfig;
 pub use crate::errors::{Error, Result};
 pub use crate::mailbox::{Mailbox, MailboxBuilder};
 pub use crate::runtime::Runtime;
-pub use crate::supervisor::Supervisor;
+pub use crate::supervisor::{ChildSpec, Strategy, Supervisor, TreeSpec};
@@ -10,15 +10,33 @@ use std::sync::Arc;
 #[derive(Clone)]
 pub struct Runtime {
     cfg: Arc<RykerConfig>,
+    #[cfg(feature = "sim")]
+    sim: Option<crate::sim::SimHandle>,
 }
 
 impl Runtime {
     pub fn new(cfg: RykerConfig) -> Self {
-        Self { cfg: Arc::new(cfg) }
+        Self {
+            cfg: Arc::new(cfg),
+            #[cfg(feature = "sim")]
+            sim: None,
+        }
+    }
+
+    /// Simulation mode (test-only): every mailbox built here is driven by `sim`.
+    #[cfg(feature = "sim")]
+    pub fn simulated(cfg: RykerConfig, sim: crate::sim::SimHandle) -> Self {
+        Self {
+            cfg: Arc::new(cfg),
+            sim: Some(sim),
+        }
     }
 
     pub fn mailbox<T>(&self, actor_name: impl Into<String>) -> MailboxBuilder<T> {
-        MailboxBuilder::new(actor_name.into(), self.cfg.clone())
+        let builder = MailboxBuilder::new(actor_name.into(), self.cfg.clone());
+        #[cfg(feature = "sim")]
+        let builder = builder.sim(self.sim.clone());
+        builder
     }
 
     /// Build a mailbox immediately with defaults (no overrides).
@@ -0,0 +1,276 @@
+//! RO:WHAT — Deterministic simulation mode (feature "sim", test-only): seeded single-threaded scheduler, virtual time,
+//!           mailbox delivery reordering, injected actor panics, replayable seeds.
+//! RO:WHY  — Pillar 1 (Kernel & Orchestration); Concerns: RES. Supervisor/mailbox races reproduce under a seed
+//!           instead of depending on real tokio timers and thread scheduling.
+//! RO:INTERACTS — runtime::Runtime::simulated (mailboxes pick up the handle); mailbox pull path; tokio test-util.
+//! RO:INVARIANTS — every nondeterministic choice draws from one seeded RNG in call order and is recorded in the trace;
+//!                 same seed + same program => same trace; time only moves when idle or via `advance`.
+//! RO:CONFIG — `RYKER_SIM_SEED` replays a seed (`Sim::from_env`).
+//! RO:TEST — Integration: tests/sim_runtime.rs (feature = "sim").
+
+use parking_lot::Mutex;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::RykerConfig;
+use crate::runtime::Runtime;
+
+/// Env var consulted by [`Sim::from_env`] to replay a seed.
+pub const SEED_ENV: &str = "RYKER_SIM_SEED";
+
+/// One recorded scheduling decision.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimEvent {
+    /// `actor` yielded `n` times before pulling (scheduler perturbation).
+    Yield { actor: String, n: u32 },
+    /// `actor` received message `picked` out of `queued` ready ones (0 = FIFO head).
+    Deliver {
+        actor: String,
+        picked: usize,
+        queued: usize,
+    },
+    /// An injected panic fired on `actor`'s `pull`-th pull (1-based).
+    Panic { actor: String, pull: u64 },
+}
+
+#[derive(Debug, Clone)]
+enum Fault {
+    /// Panic on exactly this pull (1-based).
+    OnPull { actor: String, pull: u64 },
+    /// Panic on each pull with this probability.
+    Random { actor: String, p: f64 },
+}
+
+/// Simulation plan: seed plus perturbations and faults. Consumed by [`Sim::run`].
+#[derive(Debug, Clone)]
+pub struct Sim {
+    seed: u64,
+    reorder: bool,
+    max_yields: u32,
+    faults: Vec<Fault>,
+}
+
+impl Sim {
+    /// Deterministic simulation for `seed`: deliveries reordered, up to 2 extra yields per pull, no faults.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            reorder: true,
+            max_yields: 2,
+            faults: Vec::new(),
+        }
+    }
+
+    /// Seed from `RYKER_SIM_SEED` when set (replay), otherwise a fresh random one (printed on failure).
+    pub fn from_env() -> Self {
+        let seed = std::env::var(SEED_ENV)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or_else(rand::random);
+        Self::new(seed)
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Deliver a seeded pick among ready messages instead of FIFO order.
+    pub fn reorder_deliveries(mut self, on: bool) -> Self {
+        self.reorder = on;
+        self
+    }
+
+    /// Upper bound of seeded `yield_now` calls before each pull (0 disables).
+    pub fn max_yields(mut self, n: u32) -> Self {
+        self.max_yields = n;
+        self
+    }
+
+    /// Panic inside `actor` on its `pull`-th pull (1-based).
+    pub fn panic_on_pull(mut self, actor: impl Into<String>, pull: u64) -> Self {
+        self.faults.push(Fault::OnPull {
+            actor: actor.into(),
+            pull,
+        });
+        self
+    }
+
+    /// Panic inside `actor` on each pull with probability `p` (seeded).
+    pub fn panic_probability(mut self, actor: impl Into<String>, p: f64) -> Self {
+        self.faults.push(Fault::Random {
+            actor: actor.into(),
+            p: p.clamp(0.0, 1.0),
+        });
+        self
+    }
+
+    /// Run `f` on a fresh single-threaded runtime with paused (virtual) time.
+    ///
+    /// If `f` panics, the seed and the replay env var are printed before the panic is propagated.
+    pub fn run<F, Fut, T>(self, f: F) -> SimReport<T>
+    where
+        F: FnOnce(SimHandle) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let seed = self.seed;
+        let handle = SimHandle::new(self);
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("ryker-sim: build current-thread runtime");
+        let result = panic::catch_unwind(AssertUnwindSafe(|| rt.block_on(f(handle.clone()))));
+        drop(rt);
+        match result {
+            Ok(output) => SimReport {
+                seed,
+                output,
+                trace: handle.trace(),
+            },
+            Err(payload) => {
+                eprintln!("ryker-sim: failed with seed {seed}; replay with {SEED_ENV}={seed}");
+                panic::resume_unwind(payload)
+            }
+        }
+    }
+}
+
+/// Outcome of a simulation run.
+#[derive(Debug)]
+pub struct SimReport<T> {
+    pub seed: u64,
+    pub output: T,
+    pub trace: Vec<SimEvent>,
+}
+
+struct SimState {
+    reorder: bool,
+    max_yields: u32,
+    rng: StdRng,
+    faults: Vec<Fault>,
+    pulls: HashMap<String, u64>,
+    trace: Vec<SimEvent>,
+}
+
+/// Shared handle to the running simulation; cheap to clone.
+#[derive(Clone)]
+pub struct SimHandle {
+    seed: u64,
+    state: Arc<Mutex<SimState>>,
+}
+
+impl std::fmt::Debug for SimHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SimHandle")
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl SimHandle {
+    fn new(sim: Sim) -> Self {
+        Self {
+            seed: sim.seed,
+            state: Arc::new(Mutex::new(SimState {
+                reorder: sim.reorder,
+                max_yields: sim.max_yields,
+                rng: StdRng::seed_from_u64(sim.seed),
+                faults: sim.faults,
+                pulls: HashMap::new(),
+                trace: Vec::new(),
+            })),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// A ryker runtime whose mailboxes are driven by this simulation.
+    pub fn runtime(&self, cfg: RykerConfig) -> Runtime {
+        Runtime::simulated(cfg, self.clone())
+    }
+
+    /// Fast-forward virtual time (timers and deadlines due in `d` fire).
+    pub async fn advance(&self, d: Duration) {
+        tokio::time::advance(d).await;
+    }
+
+    /// Decisions recorded so far.
+    pub fn trace(&self) -> Vec<SimEvent> {
+        self.state.lock().trace.clone()
+    }
+
+    /// Before a pull: seeded yields, then an injected panic if one is due.
+    pub(crate) async fn before_pull(&self, actor: &str) {
+        let yields = {
+            let mut st = self.state.lock();
+            let max = st.max_yields;
+            let n = if max == 0 {
+                0
+            } else {
+                st.rng.random_range(0..=max)
+            };
+            if n > 0 {
+                st.trace.push(SimEvent::Yield {
+                    actor: actor.to_string(),
+                    n,
+                });
+            }
+            n
+        };
+        for _ in 0..yields {
+            tokio::task::yield_now().await;
+        }
+
+        let fire = {
+            let mut st = self.state.lock();
+            let pull = {
+                let n = st.pulls.entry(actor.to_string()).or_insert(0);
+                *n += 1;
+                *n
+            };
+            let mut fire = false;
+            for i in 0..st.faults.len() {
+                let hit = match &st.faults[i] {
+                    Fault::OnPull { actor: a, pull: at } => a == actor && *at == pull,
+                    Fault::Random { actor: a, p } => {
+                        let p = *p;
+                        a == actor && st.rng.random_bool(p)
+                    }
+                };
+                fire |= hit;
+            }
+            if fire {
+                st.trace.push(SimEvent::Panic {
+                    actor: actor.to_string(),
+                    pull,
+                });
+            }
+            fire.then_some(pull)
+        };
+        if let Some(pull) = fire {
+            panic!("ryker-sim: injected panic in actor {actor} on pull {pull}");
+        }
+    }
+
+    /// Which of `queued` ready messages to deliver.
+    pub(crate) fn pick(&self, actor: &str, queued: usize) -> usize {
+        let mut st = self.state.lock();
+        let picked = if st.reorder && queued > 1 {
+            st.rng.random_range(0..queued)
+        } else {
+            0
+        };
+        st.trace.push(SimEvent::Deliver {
+            actor: actor.to_string(),
+            picked,
+            queued,
+        });
+        picked
+    }
+}
@@ -0,0 +1,20 @@
+//! RO:WHAT — Supervision tree errors.
+//! RO:WHY  — Escalation is a value the parent (or host) acts on, not a log line.
+//! RO:INTERACTS — tree::run (produces), TreeHandle (returns), nested ChildSpec (maps to a child failure).
+//! RO:INVARIANTS — strings carry supervisor names only; no payloads.
+
+use std::time::Duration;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SupervisorError {
+    #[error(
+        "supervisor {name} exceeded restart intensity ({max_restarts} in {window:?}); escalating"
+    )]
+    Escalated {
+        name: String,
+        max_restarts: u32,
+        window: Duration,
+    },
+    #[error("supervisor {name} task failed: {reason}")]
+    Crashed { name: String, reason: String },
+}
@@ -1,11 +1,17 @@
-//! RO:WHAT — Crash-only supervision with jittered backoff.
+//! RO:WHAT — Crash-only supervision with jittered backoff: single-actor Supervisor and supervision trees.
 //! RO:WHY  — Resilience; restarts counted by host metrics via observe hooks.
 //! RO:INTERACTS — backoff calc; host spawns async tasks; no global runtime.
-//! RO:INVARIANTS — decorrelated jitter; bounded backoff; cancel-safe.
+//! RO:INVARIANTS — decorrelated jitter; bounded backoff; cancel-safe; trees escalate past restart intensity.
 #![allow(clippy::module_inception)]
 
 mod backoff;
+mod error;
+mod spec;
 mod supervisor;
+mod tree;
 
 pub use backoff::decorrelated_jitter;
+pub use error::SupervisorError;
+pub use spec::{ChildSpec, Restart, Strategy, TreeSpec};
 pub use supervisor::Supervisor;
+pub use tree::{ChildCtx, TreeHandle};
@@ -0,0 +1,181 @@
+//! RO:WHAT — Declarative supervision tree specs: ChildSpec (name, restart policy, shutdown budget) and TreeSpec.
+//! RO:WHY  — Hosts describe children once; the tree owns restart/escalation/shutdown instead of bespoke loops.
+//! RO:INTERACTS — tree (runs specs), config::RykerConfig (backoff defaults), observe hooks (on_restart).
+//! RO:INVARIANTS — children start in declaration order and stop in reverse; a nested TreeSpec is just another child.
+
+use super::tree::{self, ChildCtx, TreeHandle};
+use crate::config::RykerConfig;
+use crate::observe::metrics::Observer;
+use parking_lot::Mutex;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub(crate) type ChildFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+pub(crate) type Factory = Box<dyn FnMut(ChildCtx) -> ChildFuture + Send>;
+
+/// Which siblings are restarted together when a child exits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Restart only the child that exited.
+    OneForOne,
+    /// Stop every other child (reverse order) and restart all of them.
+    OneForAll,
+    /// Stop the children started after the one that exited and restart them with it.
+    RestForOne,
+}
+
+/// When a child that exited is brought back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Restart {
+    /// Always (normal exit included).
+    Permanent,
+    /// Only after an error or panic.
+    Transient,
+    /// Never; an exit just removes it from the tree.
+    Temporary,
+}
+
+impl Restart {
+    pub(crate) fn should_restart(self, failed: bool) -> bool {
+        match self {
+            Restart::Permanent => true,
+            Restart::Transient => failed,
+            Restart::Temporary => false,
+        }
+    }
+}
+
+/// One supervised child: a factory for fresh runs plus its policies.
+pub struct ChildSpec {
+    pub(crate) name: String,
+    pub(crate) restart: Restart,
+    /// Grace period after the shutdown signal before the task is aborted; `None` waits indefinitely.
+    pub(crate) shutdown: Option<Duration>,
+    pub(crate) factory: Factory,
+}
+
+impl ChildSpec {
+    /// Default shutdown grace period for worker children.
+    pub const DEFAULT_SHUTDOWN: Duration = Duration::from_secs(5);
+
+    /// A permanent worker; `make` builds a fresh future for every (re)start.
+    pub fn new<F, Fut>(name: impl Into<String>, mut make: F) -> Self
+    where
+        F: FnMut(ChildCtx) -> Fut + Send + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            restart: Restart::Permanent,
+            shutdown: Some(Self::DEFAULT_SHUTDOWN),
+            factory: Box::new(move |ctx| Box::pin(make(ctx))),
+        }
+    }
+
+    /// A nested supervisor. It escalates to this tree by failing when its own restart intensity is exceeded,
+    /// and is given unlimited time to shut its children down.
+    pub fn supervisor(spec: TreeSpec) -> Self {
+        let name = spec.name.clone();
+        let slot = Arc::new(Mutex::new(Some(spec)));
+        let mut child = Self::new(name, move |ctx: ChildCtx| {
+            let slot = slot.clone();
+            async move {
+                // Only one instance runs at a time; the spec is handed back when it stops.
+                let mut spec = slot
+                    .lock()
+                    .take()
+                    .ok_or_else(|| anyhow::anyhow!("nested supervisor state lost after abort"))?;
+                let res = tree::run(&mut spec, ctx.into_shutdown()).await;
+                *slot.lock() = Some(spec);
+                res.map_err(anyhow::Error::from)
+            }
+        });
+        child.shutdown = None;
+        child
+    }
+
+    pub fn restart(mut self, restart: Restart) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Grace period after the shutdown signal before the task is aborted.
+    pub fn shutdown_timeout(mut self, d: Duration) -> Self {
+        self.shutdown = Some(d);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A supervisor node: strategy, restart intensity, backoff and ordered children.
+pub struct TreeSpec {
+    pub(crate) name: String,
+    pub(crate) strategy: Strategy,
+    pub(crate) max_restarts: u32,
+    pub(crate) window: Duration,
+    pub(crate) backoff_base_ms: u64,
+    pub(crate) backoff_cap_ms: u64,
+    pub(crate) observer: Option<Observer>,
+    pub(crate) children: Vec<ChildSpec>,
+}
+
+impl TreeSpec {
+    /// One-for-one, at most 3 restarts per 5s, backoff from `cfg.supervisor`.
+    pub fn new(name: impl Into<String>, cfg: &RykerConfig) -> Self {
+        Self {
+            name: name.into(),
+            strategy: Strategy::OneForOne,
+            max_restarts: 3,
+            window: Duration::from_secs(5),
+            backoff_base_ms: cfg.supervisor.backoff_base_ms,
+            backoff_cap_ms: cfg.supervisor.backoff_cap_ms,
+            observer: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Escalate once more than `max_restarts` restarts happen within `window`.
+    pub fn intensity(mut self, max_restarts: u32, window: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.window = window;
+        self
+    }
+
+    /// Decorrelated-jitter delay bounds before each restart (zero disables the delay).
+    pub fn backoff(mut self, base: Duration, cap: Duration) -> Self {
+        self.backoff_base_ms = base.as_millis() as u64;
+        self.backoff_cap_ms = cap.as_millis() as u64;
+        self
+    }
+
+    /// Hook notified via `on_restart(child)` for every restart.
+    pub fn observer(mut self, obs: Observer) -> Self {
+        self.observer = Some(obs);
+        self
+    }
+
+    /// Append a child; start order is declaration order.
+    pub fn child(mut self, child: ChildSpec) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Start the tree on the current tokio runtime.
+    pub fn start(self) -> TreeHandle {
+        tree::start(self)
+    }
+}
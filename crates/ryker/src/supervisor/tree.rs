@@ -0,0 +1,276 @@
+//! RO:WHAT — Supervision tree runtime: starts children, applies the restart strategy, escalates, shuts down in order.
+//! RO:WHY  — Crash-only hierarchy (Concerns: RES); one implementation instead of per-service supervisor loops.
+//! RO:INTERACTS — spec::{TreeSpec, ChildSpec}; backoff::decorrelated_jitter; observe hooks (on_restart).
+//! RO:INVARIANTS — one running instance per child; restarts in declaration order, stops in reverse;
+//!                 > max_restarts within window => stop all children and return Escalated; no lock across .await.
+
+use super::backoff::decorrelated_jitter;
+use super::error::SupervisorError;
+use super::spec::{ChildSpec, Strategy, TreeSpec};
+use std::collections::VecDeque;
+use std::future::{poll_fn, Future};
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::{JoinError, JoinHandle};
+use tokio::time::Instant;
+
+/// Handed to each child run: its name and the shutdown signal from its supervisor.
+#[derive(Clone, Debug)]
+pub struct ChildCtx {
+    name: String,
+    shutdown: watch::Receiver<bool>,
+}
+
+impl ChildCtx {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// True once the supervisor asked this child to stop.
+    pub fn is_shutdown(&self) -> bool {
+        *self.shutdown.borrow()
+    }
+
+    /// Resolves when the supervisor asks this child to stop (or goes away).
+    pub async fn shutdown_requested(&mut self) {
+        let _ = self.shutdown.wait_for(|stop| *stop).await;
+    }
+
+    pub(crate) fn into_shutdown(self) -> watch::Receiver<bool> {
+        self.shutdown
+    }
+}
+
+/// Handle to a running tree. Dropping it signals shutdown; the tree then stops in the background.
+pub struct TreeHandle {
+    name: String,
+    shutdown: watch::Sender<bool>,
+    join: JoinHandle<Result<(), SupervisorError>>,
+}
+
+impl TreeHandle {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.join.is_finished()
+    }
+
+    /// Stop all children in reverse start order and wait for the tree to finish.
+    pub async fn shutdown(mut self) -> Result<(), SupervisorError> {
+        let _ = self.shutdown.send(true);
+        self.wait().await
+    }
+
+    /// Wait for the tree to finish on its own (all children done, or escalation).
+    pub async fn join(mut self) -> Result<(), SupervisorError> {
+        let res = self.wait().await;
+        drop(self.shutdown);
+        res
+    }
+
+    async fn wait(&mut self) -> Result<(), SupervisorError> {
+        match (&mut self.join).await {
+            Ok(res) => res,
+            Err(e) => Err(SupervisorError::Crashed {
+                name: self.name.clone(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+}
+
+pub(crate) fn start(mut spec: TreeSpec) -> TreeHandle {
+    let (tx, rx) = watch::channel(false);
+    let name = spec.name.clone();
+    let join = tokio::spawn(async move { run(&mut spec, rx).await });
+    TreeHandle {
+        name,
+        shutdown: tx,
+        join,
+    }
+}
+
+struct Running {
+    stop: watch::Sender<bool>,
+    handle: JoinHandle<anyhow::Result<()>>,
+}
+
+struct Slot<'a> {
+    spec: &'a mut ChildSpec,
+    running: Option<Running>,
+}
+
+impl Slot<'_> {
+    fn start(&mut self) {
+        let (stop, rx) = watch::channel(false);
+        let ctx = ChildCtx {
+            name: self.spec.name.clone(),
+            shutdown: rx,
+        };
+        let handle = tokio::spawn((self.spec.factory)(ctx));
+        self.running = Some(Running { stop, handle });
+    }
+
+    /// Signal, wait up to the grace period, then abort. Returns whether it was running.
+    async fn stop(&mut self) -> bool {
+        let Some(mut r) = self.running.take() else {
+            return false;
+        };
+        let _ = r.stop.send(true);
+        match self.spec.shutdown {
+            None => {
+                let _ = (&mut r.handle).await;
+            }
+            Some(grace) => {
+                if tokio::time::timeout(grace, &mut r.handle).await.is_err() {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(target = "ryker", child = %self.spec.name, "shutdown grace exceeded; aborting");
+                    r.handle.abort();
+                    let _ = r.handle.await;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Run `spec` until all children are done, `shutdown` fires, or restart intensity is exceeded.
+pub(crate) async fn run(
+    spec: &mut TreeSpec,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<(), SupervisorError> {
+    let TreeSpec {
+        name,
+        strategy,
+        max_restarts,
+        window,
+        backoff_base_ms,
+        backoff_cap_ms,
+        observer,
+        children,
+    } = spec;
+    let mut slots: Vec<Slot<'_>> = children
+        .iter_mut()
+        .map(|spec| Slot {
+            spec,
+            running: None,
+        })
+        .collect();
+    for slot in &mut slots {
+        slot.start();
+    }
+
+    let mut restarts: VecDeque<Instant> = VecDeque::new();
+    let mut backoff_ms = *backoff_base_ms;
+    let mut restart_seq: u64 = 0;
+
+    loop {
+        if slots.iter().all(|s| s.running.is_none()) {
+            return Ok(());
+        }
+        // Biased: shutdown wins ties, and branch order stays deterministic.
+        let (idx, res) = tokio::select! {
+            biased;
+            _ = stop_signalled(&mut shutdown) => {
+                stop_all(&mut slots).await;
+                return Ok(());
+            }
+            exited = next_exit(&mut slots) => exited,
+        };
+        let failed = !matches!(res, Ok(Ok(())));
+        #[cfg(feature = "tracing")]
+        tracing::warn!(target = "ryker", supervisor = %name, child = %slots[idx].spec.name, failed, "child exited");
+        if !slots[idx].spec.restart.should_restart(failed) {
+            continue;
+        }
+
+        let now = Instant::now();
+        restarts.push_back(now);
+        while restarts
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > *window)
+        {
+            restarts.pop_front();
+        }
+        if restarts.len() > *max_restarts as usize {
+            stop_all(&mut slots).await;
+            return Err(SupervisorError::Escalated {
+                name: name.clone(),
+                max_restarts: *max_restarts,
+                window: *window,
+            });
+        }
+        if restarts.len() == 1 {
+            // First restart in a quiet window: start the backoff over.
+            backoff_ms = *backoff_base_ms;
+        }
+
+        let group = match strategy {
+            Strategy::OneForOne => idx..idx + 1,
+            Strategy::OneForAll => 0..slots.len(),
+            Strategy::RestForOne => idx..slots.len(),
+        };
+        let mut bring_back = vec![false; slots.len()];
+        bring_back[idx] = true;
+        for j in group.clone().rev() {
+            if j != idx && slots[j].stop().await {
+                bring_back[j] = slots[j].spec.restart.should_restart(true);
+            }
+        }
+
+        restart_seq += 1;
+        backoff_ms =
+            decorrelated_jitter(*backoff_base_ms, *backoff_cap_ms, backoff_ms, restart_seq);
+        if backoff_ms > 0 {
+            tokio::select! {
+                biased;
+                _ = stop_signalled(&mut shutdown) => {
+                    stop_all(&mut slots).await;
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(Duration::from_millis(backoff_ms)) => {}
+            }
+        }
+
+        for j in group {
+            if bring_back[j] {
+                if let Some(obs) = observer.as_ref() {
+                    obs.on_restart(&slots[j].spec.name);
+                }
+                slots[j].start();
+            }
+        }
+    }
+}
+
+/// Resolves once shutdown is requested or the requester is gone (the watch guard is not held across awaits).
+async fn stop_signalled(rx: &mut watch::Receiver<bool>) {
+    let _ = rx.wait_for(|stop| *stop).await;
+}
+
+/// Stop every running child, last-started first.
+async fn stop_all(slots: &mut [Slot<'_>]) {
+    for slot in slots.iter_mut().rev() {
+        slot.stop().await;
+    }
+}
+
+/// Wait for the next running child to exit.
+async fn next_exit(slots: &mut [Slot<'_>]) -> (usize, Result<anyhow::Result<()>, JoinError>) {
+    poll_fn(|cx| {
+        for (i, slot) in slots.iter_mut().enumerate() {
+            if let Some(r) = slot.running.as_mut() {
+                if let Poll::Ready(res) = Pin::new(&mut r.handle).poll(cx) {
+                    slot.running = None;
+                    return Poll::Ready((i, res));
+                }
+            }
+        }
+        Poll::Pending
+    })
+    .await
+}
@@ -0,0 +1,98 @@
+//! Ask/reply: typed ActorRef round trips, Busy on a full mailbox, deadline propagation and Timeout/Closed mapping.
+
+use ryker::actor::{ActorRef, Request};
+use ryker::config::RykerConfig;
+use ryker::mailbox::{MailboxError, MailboxRx};
+use ryker::runtime::Runtime;
+use std::time::Duration;
+
+type Inbox = MailboxRx<Request<u32, u32>>;
+
+fn actor(capacity: usize, deadline: Duration) -> (ActorRef<u32, u32>, Inbox) {
+    let rt = Runtime::new(RykerConfig::default());
+    ActorRef::from_mailbox(
+        rt.mailbox("doubler")
+            .capacity(capacity)
+            .deadline(deadline)
+            .build(),
+    )
+}
+
+#[tokio::test]
+async fn ask_returns_the_actors_reply() {
+    let (aref, mut inbox) = actor(8, Duration::from_secs(1));
+    tokio::spawn(async move {
+        while let Ok(req) = inbox.pull().await {
+            let n = *req.message();
+            req.reply(n * 2).unwrap();
+        }
+    });
+    let other = aref.clone();
+    assert_eq!(aref.ask(21).await.unwrap(), 42);
+    assert_eq!(other.ask(5).await.unwrap(), 10);
+    assert_eq!(aref.actor(), "doubler");
+}
+
+#[tokio::test]
+async fn full_mailbox_is_busy_not_blocking() {
+    let (aref, _inbox) = actor(1, Duration::from_secs(5));
+    let first = aref.clone();
+    // Occupies the only slot; nobody pulls.
+    let pending = tokio::spawn(async move { first.ask(1).await });
+    tokio::task::yield_now().await;
+    assert!(matches!(aref.ask(2).await, Err(MailboxError::Busy)));
+    pending.abort();
+}
+
+#[tokio::test]
+async fn deadline_travels_with_the_request() {
+    let (aref, mut inbox) = actor(8, Duration::from_millis(30));
+    let actor = tokio::spawn(async move {
+        let req = inbox.pull().await.unwrap();
+        assert!(!req.is_expired());
+        let remaining = req.deadline() - tokio::time::Instant::now();
+        assert!(remaining <= Duration::from_millis(30));
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(req.is_expired());
+        // The caller already gave up; the late reply is refused.
+        req.reply(0)
+    });
+    assert!(matches!(aref.ask(1).await, Err(MailboxError::Timeout)));
+    assert!(matches!(actor.await.unwrap(), Err(MailboxError::Timeout)));
+}
+
+#[tokio::test]
+async fn explicit_deadline_overrides_the_mailbox_default() {
+    let (aref, mut inbox) = actor(8, Duration::from_secs(5));
+    tokio::spawn(async move {
+        let _held = inbox.pull().await;
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    });
+    let started = std::time::Instant::now();
+    let res = aref.ask_with_deadline(1, Duration::from_millis(20)).await;
+    assert!(matches!(res, Err(MailboxError::Timeout)));
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn dropped_request_or_actor_is_closed() {
+    let (aref, mut inbox) = actor(8, Duration::from_secs(1));
+    let actor = tokio::spawn(async move {
+        // Drop the request without answering, then stop.
+        drop(inbox.pull().await.unwrap());
+    });
+    assert!(matches!(aref.ask(1).await, Err(MailboxError::Closed)));
+    actor.await.unwrap();
+    assert!(matches!(aref.ask(2).await, Err(MailboxError::Closed)));
+}
+
+#[tokio::test]
+async fn reply_handle_can_answer_from_another_task() {
+    let (aref, mut inbox) = actor(8, Duration::from_secs(1));
+    tokio::spawn(async move {
+        let (n, reply) = inbox.pull().await.unwrap().into_parts();
+        assert!(reply.remaining() > Duration::ZERO);
+        tokio::spawn(async move { reply.send(n + 1).unwrap() });
+    });
+    assert_eq!(aref.ask(1).await.unwrap(), 2);
+}
@@ -0,0 +1,174 @@
+//! Deterministic simulation (feature "sim"): seeded reordering reproduces exactly, virtual time fast-forwards
+//! deadlines, injected panics are supervised, and failing seeds can be replayed from the environment.
+#![cfg(feature = "sim")]
+
+use ryker::actor::ActorRef;
+use ryker::config::RykerConfig;
+use ryker::mailbox::MailboxError;
+use ryker::sim::{Sim, SimEvent, SimHandle, SEED_ENV};
+use ryker::supervisor::{ChildCtx, ChildSpec, TreeSpec};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Enqueue 0..8, then pull everything: the delivery order is the simulation's choice.
+async fn drain_eight(sim: SimHandle) -> Vec<u32> {
+    let rt = sim.runtime(RykerConfig::default());
+    let mut mb = rt.mailbox::<u32>("sink").capacity(16).build();
+    for i in 0..8 {
+        mb.try_send(i).unwrap();
+    }
+    let mut got = Vec::new();
+    for _ in 0..8 {
+        got.push(mb.pull().await.unwrap());
+    }
+    got
+}
+
+#[test]
+fn same_seed_same_schedule() {
+    let a = Sim::new(7).run(drain_eight);
+    let b = Sim::new(7).run(drain_eight);
+    assert_eq!(a.output, b.output);
+    assert_eq!(a.trace, b.trace);
+    let mut sorted = a.output.clone();
+    sorted.sort_unstable();
+    assert_eq!(
+        sorted,
+        (0..8).collect::<Vec<_>>(),
+        "nothing lost or duplicated"
+    );
+
+    let fifo: Vec<u32> = (0..8).collect();
+    assert!(
+        (0..32).any(|seed| Sim::new(seed).run(drain_eight).output != fifo),
+        "reordering never kicked in"
+    );
+    let plain = Sim::new(7).reorder_deliveries(false).run(drain_eight);
+    assert_eq!(plain.output, fifo);
+}
+
+#[test]
+fn simulated_pulls_keep_the_mailbox_bounded() {
+    let report = Sim::new(3).run(|sim| async move {
+        let rt = sim.runtime(RykerConfig::default());
+        let mut mb = rt.mailbox::<u32>("bounded").capacity(4).build();
+        for i in 0..4 {
+            mb.try_send(i).unwrap();
+        }
+        let first = mb.pull().await.unwrap();
+        // Only the delivered message's slot is free; the three drained into the sim still hold theirs.
+        mb.try_send(4).unwrap();
+        let full = mb.try_send(5);
+        let mut rest = Vec::new();
+        for _ in 0..4 {
+            rest.push(mb.pull().await.unwrap());
+        }
+        (first, full, rest)
+    });
+    let (first, full, mut rest) = report.output;
+    assert!(matches!(full, Err(MailboxError::Busy)), "{full:?}");
+    rest.push(first);
+    rest.sort_unstable();
+    assert_eq!(rest, (0..5).collect::<Vec<_>>());
+}
+
+#[test]
+fn deadlines_fast_forward_in_virtual_time() {
+    let started = std::time::Instant::now();
+    let report = Sim::new(1).run(|sim| async move {
+        let rt = sim.runtime(RykerConfig::default());
+        let (aref, mut inbox) = ActorRef::<u32, u32>::from_mailbox(
+            rt.mailbox("slow").deadline(Duration::from_secs(30)).build(),
+        );
+        tokio::spawn(async move {
+            let req = inbox.pull().await.unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            let _ = req.reply(0);
+        });
+        let t0 = tokio::time::Instant::now();
+        let res = aref.ask(1).await;
+        let waited = t0.elapsed();
+        sim.advance(Duration::from_secs(3600)).await;
+        (res, waited)
+    });
+    let (res, waited) = report.output;
+    assert!(matches!(res, Err(MailboxError::Timeout)));
+    assert_eq!(waited, Duration::from_secs(30));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+async fn supervised_panics(sim: SimHandle) -> usize {
+    let rt = sim.runtime(RykerConfig::default());
+    let runs = Arc::new(AtomicUsize::new(0));
+    let (aref, inbox) = ActorRef::<u32, u32>::from_mailbox(rt.mailbox("echo").capacity(8).build());
+    let inbox = Arc::new(tokio::sync::Mutex::new(inbox));
+    let counted = runs.clone();
+    let tree = TreeSpec::new("root", &RykerConfig::default())
+        .backoff(Duration::from_millis(10), Duration::from_millis(10))
+        .child(ChildSpec::new("echo", move |_ctx: ChildCtx| {
+            counted.fetch_add(1, Ordering::SeqCst);
+            let inbox = inbox.clone();
+            async move {
+                let mut inbox = inbox.lock().await;
+                loop {
+                    match inbox.pull().await {
+                        Ok(req) => {
+                            let n = *req.message();
+                            let _ = req.reply(n);
+                        }
+                        Err(MailboxError::Timeout) => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            }
+        }))
+        .start();
+    for i in 0..4 {
+        // The ask whose pull panics goes unanswered; retry it.
+        loop {
+            match aref.ask(i).await {
+                Ok(n) => {
+                    assert_eq!(n, i);
+                    break;
+                }
+                Err(MailboxError::Closed | MailboxError::Timeout) => continue,
+                Err(e) => panic!("{e}"),
+            }
+        }
+    }
+    tree.shutdown().await.unwrap();
+    runs.load(Ordering::SeqCst)
+}
+
+#[test]
+fn injected_panics_are_supervised_and_reproducible() {
+    let plan = || Sim::new(42).panic_on_pull("echo", 2);
+    let a = plan().run(supervised_panics);
+    let b = plan().run(supervised_panics);
+    assert_eq!(a.output, 2, "panic on pull 2 restarts the actor once");
+    assert!(a.trace.contains(&SimEvent::Panic {
+        actor: "echo".into(),
+        pull: 2
+    }));
+    assert_eq!(a.trace, b.trace);
+}
+
+#[test]
+fn failing_seeds_replay_from_the_environment() {
+    let failing = panic::catch_unwind(AssertUnwindSafe(|| {
+        Sim::new(99).run(|sim| async move {
+            let order = drain_eight(sim).await;
+            assert_eq!(order, (0..8).collect::<Vec<_>>(), "ordering bug");
+        })
+    }));
+    assert!(failing.is_err());
+
+    std::env::set_var(SEED_ENV, "99");
+    let replay = Sim::from_env();
+    std::env::remove_var(SEED_ENV);
+    assert_eq!(replay.seed(), 99);
+    let again = Sim::new(99).run(drain_eight);
+    assert_eq!(replay.run(drain_eight).trace, again.trace);
+}
@@ -0,0 +1,218 @@
+//! Supervision trees: restart strategies, restart policies, intensity escalation (nested and top-level),
+//! and reverse-order shutdown with abort after the grace period.
+
+use parking_lot::Mutex;
+use ryker::config::RykerConfig;
+use ryker::supervisor::{ChildCtx, ChildSpec, Restart, Strategy, SupervisorError, TreeSpec};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn tree(name: &str, strategy: Strategy) -> TreeSpec {
+    TreeSpec::new(name, &RykerConfig::default())
+        .strategy(strategy)
+        .backoff(Duration::ZERO, Duration::ZERO)
+        .intensity(10, Duration::from_secs(5))
+}
+
+/// Logs "start:<name>" / "stop:<name>" and runs until asked to stop.
+fn worker(name: &str, log: &Log) -> ChildSpec {
+    let log = log.clone();
+    ChildSpec::new(name, move |mut ctx: ChildCtx| {
+        let log = log.clone();
+        async move {
+            log.lock().push(format!("start:{}", ctx.name()));
+            ctx.shutdown_requested().await;
+            log.lock().push(format!("stop:{}", ctx.name()));
+            Ok(())
+        }
+    })
+}
+
+/// Fails `fails` times (after logging its start), then behaves like `worker`.
+fn flaky(name: &str, fails: usize, log: &Log) -> ChildSpec {
+    let log = log.clone();
+    let left = Arc::new(AtomicUsize::new(fails));
+    ChildSpec::new(name, move |mut ctx: ChildCtx| {
+        let log = log.clone();
+        let left = left.clone();
+        async move {
+            log.lock().push(format!("start:{}", ctx.name()));
+            if left
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                anyhow::bail!("boom");
+            }
+            ctx.shutdown_requested().await;
+            log.lock().push(format!("stop:{}", ctx.name()));
+            Ok(())
+        }
+    })
+}
+
+async fn settle() {
+    tokio::time::sleep(Duration::from_millis(50)).await;
+}
+
+fn starts(log: &Log, name: &str) -> usize {
+    let tag = format!("start:{name}");
+    log.lock().iter().filter(|l| **l == tag).count()
+}
+
+#[tokio::test]
+async fn one_for_one_restarts_only_the_failed_child() {
+    let log = Log::default();
+    let t = tree("root", Strategy::OneForOne)
+        .child(worker("a", &log))
+        .child(flaky("b", 2, &log))
+        .child(worker("c", &log))
+        .start();
+    settle().await;
+    assert_eq!(
+        (starts(&log, "a"), starts(&log, "b"), starts(&log, "c")),
+        (1, 3, 1)
+    );
+    t.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn one_for_all_restarts_every_child() {
+    let log = Log::default();
+    let t = tree("root", Strategy::OneForAll)
+        .child(worker("a", &log))
+        .child(flaky("b", 1, &log))
+        .child(worker("c", &log))
+        .start();
+    settle().await;
+    assert_eq!(
+        (starts(&log, "a"), starts(&log, "b"), starts(&log, "c")),
+        (2, 2, 2)
+    );
+    t.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn rest_for_one_restarts_the_failed_child_and_later_siblings() {
+    let log = Log::default();
+    let t = tree("root", Strategy::RestForOne)
+        .child(worker("a", &log))
+        .child(flaky("b", 1, &log))
+        .child(worker("c", &log))
+        .start();
+    settle().await;
+    assert_eq!(
+        (starts(&log, "a"), starts(&log, "b"), starts(&log, "c")),
+        (1, 2, 2)
+    );
+    // c was stopped before b came back.
+    let entries = log.lock().clone();
+    let stop_c = entries.iter().position(|l| l == "stop:c").unwrap();
+    let last_b = entries.iter().rposition(|l| l == "start:b").unwrap();
+    assert!(stop_c < last_b, "{entries:?}");
+    t.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn restart_policies_decide_what_comes_back() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let counted = |restart: Restart, fail: bool| {
+        let runs = runs.clone();
+        ChildSpec::new(format!("{restart:?}-{fail}"), move |_ctx: ChildCtx| {
+            let n = runs.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if fail && n < 100 {
+                    anyhow::bail!("fail");
+                }
+                Ok(())
+            }
+        })
+        .restart(restart)
+    };
+    // Transient + normal exit and Temporary + failure both end the tree without restarts.
+    let t = tree("root", Strategy::OneForOne)
+        .child(counted(Restart::Transient, false))
+        .child(counted(Restart::Temporary, true))
+        .start();
+    t.join().await.unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn exceeding_intensity_escalates() {
+    let log = Log::default();
+    let t = tree("root", Strategy::OneForOne)
+        .intensity(2, Duration::from_secs(5))
+        .child(worker("steady", &log))
+        .child(flaky("crashy", usize::MAX, &log))
+        .start();
+    match t.join().await {
+        Err(SupervisorError::Escalated {
+            name, max_restarts, ..
+        }) => {
+            assert_eq!(name, "root");
+            assert_eq!(max_restarts, 2);
+        }
+        other => panic!("expected escalation, got {other:?}"),
+    }
+    // Initial start plus two restarts; the sibling was stopped on the way out.
+    assert_eq!(starts(&log, "crashy"), 3);
+    assert!(log.lock().contains(&"stop:steady".to_string()));
+}
+
+#[tokio::test]
+async fn nested_supervisor_escalation_restarts_the_subtree() {
+    let log = Log::default();
+    let subtree = tree("sub", Strategy::OneForOne)
+        .intensity(1, Duration::from_secs(5))
+        .child(worker("leaf", &log))
+        .child(flaky("bad", 4, &log));
+    let t = tree("root", Strategy::OneForOne)
+        .child(ChildSpec::supervisor(subtree))
+        .start();
+    settle().await;
+    // Each subtree run tolerates one restart of "bad" and escalates on the second failure;
+    // the parent restarts the subtree until "bad" stops failing.
+    assert_eq!(starts(&log, "bad"), 5);
+    assert_eq!(starts(&log, "leaf"), 3);
+    t.shutdown().await.unwrap();
+    assert_eq!(log.lock().last().unwrap(), "stop:leaf");
+}
+
+#[tokio::test]
+async fn shutdown_is_in_reverse_start_order() {
+    let log = Log::default();
+    let t = tree("root", Strategy::OneForOne)
+        .child(worker("db", &log))
+        .child(worker("cache", &log))
+        .child(worker("http", &log))
+        .start();
+    settle().await;
+    t.shutdown().await.unwrap();
+    let stops: Vec<String> = log
+        .lock()
+        .iter()
+        .filter(|l| l.starts_with("stop:"))
+        .cloned()
+        .collect();
+    assert_eq!(stops, ["stop:http", "stop:cache", "stop:db"]);
+}
+
+#[tokio::test]
+async fn children_ignoring_shutdown_are_aborted_after_the_grace_period() {
+    let t = tree("root", Strategy::OneForOne)
+        .child(
+            ChildSpec::new("stubborn", |_ctx: ChildCtx| async {
+                tokio::time::sleep(Duration::from_secs(3600)).await;
+                Ok(())
+            })
+            .shutdown_timeout(Duration::from_millis(20)),
+        )
+        .start();
+    settle().await;
+    let started = std::time::Instant::now();
+    t.shutdown().await.unwrap();
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
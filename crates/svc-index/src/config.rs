@@ -10,6 +10,8 @@ pub struct Config {
     pub cache_ttl_secs: u64,
     pub ready_dep_timeout_ms: u64,
     pub enable_sled: bool,
+    /// UDS RPC socket for local tools (ronctl); `None` leaves it off.
+    pub uds_path: Option<String>,
 }
 
 impl Default for Config {
@@ -20,6 +22,7 @@ impl Default for Config {
             cache_ttl_secs: 30,
             ready_dep_timeout_ms: 1500,
             enable_sled: true,
+            uds_path: None,
         }
     }
 }
@@ -42,6 +45,9 @@ impl Config {
         if let Ok(v) = std::env::var("ENABLE_SLED") {
             cfg.enable_sled = v == "1" || v.eq_ignore_ascii_case("true");
         }
+        if let Ok(v) = std::env::var("RON_INDEX_SOCK") {
+            cfg.uds_path = Some(v).filter(|p| !p.is_empty());
+        }
         Ok(cfg)
     }
 }
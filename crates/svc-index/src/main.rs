@@ -9,7 +9,7 @@ use tokio::net::TcpListener;
 use tokio::signal;
 use tracing::{error, info, warn};
 
-use svc_index::{build_router, config::Config, logging, AppState};
+use svc_index::{build_router, config::Config, logging, net, AppState};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -25,6 +25,11 @@ async fn main() -> anyhow::Result<()> {
     // 3) Optional bootstrap gates (flip readiness, warm caches, etc.)
     let state = AppState::bootstrap(state).await;
 
+    // 3b) Local RPC for ronctl, when a socket path is configured
+    if let Some(sock) = cfg.uds_path.as_deref() {
+        net::uds::spawn(sock, state.store.clone())?;
+    }
+
     // 4) Build router WITHOUT state and inject state at the end
     //    This turns Router<Arc<AppState>> → Router<()>, which Axum 0.7 can serve.
     let app = build_router().with_state(state.clone());
@@ -1,4 +1,4 @@
-//! RO:WHAT — Net placeholders (UDS/TLS if needed later).
+//! RO:WHAT — Net listeners: the UDS RPC endpoint (TLS placeholder for later).
 pub mod listener {}
-pub mod uds {}
 pub mod tls {}
+pub mod uds;
@@ -1 +1,79 @@
-// uds server setup placeholder — (auto-generated by scaffold_svc_index2.sh; replace with real content later)
+//! RO:WHAT — UDS RPC endpoint: ron-bus `IndexReq`/`IndexResp` envelopes over `ron_bus::uds` (ronctl's transport).
+//! RO:WHY — Pillar 9; Concerns: DX/SEC. Local tools register and resolve bundle addresses without HTTP.
+//! RO:INTERACTS — ron_bus::{api, uds}; store::{Store, keys}; tools/ronctl.
+//! RO:INVARIANTS — socket 0600 in a 0700 dir; only peers running as this uid; one blocking thread per connection;
+//!                 a request that cannot be handled gets an error frame, never a silently dropped connection.
+//! RO:CONFIG — `Config.uds_path` (`RON_INDEX_SOCK`); unset disables the endpoint.
+//! RO:SECURITY — tokens are ignored (the peer-credential policy authorizes); payloads are never logged.
+//! RO:TEST — tests/uds_rpc.rs.
+
+use std::{io, os::unix::net::UnixStream, path::Path, thread};
+
+use ron_bus::{
+    api::{Envelope, IndexReq, IndexResp, SVC_INDEX},
+    uds::{self, PeerPolicy, UdsError},
+};
+use tracing::{info, warn};
+
+use crate::store::{keys, Store};
+
+/// Answer one request against `store`.
+pub fn handle(store: &Store, req: &Envelope) -> Result<Envelope, UdsError> {
+    if req.service != SVC_INDEX {
+        return Err(UdsError::Decode(format!(
+            "svc-index does not serve {:?}",
+            req.service
+        )));
+    }
+    let resp = match req.decode::<IndexReq>()? {
+        IndexReq::Health => IndexResp::HealthOk,
+        IndexReq::Resolve { addr } => match store.get_manifest(&keys::address_key(&addr)) {
+            Some(dir) => IndexResp::Resolved { dir },
+            None => IndexResp::NotFound,
+        },
+        IndexReq::PutAddress { addr, dir } if addr.is_empty() || dir.is_empty() => IndexResp::Err {
+            err: "addr and dir must be non-empty".into(),
+        },
+        IndexReq::PutAddress { addr, dir } => {
+            store.put_manifest(&keys::address_key(&addr), &dir);
+            IndexResp::PutOk
+        }
+        _ => IndexResp::Err {
+            err: "unsupported request".into(),
+        },
+    };
+    req.reply(&resp)
+}
+
+/// Bind `path` and serve it on background threads for the life of the process.
+pub fn spawn(path: impl AsRef<Path>, store: Store) -> io::Result<()> {
+    let path = path.as_ref();
+    let listener = uds::listen(path)?;
+    info!(sock = %path.display(), "svc-index UDS RPC listening");
+    thread::Builder::new()
+        .name("svc-index-uds".into())
+        .spawn(move || {
+            for conn in listener.incoming() {
+                match conn {
+                    Ok(stream) => serve(stream, store.clone()),
+                    Err(e) => warn!(error = %e, "uds accept failed"),
+                }
+            }
+        })?;
+    Ok(())
+}
+
+fn serve(stream: UnixStream, store: Store) {
+    let spawned = thread::Builder::new()
+        .name("svc-index-uds-conn".into())
+        .spawn(move || {
+            if let Err(e) =
+                uds::serve_conn(stream, &PeerPolicy::default(), |req| handle(&store, req))
+            {
+                warn!(error = %e, "uds connection ended with an error");
+            }
+        });
+    if let Err(e) = spawned {
+        warn!(error = %e, "uds connection thread failed to start");
+    }
+}
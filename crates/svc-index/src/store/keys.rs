@@ -13,6 +13,15 @@ pub const ASSET_MANIFEST_PREFIX: &str = "asset_manifest:";
 /// Key prefix for site/name → mutable site manifest pointer records.
 pub const SITE_MANIFEST_PREFIX: &str = "site_manifest:";
 
+/// Key prefix for bundle address (`b3:<hex>.ext`) → local directory records (UDS `PutAddress`).
+pub const ADDRESS_PREFIX: &str = "address:";
+
+/// Build the storage key for a bundle address mapping.
+#[must_use]
+pub fn address_key(addr: &str) -> String {
+    format!("{ADDRESS_PREFIX}{addr}")
+}
+
 /// Build the storage key for an asset manifest pointer.
 ///
 /// `canonical_asset_cid` must already be normalized as `b3:<64 lowercase hex>`.
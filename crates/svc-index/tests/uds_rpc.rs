@@ -0,0 +1,58 @@
+//! UDS RPC: ronctl's IndexReq/IndexResp envelopes round-trip through the svc-index handler, and
+//! requests the handler cannot decode get an error frame on a connection that stays usable.
+
+use std::path::PathBuf;
+
+use ron_bus::{
+    api::{IndexReq, IndexResp, SVC_INDEX},
+    uds::{Client, UdsError},
+};
+use svc_index::{net, store::Store};
+
+fn sock_path() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    std::env::temp_dir()
+        .join(format!("svc-index-uds-{}-{nanos}", std::process::id()))
+        .join("svc-index.sock")
+}
+
+#[test]
+fn ronctl_requests_are_served_over_the_socket() {
+    let sock = sock_path();
+    net::uds::spawn(&sock, Store::new(false).unwrap()).unwrap();
+    let mut client = Client::connect(&sock, SVC_INDEX).unwrap();
+
+    let health: IndexResp = client.call("v1.health", &IndexReq::Health).unwrap();
+    assert_eq!(health, IndexResp::HealthOk);
+
+    let addr = format!("b3:{}.txt", "ab".repeat(32));
+    let resolve = IndexReq::Resolve { addr: addr.clone() };
+    let missing: IndexResp = client.call("v1.resolve", &resolve).unwrap();
+    assert_eq!(missing, IndexResp::NotFound);
+
+    let put = IndexReq::PutAddress {
+        addr: addr.clone(),
+        dir: "/bundles/ab".into(),
+    };
+    let stored: IndexResp = client.call("v1.put_address", &put).unwrap();
+    assert_eq!(stored, IndexResp::PutOk);
+    let found: IndexResp = client.call("v1.resolve", &resolve).unwrap();
+    assert_eq!(
+        found,
+        IndexResp::Resolved {
+            dir: "/bundles/ab".into()
+        }
+    );
+
+    match client.call::<_, IndexResp>("v1.resolve", &42_u32) {
+        Err(UdsError::Remote(msg)) => assert!(msg.contains("decode"), "{msg}"),
+        other => panic!("expected an error frame, got {other:?}"),
+    }
+    let again: IndexResp = client.call("v1.health", &IndexReq::Health).unwrap();
+    assert_eq!(again, IndexResp::HealthOk);
+
+    let _ = std::fs::remove_dir_all(sock.parent().unwrap());
+}
@@ -1,2 +1,352 @@
-// Typed configuration placeholder (env > file > defaults).
+//! RO:WHAT — Typed svc-mailbox configuration (defaults < TOML file < `SVCMBX_*` env).
+//! RO:WHY  — One validated snapshot drives shard topology, leases, replay window, and retry policy.
+//! RO:INTERACTS — main.rs (`Config::load`), domain::Mailbox (`MailboxConfig`), http (payload cap),
+//!                runtime::supervisor (`journal_dir` picks memory-only vs durable).
+//! RO:INVARIANTS — fail-closed validation (docs/CONFIG.MD §4); t_replay ≥ 2 × default_visibility;
+//!                 global_inflight ≥ shard_capacity; durations are monotonic-clock budgets;
+//!                 amnesia wins over `persistence.dir` (nothing touches disk).
 
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+/// Env var naming a TOML config file.
+pub const CONFIG_PATH_ENV: &str = "SVCMBX_CONFIG";
+
+/// OAP/1 frame cap; payloads above this are refused with 413.
+pub const MAX_FRAME_BYTES: usize = 1024 * 1024;
+
+/// Top-level service configuration.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// HTTP bind address (serves `/v1/*`, `/healthz`, `/readyz`, `/metrics`).
+    pub bind_addr: SocketAddr,
+    /// Ingress limits.
+    pub limits: LimitsConfig,
+    /// Queue, lease, and retry policy.
+    pub mailbox: MailboxConfig,
+    /// RAM-only mode.
+    pub amnesia: AmnesiaConfig,
+    /// On-disk journal.
+    pub persistence: PersistenceConfig,
+    /// Caller authentication.
+    pub auth: AuthConfig,
+}
+
+/// Caller authentication.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Bearer token for operator endpoints (`/v1/dlq/reprocess`); unset disables them.
+    pub operator_token: Option<String>,
+    /// Secret the gateway sends in `X-RON-Gateway-Token`; `X-RON-Tenant` is only trusted alongside it.
+    /// Unset refuses every tenant-scoped call.
+    pub gateway_token: Option<String>,
+}
+
+/// Amnesia mode.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AmnesiaConfig {
+    /// Keep every queue, lease, and dead letter in memory only; `persistence` is ignored.
+    pub enabled: bool,
+}
+
+/// Durable journal settings; the mailbox is memory-only unless `dir` is set.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PersistenceConfig {
+    /// Journal root (one `shard-NNN/` subdirectory per shard).
+    pub dir: Option<PathBuf>,
+    /// Segment size before the journal rolls (and considers compaction).
+    pub segment_bytes: u64,
+    /// Sync every append to disk; without it a process crash is survived but a power loss may not be.
+    pub fsync: bool,
+}
+
+/// Ingress limits.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Max decoded payload bytes per SEND (OAP/1 frame cap).
+    pub max_body_bytes: usize,
+}
+
+/// Mailbox queue/lease knobs. Capacities are per shard unless noted.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MailboxConfig {
+    /// Number of ready-queue shards; topics hash onto shards.
+    pub ready_shards: u16,
+    /// Max ready + leased messages held by one shard.
+    pub shard_capacity: u32,
+    /// Max leased (received, un-ACKed) messages across all shards.
+    pub global_inflight: u32,
+    /// Shortest visibility timeout a RECV may ask for.
+    #[serde(with = "humantime_serde")]
+    pub visibility_ms_min: Duration,
+    /// Longest visibility timeout a RECV may ask for.
+    #[serde(with = "humantime_serde")]
+    pub visibility_max: Duration,
+    /// Visibility timeout used when a RECV omits one.
+    #[serde(with = "humantime_serde")]
+    pub default_visibility: Duration,
+    /// Max envelopes returned per RECV.
+    pub max_messages: u16,
+    /// Soft cap on payload bytes per RECV (the first message is always returned).
+    pub max_bytes: usize,
+    /// Idempotency replay window.
+    #[serde(with = "humantime_serde")]
+    pub t_replay: Duration,
+    /// Max idempotency records held by one shard.
+    pub dedup_capacity: u32,
+    /// Base delay for NACK backoff.
+    #[serde(with = "humantime_serde")]
+    pub backoff_base: Duration,
+    /// Upper clamp for NACK backoff.
+    #[serde(with = "humantime_serde")]
+    pub backoff_max: Duration,
+    /// Deliveries before a message is dead-lettered.
+    pub max_attempts: u32,
+    /// Max dead letters held by one shard; the oldest is evicted past this.
+    pub dlq_capacity: u32,
+    /// How often the scanner requeues expired leases.
+    #[serde(with = "humantime_serde")]
+    pub scan_interval: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            bind_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            limits: LimitsConfig::default(),
+            mailbox: MailboxConfig::default(),
+            amnesia: AmnesiaConfig::default(),
+            persistence: PersistenceConfig::default(),
+            auth: AuthConfig::default(),
+        }
+    }
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            segment_bytes: 8 * 1024 * 1024,
+            fsync: true,
+        }
+    }
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: MAX_FRAME_BYTES,
+        }
+    }
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self {
+            ready_shards: 8,
+            shard_capacity: 4096,
+            global_inflight: 8192,
+            visibility_ms_min: Duration::from_millis(250),
+            visibility_max: Duration::from_secs(12 * 60 * 60),
+            default_visibility: Duration::from_secs(5),
+            max_messages: 32,
+            max_bytes: 512 * 1024,
+            t_replay: Duration::from_secs(300),
+            dedup_capacity: 65_536,
+            backoff_base: Duration::from_millis(200),
+            backoff_max: Duration::from_secs(60),
+            max_attempts: 5,
+            dlq_capacity: 4096,
+            scan_interval: Duration::from_millis(100),
+        }
+    }
+}
+
+impl Config {
+    /// Defaults, then `$SVCMBX_CONFIG` (TOML) if set, then `SVCMBX_*` env overrides; validated.
+    pub fn load() -> anyhow::Result<Self> {
+        let mut cfg = match std::env::var(CONFIG_PATH_ENV) {
+            Ok(path) => {
+                let text = std::fs::read_to_string(&path)
+                    .with_context(|| format!("reading config file {path}"))?;
+                Self::from_toml(&text)?
+            }
+            Err(_) => Self::default(),
+        };
+        cfg.apply_env(|key| std::env::var(key).ok())?;
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// Parse a TOML document (unset keys keep their defaults).
+    pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+        toml::from_str(text).context("parsing svc-mailbox config")
+    }
+
+    /// Apply `SVCMBX_*` overrides from `get` (env in production, a map in tests).
+    pub fn apply_env(&mut self, get: impl Fn(&str) -> Option<String>) -> anyhow::Result<()> {
+        fn parse<T: std::str::FromStr>(key: &str, raw: String) -> anyhow::Result<T>
+        where
+            T::Err: std::fmt::Display,
+        {
+            raw.trim()
+                .parse()
+                .map_err(|e| anyhow::anyhow!("{key}: {e}"))
+        }
+        fn duration(key: &str, raw: String) -> anyhow::Result<Duration> {
+            humantime::parse_duration(raw.trim()).map_err(|e| anyhow::anyhow!("{key}: {e}"))
+        }
+
+        let m = &mut self.mailbox;
+        if let Some(v) = get("SVCMBX_BIND_ADDR") {
+            self.bind_addr = parse("SVCMBX_BIND_ADDR", v)?;
+        }
+        if let Some(v) = get("SVCMBX_MAX_BODY_BYTES") {
+            self.limits.max_body_bytes = parse("SVCMBX_MAX_BODY_BYTES", v)?;
+        }
+        if let Some(v) = get("SVCMBX_SHARDS") {
+            m.ready_shards = parse("SVCMBX_SHARDS", v)?;
+        }
+        if let Some(v) = get("SVCMBX_SHARD_CAP") {
+            m.shard_capacity = parse("SVCMBX_SHARD_CAP", v)?;
+        }
+        if let Some(v) = get("SVCMBX_GLOBAL_INFLIGHT") {
+            m.global_inflight = parse("SVCMBX_GLOBAL_INFLIGHT", v)?;
+        }
+        if let Some(v) = get("SVCMBX_VISIBILITY_MS_MIN") {
+            m.visibility_ms_min = duration("SVCMBX_VISIBILITY_MS_MIN", v)?;
+        }
+        if let Some(v) = get("SVCMBX_VISIBILITY_DEFAULT") {
+            m.default_visibility = duration("SVCMBX_VISIBILITY_DEFAULT", v)?;
+        }
+        if let Some(v) = get("SVCMBX_MAX_MESSAGES") {
+            m.max_messages = parse("SVCMBX_MAX_MESSAGES", v)?;
+        }
+        if let Some(v) = get("SVCMBX_MAX_BYTES") {
+            m.max_bytes = parse("SVCMBX_MAX_BYTES", v)?;
+        }
+        if let Some(v) = get("SVCMBX_T_REPLAY") {
+            m.t_replay = duration("SVCMBX_T_REPLAY", v)?;
+        }
+        if let Some(v) = get("SVCMBX_BACKOFF_BASE") {
+            m.backoff_base = duration("SVCMBX_BACKOFF_BASE", v)?;
+        }
+        if let Some(v) = get("SVCMBX_BACKOFF_MAX") {
+            m.backoff_max = duration("SVCMBX_BACKOFF_MAX", v)?;
+        }
+        if let Some(v) = get("SVCMBX_MAX_ATTEMPTS") {
+            m.max_attempts = parse("SVCMBX_MAX_ATTEMPTS", v)?;
+        }
+        if let Some(v) = get("SVCMBX_AMNESIA") {
+            self.amnesia.enabled = parse("SVCMBX_AMNESIA", v)?;
+        }
+        if let Some(v) = get("SVCMBX_DATA_DIR") {
+            self.persistence.dir = (!v.trim().is_empty()).then(|| PathBuf::from(v.trim()));
+        }
+        if let Some(v) = get("SVCMBX_FSYNC") {
+            self.persistence.fsync = parse("SVCMBX_FSYNC", v)?;
+        }
+        if let Some(v) = get("SVCMBX_OPERATOR_TOKEN") {
+            self.auth.operator_token = (!v.trim().is_empty()).then(|| v.trim().to_string());
+        }
+        if let Some(v) = get("SVCMBX_GATEWAY_TOKEN") {
+            self.auth.gateway_token = (!v.trim().is_empty()).then(|| v.trim().to_string());
+        }
+        Ok(())
+    }
+
+    /// Fail-closed checks from docs/CONFIG.MD §4.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.limits.max_body_bytes < 1024 || self.limits.max_body_bytes > MAX_FRAME_BYTES {
+            bail!("limits.max_body_bytes must be within [1 KiB, 1 MiB]");
+        }
+        if self.persistence.segment_bytes < 4096 {
+            bail!("persistence.segment_bytes must be >= 4 KiB");
+        }
+        if self
+            .persistence
+            .dir
+            .as_ref()
+            .is_some_and(|d| d.as_os_str().is_empty())
+        {
+            bail!("persistence.dir cannot be empty");
+        }
+        if self
+            .auth
+            .operator_token
+            .as_ref()
+            .is_some_and(|t| t.trim().len() < 16)
+        {
+            bail!("auth.operator_token must be at least 16 characters");
+        }
+        if self
+            .auth
+            .gateway_token
+            .as_ref()
+            .is_some_and(|t| t.trim().len() < 16)
+        {
+            bail!("auth.gateway_token must be at least 16 characters");
+        }
+        self.mailbox.validate(self.limits.max_body_bytes)
+    }
+
+    /// Journal root when running durable: `persistence.dir`, unless amnesia is on.
+    pub fn journal_dir(&self) -> Option<&Path> {
+        if self.amnesia.enabled {
+            return None;
+        }
+        self.persistence.dir.as_deref()
+    }
+}
+
+impl MailboxConfig {
+    /// Validate mailbox knobs against the ingress payload cap.
+    pub fn validate(&self, max_body_bytes: usize) -> anyhow::Result<()> {
+        if self.ready_shards == 0 || self.shard_capacity == 0 {
+            bail!("mailbox.ready_shards and mailbox.shard_capacity must be >= 1");
+        }
+        if self.global_inflight < self.shard_capacity {
+            bail!("mailbox.global_inflight must be >= mailbox.shard_capacity");
+        }
+        if self.visibility_ms_min < Duration::from_millis(1)
+            || self.default_visibility < self.visibility_ms_min
+            || self.visibility_max < self.default_visibility
+        {
+            bail!("visibility bounds must satisfy 1ms <= visibility_ms_min <= default_visibility <= visibility_max");
+        }
+        if !(1..=256).contains(&self.max_messages) {
+            bail!("mailbox.max_messages must be within [1, 256]");
+        }
+        if self.max_bytes == 0 || self.max_bytes > max_body_bytes {
+            bail!("mailbox.max_bytes must be within [1, limits.max_body_bytes]");
+        }
+        if self.t_replay < self.default_visibility * 2 {
+            bail!("mailbox.t_replay must be >= 2 * mailbox.default_visibility");
+        }
+        if self.dedup_capacity == 0 || self.dlq_capacity == 0 {
+            bail!("mailbox.dedup_capacity and mailbox.dlq_capacity must be >= 1");
+        }
+        if self.backoff_max < self.backoff_base {
+            bail!("mailbox.backoff_max must be >= mailbox.backoff_base");
+        }
+        if self.max_attempts == 0 {
+            bail!("mailbox.max_attempts must be >= 1");
+        }
+        if self.scan_interval.is_zero() {
+            bail!("mailbox.scan_interval must be > 0");
+        }
+        Ok(())
+    }
+}
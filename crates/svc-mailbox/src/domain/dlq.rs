@@ -1,2 +1,120 @@
-// DLQ envelope and reprocess semantics placeholder.
+//! RO:WHAT — Per-shard dead-letter queue with `{reason, attempt, last_error}` and operator reprocess.
+//! RO:WHY  — IDB [I-4]: over-retried messages are quarantined, never dropped silently; reprocessing is explicit.
+//! RO:INTERACTS — domain::shard (producer on expiry/NACK, consumer on reprocess), metrics (dlq_total).
+//! RO:INVARIANTS — bounded by `dlq_capacity`; an eviction is returned to the caller so it is logged and counted;
+//!                 FIFO per topic; reprocessed messages restart at attempt 0.
 
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use super::envelope::Envelope;
+
+/// Why a message was dead-lettered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DlqReason {
+    /// Lease expired on the final attempt.
+    MaxAttempts,
+    /// Consumer NACKed the final attempt.
+    Rejected,
+}
+
+impl DlqReason {
+    /// Metric label.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MaxAttempts => "max_attempts",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// A quarantined message.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    /// The message, with the attempt count it died at.
+    pub envelope: Envelope,
+    /// Why it was quarantined.
+    pub reason: DlqReason,
+    /// Consumer-supplied NACK reason, if any.
+    pub last_error: Option<String>,
+}
+
+/// Dead letters for one shard.
+#[derive(Debug)]
+pub struct Dlq {
+    topics: HashMap<String, VecDeque<DeadLetter>>,
+    len: usize,
+    capacity: usize,
+}
+
+impl Dlq {
+    /// Empty DLQ holding at most `capacity` letters.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            topics: HashMap::new(),
+            len: 0,
+            capacity,
+        }
+    }
+
+    /// Quarantine `letter`; returns the letter evicted to make room, if any.
+    pub fn push(&mut self, letter: DeadLetter) -> Option<DeadLetter> {
+        let evicted = if self.len >= self.capacity {
+            self.evict_oldest()
+        } else {
+            None
+        };
+        self.topics
+            .entry(letter.envelope.topic.clone())
+            .or_default()
+            .push_back(letter);
+        self.len += 1;
+        evicted
+    }
+
+    /// Take up to `limit` letters for `topic`, oldest first.
+    pub fn drain(&mut self, topic: &str, limit: usize) -> Vec<DeadLetter> {
+        let Some(queue) = self.topics.get_mut(topic) else {
+            return Vec::new();
+        };
+        let n = limit.min(queue.len());
+        let out: Vec<_> = queue.drain(..n).collect();
+        if queue.is_empty() {
+            self.topics.remove(topic);
+        }
+        self.len -= out.len();
+        out
+    }
+
+    /// Every letter; oldest first within each topic.
+    pub fn iter(&self) -> impl Iterator<Item = &DeadLetter> {
+        self.topics.values().flatten()
+    }
+
+    /// Letters waiting for `topic`.
+    pub fn topic_len(&self, topic: &str) -> usize {
+        self.topics.get(topic).map_or(0, VecDeque::len)
+    }
+
+    /// Letters held in total.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True when the DLQ is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn evict_oldest(&mut self) -> Option<DeadLetter> {
+        // Topics are independent FIFOs; evict from the longest one so one noisy topic pays.
+        let topic = self
+            .topics
+            .iter()
+            .max_by_key(|(_, q)| q.len())
+            .map(|(t, _)| t.clone())?;
+        self.drain(&topic, 1).pop()
+    }
+}
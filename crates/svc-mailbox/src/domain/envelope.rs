@@ -1,2 +1,61 @@
-// Envelope model placeholder: msg_id, topic, idem_key, payload_hash, attempt, version header.
+//! RO:WHAT — Stored message envelope: server msg_id, topic, sender, kind, idem_key, payload + BLAKE3 hash, attempt.
+//! RO:WHY  — One owned record moves between ready → leased → (acked | requeued | DLQ) without copying the payload.
+//! RO:INTERACTS — ron_proto::mailbox::{Send, Recv}; shard, visibility, dlq.
+//! RO:INVARIANTS — payload_hash = blake3_256(payload); msg_id is a fresh ULID per accepted SEND;
+//!                 attempt counts deliveries (0 until first RECV).
 
+use bytes::Bytes;
+use ron_proto::mailbox::{Recv, Send};
+
+/// A message held by the mailbox.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Envelope {
+    /// Server-assigned id used for ACK/NACK.
+    pub msg_id: String,
+    /// Destination topic (`Send::to`).
+    pub topic: String,
+    /// Sending tenant.
+    pub from: String,
+    /// Application message kind.
+    pub kind: String,
+    /// Idempotency key (`Send::idempotency_key`, falling back to the sender's `msg_id`).
+    pub idem_key: String,
+    /// Message body.
+    pub payload: Bytes,
+    /// BLAKE3-256 of `payload`.
+    pub payload_hash: [u8; 32],
+    /// Deliveries so far.
+    pub attempt: u32,
+}
+
+impl Envelope {
+    /// Build an envelope for an accepted SEND from `from`.
+    pub fn from_send(from: &str, send: Send) -> Self {
+        let payload = Bytes::from(send.payload);
+        Self {
+            msg_id: ulid::Ulid::new().to_string(),
+            topic: send.to,
+            from: from.to_string(),
+            kind: send.kind,
+            idem_key: send.idempotency_key.unwrap_or(send.msg_id),
+            payload_hash: *blake3::hash(&payload).as_bytes(),
+            payload,
+            attempt: 0,
+        }
+    }
+
+    /// `b3:<hex>` form of the payload hash.
+    pub fn payload_hash_b3(&self) -> String {
+        format!("b3:{}", hex::encode(self.payload_hash))
+    }
+
+    /// Consumer-facing DTO.
+    pub fn to_recv(&self) -> Recv {
+        Recv {
+            msg_id: self.msg_id.clone(),
+            from: self.from.clone(),
+            kind: self.kind.clone(),
+            payload: self.payload.to_vec(),
+        }
+    }
+}
@@ -1,2 +1,84 @@
-// Idempotency triple checks placeholder (topic, idem_key, payload_hash).
+//! RO:WHAT — Bounded replay table keyed by (topic, idem_key, payload_hash) → original msg_id.
+//! RO:WHY  — IDB [I-2]/[I-13]: duplicate SENDs inside T_replay return the first msg_id instead of enqueuing again.
+//! RO:INTERACTS — domain::shard (one table per shard; a topic always lands on the same shard).
+//! RO:INVARIANTS — records live at least `window`; when full of live records the table refuses new ones
+//!                 (Saturated) rather than evicting early and shrinking the replay window.
 
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+/// Idempotency triple.
+pub type IdemKey = (String, String, [u8; 32]);
+
+/// Replay table for one shard.
+#[derive(Debug)]
+pub struct IdemTable {
+    entries: HashMap<IdemKey, (String, Instant)>,
+    order: VecDeque<(Instant, IdemKey)>,
+    capacity: usize,
+    window: Duration,
+}
+
+impl IdemTable {
+    /// Table holding up to `capacity` records for `window`.
+    pub fn new(capacity: usize, window: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+            window,
+        }
+    }
+
+    /// msg_id recorded for `key` within the window.
+    pub fn lookup(&mut self, key: &IdemKey, now: Instant) -> Option<String> {
+        self.prune(now);
+        self.entries.get(key).map(|(msg_id, _)| msg_id.clone())
+    }
+
+    /// Whether one more record fits without evicting a live one.
+    pub fn has_room(&mut self, now: Instant) -> bool {
+        self.prune(now);
+        self.entries.len() < self.capacity
+    }
+
+    /// Record `key → msg_id` at `now`.
+    pub fn insert(&mut self, key: IdemKey, msg_id: String, now: Instant) {
+        self.order.push_back((now, key.clone()));
+        self.entries.insert(key, (msg_id, now));
+    }
+
+    /// Records as `(key, msg_id, accepted at)`; may include expired ones not yet pruned.
+    pub fn iter(&self) -> impl Iterator<Item = (&IdemKey, &str, Instant)> {
+        self.entries
+            .iter()
+            .map(|(key, (msg_id, at))| (key, msg_id.as_str(), *at))
+    }
+
+    /// Live records.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True when no records are held.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while let Some((at, _)) = self.order.front() {
+            if now.saturating_duration_since(*at) < self.window {
+                break;
+            }
+            let (at, key) = self.order.pop_front().expect("front checked");
+            // Only drop the record this queue slot created (a key may be re-inserted later).
+            if self.entries.get(&key).is_some_and(|(_, t)| *t == at) {
+                self.entries.remove(&key);
+            }
+        }
+    }
+}
@@ -0,0 +1,396 @@
+//! RO:WHAT — `Mailbox`: the sharded store-and-forward core (SEND / RECV / ACK / NACK / scan / DLQ reprocess).
+//! RO:WHY  — One entry point enforcing the global inflight ceiling and keeping metrics in step with shard state.
+//! RO:INTERACTS — domain::shard (per-topic work), metrics, http::routes, runtime::{scanner, worker},
+//!                storage (per-shard journals when opened durable).
+//! RO:INVARIANTS — at-least-once: a leased message is ACKed, redelivered, or dead-lettered, never dropped;
+//!                 consumer-held leases ≤ global_inflight; topic → shard mapping is a stable BLAKE3 hash.
+
+use std::{
+    io,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use ron_proto::mailbox::Send;
+use tokio::time::Instant;
+
+use super::{
+    envelope::Envelope,
+    shard::{NackOutcome, PutOutcome, Shard},
+};
+use crate::{
+    config::MailboxConfig,
+    error::MailboxError,
+    metrics::Metrics,
+    storage::{self, Journal, JournalOptions, JournalWriter},
+};
+
+/// Max topic length in bytes.
+pub const MAX_TOPIC_LEN: usize = 256;
+
+/// Result of a SEND.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SendOutcome {
+    /// Id to ACK/NACK by (the original id for duplicates).
+    pub msg_id: String,
+    /// True when answered from the idempotency table.
+    pub duplicate: bool,
+}
+
+/// Caller-supplied RECV knobs; unset fields fall back to config.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RecvOptions {
+    /// Lease length.
+    pub visibility: Option<Duration>,
+    /// Max envelopes to return (clamped to `max_messages`).
+    pub max_messages: Option<u16>,
+    /// Soft payload byte budget (clamped to `max_bytes`).
+    pub max_bytes: Option<usize>,
+}
+
+/// RECV knobs after defaults, clamping, and validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecvLimits {
+    /// Lease length.
+    pub visibility: Duration,
+    /// Max envelopes per batch.
+    pub max_messages: usize,
+    /// Soft payload byte budget per batch.
+    pub max_bytes: usize,
+}
+
+impl RecvOptions {
+    /// Apply defaults and bounds from `cfg`.
+    pub fn resolve(&self, cfg: &MailboxConfig) -> Result<RecvLimits, MailboxError> {
+        let visibility = self.visibility.unwrap_or(cfg.default_visibility);
+        if visibility < cfg.visibility_ms_min || visibility > cfg.visibility_max {
+            return Err(MailboxError::Schema(format!(
+                "visibility_ms must be within [{}, {}]",
+                cfg.visibility_ms_min.as_millis(),
+                cfg.visibility_max.as_millis()
+            )));
+        }
+        let max_messages = self.max_messages.unwrap_or(cfg.max_messages);
+        let max_bytes = self.max_bytes.unwrap_or(cfg.max_bytes);
+        if max_messages == 0 || max_bytes == 0 {
+            return Err(MailboxError::Schema(
+                "max_messages and max_bytes must be > 0".into(),
+            ));
+        }
+        Ok(RecvLimits {
+            visibility,
+            max_messages: usize::from(max_messages.min(cfg.max_messages)),
+            max_bytes: max_bytes.min(cfg.max_bytes),
+        })
+    }
+}
+
+/// Sharded mailbox, in memory or journaled to disk.
+#[derive(Debug)]
+pub struct Mailbox {
+    cfg: MailboxConfig,
+    shards: Vec<Shard>,
+    inflight: AtomicUsize,
+    metrics: Metrics,
+}
+
+impl Mailbox {
+    /// Empty memory-only mailbox with `cfg.ready_shards` shards.
+    pub fn new(cfg: MailboxConfig, metrics: Metrics) -> Self {
+        let shards = (0..cfg.ready_shards).map(|i| Shard::new(i, &cfg)).collect();
+        Self::from_shards(cfg, shards, metrics)
+    }
+
+    /// Durable mailbox journaled under `dir`, restoring whatever a previous run left there.
+    ///
+    /// Restored leases keep their wall-clock deadlines; ones that lapsed while down are
+    /// redelivered by the next scan.
+    pub fn open(
+        cfg: MailboxConfig,
+        metrics: Metrics,
+        dir: &Path,
+        opts: JournalOptions,
+    ) -> io::Result<Self> {
+        storage::check_layout(dir, cfg.ready_shards)?;
+        let mut shards = Vec::with_capacity(usize::from(cfg.ready_shards));
+        for index in 0..cfg.ready_shards {
+            let (journal, records) = Journal::open(&storage::shard_dir(dir, index), opts)?;
+            let restored = storage::replay(records)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let writer = JournalWriter::spawn(index, journal)?;
+            shards.push(Shard::restore(index, &cfg, writer, restored));
+        }
+        let mailbox = Self::from_shards(cfg, shards, metrics);
+        let stats: Vec<_> = mailbox.shards.iter().map(Shard::stats).collect();
+        mailbox
+            .inflight
+            .store(stats.iter().map(|s| s.inflight).sum(), Ordering::Release);
+        tracing::info!(
+            dir = %dir.display(),
+            ready = stats.iter().map(|s| s.ready).sum::<usize>(),
+            inflight = mailbox.inflight(),
+            backoff = stats.iter().map(|s| s.backoff).sum::<usize>(),
+            dlq = stats.iter().map(|s| s.dlq).sum::<usize>(),
+            "mailbox: restored from journal"
+        );
+        Ok(mailbox)
+    }
+
+    fn from_shards(cfg: MailboxConfig, shards: Vec<Shard>, metrics: Metrics) -> Self {
+        let mailbox = Self {
+            cfg,
+            shards,
+            inflight: AtomicUsize::new(0),
+            metrics,
+        };
+        for shard in &mailbox.shards {
+            mailbox.observe(shard);
+        }
+        mailbox
+    }
+
+    /// Active configuration.
+    pub fn config(&self) -> &MailboxConfig {
+        &self.cfg
+    }
+
+    /// Metric handles.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Shard owning `topic`.
+    pub fn shard_for(&self, topic: &str) -> &Shard {
+        let digest = blake3::hash(topic.as_bytes());
+        let mut prefix = [0_u8; 8];
+        prefix.copy_from_slice(&digest.as_bytes()[..8]);
+        let index = u64::from_le_bytes(prefix) % self.shards.len() as u64;
+        &self.shards[index as usize]
+    }
+
+    /// Enqueue `send` from tenant `from`; duplicates inside T_replay return the original id.
+    ///
+    /// In durable mode this resolves once the message is in the journal. `Storage` means the write
+    /// failed: the message may still be delivered from memory but will not survive a restart.
+    pub async fn send(&self, from: &str, send: Send) -> Result<SendOutcome, MailboxError> {
+        check_topic(&send.to)?;
+        let envelope = Envelope::from_send(from, send);
+        let msg_id = envelope.msg_id.clone();
+        let shard = self.shard_for(&envelope.topic);
+        let (outcome, commit) = shard.put(envelope, &self.cfg, Instant::now())?;
+        if let Some(commit) = commit {
+            if !commit.durable().await {
+                return Err(MailboxError::Storage);
+            }
+        }
+        let outcome = match outcome {
+            PutOutcome::Enqueued => {
+                self.metrics.enqueued_total.inc();
+                SendOutcome {
+                    msg_id,
+                    duplicate: false,
+                }
+            }
+            PutOutcome::Duplicate(original) => {
+                self.metrics.duplicates_total.inc();
+                SendOutcome {
+                    msg_id: original,
+                    duplicate: true,
+                }
+            }
+        };
+        self.observe(shard);
+        Ok(outcome)
+    }
+
+    /// Lease a batch from `topic` (empty when nothing is visible).
+    ///
+    /// Fails with `Saturated` when the global inflight ceiling has no free slot.
+    pub fn recv(&self, topic: &str, opts: RecvOptions) -> Result<Vec<Envelope>, MailboxError> {
+        check_topic(topic)?;
+        let limits = opts.resolve(&self.cfg)?;
+        let reserved = self.reserve_inflight(limits.max_messages)?;
+        let shard = self.shard_for(topic);
+        let batch = shard.lease(
+            topic,
+            Instant::now() + limits.visibility,
+            reserved,
+            limits.max_bytes,
+        );
+        self.inflight
+            .fetch_sub(reserved - batch.len(), Ordering::AcqRel);
+        if !batch.is_empty() {
+            self.observe(shard);
+        }
+        Ok(batch)
+    }
+
+    /// Topic of a consumer-held lease, for ownership checks before ACK/NACK.
+    pub fn held_topic(&self, msg_id: &str) -> Option<String> {
+        self.shards.iter().find_map(|s| s.held_topic(msg_id))
+    }
+
+    /// Final ACK of a leased message.
+    pub fn ack(&self, msg_id: &str) -> Result<(), MailboxError> {
+        let shard = self
+            .shards
+            .iter()
+            .find(|s| s.ack(msg_id))
+            .ok_or(MailboxError::NotFound)?;
+        self.inflight.fetch_sub(1, Ordering::AcqRel);
+        self.metrics.delivered_total.inc();
+        self.observe(shard);
+        Ok(())
+    }
+
+    /// NACK a leased message: redeliver after backoff, or dead-letter on the final attempt.
+    pub fn nack(&self, msg_id: &str, reason: Option<String>) -> Result<(), MailboxError> {
+        let now = Instant::now();
+        let (shard, outcome) = self
+            .shards
+            .iter()
+            .find_map(|s| {
+                s.nack(msg_id, reason.clone(), &self.cfg, now)
+                    .map(|o| (s, o))
+            })
+            .ok_or(MailboxError::NotFound)?;
+        self.inflight.fetch_sub(1, Ordering::AcqRel);
+        match outcome {
+            NackOutcome::Delayed => self.metrics.requeue_backoff_total.inc(),
+            NackOutcome::DeadLettered { evicted } => {
+                self.metrics
+                    .dlq_total
+                    .with_label_values(&["rejected"])
+                    .inc();
+                if let Some(letter) = evicted {
+                    self.log_eviction(&letter.envelope);
+                }
+            }
+        }
+        self.observe(shard);
+        Ok(())
+    }
+
+    /// Requeue every expired lease and finished backoff; returns messages made visible again.
+    pub fn scan(&self) -> usize {
+        let now = Instant::now();
+        self.metrics.scanner_wake_total.inc();
+        let mut requeued = 0;
+        for shard in &self.shards {
+            let outcome = shard.scan(now, self.cfg.max_attempts);
+            if outcome.released == 0 && outcome.requeued == 0 {
+                continue;
+            }
+            self.inflight.fetch_sub(outcome.released, Ordering::AcqRel);
+            self.metrics
+                .visibility_timeout_total
+                .inc_by(outcome.released as u64);
+            self.metrics
+                .redelivered_total
+                .inc_by(outcome.requeued as u64);
+            if outcome.dead_lettered > 0 {
+                self.metrics
+                    .dlq_total
+                    .with_label_values(&["max_attempts"])
+                    .inc_by(outcome.dead_lettered as u64);
+            }
+            for letter in &outcome.evicted {
+                self.log_eviction(&letter.envelope);
+            }
+            requeued += outcome.requeued;
+            self.observe(shard);
+        }
+        requeued
+    }
+
+    /// Operator action: move up to `limit` dead letters of `topic` back to ready.
+    pub fn reprocess(&self, topic: &str, limit: usize) -> Result<usize, MailboxError> {
+        check_topic(topic)?;
+        let shard = self.shard_for(topic);
+        let moved = shard.reprocess(topic, limit, &self.cfg);
+        self.metrics.dlq_reprocess_total.inc_by(moved as u64);
+        self.observe(shard);
+        Ok(moved)
+    }
+
+    /// Dead letters waiting for `topic`.
+    pub fn dlq_len(&self, topic: &str) -> usize {
+        self.shard_for(topic).dlq_len(topic)
+    }
+
+    /// False once any shard's journal has failed a write.
+    pub fn storage_ok(&self) -> bool {
+        self.shards.iter().all(Shard::storage_ok)
+    }
+
+    /// Consumer-held leases across all shards.
+    pub fn inflight(&self) -> usize {
+        self.inflight.load(Ordering::Acquire)
+    }
+
+    fn reserve_inflight(&self, want: usize) -> Result<usize, MailboxError> {
+        let ceiling = self.cfg.global_inflight as usize;
+        let mut taken = 0;
+        self.inflight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |cur| {
+                taken = want.min(ceiling.saturating_sub(cur));
+                (taken > 0).then_some(cur + taken)
+            })
+            .map_err(|_| MailboxError::Saturated("global inflight ceiling reached"))?;
+        Ok(taken)
+    }
+
+    fn log_eviction(&self, envelope: &Envelope) {
+        self.metrics.dlq_total.with_label_values(&["evicted"]).inc();
+        tracing::warn!(
+            topic = %envelope.topic,
+            msg_id = %envelope.msg_id,
+            attempt = envelope.attempt,
+            "mailbox: DLQ full, evicted oldest dead letter"
+        );
+    }
+
+    fn observe(&self, shard: &Shard) {
+        let stats = shard.stats();
+        let label = shard.index().to_string();
+        let depth = |queue: &str, n: usize| {
+            self.metrics
+                .queue_depth
+                .with_label_values(&[queue, &label])
+                .set(n as i64);
+        };
+        depth("work", stats.ready);
+        depth("requeue", stats.backoff);
+        depth("dlq", stats.dlq);
+        self.metrics
+            .inflight
+            .with_label_values(&[&label])
+            .set(stats.inflight as i64);
+        self.metrics
+            .journal_bytes
+            .with_label_values(&[&label])
+            .set(stats.journal_bytes as i64);
+        let held = stats.ready + stats.inflight + stats.backoff;
+        self.metrics
+            .saturation
+            .with_label_values(&["work", &label])
+            .set(held as f64 / f64::from(self.cfg.shard_capacity));
+    }
+}
+
+/// Tenant owning `topic`: its leading `:`-separated segment (`acme:orders` → `acme`).
+///
+/// Anyone may SEND to a topic; only its owner may RECV from it or ACK/NACK its leases.
+pub fn topic_owner(topic: &str) -> &str {
+    topic.split(':').next().unwrap_or(topic)
+}
+
+fn check_topic(topic: &str) -> Result<(), MailboxError> {
+    if topic.is_empty() || topic.len() > MAX_TOPIC_LEN {
+        return Err(MailboxError::Schema(format!(
+            "topic must be 1..={MAX_TOPIC_LEN} bytes"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+//! RO:WHAT — Mailbox domain: envelopes, shards, visibility leases, idempotency, DLQ.
+//! RO:WHY  — Pure queue semantics with no HTTP or runtime concerns; the service layers wrap `Mailbox`.
+//! RO:INTERACTS — http::routes, runtime::{scanner, worker}.
+
+pub mod dlq;
+pub mod envelope;
+pub mod idempotency;
+pub mod mailbox;
+pub mod shard;
+pub mod visibility;
+
+pub use dlq::{DeadLetter, DlqReason};
+pub use envelope::Envelope;
+pub use mailbox::{topic_owner, Mailbox, RecvLimits, RecvOptions, SendOutcome};
+pub use shard::{Shard, ShardStats};
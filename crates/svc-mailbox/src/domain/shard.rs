@@ -1,2 +1,521 @@
-// Shard queue abstraction placeholder: put/recv/ack/nack, capacity checks, metrics.
+//! RO:WHAT — One mailbox shard: per-topic ready FIFOs, visibility leases, replay table, and DLQ under one lock.
+//! RO:WHY  — IDB [P-1]: topics hash onto small independent shards so one hot topic cannot block the rest.
+//! RO:INTERACTS — domain::{envelope, visibility, idempotency, dlq}; domain::Mailbox (global inflight + metrics);
+//!                runtime::{scanner, worker} (via `scan` / `notified`); storage::Journal (durable mode).
+//! RO:INVARIANTS — ready + leased ≤ shard_capacity; the lock is never held across `.await`;
+//!                 every message is in exactly one of ready / leases / DLQ until ACKed;
+//!                 redeliveries go to the head of their topic (best-effort FIFO per shard);
+//!                 journal records are queued under the same lock as the transition they describe (which
+//!                 fixes their order) and written by `storage::writer` off the lock; a SEND is only
+//!                 acknowledged once its `Put` is written.
 
+use std::collections::{HashMap, VecDeque};
+
+use parking_lot::Mutex;
+use tokio::{
+    sync::{futures::Notified, Notify},
+    time::Instant,
+};
+
+use super::{
+    dlq::{DeadLetter, Dlq, DlqReason},
+    envelope::Envelope,
+    idempotency::IdemTable,
+    visibility::Leases,
+};
+use crate::{
+    config::MailboxConfig,
+    error::MailboxError,
+    storage::{Commit, JournalRecord, JournalWriter, Restored},
+    util::{backoff, time},
+};
+
+/// Result of a SEND on a shard.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PutOutcome {
+    /// Stored as a new message.
+    Enqueued,
+    /// Same (topic, idem_key, payload_hash) seen within T_replay; carries the original msg_id.
+    Duplicate(String),
+}
+
+/// Result of a NACK on a shard.
+#[derive(Debug)]
+pub enum NackOutcome {
+    /// Hidden for a backoff delay, then redelivered.
+    Delayed,
+    /// Final attempt; quarantined. Carries a letter evicted to make room, if any.
+    DeadLettered { evicted: Option<Box<DeadLetter>> },
+}
+
+/// What a scanner pass did.
+#[derive(Debug, Default)]
+pub struct ScanOutcome {
+    /// Consumer-held leases that ended (frees global inflight slots).
+    pub released: usize,
+    /// Messages made visible again.
+    pub requeued: usize,
+    /// Messages moved to the DLQ.
+    pub dead_lettered: usize,
+    /// Letters evicted from a full DLQ.
+    pub evicted: Vec<DeadLetter>,
+}
+
+/// Point-in-time shard gauges.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShardStats {
+    /// Visible messages.
+    pub ready: usize,
+    /// Consumer-held leases.
+    pub inflight: usize,
+    /// Messages waiting out a NACK backoff.
+    pub backoff: usize,
+    /// Dead letters.
+    pub dlq: usize,
+    /// Journal size on disk (0 in memory-only mode).
+    pub journal_bytes: u64,
+}
+
+#[derive(Debug)]
+struct State {
+    ready: HashMap<String, VecDeque<Envelope>>,
+    ready_len: usize,
+    leases: Leases,
+    dedup: IdemTable,
+    dlq: Dlq,
+    journal: Option<JournalWriter>,
+}
+
+impl State {
+    fn held_total(&self) -> usize {
+        self.ready_len + self.leases.len()
+    }
+
+    fn push_ready(&mut self, envelope: Envelope, front: bool) {
+        let queue = self.ready.entry(envelope.topic.clone()).or_default();
+        if front {
+            queue.push_front(envelope);
+        } else {
+            queue.push_back(envelope);
+        }
+        self.ready_len += 1;
+    }
+
+    /// Queue `records` for the journal thread; `wait` returns a `Commit` for the caller to await
+    /// once the lock is released. A failed journal sheds SEND until restart; other transitions
+    /// continue in memory.
+    fn log(
+        &mut self,
+        records: Vec<JournalRecord>,
+        wait: bool,
+    ) -> Result<Option<Commit>, MailboxError> {
+        let Some(journal) = self.journal.as_ref() else {
+            return Ok(None);
+        };
+        if journal.failed() {
+            return Err(MailboxError::Storage);
+        }
+        Ok(journal.submit(records, wait))
+    }
+
+    /// Move `letter` to the DLQ, describing the move (and any eviction) in `records`.
+    fn quarantine(
+        &mut self,
+        letter: DeadLetter,
+        records: &mut Vec<JournalRecord>,
+    ) -> Option<DeadLetter> {
+        records.push(JournalRecord::DeadLetter {
+            msg_id: letter.envelope.msg_id.clone(),
+            reason: letter.reason,
+            last_error: letter.last_error.clone(),
+        });
+        let evicted = self.dlq.push(letter);
+        if let Some(e) = &evicted {
+            records.push(JournalRecord::Evict {
+                msg_id: e.envelope.msg_id.clone(),
+            });
+        }
+        evicted
+    }
+
+    /// Log a transition that has already happened in memory, then compact if due.
+    fn record(&mut self, records: Vec<JournalRecord>) {
+        // Failure is already logged and surfaced through SEND and readiness.
+        if self.log(records, false).is_ok() {
+            self.maybe_compact();
+        }
+    }
+
+    /// Hand the journal thread a snapshot once enough of the journal is dead.
+    ///
+    /// Must run after the in-memory change a record describes, or the snapshot would miss it.
+    fn maybe_compact(&mut self) {
+        let live = self.ready_len + 2 * (self.leases.len() + self.dlq.len()) + self.dedup.len();
+        let due = self
+            .journal
+            .as_ref()
+            .is_some_and(|j| !j.failed() && j.should_compact(live));
+        if !due {
+            return;
+        }
+        let records = self.snapshot_records();
+        if let Some(journal) = self.journal.as_ref() {
+            journal.compact(records);
+        }
+    }
+
+    /// Records that rebuild the current state from nothing.
+    fn snapshot_records(&self) -> Vec<JournalRecord> {
+        let mut out = Vec::new();
+        for (key, msg_id, at) in self.dedup.iter() {
+            out.push(JournalRecord::Seen {
+                topic: key.0.clone(),
+                idem_key: key.1.clone(),
+                payload_hash: hex::encode(key.2),
+                msg_id: msg_id.to_string(),
+                at_ms: time::to_unix_ms(at),
+            });
+        }
+        let put = |envelope: &Envelope| JournalRecord::Put {
+            envelope: envelope.into(),
+            seen_ms: None,
+        };
+        for envelope in self.ready.values().flatten() {
+            out.push(put(envelope));
+        }
+        for lease in self.leases.iter() {
+            out.push(put(&lease.envelope));
+            out.push(JournalRecord::Lease {
+                msg_id: lease.envelope.msg_id.clone(),
+                attempt: lease.envelope.attempt,
+                deadline_ms: time::to_unix_ms(lease.deadline),
+                held: lease.held,
+            });
+        }
+        for letter in self.dlq.iter() {
+            out.push(put(&letter.envelope));
+            out.push(JournalRecord::DeadLetter {
+                msg_id: letter.envelope.msg_id.clone(),
+                reason: letter.reason,
+                last_error: letter.last_error.clone(),
+            });
+        }
+        out
+    }
+}
+
+/// One shard of the mailbox.
+#[derive(Debug)]
+pub struct Shard {
+    index: u16,
+    state: Mutex<State>,
+    notify: Notify,
+}
+
+impl Shard {
+    /// Empty shard sized from `cfg`.
+    pub fn new(index: u16, cfg: &MailboxConfig) -> Self {
+        Self {
+            index,
+            state: Mutex::new(State {
+                ready: HashMap::new(),
+                ready_len: 0,
+                leases: Leases::default(),
+                dedup: IdemTable::new(cfg.dedup_capacity as usize, cfg.t_replay),
+                dlq: Dlq::new(cfg.dlq_capacity as usize),
+                journal: None,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Shard rebuilt from `restored`, journaling further transitions to `journal`.
+    pub fn restore(
+        index: u16,
+        cfg: &MailboxConfig,
+        journal: JournalWriter,
+        restored: Restored,
+    ) -> Self {
+        let shard = Self::new(index, cfg);
+        {
+            let mut st = shard.state.lock();
+            for envelope in restored.ready {
+                st.push_ready(envelope, false);
+            }
+            for (envelope, deadline_ms, held) in restored.leases {
+                st.leases
+                    .insert(envelope, time::from_unix_ms(deadline_ms), held);
+            }
+            let mut evicted = Vec::new();
+            for letter in restored.dead {
+                // Only non-empty if dlq_capacity shrank since the journal was written.
+                evicted.extend(st.dlq.push(letter));
+            }
+            let now = Instant::now();
+            let now_ms = time::unix_ms();
+            for (key, msg_id, at_ms) in restored.seen {
+                let age = std::time::Duration::from_millis(now_ms.saturating_sub(at_ms));
+                if age < cfg.t_replay {
+                    st.dedup
+                        .insert(key, msg_id, now.checked_sub(age).unwrap_or(now));
+                }
+            }
+            st.journal = Some(journal);
+            let evictions: Vec<_> = evicted
+                .iter()
+                .map(|letter| JournalRecord::Evict {
+                    msg_id: letter.envelope.msg_id.clone(),
+                })
+                .collect();
+            st.record(evictions);
+        }
+        shard
+    }
+
+    /// Shard index (metric label).
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Future resolved the next time messages become visible on this shard.
+    ///
+    /// Call `enable()` on it before checking the queue to avoid missing a wakeup.
+    pub fn notified(&self) -> Notified<'_> {
+        self.notify.notified()
+    }
+
+    /// Enqueue unless the idempotency triple was already seen within T_replay.
+    ///
+    /// In durable mode a new message comes with the `Commit` of its `Put`; the SEND is only
+    /// acknowledged once that resolves.
+    pub fn put(
+        &self,
+        envelope: Envelope,
+        cfg: &MailboxConfig,
+        now: Instant,
+    ) -> Result<(PutOutcome, Option<Commit>), MailboxError> {
+        let mut st = self.state.lock();
+        let key = (
+            envelope.topic.clone(),
+            envelope.idem_key.clone(),
+            envelope.payload_hash,
+        );
+        if let Some(original) = st.dedup.lookup(&key, now) {
+            return Ok((PutOutcome::Duplicate(original), None));
+        }
+        if st.held_total() >= cfg.shard_capacity as usize {
+            return Err(MailboxError::Saturated("shard full"));
+        }
+        if !st.dedup.has_room(now) {
+            return Err(MailboxError::Saturated("idempotency table full"));
+        }
+        if st
+            .journal
+            .as_ref()
+            .is_some_and(|j| j.backlog() >= cfg.shard_capacity as usize)
+        {
+            return Err(MailboxError::Saturated("journal backlog"));
+        }
+        let commit = st.log(
+            vec![JournalRecord::Put {
+                envelope: (&envelope).into(),
+                seen_ms: Some(time::unix_ms()),
+            }],
+            true,
+        )?;
+        st.dedup.insert(key, envelope.msg_id.clone(), now);
+        st.push_ready(envelope, false);
+        st.maybe_compact();
+        drop(st);
+        self.notify.notify_waiters();
+        Ok((PutOutcome::Enqueued, commit))
+    }
+
+    /// Lease up to `max_messages` from `topic` until `deadline`, staying under `max_bytes`
+    /// (the first message is always returned so large payloads cannot wedge a topic).
+    pub fn lease(
+        &self,
+        topic: &str,
+        deadline: Instant,
+        max_messages: usize,
+        max_bytes: usize,
+    ) -> Vec<Envelope> {
+        let mut st = self.state.lock();
+        let mut out = Vec::new();
+        let mut bytes = 0;
+        let Some(queue) = st.ready.get_mut(topic) else {
+            return out;
+        };
+        while out.len() < max_messages {
+            let Some(next) = queue.front() else { break };
+            if !out.is_empty() && bytes + next.payload.len() > max_bytes {
+                break;
+            }
+            let mut envelope = queue.pop_front().expect("front checked");
+            envelope.attempt += 1;
+            bytes += envelope.payload.len();
+            out.push(envelope);
+        }
+        if queue.is_empty() {
+            st.ready.remove(topic);
+        }
+        st.ready_len -= out.len();
+        for envelope in &out {
+            st.leases.insert(envelope.clone(), deadline, true);
+        }
+        if st.journal.is_some() {
+            let deadline_ms = time::to_unix_ms(deadline);
+            let records: Vec<_> = out
+                .iter()
+                .map(|envelope| JournalRecord::Lease {
+                    msg_id: envelope.msg_id.clone(),
+                    attempt: envelope.attempt,
+                    deadline_ms,
+                    held: true,
+                })
+                .collect();
+            st.record(records);
+        }
+        out
+    }
+
+    /// Topic of a consumer-held lease in this shard.
+    pub fn held_topic(&self, msg_id: &str) -> Option<String> {
+        self.state
+            .lock()
+            .leases
+            .held_topic(msg_id)
+            .map(str::to_owned)
+    }
+
+    /// Final acknowledgement of a held lease.
+    pub fn ack(&self, msg_id: &str) -> bool {
+        let mut st = self.state.lock();
+        if st.leases.take_held(msg_id).is_none() {
+            return false;
+        }
+        st.record(vec![JournalRecord::Ack {
+            msg_id: msg_id.to_string(),
+        }]);
+        true
+    }
+
+    /// Negative acknowledgement: back off and redeliver, or dead-letter on the final attempt.
+    pub fn nack(
+        &self,
+        msg_id: &str,
+        reason: Option<String>,
+        cfg: &MailboxConfig,
+        now: Instant,
+    ) -> Option<NackOutcome> {
+        let mut st = self.state.lock();
+        let envelope = st.leases.take_held(msg_id)?;
+        if envelope.attempt >= cfg.max_attempts {
+            let letter = DeadLetter {
+                envelope,
+                reason: DlqReason::Rejected,
+                last_error: reason,
+            };
+            let mut records = Vec::new();
+            let evicted = st.quarantine(letter, &mut records).map(Box::new);
+            st.record(records);
+            return Some(NackOutcome::DeadLettered { evicted });
+        }
+        let delay = backoff::full_jitter(cfg.backoff_base, cfg.backoff_max, envelope.attempt);
+        let record = JournalRecord::Lease {
+            msg_id: envelope.msg_id.clone(),
+            attempt: envelope.attempt,
+            deadline_ms: time::to_unix_ms(now + delay),
+            held: false,
+        };
+        st.leases.insert(envelope, now + delay, false);
+        st.record(vec![record]);
+        Some(NackOutcome::Delayed)
+    }
+
+    /// Return every lease due at `now` to its topic, dead-lettering expired final attempts.
+    pub fn scan(&self, now: Instant, max_attempts: u32) -> ScanOutcome {
+        let mut st = self.state.lock();
+        let mut outcome = ScanOutcome::default();
+        let mut visible = Vec::new();
+        let mut records = Vec::new();
+        while let Some(lease) = st.leases.pop_expired(now) {
+            outcome.released += usize::from(lease.held);
+            if lease.held && lease.envelope.attempt >= max_attempts {
+                outcome.dead_lettered += 1;
+                let letter = DeadLetter {
+                    envelope: lease.envelope,
+                    reason: DlqReason::MaxAttempts,
+                    last_error: None,
+                };
+                outcome.evicted.extend(st.quarantine(letter, &mut records));
+            } else {
+                visible.push(lease.envelope);
+            }
+        }
+        outcome.requeued = visible.len();
+        // Earliest deadline ends up at the head of its topic.
+        for envelope in visible.into_iter().rev() {
+            records.push(JournalRecord::Requeue {
+                msg_id: envelope.msg_id.clone(),
+            });
+            st.push_ready(envelope, true);
+        }
+        st.record(records);
+        drop(st);
+        if outcome.requeued > 0 {
+            self.notify.notify_waiters();
+        }
+        outcome
+    }
+
+    /// Move up to `limit` dead letters for `topic` back to ready (attempt reset), as room allows.
+    pub fn reprocess(&self, topic: &str, limit: usize, cfg: &MailboxConfig) -> usize {
+        let mut st = self.state.lock();
+        let room = (cfg.shard_capacity as usize).saturating_sub(st.held_total());
+        let letters = st.dlq.drain(topic, limit.min(room));
+        let moved = letters.len();
+        let mut records = Vec::with_capacity(moved);
+        for letter in letters {
+            let mut envelope = letter.envelope;
+            envelope.attempt = 0;
+            records.push(JournalRecord::Reprocess {
+                msg_id: envelope.msg_id.clone(),
+            });
+            st.push_ready(envelope, false);
+        }
+        st.record(records);
+        drop(st);
+        if moved > 0 {
+            self.notify.notify_waiters();
+        }
+        moved
+    }
+
+    /// Dead letters waiting for `topic`.
+    pub fn dlq_len(&self, topic: &str) -> usize {
+        self.state.lock().dlq.topic_len(topic)
+    }
+
+    /// Current gauges.
+    pub fn stats(&self) -> ShardStats {
+        let st = self.state.lock();
+        ShardStats {
+            ready: st.ready_len,
+            inflight: st.leases.held(),
+            backoff: st.leases.len() - st.leases.held(),
+            dlq: st.dlq.len(),
+            journal_bytes: st.journal.as_ref().map_or(0, JournalWriter::bytes),
+        }
+    }
+
+    /// False once a journal write has failed (always true in memory-only mode).
+    pub fn storage_ok(&self) -> bool {
+        !self
+            .state
+            .lock()
+            .journal
+            .as_ref()
+            .is_some_and(JournalWriter::failed)
+    }
+}
@@ -1,2 +1,108 @@
-// Visibility lease management placeholder.
+//! RO:WHAT — Visibility leases: messages hidden from RECV until ACKed or their deadline passes.
+//! RO:WHY  — IDB [I-3]/[C-3]: RECV moves a message to inflight(deadline); the scanner returns overdue ones.
+//! RO:INTERACTS — domain::shard (one table per shard), runtime::scanner (drives `pop_expired`).
+//! RO:INVARIANTS — deadlines use the monotonic clock; a message is in at most one lease;
+//!                 NACK backoff reuses the table with `held = false` so it cannot be ACKed while waiting.
 
+use std::collections::{BTreeMap, HashMap};
+
+use tokio::time::Instant;
+
+use super::envelope::Envelope;
+
+/// A hidden message.
+#[derive(Debug)]
+pub struct Lease {
+    /// The message.
+    pub envelope: Envelope,
+    /// When it becomes visible again.
+    pub deadline: Instant,
+    /// Held by a consumer (RECV) rather than waiting out a NACK backoff.
+    pub held: bool,
+    seq: u64,
+}
+
+/// Leases for one shard, indexed by msg_id and by (deadline, lease order).
+#[derive(Debug, Default)]
+pub struct Leases {
+    by_id: HashMap<String, Lease>,
+    by_deadline: BTreeMap<(Instant, u64), String>,
+    next_seq: u64,
+    held: usize,
+}
+
+impl Leases {
+    /// Hide `envelope` until `deadline`. Equal deadlines expire in insertion order.
+    pub fn insert(&mut self, envelope: Envelope, deadline: Instant, held: bool) {
+        let id = envelope.msg_id.clone();
+        // Re-leasing the same id replaces the old deadline.
+        if let Some(old) = self.by_id.remove(&id) {
+            self.by_deadline.remove(&(old.deadline, old.seq));
+            self.held -= usize::from(old.held);
+        }
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.by_deadline.insert((deadline, seq), id.clone());
+        self.held += usize::from(held);
+        self.by_id.insert(
+            id,
+            Lease {
+                envelope,
+                deadline,
+                held,
+                seq,
+            },
+        );
+    }
+
+    /// Remove a consumer-held lease (ACK/NACK). Backoff entries are not returned.
+    pub fn take_held(&mut self, msg_id: &str) -> Option<Envelope> {
+        if !self.by_id.get(msg_id)?.held {
+            return None;
+        }
+        let lease = self.by_id.remove(msg_id)?;
+        self.by_deadline.remove(&(lease.deadline, lease.seq));
+        self.held -= 1;
+        Some(lease.envelope)
+    }
+
+    /// Topic of a consumer-held lease, if `msg_id` is one.
+    pub fn held_topic(&self, msg_id: &str) -> Option<&str> {
+        self.by_id
+            .get(msg_id)
+            .filter(|lease| lease.held)
+            .map(|lease| lease.envelope.topic.as_str())
+    }
+
+    /// Remove and return the earliest lease whose deadline is at or before `now`.
+    pub fn pop_expired(&mut self, now: Instant) -> Option<Lease> {
+        let (&(deadline, _), _) = self.by_deadline.first_key_value()?;
+        if deadline > now {
+            return None;
+        }
+        let (_, id) = self.by_deadline.pop_first()?;
+        let lease = self.by_id.remove(&id)?;
+        self.held -= usize::from(lease.held);
+        Some(lease)
+    }
+
+    /// Hidden messages, earliest deadline first.
+    pub fn iter(&self) -> impl Iterator<Item = &Lease> {
+        self.by_deadline.values().map(|id| &self.by_id[id])
+    }
+
+    /// All hidden messages (held + backoff).
+    pub fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    /// True when nothing is hidden.
+    pub fn is_empty(&self) -> bool {
+        self.by_id.is_empty()
+    }
+
+    /// Consumer-held leases only.
+    pub fn held(&self) -> usize {
+        self.held
+    }
+}
@@ -1,2 +1,105 @@
-// Error taxonomy placeholder: AuthError, Busy, State, Integrity...
+//! RO:WHAT — Mailbox error taxonomy and its HTTP mapping (`{code, message}` + status).
+//! RO:WHY  — Clients branch on stable `E_*` codes (docs/API.MD §8.1), not on message text.
+//! RO:INTERACTS — domain::Mailbox (producer), http::routes (converted via `IntoResponse`).
+//! RO:INVARIANTS — codes are additive-only; 429/503 always carry `Retry-After`.
 
+use axum::{
+    http::{header::RETRY_AFTER, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+/// Errors surfaced by mailbox operations.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum MailboxError {
+    /// Invalid JSON, unknown fields, or out-of-range parameters.
+    #[error("invalid request: {0}")]
+    Schema(String),
+    /// Payload larger than the OAP/1 frame cap.
+    #[error("payload exceeds {limit} bytes")]
+    FrameTooLarge { limit: usize },
+    /// A shard, the dedup table, or the global inflight ceiling is full.
+    #[error("saturated: {0}")]
+    Saturated(&'static str),
+    /// Missing or invalid operator credentials.
+    #[error("operator credentials required")]
+    Unauthorized,
+    /// The caller's tenant or credentials do not cover this topic or operation.
+    #[error("forbidden: {0}")]
+    Forbidden(&'static str),
+    /// `msg_id` is not currently leased (unknown, expired, or already acknowledged).
+    #[error("unknown or expired msg_id")]
+    NotFound,
+    /// Service is draining or not ready.
+    #[error("service unavailable")]
+    Unavailable,
+    /// The durable journal could not be written; nothing new is accepted until restart.
+    #[error("journal write failed")]
+    Storage,
+}
+
+impl MailboxError {
+    /// Stable wire code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Schema(_) => "E_SCHEMA",
+            Self::FrameTooLarge { .. } => "E_FRAME_TOO_LARGE",
+            Self::Saturated(_) => "E_SATURATED",
+            Self::Unauthorized => "E_CAP_AUTH",
+            Self::Forbidden(_) => "E_CAP_SCOPE",
+            Self::NotFound => "E_NOT_FOUND",
+            Self::Unavailable => "E_UNAVAILABLE",
+            Self::Storage => "E_STORAGE",
+        }
+    }
+
+    /// `rejected_total{reason}` label.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::Schema(_) => "schema",
+            Self::FrameTooLarge { .. } => "oversize",
+            Self::Saturated(_) => "saturated",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden(_) => "forbidden",
+            Self::NotFound => "not_found",
+            Self::Unavailable => "unavailable",
+            Self::Storage => "storage",
+        }
+    }
+
+    /// HTTP status for this error.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Schema(_) => StatusCode::BAD_REQUEST,
+            Self::FrameTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Saturated(_) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Unavailable | Self::Storage => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody<'a> {
+    code: &'a str,
+    message: String,
+}
+
+impl IntoResponse for MailboxError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let body = ErrorBody {
+            code: self.code(),
+            message: self.to_string(),
+        };
+        let mut resp = (status, Json(body)).into_response();
+        if matches!(self, Self::Saturated(_) | Self::Unavailable | Self::Storage) {
+            resp.headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from_static("1"));
+        }
+        resp
+    }
+}
@@ -1,2 +1,67 @@
-// DTOs placeholder with deny_unknown_fields and payload_hash (b3:<hex>).
+//! RO:WHAT — HTTP DTOs around the `ron_proto::mailbox` Send/Recv/Ack shapes.
+//! RO:WHY  — Message bodies stay the shared ron-proto types; only batch/lease wrappers are mailbox-specific.
+//! RO:INTERACTS — http::routes; ron_proto::mailbox::{Send, Recv, Ack}.
+//! RO:INVARIANTS — requests deny unknown fields; `visibility_ms` is milliseconds.
 
+use serde::{Deserialize, Serialize};
+
+use ron_proto::mailbox::Recv;
+
+/// `POST /v1/send` response.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SendResponse {
+    pub msg_id: String,
+    pub duplicate: bool,
+}
+
+/// `POST /v1/recv` and `/v1/recv/stream` request.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RecvRequest {
+    pub topic: String,
+    #[serde(default)]
+    pub visibility_ms: Option<u64>,
+    #[serde(default)]
+    pub max_messages: Option<u16>,
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
+}
+
+/// `POST /v1/recv` response.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RecvResponse {
+    pub messages: Vec<Recv>,
+}
+
+/// Optional `POST /v1/nack/{msg_id}` body.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct NackRequest {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// `POST /v1/dlq/reprocess` request.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ReprocessRequest {
+    pub topic: String,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// `POST /v1/dlq/reprocess` response.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ReprocessResponse {
+    pub moved: usize,
+}
+
+/// `{ "ok": true }` acknowledgement.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct OkResponse {
+    pub ok: bool,
+}
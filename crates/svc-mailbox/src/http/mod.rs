@@ -1,2 +1,71 @@
-// HTTP router composition and middleware placeholder (limits, timeouts).
+//! RO:WHAT — HTTP router composition: `/v1` mailbox API plus `/healthz`, `/readyz`, `/metrics`.
+//! RO:WHY  — Single place for body limits and shared state so tests can drive the exact production router.
+//! RO:INTERACTS — http::routes, observability::health, domain::Mailbox, runtime::shutdown.
+//! RO:INVARIANTS — JSON body cap leaves room for byte-array payload encoding of a full OAP/1 frame;
+//!                 `AppState` is cheap to clone (Arc inside).
 
+pub mod dto;
+pub mod routes;
+
+use std::sync::Arc;
+
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+    Router,
+};
+
+use crate::{domain::Mailbox, error::MailboxError, observability::health, runtime::Shutdown};
+
+/// Shared handler state.
+#[derive(Clone, Debug)]
+pub struct AppState {
+    /// The mailbox core.
+    pub mailbox: Arc<Mailbox>,
+    /// Drain signal (sheds writes, ends streams).
+    pub shutdown: Shutdown,
+    /// Max decoded payload bytes per SEND.
+    pub max_body_bytes: usize,
+    /// Bearer token for operator endpoints; `None` disables them.
+    pub operator_token: Option<Arc<str>>,
+    /// Gateway secret that vouches for `X-RON-Tenant`; `None` refuses tenant-scoped calls.
+    pub gateway_token: Option<Arc<str>>,
+}
+
+impl AppState {
+    /// Count a rejection and hand the error back for `?`.
+    pub fn reject(&self, err: MailboxError) -> MailboxError {
+        self.mailbox
+            .metrics()
+            .rejected_total
+            .with_label_values(&[err.reason()])
+            .inc();
+        err
+    }
+}
+
+/// JSON bytes needed to carry `payload` bytes as a number array (`"255,"` per byte) plus envelope fields.
+fn json_body_limit(payload: usize) -> usize {
+    payload * 4 + 64 * 1024
+}
+
+/// Build the service router.
+pub fn router(state: AppState) -> Router {
+    let limit = json_body_limit(state.max_body_bytes);
+    let api = Router::new()
+        .route("/v1/send", post(routes::send))
+        .route("/v1/recv", post(routes::recv))
+        .route("/v1/recv/stream", post(routes::recv_stream))
+        .route("/v1/ack", post(routes::ack))
+        .route("/v1/ack/:msg_id", post(routes::ack_id))
+        .route("/v1/nack/:msg_id", post(routes::nack_id))
+        .route("/v1/dlq/reprocess", post(routes::dlq_reprocess))
+        .layer(DefaultBodyLimit::max(limit));
+
+    Router::new()
+        .route("/healthz", get(health::healthz))
+        .route("/readyz", get(health::readyz))
+        .route("/metrics", get(health::metrics))
+        .merge(api)
+        .with_state(state)
+}
@@ -1,2 +1,275 @@
-// Handlers placeholder: /v1/send, /v1/recv, /v1/recv/stream, /ack, /nack, /dlq/reprocess.
+//! RO:WHAT — `/v1` handlers: send, recv, recv/stream (NDJSON), ack, nack, dlq/reprocess.
+//! RO:WHY  — Thin translation between ron-proto mailbox DTOs and `domain::Mailbox`.
+//! RO:INTERACTS — http::{dto, AppState}, domain::Mailbox, runtime::worker (streaming).
+//! RO:INVARIANTS — every call names its tenant (`X-RON-Tenant`), trusted only when the gateway vouches for the
+//!                 request with `X-RON-Gateway-Token`; RECV/ACK/NACK only touch topics the
+//!                 tenant owns (`domain::topic_owner`), and another tenant's lease reads as `E_NOT_FOUND`;
+//!                 DLQ reprocess needs the operator bearer token;
+//!                 schema failures are `E_SCHEMA` JSON errors, never axum's plain-text rejections;
+//!                 writes are shed (503) once draining while reads/ACKs keep working;
+//!                 every rejection increments `rejected_total{reason}`.
 
+use axum::{
+    body::{Body, Bytes},
+    extract::{rejection::JsonRejection, Path, State},
+    http::{
+        header::{AUTHORIZATION, CONTENT_TYPE},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use ron_proto::mailbox::{Ack, Send};
+use std::{convert::Infallible, time::Duration};
+
+use super::{
+    dto::{
+        NackRequest, OkResponse, RecvRequest, RecvResponse, ReprocessRequest, ReprocessResponse,
+        SendResponse,
+    },
+    AppState,
+};
+use crate::{
+    domain::{topic_owner, Envelope, RecvOptions},
+    error::MailboxError,
+    runtime::worker,
+};
+
+/// Sending tenant header.
+pub const TENANT_HEADER: &str = "x-ron-tenant";
+/// Gateway secret that makes `X-RON-Tenant` trustworthy (`auth.gateway_token`).
+pub const GATEWAY_TOKEN_HEADER: &str = "x-ron-gateway-token";
+/// Opt-in duplicate signalling (`200-flag` default, or `409-conflict`).
+pub const IDEMPOTENCY_MODE_HEADER: &str = "x-idempotency-mode";
+
+/// Default DLQ reprocess batch.
+const DEFAULT_REPROCESS_LIMIT: usize = 100;
+
+type Handled<T> = Result<T, MailboxError>;
+
+fn body<T>(state: &AppState, json: Result<Json<T>, JsonRejection>) -> Handled<T> {
+    json.map(|Json(v)| v)
+        .map_err(|e| state.reject(MailboxError::Schema(e.body_text())))
+}
+
+/// Compare digests so a check does not leak how much of the token matched.
+fn same_token(presented: &str, expected: &str) -> bool {
+    blake3::hash(presented.as_bytes()) == blake3::hash(expected.as_bytes())
+}
+
+/// The calling tenant from `X-RON-Tenant`.
+///
+/// Clients cannot name their own tenant: the header only counts on requests carrying the
+/// gateway token, which the gateway sets after authenticating the caller.
+fn tenant(state: &AppState, headers: &HeaderMap) -> Handled<String> {
+    let Some(expected) = state.gateway_token.as_deref() else {
+        return Err(state.reject(MailboxError::Forbidden(
+            "no gateway is configured to vouch for tenants",
+        )));
+    };
+    let vouched = headers
+        .get(GATEWAY_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .is_some_and(|presented| same_token(presented, expected));
+    if !vouched {
+        return Err(state.reject(MailboxError::Unauthorized));
+    }
+    headers
+        .get(TENANT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| state.reject(MailboxError::Schema("missing X-RON-Tenant".into())))
+}
+
+/// Refuse consumer access to a topic `tenant` does not own.
+fn owns_topic(state: &AppState, tenant: &str, topic: &str) -> Handled<()> {
+    if topic_owner(topic) == tenant {
+        return Ok(());
+    }
+    Err(state.reject(MailboxError::Forbidden("topic belongs to another tenant")))
+}
+
+/// Refuse ACK/NACK of a lease outside `tenant`'s topics; indistinguishable from an unknown id.
+fn owns_lease(state: &AppState, tenant: &str, msg_id: &str) -> Handled<()> {
+    match state.mailbox.held_topic(msg_id) {
+        Some(topic) if topic_owner(&topic) == tenant => Ok(()),
+        _ => Err(state.reject(MailboxError::NotFound)),
+    }
+}
+
+/// Require `Authorization: Bearer <auth.operator_token>`.
+fn operator(state: &AppState, headers: &HeaderMap) -> Handled<()> {
+    let Some(expected) = state.operator_token.as_deref() else {
+        return Err(state.reject(MailboxError::Forbidden("operator endpoints are disabled")));
+    };
+    let presented = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or_else(|| state.reject(MailboxError::Unauthorized))?;
+    if !same_token(presented, expected) {
+        return Err(state.reject(MailboxError::Unauthorized));
+    }
+    Ok(())
+}
+
+fn recv_options(req: &RecvRequest) -> RecvOptions {
+    RecvOptions {
+        visibility: req.visibility_ms.map(Duration::from_millis),
+        max_messages: req.max_messages,
+        max_bytes: req.max_bytes,
+    }
+}
+
+/// `POST /v1/send` — enqueue a `ron_proto::mailbox::Send` addressed to topic `to`.
+pub async fn send(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    json: Result<Json<Send>, JsonRejection>,
+) -> Handled<Response> {
+    if state.shutdown.is_triggered() {
+        return Err(state.reject(MailboxError::Unavailable));
+    }
+    let req = body(&state, json)?;
+    let from = tenant(&state, &headers)?;
+    if req.payload.len() > state.max_body_bytes {
+        return Err(state.reject(MailboxError::FrameTooLarge {
+            limit: state.max_body_bytes,
+        }));
+    }
+
+    let outcome = state
+        .mailbox
+        .send(&from, req)
+        .await
+        .map_err(|e| state.reject(e))?;
+    let conflict_mode = headers
+        .get(IDEMPOTENCY_MODE_HEADER)
+        .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"409-conflict"));
+    let status = if outcome.duplicate && conflict_mode {
+        StatusCode::CONFLICT
+    } else {
+        StatusCode::OK
+    };
+    let resp = SendResponse {
+        msg_id: outcome.msg_id,
+        duplicate: outcome.duplicate,
+    };
+    Ok((status, Json(resp)).into_response())
+}
+
+/// `POST /v1/recv` — lease a batch (empty list when nothing is visible).
+pub async fn recv(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    json: Result<Json<RecvRequest>, JsonRejection>,
+) -> Handled<Json<RecvResponse>> {
+    let req = body(&state, json)?;
+    owns_topic(&state, &tenant(&state, &headers)?, &req.topic)?;
+    let batch = state
+        .mailbox
+        .recv(&req.topic, recv_options(&req))
+        .map_err(|e| state.reject(e))?;
+    Ok(Json(RecvResponse {
+        messages: batch.iter().map(Envelope::to_recv).collect(),
+    }))
+}
+
+/// `POST /v1/recv/stream` — NDJSON of `ron_proto::mailbox::Recv`, leased as messages arrive.
+pub async fn recv_stream(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    json: Result<Json<RecvRequest>, JsonRejection>,
+) -> Handled<Response> {
+    let req = body(&state, json)?;
+    owns_topic(&state, &tenant(&state, &headers)?, &req.topic)?;
+    let opts = recv_options(&req);
+    // Surface bad knobs as a 400 before the stream starts.
+    opts.resolve(state.mailbox.config())
+        .map_err(|e| state.reject(e))?;
+
+    let rx = worker::spawn_stream(
+        state.mailbox.clone(),
+        req.topic,
+        opts,
+        state.shutdown.clone(),
+    );
+    let lines = futures_util::stream::unfold(rx, |mut rx| async move {
+        let envelope = rx.recv().await?;
+        let mut line = serde_json::to_vec(&envelope.to_recv()).expect("Recv serializes");
+        line.push(b'\n');
+        Some((Ok::<_, Infallible>(Bytes::from(line)), rx))
+    });
+    Ok((
+        [(CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response())
+}
+
+/// `POST /v1/ack` — `ron_proto::mailbox::Ack`; `ok: false` is a NACK carrying `error.message`.
+pub async fn ack(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    json: Result<Json<Ack>, JsonRejection>,
+) -> Handled<Json<OkResponse>> {
+    let ack = body(&state, json)?;
+    owns_lease(&state, &tenant(&state, &headers)?, &ack.msg_id)?;
+    let done = if ack.ok {
+        state.mailbox.ack(&ack.msg_id)
+    } else {
+        state
+            .mailbox
+            .nack(&ack.msg_id, ack.error.map(|e| e.message))
+    };
+    done.map_err(|e| state.reject(e))?;
+    Ok(Json(OkResponse { ok: true }))
+}
+
+/// `POST /v1/ack/{msg_id}`.
+pub async fn ack_id(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(msg_id): Path<String>,
+) -> Handled<Json<OkResponse>> {
+    owns_lease(&state, &tenant(&state, &headers)?, &msg_id)?;
+    state.mailbox.ack(&msg_id).map_err(|e| state.reject(e))?;
+    Ok(Json(OkResponse { ok: true }))
+}
+
+/// `POST /v1/nack/{msg_id}` with optional `{ "reason": "..." }`.
+pub async fn nack_id(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(msg_id): Path<String>,
+    json: Option<Json<NackRequest>>,
+) -> Handled<Json<OkResponse>> {
+    owns_lease(&state, &tenant(&state, &headers)?, &msg_id)?;
+    let reason = json.and_then(|Json(req)| req.reason);
+    state
+        .mailbox
+        .nack(&msg_id, reason)
+        .map_err(|e| state.reject(e))?;
+    Ok(Json(OkResponse { ok: true }))
+}
+
+/// `POST /v1/dlq/reprocess` — operator move of dead letters back to ready (bearer `auth.operator_token`).
+pub async fn dlq_reprocess(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    json: Result<Json<ReprocessRequest>, JsonRejection>,
+) -> Handled<Json<ReprocessResponse>> {
+    operator(&state, &headers)?;
+    let req = body(&state, json)?;
+    let limit = req.limit.unwrap_or(DEFAULT_REPROCESS_LIMIT);
+    let moved = state
+        .mailbox
+        .reprocess(&req.topic, limit)
+        .map_err(|e| state.reject(e))?;
+    tracing::info!(topic = %req.topic, moved, "mailbox: DLQ reprocess");
+    Ok(Json(ReprocessResponse { moved }))
+}
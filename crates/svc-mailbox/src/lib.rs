@@ -1 +1,24 @@
-// Re-exports placeholder for svc-mailbox2 library surface.
+//! svc-mailbox — store-and-forward messaging between tenants.
+//!
+//! RO:WHAT — Sharded bounded queues with visibility leases, idempotent SEND, ACK/NACK with
+//!           jittered backoff, a dead-letter queue, an optional on-disk journal, and the `/v1`
+//!           HTTP surface over `ron_proto::mailbox`.
+//! RO:WHY  — Pillar 11: async messaging for apps; at-least-once with idempotency, never exactly-once.
+//! RO:INVARIANTS — see docs/IDB.md ([I-1]..[I-5], [I-13], [I-14]).
+
+#![forbid(unsafe_code)]
+
+pub mod config;
+pub mod domain;
+pub mod error;
+pub mod http;
+pub mod metrics;
+pub mod observability;
+pub mod runtime;
+pub mod storage;
+pub mod util;
+
+pub use config::Config;
+pub use domain::Mailbox;
+pub use error::MailboxError;
+pub use runtime::{run, Shutdown};
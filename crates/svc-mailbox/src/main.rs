@@ -1,3 +1,23 @@
-// Binary entrypoint placeholder for svc-mailbox2.
-// Intentionally no code — will wire config, routes, and runtime when implementing.
-fn main() {}
+//! svc-mailbox binary: load config (defaults < $SVCMBX_CONFIG < SVCMBX_* env), serve until Ctrl-C.
+
+use svc_mailbox::{Config, Shutdown};
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let cfg = Config::load()?;
+    let shutdown = Shutdown::new();
+    let trigger = shutdown.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            tracing::info!("svc-mailbox: shutdown requested");
+        }
+        trigger.trigger();
+    });
+
+    svc_mailbox::run(cfg, shutdown).await
+}
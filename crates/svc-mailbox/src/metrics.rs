@@ -1,2 +1,129 @@
-// Prometheus metrics registry and helpers placeholder.
+//! RO:WHAT — Prometheus metrics for svc-mailbox (golden names from docs/OBSERVABILITY.MD).
+//! RO:WHY  — IDB [I-10]: depth, inflight, saturation, rejections, and DLQ movement must be observable per shard.
+//! RO:INTERACTS — domain::Mailbox (updates), runtime::scanner (wakeups), observability::health (`/metrics`).
+//! RO:INVARIANTS — label cardinality is bounded (shard index / fixed reason sets, never topic);
+//!                 each instance owns its registry so tests can run several side by side.
 
+use prometheus::{
+    Encoder, GaugeVec, IntCounter, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+/// Mailbox metric handles plus their registry.
+#[derive(Clone, Debug)]
+pub struct Metrics {
+    registry: Registry,
+    /// Messages accepted by SEND.
+    pub enqueued_total: IntCounter,
+    /// SENDs answered from the idempotency table.
+    pub duplicates_total: IntCounter,
+    /// Successful final ACKs.
+    pub delivered_total: IntCounter,
+    /// Reappearances after a lease expiry or NACK backoff.
+    pub redelivered_total: IntCounter,
+    /// Leases that expired without an ACK.
+    pub visibility_timeout_total: IntCounter,
+    /// NACKs that scheduled a backoff redelivery.
+    pub requeue_backoff_total: IntCounter,
+    /// Scanner passes.
+    pub scanner_wake_total: IntCounter,
+    /// Rejected operations, by reason (`schema|oversize|saturated|unauthorized|forbidden|not_found|unavailable|storage`).
+    pub rejected_total: IntCounterVec,
+    /// Messages dead-lettered, by reason (`max_attempts|rejected|evicted`).
+    pub dlq_total: IntCounterVec,
+    /// Dead letters moved back to ready by an operator.
+    pub dlq_reprocess_total: IntCounter,
+    /// Messages per shard by queue (`work` = ready, `requeue` = NACK backoff, `dlq`).
+    pub queue_depth: IntGaugeVec,
+    /// Consumer-held leases per shard.
+    pub inflight: IntGaugeVec,
+    /// Shard fill ratio (ready + leased) / shard_capacity.
+    pub saturation: GaugeVec,
+    /// Durable journal size per shard (0 in memory-only mode).
+    pub journal_bytes: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Create and register every mailbox metric in a fresh registry.
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let counter = |name: &str, help: &str| {
+            let c = IntCounter::new(name, help).expect("valid counter");
+            registry.register(Box::new(c.clone())).expect("register");
+            c
+        };
+        let counter_vec = |name: &str, help: &str, labels: &[&str]| {
+            let c = IntCounterVec::new(Opts::new(name, help), labels).expect("valid counter");
+            registry.register(Box::new(c.clone())).expect("register");
+            c
+        };
+        let gauge_vec = |name: &str, help: &str, labels: &[&str]| {
+            let g = IntGaugeVec::new(Opts::new(name, help), labels).expect("valid gauge");
+            registry.register(Box::new(g.clone())).expect("register");
+            g
+        };
+        let saturation = GaugeVec::new(
+            Opts::new("saturation", "Shard fill ratio (0..1)"),
+            &["queue", "shard"],
+        )
+        .expect("valid gauge");
+        registry
+            .register(Box::new(saturation.clone()))
+            .expect("register");
+
+        Self {
+            enqueued_total: counter("mailbox_enqueued_total", "Messages accepted by SEND"),
+            duplicates_total: counter(
+                "mailbox_duplicates_total",
+                "SENDs answered from the idempotency table",
+            ),
+            delivered_total: counter("mailbox_delivered_total", "Successful ACKs"),
+            redelivered_total: counter(
+                "mailbox_redelivered_total",
+                "Messages visible again after a lease expiry or NACK backoff",
+            ),
+            visibility_timeout_total: counter(
+                "mailbox_visibility_timeout_total",
+                "Leases that expired without an ACK",
+            ),
+            requeue_backoff_total: counter(
+                "requeue_backoff_total",
+                "NACKs scheduled for backoff redelivery",
+            ),
+            scanner_wake_total: counter("scanner_wake_total", "Visibility scanner passes"),
+            rejected_total: counter_vec("rejected_total", "Rejected operations", &["reason"]),
+            dlq_total: counter_vec("mailbox_dlq_total", "Messages dead-lettered", &["reason"]),
+            dlq_reprocess_total: counter(
+                "mailbox_dlq_reprocess_total",
+                "Dead letters moved back to ready",
+            ),
+            queue_depth: gauge_vec(
+                "queue_depth",
+                "Messages per shard by queue",
+                &["queue", "shard"],
+            ),
+            inflight: gauge_vec("inflight", "Consumer-held leases per shard", &["shard"]),
+            journal_bytes: gauge_vec(
+                "mailbox_journal_bytes",
+                "Durable journal size per shard",
+                &["shard"],
+            ),
+            saturation,
+            registry,
+        }
+    }
+
+    /// Prometheus text exposition of this registry.
+    pub fn render(&self) -> String {
+        let mut buf = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buf)
+            .expect("text encoding");
+        String::from_utf8(buf).expect("prometheus text is utf-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
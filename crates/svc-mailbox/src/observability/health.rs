@@ -1,2 +1,44 @@
-// /healthz, /readyz, /metrics handlers placeholder.
+//! RO:WHAT — `/healthz`, `/readyz`, `/metrics` handlers.
+//! RO:WHY  — Liveness is unconditional; readiness degrades on drain or a failed journal so balancers stop sending writes first.
+//! RO:INTERACTS — http::AppState, runtime::shutdown, metrics.
+//! RO:INVARIANTS — `/readyz` 503 body names what is missing; `/metrics` is Prometheus text.
 
+use axum::{
+    extract::State,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+
+use crate::http::AppState;
+
+/// Liveness.
+pub async fn healthz() -> &'static str {
+    "ok"
+}
+
+/// Readiness: 503 once draining or after a journal write failure.
+pub async fn readyz(State(state): State<AppState>) -> Response {
+    let mut missing = Vec::new();
+    if state.shutdown.is_triggered() {
+        missing.push("draining");
+    }
+    if !state.mailbox.storage_ok() {
+        missing.push("storage");
+    }
+    if !missing.is_empty() {
+        let body = json!({ "degraded": true, "missing": missing });
+        return (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response();
+    }
+    Json(json!({ "ready": true })).into_response()
+}
+
+/// Prometheus exposition.
+pub async fn metrics(State(state): State<AppState>) -> Response {
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.mailbox.metrics().render(),
+    )
+        .into_response()
+}
@@ -0,0 +1,3 @@
+//! RO:WHAT — Health, readiness, and metrics exposition.
+
+pub mod health;
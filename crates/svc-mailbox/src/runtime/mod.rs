@@ -0,0 +1,9 @@
+//! RO:WHAT — Background runtime: visibility scanner, streaming dequeuers, shutdown, and service assembly.
+
+pub mod scanner;
+pub mod shutdown;
+pub mod supervisor;
+pub mod worker;
+
+pub use shutdown::Shutdown;
+pub use supervisor::{build_state, run, serve};
@@ -1,2 +1,33 @@
-// Scanner placeholder: requeues timed-out inflight messages, increments metrics.
+//! RO:WHAT — Visibility scanner: every `scan_interval`, requeue expired leases and finished NACK backoffs.
+//! RO:WHY  — IDB [C-3]: un-ACKed messages must reappear on their own; consumers never drive redelivery.
+//! RO:INTERACTS — domain::Mailbox::scan, runtime::shutdown.
+//! RO:INVARIANTS — deadlines use tokio's monotonic clock; missed ticks are skipped, not bunched.
 
+use std::sync::Arc;
+
+use tokio::{
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+
+use super::shutdown::Shutdown;
+use crate::domain::Mailbox;
+
+/// Spawn the scanner; it exits when `shutdown` fires.
+pub fn spawn(mailbox: Arc<Mailbox>, shutdown: Shutdown) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut tick = interval(mailbox.config().scan_interval);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = tick.tick() => {
+                    let requeued = mailbox.scan();
+                    if requeued > 0 {
+                        tracing::debug!(requeued, "mailbox scanner: leases returned to ready");
+                    }
+                }
+                _ = shutdown.wait() => break,
+            }
+        }
+    })
+}
@@ -1,2 +1,46 @@
-// Graceful shutdown orchestration placeholder.
+//! RO:WHAT — Cloneable shutdown signal shared by the HTTP server, scanner, and streaming workers.
+//! RO:WHY  — One trigger drains everything; readiness flips to degraded the moment it fires.
+//! RO:INTERACTS — runtime::{supervisor, scanner, worker}, observability::health, http::routes.
+//! RO:INVARIANTS — once triggered stays triggered; `wait` resolves immediately afterwards.
 
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Shutdown signal.
+#[derive(Clone, Debug)]
+pub struct Shutdown {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl Shutdown {
+    /// Untriggered signal.
+    pub fn new() -> Self {
+        Self {
+            tx: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    /// Fire the signal.
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// Whether the signal has fired.
+    pub fn is_triggered(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Resolve once the signal fires.
+    pub async fn wait(&self) {
+        let mut rx = self.tx.subscribe();
+        // The sender lives in `self`, so this only returns once triggered.
+        let _ = rx.wait_for(|fired| *fired).await;
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,2 +1,64 @@
-// Supervisor task placeholder: spawn listener, workers, scanner, reprocessor; graceful shutdown.
+//! RO:WHAT — Service assembly: build the mailbox, spawn the scanner, serve HTTP until shutdown.
+//! RO:WHY  — main.rs and tests share one startup path.
+//! RO:INTERACTS — config::Config, domain::Mailbox, http::router, runtime::{scanner, shutdown}, storage.
+//! RO:INVARIANTS — the scanner runs for the server's whole lifetime; shutdown drains HTTP, then stops the scanner.
 
+use std::sync::Arc;
+
+use anyhow::Context;
+use tokio::net::TcpListener;
+
+use super::{scanner, shutdown::Shutdown};
+use crate::{
+    config::Config,
+    domain::Mailbox,
+    http::{router, AppState},
+    metrics::Metrics,
+    storage::JournalOptions,
+};
+
+/// Router state for `cfg`: a memory-only mailbox, or one restored from `persistence.dir`.
+pub fn build_state(cfg: &Config, shutdown: Shutdown) -> anyhow::Result<AppState> {
+    if cfg.amnesia.enabled && cfg.persistence.dir.is_some() {
+        tracing::warn!("amnesia enabled: ignoring persistence.dir, queues and DLQ are RAM-only");
+    }
+    let mailbox = match cfg.journal_dir() {
+        Some(dir) => {
+            let opts = JournalOptions {
+                segment_bytes: cfg.persistence.segment_bytes,
+                fsync: cfg.persistence.fsync,
+            };
+            Mailbox::open(cfg.mailbox.clone(), Metrics::new(), dir, opts)
+                .with_context(|| format!("opening mailbox journal at {}", dir.display()))?
+        }
+        None => Mailbox::new(cfg.mailbox.clone(), Metrics::new()),
+    };
+    Ok(AppState {
+        mailbox: Arc::new(mailbox),
+        shutdown,
+        max_body_bytes: cfg.limits.max_body_bytes,
+        operator_token: cfg.auth.operator_token.as_deref().map(Arc::from),
+        gateway_token: cfg.auth.gateway_token.as_deref().map(Arc::from),
+    })
+}
+
+/// Serve on `listener` until `shutdown` fires.
+pub async fn serve(listener: TcpListener, state: AppState) -> anyhow::Result<()> {
+    let shutdown = state.shutdown.clone();
+    let scanner = scanner::spawn(state.mailbox.clone(), shutdown.clone());
+    tracing::info!(addr = %listener.local_addr()?, "svc-mailbox listening");
+
+    let drained = shutdown.clone();
+    let served = axum::serve(listener, router(state))
+        .with_graceful_shutdown(async move { drained.wait().await })
+        .await;
+    shutdown.trigger();
+    let _ = scanner.await;
+    served.map_err(Into::into)
+}
+
+/// Bind `cfg.bind_addr` and serve until `shutdown` fires.
+pub async fn run(cfg: Config, shutdown: Shutdown) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(cfg.bind_addr).await?;
+    serve(listener, build_state(&cfg, shutdown)?).await
+}
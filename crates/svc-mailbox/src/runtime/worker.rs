@@ -1,2 +1,71 @@
-// Worker placeholder: per-shard dequeuer with visibility handling.
+//! RO:WHAT — Per-consumer dequeuer backing `/v1/recv/stream`: leases batches as they become visible.
+//! RO:WHY  — Long-lived consumers should not poll; they park on their shard until a SEND or requeue wakes them.
+//! RO:INTERACTS — domain::{Mailbox, Shard::notified}, runtime::shutdown, http::routes (NDJSON body).
+//! RO:INVARIANTS — every envelope handed out is leased first, so a consumer that disconnects mid-batch
+//!                 only delays redelivery (visibility timeout), never loses messages;
+//!                 the channel is bounded by one batch so a slow reader stops further leasing.
 
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use super::shutdown::Shutdown;
+use crate::{
+    domain::{Envelope, Mailbox, RecvOptions},
+    error::MailboxError,
+};
+
+/// Spawn a dequeuer for `topic`; envelopes arrive on the returned channel until it is dropped,
+/// shutdown fires, or a non-retryable error occurs.
+pub fn spawn_stream(
+    mailbox: Arc<Mailbox>,
+    topic: String,
+    opts: RecvOptions,
+    shutdown: Shutdown,
+) -> mpsc::Receiver<Envelope> {
+    let depth = usize::from(mailbox.config().max_messages);
+    let (tx, rx) = mpsc::channel(depth);
+    tokio::spawn(run(mailbox, topic, opts, shutdown, tx));
+    rx
+}
+
+async fn run(
+    mailbox: Arc<Mailbox>,
+    topic: String,
+    opts: RecvOptions,
+    shutdown: Shutdown,
+    tx: mpsc::Sender<Envelope>,
+) {
+    let retry = mailbox.config().scan_interval;
+    loop {
+        let shard = mailbox.shard_for(&topic);
+        let notified = shard.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        let saturated = match mailbox.recv(&topic, opts) {
+            Ok(batch) if !batch.is_empty() => {
+                for envelope in batch {
+                    if tx.send(envelope).await.is_err() {
+                        return;
+                    }
+                }
+                continue;
+            }
+            Ok(_) => false,
+            Err(MailboxError::Saturated(_)) => true,
+            Err(err) => {
+                tracing::debug!(%topic, error = %err, "mailbox stream: stopping");
+                return;
+            }
+        };
+
+        tokio::select! {
+            _ = &mut notified => {}
+            // ACKs free inflight slots without a wakeup; re-check on the scanner cadence.
+            _ = tokio::time::sleep(retry), if saturated => {}
+            _ = tx.closed() => return,
+            _ = shutdown.wait() => return,
+        }
+    }
+}
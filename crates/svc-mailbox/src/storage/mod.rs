@@ -0,0 +1,267 @@
+//! RO:WHAT — Durable mailbox backend: one append-only segment journal per shard, with snapshot compaction.
+//! RO:WHY  — Queued, in-flight, and dead-lettered messages must survive a restart unless amnesia mode is on.
+//! RO:INTERACTS — domain::shard (queues a record per state transition through `writer`, restores from `replay`),
+//!                domain::Mailbox::open, config::PersistenceConfig.
+//! RO:INVARIANTS — a SEND is acknowledged only once its `Put` is written; a compaction segment starts
+//!                 with `Snapshot` and is renamed into place before older segments are deleted; only the newest
+//!                 segment may have a torn tail; the on-disk shard count must match `ready_shards`.
+
+pub mod record;
+pub mod replay;
+pub mod segment;
+pub mod writer;
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+pub use record::{JournalRecord, StoredEnvelope};
+pub use replay::{replay, Restored};
+pub use writer::{Commit, JournalWriter};
+
+/// Journal tuning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JournalOptions {
+    /// Roll to a new segment once the active one reaches this size.
+    pub segment_bytes: u64,
+    /// `fdatasync` after every append (power-loss safety) instead of relying on the page cache.
+    pub fsync: bool,
+}
+
+/// Append-only segmented journal for one shard.
+#[derive(Debug)]
+pub struct Journal {
+    dir: PathBuf,
+    opts: JournalOptions,
+    active: File,
+    active_id: u64,
+    active_bytes: u64,
+    sealed: u64,
+    sealed_bytes: u64,
+    records: u64,
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+impl Journal {
+    /// Open or create the journal in `dir`; returns it with the records to replay, oldest first.
+    ///
+    /// A torn tail on the newest segment is truncated. Corruption in an older segment fails the open.
+    pub fn open(dir: &Path, opts: JournalOptions) -> io::Result<(Self, Vec<JournalRecord>)> {
+        fs::create_dir_all(dir)?;
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "tmp") {
+                // Compaction output that never got renamed into place.
+                fs::remove_file(path)?;
+            }
+        }
+
+        let segments = segment::list(dir)?;
+        let mut decoded = Vec::with_capacity(segments.len());
+        let mut base = 0;
+        let mut tail_len = 0;
+        for (i, (_, path)) in segments.iter().enumerate() {
+            let scan = segment::scan(path)?;
+            if scan.valid_len < scan.file_len {
+                if i + 1 < segments.len() {
+                    return Err(invalid(format!(
+                        "corrupt journal segment {}",
+                        path.display()
+                    )));
+                }
+                tracing::warn!(
+                    segment = %path.display(),
+                    dropped = scan.file_len - scan.valid_len,
+                    "mailbox journal: truncating torn tail"
+                );
+                OpenOptions::new()
+                    .write(true)
+                    .open(path)?
+                    .set_len(scan.valid_len)?;
+            }
+            let records = scan
+                .records
+                .iter()
+                .map(|body| serde_json::from_slice::<JournalRecord>(body))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| invalid(format!("journal segment {}: {e}", path.display())))?;
+            if records.first() == Some(&JournalRecord::Snapshot) {
+                base = i;
+            }
+            tail_len = scan.valid_len;
+            decoded.push((records, scan.valid_len));
+        }
+
+        // Segments older than the newest snapshot are leftovers of an interrupted compaction.
+        for (_, path) in &segments[..base] {
+            fs::remove_file(path)?;
+        }
+        let kept = decoded.split_off(base.min(decoded.len()));
+        let sealed = kept.len().saturating_sub(1) as u64;
+        let sealed_bytes = kept
+            .iter()
+            .take(kept.len().saturating_sub(1))
+            .map(|(_, len)| *len)
+            .sum();
+        let records: Vec<JournalRecord> = kept.into_iter().flat_map(|(r, _)| r).collect();
+
+        let (active_id, active_bytes) = match segments.last() {
+            Some((id, _)) => (*id, tail_len),
+            None => (1, 0),
+        };
+        let active = open_append(&dir.join(segment::file_name(active_id)))?;
+        if segments.is_empty() && opts.fsync {
+            sync_dir(dir)?;
+        }
+        let journal = Self {
+            dir: dir.to_path_buf(),
+            opts,
+            active,
+            active_id,
+            active_bytes,
+            sealed,
+            sealed_bytes,
+            records: records.len() as u64,
+        };
+        Ok((journal, records))
+    }
+
+    /// Append `records` as one write (and one sync when `fsync` is set).
+    pub fn append(&mut self, records: &[JournalRecord]) -> io::Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let mut buf = Vec::new();
+        for record in records {
+            segment::write_frame(&mut buf, &serde_json::to_vec(record)?)?;
+        }
+        self.active.write_all(&buf)?;
+        if self.opts.fsync {
+            self.active.sync_data()?;
+        }
+        self.active_bytes += buf.len() as u64;
+        self.records += records.len() as u64;
+        if self.active_bytes >= self.opts.segment_bytes {
+            self.roll()?;
+        }
+        Ok(())
+    }
+
+    /// Replace the journal with `Snapshot` followed by `records` (the shard's live state).
+    pub fn compact(&mut self, records: &[JournalRecord]) -> io::Result<()> {
+        let id = self.active_id + 1;
+        let path = self.dir.join(segment::file_name(id));
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        let mut buf = Vec::new();
+        segment::write_frame(&mut buf, &serde_json::to_vec(&JournalRecord::Snapshot)?)?;
+        for record in records {
+            segment::write_frame(&mut buf, &serde_json::to_vec(record)?)?;
+        }
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(&buf)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &path)?;
+        sync_dir(&self.dir)?;
+
+        // From here on the snapshot segment is authoritative; switch before deleting history.
+        self.active = open_append(&path)?;
+        self.active_id = id;
+        self.active_bytes = buf.len() as u64;
+        self.sealed = 0;
+        self.sealed_bytes = 0;
+        self.records = records.len() as u64 + 1;
+        for (old, old_path) in segment::list(&self.dir)? {
+            if old < id {
+                fs::remove_file(old_path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Bytes on disk across all segments.
+    pub fn bytes(&self) -> u64 {
+        self.sealed_bytes + self.active_bytes
+    }
+
+    /// Records across all segments.
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Segments before the active one.
+    pub fn sealed(&self) -> u64 {
+        self.sealed
+    }
+
+    fn roll(&mut self) -> io::Result<()> {
+        let id = self.active_id + 1;
+        self.active = open_append(&self.dir.join(segment::file_name(id)))?;
+        if self.opts.fsync {
+            sync_dir(&self.dir)?;
+        }
+        self.sealed += 1;
+        self.sealed_bytes += self.active_bytes;
+        self.active_id = id;
+        self.active_bytes = 0;
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Layout {
+    shards: u16,
+}
+
+const LAYOUT_FILE: &str = "layout.json";
+
+/// Directory for shard `index`'s journal under `root`.
+pub fn shard_dir(root: &Path, index: u16) -> PathBuf {
+    root.join(format!("shard-{index:03}"))
+}
+
+/// Record the shard count on first use; refuse to reopen `root` with a different one.
+///
+/// Topics hash onto shards, so changing `ready_shards` would strand journaled messages.
+pub fn check_layout(root: &Path, shards: u16) -> io::Result<()> {
+    fs::create_dir_all(root)?;
+    let path = root.join(LAYOUT_FILE);
+    match fs::read(&path) {
+        Ok(bytes) => {
+            let layout: Layout = serde_json::from_slice(&bytes)
+                .map_err(|e| invalid(format!("{}: {e}", path.display())))?;
+            if layout.shards != shards {
+                return Err(invalid(format!(
+                    "journal at {} was written with {} shards, config has {shards}; drain it before changing ready_shards",
+                    root.display(),
+                    layout.shards
+                )));
+            }
+            Ok(())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            fs::write(&path, serde_json::to_vec(&Layout { shards })?)?;
+            sync_dir(root)
+        }
+        Err(e) => Err(e),
+    }
+}
@@ -0,0 +1,147 @@
+//! RO:WHAT — Journal records: one per shard state transition (put, lease, ack, requeue, dead-letter, evict, reprocess).
+//! RO:WHY  — Replaying them in order rebuilds ready / leased / DLQ state and the replay window after a restart.
+//! RO:INTERACTS — storage::{segment, replay}, domain::shard (producer).
+//! RO:INVARIANTS — deadlines and timestamps are wall-clock unix ms (monotonic instants do not survive a restart);
+//!                 records are JSON with hex payloads; `Snapshot` supersedes everything before it.
+
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{DlqReason, Envelope};
+
+/// One state transition of a shard.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum JournalRecord {
+    /// Compaction marker: state from earlier records is replaced by the records that follow.
+    Snapshot,
+    /// Message accepted (or re-seeded by compaction); joins the back of its topic.
+    ///
+    /// `seen_ms` is set on SEND so the replay window survives; compaction writes `Seen` instead.
+    Put {
+        /// The message, with its current attempt count.
+        envelope: StoredEnvelope,
+        /// When the SEND was accepted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seen_ms: Option<u64>,
+    },
+    /// Message hidden until `deadline_ms` (consumer lease when `held`, NACK backoff otherwise).
+    Lease {
+        /// Leased message.
+        msg_id: String,
+        /// Deliveries so far, including this one.
+        attempt: u32,
+        /// Wall-clock end of the lease.
+        deadline_ms: u64,
+        /// Consumer-held (ACKable) rather than backing off.
+        held: bool,
+    },
+    /// Final ACK; the message is gone.
+    Ack {
+        /// Acknowledged message.
+        msg_id: String,
+    },
+    /// Lease or backoff ended; the message is visible again at the head of its topic.
+    Requeue {
+        /// Requeued message.
+        msg_id: String,
+    },
+    /// Message quarantined.
+    DeadLetter {
+        /// Quarantined message.
+        msg_id: String,
+        /// Why.
+        reason: DlqReason,
+        /// Consumer-supplied NACK reason.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last_error: Option<String>,
+    },
+    /// Dead letter evicted from a full DLQ; the message is gone.
+    Evict {
+        /// Evicted message.
+        msg_id: String,
+    },
+    /// Dead letter moved back to the back of its topic with a fresh attempt budget.
+    Reprocess {
+        /// Reprocessed message.
+        msg_id: String,
+    },
+    /// Replay-window entry for a message the shard no longer holds.
+    Seen {
+        /// Topic of the original SEND.
+        topic: String,
+        /// Idempotency key of the original SEND.
+        idem_key: String,
+        /// Hex BLAKE3-256 of the original payload.
+        payload_hash: String,
+        /// msg_id returned for duplicates.
+        msg_id: String,
+        /// When the original SEND was accepted.
+        at_ms: u64,
+    },
+}
+
+/// Serialized form of `Envelope`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredEnvelope {
+    /// Server-assigned id.
+    pub msg_id: String,
+    /// Destination topic.
+    pub topic: String,
+    /// Sending tenant.
+    pub from: String,
+    /// Application message kind.
+    pub kind: String,
+    /// Idempotency key.
+    pub idem_key: String,
+    /// Hex payload.
+    pub payload: String,
+    /// Hex BLAKE3-256 of the payload.
+    pub payload_hash: String,
+    /// Deliveries so far.
+    pub attempt: u32,
+}
+
+impl From<&Envelope> for StoredEnvelope {
+    fn from(e: &Envelope) -> Self {
+        Self {
+            msg_id: e.msg_id.clone(),
+            topic: e.topic.clone(),
+            from: e.from.clone(),
+            kind: e.kind.clone(),
+            idem_key: e.idem_key.clone(),
+            payload: hex::encode(&e.payload),
+            payload_hash: hex::encode(e.payload_hash),
+            attempt: e.attempt,
+        }
+    }
+}
+
+impl TryFrom<StoredEnvelope> for Envelope {
+    type Error = String;
+
+    fn try_from(s: StoredEnvelope) -> Result<Self, Self::Error> {
+        let payload = Bytes::from(hex::decode(&s.payload).map_err(|e| e.to_string())?);
+        let payload_hash = decode_hash(&s.payload_hash)?;
+        if *blake3::hash(&payload).as_bytes() != payload_hash {
+            return Err(format!("payload hash mismatch for {}", s.msg_id));
+        }
+        Ok(Self {
+            msg_id: s.msg_id,
+            topic: s.topic,
+            from: s.from,
+            kind: s.kind,
+            idem_key: s.idem_key,
+            payload,
+            payload_hash,
+            attempt: s.attempt,
+        })
+    }
+}
+
+/// Parse a hex BLAKE3-256 digest.
+pub fn decode_hash(hex_hash: &str) -> Result<[u8; 32], String> {
+    let mut out = [0_u8; 32];
+    hex::decode_to_slice(hex_hash, &mut out).map_err(|e| e.to_string())?;
+    Ok(out)
+}
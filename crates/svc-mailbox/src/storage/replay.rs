@@ -0,0 +1,182 @@
+//! RO:WHAT — Fold journal records into the state a shard held when it stopped.
+//! RO:WHY  — Restart recovery: ready order, lease deadlines, DLQ contents, and the replay window come back as they were.
+//! RO:INTERACTS — storage::record (input), domain::shard::Shard::restore (consumer).
+//! RO:INVARIANTS — deterministic for a given record sequence; records for unknown msg_ids are ignored
+//!                 (e.g. an ACK racing an eviction); head/back positions mirror the live deque operations.
+
+use std::collections::HashMap;
+
+use super::record::{decode_hash, JournalRecord};
+use crate::domain::{idempotency::IdemKey, DeadLetter, DlqReason, Envelope};
+
+/// Shard contents rebuilt from a journal.
+#[derive(Debug, Default)]
+pub struct Restored {
+    /// Visible messages in queue order (per-topic order is preserved).
+    pub ready: Vec<Envelope>,
+    /// Hidden messages as `(envelope, deadline unix ms, held)`, earliest deadline first.
+    pub leases: Vec<(Envelope, u64, bool)>,
+    /// Dead letters in quarantine order.
+    pub dead: Vec<DeadLetter>,
+    /// Replay-window entries as `(key, msg_id, accepted unix ms)`, oldest first.
+    pub seen: Vec<(IdemKey, String, u64)>,
+}
+
+#[derive(Debug)]
+enum Place {
+    Ready(i64),
+    Leased { deadline_ms: u64, held: bool },
+    Dead(DlqReason, Option<String>),
+}
+
+#[derive(Debug)]
+struct Slot {
+    envelope: Envelope,
+    place: Place,
+    /// Record index of the last transition (tie-break for leases and DLQ order).
+    at: u64,
+}
+
+#[derive(Debug, Default)]
+struct Fold {
+    slots: HashMap<String, Slot>,
+    seen: HashMap<IdemKey, (String, u64)>,
+    front: i64,
+    back: i64,
+    index: u64,
+}
+
+impl Fold {
+    fn apply(&mut self, record: JournalRecord) -> Result<(), String> {
+        self.index += 1;
+        let at = self.index;
+        match record {
+            JournalRecord::Snapshot => {
+                let index = self.index;
+                *self = Self {
+                    index,
+                    ..Self::default()
+                };
+            }
+            JournalRecord::Put { envelope, seen_ms } => {
+                let envelope = Envelope::try_from(envelope)?;
+                if let Some(at_ms) = seen_ms {
+                    let key = (
+                        envelope.topic.clone(),
+                        envelope.idem_key.clone(),
+                        envelope.payload_hash,
+                    );
+                    self.seen.insert(key, (envelope.msg_id.clone(), at_ms));
+                }
+                self.back += 1;
+                let place = Place::Ready(self.back);
+                self.slots.insert(
+                    envelope.msg_id.clone(),
+                    Slot {
+                        envelope,
+                        place,
+                        at,
+                    },
+                );
+            }
+            JournalRecord::Lease {
+                msg_id,
+                attempt,
+                deadline_ms,
+                held,
+            } => {
+                if let Some(slot) = self.slots.get_mut(&msg_id) {
+                    slot.envelope.attempt = attempt;
+                    slot.place = Place::Leased { deadline_ms, held };
+                    slot.at = at;
+                }
+            }
+            JournalRecord::Ack { msg_id } | JournalRecord::Evict { msg_id } => {
+                self.slots.remove(&msg_id);
+            }
+            JournalRecord::Requeue { msg_id } => {
+                if let Some(slot) = self.slots.get_mut(&msg_id) {
+                    self.front -= 1;
+                    slot.place = Place::Ready(self.front);
+                    slot.at = at;
+                }
+            }
+            JournalRecord::DeadLetter {
+                msg_id,
+                reason,
+                last_error,
+            } => {
+                if let Some(slot) = self.slots.get_mut(&msg_id) {
+                    slot.place = Place::Dead(reason, last_error);
+                    slot.at = at;
+                }
+            }
+            JournalRecord::Reprocess { msg_id } => {
+                if let Some(slot) = self.slots.get_mut(&msg_id) {
+                    self.back += 1;
+                    slot.envelope.attempt = 0;
+                    slot.place = Place::Ready(self.back);
+                    slot.at = at;
+                }
+            }
+            JournalRecord::Seen {
+                topic,
+                idem_key,
+                payload_hash,
+                msg_id,
+                at_ms,
+            } => {
+                let key = (topic, idem_key, decode_hash(&payload_hash)?);
+                self.seen.insert(key, (msg_id, at_ms));
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Restored {
+        let mut ready = Vec::new();
+        let mut leases = Vec::new();
+        let mut dead = Vec::new();
+        for slot in self.slots.into_values() {
+            match slot.place {
+                Place::Ready(pos) => ready.push((pos, slot.envelope)),
+                Place::Leased { deadline_ms, held } => {
+                    leases.push(((deadline_ms, slot.at), (slot.envelope, deadline_ms, held)))
+                }
+                Place::Dead(reason, last_error) => dead.push((
+                    slot.at,
+                    DeadLetter {
+                        envelope: slot.envelope,
+                        reason,
+                        last_error,
+                    },
+                )),
+            }
+        }
+        ready.sort_by_key(|(pos, _)| *pos);
+        leases.sort_by_key(|(key, _)| *key);
+        dead.sort_by_key(|(at, _)| *at);
+        let mut seen: Vec<_> = self
+            .seen
+            .into_iter()
+            .map(|(key, (msg_id, at_ms))| (key, msg_id, at_ms))
+            .collect();
+        seen.sort_by_key(|(_, _, at_ms)| *at_ms);
+
+        Restored {
+            ready: ready.into_iter().map(|(_, e)| e).collect(),
+            leases: leases.into_iter().map(|(_, l)| l).collect(),
+            dead: dead.into_iter().map(|(_, d)| d).collect(),
+            seen,
+        }
+    }
+}
+
+/// Fold `records` (oldest first) into shard state. Fails on a record that cannot be decoded.
+pub fn replay(records: impl IntoIterator<Item = JournalRecord>) -> Result<Restored, String> {
+    let mut fold = Fold::default();
+    for record in records {
+        fold.apply(record)?;
+    }
+    Ok(fold.finish())
+}
@@ -0,0 +1,100 @@
+//! RO:WHAT — Segment file framing: `len:u32 LE | b3[..8] | body` per record, files named `<id:020>.seg`.
+//! RO:WHY  — Length + checksum framing lets replay tell a torn tail write from a complete record.
+//! RO:INTERACTS — storage::Journal (writer + open-time reader).
+//! RO:INVARIANTS — a record is trusted only if its full frame is present and the checksum matches;
+//!                 frames above `MAX_RECORD_BYTES` are treated as corruption, never allocated.
+
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Largest accepted record body (a 1 MiB payload hex-encodes to 2 MiB, plus envelope fields).
+pub const MAX_RECORD_BYTES: usize = 4 * 1024 * 1024;
+
+const HEADER_BYTES: usize = 4 + 8;
+const SUFFIX: &str = ".seg";
+
+/// Result of scanning one segment file.
+#[derive(Debug, Default)]
+pub struct SegmentScan {
+    /// Bodies of complete, checksummed records in file order.
+    pub records: Vec<Vec<u8>>,
+    /// Bytes covered by those records; anything past this is a torn or corrupt tail.
+    pub valid_len: u64,
+    /// Total file length.
+    pub file_len: u64,
+}
+
+fn checksum(body: &[u8]) -> [u8; 8] {
+    let mut out = [0_u8; 8];
+    out.copy_from_slice(&blake3::hash(body).as_bytes()[..8]);
+    out
+}
+
+/// Write one framed record; returns the bytes written.
+pub fn write_frame(w: &mut impl Write, body: &[u8]) -> io::Result<u64> {
+    if body.len() > MAX_RECORD_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "journal record exceeds MAX_RECORD_BYTES",
+        ));
+    }
+    let mut frame = Vec::with_capacity(HEADER_BYTES + body.len());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&checksum(body));
+    frame.extend_from_slice(body);
+    // One write per frame so a crash tears at most the final record.
+    w.write_all(&frame)?;
+    Ok(frame.len() as u64)
+}
+
+/// Read every intact record of `path`, stopping at the first incomplete or corrupt frame.
+pub fn scan(path: &Path) -> io::Result<SegmentScan> {
+    let bytes = fs::read(path)?;
+    let mut out = SegmentScan {
+        file_len: bytes.len() as u64,
+        ..SegmentScan::default()
+    };
+    let mut pos = 0;
+    while bytes.len() - pos >= HEADER_BYTES {
+        let len = u32::from_le_bytes(bytes[pos..pos + 4].try_into().expect("4 bytes")) as usize;
+        let sum = &bytes[pos + 4..pos + HEADER_BYTES];
+        let start = pos + HEADER_BYTES;
+        if len > MAX_RECORD_BYTES || bytes.len() - start < len {
+            break;
+        }
+        let body = &bytes[start..start + len];
+        if checksum(body) != sum {
+            break;
+        }
+        out.records.push(body.to_vec());
+        pos = start + len;
+    }
+    out.valid_len = pos as u64;
+    Ok(out)
+}
+
+/// File name for segment `id`.
+pub fn file_name(id: u64) -> String {
+    format!("{id:020}{SUFFIX}")
+}
+
+/// Segment files in `dir` as `(id, path)`, oldest first.
+pub fn list(dir: &Path) -> io::Result<Vec<(u64, PathBuf)>> {
+    let mut out = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let id = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(SUFFIX))
+            .and_then(|stem| stem.parse::<u64>().ok());
+        if let Some(id) = id {
+            out.push((id, path));
+        }
+    }
+    out.sort_by_key(|(id, _)| *id);
+    Ok(out)
+}
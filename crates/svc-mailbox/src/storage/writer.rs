@@ -0,0 +1,205 @@
+//! RO:WHAT — Journal writer: one thread per shard owns the `Journal` and group-commits queued records.
+//! RO:WHY  — Shard transitions run under a parking_lot lock inside async handlers; writes, fsync, and
+//!           compaction must happen off that lock and off the runtime's worker threads.
+//! RO:INTERACTS — storage::Journal, domain::shard (submits under its lock, which fixes record order),
+//!                domain::Mailbox (awaits the `Commit` of a SEND before acknowledging it).
+//! RO:INVARIANTS — records reach disk in submission order; one write + one sync per drained batch;
+//!                 after the first failed write every later commit reports failure;
+//!                 dropping the writer drains the queue and joins the thread.
+
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread::JoinHandle,
+};
+
+use tokio::sync::oneshot;
+
+use super::{Journal, JournalRecord};
+
+/// Most queued jobs folded into one group commit, so a busy shard still sees steady progress.
+const MAX_BATCH_JOBS: usize = 1024;
+
+enum Job {
+    Append {
+        records: Vec<JournalRecord>,
+        done: Option<oneshot::Sender<bool>>,
+    },
+    Compact(Vec<JournalRecord>),
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    failed: AtomicBool,
+    compacting: AtomicBool,
+    /// Records submitted but not yet written.
+    backlog: AtomicUsize,
+    bytes: AtomicU64,
+    records: AtomicU64,
+    sealed: AtomicU64,
+}
+
+impl Shared {
+    fn publish(&self, journal: &Journal) {
+        self.bytes.store(journal.bytes(), Ordering::Release);
+        self.records.store(journal.records(), Ordering::Release);
+        self.sealed.store(journal.sealed(), Ordering::Release);
+    }
+}
+
+/// Resolves once the records it was issued for are on disk (or the journal has failed).
+#[derive(Debug)]
+pub struct Commit(oneshot::Receiver<bool>);
+
+impl Commit {
+    /// True when the records were written (and synced, if `fsync` is on).
+    pub async fn durable(self) -> bool {
+        self.0.await.unwrap_or(false)
+    }
+}
+
+/// Handle to a shard's journal thread.
+#[derive(Debug)]
+pub struct JournalWriter {
+    tx: Option<mpsc::Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+    shared: Arc<Shared>,
+}
+
+impl JournalWriter {
+    /// Move `journal` onto its own thread.
+    pub fn spawn(index: u16, journal: Journal) -> io::Result<Self> {
+        let shared = Arc::new(Shared::default());
+        shared.publish(&journal);
+        let (tx, rx) = mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name(format!("mailbox-journal-{index:03}"))
+            .spawn({
+                let shared = shared.clone();
+                move || run(journal, &rx, &shared)
+            })?;
+        Ok(Self {
+            tx: Some(tx),
+            thread: Some(thread),
+            shared,
+        })
+    }
+
+    /// Queue `records` behind everything submitted before; `wait` asks for a `Commit`.
+    pub fn submit(&self, records: Vec<JournalRecord>, wait: bool) -> Option<Commit> {
+        if records.is_empty() && !wait {
+            return None;
+        }
+        let (done, commit) = if wait {
+            let (tx, rx) = oneshot::channel();
+            (Some(tx), Some(Commit(rx)))
+        } else {
+            (None, None)
+        };
+        self.shared
+            .backlog
+            .fetch_add(records.len(), Ordering::AcqRel);
+        self.send(Job::Append { records, done });
+        commit
+    }
+
+    /// Queue a rewrite of the journal as `Snapshot` + `records`.
+    ///
+    /// `records` must describe the state after everything already submitted, which holds when the
+    /// snapshot is taken under the same lock that orders submissions.
+    pub fn compact(&self, records: Vec<JournalRecord>) {
+        self.shared.compacting.store(true, Ordering::Release);
+        self.send(Job::Compact(records));
+    }
+
+    /// Whether rewriting the live state (about `live` records) would at least halve the journal.
+    ///
+    /// Only true once a segment has been sealed, so compaction runs at most once per segment's
+    /// worth of writes, and never while one is already queued.
+    pub fn should_compact(&self, live: usize) -> bool {
+        let s = &self.shared;
+        !s.compacting.load(Ordering::Acquire)
+            && s.sealed.load(Ordering::Acquire) > 0
+            && s.records.load(Ordering::Acquire) >= 2 * (live as u64).max(1)
+    }
+
+    /// Records queued but not yet written.
+    pub fn backlog(&self) -> usize {
+        self.shared.backlog.load(Ordering::Acquire)
+    }
+
+    /// Bytes on disk as of the last written batch.
+    pub fn bytes(&self) -> u64 {
+        self.shared.bytes.load(Ordering::Acquire)
+    }
+
+    /// True once a write or compaction has failed.
+    pub fn failed(&self) -> bool {
+        self.shared.failed.load(Ordering::Acquire)
+    }
+
+    fn send(&self, job: Job) {
+        // The thread only exits once `tx` is dropped, so a send cannot fail while `self` lives.
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(job);
+        }
+    }
+}
+
+impl Drop for JournalWriter {
+    fn drop(&mut self) {
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run(mut journal: Journal, rx: &mpsc::Receiver<Job>, shared: &Shared) {
+    while let Ok(first) = rx.recv() {
+        let mut records = Vec::new();
+        let mut waiters = Vec::new();
+        let mut queued = 0;
+        let mut compacted = false;
+        for job in std::iter::once(first).chain(rx.try_iter().take(MAX_BATCH_JOBS - 1)) {
+            match job {
+                Job::Append { records: r, done } => {
+                    queued += r.len();
+                    records.extend(r);
+                    waiters.extend(done);
+                }
+                Job::Compact(snapshot) => {
+                    // The snapshot already reflects every record queued ahead of it.
+                    records.clear();
+                    write(shared, "compaction", || journal.compact(&snapshot));
+                    compacted = true;
+                }
+            }
+        }
+        if !records.is_empty() {
+            write(shared, "append", || journal.append(&records));
+        }
+        if compacted {
+            shared.compacting.store(false, Ordering::Release);
+        }
+        shared.publish(&journal);
+        shared.backlog.fetch_sub(queued, Ordering::AcqRel);
+        let ok = !shared.failed.load(Ordering::Acquire);
+        for done in waiters {
+            let _ = done.send(ok);
+        }
+    }
+}
+
+fn write(shared: &Shared, what: &str, op: impl FnOnce() -> io::Result<()>) {
+    if shared.failed.load(Ordering::Acquire) {
+        return;
+    }
+    if let Err(e) = op() {
+        shared.failed.store(true, Ordering::Release);
+        tracing::error!(error = %e, op = what, "mailbox journal: write failed; refusing new SENDs");
+    }
+}
@@ -1,2 +1,19 @@
-// Jittered backoff helpers placeholder.
+//! RO:WHAT — Exponential backoff with full jitter for NACK redelivery.
+//! RO:WHY  — IDB [P-7]: `rand(0, min(backoff_max, base * 2^attempt))` spreads retries of a failing topic.
+//! RO:INTERACTS — domain::shard (NACK path).
+//! RO:INVARIANTS — never exceeds `max`; saturates instead of overflowing for large attempts.
 
+use std::time::Duration;
+
+/// Upper bound of the jitter window for `attempt` (0-based exponent).
+pub fn ceiling(base: Duration, max: Duration, attempt: u32) -> Duration {
+    let factor = 1_u32.checked_shl(attempt.min(31)).unwrap_or(u32::MAX);
+    base.saturating_mul(factor).min(max)
+}
+
+/// Random delay in `[0, ceiling(base, max, attempt)]`.
+pub fn full_jitter(base: Duration, max: Duration, attempt: u32) -> Duration {
+    let cap = ceiling(base, max, attempt);
+    let nanos = u64::try_from(cap.as_nanos()).unwrap_or(u64::MAX);
+    Duration::from_nanos(fastrand::u64(0..=nanos))
+}
@@ -0,0 +1,4 @@
+//! RO:WHAT — Small shared helpers.
+
+pub mod backoff;
+pub mod time;
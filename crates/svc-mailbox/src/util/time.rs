@@ -1,2 +1,38 @@
-// Duration/size parsing and monotonic clock helpers placeholder.
+//! RO:WHAT — Conversions between tokio monotonic instants and wall-clock unix milliseconds.
+//! RO:WHY  — Leases run on the monotonic clock, but a journal must store deadlines that still mean something after a restart.
+//! RO:INTERACTS — domain::shard (journal writes and restore), storage::record.
+//! RO:INVARIANTS — conversions anchor on "now" for both clocks and never panic.
 
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::time::Instant;
+
+/// Wall-clock now as unix milliseconds.
+pub fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
+/// Wall-clock unix milliseconds for a monotonic `at`.
+pub fn to_unix_ms(at: Instant) -> u64 {
+    let now = Instant::now();
+    let wall = unix_ms();
+    if at >= now {
+        wall.saturating_add((at - now).as_millis() as u64)
+    } else {
+        wall.saturating_sub((now - at).as_millis() as u64)
+    }
+}
+
+/// Monotonic instant for wall-clock `ms` (now, if that past instant is not representable).
+pub fn from_unix_ms(ms: u64) -> Instant {
+    let now = Instant::now();
+    let wall = unix_ms();
+    if ms >= wall {
+        now + Duration::from_millis(ms - wall)
+    } else {
+        now.checked_sub(Duration::from_millis(wall - ms))
+            .unwrap_or(now)
+    }
+}
@@ -0,0 +1,123 @@
+//! Dead-letter queue: over-retried and finally-NACKed messages are quarantined, never lost, and reprocessable.
+
+use std::time::Duration;
+
+use ron_proto::mailbox::Send;
+use svc_mailbox::{config::MailboxConfig, domain::RecvOptions, metrics::Metrics, Mailbox};
+
+fn config() -> MailboxConfig {
+    MailboxConfig {
+        max_attempts: 2,
+        backoff_base: Duration::from_millis(10),
+        backoff_max: Duration::from_millis(10),
+        ..MailboxConfig::default()
+    }
+}
+
+fn mailbox(cfg: MailboxConfig) -> Mailbox {
+    Mailbox::new(cfg, Metrics::new())
+}
+
+async fn put(mb: &Mailbox, topic: &str, id: &str) -> String {
+    mb.send(
+        "tenant",
+        Send {
+            msg_id: id.into(),
+            to: topic.into(),
+            kind: "job".into(),
+            payload: id.as_bytes().to_vec(),
+            idempotency_key: None,
+        },
+    )
+    .await
+    .unwrap()
+    .msg_id
+}
+
+fn lease(mb: &Mailbox, topic: &str) -> Vec<String> {
+    mb.recv(
+        topic,
+        RecvOptions {
+            visibility: Some(Duration::from_secs(1)),
+            ..RecvOptions::default()
+        },
+    )
+    .unwrap()
+    .into_iter()
+    .map(|e| e.msg_id)
+    .collect()
+}
+
+#[tokio::test(start_paused = true)]
+async fn expired_final_attempts_and_final_nacks_are_dead_lettered() {
+    let mb = mailbox(config());
+    let expiring = put(&mb, "jobs", "a").await;
+
+    for _ in 0..2 {
+        assert_eq!(lease(&mb, "jobs"), std::slice::from_ref(&expiring));
+        tokio::time::advance(Duration::from_secs(1)).await;
+        mb.scan();
+    }
+    assert!(lease(&mb, "jobs").is_empty());
+    assert_eq!(mb.dlq_len("jobs"), 1);
+    assert_eq!(mb.inflight(), 0);
+
+    let rejected = put(&mb, "jobs", "b").await;
+    assert_eq!(lease(&mb, "jobs"), std::slice::from_ref(&rejected));
+    mb.nack(&rejected, Some("transient".into())).unwrap();
+    tokio::time::advance(Duration::from_millis(10)).await;
+    mb.scan();
+    assert_eq!(lease(&mb, "jobs"), std::slice::from_ref(&rejected));
+    mb.nack(&rejected, Some("poison".into())).unwrap();
+    assert_eq!(mb.dlq_len("jobs"), 2);
+
+    let m = mb.metrics();
+    assert_eq!(m.dlq_total.with_label_values(&["max_attempts"]).get(), 1);
+    assert_eq!(m.dlq_total.with_label_values(&["rejected"]).get(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn reprocess_moves_letters_back_with_a_fresh_attempt_budget() {
+    let mb = mailbox(config());
+    let mut ids = Vec::new();
+    for i in 0..3 {
+        ids.push(put(&mb, "jobs", &format!("m{i}")).await);
+    }
+    for _ in 0..2 {
+        lease(&mb, "jobs");
+        tokio::time::advance(Duration::from_secs(1)).await;
+        mb.scan();
+    }
+    assert_eq!(mb.dlq_len("jobs"), 3);
+    assert_eq!(mb.reprocess("other", 10).unwrap(), 0);
+
+    assert_eq!(mb.reprocess("jobs", 2).unwrap(), 2);
+    assert_eq!(mb.dlq_len("jobs"), 1);
+    let batch = mb.recv("jobs", RecvOptions::default()).unwrap();
+    assert_eq!(
+        batch.iter().map(|e| e.msg_id.as_str()).collect::<Vec<_>>(),
+        [ids[0].as_str(), ids[1].as_str()]
+    );
+    assert!(batch.iter().all(|e| e.attempt == 1));
+    assert_eq!(mb.metrics().dlq_reprocess_total.get(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn a_full_dlq_evicts_visibly() {
+    let mb = mailbox(MailboxConfig {
+        ready_shards: 1,
+        dlq_capacity: 2,
+        max_attempts: 1,
+        ..config()
+    });
+    for i in 0..3 {
+        let id = put(&mb, "jobs", &format!("m{i}")).await;
+        lease(&mb, "jobs");
+        mb.nack(&id, None).unwrap();
+    }
+    assert_eq!(mb.dlq_len("jobs"), 2);
+    assert_eq!(
+        mb.metrics().dlq_total.with_label_values(&["evicted"]).get(),
+        1
+    );
+}
@@ -1,5 +1,47 @@
-// Asserts expected metric names exist (placeholder).
-#[test]
-fn golden_metrics() {
-    assert!(true);
+//! Golden metric names (docs/OBSERVABILITY.MD) are exposed on /metrics.
+
+use axum::{
+    body::{to_bytes, Body},
+    http::Request,
+};
+use serde_json::json;
+use svc_mailbox::{http::router, runtime::build_state, Config, Shutdown};
+use tower::ServiceExt;
+
+#[tokio::test]
+async fn golden_metrics() {
+    let app = router(build_state(&Config::default(), Shutdown::new()).unwrap());
+    // One rejection so labelled counters have a series.
+    let bad = Request::post("/v1/recv")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "nope": 1 }).to_string()))
+        .unwrap();
+    app.clone().oneshot(bad).await.unwrap();
+
+    let resp = app
+        .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let text = String::from_utf8(
+        to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .to_vec(),
+    )
+    .unwrap();
+    for name in [
+        "mailbox_enqueued_total",
+        "mailbox_delivered_total",
+        "mailbox_redelivered_total",
+        "mailbox_visibility_timeout_total",
+        "mailbox_dlq_reprocess_total",
+        "requeue_backoff_total",
+        "scanner_wake_total",
+        "rejected_total{reason=\"schema\"}",
+        "queue_depth{queue=\"work\",shard=\"0\"}",
+        "inflight{shard=\"0\"}",
+        "saturation{queue=\"work\",shard=\"0\"}",
+    ] {
+        assert!(text.contains(name), "missing {name}");
+    }
 }
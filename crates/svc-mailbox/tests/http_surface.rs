@@ -1,5 +1,445 @@
-// HTTP surface tests placeholder: send/recv/ack/streaming.
-#[test]
-fn http_surface() {
-    assert!(true);
+//! HTTP surface: ron-proto Send/Recv/Ack over /v1, NDJSON streaming, error taxonomy.
+
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+    Router,
+};
+use futures_util::StreamExt;
+use ron_proto::{
+    mailbox::{Ack, Recv, Send},
+    Kind, ProtoError,
+};
+use serde_json::{json, Value};
+use svc_mailbox::{
+    http::{
+        dto::{RecvResponse, SendResponse},
+        router,
+    },
+    runtime::build_state,
+    Config, Shutdown,
+};
+use tower::ServiceExt;
+
+const GATEWAY: &str = "gateway-secret-0123";
+
+/// Defaults plus the gateway token every `post` presents.
+fn config() -> Config {
+    let mut cfg = Config::default();
+    cfg.auth.gateway_token = Some(GATEWAY.into());
+    cfg
+}
+
+fn app() -> Router {
+    router(build_state(&config(), Shutdown::new()).unwrap())
+}
+
+/// A request as the gateway forwards it, naming `tenant` when given.
+fn post(path: &str, tenant: Option<&str>, body: Value) -> Request<Body> {
+    let mut req = Request::post(path)
+        .header("content-type", "application/json")
+        .header("x-ron-gateway-token", GATEWAY);
+    if let Some(t) = tenant {
+        req = req.header("x-ron-tenant", t);
+    }
+    req.body(Body::from(body.to_string())).unwrap()
+}
+
+async fn call(app: &Router, req: Request<Body>) -> (StatusCode, Value) {
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+fn send_body(to: &str, id: &str, payload: &[u8]) -> Value {
+    serde_json::to_value(Send {
+        msg_id: id.into(),
+        to: to.into(),
+        kind: "chat".into(),
+        payload: payload.to_vec(),
+        idempotency_key: None,
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn send_recv_ack_round_trip() {
+    let app = app();
+    let (status, body) = call(
+        &app,
+        post(
+            "/v1/send",
+            Some("tenant-a"),
+            send_body("user:42:inbox", "c1", b"hi"),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let sent: SendResponse = serde_json::from_value(body).unwrap();
+    assert!(!sent.duplicate);
+
+    let (status, body) = call(
+        &app,
+        post(
+            "/v1/recv",
+            Some("user"),
+            json!({ "topic": "user:42:inbox", "visibility_ms": 5000 }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let got: RecvResponse = serde_json::from_value(body).unwrap();
+    assert_eq!(
+        got.messages,
+        [Recv {
+            msg_id: sent.msg_id.clone(),
+            from: "tenant-a".into(),
+            kind: "chat".into(),
+            payload: b"hi".to_vec(),
+        }]
+    );
+
+    let ack = serde_json::to_value(Ack {
+        msg_id: sent.msg_id.clone(),
+        ok: true,
+        error: None,
+    })
+    .unwrap();
+    let (status, body) = call(&app, post("/v1/ack", Some("user"), ack.clone())).await;
+    assert_eq!((status, body), (StatusCode::OK, json!({ "ok": true })));
+    let (status, body) = call(&app, post("/v1/ack", Some("user"), ack)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["code"], "E_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn negative_ack_and_path_forms() {
+    let app = app();
+    let send = |id: &'static str| {
+        post(
+            "/v1/send",
+            Some("t"),
+            send_body("t:jobs", id, id.as_bytes()),
+        )
+    };
+    let recv = || {
+        post(
+            "/v1/recv",
+            Some("t"),
+            json!({ "topic": "t:jobs", "max_messages": 1 }),
+        )
+    };
+    let first: SendResponse = serde_json::from_value(call(&app, send("a")).await.1).unwrap();
+    let second: SendResponse = serde_json::from_value(call(&app, send("b")).await.1).unwrap();
+
+    call(&app, recv()).await;
+    let nack = Ack {
+        msg_id: first.msg_id.clone(),
+        ok: false,
+        error: Some(ProtoError {
+            kind: Kind::Unavailable,
+            message: "downstream busy".into(),
+        }),
+    };
+    let (status, _) = call(
+        &app,
+        post("/v1/ack", Some("t"), serde_json::to_value(nack).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    call(&app, recv()).await;
+    let path = format!("/v1/nack/{}", second.msg_id);
+    let (status, _) = call(
+        &app,
+        post(&path, Some("t"), json!({ "reason": "retry later" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let empty = Request::post(format!("/v1/ack/{}", second.msg_id))
+        .header("x-ron-gateway-token", GATEWAY)
+        .header("x-ron-tenant", "t")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(call(&app, empty).await.0, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn schema_size_and_duplicate_errors() {
+    let app = app();
+    let (status, body) = call(
+        &app,
+        post(
+            "/v1/send",
+            Some("t"),
+            json!({ "msg_id": "x", "to": "t", "kind": "k", "payload": [], "extra": 1 }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["code"], "E_SCHEMA");
+
+    let (status, body) = call(&app, post("/v1/send", None, send_body("t", "x", b"x"))).await;
+    assert_eq!(
+        (status, body["code"].clone()),
+        (StatusCode::BAD_REQUEST, json!("E_SCHEMA"))
+    );
+
+    let big = vec![7_u8; 1024 * 1024 + 1];
+    let (status, body) = call(
+        &app,
+        post("/v1/send", Some("t"), send_body("t", "big", &big)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(body["code"], "E_FRAME_TOO_LARGE");
+
+    let (status, body) = call(
+        &app,
+        post(
+            "/v1/recv",
+            Some("t"),
+            json!({ "topic": "t", "visibility_ms": 1 }),
+        ),
+    )
+    .await;
+    assert_eq!(
+        (status, body["code"].clone()),
+        (StatusCode::BAD_REQUEST, json!("E_SCHEMA"))
+    );
+
+    let dup = || {
+        let mut req = post("/v1/send", Some("t"), send_body("t", "same", b"same"));
+        req.headers_mut()
+            .insert("x-idempotency-mode", "409-conflict".parse().unwrap());
+        req
+    };
+    let (status, first) = call(&app, dup()).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, again) = call(&app, dup()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(again["msg_id"], first["msg_id"]);
+    assert_eq!(again["duplicate"], true);
+}
+
+async fn next_line<S>(body: &mut S) -> Recv
+where
+    S: futures_util::Stream<Item = Result<axum::body::Bytes, axum::Error>> + Unpin,
+{
+    let bytes = tokio::time::timeout(Duration::from_secs(5), body.next())
+        .await
+        .expect("line arrives")
+        .unwrap()
+        .unwrap();
+    assert_eq!(bytes.last(), Some(&b'\n'));
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn recv_stream_emits_ndjson_as_messages_arrive() {
+    let app = app();
+    call(
+        &app,
+        post("/v1/send", Some("t"), send_body("t:feed", "1", b"one")),
+    )
+    .await;
+
+    let resp = app
+        .clone()
+        .oneshot(post(
+            "/v1/recv/stream",
+            Some("t"),
+            json!({ "topic": "t:feed" }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/x-ndjson");
+    let mut body = resp.into_body().into_data_stream();
+
+    assert_eq!(next_line(&mut body).await.payload, b"one");
+
+    // A later SEND wakes the parked stream.
+    call(
+        &app,
+        post("/v1/send", Some("t"), send_body("t:feed", "2", b"two")),
+    )
+    .await;
+    assert_eq!(next_line(&mut body).await.payload, b"two");
+}
+
+fn operator_post(token: Option<&str>, body: Value) -> Request<Body> {
+    let mut req = post("/v1/dlq/reprocess", None, body);
+    if let Some(token) = token {
+        let value = format!("Bearer {token}").parse().unwrap();
+        req.headers_mut().insert("authorization", value);
+    }
+    req
+}
+
+#[tokio::test]
+async fn dlq_reprocess_endpoint() {
+    const TOKEN: &str = "operator-secret-0123";
+    let mut cfg = config();
+    cfg.mailbox.max_attempts = 1;
+    cfg.auth.operator_token = Some(TOKEN.into());
+    let state = build_state(&cfg, Shutdown::new()).unwrap();
+    let app = router(state.clone());
+
+    let (_, sent) = call(
+        &app,
+        post("/v1/send", Some("t"), send_body("t:jobs", "p", b"poison")),
+    )
+    .await;
+    call(
+        &app,
+        post("/v1/recv", Some("t"), json!({ "topic": "t:jobs" })),
+    )
+    .await;
+    let path = format!("/v1/nack/{}", sent["msg_id"].as_str().unwrap());
+    call(&app, post(&path, Some("t"), json!({}))).await;
+    assert_eq!(state.mailbox.dlq_len("t:jobs"), 1);
+
+    let body = json!({ "topic": "t:jobs" });
+    let (status, err) = call(&app, operator_post(None, body.clone())).await;
+    assert_eq!(
+        (status, err["code"].clone()),
+        (StatusCode::UNAUTHORIZED, json!("E_CAP_AUTH"))
+    );
+    let (status, _) = call(
+        &app,
+        operator_post(Some("wrong-token-0123456"), body.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(state.mailbox.dlq_len("t:jobs"), 1);
+
+    let (status, moved) = call(&app, operator_post(Some(TOKEN), body)).await;
+    assert_eq!((status, moved), (StatusCode::OK, json!({ "moved": 1 })));
+    let (_, got) = call(
+        &app,
+        post("/v1/recv", Some("t"), json!({ "topic": "t:jobs" })),
+    )
+    .await;
+    assert_eq!(got["messages"][0]["msg_id"], sent["msg_id"]);
+}
+
+#[tokio::test]
+async fn dlq_reprocess_is_disabled_without_an_operator_token() {
+    let app = app();
+    let (status, body) = call(
+        &app,
+        operator_post(Some("anything-at-all-0123"), json!({ "topic": "t:jobs" })),
+    )
+    .await;
+    assert_eq!(
+        (status, body["code"].clone()),
+        (StatusCode::FORBIDDEN, json!("E_CAP_SCOPE"))
+    );
+}
+
+#[tokio::test]
+async fn consumers_only_touch_their_own_topics() {
+    let app = app();
+    let (_, sent) = call(
+        &app,
+        post(
+            "/v1/send",
+            Some("mallory"),
+            send_body("alice:inbox", "m", b"hi"),
+        ),
+    )
+    .await;
+    let msg_id = sent["msg_id"].as_str().unwrap();
+
+    // Senders cannot read back what they delivered to another tenant's topic.
+    let recv = |tenant: Option<&str>| post("/v1/recv", tenant, json!({ "topic": "alice:inbox" }));
+    let (status, body) = call(&app, recv(Some("mallory"))).await;
+    assert_eq!(
+        (status, body["code"].clone()),
+        (StatusCode::FORBIDDEN, json!("E_CAP_SCOPE"))
+    );
+    let (status, body) = call(&app, recv(None)).await;
+    assert_eq!(
+        (status, body["code"].clone()),
+        (StatusCode::BAD_REQUEST, json!("E_SCHEMA"))
+    );
+    let (status, _) = call(
+        &app,
+        post(
+            "/v1/recv/stream",
+            Some("mallory"),
+            json!({ "topic": "alice:inbox" }),
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, got) = call(&app, recv(Some("alice"))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(got["messages"][0]["msg_id"], msg_id);
+
+    // Another tenant's lease looks unknown, and stays leased to its owner.
+    for path in [format!("/v1/ack/{msg_id}"), format!("/v1/nack/{msg_id}")] {
+        let (status, body) = call(&app, post(&path, Some("mallory"), json!({}))).await;
+        assert_eq!(
+            (status, body["code"].clone()),
+            (StatusCode::NOT_FOUND, json!("E_NOT_FOUND"))
+        );
+    }
+    let ack = json!({ "msg_id": msg_id, "ok": true });
+    let (status, _) = call(&app, post("/v1/ack", Some("mallory"), ack.clone())).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = call(&app, post("/v1/ack", Some("alice"), ack)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn tenant_header_is_only_trusted_from_the_gateway() {
+    let app = app();
+    call(
+        &app,
+        post(
+            "/v1/send",
+            Some("alice"),
+            send_body("alice:inbox", "a", b"hi"),
+        ),
+    )
+    .await;
+
+    // A direct caller naming alice, with no or a guessed gateway token.
+    let direct = |token: Option<&str>| {
+        let mut req = Request::post("/v1/recv")
+            .header("content-type", "application/json")
+            .header("x-ron-tenant", "alice");
+        if let Some(token) = token {
+            req = req.header("x-ron-gateway-token", token);
+        }
+        req.body(Body::from(json!({ "topic": "alice:inbox" }).to_string()))
+            .unwrap()
+    };
+    for token in [None, Some("not-the-gateway-0123")] {
+        let (status, body) = call(&app, direct(token)).await;
+        assert_eq!(
+            (status, body["code"].clone()),
+            (StatusCode::UNAUTHORIZED, json!("E_CAP_AUTH"))
+        );
+    }
+    let (status, got) = call(&app, direct(Some(GATEWAY))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(got["messages"][0]["payload"], json!(b"hi"));
+
+    // Without a configured gateway nobody can name a tenant.
+    let closed = router(build_state(&Config::default(), Shutdown::new()).unwrap());
+    let (status, body) = call(&closed, direct(Some(GATEWAY))).await;
+    assert_eq!(
+        (status, body["code"].clone()),
+        (StatusCode::FORBIDDEN, json!("E_CAP_SCOPE"))
+    );
 }
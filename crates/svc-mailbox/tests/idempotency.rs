@@ -1,5 +1,121 @@
-// Idempotency triple tests placeholder.
-#[test]
-fn idempotency_triple() {
-    assert!(true);
+//! Idempotency triple (topic, idem_key, payload_hash): one effective delivery inside T_replay.
+
+use std::time::Duration;
+
+use ron_proto::mailbox::Send;
+use svc_mailbox::{config::MailboxConfig, domain::RecvOptions, metrics::Metrics, Mailbox};
+
+fn send(to: &str, key: Option<&str>, payload: &[u8]) -> Send {
+    Send {
+        msg_id: "client-1".into(),
+        to: to.into(),
+        kind: "note".into(),
+        payload: payload.to_vec(),
+        idempotency_key: key.map(Into::into),
+    }
+}
+
+fn mailbox() -> Mailbox {
+    Mailbox::new(MailboxConfig::default(), Metrics::new())
+}
+
+#[tokio::test]
+async fn duplicates_return_the_original_id_and_deliver_once() {
+    let mb = mailbox();
+    let first = mb
+        .send("tenant-a", send("user:1:inbox", Some("k1"), b"hi"))
+        .await
+        .unwrap();
+    assert!(!first.duplicate);
+    for _ in 0..5 {
+        let again = mb
+            .send("tenant-a", send("user:1:inbox", Some("k1"), b"hi"))
+            .await
+            .unwrap();
+        assert!(again.duplicate);
+        assert_eq!(again.msg_id, first.msg_id);
+    }
+
+    let batch = mb.recv("user:1:inbox", RecvOptions::default()).unwrap();
+    assert_eq!(batch.len(), 1);
+    assert_eq!(batch[0].msg_id, first.msg_id);
+    assert_eq!(mb.metrics().duplicates_total.get(), 5);
+    assert_eq!(mb.metrics().enqueued_total.get(), 1);
+}
+
+#[tokio::test]
+async fn any_part_of_the_triple_changing_is_a_new_message() {
+    let mb = mailbox();
+    let base = mb
+        .send("t", send("topic-a", Some("k"), b"x"))
+        .await
+        .unwrap();
+    let other_payload = mb
+        .send("t", send("topic-a", Some("k"), b"y"))
+        .await
+        .unwrap();
+    let other_key = mb
+        .send("t", send("topic-a", Some("k2"), b"x"))
+        .await
+        .unwrap();
+    let other_topic = mb
+        .send("t", send("topic-b", Some("k"), b"x"))
+        .await
+        .unwrap();
+    for outcome in [&other_payload, &other_key, &other_topic] {
+        assert!(!outcome.duplicate);
+        assert_ne!(outcome.msg_id, base.msg_id);
+    }
+
+    // Without an explicit key the sender's msg_id is the idempotency key.
+    let a = mb.send("t", send("topic-c", None, b"x")).await.unwrap();
+    let b = mb.send("t", send("topic-c", None, b"x")).await.unwrap();
+    assert!(b.duplicate);
+    assert_eq!(a.msg_id, b.msg_id);
+}
+
+#[tokio::test(start_paused = true)]
+async fn key_becomes_eligible_again_after_the_replay_window() {
+    let mb = mailbox();
+    let t_replay = mb.config().t_replay;
+    let first = mb.send("t", send("topic", Some("k"), b"x")).await.unwrap();
+
+    tokio::time::advance(t_replay - Duration::from_millis(1)).await;
+    assert!(
+        mb.send("t", send("topic", Some("k"), b"x"))
+            .await
+            .unwrap()
+            .duplicate
+    );
+
+    tokio::time::advance(Duration::from_millis(2)).await;
+    let fresh = mb.send("t", send("topic", Some("k"), b"x")).await.unwrap();
+    assert!(!fresh.duplicate);
+    assert_ne!(fresh.msg_id, first.msg_id);
+}
+
+#[tokio::test]
+async fn a_full_replay_table_refuses_rather_than_shrinking_the_window() {
+    let mb = Mailbox::new(
+        MailboxConfig {
+            ready_shards: 1,
+            dedup_capacity: 2,
+            ..MailboxConfig::default()
+        },
+        Metrics::new(),
+    );
+    mb.send("t", send("topic", Some("a"), b"x")).await.unwrap();
+    mb.send("t", send("topic", Some("b"), b"x")).await.unwrap();
+    let err = mb
+        .send("t", send("topic", Some("c"), b"x"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), "E_SATURATED");
+    // Known keys are still answered.
+    assert!(
+        mb.send("t", send("topic", Some("a"), b"x"))
+            .await
+            .unwrap()
+            .duplicate
+    );
 }
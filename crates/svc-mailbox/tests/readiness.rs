@@ -1,5 +1,115 @@
-// Readiness shedding behavior tests placeholder.
+//! Readiness shedding: draining sheds writes first; saturation answers 429 with Retry-After.
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use serde_json::json;
+use svc_mailbox::{http::router, runtime::build_state, Config, Shutdown};
+use tower::ServiceExt;
+
+const GATEWAY: &str = "gateway-secret-0123";
+
+fn config() -> Config {
+    let mut cfg = Config::default();
+    cfg.auth.gateway_token = Some(GATEWAY.into());
+    cfg
+}
+
+fn send(to: &str, id: &str) -> Request<Body> {
+    let body = json!({ "msg_id": id, "to": to, "kind": "k", "payload": [1, 2, 3] });
+    Request::post("/v1/send")
+        .header("content-type", "application/json")
+        .header("x-ron-gateway-token", GATEWAY)
+        .header("x-ron-tenant", "t")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+fn recv(topic: &str) -> Request<Body> {
+    Request::post("/v1/recv")
+        .header("content-type", "application/json")
+        .header("x-ron-gateway-token", GATEWAY)
+        .header("x-ron-tenant", "t")
+        .body(Body::from(json!({ "topic": topic }).to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn readiness_sheds_writes_first() {
+    let shutdown = Shutdown::new();
+    let app = router(build_state(&config(), shutdown.clone()).unwrap());
+    let get = |path: &str| Request::get(path).body(Body::empty()).unwrap();
+
+    assert_eq!(
+        app.clone().oneshot(get("/readyz")).await.unwrap().status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        app.clone()
+            .oneshot(send("t:q", "1"))
+            .await
+            .unwrap()
+            .status(),
+        StatusCode::OK
+    );
+
+    shutdown.trigger();
+    assert_eq!(
+        app.clone().oneshot(get("/readyz")).await.unwrap().status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(
+        app.clone().oneshot(get("/healthz")).await.unwrap().status(),
+        StatusCode::OK
+    );
+    let shed = app.clone().oneshot(send("t:q", "2")).await.unwrap();
+    assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(shed.headers()["retry-after"], "1");
+    // Reads keep draining what is already queued.
+    assert_eq!(
+        app.clone().oneshot(recv("t:q")).await.unwrap().status(),
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn saturated_shards_return_429_with_retry_after() {
+    let mut cfg = config();
+    cfg.mailbox.ready_shards = 1;
+    cfg.mailbox.shard_capacity = 2;
+    let app = router(build_state(&cfg, Shutdown::new()).unwrap());
+
+    for id in ["a", "b"] {
+        assert_eq!(
+            app.clone().oneshot(send("t:q", id)).await.unwrap().status(),
+            StatusCode::OK
+        );
+    }
+    let full = app.clone().oneshot(send("t:q", "c")).await.unwrap();
+    assert_eq!(full.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(full.headers()["retry-after"], "1");
+}
+
 #[test]
-fn readiness_sheds_writes_first() {
-    assert!(true);
+fn shipped_config_validates_and_bad_knobs_fail_closed() {
+    let text = include_str!("../configs/svc-mailbox.toml");
+    let cfg = Config::from_toml(text).unwrap();
+    cfg.validate().unwrap();
+
+    let mut bad = cfg.clone();
+    bad.mailbox.t_replay = bad.mailbox.default_visibility;
+    assert!(bad.validate().is_err());
+
+    let mut env = cfg;
+    env.apply_env(|k| (k == "SVCMBX_SHARDS").then(|| "0".to_string()))
+        .unwrap();
+    assert!(env.validate().is_err());
+    assert!(Config::from_toml("unknown_key = 1").is_err());
+
+    let mut short = Config::default();
+    short
+        .apply_env(|k| (k == "SVCMBX_GATEWAY_TOKEN").then(|| "short".to_string()))
+        .unwrap();
+    assert!(short.validate().is_err());
 }
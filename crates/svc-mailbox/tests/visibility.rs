@@ -1,5 +1,172 @@
-// Visibility lease property tests placeholder.
-#[test]
-fn visibility_props() {
-    assert!(true);
+//! Visibility leases: hidden while leased, reappear after the deadline, ACK is final, inflight is bounded.
+
+use std::time::Duration;
+
+use ron_proto::mailbox::Send;
+use svc_mailbox::{config::MailboxConfig, domain::RecvOptions, metrics::Metrics, Mailbox};
+
+const TOPIC: &str = "jobs";
+
+fn mailbox(cfg: MailboxConfig) -> Mailbox {
+    Mailbox::new(cfg, Metrics::new())
+}
+
+async fn put(mb: &Mailbox, n: usize) -> Vec<String> {
+    let mut ids = Vec::with_capacity(n);
+    for i in 0..n {
+        let sent = mb
+            .send(
+                "tenant",
+                Send {
+                    msg_id: format!("m{i}"),
+                    to: TOPIC.into(),
+                    kind: "job".into(),
+                    payload: vec![i as u8; 16],
+                    idempotency_key: None,
+                },
+            )
+            .await
+            .unwrap();
+        ids.push(sent.msg_id);
+    }
+    ids
+}
+
+fn lease(mb: &Mailbox, visibility: Duration) -> Vec<svc_mailbox::domain::Envelope> {
+    mb.recv(
+        TOPIC,
+        RecvOptions {
+            visibility: Some(visibility),
+            ..RecvOptions::default()
+        },
+    )
+    .unwrap()
+}
+
+#[tokio::test(start_paused = true)]
+async fn unacked_messages_reappear_after_their_deadline() {
+    let mb = mailbox(MailboxConfig::default());
+    let ids = put(&mb, 3).await;
+
+    let first = lease(&mb, Duration::from_secs(1));
+    assert_eq!(
+        first.iter().map(|e| e.msg_id.clone()).collect::<Vec<_>>(),
+        ids
+    );
+    assert!(first.iter().all(|e| e.attempt == 1));
+    assert!(lease(&mb, Duration::from_secs(1)).is_empty());
+    assert_eq!(mb.inflight(), 3);
+
+    mb.ack(&ids[1]).unwrap();
+    tokio::time::advance(Duration::from_millis(999)).await;
+    assert_eq!(mb.scan(), 0);
+    tokio::time::advance(Duration::from_millis(1)).await;
+    assert_eq!(mb.scan(), 2);
+    assert_eq!(mb.inflight(), 0);
+
+    // ACK is final: the acknowledged message never comes back; the others keep FIFO order.
+    let again = lease(&mb, Duration::from_secs(1));
+    assert_eq!(
+        again.iter().map(|e| e.msg_id.as_str()).collect::<Vec<_>>(),
+        [ids[0].as_str(), ids[2].as_str()]
+    );
+    assert!(again.iter().all(|e| e.attempt == 2));
+    assert_eq!(mb.ack(&ids[1]).unwrap_err().code(), "E_NOT_FOUND");
+    assert_eq!(mb.metrics().visibility_timeout_total.get(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn late_acks_after_expiry_are_rejected() {
+    let mb = mailbox(MailboxConfig::default());
+    let ids = put(&mb, 1).await;
+    lease(&mb, Duration::from_millis(250));
+    tokio::time::advance(Duration::from_millis(300)).await;
+    mb.scan();
+    assert_eq!(mb.ack(&ids[0]).unwrap_err().code(), "E_NOT_FOUND");
+    assert_eq!(lease(&mb, Duration::from_secs(1)).len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn nack_backs_off_before_redelivery() {
+    let mb = mailbox(MailboxConfig {
+        backoff_base: Duration::from_secs(1),
+        backoff_max: Duration::from_secs(1),
+        ..MailboxConfig::default()
+    });
+    let ids = put(&mb, 1).await;
+    lease(&mb, Duration::from_secs(30));
+    mb.nack(&ids[0], Some("transient".into())).unwrap();
+    assert_eq!(mb.inflight(), 0);
+    // Waiting out a backoff is not a consumer lease.
+    assert_eq!(mb.ack(&ids[0]).unwrap_err().code(), "E_NOT_FOUND");
+    assert_eq!(mb.nack(&ids[0], None).unwrap_err().code(), "E_NOT_FOUND");
+
+    tokio::time::advance(Duration::from_secs(1)).await;
+    mb.scan();
+    let again = lease(&mb, Duration::from_secs(30));
+    assert_eq!(again.len(), 1);
+    assert_eq!(again[0].attempt, 2);
+}
+
+#[tokio::test]
+async fn batches_respect_message_byte_and_inflight_limits() {
+    let mb = mailbox(MailboxConfig {
+        ready_shards: 1,
+        shard_capacity: 8,
+        global_inflight: 8,
+        max_bytes: 40,
+        ..MailboxConfig::default()
+    });
+    put(&mb, 8).await;
+
+    let two = mb
+        .recv(
+            TOPIC,
+            RecvOptions {
+                max_messages: Some(2),
+                ..RecvOptions::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(two.len(), 2);
+    // 16-byte payloads under a 40-byte budget.
+    assert_eq!(mb.recv(TOPIC, RecvOptions::default()).unwrap().len(), 2);
+    assert_eq!(
+        mb.recv(
+            TOPIC,
+            RecvOptions {
+                max_bytes: Some(1),
+                ..RecvOptions::default()
+            }
+        )
+        .unwrap()
+        .len(),
+        1,
+        "the first message is always returned"
+    );
+    let rest = mb.recv(TOPIC, RecvOptions::default()).unwrap();
+    assert_eq!(rest.len(), 2);
+    assert_eq!(mb.inflight(), 7);
+
+    mb.send(
+        "tenant",
+        Send {
+            msg_id: "overflow".into(),
+            to: "other".into(),
+            kind: "job".into(),
+            payload: vec![],
+            idempotency_key: None,
+        },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(mb.recv(TOPIC, RecvOptions::default()).unwrap().len(), 1);
+    let err = mb.recv(TOPIC, RecvOptions::default()).unwrap_err();
+    assert_eq!(err.code(), "E_SATURATED");
+
+    let bad = RecvOptions {
+        visibility: Some(Duration::from_millis(1)),
+        ..RecvOptions::default()
+    };
+    assert_eq!(mb.recv(TOPIC, bad).unwrap_err().code(), "E_SCHEMA");
 }
@@ -11,6 +11,12 @@ pub struct Config {
     pub cache: Cache,
     pub limits: Limits,
     pub security: Security,
+    #[serde(default)]
+    pub revocation: Revocation,
+    #[serde(default)]
+    pub kms: Kms,
+    #[serde(default)]
+    pub rotation: Rotation,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -51,14 +57,159 @@ pub struct Security {
     pub require_aud: bool,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Revocation {
+    /// Append-only JSONL log; `None` keeps revocations in memory only.
+    pub path: Option<String>,
+    /// How long a revoked KID stays listed; floored by `Config::kid_revocation_ttl_s` so the
+    /// entry outlives every token the KID signed and its verify-only grace window.
+    pub kid_ttl_s: u64,
+    /// SSE heartbeat for `/v1/revocations/stream`.
+    pub sse_heartbeat_ms: u64,
+    /// Bearer token required by `POST /v1/passport/revoke` (env `PASSPORT_OPERATOR_TOKEN`);
+    /// `None` disables the endpoint.
+    pub operator_token: Option<String>,
+}
+
+impl Default for Revocation {
+    fn default() -> Self {
+        Self {
+            path: None,
+            kid_ttl_s: 30 * 24 * 3600,
+            sse_heartbeat_ms: 15_000,
+            operator_token: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KmsBackend {
+    /// ron-kms in-memory keystore with staged rotation; keys die with the process (dev/test only).
+    #[serde(alias = "ron-kms")]
+    Memory,
+    /// ron-kms PKCS#11 keystore (feature `pkcs11`): keys live on the token, the ring in `state_path`.
+    Pkcs11,
+    /// In-process DevKms (feature `dev-kms`); rotation swaps the signer immediately.
+    Dev,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Kms {
+    pub backend: KmsBackend,
+    /// ron-kms key root tenant/purpose for issuer keys.
+    pub tenant: String,
+    pub purpose: String,
+    /// Key ring state (JSON), rewritten on every stage/promote; required by `pkcs11`.
+    pub state_path: Option<String>,
+    /// Existing KID to sign with on first boot (no state yet) instead of creating a key.
+    pub key_id: Option<String>,
+    /// `[kms.pkcs11]` module/slot/PIN env for `backend = "pkcs11"`.
+    pub pkcs11: Option<ron_kms::config::Pkcs11Config>,
+}
+
+impl Default for Kms {
+    fn default() -> Self {
+        Self {
+            backend: KmsBackend::Memory,
+            tenant: "svc-passport".into(),
+            purpose: "passport".into(),
+            state_path: None,
+            key_id: None,
+            pkcs11: None,
+        }
+    }
+}
+
+impl Kms {
+    /// Durability settings must match the backend: memory keys cannot outlive the process.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self.backend {
+            KmsBackend::Pkcs11 if self.pkcs11.is_none() => {
+                anyhow::bail!("kms.backend = \"pkcs11\" needs a [kms.pkcs11] section")
+            }
+            KmsBackend::Pkcs11 if self.state_path.is_none() => {
+                anyhow::bail!("kms.backend = \"pkcs11\" needs kms.state_path")
+            }
+            KmsBackend::Memory | KmsBackend::Dev
+                if self.state_path.is_some() || self.key_id.is_some() =>
+            {
+                anyhow::bail!(
+                    "kms.state_path/key_id need a durable keystore (backend = \"pkcs11\")"
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Rotation {
+    /// Rotate after the active key has signed this long; 0 = only via /admin/rotate.
+    pub max_age_s: u64,
+    /// Publish the next key this long before it starts signing (>= cache.jwks_ttl_s).
+    pub stage_lead_s: u64,
+    /// Keep a demoted key verify-only this long (floored at passport.max_ttl_s + clock_skew_s).
+    pub grace_s: u64,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self {
+            max_age_s: 7 * 24 * 3600,
+            stage_lead_s: 3600,
+            grace_s: 0,
+        }
+    }
+}
+
 impl Config {
+    /// Longest a passport can be honoured after issue: `max_ttl_s` plus clock skew.
+    pub fn token_lifetime_s(&self) -> u64 {
+        self.passport
+            .max_ttl_s
+            .saturating_add(self.passport.clock_skew_s.unsigned_abs())
+    }
+
+    /// Shortest a KID revocation may be listed: a token signed just before the revocation lives
+    /// `token_lifetime_s`, and the demoted key keeps verifying for the (floored) rotation grace.
+    pub fn min_kid_revocation_ttl_s(&self) -> u64 {
+        let lifetime = self.token_lifetime_s();
+        lifetime.saturating_add(self.rotation.grace_s.max(lifetime))
+    }
+
+    /// How long a revoked KID stays listed: `revocation.kid_ttl_s`, never below the minimum.
+    pub fn kid_revocation_ttl_s(&self) -> u64 {
+        self.revocation
+            .kid_ttl_s
+            .max(self.min_kid_revocation_ttl_s())
+    }
+
     pub fn load() -> anyhow::Result<Self> {
-        if let Ok(s) = env::var("PASSPORT_CONFIG") {
-            return Ok(toml::from_str(&s)?);
+        let mut cfg: Self = if let Ok(s) = env::var("PASSPORT_CONFIG") {
+            toml::from_str(&s)?
+        } else {
+            let path = env::var("PASSPORT_CONFIG_FILE")
+                .unwrap_or_else(|_| "crates/svc-passport/config/default.toml".to_string());
+            let text = fs::read_to_string(Path::new(&path))?;
+            toml::from_str(&text)?
+        };
+        // Secrets come from the environment rather than a checked-in file.
+        if let Ok(token) = env::var("PASSPORT_OPERATOR_TOKEN") {
+            cfg.revocation.operator_token = Some(token);
+        }
+        if cfg
+            .revocation
+            .operator_token
+            .as_deref()
+            .is_some_and(|t| t.len() < 16)
+        {
+            anyhow::bail!("revocation.operator_token must be at least 16 characters");
         }
-        let path = env::var("PASSPORT_CONFIG_FILE")
-            .unwrap_or_else(|_| "crates/svc-passport/config/default.toml".to_string());
-        let text = fs::read_to_string(Path::new(&path))?;
-        Ok(toml::from_str(&text)?)
+        cfg.kms.validate()?;
+        Ok(cfg)
     }
 }
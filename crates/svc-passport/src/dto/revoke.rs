@@ -1,8 +1,26 @@
-#![allow(dead_code)]
 use serde::Deserialize;
 
+/// `POST /v1/passport/revoke`: exactly one of `jti` (token id) or `kid`.
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct RevokeRequest {
-    pub jti: String,
+    #[serde(default)]
+    pub jti: Option<String>,
+    #[serde(default)]
+    pub kid: Option<String>,
+    /// Defaults to, and is capped at, `passport.max_ttl_s + clock_skew_s` for tokens and
+    /// `Config::kid_revocation_ttl_s` for KIDs; a KID's is also floored at
+    /// `Config::min_kid_revocation_ttl_s`.
+    #[serde(default)]
+    pub ttl_s: Option<u64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// `GET /v1/revocations?since=N`
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RevocationsQuery {
+    #[serde(default)]
+    pub since: u64,
 }
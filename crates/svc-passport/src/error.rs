@@ -26,6 +26,12 @@ pub enum Error {
     UnknownKid,
     #[error("scope_denied")]
     ScopeDenied,
+    #[error("revoked")]
+    Revoked,
+    #[error("unauthorized")]
+    Unauthorized,
+    #[error("forbidden")]
+    Forbidden,
     #[error("internal")]
     Internal(anyhow::Error),
 }
@@ -67,6 +73,24 @@ impl IntoResponse for Error {
                 StatusCode::FORBIDDEN,
                 false,
             ),
+            Error::Revoked => (
+                "revoked",
+                "token or key revoked",
+                StatusCode::FORBIDDEN,
+                false,
+            ),
+            Error::Unauthorized => (
+                "unauthorized",
+                "operator credentials required",
+                StatusCode::UNAUTHORIZED,
+                false,
+            ),
+            Error::Forbidden => (
+                "forbidden",
+                "endpoint disabled",
+                StatusCode::FORBIDDEN,
+                false,
+            ),
             Error::Internal(_) => (
                 "internal",
                 "internal error",
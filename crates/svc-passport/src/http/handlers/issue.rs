@@ -14,8 +14,10 @@ use base64::Engine as _; // base64 0.22 API for .encode()
 use blake3;
 
 use crate::{
+    error::Error,
     metrics::{OPS_TOTAL, OP_LATENCY},
     state::issuer::IssuerState,
+    util::hashing::token_id,
 };
 
 #[derive(serde::Serialize)]
@@ -114,7 +116,7 @@ pub async fn keys(
 
 /// POST /v1/passport/issue
 /// Body: arbitrary JSON payload to be signed
-/// Returns: Envelope { alg, kid, msg_b64, sig_b64, aud? }
+/// Returns: Envelope { alg, kid, jti, msg_b64, sig_b64, aud? } (`jti` = BLAKE3 hex of the signed bytes)
 pub async fn issue(
     Extension(issuer): Extension<Arc<IssuerState>>,
     Json(payload): Json<Value>,
@@ -140,13 +142,18 @@ pub async fn issue(
         ));
     }
 
-    // Sign
-    let (kid, sig) = issuer.sign(&bytes).await.map_err(|_| {
-        problem(
+    // Sign (rotates away from a revoked KID; refused only if the fresh key is revoked too)
+    let (kid, sig) = issuer.sign(&bytes).await.map_err(|e| match e {
+        Error::Revoked => problem(
+            StatusCode::CONFLICT,
+            "KidRevoked",
+            "Signing key is revoked; rotate before issuing",
+        ),
+        _ => problem(
             StatusCode::INTERNAL_SERVER_ERROR,
             "Internal",
             "Signer failed",
-        )
+        ),
     })?;
 
     // If require_aud=true, include default audience = passport.issuer
@@ -163,6 +170,7 @@ pub async fn issue(
     let mut env = json!({
         "alg": "Ed25519",
         "kid": kid,
+        "jti": token_id(&bytes),
         "sig_b64": base64::engine::general_purpose::STANDARD.encode(&sig),
         "msg_b64": base64::engine::general_purpose::STANDARD.encode(&bytes)
     });
@@ -1,3 +1,140 @@
-//! RO:WHAT — (stub) Revocation endpoint; Bronze stores nonce/jti in-memory with TTL.
-//! RO:WHY  — Demonstrate shape; Silver will back by CAS/index if needed.
-#![allow(dead_code)]
+//! RO:WHAT — Revocation plane: revoke a token id or KID, fetch the signed incremental list, stream changes.
+//! RO:WHY  — Leaked passports must stop verifying everywhere before they expire.
+//! RO:INTERACTS — state::issuer (store + signer), revocation::{store, list}, metrics.
+//! RO:INVARIANTS — revoking needs the operator bearer token; a TTL never exceeds the longest lifetime it
+//!                 can matter for, and a KID's never falls short of it; revocations are durable before the 200 and before any event; lists are
+//!                 signed Envelopes over the exact list JSON; SSE clients that lag get a `resync` event.
+
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use axum::{
+    extract::Query,
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+    Extension, Json,
+};
+use futures_util::stream::{self, Stream};
+
+use crate::{
+    dto::revoke::{RevocationsQuery, RevokeRequest},
+    error::Error,
+    metrics::OPS_TOTAL,
+    revocation::{RevocationStore, RevokedKind},
+    state::issuer::IssuerState,
+    util::time::now_unix_s,
+};
+
+/// Upper bound on operator-supplied revocation reasons.
+const MAX_REASON_BYTES: usize = 256;
+
+/// POST /v1/passport/revoke -> the (possibly pre-existing) revocation entry.
+///
+/// Needs `Authorization: Bearer <revocation.operator_token>`.
+pub async fn revoke(
+    Extension(issuer): Extension<Arc<IssuerState>>,
+    headers: HeaderMap,
+    Json(req): Json<RevokeRequest>,
+) -> Result<impl IntoResponse, Error> {
+    operator(&issuer, &headers)?;
+    // The default TTL is also the cap: a longer revocation outlives everything it could block.
+    // KIDs also have a floor: a shorter entry would lapse while the KID's tokens still verify.
+    let (kind, id, min_ttl, max_ttl) = match (req.jti, req.kid) {
+        (Some(jti), None) if is_token_id(&jti) => {
+            (RevokedKind::Token, jti, 0, issuer.cfg.token_lifetime_s())
+        }
+        (None, Some(kid)) if !kid.trim().is_empty() => (
+            RevokedKind::Kid,
+            kid,
+            issuer.cfg.min_kid_revocation_ttl_s(),
+            issuer.cfg.kid_revocation_ttl_s(),
+        ),
+        _ => return Err(Error::Malformed),
+    };
+    if req
+        .reason
+        .as_ref()
+        .is_some_and(|r| r.len() > MAX_REASON_BYTES)
+    {
+        return Err(Error::Malformed);
+    }
+    let ttl_s = req.ttl_s.map_or(max_ttl, |t| t.clamp(min_ttl, max_ttl));
+
+    let store = issuer.revocations.clone();
+    let reason = req.reason;
+    let entry =
+        tokio::task::spawn_blocking(move || store.revoke(kind, &id, ttl_s, reason, now_unix_s()))
+            .await
+            .map_err(|e| Error::Internal(e.into()))?
+            .map_err(|e| Error::Internal(e.into()))?;
+
+    OPS_TOTAL.with_label_values(&["revoke", "ok", "n/a"]).inc();
+    Ok(Json(entry))
+}
+
+/// GET /v1/revocations?since=N -> Envelope whose `msg_b64` is a `RevocationList`.
+pub async fn list(
+    Extension(issuer): Extension<Arc<IssuerState>>,
+    Query(query): Query<RevocationsQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let env = issuer.revocation_list(query.since).await?;
+    OPS_TOTAL
+        .with_label_values(&["revocations", "ok", "Ed25519"])
+        .inc();
+    Ok((
+        StatusCode::OK,
+        [(axum::http::header::CACHE_CONTROL, "no-cache")],
+        Json(env),
+    ))
+}
+
+/// GET /v1/revocations/stream -> SSE `revoked` events (data = entry JSON), `resync` when lagging.
+pub async fn stream(Extension(issuer): Extension<Arc<IssuerState>>) -> impl IntoResponse {
+    let heartbeat = Duration::from_millis(issuer.cfg.revocation.sse_heartbeat_ms.max(1));
+    Sse::new(revocation_events(issuer.revocations.clone()))
+        .keep_alive(KeepAlive::new().interval(heartbeat).text("heartbeat"))
+}
+
+fn revocation_events(
+    store: Arc<RevocationStore>,
+) -> impl Stream<Item = Result<Event, Infallible>> + Send {
+    // The store travels with the receiver so the bus sender outlives every subscriber.
+    let rx = store.bus().subscribe();
+    stream::unfold((rx, store), |(mut rx, store)| async move {
+        let event = match rx.recv().await {
+            Ok(entry) => Event::default()
+                .event("revoked")
+                .id(entry.version.to_string())
+                .data(serde_json::to_string(&entry).unwrap_or_else(|_| "{}".into())),
+            // Missed events: tell the client to refetch `/v1/revocations?since=<its version>`.
+            Err(_) => Event::default()
+                .event("resync")
+                .data(store.version().to_string()),
+        };
+        Some((Ok(event), (rx, store)))
+    })
+}
+
+/// Require `Authorization: Bearer <revocation.operator_token>`; no token configured disables revoking.
+fn operator(issuer: &IssuerState, headers: &HeaderMap) -> Result<(), Error> {
+    let Some(expected) = issuer.cfg.revocation.operator_token.as_deref() else {
+        return Err(Error::Forbidden);
+    };
+    let presented = headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or(Error::Unauthorized)?;
+    // Compare digests so the check does not leak how much of the token matched.
+    if blake3::hash(presented.as_bytes()) != blake3::hash(expected.as_bytes()) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn is_token_id(s: &str) -> bool {
+    s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
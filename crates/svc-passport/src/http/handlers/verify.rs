@@ -1,6 +1,7 @@
 //! RO:WHAT   Verify endpoints (single + batch) with alg/aud checks and batch/size limits.
 //! RO:WHY    Tighten negative paths per Beta criteria; stable, minimal error surface.
-//! RO:INVARS No secret leakage; constant-time verify in issuer; early rejects pre-crypto.
+//! RO:INVARS No secret leakage; constant-time verify in issuer; early rejects pre-crypto;
+//!           revoked token ids / KIDs verify as `false` even with a valid signature.
 
 use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
 use base64::engine::general_purpose::STANDARD as B64;
@@ -88,6 +89,14 @@ fn kid_check(kid: &str) -> Result<(), (StatusCode, Json<Problem<'static>>)> {
     Ok(())
 }
 
+fn outcome(ok: bool, revoked: bool) -> &'static str {
+    match (ok, revoked) {
+        (true, false) => "ok",
+        (true, true) => "revoked",
+        (false, _) => "fail",
+    }
+}
+
 pub async fn verify(
     Extension(issuer): Extension<Arc<IssuerState>>,
     Json(env): Json<Envelope>,
@@ -128,11 +137,12 @@ pub async fn verify(
             "Invalid envelope or signature",
         )
     })?;
+    let revoked = ok && issuer.is_revoked(&env.kid, &msg);
 
     OPS_TOTAL
-        .with_label_values(&["verify", if ok { "ok" } else { "fail" }, "Ed25519"])
+        .with_label_values(&["verify", outcome(ok, revoked), "Ed25519"])
         .inc();
-    Ok(Json(json!(ok)))
+    Ok(Json(json!(ok && !revoked)))
 }
 
 pub async fn verify_batch(
@@ -197,7 +207,13 @@ pub async fn verify_batch(
                 "Invalid envelope or signature",
             )
         })?;
-        results.push(ok);
+        let revoked = ok && issuer.is_revoked(&env.kid, &msg);
+        if revoked {
+            OPS_TOTAL
+                .with_label_values(&["verify_batch_item", "revoked", "Ed25519"])
+                .inc();
+        }
+        results.push(ok && !revoked);
     }
 
     let any_ok = results.iter().any(|&v| v);
@@ -1,7 +1,7 @@
 // crates/svc-passport/src/http/router.rs
 //! RO:WHAT — HTTP router assembly for svc-passport.
 //! RO:WHY — Axum 0.7 serve accepts Router<()> directly; shared state is carried via typed Extension layers.
-//! RO:INTERACTS — issue/verify/profile handlers, DevKms, IssuerState, UsernameClaimStore, metrics exporter.
+//! RO:INTERACTS — issue/verify/revoke/profile handlers, kms::from_config (RonKms/DevKms), IssuerState, UsernameClaimStore, metrics exporter.
 //! RO:INVARIANTS — body caps on mutating/hot routes; no wallet/ledger mutation in profile routes.
 //! RO:METRICS — /metrics exporter plus handler-level passport counters where already present.
 //! RO:CONFIG — PASSPORT_MAX_MSG_BYTES, PASSPORT_VERIFY_CONCURRENCY, PASSPORT_VERIFY_BATCH_CONCURRENCY.
@@ -9,11 +9,7 @@
 //! RO:TEST — tests/handlers.rs, tests/profile_routes.rs, tests/limits.rs, tests/audience_alg.rs.
 
 use crate::{
-    config::Config,
-    health::Health,
-    kms::client::{DevKms, KmsClient},
-    metrics,
-    profile::UsernameClaimStore,
+    config::Config, health::Health, kms, metrics, profile::UsernameClaimStore,
     state::issuer::IssuerState,
 };
 use axum::{
@@ -25,14 +21,14 @@ use axum::{
 use std::sync::Arc;
 use tower::limit::ConcurrencyLimitLayer;
 
-use crate::http::handlers::{issue, profile, verify};
+use crate::http::handlers::{issue, profile, revoke, verify};
 
 /// Build the svc-passport HTTP router.
 ///
 /// The router remains unit-state. Internal shared state is injected with typed
 /// `Extension(Arc<_>)` layers so Axum 0.7 service bootstrap stays simple.
 pub fn build_router(cfg: Config, _health: Health) -> Router {
-    let kms: Arc<dyn KmsClient> = Arc::new(DevKms::new());
+    let kms = kms::from_config(&cfg).expect("kms backend");
     let issuer = Arc::new(IssuerState::new(cfg, kms));
     let profile_store = Arc::new(UsernameClaimStore::new());
 
@@ -66,6 +62,13 @@ pub fn build_router(cfg: Config, _health: Health) -> Router {
                 .route_layer(ConcurrencyLimitLayer::new(verify_batch_conc)),
         )
         .route("/v1/keys", get(issue::keys))
+        // Revocation plane (signed incremental list + SSE change feed).
+        .route(
+            "/v1/passport/revoke",
+            post(revoke::revoke).route_layer(DefaultBodyLimit::max(max_body_bytes)),
+        )
+        .route("/v1/revocations", get(revoke::list))
+        .route("/v1/revocations/stream", get(revoke::stream))
         // NEXT_LEVEL Phase 3 local public profile API.
         .route("/v1/passport/profile/_debug", get(profile::profile_debug))
         .route(
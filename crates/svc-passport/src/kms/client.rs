@@ -1,5 +1,5 @@
-//! RO:WHAT — KmsClient trait; DevKms (ed25519-dalek) for dev/tests.
-//! RO:INVARIANTS — versioned KID "ed25519/default/v{n}"
+//! RO:WHAT — KmsClient trait; DevKms (ed25519-dalek) for dev/tests. Production: kms::ron_kms::RonKms.
+//! RO:INVARIANTS — DevKms: versioned KID "ed25519/default/v{n}"
 //! RO:NOTES — DevKms is feature-gated behind `dev-kms` and uses rand_core::OsRng.
 
 use async_trait::async_trait;
@@ -115,6 +115,3 @@ mod dev {
 
 #[cfg(feature = "dev-kms")]
 pub use dev::DevKms;
-
-#[cfg(not(feature = "dev-kms"))]
-compile_error!("Enable feature `dev-kms` or wire a real ron-kms client here.");
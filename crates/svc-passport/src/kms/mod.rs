@@ -1,6 +1,55 @@
-//! RO:WHAT — KMS boundary (trait) + local dev implementation.
-//! RO:WHY  — Swap to ron-kms without touching service code.
+//! RO:WHAT — KMS boundary (trait) + ron-kms client with staged rotation + local dev implementation.
+//! RO:WHY  — Swap backends via `[kms].backend` without touching service code.
+//! RO:NOTES — `memory` keys (and so every passport) die with the process; `pkcs11` keeps keys on the
+//!            token and the key ring in `kms.state_path`, so restarts keep verifying.
 
 pub mod client;
 pub mod keyslot;
+pub mod ron_kms;
 pub mod rotation;
+
+use crate::{
+    config::{Config, KmsBackend},
+    util::time::now_unix_s,
+};
+use client::KmsClient;
+use rotation::RotationPolicy;
+use std::sync::Arc;
+
+/// Build the configured KMS client.
+pub fn from_config(cfg: &Config) -> anyhow::Result<Arc<dyn KmsClient>> {
+    match cfg.kms.backend {
+        KmsBackend::Memory => {
+            tracing::warn!("kms.backend = \"memory\": issuer keys are lost on restart");
+            Ok(Arc::new(ron_kms::RonKms::new(
+                ::ron_kms::memory_keystore(),
+                cfg.kms.tenant.clone(),
+                cfg.kms.purpose.clone(),
+                RotationPolicy::from_config(cfg),
+                now_unix_s(),
+            )?))
+        }
+        #[cfg(feature = "pkcs11")]
+        KmsBackend::Pkcs11 => {
+            cfg.kms.validate()?;
+            let (Some(hsm), Some(state)) = (&cfg.kms.pkcs11, &cfg.kms.state_path) else {
+                anyhow::bail!("kms.backend = \"pkcs11\" needs [kms.pkcs11] and kms.state_path");
+            };
+            Ok(Arc::new(ron_kms::RonKms::open(
+                ::ron_kms::backends::Pkcs11Keystore::open(hsm)?,
+                cfg.kms.tenant.clone(),
+                cfg.kms.purpose.clone(),
+                RotationPolicy::from_config(cfg),
+                state,
+                cfg.kms.key_id.as_deref(),
+                now_unix_s(),
+            )?))
+        }
+        #[cfg(not(feature = "pkcs11"))]
+        KmsBackend::Pkcs11 => anyhow::bail!("kms.backend = \"pkcs11\" requires feature `pkcs11`"),
+        #[cfg(feature = "dev-kms")]
+        KmsBackend::Dev => Ok(Arc::new(client::DevKms::new())),
+        #[cfg(not(feature = "dev-kms"))]
+        KmsBackend::Dev => anyhow::bail!("kms.backend = \"dev\" requires feature `dev-kms`"),
+    }
+}
@@ -0,0 +1,317 @@
+//! RO:WHAT — `RonKms`: production `KmsClient` over ron-kms `Keystore`/`Signer`/`Verifier` with staged rotation.
+//! RO:WHY  — Private keys stay in ron-kms custody; the service only tracks which KID plays which role.
+//! RO:INTERACTS — kms::rotation (KeyRing + RotationPolicy), ron_kms backends (memory; `Pkcs11Keystore` for HSM custody).
+//! RO:INVARIANTS — one key root per generation (ron-kms `rotate` swaps a root's signer in place, which
+//!                 cannot be staged); only `KeyRing::accepts` KIDs verify; the ring lock is never held
+//!                 across a KMS call; ring changes are serialized, so concurrent ticks create one key.
+//! RO:NOTES — The policy is applied lazily on sign/public_keys: no background task, and a staged key is
+//!            always visible in /v1/keys for at least `stage_lead_s` before it signs.
+//!            `open` persists the ring to a JSON state file after every change, so a durable keystore
+//!            (PKCS#11) keeps signing and verifying with the same KIDs across restarts.
+
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use parking_lot::{Mutex, RwLock};
+use ron_kms::{traits::PubkeyProvider, Alg, KeyId, Keystore, KmsError, Signer, Verifier};
+use serde_json::{json, Value};
+
+use super::{
+    client::KmsClient,
+    rotation::{KeyRing, RotationPolicy, RotationStep},
+};
+use crate::{metrics::OPS_TOTAL, util::time::now_unix_s};
+
+pub struct RonKms<K> {
+    store: K,
+    tenant: String,
+    purpose: String,
+    policy: RotationPolicy,
+    ring: RwLock<KeyRing<KeyId>>,
+    /// Held by `tick_at`/`rotate_at` from deciding to create a key until the ring has it; signers
+    /// only take `ring`, so they never wait on key generation.
+    changing: Mutex<()>,
+    /// Where the ring is saved after each change; `None` keeps it in memory only.
+    state: Option<PathBuf>,
+}
+
+impl<K> RonKms<K>
+where
+    K: Keystore + Signer + Verifier + PubkeyProvider,
+{
+    /// Create the first issuer key under `tenant`/`purpose` and make it active at `now`.
+    pub fn new(
+        store: K,
+        tenant: impl Into<String>,
+        purpose: impl Into<String>,
+        policy: RotationPolicy,
+        now: u64,
+    ) -> anyhow::Result<Self> {
+        let (tenant, purpose) = (tenant.into(), purpose.into());
+        let kid = store.create_ed25519(&tenant, &purpose)?;
+        Ok(Self {
+            store,
+            tenant,
+            purpose,
+            policy,
+            ring: RwLock::new(KeyRing::new(kid, now)),
+            changing: Mutex::new(()),
+            state: None,
+        })
+    }
+
+    /// Resume the ring saved at `state`, or start one on first boot and save it there.
+    ///
+    /// With no saved ring, `key_id` adopts an existing key root (e.g. one provisioned on the HSM)
+    /// as the active signer; otherwise a fresh key is created. Every KID the ring names must still
+    /// be held by `store`.
+    pub fn open(
+        store: K,
+        tenant: impl Into<String>,
+        purpose: impl Into<String>,
+        policy: RotationPolicy,
+        state: impl Into<PathBuf>,
+        key_id: Option<&str>,
+        now: u64,
+    ) -> anyhow::Result<Self> {
+        let (tenant, purpose, state) = (tenant.into(), purpose.into(), state.into());
+        let ring = match fs::read(&state) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .with_context(|| format!("kms state {} is corrupt", state.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let kid = match key_id {
+                    Some(kid) => kid
+                        .parse()
+                        .map_err(|e| anyhow::anyhow!("kms.key_id {kid:?}: {e}"))?,
+                    None => store.create_ed25519(&tenant, &purpose)?,
+                };
+                KeyRing::new(kid, now)
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("reading kms state {}", state.display()))
+            }
+        };
+        for kid in kids(&ring) {
+            store
+                .verifying_key_bytes(kid)
+                .with_context(|| format!("keystore does not hold {kid}"))?;
+        }
+        save(&state, &ring)?;
+        Ok(Self {
+            store,
+            tenant,
+            purpose,
+            policy,
+            ring: RwLock::new(ring),
+            changing: Mutex::new(()),
+            state: Some(state),
+        })
+    }
+
+    pub fn policy(&self) -> RotationPolicy {
+        self.policy
+    }
+
+    /// Snapshot of the key timeline.
+    pub fn ring(&self) -> KeyRing<KeyId> {
+        self.ring.read().clone()
+    }
+
+    /// Apply the rotation policy at `now`: drop expired verify-only keys, then stage or promote.
+    pub fn tick_at(&self, now: u64) -> anyhow::Result<RotationStep> {
+        if self.is_idle_at(now) {
+            return Ok(RotationStep::Idle);
+        }
+
+        // Re-decide once no other tick/rotate is mid-change: it may have acted already, and a key
+        // created here is then always the one staged (never an orphan in the keystore).
+        let _changing = self.changing.lock();
+        let step = self.policy.decide(&self.ring.read(), now);
+        let staged = match step {
+            RotationStep::Stage => Some(self.store.create_ed25519(&self.tenant, &self.purpose)?),
+            _ => None,
+        };
+
+        let mut ring = self.ring.write();
+        ring.retire_expired(now);
+        let step = match (step, staged) {
+            (RotationStep::Stage, Some(kid)) => {
+                ring.stage(kid, now);
+                RotationStep::Stage
+            }
+            (RotationStep::Promote, _) => {
+                ring.promote(now, self.policy.grace_s);
+                RotationStep::Promote
+            }
+            _ => RotationStep::Idle,
+        };
+        self.persist(&ring)?;
+        drop(ring);
+
+        match step {
+            RotationStep::Stage => OPS_TOTAL
+                .with_label_values(&["rotate_stage", "ok", "Ed25519"])
+                .inc(),
+            RotationStep::Promote => OPS_TOTAL
+                .with_label_values(&["rotate_promote", "ok", "Ed25519"])
+                .inc(),
+            RotationStep::Idle => {}
+        }
+        Ok(step)
+    }
+
+    /// Nothing to retire, stage or promote at `now` (the common case; read lock only).
+    fn is_idle_at(&self, now: u64) -> bool {
+        let ring = self.ring.read();
+        !ring.previous().iter().any(|r| r.until_s <= now)
+            && self.policy.decide(&ring, now) == RotationStep::Idle
+    }
+
+    /// Rotate now (admin plane): promote the staged key, staging a fresh one first if none is.
+    ///
+    /// Skips the publication lead, so use it for compromise response; the old key still gets the
+    /// full grace window unless its KID is revoked.
+    pub fn rotate_at(&self, now: u64) -> anyhow::Result<KeyId> {
+        let _changing = self.changing.lock();
+        let fresh = if self.ring.read().next().is_none() {
+            Some(self.store.create_ed25519(&self.tenant, &self.purpose)?)
+        } else {
+            None
+        };
+        let mut ring = self.ring.write();
+        if let Some(kid) = fresh {
+            ring.stage(kid, now);
+        }
+        ring.promote(now, self.policy.grace_s);
+        self.persist(&ring)?;
+        let kid = ring.active().kid.clone();
+        drop(ring);
+
+        OPS_TOTAL
+            .with_label_values(&["rotate_promote", "ok", "Ed25519"])
+            .inc();
+        Ok(kid)
+    }
+
+    /// Save `ring` if this instance is durable; called under the ring's write lock so saves keep order.
+    fn persist(&self, ring: &KeyRing<KeyId>) -> anyhow::Result<()> {
+        match &self.state {
+            Some(path) => save(path, ring),
+            None => Ok(()),
+        }
+    }
+
+    /// `KmsClient::sign` at an explicit clock (tests, replay).
+    pub fn sign_at(&self, msg: &[u8], now: u64) -> anyhow::Result<(String, Vec<u8>)> {
+        self.tick_at(now)?;
+        let kid = self.ring.read().active().kid.clone();
+        let sig = self.store.sign(&kid, msg)?;
+        Ok((kid.to_string(), sig))
+    }
+
+    pub fn verify_at(&self, kid: &str, msg: &[u8], sig: &[u8], now: u64) -> anyhow::Result<bool> {
+        let kid: KeyId = kid
+            .parse()
+            .map_err(|e| anyhow::anyhow!("unknown kid: {e}"))?;
+        if !self.ring.read().accepts(&kid, now) {
+            anyhow::bail!("unknown kid");
+        }
+        match self.store.verify(&kid, msg, sig) {
+            Ok(ok) => Ok(ok),
+            // Malformed signature bytes are a plain verification failure, as with DevKms.
+            Err(KmsError::VerifyFailed) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn public_keys_at(&self, now: u64) -> anyhow::Result<Value> {
+        self.tick_at(now)?;
+        let ring = self.ring();
+        let keys = ring
+            .published(now)
+            .into_iter()
+            .map(|(kid, status)| {
+                let vk = self.store.verifying_key_bytes(kid)?;
+                Ok(json!({
+                    "kid": kid.to_string(),
+                    "vk_b64": URL_SAFE_NO_PAD.encode(vk),
+                    "alg": Alg::Ed25519.to_string(),
+                    "status": status,
+                }))
+            })
+            .collect::<Result<Vec<_>, KmsError>>()?;
+        Ok(json!({
+            "alg": "Ed25519",
+            "current": ring.active().kid.to_string(),
+            "next": ring.next().map(|n| n.kid.to_string()),
+            "keys": keys,
+        }))
+    }
+}
+
+/// Every KID the ring names, in any role.
+fn kids(ring: &KeyRing<KeyId>) -> impl Iterator<Item = &KeyId> {
+    std::iter::once(&ring.active().kid)
+        .chain(ring.next().map(|n| &n.kid))
+        .chain(ring.previous().iter().map(|r| &r.kid))
+}
+
+/// Write `ring` to `path` atomically (temp file, fsync, rename).
+fn save(path: &Path, ring: &KeyRing<KeyId>) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    {
+        let mut f = File::create(&tmp)?;
+        f.write_all(&serde_json::to_vec(ring)?)?;
+        f.sync_all()?;
+    }
+    fs::rename(&tmp, path).with_context(|| format!("saving kms state {}", path.display()))
+}
+
+#[async_trait]
+impl<K> KmsClient for RonKms<K>
+where
+    K: Keystore + Signer + Verifier + PubkeyProvider + 'static,
+{
+    async fn sign(&self, msg: &[u8]) -> anyhow::Result<(String, Vec<u8>)> {
+        self.sign_at(msg, now_unix_s())
+    }
+
+    async fn verify(&self, kid: &str, msg: &[u8], sig: &[u8]) -> anyhow::Result<bool> {
+        self.verify_at(kid, msg, sig, now_unix_s())
+    }
+
+    async fn public_keys(&self) -> anyhow::Result<Value> {
+        self.public_keys_at(now_unix_s())
+    }
+
+    async fn rotate(&self) -> anyhow::Result<String> {
+        Ok(self.rotate_at(now_unix_s())?.to_string())
+    }
+
+    async fn attest(&self) -> anyhow::Result<Value> {
+        let mut view = self.public_keys().await?;
+        let ring = self.ring();
+        view["backend"] = json!("ron-kms");
+        view["policy"] = json!(self.policy);
+        view["timeline"] = json!({
+            "active_since_s": ring.active().since_s,
+            "next_since_s": ring.next().map(|n| n.since_s),
+            "verify_only": ring
+                .previous()
+                .iter()
+                .map(|r| json!({ "kid": r.kid.to_string(), "until_s": r.until_s }))
+                .collect::<Vec<_>>(),
+        });
+        Ok(view)
+    }
+}
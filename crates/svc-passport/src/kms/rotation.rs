@@ -1,2 +1,170 @@
-//! RO:WHAT — Rotation helpers (admin plane).
-#![allow(dead_code)]
+//! RO:WHAT — Staged key rotation: a policy plus the key ring it drives (next → active → verify-only).
+//! RO:WHY  — Verifiers must see a key in /v1/keys before it signs, and keep the old key until the
+//!           passports it signed have expired; swapping the signer in place breaks both.
+//! RO:INVARIANTS — exactly one active signer; a staged key never signs or verifies before
+//!                 promotion; demoted keys verify (never sign) until `until_s`, then drop out.
+//! RO:CONFIG — `[rotation]` max_age_s / stage_lead_s / grace_s (grace floored at max TTL + skew).
+
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+
+/// When to stage, promote and retire issuer keys. All values in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RotationPolicy {
+    /// Rotate once the active key has signed this long; `0` = manual (`/admin/rotate`) only.
+    pub max_age_s: u64,
+    /// How long the next key is published before it may sign.
+    pub stage_lead_s: u64,
+    /// How long a demoted key keeps verifying.
+    pub grace_s: u64,
+}
+
+impl RotationPolicy {
+    /// Policy from `[rotation]`; the grace window never undercuts the longest passport TTL.
+    pub fn from_config(cfg: &Config) -> Self {
+        Self {
+            max_age_s: cfg.rotation.max_age_s,
+            stage_lead_s: cfg.rotation.stage_lead_s,
+            grace_s: cfg.rotation.grace_s.max(cfg.token_lifetime_s()),
+        }
+    }
+
+    /// What the ring should do next at `now`.
+    pub fn decide<K>(&self, ring: &KeyRing<K>, now: u64) -> RotationStep {
+        if self.max_age_s == 0 {
+            return RotationStep::Idle;
+        }
+        let due_at = ring.active.since_s.saturating_add(self.max_age_s);
+        match &ring.next {
+            None if now.saturating_add(self.stage_lead_s) >= due_at => RotationStep::Stage,
+            Some(next)
+                if now >= due_at && now >= next.since_s.saturating_add(self.stage_lead_s) =>
+            {
+                RotationStep::Promote
+            }
+            _ => RotationStep::Idle,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationStep {
+    Idle,
+    /// Create the next key and publish it (verify-only for clients, not yet signing).
+    Stage,
+    /// Make the staged key the signer; demote the active key to verify-only.
+    Promote,
+}
+
+/// A key and the moment it entered its current role.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Slot<K> {
+    pub kid: K,
+    pub since_s: u64,
+}
+
+/// A demoted key, accepted for verification until `until_s`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Retiring<K> {
+    pub kid: K,
+    pub until_s: u64,
+}
+
+/// Role of a key in the ring, as published.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyStatus {
+    Active,
+    Next,
+    VerifyOnly,
+}
+
+/// The issuer's key timeline. Pure bookkeeping: key material lives in the KMS.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRing<K> {
+    active: Slot<K>,
+    next: Option<Slot<K>>,
+    previous: Vec<Retiring<K>>,
+}
+
+impl<K: Clone + PartialEq> KeyRing<K> {
+    pub fn new(active: K, now: u64) -> Self {
+        Self {
+            active: Slot {
+                kid: active,
+                since_s: now,
+            },
+            next: None,
+            previous: Vec::new(),
+        }
+    }
+
+    pub fn active(&self) -> &Slot<K> {
+        &self.active
+    }
+
+    pub fn next(&self) -> Option<&Slot<K>> {
+        self.next.as_ref()
+    }
+
+    pub fn previous(&self) -> &[Retiring<K>] {
+        &self.previous
+    }
+
+    /// Publish `kid` as the next signer. Returns a previously staged key it displaced, if any.
+    pub fn stage(&mut self, kid: K, now: u64) -> Option<K> {
+        self.next
+            .replace(Slot { kid, since_s: now })
+            .map(|old| old.kid)
+    }
+
+    /// Promote the staged key; the active key verifies for `grace_s` more. `false` if none staged.
+    pub fn promote(&mut self, now: u64, grace_s: u64) -> bool {
+        let Some(next) = self.next.take() else {
+            return false;
+        };
+        let old = std::mem::replace(
+            &mut self.active,
+            Slot {
+                kid: next.kid,
+                since_s: now,
+            },
+        );
+        self.previous.push(Retiring {
+            kid: old.kid,
+            until_s: now.saturating_add(grace_s),
+        });
+        true
+    }
+
+    /// Drop demoted keys whose grace window has closed; returns them.
+    pub fn retire_expired(&mut self, now: u64) -> Vec<K> {
+        let (gone, kept) = std::mem::take(&mut self.previous)
+            .into_iter()
+            .partition(|r| r.until_s <= now);
+        self.previous = kept;
+        gone.into_iter().map(|r: Retiring<K>| r.kid).collect()
+    }
+
+    /// Keys to publish in /v1/keys, with their role.
+    pub fn published(&self, now: u64) -> Vec<(&K, KeyStatus)> {
+        let mut out = vec![(&self.active.kid, KeyStatus::Active)];
+        out.extend(self.next.iter().map(|n| (&n.kid, KeyStatus::Next)));
+        out.extend(
+            self.previous
+                .iter()
+                .filter(|r| r.until_s > now)
+                .map(|r| (&r.kid, KeyStatus::VerifyOnly)),
+        );
+        out
+    }
+
+    /// Whether signatures by `kid` are accepted: the active key or a demoted key within grace.
+    pub fn accepts(&self, kid: &K, now: u64) -> bool {
+        self.active.kid == *kid
+            || self
+                .previous
+                .iter()
+                .any(|r| r.kid == *kid && r.until_s > now)
+    }
+}
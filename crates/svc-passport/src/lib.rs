@@ -1,6 +1,6 @@
 //! RO:WHAT — Library root for svc-passport: issue/verify Ed25519 passports plus public profile claim helpers.
 //! RO:WHY  — P3 Identity & Keys; Concerns: SEC/RES/PERF/GOV. Short-TTL capability tokens and username/profile contracts.
-//! RO:INTERACTS — http::handlers, token::{encode,macaroon}, kms::client, state::issuer, policy::eval, profile
+//! RO:INTERACTS — http::handlers, token::{encode,macaroon}, kms::client, state::issuer, policy::eval, profile, revocation
 //! RO:INVARIANTS — strict Ed25519 capability envelope path; deterministic profile claims; no locks across .await
 //! RO:METRICS — passport_ops_total, passport_failures_total, passport_op_latency_seconds, passport_batch_len
 //! RO:CONFIG — see config.rs (ttl, batch, caps); /metrics + /healthz + /readyz via service HTTP surfaces
@@ -18,6 +18,7 @@ pub mod kms;
 pub mod metrics;
 pub mod policy;
 pub mod profile;
+pub mod revocation;
 pub mod state;
 pub mod telemetry;
 pub mod token;
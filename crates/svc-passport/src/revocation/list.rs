@@ -0,0 +1,34 @@
+//! RO:WHAT — Versioned, incremental revocation list body (signed by the issuer as an Envelope).
+//! RO:WHY  — Verifiers poll `since=<their version>` and only receive what changed; the signature
+//!           lets them accept lists relayed through caches or the gateway.
+//! RO:INTERACTS — revocation::store (source), http::handlers::revoke (signs via IssuerState), omnigate::auth::revocation.
+//! RO:INVARIANTS — `version` >= every entry's version; `since` echoes the request; canonical JSON bytes are what is signed.
+
+use super::store::{RevocationEntry, RevocationStore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RevocationList {
+    /// `passport.issuer` of the service that produced the list.
+    pub issuer: String,
+    /// Store version at generation time; pass it back as `since` next time.
+    pub version: u64,
+    pub since: u64,
+    pub generated_at_s: u64,
+    /// Unexpired revocations with `version > since`, oldest first.
+    pub entries: Vec<RevocationEntry>,
+}
+
+impl RevocationList {
+    pub fn from_store(issuer: &str, store: &RevocationStore, since: u64, now_s: u64) -> Self {
+        let (version, entries) = store.since(since, now_s);
+        Self {
+            issuer: issuer.to_owned(),
+            version,
+            since,
+            generated_at_s: now_s,
+            entries,
+        }
+    }
+}
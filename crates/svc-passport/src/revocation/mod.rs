@@ -0,0 +1,11 @@
+//! RO:WHAT — Durable revocation of passports (token ids) and signing keys (KIDs), with TTLs.
+//! RO:WHY  — A leaked passport must stop verifying before it expires; verifiers elsewhere must learn fast.
+//! RO:INTERACTS — state::issuer (owns the store), http::handlers::{revoke, verify, issue}, ron_kernel::Bus.
+//! RO:INVARIANTS — append-then-publish (never announce an unpersisted revocation); versions strictly increase;
+//!                 entries outlive the longest token they can affect and are dropped only after expiry.
+
+pub mod list;
+pub mod store;
+
+pub use list::RevocationList;
+pub use store::{RevocationEntry, RevocationStore, RevokedKind};
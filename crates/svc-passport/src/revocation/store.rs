@@ -0,0 +1,250 @@
+//! RO:WHAT — RevocationStore: live revocations in memory, persisted to an append-only JSONL file.
+//! RO:WHY  — Survive restarts without a database; one fsynced line per revocation, compacted on open.
+//! RO:INTERACTS — revocation::list (incremental views), ron_kernel::Bus (change events for SSE/in-proc).
+//! RO:INVARIANTS — a revocation is durable before it is visible or published; expired entries never
+//!                 resurrect; versions never go backwards, even once every entry has expired (compaction
+//!                 writes a `HighWater` line first); a torn trailing line (crash mid-append) is ignored,
+//!                 any other bad line is an error.
+
+use parking_lot::Mutex;
+use ron_kernel::Bus;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// What a revocation applies to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum RevokedKind {
+    /// One passport, by token id (`util::hashing::token_id` of its signed bytes).
+    Token,
+    /// Every passport signed by a KID.
+    Kid,
+}
+
+/// One revocation, as persisted, listed and published.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RevocationEntry {
+    /// Store-wide sequence number; strictly increasing.
+    pub version: u64,
+    pub kind: RevokedKind,
+    pub id: String,
+    pub revoked_at_s: u64,
+    /// After this instant the entry is dropped (the revoked thing can no longer verify anyway).
+    pub expires_at_s: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// First line of a compacted log: the version counter, which outlives the entries it numbered.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HighWater {
+    high_water: u64,
+}
+
+/// One line of the log.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Line {
+    Entry(RevocationEntry),
+    HighWater(HighWater),
+}
+
+struct Inner {
+    version: u64,
+    live: HashMap<(RevokedKind, String), RevocationEntry>,
+    file: Option<File>,
+}
+
+/// Revoked token ids and KIDs. Cheap to query; writes append one line and fsync.
+pub struct RevocationStore {
+    path: Option<PathBuf>,
+    inner: Mutex<Inner>,
+    bus: Bus<RevocationEntry>,
+}
+
+impl RevocationStore {
+    /// Volatile store (tests, amnesia mode): same semantics, nothing survives a restart.
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            inner: Mutex::new(Inner {
+                version: 0,
+                live: HashMap::new(),
+                file: None,
+            }),
+            bus: Bus::new(),
+        }
+    }
+
+    /// Open (or create) the log at `path`, replay it, drop expired entries and compact.
+    pub fn open(path: impl AsRef<Path>, now_s: u64) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let text = match fs::read_to_string(&path) {
+            Ok(t) => t,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let mut version = 0;
+        let mut live: HashMap<(RevokedKind, String), RevocationEntry> = HashMap::new();
+        let complete = text.ends_with('\n');
+        let lines: Vec<&str> = text.lines().collect();
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = match serde_json::from_str(line) {
+                Ok(Line::Entry(e)) => e,
+                Ok(Line::HighWater(hw)) => {
+                    version = version.max(hw.high_water);
+                    continue;
+                }
+                Err(_) if i + 1 == lines.len() && !complete => break, // torn append
+                Err(e) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{} line {}: {e}", path.display(), i + 1),
+                    ))
+                }
+            };
+            version = version.max(entry.version);
+            if entry.expires_at_s > now_s {
+                merge(&mut live, entry);
+            }
+        }
+
+        let mut entries: Vec<&RevocationEntry> = live.values().collect();
+        entries.sort_by_key(|e| e.version);
+        let tmp = path.with_extension("compact");
+        {
+            let mut f = File::create(&tmp)?;
+            writeln!(
+                f,
+                "{}",
+                serde_json::to_string(&HighWater {
+                    high_water: version
+                })?
+            )?;
+            for e in entries {
+                writeln!(f, "{}", serde_json::to_string(e)?)?;
+            }
+            f.sync_all()?;
+        }
+        fs::rename(&tmp, &path)?;
+        let file = OpenOptions::new().append(true).open(&path)?;
+
+        Ok(Self {
+            path: Some(path),
+            inner: Mutex::new(Inner {
+                version,
+                live,
+                file: Some(file),
+            }),
+            bus: Bus::new(),
+        })
+    }
+
+    /// Backing file, if durable.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Revoke `id` until `now_s + ttl_s`. Idempotent: an existing revocation that already lasts
+    /// at least as long is returned unchanged (no new version, no event).
+    pub fn revoke(
+        &self,
+        kind: RevokedKind,
+        id: &str,
+        ttl_s: u64,
+        reason: Option<String>,
+        now_s: u64,
+    ) -> io::Result<RevocationEntry> {
+        let expires_at_s = now_s.saturating_add(ttl_s.max(1));
+        let entry = {
+            let mut inner = self.inner.lock();
+            if let Some(cur) = inner.live.get(&(kind, id.to_owned())) {
+                if cur.expires_at_s >= expires_at_s && cur.expires_at_s > now_s {
+                    return Ok(cur.clone());
+                }
+            }
+            let entry = RevocationEntry {
+                version: inner.version + 1,
+                kind,
+                id: id.to_owned(),
+                revoked_at_s: now_s,
+                expires_at_s,
+                reason,
+            };
+            if let Some(f) = inner.file.as_mut() {
+                let mut line = serde_json::to_vec(&entry)?;
+                line.push(b'\n');
+                f.write_all(&line)?;
+                f.sync_data()?;
+            }
+            inner.version = entry.version;
+            inner.live.insert((kind, entry.id.clone()), entry.clone());
+            entry
+        };
+        self.bus.publish(entry.clone());
+        Ok(entry)
+    }
+
+    pub fn is_revoked(&self, kind: RevokedKind, id: &str, now_s: u64) -> bool {
+        self.inner
+            .lock()
+            .live
+            .get(&(kind, id.to_owned()))
+            .is_some_and(|e| e.expires_at_s > now_s)
+    }
+
+    /// Latest version handed out (0 when nothing was ever revoked).
+    pub fn version(&self) -> u64 {
+        self.inner.lock().version
+    }
+
+    /// Unexpired entries newer than `since`, oldest first.
+    pub fn since(&self, since: u64, now_s: u64) -> (u64, Vec<RevocationEntry>) {
+        let inner = self.inner.lock();
+        let mut out: Vec<RevocationEntry> = inner
+            .live
+            .values()
+            .filter(|e| e.version > since && e.expires_at_s > now_s)
+            .cloned()
+            .collect();
+        out.sort_by_key(|e| e.version);
+        (inner.version, out)
+    }
+
+    /// Forget expired entries in memory (the file is compacted on next open).
+    pub fn prune(&self, now_s: u64) -> usize {
+        let mut inner = self.inner.lock();
+        let before = inner.live.len();
+        inner.live.retain(|_, e| e.expires_at_s > now_s);
+        before - inner.live.len()
+    }
+
+    /// In-process change feed; every durable revocation is published once.
+    pub fn bus(&self) -> &Bus<RevocationEntry> {
+        &self.bus
+    }
+}
+
+fn merge(live: &mut HashMap<(RevokedKind, String), RevocationEntry>, entry: RevocationEntry) {
+    let key = (entry.kind, entry.id.clone());
+    match live.get(&key) {
+        Some(cur) if cur.version >= entry.version => {}
+        _ => {
+            live.insert(key, entry);
+        }
+    }
+}
@@ -1,31 +1,133 @@
-// RO:WHAT   IssuerState: thin service state around a KMS client + helpers.
-// RO:WHY    Handlers expect helpers: build_envelope, jwks, rotate, attest, verify_envelope.
+// RO:WHAT   IssuerState: thin service state around a KMS client + revocation store + helpers.
+// RO:WHY    Handlers expect helpers: build_envelope, jwks, rotate, attest, verify_envelope, is_revoked.
+// RO:INVARS Nothing is signed with a revoked KID (signing rotates away from it first); a signed
+//           revocation list is reused for its store version, for at most `cache.jwks_ttl_s`.
 
-use crate::{config::Config, dto::verify::Envelope, error::Error, kms::client::KmsClient};
+use crate::{
+    config::Config,
+    dto::verify::Envelope,
+    error::Error,
+    kms::client::KmsClient,
+    revocation::{RevocationList, RevocationStore, RevokedKind},
+    util::{hashing::token_id, time::now_unix_s},
+};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use parking_lot::RwLock;
+use parking_lot::Mutex;
 use serde_json::{json, Value};
 use std::{collections::HashMap, sync::Arc};
 
+/// Distinct `since` values cached per store version before the cache starts over.
+const MAX_CACHED_LISTS: usize = 64;
+
 #[derive(Clone)]
 pub struct IssuerState {
     pub cfg: Config,
     pub kms: Arc<dyn KmsClient>,
-    pub cache: Arc<RwLock<HashMap<String, ()>>>,
+    pub revocations: Arc<RevocationStore>,
+    lists: Arc<Mutex<ListCache>>,
+}
+
+/// Signed revocation lists for one store version, by `since` (with the time each was signed).
+#[derive(Default)]
+struct ListCache {
+    version: u64,
+    by_since: HashMap<u64, (u64, Envelope)>,
 }
 
 impl IssuerState {
+    /// Build state, opening the revocation log from `cfg.revocation.path` (in-memory if unset).
+    ///
+    /// Panics if a configured log cannot be opened: serving without it would un-revoke tokens.
     pub fn new(cfg: Config, kms: Arc<dyn KmsClient>) -> Self {
+        Self::try_new(cfg, kms).expect("open revocation log")
+    }
+
+    pub fn try_new(cfg: Config, kms: Arc<dyn KmsClient>) -> anyhow::Result<Self> {
+        let revocations = match &cfg.revocation.path {
+            Some(path) => RevocationStore::open(path, now_unix_s())?,
+            None => RevocationStore::in_memory(),
+        };
+        Ok(Self::with_revocations(cfg, kms, Arc::new(revocations)))
+    }
+
+    pub fn with_revocations(
+        cfg: Config,
+        kms: Arc<dyn KmsClient>,
+        revocations: Arc<RevocationStore>,
+    ) -> Self {
         Self {
             cfg,
             kms,
-            cache: Arc::new(RwLock::new(HashMap::new())),
+            revocations,
+            lists: Arc::default(),
         }
     }
 
     /// Sign raw message bytes; returns (kid, signature).
+    ///
+    /// If the current KID has been revoked, rotates to a fresh key and signs with that; refuses
+    /// only if the rotated key is revoked too.
     pub async fn sign(&self, msg: &[u8]) -> Result<(String, Vec<u8>), Error> {
-        self.kms.sign(msg).await.map_err(Error::Internal)
+        let (kid, sig) = self.kms.sign(msg).await.map_err(Error::Internal)?;
+        if !self.kid_revoked(&kid) {
+            return Ok((kid, sig));
+        }
+        let fresh = self.kms.rotate().await.map_err(Error::Internal)?;
+        tracing::warn!(revoked = %kid, %fresh, "signing key revoked; rotated");
+        let (kid, sig) = self.kms.sign(msg).await.map_err(Error::Internal)?;
+        if self.kid_revoked(&kid) {
+            return Err(Error::Revoked);
+        }
+        Ok((kid, sig))
+    }
+
+    fn kid_revoked(&self, kid: &str) -> bool {
+        self.revocations
+            .is_revoked(RevokedKind::Kid, kid, now_unix_s())
+    }
+
+    /// Signed `RevocationList` of entries newer than `since`.
+    ///
+    /// Signed once per store version and `since`, then served from cache until the version moves
+    /// or `cache.jwks_ttl_s` passes (so the signing key is still published when a verifier checks).
+    pub async fn revocation_list(&self, since: u64) -> Result<Envelope, Error> {
+        let now = now_unix_s();
+        let version = self.revocations.version();
+        {
+            let cache = self.lists.lock();
+            if cache.version == version {
+                if let Some((signed_at, env)) = cache.by_since.get(&since) {
+                    if now.saturating_sub(*signed_at) < self.cfg.cache.jwks_ttl_s {
+                        return Ok(env.clone());
+                    }
+                }
+            }
+        }
+
+        let body =
+            RevocationList::from_store(&self.cfg.passport.issuer, &self.revocations, since, now);
+        let msg = serde_json::to_vec(&body).map_err(|e| Error::Internal(e.into()))?;
+        let (kid, sig) = self.sign(&msg).await?;
+        let env = self.build_envelope(kid, msg, sig);
+
+        let mut cache = self.lists.lock();
+        if body.version > cache.version || cache.by_since.len() >= MAX_CACHED_LISTS {
+            cache.by_since.clear();
+            cache.version = cache.version.max(body.version);
+        }
+        if body.version == cache.version {
+            cache.by_since.insert(since, (now, env.clone()));
+        }
+        Ok(env)
+    }
+
+    /// Whether a passport signed by `kid` over `msg` has been revoked (by token id or KID).
+    pub fn is_revoked(&self, kid: &str, msg: &[u8]) -> bool {
+        let now = now_unix_s();
+        self.revocations.is_revoked(RevokedKind::Kid, kid, now)
+            || self
+                .revocations
+                .is_revoked(RevokedKind::Token, &token_id(msg), now)
     }
 
     /// Verify signature with kid; returns true/false.
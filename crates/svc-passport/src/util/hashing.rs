@@ -1,2 +1,7 @@
-//! RO:WHAT — (stub) Hash helpers if needed later.
-#![allow(dead_code)]
+//! RO:WHAT — Hash helpers: stable token ids (jti) derived from signed message bytes.
+//! RO:WHY  — Issue payloads are caller JSON; the exact signed bytes are the one identity every party sees.
+
+/// Token id for a signed passport message: lowercase hex BLAKE3 of the exact `msg` bytes.
+pub fn token_id(msg: &[u8]) -> String {
+    blake3::hash(msg).to_hex().to_string()
+}
@@ -2,3 +2,8 @@
 pub fn now_s() -> i64 {
     time::OffsetDateTime::now_utc().unix_timestamp()
 }
+
+/// Unix seconds as `u64` (clamped at 0), the unit revocation and rotation bookkeeping use.
+pub fn now_unix_s() -> u64 {
+    u64::try_from(now_s()).unwrap_or(0)
+}
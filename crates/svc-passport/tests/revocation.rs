@@ -0,0 +1,380 @@
+// crates/svc-passport/tests/revocation.rs
+// Durable revocation: store persistence/TTL, HTTP revoke -> verify false, signed incremental list.
+use axum::body::to_bytes;
+use axum::{body::Body, http, http::Request, Router};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use tower::ServiceExt;
+
+use svc_passport::{
+    health::Health,
+    http::router::build_router,
+    kms::rotation::RotationPolicy,
+    revocation::{RevocationList, RevocationStore, RevokedKind},
+};
+
+#[path = "../src/test_support.rs"]
+mod test_support;
+
+use test_support::default_config;
+
+const NOW: u64 = 1_700_000_000;
+const OPERATOR: &str = "revocation-operator-token";
+
+fn app() -> Router {
+    let mut cfg = default_config();
+    cfg.revocation.operator_token = Some(OPERATOR.into());
+    build_router(cfg, Health::default())
+}
+
+fn temp_log(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "svc-passport-revocation-{}-{name}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir.join("revocations.jsonl")
+}
+
+async fn call(app: &Router, method: http::Method, uri: &str, body: Option<Value>) -> (u16, Value) {
+    call_as(app, Some(OPERATOR), method, uri, body).await
+}
+
+async fn call_as(
+    app: &Router,
+    bearer: Option<&str>,
+    method: http::Method,
+    uri: &str,
+    body: Option<Value>,
+) -> (u16, Value) {
+    let mut req = Request::builder().method(method).uri(uri);
+    if let Some(token) = bearer {
+        req = req.header(http::header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let body = match body {
+        Some(v) => {
+            req = req.header(http::header::CONTENT_TYPE, "application/json");
+            Body::from(serde_json::to_vec(&v).unwrap())
+        }
+        None => Body::empty(),
+    };
+    let resp = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    let status = resp.status().as_u16();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
+
+#[test]
+fn store_persists_across_reopen_and_drops_expired() {
+    let path = temp_log("reopen");
+    {
+        let store = RevocationStore::open(&path, NOW).unwrap();
+        let a = store
+            .revoke(RevokedKind::Token, "aa", 100, Some("leak".into()), NOW)
+            .unwrap();
+        let b = store
+            .revoke(RevokedKind::Kid, "ed25519/default/v1", 10, None, NOW)
+            .unwrap();
+        assert_eq!((a.version, b.version), (1, 2));
+        // Idempotent while the existing entry lasts at least as long.
+        assert_eq!(
+            store
+                .revoke(RevokedKind::Token, "aa", 50, None, NOW)
+                .unwrap(),
+            a
+        );
+    }
+    // Simulate a crash mid-append: a torn trailing line is ignored.
+    let mut text = std::fs::read_to_string(&path).unwrap();
+    text.push_str("{\"version\":3,\"kind\":\"tok");
+    std::fs::write(&path, text).unwrap();
+
+    let store = RevocationStore::open(&path, NOW + 20).unwrap();
+    assert!(store.is_revoked(RevokedKind::Token, "aa", NOW + 20));
+    assert!(!store.is_revoked(RevokedKind::Kid, "ed25519/default/v1", NOW + 20));
+    assert_eq!(store.version(), 2);
+    let next = store
+        .revoke(RevokedKind::Token, "bb", 100, None, NOW + 20)
+        .unwrap();
+    assert_eq!(next.version, 3, "versions never go backwards");
+
+    let (version, entries) = store.since(1, NOW + 20);
+    assert_eq!(version, 3);
+    assert_eq!(
+        entries.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(),
+        ["bb"]
+    );
+
+    // Compaction on open kept only the high-water mark and live lines.
+    let lines = std::fs::read_to_string(&path).unwrap().lines().count();
+    assert_eq!(lines, 3);
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn versions_survive_every_entry_expiring() {
+    let path = temp_log("high-water");
+    {
+        let store = RevocationStore::open(&path, NOW).unwrap();
+        for id in ["a", "b", "c"] {
+            store.revoke(RevokedKind::Token, id, 10, None, NOW).unwrap();
+        }
+    }
+    // Everything has expired: compaction drops all entries but keeps the counter.
+    drop(RevocationStore::open(&path, NOW + 60).unwrap());
+    let store = RevocationStore::open(&path, NOW + 120).unwrap();
+    assert_eq!(store.version(), 3);
+    assert_eq!(store.since(0, NOW + 120), (3, vec![]));
+    // A verifier holding since=3 still sees the next revocation.
+    store
+        .revoke(RevokedKind::Token, "d", 10, None, NOW + 120)
+        .unwrap();
+    let (version, entries) = store.since(3, NOW + 120);
+    assert_eq!((version, entries.len()), (4, 1));
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[test]
+fn corrupt_interior_line_is_an_error() {
+    let path = temp_log("corrupt");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "not json\n{}\n").unwrap();
+    assert!(RevocationStore::open(&path, NOW).is_err());
+    let _ = std::fs::remove_dir_all(path.parent().unwrap());
+}
+
+#[tokio::test]
+async fn revocations_are_published_on_the_bus() {
+    let store = RevocationStore::in_memory();
+    let mut rx = store.bus().subscribe();
+    store
+        .revoke(RevokedKind::Token, "cc", 60, None, NOW)
+        .unwrap();
+    let entry = rx.recv().await.unwrap();
+    assert_eq!(
+        (entry.kind, entry.id.as_str(), entry.version),
+        (RevokedKind::Token, "cc", 1)
+    );
+}
+
+#[tokio::test]
+async fn revoked_token_stops_verifying_and_is_listed_signed() {
+    let aud = default_config().passport.issuer;
+    let app = app();
+
+    let (status, env) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/issue",
+        Some(json!({ "sub": "passport:main:dev", "nonce": "revoke-me" })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let jti = env["jti"].as_str().unwrap().to_owned();
+    let msg = STANDARD.decode(env["msg_b64"].as_str().unwrap()).unwrap();
+    assert_eq!(jti, blake3::hash(&msg).to_hex().to_string());
+
+    let mut verify_body = env.clone();
+    verify_body["aud"] = json!(aud);
+    let (_, ok) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/verify",
+        Some(verify_body.clone()),
+    )
+    .await;
+    assert_eq!(ok, json!(true));
+
+    let (status, entry) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/revoke",
+        Some(json!({ "jti": jti, "reason": "leaked" })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(entry["kind"], "token");
+
+    let (_, ok) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/verify",
+        Some(verify_body),
+    )
+    .await;
+    assert_eq!(ok, json!(false));
+
+    // Signed incremental list: verify the Envelope against /v1/keys.
+    let (status, list_env) = call(&app, http::Method::GET, "/v1/revocations?since=0", None).await;
+    assert_eq!(status, 200);
+    let (_, jwks) = call(&app, http::Method::GET, "/v1/keys", None).await;
+    let kid = list_env["kid"].as_str().unwrap();
+    let x = jwks["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|k| k["kid"] == kid)
+        .and_then(|k| k["x"].as_str())
+        .unwrap();
+    let vk =
+        VerifyingKey::from_bytes(&URL_SAFE_NO_PAD.decode(x).unwrap().try_into().unwrap()).unwrap();
+    let list_msg = STANDARD
+        .decode(list_env["msg_b64"].as_str().unwrap())
+        .unwrap();
+    let sig = Signature::from_slice(
+        &STANDARD
+            .decode(list_env["sig_b64"].as_str().unwrap())
+            .unwrap(),
+    )
+    .unwrap();
+    vk.verify_strict(&list_msg, &sig).unwrap();
+
+    let list: RevocationList = serde_json::from_slice(&list_msg).unwrap();
+    assert_eq!(list.version, 1);
+    assert_eq!(list.entries.len(), 1);
+    assert_eq!(list.entries[0].id, jti);
+
+    let (_, newer) = call(&app, http::Method::GET, "/v1/revocations?since=1", None).await;
+    let newer: RevocationList =
+        serde_json::from_slice(&STANDARD.decode(newer["msg_b64"].as_str().unwrap()).unwrap())
+            .unwrap();
+    assert!(newer.entries.is_empty());
+}
+
+fn list_of(env: &Value) -> RevocationList {
+    serde_json::from_slice(&STANDARD.decode(env["msg_b64"].as_str().unwrap()).unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn revoking_the_signing_kid_rotates_and_bad_requests_are_rejected() {
+    let app = app();
+    let (_, env) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/issue",
+        Some(json!({ "n": 1 })),
+    )
+    .await;
+    let kid = env["kid"].as_str().unwrap().to_owned();
+
+    for bad in [
+        json!({}),
+        json!({ "jti": "not-hex" }),
+        json!({ "jti": "00", "kid": kid }),
+    ] {
+        let (status, _) = call(&app, http::Method::POST, "/v1/passport/revoke", Some(bad)).await;
+        assert_eq!(status, 400);
+    }
+
+    // An absurd TTL is capped at the KID revocation TTL.
+    let (status, entry) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/revoke",
+        Some(json!({ "kid": kid, "ttl_s": u64::MAX })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let kid_ttl_s = default_config().kid_revocation_ttl_s();
+    assert_eq!(
+        entry["expires_at_s"].as_u64().unwrap() - entry["revoked_at_s"].as_u64().unwrap(),
+        kid_ttl_s
+    );
+
+    // The list announcing the revocation is signed, just not with the revoked key.
+    let (status, list_env) = call(&app, http::Method::GET, "/v1/revocations", None).await;
+    assert_eq!(status, 200);
+    assert_ne!(list_env["kid"], kid);
+    assert_eq!(list_of(&list_env).entries[0].id, kid);
+
+    // Issuance continues on the fresh key.
+    let (status, env) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/issue",
+        Some(json!({ "n": 2 })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(env["kid"], list_env["kid"]);
+}
+
+#[tokio::test]
+async fn revoking_needs_the_operator_token() {
+    let body = json!({ "kid": "ed25519/default/v9" });
+    let app = app();
+    for bearer in [None, Some("wrong-operator-token-xx")] {
+        let (status, err) = call_as(
+            &app,
+            bearer,
+            http::Method::POST,
+            "/v1/passport/revoke",
+            Some(body.clone()),
+        )
+        .await;
+        assert_eq!((status, err["code"].as_str()), (401, Some("unauthorized")));
+    }
+
+    // No token configured: the endpoint is off.
+    let open = build_router(default_config(), Health::default());
+    let (status, _) = call(&open, http::Method::POST, "/v1/passport/revoke", Some(body)).await;
+    assert_eq!(status, 403);
+}
+
+#[tokio::test]
+async fn signed_lists_are_reused_until_the_version_moves() {
+    let app = app();
+    let (_, first) = call(&app, http::Method::GET, "/v1/revocations?since=0", None).await;
+    let (_, again) = call(&app, http::Method::GET, "/v1/revocations?since=0", None).await;
+    assert_eq!(first, again, "same version, same signed bytes");
+
+    let (status, _) = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/revoke",
+        Some(json!({ "kid": "ed25519/default/v9" })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let (_, fresh) = call(&app, http::Method::GET, "/v1/revocations?since=0", None).await;
+    assert_eq!(list_of(&fresh).version, 1);
+    assert_eq!(list_of(&fresh).entries.len(), 1);
+}
+
+#[tokio::test]
+async fn kid_revocations_outlive_the_tokens_the_kid_signed() {
+    let mut cfg = default_config();
+    cfg.revocation.operator_token = Some(OPERATOR.into());
+    cfg.revocation.kid_ttl_s = 60;
+    let floor = cfg.token_lifetime_s() + RotationPolicy::from_config(&cfg).grace_s;
+    assert_eq!(cfg.min_kid_revocation_ttl_s(), floor);
+    assert_eq!(cfg.kid_revocation_ttl_s(), floor);
+    let app = build_router(cfg, Health::default());
+
+    for (n, ttl_s) in [(1, None), (2, Some(1))] {
+        let (_, env) = call(
+            &app,
+            http::Method::POST,
+            "/v1/passport/issue",
+            Some(json!({ "n": n })),
+        )
+        .await;
+        let mut req = json!({ "kid": env["kid"] });
+        if let Some(t) = ttl_s {
+            req["ttl_s"] = json!(t);
+        }
+        let (status, entry) =
+            call(&app, http::Method::POST, "/v1/passport/revoke", Some(req)).await;
+        assert_eq!(status, 200);
+        assert_eq!(
+            entry["expires_at_s"].as_u64().unwrap() - entry["revoked_at_s"].as_u64().unwrap(),
+            floor
+        );
+    }
+}
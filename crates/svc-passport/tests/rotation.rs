@@ -0,0 +1,360 @@
+// crates/svc-passport/tests/rotation.rs
+// Staged rotation over ron-kms: next key published before it signs, old key verify-only for a grace window.
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use axum::body::to_bytes;
+use axum::{body::Body, http, http::Request, Router};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use ron_kms::Keystore;
+use svc_passport::{
+    config::KmsBackend,
+    health::Health,
+    http::router::build_router,
+    kms::{
+        ron_kms::RonKms,
+        rotation::{KeyRing, RotationPolicy, RotationStep},
+    },
+};
+
+#[path = "../src/test_support.rs"]
+mod test_support;
+
+use test_support::default_config;
+
+const T0: u64 = 1_700_000_000;
+
+const POLICY: RotationPolicy = RotationPolicy {
+    max_age_s: 100,
+    stage_lead_s: 10,
+    grace_s: 50,
+};
+
+fn kms() -> RonKms<ron_kms::backends::MemoryKeystore> {
+    RonKms::new(ron_kms::memory_keystore(), "t", "passport", POLICY, T0).unwrap()
+}
+
+fn statuses(view: &Value) -> Vec<(String, String)> {
+    view["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|k| {
+            (
+                k["kid"].as_str().unwrap().to_owned(),
+                k["status"].as_str().unwrap().to_owned(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn next_key_is_published_before_it_signs_and_old_key_verifies_through_grace() {
+    let kms = kms();
+    let (kid1, sig1) = kms.sign_at(b"old", T0 + 50).unwrap();
+    assert_eq!(kms.tick_at(T0 + 50).unwrap(), RotationStep::Idle);
+
+    // Stage window opens at due (T0+100) - lead (10).
+    assert_eq!(kms.tick_at(T0 + 90).unwrap(), RotationStep::Stage);
+    let view = kms.public_keys_at(T0 + 90).unwrap();
+    let kid2 = view["next"].as_str().unwrap().to_owned();
+    assert_eq!(
+        statuses(&view),
+        vec![
+            (kid1.clone(), "active".into()),
+            (kid2.clone(), "next".into())
+        ]
+    );
+    let (signer, _) = kms.sign_at(b"still-old", T0 + 95).unwrap();
+    assert_eq!(signer, kid1, "a staged key never signs early");
+    assert!(
+        kms.verify_at(&kid2, b"x", &[0; 64], T0 + 95).is_err(),
+        "nor verifies before promotion"
+    );
+
+    // Promotion: the next key signs, the old one is verify-only.
+    let (signer, sig2) = kms.sign_at(b"new", T0 + 100).unwrap();
+    assert_eq!(signer, kid2);
+    assert!(kms.verify_at(&kid2, b"new", &sig2, T0 + 100).unwrap());
+    assert!(kms.verify_at(&kid1, b"old", &sig1, T0 + 149).unwrap());
+    assert_eq!(
+        statuses(&kms.public_keys_at(T0 + 149).unwrap()),
+        vec![
+            (kid2.clone(), "active".into()),
+            (kid1.clone(), "verify_only".into())
+        ]
+    );
+
+    // Grace over: the old key is gone from /v1/keys and from verification.
+    assert!(kms.verify_at(&kid1, b"old", &sig1, T0 + 150).is_err());
+    assert_eq!(
+        statuses(&kms.public_keys_at(T0 + 150).unwrap()),
+        vec![(kid2, "active".into())]
+    );
+    assert!(kms.ring().previous().is_empty());
+}
+
+#[test]
+fn promotion_waits_for_the_full_publication_lead() {
+    let mut ring = KeyRing::new("k1", T0);
+    // Staged late (5s before due): promotion slips to staged + lead.
+    assert_eq!(POLICY.decide(&ring, T0 + 95), RotationStep::Stage);
+    ring.stage("k2", T0 + 95);
+    assert_eq!(POLICY.decide(&ring, T0 + 100), RotationStep::Idle);
+    assert_eq!(POLICY.decide(&ring, T0 + 105), RotationStep::Promote);
+    assert!(ring.promote(T0 + 105, POLICY.grace_s));
+    assert_eq!(ring.active().kid, "k2");
+    assert!(ring.accepts(&"k1", T0 + 154) && !ring.accepts(&"k1", T0 + 155));
+
+    let manual = RotationPolicy {
+        max_age_s: 0,
+        ..POLICY
+    };
+    assert_eq!(manual.decide(&ring, u64::MAX), RotationStep::Idle);
+}
+
+fn state_file(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "svc-passport-rotation-{}-{name}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir.join("ring.json")
+}
+
+#[test]
+fn a_saved_ring_survives_a_restart() {
+    // The keystore outlives the service, as an HSM token does.
+    let store = ron_kms::memory_keystore();
+    let state = state_file("restart");
+    let (kid1, sig1, kid2) = {
+        let kms = RonKms::open(store.clone(), "t", "passport", POLICY, &state, None, T0).unwrap();
+        let (kid1, sig1) = kms.sign_at(b"old", T0).unwrap();
+        let kid2 = kms.rotate_at(T0 + 10).unwrap().to_string();
+        (kid1, sig1, kid2)
+    };
+
+    let kms = RonKms::open(
+        store.clone(),
+        "t",
+        "passport",
+        POLICY,
+        &state,
+        None,
+        T0 + 20,
+    )
+    .unwrap();
+    let (signer, _) = kms.sign_at(b"new", T0 + 20).unwrap();
+    assert_eq!(signer, kid2, "the promoted key still signs");
+    assert!(kms.verify_at(&kid1, b"old", &sig1, T0 + 20).unwrap());
+    assert_eq!(kms.ring().previous()[0].until_s, T0 + 10 + POLICY.grace_s);
+
+    // A ring naming keys the keystore no longer holds is refused, not silently replaced.
+    let err = RonKms::open(
+        ron_kms::memory_keystore(),
+        "t",
+        "passport",
+        POLICY,
+        &state,
+        None,
+        T0,
+    )
+    .err()
+    .unwrap();
+    assert!(err.to_string().contains("does not hold"), "{err}");
+}
+
+#[test]
+fn first_boot_adopts_a_configured_kid() {
+    let store = ron_kms::memory_keystore();
+    let kid = store.create_ed25519("t", "passport").unwrap().to_string();
+    let kms = RonKms::open(
+        store,
+        "t",
+        "passport",
+        POLICY,
+        state_file("adopt"),
+        Some(&kid),
+        T0,
+    )
+    .unwrap();
+    assert_eq!(kms.sign_at(b"m", T0).unwrap().0, kid);
+}
+
+#[test]
+fn durable_kms_settings_need_a_durable_backend() {
+    let mut kms = default_config().kms;
+    assert_eq!(kms.backend, KmsBackend::Memory);
+    kms.validate().unwrap();
+    kms.state_path = Some("ring.json".into());
+    assert!(kms.validate().is_err());
+
+    kms.backend = KmsBackend::Pkcs11;
+    assert!(kms.validate().is_err(), "no [kms.pkcs11] section");
+    let legacy: toml::Value = toml::from_str("backend = \"ron-kms\"").unwrap();
+    assert_eq!(
+        legacy
+            .try_into::<svc_passport::config::Kms>()
+            .unwrap()
+            .backend,
+        KmsBackend::Memory
+    );
+}
+
+#[test]
+fn grace_never_undercuts_passport_ttl() {
+    let mut cfg = default_config();
+    cfg.rotation.grace_s = 1;
+    let policy = RotationPolicy::from_config(&cfg);
+    let floor = cfg.passport.max_ttl_s + cfg.passport.clock_skew_s.unsigned_abs();
+    assert_eq!(policy.grace_s, floor);
+    cfg.rotation.grace_s = floor + 1;
+    assert_eq!(RotationPolicy::from_config(&cfg).grace_s, floor + 1);
+}
+
+async fn call(app: &Router, method: http::Method, uri: &str, body: Option<Value>) -> Value {
+    let mut req = Request::builder().method(method).uri(uri);
+    let body = match body {
+        Some(v) => {
+            req = req.header(http::header::CONTENT_TYPE, "application/json");
+            Body::from(serde_json::to_vec(&v).unwrap())
+        }
+        None => Body::empty(),
+    };
+    let resp = app.clone().oneshot(req.body(body).unwrap()).await.unwrap();
+    assert!(resp.status().is_success(), "{uri}: {}", resp.status());
+    serde_json::from_slice(&to_bytes(resp.into_body(), usize::MAX).await.unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn admin_rotate_keeps_outstanding_passports_valid() {
+    let cfg = default_config();
+    let aud = cfg.passport.issuer.clone();
+    let app = build_router(cfg, Health::default());
+
+    let mut env = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/issue",
+        Some(json!({ "sub": "before-rotation" })),
+    )
+    .await;
+    env["aud"] = json!(aud);
+
+    let rotated = call(&app, http::Method::POST, "/admin/rotate", None).await;
+    assert_ne!(rotated["current_kid"], env["kid"]);
+
+    let ok = call(
+        &app,
+        http::Method::POST,
+        "/v1/passport/verify",
+        Some(env.clone()),
+    )
+    .await;
+    assert_eq!(ok, json!(true));
+
+    let jwks = call(&app, http::Method::GET, "/v1/keys", None).await;
+    let kids: Vec<&str> = jwks["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|k| k["kid"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        kids,
+        [
+            rotated["current_kid"].as_str().unwrap(),
+            env["kid"].as_str().unwrap()
+        ]
+    );
+}
+
+/// Memory keystore whose key generation is slow and counted, to widen any tick race.
+struct SlowKeystore {
+    inner: ron_kms::backends::MemoryKeystore,
+    created: Arc<AtomicUsize>,
+}
+
+impl Keystore for SlowKeystore {
+    fn create_ed25519(
+        &self,
+        tenant: &str,
+        purpose: &str,
+    ) -> Result<ron_kms::KeyId, ron_kms::KmsError> {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        self.created.fetch_add(1, Ordering::SeqCst);
+        self.inner.create_ed25519(tenant, purpose)
+    }
+    fn rotate(&self, kid: &ron_kms::KeyId) -> Result<ron_kms::KeyId, ron_kms::KmsError> {
+        self.inner.rotate(kid)
+    }
+    fn alg(&self, kid: &ron_kms::KeyId) -> Result<ron_kms::Alg, ron_kms::KmsError> {
+        self.inner.alg(kid)
+    }
+    fn meta(&self, kid: &ron_kms::KeyId) -> Result<ron_kms::types::KeyMeta, ron_kms::KmsError> {
+        self.inner.meta(kid)
+    }
+}
+
+impl ron_kms::Signer for SlowKeystore {
+    fn sign(&self, kid: &ron_kms::KeyId, msg: &[u8]) -> Result<Vec<u8>, ron_kms::KmsError> {
+        self.inner.sign(kid, msg)
+    }
+}
+
+impl ron_kms::Verifier for SlowKeystore {
+    fn verify(
+        &self,
+        kid: &ron_kms::KeyId,
+        msg: &[u8],
+        sig: &[u8],
+    ) -> Result<bool, ron_kms::KmsError> {
+        self.inner.verify(kid, msg, sig)
+    }
+}
+
+impl ron_kms::traits::PubkeyProvider for SlowKeystore {
+    fn verifying_key_bytes(&self, kid: &ron_kms::KeyId) -> Result<[u8; 32], ron_kms::KmsError> {
+        self.inner.verifying_key_bytes(kid)
+    }
+}
+
+#[test]
+fn concurrent_ticks_create_a_single_staged_key() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let store = SlowKeystore {
+        inner: ron_kms::memory_keystore(),
+        created: created.clone(),
+    };
+    let kms = RonKms::new(store, "t", "passport", POLICY, T0).unwrap();
+    let steps: Vec<RotationStep> = std::thread::scope(|s| {
+        let ticks: Vec<_> = (0..8)
+            .map(|_| s.spawn(|| kms.tick_at(T0 + 90).unwrap()))
+            .collect();
+        ticks.into_iter().map(|t| t.join().unwrap()).collect()
+    });
+    assert_eq!(
+        steps.iter().filter(|s| **s == RotationStep::Stage).count(),
+        1
+    );
+    assert!(kms.ring().next().is_some());
+
+    // Concurrent admin rotations each promote their own freshly staged key; none is left orphaned.
+    std::thread::scope(|s| {
+        for i in 0..4 {
+            let kms = &kms;
+            s.spawn(move || kms.rotate_at(T0 + 91 + i).unwrap());
+        }
+    });
+    let ring = kms.ring();
+    let held = 1 + ring.previous().len() + usize::from(ring.next().is_some());
+    assert_eq!(
+        created.load(Ordering::SeqCst),
+        held,
+        "every key created is in the ring"
+    );
+}
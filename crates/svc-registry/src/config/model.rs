@@ -1,6 +1,10 @@
 //! Config model and defaults. Env prefix SVCR_.
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+use crate::governance::Signer;
+
 /// Service configuration (beta scope).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -23,14 +27,42 @@ pub struct Config {
     pub sse: Sse,
     #[serde(default)]
     pub cors: Cors,
+    #[serde(default)]
+    pub governance: Governance,
+    #[serde(default)]
+    pub retention: Retention,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Storage {
-    pub kind: String, // "sled" | "sqlite" (foundation uses stub)
+    pub kind: String, // "sled" | "memory" ("sqlite" reserved, not yet implemented)
     pub data_dir: String,
     pub fsync: bool,
+    /// How often the checkpointor signs HEAD (and retention runs).
+    #[serde(with = "humantime_serde", default = "default_checkpoint_interval")]
+    pub checkpoint_interval: Duration,
+    /// Checkpoint signing seed; empty = `<data_dir>/checkpoint.key` (created on first start).
+    #[serde(default)]
+    pub checkpoint_key_path: String,
+}
+
+fn default_checkpoint_interval() -> Duration {
+    Duration::from_secs(600)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Retention {
+    /// Newest versions always kept; older ones are pruned once a checkpoint covers them.
+    /// 0 keeps everything.
+    pub max_count: u64,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self { max_count: 10_000 }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -97,6 +129,28 @@ impl Default for Cors {
     }
 }
 
+/// Genesis trust root. Later signer sets are installed only by committed signer-set proposals.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct Governance {
+    /// M in M-of-N for the genesis signer set (N = signers.len()).
+    pub threshold: u8,
+    /// Pending proposals accepted before `TooManyProposals`.
+    pub max_pending: usize,
+    /// Genesis signers; empty disables the write plane (fail closed).
+    pub signers: Vec<Signer>,
+}
+
+impl Default for Governance {
+    fn default() -> Self {
+        Self {
+            threshold: 1,
+            max_pending: 1024,
+            signers: Vec::new(),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -110,11 +164,15 @@ impl Default for Config {
                 kind: "sled".into(),
                 data_dir: "./target/dev-registry".into(),
                 fsync: true,
+                checkpoint_interval: default_checkpoint_interval(),
+                checkpoint_key_path: String::new(),
             },
             limits: Limits::default(),
             timeouts: Timeouts::default(),
             sse: Sse::default(),
             cors: Cors::default(),
+            governance: Governance::default(),
+            retention: Retention::default(),
         }
     }
 }
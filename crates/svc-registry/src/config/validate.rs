@@ -27,5 +27,22 @@ pub fn validate_config(c: &Config) -> anyhow::Result<()> {
         anyhow::bail!("sse.max_clients must be > 0");
     }
 
+    match c.storage.kind.as_str() {
+        "sled" | "memory" => {}
+        "sqlite" => anyhow::bail!("storage.kind = \"sqlite\" is not implemented yet; use \"sled\""),
+        other => anyhow::bail!("storage.kind must be \"sled\" or \"memory\" (got {other:?})"),
+    }
+    if c.storage.checkpoint_interval < std::time::Duration::from_secs(1) {
+        anyhow::bail!("storage.checkpoint_interval must be >= 1s");
+    }
+
+    let g = &c.governance;
+    if g.max_pending == 0 {
+        anyhow::bail!("governance.max_pending must be > 0");
+    }
+    if !g.signers.is_empty() && (g.threshold == 0 || usize::from(g.threshold) > g.signers.len()) {
+        anyhow::bail!("governance.threshold must be in 1..=signers.len()");
+    }
+
     Ok(())
 }
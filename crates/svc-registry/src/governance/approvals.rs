@@ -1 +1,53 @@
-/*! Approval verification (scaffold) */
+//! RO:WHAT — Detached approval signatures over a proposal id.
+//! RO:WHY  — Approvals travel separately from the descriptor; binding them to the proposal id
+//!           (which commits to descriptor, change, base version and signer-set version) stops replay.
+//! RO:INVARIANTS — message = APPROVAL_DOMAIN || proposal_id; one approval per signer per proposal.
+
+use serde::{Deserialize, Serialize};
+
+use super::{signer_set::SignerSet, GovError};
+
+/// Domain separator for approval signatures.
+pub const APPROVAL_DOMAIN: &[u8] = b"ron/registry/approval/v1\0";
+
+/// Bytes a signer signs to approve `proposal_id`.
+pub fn approval_message(proposal_id: &str) -> Vec<u8> {
+    let mut msg = Vec::with_capacity(APPROVAL_DOMAIN.len() + proposal_id.len());
+    msg.extend_from_slice(APPROVAL_DOMAIN);
+    msg.extend_from_slice(proposal_id.as_bytes());
+    msg
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Approval {
+    pub signer_id: String,
+    /// Detached signature over [`approval_message`], standard base64.
+    pub sig_b64: String,
+}
+
+impl Approval {
+    /// Check this approval against `set` for `proposal_id`.
+    pub fn verify(&self, set: &SignerSet, proposal_id: &str) -> Result<(), GovError> {
+        set.verify(
+            &self.signer_id,
+            &approval_message(proposal_id),
+            &self.sig_b64,
+        )
+    }
+}
+
+/// Verify and append `approval`, rejecting a second approval from the same signer.
+pub fn add_approval(
+    approvals: &mut Vec<Approval>,
+    set: &SignerSet,
+    proposal_id: &str,
+    approval: Approval,
+) -> Result<(), GovError> {
+    if approvals.iter().any(|a| a.signer_id == approval.signer_id) {
+        return Err(GovError::DuplicateApproval);
+    }
+    approval.verify(set, proposal_id)?;
+    approvals.push(approval);
+    Ok(())
+}
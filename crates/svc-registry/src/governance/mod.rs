@@ -1 +1,74 @@
-/*! Governance facade (scaffold) */
+//! RO:WHAT — Governance facade: signer sets, detached approvals, M-of-N quorum, supersede rules.
+//! RO:WHY  — [I-3]/[I-12] mutations commit only with a quorum of a versioned signer set.
+//! RO:INTERACTS — pipeline::{propose,approve,commit}; ron_proto::gov::{SignedDescriptorV1, MultiSigNofM}.
+//! RO:INVARIANTS — pure checks (no I/O); error codes are stable and double as metric labels.
+
+pub mod approvals;
+pub mod quorum;
+pub mod signer_set;
+pub mod supersede;
+
+pub use approvals::{approval_message, Approval, APPROVAL_DOMAIN};
+pub use quorum::QuorumStatus;
+pub use signer_set::{Signer, SignerSet, SignerSetSpec};
+pub use supersede::SupersedeReason;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum GovError {
+    #[error("no signer set configured; governance writes are disabled")]
+    NoSignerSet,
+    #[error("invalid signer set: {0}")]
+    InvalidSignerSet(String),
+    #[error("bad proposal: {0}")]
+    BadProposal(String),
+    #[error("signature algorithm not supported")]
+    UnsupportedAlg,
+    #[error("proposal not found")]
+    NotFound,
+    #[error("signer not in the current signer set")]
+    UnknownSigner,
+    #[error("signature did not verify")]
+    BadSignature,
+    #[error("signer already approved this proposal")]
+    DuplicateApproval,
+    #[error("descriptor expired or not yet valid")]
+    Expired,
+    #[error("proposal based on version {base}, head is {head}")]
+    StaleVersion { base: u64, head: u64 },
+    #[error("proposal superseded: {0}")]
+    Superseded(SupersedeReason),
+    #[error("proposal already committed at version {0}")]
+    AlreadyCommitted(u64),
+    #[error("proposal requires {need} valid signatures (got {have})")]
+    NoQuorum { have: usize, need: usize },
+    #[error("too many pending proposals")]
+    TooManyProposals,
+    #[error("storage: {0}")]
+    Storage(String),
+}
+
+impl GovError {
+    /// Stable machine code (README error table; `rejected_total{reason}` label).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoSignerSet => "NoSignerSet",
+            Self::InvalidSignerSet(_) => "InvalidSignerSet",
+            Self::BadProposal(_) => "BadProposal",
+            Self::UnsupportedAlg => "UnsupportedAlg",
+            Self::NotFound => "NotFound",
+            Self::UnknownSigner => "UnknownSigner",
+            Self::BadSignature => "BadSignature",
+            Self::DuplicateApproval => "DuplicateApproval",
+            Self::Expired => "Expired",
+            Self::StaleVersion { .. } => "StaleVersion",
+            Self::Superseded(_) => "Superseded",
+            Self::AlreadyCommitted(_) => "AlreadyCommitted",
+            Self::NoQuorum { .. } => "NoQuorum",
+            Self::TooManyProposals => "TooManyProposals",
+            Self::Storage(_) => "Storage",
+        }
+    }
+}
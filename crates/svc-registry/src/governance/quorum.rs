@@ -1 +1,53 @@
-/*! M-of-N quorum evaluation (scaffold) */
+//! RO:WHAT — M-of-N quorum evaluation over verified approvals.
+//! RO:WHY  — [I-3] commits need M distinct, currently valid signers from an N-member set.
+//! RO:INVARIANTS — counts distinct signer ids whose signature verifies against the *current* set;
+//!                 the threshold is the stricter of the set's and the descriptor's `m`.
+
+use ron_proto::MultiSigNofM;
+use serde::Serialize;
+
+use super::{approvals::Approval, signer_set::SignerSet, GovError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct QuorumStatus {
+    pub have: usize,
+    pub need: usize,
+    pub n: usize,
+}
+
+impl QuorumStatus {
+    pub fn reached(&self) -> bool {
+        self.have >= self.need
+    }
+}
+
+/// Check a descriptor's declared quorum against the signer set: same N, no weaker M.
+pub fn check_declared(set: &SignerSet, declared: &MultiSigNofM) -> Result<(), GovError> {
+    if declared.n != set.quorum.n || declared.m < set.quorum.m {
+        return Err(GovError::BadProposal(format!(
+            "descriptor quorum {}-of-{} weaker than or inconsistent with signer set {}-of-{}",
+            declared.m, declared.n, set.quorum.m, set.quorum.n
+        )));
+    }
+    Ok(())
+}
+
+/// Count valid approvals for `proposal_id`; invalid or revoked signers simply don't count.
+pub fn evaluate(
+    set: &SignerSet,
+    declared: &MultiSigNofM,
+    proposal_id: &str,
+    approvals: &[Approval],
+) -> QuorumStatus {
+    let mut seen = Vec::with_capacity(approvals.len());
+    for a in approvals {
+        if !seen.contains(&a.signer_id.as_str()) && a.verify(set, proposal_id).is_ok() {
+            seen.push(a.signer_id.as_str());
+        }
+    }
+    QuorumStatus {
+        have: seen.len(),
+        need: usize::from(set.quorum.m.max(declared.m)),
+        n: set.signers.len(),
+    }
+}
@@ -1 +1,118 @@
-/*! Signer set lifecycle (scaffold) */
+//! RO:WHAT — Versioned signer set: who may approve, and the M-of-N threshold they must meet.
+//! RO:WHY  — [I-12] signer sets are registry artifacts; approvals bind to one set version.
+//! RO:INVARIANTS — unique non-empty signer ids; 1 <= m <= n == signers.len(); Ed25519 keys only
+//!                 (PQ tags parse but are rejected until a verifier lands).
+
+use std::collections::BTreeSet;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ed25519_dalek::{Signature, VerifyingKey};
+use ron_proto::{MultiSigNofM, SignatureAlg};
+use serde::{Deserialize, Serialize};
+
+use super::GovError;
+
+/// One approver key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Signer {
+    pub signer_id: String,
+    pub alg: SignatureAlg,
+    /// Raw public key, standard base64 (32 bytes for Ed25519).
+    pub public_key_b64: String,
+}
+
+/// Proposed membership of the next signer set (content of a signer-set change).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignerSetSpec {
+    pub signers: Vec<Signer>,
+    pub quorum: MultiSigNofM,
+}
+
+impl SignerSetSpec {
+    /// Content id a signer-set proposal's descriptor must carry (`b3:` of the canonical JSON).
+    pub fn cid(&self) -> String {
+        let bytes = serde_json::to_vec(self).unwrap_or_default();
+        format!("b3:{}", blake3::hash(&bytes).to_hex())
+    }
+}
+
+/// The active signer set at `version` (genesis = 1).
+#[derive(Debug, Clone, Serialize)]
+pub struct SignerSet {
+    pub version: u64,
+    pub signers: Vec<Signer>,
+    pub quorum: MultiSigNofM,
+    #[serde(skip)]
+    keys: Vec<VerifyingKey>,
+}
+
+impl SignerSet {
+    pub fn new(version: u64, spec: SignerSetSpec) -> Result<Self, GovError> {
+        let SignerSetSpec { signers, quorum } = spec;
+        let invalid = |msg: String| Err(GovError::InvalidSignerSet(msg));
+
+        if signers.is_empty() {
+            return invalid("no signers".into());
+        }
+        if usize::from(quorum.n) != signers.len() {
+            return invalid(format!(
+                "quorum.n = {} but {} signers listed",
+                quorum.n,
+                signers.len()
+            ));
+        }
+        if quorum.m == 0 || quorum.m > quorum.n {
+            return invalid(format!("quorum {}-of-{} out of range", quorum.m, quorum.n));
+        }
+
+        let mut ids = BTreeSet::new();
+        let mut keys = Vec::with_capacity(signers.len());
+        for s in &signers {
+            if s.signer_id.trim().is_empty() || !ids.insert(s.signer_id.as_str()) {
+                return invalid(format!("empty or duplicate signer_id {:?}", s.signer_id));
+            }
+            if s.alg != SignatureAlg::Ed25519 {
+                return Err(GovError::UnsupportedAlg);
+            }
+            let key = STANDARD
+                .decode(&s.public_key_b64)
+                .ok()
+                .and_then(|b| <[u8; 32]>::try_from(b).ok())
+                .and_then(|b| VerifyingKey::from_bytes(&b).ok());
+            match key {
+                Some(k) => keys.push(k),
+                None => return invalid(format!("bad public key for {}", s.signer_id)),
+            }
+        }
+
+        Ok(Self {
+            version,
+            signers,
+            quorum,
+            keys,
+        })
+    }
+
+    pub fn contains(&self, signer_id: &str) -> bool {
+        self.signers.iter().any(|s| s.signer_id == signer_id)
+    }
+
+    /// Verify a detached signature by `signer_id` over `msg` (strict Ed25519).
+    pub fn verify(&self, signer_id: &str, msg: &[u8], sig_b64: &str) -> Result<(), GovError> {
+        let idx = self
+            .signers
+            .iter()
+            .position(|s| s.signer_id == signer_id)
+            .ok_or(GovError::UnknownSigner)?;
+        let sig = STANDARD
+            .decode(sig_b64)
+            .ok()
+            .and_then(|b| Signature::from_slice(&b).ok())
+            .ok_or(GovError::BadSignature)?;
+        self.keys[idx]
+            .verify_strict(msg, &sig)
+            .map_err(|_| GovError::BadSignature)
+    }
+}
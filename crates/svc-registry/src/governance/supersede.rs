@@ -1 +1,53 @@
-/*! Supersede mechanics (scaffold) */
+//! RO:WHAT — Supersede rules: when a pending proposal can no longer commit.
+//! RO:WHY  — A commit moves HEAD (stale bases) and a signer-set change invalidates every approval
+//!           gathered under the old set; both must be decided the same way everywhere.
+//! RO:INVARIANTS — superseded is terminal; rules depend only on (base, set version) vs current state.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum SupersedeReason {
+    /// HEAD moved past the version the proposal was based on.
+    StaleBase {
+        base_version: u64,
+        head_version: u64,
+    },
+    /// The signer set rotated; approvals under the old set no longer count.
+    SignerSetRotated { from: u64, to: u64 },
+}
+
+impl std::fmt::Display for SupersedeReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StaleBase {
+                base_version,
+                head_version,
+            } => write!(f, "based on version {base_version}, head is {head_version}"),
+            Self::SignerSetRotated { from, to } => {
+                write!(f, "signer set rotated from v{from} to v{to}")
+            }
+        }
+    }
+}
+
+/// Why a proposal on (`base_version`, `signer_set_version`) is superseded now, if it is.
+///
+/// Signer-set rotation wins over staleness: it is the reason re-approval would not help.
+pub fn check(
+    base_version: u64,
+    signer_set_version: u64,
+    head_version: u64,
+    current_set_version: u64,
+) -> Option<SupersedeReason> {
+    if signer_set_version != current_set_version {
+        return Some(SupersedeReason::SignerSetRotated {
+            from: signer_set_version,
+            to: current_set_version,
+        });
+    }
+    (base_version != head_version).then_some(SupersedeReason::StaleBase {
+        base_version,
+        head_version,
+    })
+}
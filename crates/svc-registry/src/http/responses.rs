@@ -2,9 +2,36 @@
 use axum::{response::IntoResponse, Json};
 use http::StatusCode;
 
+use crate::governance::GovError;
+
 pub fn err(status: StatusCode, code: &str, message: &str, corr_id: &str) -> impl IntoResponse {
     let body = serde_json::json!({
         "error": { "code": code, "message": message, "corr_id": corr_id }
     });
     (status, Json(body))
 }
+
+/// HTTP status for a governance rejection (codes documented in docs/API.MD).
+pub fn gov_status(e: &GovError) -> StatusCode {
+    match e {
+        GovError::NoSignerSet => StatusCode::SERVICE_UNAVAILABLE,
+        GovError::InvalidSignerSet(_) | GovError::BadProposal(_) => StatusCode::BAD_REQUEST,
+        GovError::NotFound => StatusCode::NOT_FOUND,
+        GovError::UnknownSigner => StatusCode::FORBIDDEN,
+        GovError::UnsupportedAlg | GovError::BadSignature | GovError::Expired => {
+            StatusCode::UNPROCESSABLE_ENTITY
+        }
+        GovError::DuplicateApproval
+        | GovError::StaleVersion { .. }
+        | GovError::Superseded(_)
+        | GovError::AlreadyCommitted(_) => StatusCode::CONFLICT,
+        GovError::NoQuorum { .. } => StatusCode::PRECONDITION_FAILED,
+        GovError::TooManyProposals => StatusCode::TOO_MANY_REQUESTS,
+        GovError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Error envelope for a governance rejection.
+pub fn gov_err(e: &GovError, corr_id: &str) -> impl IntoResponse {
+    err(gov_status(e), e.code(), &e.to_string(), corr_id)
+}
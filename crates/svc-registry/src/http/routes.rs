@@ -1,10 +1,11 @@
-//! RO:WHAT — API routes: read plane + SSE + commit write path.
-//! RO:INVARIANTS — POST /registry/commit monotonic bump; idempotency-by-value left to client.
+//! RO:WHAT — API routes: read plane + SSE + governed write path (propose → approve → commit).
+//! RO:INVARIANTS — HEAD moves only via POST /registry/commit/:id after quorum; rejections counted
+//!                 in rejected_total{reason} with the stable GovError code.
 
 use std::sync::Arc;
 
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     response::IntoResponse,
     routing::{get, post},
     Json, Router,
@@ -22,8 +23,10 @@ use super::middleware::{
 };
 use super::sse::sse_stream;
 use crate::config::model::Config;
+use crate::governance::{Approval, GovError};
 use crate::http::responses;
 use crate::observability::metrics::RegistryMetrics;
+use crate::pipeline::{Pipeline, ProposalReq};
 use crate::storage::RegistryStore;
 
 /// Shared application state for HTTP handlers.
@@ -31,15 +34,29 @@ use crate::storage::RegistryStore;
 pub struct AppState {
     pub metrics: RegistryMetrics,
     pub store: Arc<dyn RegistryStore>,
+    pub pipeline: Arc<Pipeline>,
 
     // Config bits handlers need fast access to:
     pub sse_heartbeat_ms: u64,
 }
 
+/// Routes with a pipeline built from `cfg.governance`; an invalid genesis set fails closed.
 pub fn registry_routes_with_cfg(
     metrics: RegistryMetrics,
     store: Arc<dyn RegistryStore>,
     cfg: &Config,
+) -> Router {
+    let pipeline = Pipeline::from_config(store.clone(), &cfg.governance).unwrap_or_else(|e| {
+        tracing::error!(error = %e, "governance config rejected; write plane disabled");
+        Pipeline::new(store, None, cfg.governance.max_pending)
+    });
+    registry_routes_with_pipeline(metrics, Arc::new(pipeline), cfg)
+}
+
+pub fn registry_routes_with_pipeline(
+    metrics: RegistryMetrics,
+    pipeline: Arc<Pipeline>,
+    cfg: &Config,
 ) -> Router {
     // Construct auth cfg and *read* a field so it’s not dead code until real auth wired.
     let auth = AuthCfg::default();
@@ -47,7 +64,8 @@ pub fn registry_routes_with_cfg(
 
     let state = AppState {
         metrics: metrics.clone(),
-        store,
+        store: pipeline.store().clone(),
+        pipeline,
         sse_heartbeat_ms: cfg.sse.heartbeat_ms,
     };
 
@@ -70,7 +88,14 @@ pub fn registry_routes_with_cfg(
 
     Router::new()
         .route("/registry/head", get(get_head))
-        .route("/registry/commit", post(post_commit))
+        .route("/registry/log", get(get_log))
+        .route("/registry/checkpoint", get(get_checkpoint))
+        .route("/registry/:version", get(get_version))
+        .route("/registry/signers", get(get_signers))
+        .route("/registry/proposals", post(post_proposal))
+        .route("/registry/proposals/:proposal_id", get(get_proposal))
+        .route("/registry/approvals/:proposal_id", post(post_approval))
+        .route("/registry/commit/:proposal_id", post(post_commit))
         .route("/registry/stream", get(sse_stream))
         .fallback(|| async { (StatusCode::NOT_FOUND, "not found") })
         .with_state(state)
@@ -99,39 +124,171 @@ async fn get_head(State(st): State<AppState>) -> impl IntoResponse {
     Json(head)
 }
 
-/// POST /registry/commit
 #[derive(Debug, serde::Deserialize)]
-struct CommitReq {
-    /// b3:<base64> payload; validated minimally here.
-    payload_b3: String,
+struct LogQuery {
+    #[serde(default)]
+    from: u64,
+    #[serde(default = "default_page_limit")]
+    limit: usize,
+}
+
+fn default_page_limit() -> usize {
+    100
+}
+
+/// GET /registry/log?from=&limit= — committed versions, oldest first.
+async fn get_log(State(st): State<AppState>, Query(q): Query<LogQuery>) -> impl IntoResponse {
+    match st.store.page(q.from, q.limit).await {
+        Ok(page) => Json(page).into_response(),
+        Err(e) => responses::err(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Storage",
+            &e.to_string(),
+            "",
+        )
+        .into_response(),
+    }
 }
 
-async fn post_commit(State(st): State<AppState>, Json(req): Json<CommitReq>) -> impl IntoResponse {
-    if !req.payload_b3.starts_with("b3:") {
+/// GET /registry/checkpoint — latest signed checkpoint.
+async fn get_checkpoint(State(st): State<AppState>) -> impl IntoResponse {
+    match st.store.latest_checkpoint().await {
+        Some(cp) => Json(cp).into_response(),
+        None => responses::err(StatusCode::NOT_FOUND, "NotFound", "no checkpoint yet", "")
+            .into_response(),
+    }
+}
+
+/// GET /registry/:version — one historical entry (410 once pruned behind a checkpoint).
+async fn get_version(State(st): State<AppState>, Path(version): Path<u64>) -> impl IntoResponse {
+    let page = match st.store.page(version, 1).await {
+        Ok(p) => p,
+        Err(e) => {
+            return responses::err(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Storage",
+                &e.to_string(),
+                "",
+            )
+            .into_response()
+        }
+    };
+    if version < page.first_retained && version > 0 {
         return responses::err(
-            StatusCode::BAD_REQUEST,
-            "invalid_payload",
-            "payload_b3 must start with 'b3:'",
+            StatusCode::GONE,
+            "Pruned",
+            &format!(
+                "version {version} pruned; oldest retained is {}",
+                page.first_retained
+            ),
             "",
         )
         .into_response();
     }
+    match page
+        .entries
+        .into_iter()
+        .next()
+        .filter(|e| e.version == version)
+    {
+        Some(e) => Json(e).into_response(),
+        None => {
+            responses::err(StatusCode::NOT_FOUND, "NotFound", "no such version", "").into_response()
+        }
+    }
+}
+
+fn now_s() -> u64 {
+    u64::try_from(chrono::Utc::now().timestamp()).unwrap_or(0)
+}
+
+fn reject(st: &AppState, e: &GovError) -> axum::response::Response {
+    st.metrics.inc_rejected(e.code());
+    responses::gov_err(e, "").into_response()
+}
+
+/// GET /registry/signers — current signer set (503 when governance writes are disabled).
+async fn get_signers(State(st): State<AppState>) -> impl IntoResponse {
+    match st.pipeline.signer_set() {
+        Some(set) => Json(set).into_response(),
+        None => responses::gov_err(&GovError::NoSignerSet, "").into_response(),
+    }
+}
+
+/// POST /registry/proposals
+async fn post_proposal(
+    State(st): State<AppState>,
+    Json(req): Json<ProposalReq>,
+) -> impl IntoResponse {
+    let res = st.pipeline.propose(req, now_s()).await;
+    st.metrics
+        .set_pending_proposals(st.pipeline.pending_count());
+    match res {
+        Ok(p) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({
+                "proposal_id": p.id,
+                "base_version": p.base_version,
+                "signer_set_version": p.signer_set_version,
+                "expires_at": p.descriptor.expires_at,
+                "quorum": p.descriptor.quorum,
+            })),
+        )
+            .into_response(),
+        Err(e) => reject(&st, &e),
+    }
+}
+
+/// GET /registry/proposals/:proposal_id
+async fn get_proposal(
+    State(st): State<AppState>,
+    Path(proposal_id): Path<String>,
+) -> impl IntoResponse {
+    match st.pipeline.proposal(&proposal_id) {
+        Some(p) => Json(p).into_response(),
+        None => responses::gov_err(&GovError::NotFound, "").into_response(),
+    }
+}
+
+/// POST /registry/approvals/:proposal_id
+async fn post_approval(
+    State(st): State<AppState>,
+    Path(proposal_id): Path<String>,
+    Json(approval): Json<Approval>,
+) -> impl IntoResponse {
+    let res = st.pipeline.approve(&proposal_id, approval, now_s()).await;
+    st.metrics
+        .set_pending_proposals(st.pipeline.pending_count());
+    match res {
+        Ok((p, q)) => Json(serde_json::json!({
+            "status": "accepted",
+            "proposal_id": p.id,
+            "approvals": q.have,
+            "quorum": { "m": q.need, "n": q.n },
+            "ready": q.reached(),
+        }))
+        .into_response(),
+        Err(e) => reject(&st, &e),
+    }
+}
 
-    match st.store.commit(req.payload_b3).await {
-        Ok(head) => {
+/// POST /registry/commit/:proposal_id
+async fn post_commit(
+    State(st): State<AppState>,
+    Path(proposal_id): Path<String>,
+) -> impl IntoResponse {
+    let res = st.pipeline.commit(&proposal_id, now_s()).await;
+    st.metrics
+        .set_pending_proposals(st.pipeline.pending_count());
+    match res {
+        Ok((head, _)) => {
             st.metrics.inc_commit_ok();
             st.metrics.set_head_version(head.version);
-            (StatusCode::OK, Json(head)).into_response()
+            (StatusCode::CREATED, Json(head)).into_response()
         }
         Err(e) => {
             st.metrics.inc_commit_err();
-            responses::err(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "commit_failed",
-                &e.to_string(),
-                "",
-            )
-            .into_response()
+            reject(&st, &e)
         }
     }
 }
@@ -7,8 +7,10 @@
 pub mod build_info;
 pub mod config;
 pub mod error;
+pub mod governance;
 pub mod http;
 pub mod observability;
+pub mod pipeline;
 pub mod shutdown;
 pub mod storage; // <-- new
 
@@ -1,11 +1,5 @@
 #![deny(clippy::unwrap_used, clippy::expect_used, clippy::await_holding_lock)]
 
-mod build_info;
-mod config;
-mod http;
-mod observability;
-mod storage;
-
 use std::{net::SocketAddr, sync::Arc};
 
 use axum::Router;
@@ -13,28 +7,53 @@ use ron_kernel::{wait_for_ctrl_c, HealthState};
 use tokio::net::TcpListener;
 use tracing::info;
 
-use crate::config::load::load_config;
-use crate::http::routes::registry_routes_with_cfg;
-use crate::observability::endpoints::{admin_router, set_queues_ok, set_services_ok, AdminState};
-use crate::observability::tracing::SERVICE_NAME;
-use crate::storage::inmem::InMemoryStore;
-use crate::storage::RegistryStore; // bring trait into scope for .subscribe()
+use svc_registry::config::load::load_config;
+use svc_registry::http::routes::registry_routes_with_pipeline;
+use svc_registry::observability::endpoints::{
+    admin_router, set_queues_ok, set_services_ok, AdminState,
+};
+use svc_registry::observability::tracing::SERVICE_NAME;
+use svc_registry::pipeline::{checkpoint::spawn_checkpointor, Pipeline};
+use svc_registry::storage::inmem::InMemoryStore;
+use svc_registry::storage::sled_store::SledStore;
+use svc_registry::storage::RegistryStore; // bring trait into scope for .subscribe()
+use svc_registry::{build_info, config, observability};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Use the shared logger in observability::logging to avoid dead-code.
-    crate::observability::logging::init_tracing();
+    observability::logging::init_tracing();
 
     // Load config (env/file precedence handled in load_config)
     let cfg = load_config(None)?;
-    let metrics = crate::observability::metrics::RegistryMetrics::new();
+    let metrics = observability::metrics::RegistryMetrics::new();
     let health = Arc::new(HealthState::default());
 
     // Spawn (stub) config reloader so the function isn't dead code.
-    crate::config::reload::spawn_reloader();
-
-    // Storage (in-memory for beta); after created, flip services_ok.
-    let store = Arc::new(InMemoryStore::new());
+    config::reload::spawn_reloader();
+
+    // Storage: open + replay the durable log (refuses to start if it does not verify);
+    // after created, flip services_ok.
+    let store: Arc<dyn RegistryStore> = match cfg.storage.kind.as_str() {
+        "memory" => Arc::new(InMemoryStore::new()),
+        _ => {
+            let sled = Arc::new(SledStore::open(&cfg.storage)?);
+            let head = sled.head().await;
+            info!(
+                version = head.version,
+                first_retained = sled.first_retained(),
+                "registry log replayed"
+            );
+            spawn_checkpointor(
+                sled.clone(),
+                cfg.storage.checkpoint_interval,
+                cfg.retention.max_count,
+                metrics.clone(),
+            );
+            sled
+        }
+    };
+    metrics.set_head_version(store.head().await.version);
     set_services_ok(&health, true);
 
     // Probe queues by trying a subscribe; on success flip queues_ok.
@@ -49,7 +68,13 @@ async fn main() -> anyhow::Result<()> {
         build: build_info::build_info(),
         metrics: metrics.clone(),
     });
-    let api: Router = registry_routes_with_cfg(metrics.clone(), store.clone(), &cfg);
+    // Governance pipeline: genesis signer set from config; refuse to start on a bad one.
+    let pipeline = Arc::new(Pipeline::from_config(store.clone(), &cfg.governance)?);
+    pipeline.restore_signer_set().await?;
+    if pipeline.signer_set().is_none() {
+        tracing::warn!("no [governance] signers configured; write plane disabled");
+    }
+    let api: Router = registry_routes_with_pipeline(metrics.clone(), pipeline, &cfg);
 
     // Bind (cfg.metrics_addr == admin plane; cfg.bind_addr == public API)
     let admin_addr: SocketAddr = cfg.metrics_addr.parse()?;
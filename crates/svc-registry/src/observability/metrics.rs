@@ -22,6 +22,12 @@ pub struct RegistryMetrics {
     pub registry_commits_total: IntCounterVec,
     /// Current head version (gauge).
     pub registry_head_version: Gauge,
+    /// Governance proposals awaiting quorum (gauge).
+    pub registry_pending_proposals: Gauge,
+    /// Governance rejections by stable error code: {reason}.
+    pub rejected_total: IntCounterVec,
+    /// Seconds since the last signed checkpoint (gauge).
+    pub registry_checkpoint_age_seconds: Gauge,
 
     /// SSE lifecycle counters.
     pub registry_sse_clients_connected_total: IntCounter,
@@ -39,7 +45,7 @@ impl Default for RegistryMetrics {
 impl RegistryMetrics {
     /// Return a clone of the singleton metrics set; first caller registers.
     pub fn new() -> Self {
-        METRICS_ONCE.get_or_init(|| Self::register_all()).clone()
+        METRICS_ONCE.get_or_init(Self::register_all).clone()
     }
 
     /// Increment commit-success counter.
@@ -59,6 +65,21 @@ impl RegistryMetrics {
         self.registry_head_version.set(v as f64);
     }
 
+    /// Update the pending-proposals gauge.
+    pub fn set_pending_proposals(&self, n: usize) {
+        self.registry_pending_proposals.set(n as f64);
+    }
+
+    /// Count a rejected governance request by reason code.
+    pub fn inc_rejected(&self, reason: &str) {
+        self.rejected_total.with_label_values(&[reason]).inc();
+    }
+
+    /// Update the checkpoint-age gauge.
+    pub fn set_checkpoint_age(&self, secs: f64) {
+        self.registry_checkpoint_age_seconds.set(secs);
+    }
+
     /// Record an SSE **connect** event.
     pub fn sse_client_connected(&self) {
         self.registry_sse_clients_connected_total.inc();
@@ -115,6 +136,28 @@ impl RegistryMetrics {
             register_gauge!("registry_head_version", "Current registry head version")
                 .expect("register registry_head_version");
 
+        // Governance
+        let registry_pending_proposals = register_gauge!(
+            "registry_pending_proposals",
+            "Governance proposals awaiting quorum"
+        )
+        .expect("register registry_pending_proposals");
+
+        let rejected_total = register_int_counter_vec!(
+            opts!(
+                "rejected_total",
+                "Rejected governance requests by reason code"
+            ),
+            &["reason"]
+        )
+        .expect("register rejected_total");
+
+        let registry_checkpoint_age_seconds = register_gauge!(
+            "registry_checkpoint_age_seconds",
+            "Seconds since the last signed checkpoint"
+        )
+        .expect("register registry_checkpoint_age_seconds");
+
         // SSE lifecycle
         let registry_sse_clients_connected_total = register_int_counter!(
             "registry_sse_clients_connected_total",
@@ -133,6 +176,9 @@ impl RegistryMetrics {
             request_latency_seconds,
             registry_commits_total,
             registry_head_version,
+            registry_pending_proposals,
+            rejected_total,
+            registry_checkpoint_age_seconds,
             registry_sse_clients_connected_total,
             registry_sse_clients_disconnected_total,
         }
@@ -1 +1,43 @@
-/*! Add approval (scaffold) */
+//! RO:WHAT — Add a detached approval to a pending proposal.
+//! RO:WHY  — Approvals are verified on arrival so a bad signature is rejected before it is stored.
+//! RO:INVARIANTS — one approval per signer; a proposal that the current HEAD or signer set has
+//!                 superseded is marked terminal and rejects further approvals.
+
+use super::{Pipeline, Proposal, ProposalStatus};
+use crate::governance::{
+    approvals::add_approval, quorum, supersede, Approval, GovError, QuorumStatus,
+};
+
+impl Pipeline {
+    /// Verify and record `approval`; returns the proposal and its quorum status.
+    pub async fn approve(
+        &self,
+        proposal_id: &str,
+        approval: Approval,
+        now: u64,
+    ) -> Result<(Proposal, QuorumStatus), GovError> {
+        let head = self.store.head().await;
+        let mut st = self.state.lock();
+        let set = st.signer_set()?.clone();
+        let p = st.pending_mut(proposal_id)?;
+
+        if let Some(reason) = supersede::check(
+            p.base_version,
+            p.signer_set_version,
+            head.version,
+            set.version,
+        ) {
+            p.status = ProposalStatus::Superseded {
+                reason: reason.clone(),
+            };
+            return Err(GovError::Superseded(reason));
+        }
+        if now >= p.descriptor.expires_at {
+            return Err(GovError::Expired);
+        }
+
+        add_approval(&mut p.approvals, &set, proposal_id, approval)?;
+        let status = quorum::evaluate(&set, &p.descriptor.quorum, &p.id, &p.approvals);
+        Ok((p.clone(), status))
+    }
+}
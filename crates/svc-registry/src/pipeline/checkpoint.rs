@@ -1 +1,50 @@
-/*! Periodic checkpoints (scaffold) */
+//! RO:WHAT — Checkpointor: periodically sign HEAD, then let retention prune behind it.
+//! RO:WHY  — Checkpoints bound replay trust and are the only thing that makes pruning safe.
+//! RO:INTERACTS — storage::sled_store::SledStore, pipeline::retention, observability::metrics.
+//! RO:INVARIANTS — never blocks the committer for long (shares its writer gate briefly);
+//!                 failures are logged and retried next tick.
+
+use std::{sync::Arc, time::Duration};
+
+use chrono::Utc;
+
+use super::retention;
+use crate::{
+    observability::metrics::RegistryMetrics,
+    storage::{sled_store::SledStore, RegistryStore},
+};
+
+/// One checkpoint + retention pass.
+pub async fn run_once(store: &SledStore, max_count: u64) -> anyhow::Result<u64> {
+    store.checkpoint_now().await?;
+    let head = store.head().await;
+    let cp = store.latest_checkpoint().await.map(|c| c.version);
+    match retention::prune_below(store.first_retained(), head.version, cp, max_count) {
+        Some(below) => store.prune_below(below).await,
+        None => Ok(0),
+    }
+}
+
+pub fn spawn_checkpointor(
+    store: Arc<SledStore>,
+    interval: Duration,
+    max_count: u64,
+    metrics: RegistryMetrics,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(interval);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tick.tick().await;
+            match run_once(&store, max_count).await {
+                Ok(pruned) if pruned > 0 => tracing::info!(pruned, "retention pruned log"),
+                Ok(_) => {}
+                Err(e) => tracing::warn!(error = %e, "checkpoint/retention pass failed"),
+            }
+            if let Some(cp) = store.latest_checkpoint().await {
+                let age = (Utc::now() - cp.created_at).num_seconds().max(0);
+                metrics.set_checkpoint_age(age as f64);
+            }
+        }
+    })
+}
@@ -1 +1,88 @@
-/*! Single-writer commit path (scaffold) */
+//! RO:WHAT — Single-writer commit: move HEAD to an approved proposal's descriptor.
+//! RO:WHY  — [I-2]/[I-3] HEAD advances by exactly one version per quorum-approved proposal.
+//! RO:INVARIANTS — commits serialized by `writer`; CAS on HEAD (base_version == head.version);
+//!                 a signer-set change installs set v+1 and supersedes every other pending proposal;
+//!                 the new set is written in the same store transaction as the HEAD move.
+
+use super::{deep_verify::verify_proposal, Change, Pipeline, Proposal, ProposalStatus};
+use crate::{
+    governance::{supersede, GovError, SignerSet},
+    storage::Head,
+};
+
+impl Pipeline {
+    /// Commit `proposal_id` if it still applies and carries a quorum of valid approvals.
+    pub async fn commit(&self, proposal_id: &str, now: u64) -> Result<(Head, Proposal), GovError> {
+        let _writer = self.writer.lock().await;
+        let head = self.store.head().await;
+
+        let (payload_b3, rotated) = {
+            let mut st = self.state.lock();
+            let set = st.signer_set()?.clone();
+            let p = st.pending_mut(proposal_id)?;
+            if let Some(reason) = supersede::check(
+                p.base_version,
+                p.signer_set_version,
+                head.version,
+                set.version,
+            ) {
+                p.status = ProposalStatus::Superseded {
+                    reason: reason.clone(),
+                };
+                return Err(GovError::Superseded(reason));
+            }
+            let status = verify_proposal(&set, p, now)?;
+            if !status.reached() {
+                return Err(GovError::NoQuorum {
+                    have: status.have,
+                    need: status.need,
+                });
+            }
+            let rotated = match &p.change {
+                Change::SignerSet { next } => {
+                    Some(SignerSet::new(set.version.saturating_add(1), next.clone())?)
+                }
+                Change::Descriptor => None,
+            };
+            (p.descriptor.descriptor_cid.to_string(), rotated)
+        };
+
+        // Holding only the async writer lock here; other proposals may still gather approvals.
+        // A rotated set is persisted atomically with HEAD, then installed.
+        let new_head = match &rotated {
+            Some(set) => {
+                let json = serde_json::to_vec(set).map_err(|e| GovError::Storage(e.to_string()))?;
+                self.store.commit_with_signer_set(payload_b3, json).await
+            }
+            None => self.store.commit(payload_b3).await,
+        }
+        .map_err(|e| GovError::Storage(e.to_string()))?;
+
+        let mut st = self.state.lock();
+        if let Some(set) = rotated {
+            st.signer_set = Some(set);
+        }
+        let set_version = st.signer_set()?.version;
+
+        let mut committed = None;
+        for p in st.proposals.values_mut() {
+            if p.id == proposal_id {
+                p.status = ProposalStatus::Committed {
+                    version: new_head.version,
+                };
+                committed = Some(p.clone());
+            } else if p.status == ProposalStatus::Pending {
+                if let Some(reason) = supersede::check(
+                    p.base_version,
+                    p.signer_set_version,
+                    new_head.version,
+                    set_version,
+                ) {
+                    p.status = ProposalStatus::Superseded { reason };
+                }
+            }
+        }
+        let committed = committed.ok_or(GovError::NotFound)?;
+        Ok((new_head, committed))
+    }
+}
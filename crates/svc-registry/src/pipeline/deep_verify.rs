@@ -1 +1,29 @@
-/*! Background integrity verification (scaffold) */
+//! RO:WHAT — Commit-time deep verification of a stored proposal.
+//! RO:WHY  — Approvals were checked on arrival, but the signer set may have changed and the
+//!           record is about to become HEAD; re-derive everything rather than trust earlier checks.
+//! RO:INVARIANTS — recomputed id must match; every approval is re-verified against `set`.
+
+use super::{proposal_id, propose::check_content, Proposal};
+use crate::governance::{quorum, GovError, QuorumStatus, SignerSet};
+
+/// Re-check `p` end to end against `set` at `now`; returns the recomputed quorum status.
+pub fn verify_proposal(set: &SignerSet, p: &Proposal, now: u64) -> Result<QuorumStatus, GovError> {
+    if proposal_id(
+        &p.descriptor,
+        &p.change,
+        p.base_version,
+        p.signer_set_version,
+    ) != p.id
+    {
+        return Err(GovError::BadProposal(
+            "proposal id does not match its content".into(),
+        ));
+    }
+    check_content(set, &p.descriptor, &p.change, now)?;
+    Ok(quorum::evaluate(
+        set,
+        &p.descriptor.quorum,
+        &p.id,
+        &p.approvals,
+    ))
+}
@@ -1 +1,186 @@
-/*! Pipeline wiring (scaffold) */
+//! RO:WHAT — Governed write pipeline: propose → approve → commit (single writer).
+//! RO:WHY  — [C-3] HEAD only moves for a proposal that reached quorum of the current signer set.
+//! RO:INTERACTS — governance::* (pure checks), storage::RegistryStore (HEAD), http::routes.
+//! RO:INVARIANTS — no sync lock held across .await; commits serialized by `writer`; proposals are
+//!                 terminal once committed or superseded; pending proposals are bounded.
+//! RO:NOTES — Proposals and their approvals live in memory only: a restart drops every pending
+//!            proposal (signers re-propose and re-approve). Committed history and the signer set
+//!            are durable in the store and restored on boot.
+
+pub mod approve;
+pub mod checkpoint;
+pub mod commit;
+pub mod deep_verify;
+pub mod propose;
+pub mod retention;
+
+pub use propose::{proposal_id, Change, Proposal, ProposalReq, ProposalStatus};
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+use ron_proto::MultiSigNofM;
+use serde::Deserialize;
+
+use crate::{
+    config::model::Governance,
+    governance::{GovError, Signer, SignerSet, SignerSetSpec},
+    storage::RegistryStore,
+};
+
+/// Future-dated descriptors are tolerated up to this much clock skew.
+pub const MAX_FUTURE_SKEW_S: u64 = 300;
+
+pub struct Pipeline {
+    store: Arc<dyn RegistryStore>,
+    state: Mutex<GovState>,
+    writer: tokio::sync::Mutex<()>,
+    max_pending: usize,
+}
+
+/// Wire form of a persisted `SignerSet` (keys are re-derived on load).
+#[derive(Deserialize)]
+struct StoredSignerSet {
+    version: u64,
+    signers: Vec<Signer>,
+    quorum: MultiSigNofM,
+}
+
+struct GovState {
+    signer_set: Option<SignerSet>,
+    proposals: HashMap<String, Proposal>,
+    /// Insertion order, for evicting terminal proposals.
+    order: VecDeque<String>,
+}
+
+impl Pipeline {
+    /// `signer_set = None` fails closed: every write is rejected with `NoSignerSet`.
+    pub fn new(
+        store: Arc<dyn RegistryStore>,
+        signer_set: Option<SignerSet>,
+        max_pending: usize,
+    ) -> Self {
+        Self {
+            store,
+            state: Mutex::new(GovState {
+                signer_set,
+                proposals: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+            writer: tokio::sync::Mutex::new(()),
+            max_pending: max_pending.max(1),
+        }
+    }
+
+    /// Genesis signer set (version 1) from `[governance]`; an empty signer list disables writes.
+    pub fn from_config(store: Arc<dyn RegistryStore>, cfg: &Governance) -> Result<Self, GovError> {
+        let set = if cfg.signers.is_empty() {
+            None
+        } else {
+            let n = u8::try_from(cfg.signers.len())
+                .map_err(|_| GovError::InvalidSignerSet("more than 255 signers".into()))?;
+            Some(SignerSet::new(
+                1,
+                SignerSetSpec {
+                    signers: cfg.signers.clone(),
+                    quorum: MultiSigNofM {
+                        n,
+                        m: cfg.threshold,
+                    },
+                },
+            )?)
+        };
+        Ok(Self::new(store, set, cfg.max_pending))
+    }
+
+    /// Resume with the signer set persisted by the last rotation, if newer than the genesis set.
+    pub async fn restore_signer_set(&self) -> Result<(), GovError> {
+        let Some(bytes) = self
+            .store
+            .signer_set()
+            .await
+            .map_err(|e| GovError::Storage(e.to_string()))?
+        else {
+            return Ok(());
+        };
+        let stored: StoredSignerSet = serde_json::from_slice(&bytes)
+            .map_err(|e| GovError::InvalidSignerSet(format!("persisted signer set: {e}")))?;
+        let set = SignerSet::new(
+            stored.version,
+            SignerSetSpec {
+                signers: stored.signers,
+                quorum: stored.quorum,
+            },
+        )?;
+        let mut st = self.state.lock();
+        if st
+            .signer_set
+            .as_ref()
+            .is_none_or(|cur| set.version > cur.version)
+        {
+            st.signer_set = Some(set);
+        }
+        Ok(())
+    }
+
+    pub fn store(&self) -> &Arc<dyn RegistryStore> {
+        &self.store
+    }
+
+    /// Current signer set, if governance writes are enabled.
+    pub fn signer_set(&self) -> Option<SignerSet> {
+        self.state.lock().signer_set.clone()
+    }
+
+    pub fn proposal(&self, id: &str) -> Option<Proposal> {
+        self.state.lock().proposals.get(id).cloned()
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.state.lock().pending_count()
+    }
+}
+
+impl GovState {
+    fn pending_count(&self) -> usize {
+        self.proposals
+            .values()
+            .filter(|p| p.status == ProposalStatus::Pending)
+            .count()
+    }
+
+    fn signer_set(&self) -> Result<&SignerSet, GovError> {
+        self.signer_set.as_ref().ok_or(GovError::NoSignerSet)
+    }
+
+    /// Mutable pending proposal, or the error its terminal status implies.
+    fn pending_mut(&mut self, id: &str) -> Result<&mut Proposal, GovError> {
+        let p = self.proposals.get_mut(id).ok_or(GovError::NotFound)?;
+        match &p.status {
+            ProposalStatus::Pending => Ok(p),
+            ProposalStatus::Committed { version } => Err(GovError::AlreadyCommitted(*version)),
+            ProposalStatus::Superseded { reason } => Err(GovError::Superseded(reason.clone())),
+        }
+    }
+
+    fn insert(&mut self, p: Proposal, cap: usize) {
+        // Keep at most `2 * cap` records; drop the oldest terminal ones first.
+        while self.proposals.len() >= cap.saturating_mul(2) {
+            let Some(pos) = self.order.iter().position(|id| {
+                self.proposals
+                    .get(id)
+                    .is_some_and(|p| p.status != ProposalStatus::Pending)
+            }) else {
+                break;
+            };
+            if let Some(id) = self.order.remove(pos) {
+                self.proposals.remove(&id);
+            }
+        }
+        self.order.push_back(p.id.clone());
+        self.proposals.insert(p.id.clone(), p);
+    }
+}
@@ -1 +1,145 @@
-/*! Accept proposal, enqueue (scaffold) */
+//! RO:WHAT — Accept a proposal: a `SignedDescriptorV1` (+ optional signer-set change) on a base version.
+//! RO:WHY  — Everything approvers sign is fixed here; the proposal id commits to all of it.
+//! RO:INVARIANTS — id = b3(PROPOSAL_DOMAIN || canonical JSON); base_version == HEAD at propose time;
+//!                 re-proposing identical content is idempotent.
+
+use ron_proto::{SignatureAlg, SignedDescriptorV1};
+use serde::{Deserialize, Serialize};
+
+use super::{GovState, Pipeline, MAX_FUTURE_SKEW_S};
+use crate::governance::{quorum, Approval, GovError, SignerSet, SignerSetSpec, SupersedeReason};
+
+/// Domain separator for proposal ids.
+pub const PROPOSAL_DOMAIN: &[u8] = b"ron/registry/proposal/v1\0";
+
+/// What committing the proposal changes besides HEAD.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Change {
+    /// HEAD moves to `descriptor.descriptor_cid`.
+    #[default]
+    Descriptor,
+    /// Also rotate the signer set to `next`; `descriptor_cid` must equal `next.cid()`.
+    SignerSet { next: SignerSetSpec },
+}
+
+/// POST /registry/proposals body.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProposalReq {
+    pub descriptor: SignedDescriptorV1,
+    pub base_version: u64,
+    #[serde(default)]
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending,
+    Committed { version: u64 },
+    Superseded { reason: SupersedeReason },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Proposal {
+    pub id: String,
+    pub descriptor: SignedDescriptorV1,
+    pub change: Change,
+    pub base_version: u64,
+    pub signer_set_version: u64,
+    pub created_at_s: u64,
+    pub approvals: Vec<Approval>,
+    pub status: ProposalStatus,
+}
+
+#[derive(Serialize)]
+struct ProposalBody<'a> {
+    descriptor: &'a SignedDescriptorV1,
+    change: &'a Change,
+    base_version: u64,
+    signer_set_version: u64,
+}
+
+/// Proposal id approvers sign over (see `governance::approval_message`).
+pub fn proposal_id(
+    descriptor: &SignedDescriptorV1,
+    change: &Change,
+    base_version: u64,
+    signer_set_version: u64,
+) -> String {
+    let body = serde_json::to_vec(&ProposalBody {
+        descriptor,
+        change,
+        base_version,
+        signer_set_version,
+    })
+    .unwrap_or_default();
+    let mut h = blake3::Hasher::new();
+    h.update(PROPOSAL_DOMAIN);
+    h.update(&body);
+    format!("b3:{}", h.finalize().to_hex())
+}
+
+/// Stateless checks shared by propose and commit-time deep verification.
+pub(crate) fn check_content(
+    set: &SignerSet,
+    descriptor: &SignedDescriptorV1,
+    change: &Change,
+    now: u64,
+) -> Result<(), GovError> {
+    if descriptor.alg != SignatureAlg::Ed25519 {
+        return Err(GovError::UnsupportedAlg);
+    }
+    if descriptor.issued_at > now.saturating_add(MAX_FUTURE_SKEW_S) || now >= descriptor.expires_at
+    {
+        return Err(GovError::Expired);
+    }
+    quorum::check_declared(set, &descriptor.quorum)?;
+    if let Change::SignerSet { next } = change {
+        SignerSet::new(set.version.saturating_add(1), next.clone())?;
+        if descriptor.descriptor_cid.as_str() != next.cid() {
+            return Err(GovError::BadProposal(
+                "descriptor_cid must be the b3 of the proposed signer set".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl Pipeline {
+    /// Record a pending proposal against the current HEAD and signer set.
+    pub async fn propose(&self, req: ProposalReq, now: u64) -> Result<Proposal, GovError> {
+        let head = self.store.head().await;
+        let mut st = self.state.lock();
+        let set = st.signer_set()?;
+        check_content(set, &req.descriptor, &req.change, now)?;
+        if req.base_version != head.version {
+            return Err(GovError::StaleVersion {
+                base: req.base_version,
+                head: head.version,
+            });
+        }
+
+        let id = proposal_id(&req.descriptor, &req.change, req.base_version, set.version);
+        if let Some(existing) = st.proposals.get(&id) {
+            return Ok(existing.clone());
+        }
+        if st.pending_count() >= self.max_pending {
+            return Err(GovError::TooManyProposals);
+        }
+
+        let proposal = Proposal {
+            id,
+            descriptor: req.descriptor,
+            change: req.change,
+            base_version: req.base_version,
+            signer_set_version: set.version,
+            created_at_s: now,
+            approvals: Vec::new(),
+            status: ProposalStatus::Pending,
+        };
+        GovState::insert(&mut st, proposal.clone(), self.max_pending);
+        Ok(proposal)
+    }
+}
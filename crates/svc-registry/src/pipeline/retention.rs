@@ -1 +1,19 @@
-/*! Retention/pruning (scaffold) */
+//! RO:WHAT — Retention policy: how far the log may be pruned.
+//! RO:WHY  — Bounded storage without losing verifiability: only checkpointed history may go.
+//! RO:INVARIANTS — never prunes the newest `max_count` versions nor past the latest checkpoint
+//!                 (the checkpointed entry itself stays as the anchor for replay).
+
+/// First version to keep, if pruning would remove anything.
+pub fn prune_below(
+    first_retained: u64,
+    head_version: u64,
+    checkpoint_version: Option<u64>,
+    max_count: u64,
+) -> Option<u64> {
+    if max_count == 0 {
+        return None;
+    }
+    let by_count = head_version.saturating_add(1).saturating_sub(max_count);
+    let below = by_count.min(checkpoint_version?);
+    (below > first_retained).then_some(below)
+}
@@ -1 +1,137 @@
-/*! Durable checkpoints (scaffold) */
+//! RO:WHAT — Signed checkpoints over the log chain, and the node key that signs them.
+//! RO:WHY  — Retention may only drop entries covered by a checkpoint; the signature lets replay
+//!           trust a pruned prefix it can no longer re-hash.
+//! RO:INTERACTS — storage::sled_store (persist/verify), pipeline::{checkpoint,retention}.
+//! RO:INVARIANTS — message = CHECKPOINT_DOMAIN || JSON{version, entry_hash, created_at};
+//!                 this key signs checkpoints only — it never counts toward governance quorum.
+
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer as _, SigningKey, Verifier as _, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Domain separator for checkpoint signatures.
+pub const CHECKPOINT_DOMAIN: &[u8] = b"ron/registry/checkpoint/v1\0";
+
+/// Signed statement "the log at `version` hashes to `entry_hash`".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub version: u64,
+    pub entry_hash: String,
+    pub created_at: DateTime<Utc>,
+    /// Signing key, raw 32 bytes in standard base64.
+    pub public_key_b64: String,
+    pub sig_b64: String,
+}
+
+#[derive(Serialize)]
+struct CheckpointBody<'a> {
+    version: u64,
+    entry_hash: &'a str,
+    created_at: &'a DateTime<Utc>,
+}
+
+fn message(version: u64, entry_hash: &str, created_at: &DateTime<Utc>) -> Vec<u8> {
+    let body = serde_json::to_vec(&CheckpointBody {
+        version,
+        entry_hash,
+        created_at,
+    })
+    .unwrap_or_default();
+    let mut msg = Vec::with_capacity(CHECKPOINT_DOMAIN.len() + body.len());
+    msg.extend_from_slice(CHECKPOINT_DOMAIN);
+    msg.extend_from_slice(&body);
+    msg
+}
+
+impl Checkpoint {
+    /// Verify the signature and that it was made by `expected`.
+    pub fn verify(&self, expected: &VerifyingKey) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.public_key_b64 == STANDARD.encode(expected.to_bytes()),
+            "checkpoint {} signed by an unknown key",
+            self.version
+        );
+        let sig = STANDARD
+            .decode(&self.sig_b64)
+            .ok()
+            .and_then(|b| Signature::from_slice(&b).ok())
+            .ok_or_else(|| anyhow::anyhow!("checkpoint {} signature malformed", self.version))?;
+        expected
+            .verify(
+                &message(self.version, &self.entry_hash, &self.created_at),
+                &sig,
+            )
+            .map_err(|_| anyhow::anyhow!("checkpoint {} signature invalid", self.version))
+    }
+}
+
+/// Node-local Ed25519 key for checkpoints (raw 32-byte seed on disk, 0600).
+pub struct CheckpointKey {
+    sk: SigningKey,
+}
+
+impl CheckpointKey {
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self {
+            sk: SigningKey::from_bytes(&seed),
+        }
+    }
+
+    /// Load the seed at `path`, creating a fresh one if the file does not exist.
+    pub fn load_or_create(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => {
+                let seed = <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+                    anyhow::anyhow!("{}: checkpoint key must be 32 bytes", path.display())
+                })?;
+                Ok(Self::from_seed(seed))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let mut seed = [0u8; 32];
+                rand::RngCore::fill_bytes(&mut rand::rng(), &mut seed);
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                write_private(path, &seed)?;
+                Ok(Self::from_seed(seed))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.sk.verifying_key()
+    }
+
+    pub fn sign(&self, version: u64, entry_hash: &str, created_at: DateTime<Utc>) -> Checkpoint {
+        let sig = self.sk.sign(&message(version, entry_hash, &created_at));
+        Checkpoint {
+            version,
+            entry_hash: entry_hash.to_string(),
+            created_at,
+            public_key_b64: STANDARD.encode(self.sk.verifying_key().to_bytes()),
+            sig_b64: STANDARD.encode(sig.to_bytes()),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write as _;
+    use std::os::unix::fs::OpenOptionsExt as _;
+    let mut f = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?;
+    f.write_all(bytes)?;
+    f.sync_all()
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    std::fs::write(path, bytes)
+}
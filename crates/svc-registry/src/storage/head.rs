@@ -1 +1,28 @@
-/*! HEAD snapshot & CAS (scaffold) */
+//! RO:WHAT — HEAD snapshot derived from the last log entry.
+//! RO:WHY  — Reads serve HEAD without touching the log; the snapshot is written in the same
+//!           transaction as the entry, and replay rejects a snapshot that disagrees with the log.
+//! RO:INVARIANTS — head.hash == last entry_hash (GENESIS_HASH when empty).
+
+use super::{log::LogEntry, log::GENESIS_HASH, Head};
+
+impl Head {
+    /// HEAD of the empty registry.
+    pub fn genesis() -> Self {
+        Self {
+            version: 0,
+            payload_b3: "b3:0".to_string(),
+            committed_at: None,
+            hash: GENESIS_HASH.to_string(),
+        }
+    }
+
+    /// HEAD after committing `e`.
+    pub fn from_entry(e: &LogEntry) -> Self {
+        Self {
+            version: e.version,
+            payload_b3: e.payload_b3.clone(),
+            committed_at: Some(e.committed_at),
+            hash: e.entry_hash.clone(),
+        }
+    }
+}
@@ -1,9 +1,12 @@
 //! RO:WHAT — In-memory RegistryStore implementation with broadcasted commit events.
-//! RO:WHY  — Fast foundation path; persistence can plug in later behind feature flags.
+//! RO:WHY  — Fast path for tests and amnesia mode; same hash chain as the durable backend.
 //! RO:INTERACTS — storage::RegistryStore trait; http::{routes,sse}.
-//! RO:INVARIANTS — Single-writer discipline by &mut on commit path via Mutex gate.
+//! RO:INVARIANTS — Single-writer discipline by &mut on commit path via Mutex gate; nothing pruned.
 
-use super::{Head, RegistryEvent, RegistryStore};
+use super::{
+    log::{LogEntry, LogPage, MAX_PAGE},
+    Head, RegistryEvent, RegistryStore,
+};
 use chrono::Utc;
 use std::sync::Arc;
 use tokio::sync::{broadcast, Mutex, RwLock};
@@ -14,6 +17,7 @@ const BROADCAST_CAPACITY: usize = 1024; // bounded, drops oldest when overrun
 #[derive(Clone)]
 pub struct InMemoryStore {
     head: Arc<RwLock<Head>>,
+    log: Arc<RwLock<Vec<LogEntry>>>,
     tx: broadcast::Sender<RegistryEvent>,
     // Single-writer commit gate; cheap because commit is tiny.
     writer: Arc<Mutex<()>>,
@@ -22,13 +26,9 @@ pub struct InMemoryStore {
 impl InMemoryStore {
     pub fn new() -> Self {
         let (tx, _rx) = broadcast::channel(BROADCAST_CAPACITY);
-        let head = Head {
-            version: 0,
-            payload_b3: "b3:0".to_string(),
-            committed_at: None,
-        };
         Self {
-            head: Arc::new(RwLock::new(head)),
+            head: Arc::new(RwLock::new(Head::genesis())),
+            log: Arc::new(RwLock::new(Vec::new())),
             tx,
             writer: Arc::new(Mutex::new(())),
         }
@@ -50,13 +50,11 @@ impl RegistryStore for InMemoryStore {
         // Single writer section
         let _guard = self.writer.lock().await;
 
-        // Bump version/commit time
+        // Append the chained entry, then bump HEAD
         let mut w = self.head.write().await;
-        let new_head = Head {
-            version: w.version.saturating_add(1),
-            payload_b3,
-            committed_at: Some(Utc::now()),
-        };
+        let entry = LogEntry::next(w.version, &w.hash, payload_b3, Utc::now());
+        let new_head = Head::from_entry(&entry);
+        self.log.write().await.push(entry);
         *w = new_head.clone();
 
         // Broadcast (best-effort; drop if no one is listening)
@@ -67,6 +65,29 @@ impl RegistryStore for InMemoryStore {
         Ok(new_head)
     }
 
+    async fn page(&self, from: u64, limit: usize) -> anyhow::Result<LogPage> {
+        let log = self.log.read().await;
+        let head_version = log.len() as u64;
+        let from = from.max(1);
+        let start = usize::try_from(from - 1).unwrap_or(usize::MAX);
+        let entries: Vec<LogEntry> = log
+            .iter()
+            .skip(start)
+            .take(limit.clamp(1, MAX_PAGE))
+            .cloned()
+            .collect();
+        let next_from = entries
+            .last()
+            .map(|e| e.version + 1)
+            .filter(|v| *v <= head_version);
+        Ok(LogPage {
+            entries,
+            next_from,
+            first_retained: 1,
+            head_version,
+        })
+    }
+
     fn subscribe(&self) -> BroadcastStream<RegistryEvent> {
         BroadcastStream::new(self.tx.subscribe())
     }
@@ -1,19 +1,30 @@
-//! RO:WHAT — Storage facade for svc-registry (trait + in-memory backend).
-//! RO:WHY  — Abstracts the read/write plane and the SSE event source.
-//! RO:INTERACTS — http::{routes,sse}, observability::metrics, readiness gate.
-//! RO:INVARIANTS — Monotonic head.version; subscribe is non-blocking.
+//! RO:WHAT — Storage facade for svc-registry (trait + in-memory and sled backends).
+//! RO:WHY  — Abstracts the read/write plane, history paging and the SSE event source.
+//! RO:INTERACTS — http::{routes,sse}, pipeline::{commit,checkpoint}, observability::metrics.
+//! RO:INVARIANTS — Monotonic head.version; head.hash chains every committed entry;
+//!                 subscribe is non-blocking.
 
+pub mod checkpoint;
+pub mod head;
 pub mod inmem;
+pub mod log;
+pub mod sled_store;
 
 use chrono::{DateTime, Utc};
 use tokio_stream::wrappers::BroadcastStream;
 
+pub use checkpoint::Checkpoint;
+pub use log::{LogEntry, LogPage};
+
 /// Public head shape returned to clients.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Head {
     pub version: u64,
     pub payload_b3: String,
     pub committed_at: Option<DateTime<Utc>>,
+    /// Chain hash of the entry at `version` (`log::GENESIS_HASH` when empty).
+    #[serde(default)]
+    pub hash: String,
 }
 
 /// Internal SSE events exposed by the store.
@@ -33,6 +44,39 @@ pub trait RegistryStore: Send + Sync {
     /// RO:INVARIANTS — version strictly increases; committed_at set to now.
     async fn commit(&self, payload_b3: String) -> anyhow::Result<Head>;
 
+    /// RO:WHAT — Page through committed versions, oldest first, starting at `from`.
+    /// RO:INVARIANTS — `limit` clamped to `log::MAX_PAGE`; versions below `first_retained` are
+    ///                 pruned and never returned.
+    async fn page(&self, from: u64, limit: usize) -> anyhow::Result<LogPage>;
+
+    /// RO:WHAT — Latest signed checkpoint, if this backend produces them.
+    async fn latest_checkpoint(&self) -> Option<Checkpoint> {
+        None
+    }
+
+    /// RO:WHAT — Persist the active governance signer set (opaque JSON) across restarts.
+    async fn put_signer_set(&self, _json: Vec<u8>) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// RO:WHAT — Commit `payload_b3` and persist `signer_set` (opaque JSON) as one write.
+    /// RO:INVARIANTS — durable backends apply both or neither, so a crash never leaves HEAD past a
+    ///                 rotation whose signer set was lost (or the reverse).
+    async fn commit_with_signer_set(
+        &self,
+        payload_b3: String,
+        signer_set: Vec<u8>,
+    ) -> anyhow::Result<Head> {
+        let head = self.commit(payload_b3).await?;
+        self.put_signer_set(signer_set).await?;
+        Ok(head)
+    }
+
+    /// RO:WHAT — Last persisted signer set, if any.
+    async fn signer_set(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
     /// RO:WHAT — Subscribe to store events (commit stream).
     /// RO:WHY  — Feeds SSE; non-blocking broadcast with bounded buffers.
     fn subscribe(&self) -> BroadcastStream<RegistryEvent>;
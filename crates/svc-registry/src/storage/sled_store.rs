@@ -1 +1,340 @@
-/*! Sled backend adapter (scaffold) */
+//! RO:WHAT — Durable RegistryStore on sled: append-only chained log, HEAD snapshot, checkpoints.
+//! RO:WHY  — Restarts must resume at the committed HEAD; history must be provably untampered.
+//! RO:INTERACTS — storage::{log,head,checkpoint}; pipeline::{checkpoint,retention}; main (boot).
+//! RO:INVARIANTS — entry + HEAD snapshot written in one transaction; open() replays the retained
+//!                 log and every checkpoint and refuses to start on any mismatch; pruning never
+//!                 passes the latest checkpoint.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use chrono::Utc;
+use parking_lot::RwLock;
+use sled::Transactional;
+use tokio::sync::{broadcast, Mutex};
+use tokio_stream::wrappers::BroadcastStream;
+
+use super::{
+    checkpoint::{Checkpoint, CheckpointKey},
+    log::{ChainCursor, LogEntry, LogPage, MAX_PAGE},
+    Head, RegistryEvent, RegistryStore,
+};
+use crate::config::model::Storage;
+
+const BROADCAST_CAPACITY: usize = 1024;
+const META_HEAD: &[u8] = b"head";
+const META_FIRST_RETAINED: &[u8] = b"first_retained";
+const META_SIGNER_SET: &[u8] = b"signer_set";
+
+pub struct SledStore {
+    db: sled::Db,
+    /// version (BE u64) → JSON LogEntry
+    log: sled::Tree,
+    /// HEAD snapshot + retention watermark
+    meta: sled::Tree,
+    /// version (BE u64) → JSON Checkpoint; never pruned
+    checkpoints: sled::Tree,
+    key: CheckpointKey,
+    fsync: bool,
+    head: RwLock<Head>,
+    first_retained: AtomicU64,
+    tx: broadcast::Sender<RegistryEvent>,
+    writer: Mutex<()>,
+}
+
+fn vkey(version: u64) -> [u8; 8] {
+    version.to_be_bytes()
+}
+
+fn decode<T: serde::de::DeserializeOwned>(what: &str, bytes: &[u8]) -> anyhow::Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| anyhow::anyhow!("corrupt {what}: {e}"))
+}
+
+impl SledStore {
+    /// Open from `[storage]`; the checkpoint key defaults to `<data_dir>/checkpoint.key`.
+    pub fn open(cfg: &Storage) -> anyhow::Result<Self> {
+        let dir = PathBuf::from(&cfg.data_dir);
+        let key_path = if cfg.checkpoint_key_path.is_empty() {
+            dir.join("checkpoint.key")
+        } else {
+            PathBuf::from(&cfg.checkpoint_key_path)
+        };
+        let key = CheckpointKey::load_or_create(&key_path)?;
+        Self::open_with(&dir.join("log"), key, cfg.fsync)
+    }
+
+    /// Open the database at `path` and replay it; fails if the log does not verify.
+    pub fn open_with(path: &Path, key: CheckpointKey, fsync: bool) -> anyhow::Result<Self> {
+        let db = sled::open(path)?;
+        let log = db.open_tree("log")?;
+        let meta = db.open_tree("meta")?;
+        let checkpoints = db.open_tree("checkpoints")?;
+        let (tx, _rx) = broadcast::channel(BROADCAST_CAPACITY);
+        let store = Self {
+            db,
+            log,
+            meta,
+            checkpoints,
+            key,
+            fsync,
+            head: RwLock::new(Head::genesis()),
+            first_retained: AtomicU64::new(1),
+            tx,
+            writer: Mutex::new(()),
+        };
+        let (head, first_retained) = store.replay()?;
+        *store.head.write() = head;
+        store.first_retained.store(first_retained, Ordering::SeqCst);
+        Ok(store)
+    }
+
+    /// Integrity check: re-hash the retained chain, match the HEAD snapshot, verify checkpoints.
+    fn replay(&self) -> anyhow::Result<(Head, u64)> {
+        let first_retained = match self.meta.get(META_FIRST_RETAINED)? {
+            Some(v) => u64::from_be_bytes(
+                <[u8; 8]>::try_from(v.as_ref())
+                    .map_err(|_| anyhow::anyhow!("corrupt retention watermark"))?,
+            ),
+            None => 1,
+        };
+
+        let mut cursor: Option<ChainCursor> = None;
+        let mut last: Option<LogEntry> = None;
+        for kv in self.log.range(vkey(first_retained)..) {
+            let (k, v) = kv?;
+            let e: LogEntry = decode("log entry", &v)?;
+            anyhow::ensure!(
+                k.as_ref() == vkey(e.version),
+                "log entry {} stored under the wrong key",
+                e.version
+            );
+            let c = cursor.get_or_insert_with(|| {
+                if e.version == 1 {
+                    ChainCursor::genesis()
+                } else {
+                    ChainCursor::resume(&e)
+                }
+            });
+            anyhow::ensure!(
+                last.is_some() || e.version == first_retained,
+                "log starts at {} but retention watermark is {}",
+                e.version,
+                first_retained
+            );
+            c.advance(&e)?;
+            last = Some(e);
+        }
+
+        let head = last.as_ref().map_or_else(Head::genesis, Head::from_entry);
+        anyhow::ensure!(
+            first_retained == 1 || last.is_some(),
+            "log pruned to {first_retained} but no entries retained"
+        );
+        match self.meta.get(META_HEAD)? {
+            Some(v) => {
+                let snap: Head = decode("HEAD snapshot", &v)?;
+                anyhow::ensure!(
+                    snap.version == head.version && snap.hash == head.hash,
+                    "HEAD snapshot v{} disagrees with log v{}",
+                    snap.version,
+                    head.version
+                );
+            }
+            None => anyhow::ensure!(head.version == 0, "log present but HEAD snapshot missing"),
+        }
+
+        let verifier = self.key.verifying_key();
+        let mut anchored = first_retained == 1;
+        for kv in self.checkpoints.iter() {
+            let (_, v) = kv?;
+            let cp: Checkpoint = decode("checkpoint", &v)?;
+            cp.verify(&verifier)?;
+            anyhow::ensure!(
+                cp.version <= head.version,
+                "checkpoint {} is beyond HEAD {}",
+                cp.version,
+                head.version
+            );
+            if cp.version >= first_retained {
+                let e: LogEntry = match self.log.get(vkey(cp.version))? {
+                    Some(v) => decode("log entry", &v)?,
+                    None => anyhow::bail!("checkpoint {} entry missing", cp.version),
+                };
+                anyhow::ensure!(
+                    e.entry_hash == cp.entry_hash,
+                    "checkpoint {} does not match the log",
+                    cp.version
+                );
+                anchored = true;
+            }
+        }
+        anyhow::ensure!(
+            anchored,
+            "pruned log below {first_retained} is not covered by a checkpoint"
+        );
+        Ok((head, first_retained))
+    }
+
+    /// Oldest version still in the log.
+    pub fn first_retained(&self) -> u64 {
+        self.first_retained.load(Ordering::SeqCst)
+    }
+
+    /// Sign a checkpoint at HEAD unless HEAD is empty or already checkpointed.
+    pub async fn checkpoint_now(&self) -> anyhow::Result<Option<Checkpoint>> {
+        let _guard = self.writer.lock().await;
+        let head = self.head.read().clone();
+        if head.version == 0 || self.last_checkpoint()?.map(|c| c.version) == Some(head.version) {
+            return Ok(None);
+        }
+        let cp = self.key.sign(head.version, &head.hash, Utc::now());
+        self.checkpoints
+            .insert(vkey(cp.version), serde_json::to_vec(&cp)?)?;
+        self.db.flush_async().await?;
+        Ok(Some(cp))
+    }
+
+    /// Drop entries below `below`; `below` may not pass the latest checkpoint.
+    /// Returns the number of entries removed.
+    pub async fn prune_below(&self, below: u64) -> anyhow::Result<u64> {
+        let _guard = self.writer.lock().await;
+        let first = self.first_retained();
+        if below <= first {
+            return Ok(0);
+        }
+        let covered = self.last_checkpoint()?.map_or(0, |c| c.version);
+        anyhow::ensure!(
+            below <= covered,
+            "cannot prune below {below}: latest checkpoint is {covered}"
+        );
+
+        // Move the watermark first: a crash mid-prune leaves only ignorable leftovers.
+        self.meta.insert(META_FIRST_RETAINED, &vkey(below))?;
+        self.db.flush_async().await?;
+        self.first_retained.store(below, Ordering::SeqCst);
+
+        let mut batch = sled::Batch::default();
+        for v in first..below {
+            batch.remove(&vkey(v));
+        }
+        self.log.apply_batch(batch)?;
+        if self.fsync {
+            self.db.flush_async().await?;
+        }
+        Ok(below - first)
+    }
+
+    /// Every checkpoint, oldest first (kept across pruning as proofs).
+    pub fn checkpoints(&self) -> anyhow::Result<Vec<Checkpoint>> {
+        self.checkpoints
+            .iter()
+            .map(|kv| decode("checkpoint", &kv?.1))
+            .collect()
+    }
+
+    fn last_checkpoint(&self) -> anyhow::Result<Option<Checkpoint>> {
+        match self.checkpoints.last()? {
+            Some((_, v)) => Ok(Some(decode("checkpoint", &v)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Append one entry and move HEAD; `signer_set` is written in the same transaction.
+    async fn append(&self, payload_b3: String, signer_set: Option<&[u8]>) -> anyhow::Result<Head> {
+        anyhow::ensure!(
+            payload_b3.starts_with("b3:"),
+            "payload must be base64-with-prefix (b3:..)"
+        );
+
+        let _guard = self.writer.lock().await;
+        let prev = self.head.read().clone();
+        let entry = LogEntry::next(prev.version, &prev.hash, payload_b3, Utc::now());
+        let new_head = Head::from_entry(&entry);
+        let entry_bytes = serde_json::to_vec(&entry)?;
+        let head_bytes = serde_json::to_vec(&new_head)?;
+
+        (&self.log, &self.meta)
+            .transaction(|(log, meta)| {
+                log.insert(&vkey(entry.version), entry_bytes.as_slice())?;
+                meta.insert(META_HEAD, head_bytes.as_slice())?;
+                if let Some(set) = signer_set {
+                    meta.insert(META_SIGNER_SET, set)?;
+                }
+                Ok(())
+            })
+            .map_err(|e: sled::transaction::TransactionError<()>| {
+                anyhow::anyhow!("commit transaction failed: {e:?}")
+            })?;
+        // A signer-set rotation is always flushed: governance must not roll back on a crash.
+        if self.fsync || signer_set.is_some() {
+            self.db.flush_async().await?;
+        }
+
+        *self.head.write() = new_head.clone();
+        let _ = self.tx.send(RegistryEvent::Commit {
+            head: new_head.clone(),
+        });
+        Ok(new_head)
+    }
+}
+
+#[async_trait::async_trait]
+impl RegistryStore for SledStore {
+    async fn head(&self) -> Head {
+        self.head.read().clone()
+    }
+
+    async fn commit(&self, payload_b3: String) -> anyhow::Result<Head> {
+        self.append(payload_b3, None).await
+    }
+
+    async fn commit_with_signer_set(
+        &self,
+        payload_b3: String,
+        signer_set: Vec<u8>,
+    ) -> anyhow::Result<Head> {
+        self.append(payload_b3, Some(&signer_set)).await
+    }
+
+    async fn page(&self, from: u64, limit: usize) -> anyhow::Result<LogPage> {
+        let head_version = self.head.read().version;
+        let first_retained = self.first_retained();
+        let from = from.max(first_retained).max(1);
+        let entries = self
+            .log
+            .range(vkey(from)..)
+            .take(limit.clamp(1, MAX_PAGE))
+            .map(|kv| decode::<LogEntry>("log entry", &kv?.1))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let next_from = entries
+            .last()
+            .map(|e| e.version + 1)
+            .filter(|v| *v <= head_version);
+        Ok(LogPage {
+            entries,
+            next_from,
+            first_retained,
+            head_version,
+        })
+    }
+
+    async fn put_signer_set(&self, json: Vec<u8>) -> anyhow::Result<()> {
+        self.meta.insert(META_SIGNER_SET, json)?;
+        self.db.flush_async().await?;
+        Ok(())
+    }
+
+    async fn signer_set(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        Ok(self.meta.get(META_SIGNER_SET)?.map(|v| v.to_vec()))
+    }
+
+    async fn latest_checkpoint(&self) -> Option<Checkpoint> {
+        self.last_checkpoint().ok().flatten()
+    }
+
+    fn subscribe(&self) -> BroadcastStream<RegistryEvent> {
+        BroadcastStream::new(self.tx.subscribe())
+    }
+}
@@ -1 +1,2 @@
-/*! SQLite backend adapter (scaffold) */
+/*! SQLite backend adapter (scaffold; `storage.kind = "sqlite"` is rejected by config validation
+until this lands — `sled_store` is the durable backend) */
@@ -1,31 +1,121 @@
 use std::sync::Arc;
 
 use axum::{body, http::Request, Router};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ed25519_dalek::{Signer as _, SigningKey};
+use ron_proto::SignatureAlg;
 use svc_registry::{
-    config::model::Config, http::routes::registry_routes_with_cfg,
-    observability::metrics::RegistryMetrics, storage::inmem::InMemoryStore,
+    config::model::Config,
+    governance::{approval_message, Signer},
+    http::routes::registry_routes_with_cfg,
+    observability::metrics::RegistryMetrics,
+    storage::inmem::InMemoryStore,
 };
 use tower::util::ServiceExt; // for .oneshot
 
-#[tokio::test]
-async fn commit_endpoint_roundtrip_and_body_shape() {
-    let metrics = RegistryMetrics::default();
-    let store = Arc::new(InMemoryStore::new());
-    let cfg = Config::default();
-    let api: Router = registry_routes_with_cfg(metrics, store.clone(), &cfg);
+fn key(i: u8) -> SigningKey {
+    SigningKey::from_bytes(&[i + 1; 32])
+}
 
-    // Commit once
-    let req = Request::post("/registry/commit")
+fn governed_cfg() -> Config {
+    let mut cfg = Config::default();
+    cfg.governance.threshold = 2;
+    cfg.governance.signers = (0..3)
+        .map(|i| Signer {
+            signer_id: format!("org:test#key{i}"),
+            alg: SignatureAlg::Ed25519,
+            public_key_b64: STANDARD.encode(key(i).verifying_key().to_bytes()),
+        })
+        .collect();
+    cfg
+}
+
+async fn post(api: &Router, uri: &str, body: String) -> (u16, serde_json::Value) {
+    let req = Request::post(uri)
         .header("content-type", "application/json")
-        .body(axum::body::Body::from(r#"{"payload_b3":"b3:roundtrip"}"#))
+        .body(axum::body::Body::from(body))
         .unwrap();
+    let res = api.clone().oneshot(req).await.unwrap();
+    let status = res.status().as_u16();
+    let body = body::to_bytes(res.into_body(), 1 << 20).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
 
+async fn get(api: &Router, uri: &str) -> (u16, serde_json::Value) {
+    let req = Request::get(uri).body(axum::body::Body::empty()).unwrap();
     let res = api.clone().oneshot(req).await.unwrap();
-    assert!(res.status().is_success());
+    let status = res.status().as_u16();
     let body = body::to_bytes(res.into_body(), 1 << 20).await.unwrap();
-    let s = String::from_utf8(body.to_vec()).unwrap();
-    assert!(s.contains(r#""version":1"#));
-    assert!(s.contains(r#""payload_b3":"b3:roundtrip""#));
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+async fn approve(api: &Router, proposal_id: &str, i: u8) -> (u16, serde_json::Value) {
+    let sig = key(i).sign(&approval_message(proposal_id));
+    let body = serde_json::json!({
+        "signer_id": format!("org:test#key{i}"),
+        "sig_b64": STANDARD.encode(sig.to_bytes()),
+    });
+    post(
+        api,
+        &format!("/registry/approvals/{proposal_id}"),
+        body.to_string(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn propose_approve_commit_roundtrip_and_body_shape() {
+    let metrics = RegistryMetrics::default();
+    let store = Arc::new(InMemoryStore::new());
+    let api: Router = registry_routes_with_cfg(metrics, store.clone(), &governed_cfg());
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    let cid = format!("b3:{}", blake3::hash(b"roundtrip").to_hex());
+    let proposal = serde_json::json!({
+        "descriptor": {
+            "descriptor_cid": cid,
+            "alg": "ed25519",
+            "quorum": { "n": 3, "m": 2 },
+            "issued_at": now - 10,
+            "expires_at": now + 3_600,
+            "rationale_cid": null
+        },
+        "base_version": 0
+    });
+    let (status, body) = post(&api, "/registry/proposals", proposal.to_string()).await;
+    assert_eq!(status, 202, "{body}");
+    let id = body["proposal_id"].as_str().unwrap().to_string();
+    assert_eq!(body["signer_set_version"], 1);
+
+    // One approval is not enough: 412 NoQuorum.
+    let (status, body) = approve(&api, &id, 0).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["approvals"], 1);
+    assert_eq!(body["ready"], false);
+    let (status, body) = post(&api, &format!("/registry/commit/{id}"), String::new()).await;
+    assert_eq!(status, 412);
+    assert_eq!(body["error"]["code"], "NoQuorum");
+
+    // Same signer twice: 409 DuplicateApproval.
+    let (status, body) = approve(&api, &id, 0).await;
+    assert_eq!(status, 409);
+    assert_eq!(body["error"]["code"], "DuplicateApproval");
+
+    let (_, body) = approve(&api, &id, 2).await;
+    assert_eq!(body["ready"], true);
+    let (status, body) = post(&api, &format!("/registry/commit/{id}"), String::new()).await;
+    assert_eq!(status, 201);
+    assert_eq!(body["version"], 1);
+    assert_eq!(body["payload_b3"], cid.as_str());
+
+    // History: the committed version is addressable and pageable.
+    let (status, body) = get(&api, "/registry/1").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["payload_b3"], cid.as_str());
+    let (status, body) = get(&api, "/registry/log?from=1&limit=10").await;
+    assert_eq!(status, 200);
+    assert_eq!(body["entries"].as_array().map(Vec::len), Some(1));
+    assert_eq!(get(&api, "/registry/2").await.0, 404);
 
     // GET head via router to ensure JSON shape is stable
     let res = api
@@ -41,3 +131,14 @@ async fn commit_endpoint_roundtrip_and_body_shape() {
     let s = String::from_utf8(body.to_vec()).unwrap();
     assert!(s.contains(r#""version":1"#));
 }
+
+#[tokio::test]
+async fn writes_fail_closed_without_signer_set() {
+    let metrics = RegistryMetrics::default();
+    let store = Arc::new(InMemoryStore::new());
+    let api: Router = registry_routes_with_cfg(metrics, store, &Config::default());
+
+    let (status, body) = post(&api, "/registry/commit/b3:nope", String::new()).await;
+    assert_eq!(status, 503);
+    assert_eq!(body["error"]["code"], "NoSignerSet");
+}
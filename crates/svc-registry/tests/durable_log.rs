@@ -0,0 +1,284 @@
+//! Durable registry log: restart persistence, paging, replay integrity, checkpoints + retention.
+
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ed25519_dalek::{Signer as _, SigningKey};
+use ron_proto::{ContentId, MultiSigNofM, SignatureAlg, SignedDescriptorV1};
+use svc_registry::{
+    config::model::Governance,
+    governance::{approval_message, Approval, Signer, SignerSetSpec},
+    pipeline::{checkpoint::run_once, Change, Pipeline, ProposalReq, ProposalStatus},
+    storage::{
+        checkpoint::CheckpointKey,
+        log::{LogEntry, LogPage},
+        sled_store::SledStore,
+        RegistryEvent, RegistryStore,
+    },
+};
+use tokio_stream::wrappers::BroadcastStream;
+
+fn open(dir: &tempfile::TempDir) -> anyhow::Result<SledStore> {
+    SledStore::open_with(
+        &dir.path().join("log"),
+        CheckpointKey::from_seed([7; 32]),
+        true,
+    )
+}
+
+fn cid(i: u64) -> String {
+    format!("b3:{}", blake3::hash(&i.to_be_bytes()).to_hex())
+}
+
+async fn commit_n(store: &SledStore, n: u64) {
+    let start = store.head().await.version;
+    for i in start + 1..=start + n {
+        store.commit(cid(i)).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn head_survives_restart_and_history_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    let head = {
+        let store = open(&dir).unwrap();
+        commit_n(&store, 5).await;
+        store.head().await
+    };
+
+    let store = open(&dir).unwrap();
+    let reopened = store.head().await;
+    assert_eq!(reopened.version, 5);
+    assert_eq!(reopened.hash, head.hash);
+    assert_eq!(reopened.payload_b3, cid(5));
+
+    let page = store.page(2, 2).await.unwrap();
+    let versions: Vec<u64> = page.entries.iter().map(|e| e.version).collect();
+    assert_eq!(versions, vec![2, 3]);
+    assert_eq!(page.next_from, Some(4));
+    assert_eq!(page.entries[1].prev_hash, page.entries[0].entry_hash);
+
+    let last = store.page(4, 100).await.unwrap();
+    assert_eq!(last.entries.len(), 2);
+    assert_eq!(last.next_from, None);
+
+    // Commits continue the chain from the persisted HEAD.
+    let h6 = store.commit(cid(6)).await.unwrap();
+    assert_eq!(h6.version, 6);
+    assert_eq!(
+        store.page(6, 1).await.unwrap().entries[0].prev_hash,
+        head.hash
+    );
+}
+
+#[tokio::test]
+async fn replay_rejects_a_tampered_entry() {
+    let dir = tempfile::tempdir().unwrap();
+    {
+        let store = open(&dir).unwrap();
+        commit_n(&store, 3).await;
+    }
+    {
+        let db = sled::open(dir.path().join("log")).unwrap();
+        let log = db.open_tree("log").unwrap();
+        let raw = log.get(2u64.to_be_bytes()).unwrap().unwrap();
+        let mut e: LogEntry = serde_json::from_slice(&raw).unwrap();
+        e.payload_b3 = cid(99);
+        log.insert(2u64.to_be_bytes(), serde_json::to_vec(&e).unwrap())
+            .unwrap();
+        db.flush().unwrap();
+    }
+    let err = open(&dir).err().expect("tampered log must not open");
+    assert!(err.to_string().contains("hash mismatch"), "{err}");
+}
+
+#[tokio::test]
+async fn retention_prunes_only_behind_checkpoints() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = open(&dir).unwrap();
+    commit_n(&store, 10).await;
+
+    // No checkpoint yet: pruning is refused.
+    assert!(store.prune_below(5).await.is_err());
+
+    // Checkpoint at 10, keep newest 3 → prune below 8.
+    assert_eq!(run_once(&store, 3).await.unwrap(), 7);
+    assert_eq!(store.first_retained(), 8);
+    let cp = store.latest_checkpoint().await.unwrap();
+    assert_eq!(cp.version, 10);
+    assert_eq!(cp.entry_hash, store.head().await.hash);
+
+    let page = store.page(1, 100).await.unwrap();
+    assert_eq!(page.first_retained, 8);
+    assert_eq!(page.entries.first().map(|e| e.version), Some(8));
+
+    // Checkpoint never prunes its own entry, even with a tiny max_count.
+    commit_n(&store, 2).await;
+    run_once(&store, 1).await.unwrap();
+    assert_eq!(store.first_retained(), 12);
+    assert_eq!(store.checkpoints().unwrap().len(), 2);
+    drop(store);
+
+    // Replay accepts the pruned log because a checkpoint anchors it.
+    let store = open(&dir).unwrap();
+    assert_eq!(store.head().await.version, 12);
+    assert_eq!(store.first_retained(), 12);
+    assert_eq!(store.checkpoints().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn replay_rejects_checkpoints_from_another_key() {
+    let dir = tempfile::tempdir().unwrap();
+    {
+        let store = open(&dir).unwrap();
+        commit_n(&store, 2).await;
+        store.checkpoint_now().await.unwrap();
+    }
+    let res = SledStore::open_with(
+        &dir.path().join("log"),
+        CheckpointKey::from_seed([8; 32]),
+        true,
+    );
+    assert!(res.is_err());
+}
+
+fn key(i: u8) -> SigningKey {
+    SigningKey::from_bytes(&[i + 1; 32])
+}
+
+fn signer(i: u8) -> Signer {
+    Signer {
+        signer_id: format!("org:test#key{i}"),
+        alg: SignatureAlg::Ed25519,
+        public_key_b64: STANDARD.encode(key(i).verifying_key().to_bytes()),
+    }
+}
+
+fn approval(i: u8, proposal_id: &str) -> Approval {
+    Approval {
+        signer_id: signer(i).signer_id,
+        sig_b64: STANDARD.encode(key(i).sign(&approval_message(proposal_id)).to_bytes()),
+    }
+}
+
+#[tokio::test]
+async fn rotated_signer_set_survives_restart() {
+    const NOW: u64 = 1_760_000_000;
+    let dir = tempfile::tempdir().unwrap();
+    let genesis = Governance {
+        threshold: 1,
+        max_pending: 16,
+        signers: vec![signer(0)],
+    };
+    let next = SignerSetSpec {
+        signers: vec![signer(1), signer(2)],
+        quorum: MultiSigNofM { n: 2, m: 2 },
+    };
+
+    {
+        let store: Arc<dyn RegistryStore> = Arc::new(open(&dir).unwrap());
+        let pl = Pipeline::from_config(store, &genesis).unwrap();
+        let p = pl
+            .propose(
+                ProposalReq {
+                    descriptor: SignedDescriptorV1 {
+                        descriptor_cid: ContentId::parse(&next.cid()).unwrap(),
+                        alg: SignatureAlg::Ed25519,
+                        quorum: MultiSigNofM { n: 1, m: 1 },
+                        issued_at: NOW,
+                        expires_at: NOW + 60,
+                        rationale_cid: None,
+                    },
+                    base_version: 0,
+                    change: Change::SignerSet { next: next.clone() },
+                },
+                NOW,
+            )
+            .await
+            .unwrap();
+        pl.approve(&p.id, approval(0, &p.id), NOW).await.unwrap();
+        pl.commit(&p.id, NOW).await.unwrap();
+    }
+
+    let store: Arc<dyn RegistryStore> = Arc::new(open(&dir).unwrap());
+    assert_eq!(store.head().await.version, 1);
+    let pl = Pipeline::from_config(store, &genesis).unwrap();
+    assert_eq!(pl.signer_set().unwrap().version, 1);
+    pl.restore_signer_set().await.unwrap();
+    let set = pl.signer_set().unwrap();
+    assert_eq!(set.version, 2);
+    assert_eq!(set.quorum, next.quorum);
+    assert!(!set.contains(&signer(0).signer_id));
+}
+
+/// Accepts plain commits but fails the atomic HEAD + signer-set write.
+struct RotationFails(SledStore);
+
+#[async_trait::async_trait]
+impl RegistryStore for RotationFails {
+    async fn head(&self) -> svc_registry::storage::Head {
+        self.0.head().await
+    }
+    async fn commit(&self, payload_b3: String) -> anyhow::Result<svc_registry::storage::Head> {
+        self.0.commit(payload_b3).await
+    }
+    async fn commit_with_signer_set(
+        &self,
+        _payload_b3: String,
+        _signer_set: Vec<u8>,
+    ) -> anyhow::Result<svc_registry::storage::Head> {
+        anyhow::bail!("disk full")
+    }
+    async fn page(&self, from: u64, limit: usize) -> anyhow::Result<LogPage> {
+        self.0.page(from, limit).await
+    }
+    async fn signer_set(&self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.0.signer_set().await
+    }
+    fn subscribe(&self) -> BroadcastStream<RegistryEvent> {
+        self.0.subscribe()
+    }
+}
+
+#[tokio::test]
+async fn a_failed_rotation_moves_neither_head_nor_the_signer_set() {
+    const NOW: u64 = 1_760_000_000;
+    let dir = tempfile::tempdir().unwrap();
+    let genesis = Governance {
+        threshold: 1,
+        max_pending: 16,
+        signers: vec![signer(0)],
+    };
+    let next = SignerSetSpec {
+        signers: vec![signer(1)],
+        quorum: MultiSigNofM { n: 1, m: 1 },
+    };
+    let store: Arc<dyn RegistryStore> = Arc::new(RotationFails(open(&dir).unwrap()));
+    let pl = Pipeline::from_config(store.clone(), &genesis).unwrap();
+    let p = pl
+        .propose(
+            ProposalReq {
+                descriptor: SignedDescriptorV1 {
+                    descriptor_cid: ContentId::parse(&next.cid()).unwrap(),
+                    alg: SignatureAlg::Ed25519,
+                    quorum: MultiSigNofM { n: 1, m: 1 },
+                    issued_at: NOW,
+                    expires_at: NOW + 60,
+                    rationale_cid: None,
+                },
+                base_version: 0,
+                change: Change::SignerSet { next },
+            },
+            NOW,
+        )
+        .await
+        .unwrap();
+    pl.approve(&p.id, approval(0, &p.id), NOW).await.unwrap();
+
+    assert!(pl.commit(&p.id, NOW).await.is_err());
+    assert_eq!(store.head().await.version, 0);
+    assert!(store.signer_set().await.unwrap().is_none());
+    assert_eq!(pl.signer_set().unwrap().version, 1);
+    // Still pending, so it can be committed once storage recovers.
+    assert_eq!(pl.proposal(&p.id).unwrap().status, ProposalStatus::Pending);
+}
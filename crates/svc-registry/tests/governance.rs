@@ -0,0 +1,261 @@
+//! Governance pipeline: M-of-N quorum, signature checks, supersede rules, signer-set rotation.
+
+use std::sync::Arc;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ed25519_dalek::{Signer as _, SigningKey};
+use ron_proto::{ContentId, MultiSigNofM, SignatureAlg, SignedDescriptorV1};
+use svc_registry::{
+    governance::{approval_message, Approval, GovError, Signer, SignerSet, SignerSetSpec},
+    pipeline::{Change, Pipeline, ProposalReq, ProposalStatus},
+    storage::{inmem::InMemoryStore, RegistryStore},
+};
+
+const NOW: u64 = 1_760_000_000;
+
+fn key(i: u8) -> SigningKey {
+    SigningKey::from_bytes(&[i + 1; 32])
+}
+
+fn signer(i: u8) -> Signer {
+    Signer {
+        signer_id: format!("org:test#key{i}"),
+        alg: SignatureAlg::Ed25519,
+        public_key_b64: STANDARD.encode(key(i).verifying_key().to_bytes()),
+    }
+}
+
+fn spec(ids: &[u8], m: u8) -> SignerSetSpec {
+    SignerSetSpec {
+        signers: ids.iter().map(|&i| signer(i)).collect(),
+        quorum: MultiSigNofM {
+            n: ids.len() as u8,
+            m,
+        },
+    }
+}
+
+fn pipeline(ids: &[u8], m: u8) -> (Pipeline, Arc<InMemoryStore>) {
+    let store = Arc::new(InMemoryStore::new());
+    let set = SignerSet::new(1, spec(ids, m)).unwrap();
+    (Pipeline::new(store.clone(), Some(set), 16), store)
+}
+
+fn cid(tag: &str) -> String {
+    format!("b3:{}", blake3::hash(tag.as_bytes()).to_hex())
+}
+
+fn descriptor(cid: &str, m: u8, n: u8) -> SignedDescriptorV1 {
+    SignedDescriptorV1 {
+        descriptor_cid: ContentId::parse(cid).unwrap(),
+        alg: SignatureAlg::Ed25519,
+        quorum: MultiSigNofM { n, m },
+        issued_at: NOW - 60,
+        expires_at: NOW + 3_600,
+        rationale_cid: None,
+    }
+}
+
+fn req(tag: &str, base_version: u64) -> ProposalReq {
+    ProposalReq {
+        descriptor: descriptor(&cid(tag), 2, 3),
+        base_version,
+        change: Change::Descriptor,
+    }
+}
+
+fn approval(i: u8, proposal_id: &str) -> Approval {
+    Approval {
+        signer_id: signer(i).signer_id,
+        sig_b64: STANDARD.encode(key(i).sign(&approval_message(proposal_id)).to_bytes()),
+    }
+}
+
+#[tokio::test]
+async fn two_of_three_quorum_commits_and_bumps_head() {
+    let (pl, store) = pipeline(&[0, 1, 2], 2);
+    let p = pl.propose(req("a", 0), NOW).await.unwrap();
+    assert_eq!(pl.pending_count(), 1);
+
+    // Re-proposing identical content is idempotent.
+    assert_eq!(pl.propose(req("a", 0), NOW).await.unwrap().id, p.id);
+    assert_eq!(pl.pending_count(), 1);
+
+    let (_, q) = pl.approve(&p.id, approval(0, &p.id), NOW).await.unwrap();
+    assert!(!q.reached());
+    assert!(matches!(
+        pl.commit(&p.id, NOW).await,
+        Err(GovError::NoQuorum { have: 1, need: 2 })
+    ));
+    assert_eq!(store.head().await.version, 0);
+
+    let (_, q) = pl.approve(&p.id, approval(2, &p.id), NOW).await.unwrap();
+    assert!(q.reached());
+    let (head, committed) = pl.commit(&p.id, NOW).await.unwrap();
+    assert_eq!(head.version, 1);
+    assert_eq!(head.payload_b3, cid("a"));
+    assert_eq!(committed.status, ProposalStatus::Committed { version: 1 });
+    assert_eq!(pl.pending_count(), 0);
+    assert!(matches!(
+        pl.commit(&p.id, NOW).await,
+        Err(GovError::AlreadyCommitted(1))
+    ));
+}
+
+#[tokio::test]
+async fn rejects_duplicate_unknown_and_forged_approvals() {
+    let (pl, _) = pipeline(&[0, 1, 2], 2);
+    let p = pl.propose(req("b", 0), NOW).await.unwrap();
+
+    pl.approve(&p.id, approval(0, &p.id), NOW).await.unwrap();
+    assert!(matches!(
+        pl.approve(&p.id, approval(0, &p.id), NOW).await,
+        Err(GovError::DuplicateApproval)
+    ));
+    assert!(matches!(
+        pl.approve(&p.id, approval(7, &p.id), NOW).await,
+        Err(GovError::UnknownSigner)
+    ));
+
+    // Signature by key1 over a different proposal id does not verify here.
+    assert!(matches!(
+        pl.approve(&p.id, approval(1, "b3:other"), NOW).await,
+        Err(GovError::BadSignature)
+    ));
+    assert!(matches!(
+        pl.approve("b3:missing", approval(1, "b3:missing"), NOW)
+            .await,
+        Err(GovError::NotFound)
+    ));
+}
+
+#[tokio::test]
+async fn rejects_weaker_quorum_and_expired_descriptors() {
+    let (pl, _) = pipeline(&[0, 1, 2], 2);
+
+    let mut weak = req("c", 0);
+    weak.descriptor.quorum.m = 1;
+    assert!(matches!(
+        pl.propose(weak, NOW).await,
+        Err(GovError::BadProposal(_))
+    ));
+
+    let mut expired = req("c", 0);
+    expired.descriptor.expires_at = NOW;
+    assert!(matches!(
+        pl.propose(expired, NOW).await,
+        Err(GovError::Expired)
+    ));
+
+    // Valid at propose time, expired by commit time.
+    let p = pl.propose(req("c", 0), NOW).await.unwrap();
+    pl.approve(&p.id, approval(0, &p.id), NOW).await.unwrap();
+    pl.approve(&p.id, approval(1, &p.id), NOW).await.unwrap();
+    assert!(matches!(
+        pl.commit(&p.id, NOW + 3_600).await,
+        Err(GovError::Expired)
+    ));
+}
+
+#[tokio::test]
+async fn commit_supersedes_proposals_on_the_old_head() {
+    let (pl, _) = pipeline(&[0, 1, 2], 2);
+    let a = pl.propose(req("a", 0), NOW).await.unwrap();
+    let b = pl.propose(req("b", 0), NOW).await.unwrap();
+    for p in [&a, &b] {
+        pl.approve(&p.id, approval(0, &p.id), NOW).await.unwrap();
+        pl.approve(&p.id, approval(1, &p.id), NOW).await.unwrap();
+    }
+
+    pl.commit(&a.id, NOW).await.unwrap();
+    assert!(matches!(
+        pl.commit(&b.id, NOW).await,
+        Err(GovError::Superseded(_))
+    ));
+    assert!(matches!(
+        pl.proposal(&b.id).unwrap().status,
+        ProposalStatus::Superseded { .. }
+    ));
+    assert!(matches!(
+        pl.propose(req("c", 0), NOW).await,
+        Err(GovError::StaleVersion { base: 0, head: 1 })
+    ));
+}
+
+#[tokio::test]
+async fn signer_set_rotation_invalidates_old_approvals() {
+    let (pl, store) = pipeline(&[0, 1, 2], 2);
+    let pending = pl.propose(req("pending", 0), NOW).await.unwrap();
+    pl.approve(&pending.id, approval(0, &pending.id), NOW)
+        .await
+        .unwrap();
+
+    let next = spec(&[3, 4, 5], 2);
+    let rotate = pl
+        .propose(
+            ProposalReq {
+                descriptor: descriptor(&next.cid(), 2, 3),
+                base_version: 0,
+                change: Change::SignerSet { next: next.clone() },
+            },
+            NOW,
+        )
+        .await
+        .unwrap();
+    pl.approve(&rotate.id, approval(1, &rotate.id), NOW)
+        .await
+        .unwrap();
+    pl.approve(&rotate.id, approval(2, &rotate.id), NOW)
+        .await
+        .unwrap();
+    let (head, _) = pl.commit(&rotate.id, NOW).await.unwrap();
+    assert_eq!(head.payload_b3, next.cid());
+
+    let set = pl.signer_set().unwrap();
+    assert_eq!(set.version, 2);
+    assert!(set.contains(&signer(3).signer_id));
+    assert!(!set.contains(&signer(0).signer_id));
+    assert!(matches!(
+        pl.proposal(&pending.id).unwrap().status,
+        ProposalStatus::Superseded { .. }
+    ));
+
+    // Old signers no longer count; the new set can commit.
+    let p = pl.propose(req("after", head.version), NOW).await.unwrap();
+    assert_eq!(p.signer_set_version, 2);
+    assert!(matches!(
+        pl.approve(&p.id, approval(0, &p.id), NOW).await,
+        Err(GovError::UnknownSigner)
+    ));
+    pl.approve(&p.id, approval(3, &p.id), NOW).await.unwrap();
+    pl.approve(&p.id, approval(5, &p.id), NOW).await.unwrap();
+    pl.commit(&p.id, NOW).await.unwrap();
+    assert_eq!(store.head().await.version, 2);
+}
+
+#[tokio::test]
+async fn signer_set_change_must_bind_descriptor_cid() {
+    let (pl, _) = pipeline(&[0, 1, 2], 2);
+    let res = pl
+        .propose(
+            ProposalReq {
+                descriptor: descriptor(&cid("unrelated"), 2, 3),
+                base_version: 0,
+                change: Change::SignerSet {
+                    next: spec(&[3, 4, 5], 2),
+                },
+            },
+            NOW,
+        )
+        .await;
+    assert!(matches!(res, Err(GovError::BadProposal(_))));
+}
+
+#[tokio::test]
+async fn no_signer_set_fails_closed() {
+    let pl = Pipeline::new(Arc::new(InMemoryStore::new()), None, 16);
+    assert!(matches!(
+        pl.propose(req("a", 0), NOW).await,
+        Err(GovError::NoSignerSet)
+    ));
+}
@@ -65,7 +65,7 @@ async fn readiness_flips_200_when_gates_true() {
 }
 
 #[tokio::test]
-async fn commit_bumps_head_and_returns_200() {
+async fn legacy_unsigned_commit_is_gone_and_head_stays_put() {
     let metrics = RegistryMetrics::default();
     let store = Arc::new(InMemoryStore::new());
 
@@ -79,20 +79,17 @@ async fn commit_bumps_head_and_returns_200() {
     let h0 = store.head().await;
     assert_eq!(h0.version, 0);
 
-    // POST /registry/commit
+    // POST /registry/commit with a bare payload no longer moves HEAD; writes need a quorum.
     let req = Request::post("/registry/commit")
         .header("content-type", "application/json")
         .body(axum::body::Body::from(r#"{"payload_b3":"b3:test"}"#))
         .unwrap();
 
     let res = api.clone().oneshot(req).await.unwrap();
-    assert!(res.status().is_success());
-
-    // Head should bump to 1
-    let h1 = store.head().await;
-    assert_eq!(h1.version, 1);
+    assert!(!res.status().is_success());
+    assert_eq!(store.head().await.version, 0);
 
-    // And /registry/head returns the bumped version if we call through the router:
+    // And /registry/head still reports version 0 through the router:
     let res = api
         .oneshot(
             Request::get("/registry/head")
@@ -104,5 +101,5 @@ async fn commit_bumps_head_and_returns_200() {
     assert!(res.status().is_success());
     let body = body::to_bytes(res.into_body(), 1 << 20).await.unwrap();
     let s = String::from_utf8(body.to_vec()).unwrap();
-    assert!(s.contains(r#""version":1"#));
+    assert!(s.contains(r#""version":0"#));
 }
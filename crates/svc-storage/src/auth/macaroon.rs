@@ -1,11 +1,12 @@
 //! RO:WHAT — Minimal macaroon-ish token + Axum extractor, signed with keyed BLAKE3.
 //! RO:WHY  — Gate write endpoints without heavy deps or external KMS.
-//! RO:NOTE — If `RON_STORAGE_MACAROON_SECRET` is unset, extractor is permissive (dev mode).
+//! RO:NOTE — If `RON_STORAGE_MACAROON_SECRET` is unset, extractor is permissive (dev mode);
+//!           `require_token` (sealed-object reads) is never permissive.
 
 use axum::{
     async_trait,
     extract::FromRequestParts,
-    http::{header, request::Parts, StatusCode},
+    http::{header, request::Parts, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
 };
 use base64::engine::general_purpose::URL_SAFE_NO_PAD as B64;
@@ -139,6 +140,15 @@ fn verify_impl_with_header(authz: &str) -> Result<MacaroonClaims, MacaroonError>
     })
 }
 
+/// Strict check with no dev-permissive path: a missing secret fails closed.
+pub fn require_token(headers: &HeaderMap) -> Result<MacaroonClaims, MacaroonError> {
+    let auth = headers
+        .get(header::AUTHORIZATION)
+        .ok_or(MacaroonError::Missing)?;
+    let auth = auth.to_str().map_err(|_| MacaroonError::Malformed)?;
+    verify_impl_with_header(auth)
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for MacaroonClaims
 where
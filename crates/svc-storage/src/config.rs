@@ -3,7 +3,8 @@
 //! RO:INTERACTS — main, http::routes::paid_object, accounting::exporter, policy::{paid_write,settlement}.
 //! RO:INVARIANTS — paid mode explicit; wallet mode fail-closed; settlement/export opt-in; disabled never writes.
 //! RO:METRICS — paid/accounting mode outcomes map to storage_* metrics.
-//! RO:CONFIG — RON_STORAGE_ADDR, RON_STORAGE_DATA_DIR, max body, wallet, settlement, accounting env vars.
+//! RO:CONFIG — RON_STORAGE_ADDR, RON_STORAGE_DATA_DIR, max body, wallet, settlement, accounting, RON_STORAGE_EC_*, RON_STORAGE_COMPRESSION*,
+//!             replication peers (RON_STORAGE_NODE_ID / RON_STORAGE_PEERS / RON_STORAGE_PEER_TOKEN) env vars.
 //! RO:SECURITY — production should not use dev-header accidentally; exporters require explicit base URL.
 //! RO:TEST — config tests plus paid_write_policy/paid_write_verifier/paid_write_settlement/accounting_export tests.
 
@@ -14,6 +15,12 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
 
+use crate::storage::compression::{
+    CompressionConfig, DEFAULT_CHUNK_SIZE, DEFAULT_LEVEL, DEFAULT_MIN_BYTES, MAX_CHUNK_SIZE,
+};
+use crate::storage::erasure::{ErasureConfig, DEFAULT_DATA_SHARDS, DEFAULT_PARITY_SHARDS};
+use crate::storage::placement::Peer;
+
 /// Environment variable selecting the `/paid/o` verifier behavior.
 pub const ENV_PAID_WRITE_VERIFIER_MODE: &str = "RON_STORAGE_PAID_WRITE_VERIFIER_MODE";
 
@@ -44,6 +51,48 @@ pub const ENV_ACCOUNTING_BEARER: &str = "RON_STORAGE_ACCOUNTING_BEARER";
 /// Environment variable for accounting export timeout in milliseconds.
 pub const ENV_ACCOUNTING_TIMEOUT_MS: &str = "RON_STORAGE_ACCOUNTING_TIMEOUT_MS";
 
+/// Environment variable enabling erasure coding for the filesystem backend.
+pub const ENV_EC: &str = "RON_STORAGE_EC";
+
+/// Environment variable for the erasure-coding data shard count.
+pub const ENV_EC_DATA_SHARDS: &str = "RON_STORAGE_EC_DATA_SHARDS";
+
+/// Environment variable for the erasure-coding parity shard count.
+pub const ENV_EC_PARITY_SHARDS: &str = "RON_STORAGE_EC_PARITY_SHARDS";
+
+/// Environment variable listing shard roots (comma-separated, one per disk) for erasure shards.
+pub const ENV_EC_ROOTS: &str = "RON_STORAGE_EC_ROOTS";
+
+/// Environment variable naming this node for placement (required when peers are configured).
+pub const ENV_NODE_ID: &str = "RON_STORAGE_NODE_ID";
+
+/// Environment variable listing replication peers as comma-separated `id@region=base_url`.
+pub const ENV_PEERS: &str = "RON_STORAGE_PEERS";
+
+/// Environment variable holding the bearer macaroon sent to replication peers.
+pub const ENV_PEER_TOKEN: &str = "RON_STORAGE_PEER_TOKEN";
+
+/// Environment variable for the delay between replication rounds, in seconds.
+pub const ENV_REPLICATION_INTERVAL_SECS: &str = "RON_STORAGE_REPLICATION_INTERVAL_SECS";
+
+/// Default delay between replication rounds.
+pub const DEFAULT_REPLICATION_INTERVAL_SECS: u64 = 60;
+
+/// Environment variable enabling at-rest compression for the filesystem backend.
+pub const ENV_COMPRESSION: &str = "RON_STORAGE_COMPRESSION";
+
+/// Environment variable for the zstd compression level.
+pub const ENV_COMPRESSION_LEVEL: &str = "RON_STORAGE_COMPRESSION_LEVEL";
+
+/// Environment variable for the smallest object worth compressing, in bytes.
+pub const ENV_COMPRESSION_MIN_BYTES: &str = "RON_STORAGE_COMPRESSION_MIN_BYTES";
+
+/// Environment variable for the uncompressed bytes per compressed frame.
+pub const ENV_COMPRESSION_CHUNK_BYTES: &str = "RON_STORAGE_COMPRESSION_CHUNK_BYTES";
+
+/// Environment variable naming the hybrid X25519+ML-KEM key file for sealed envelopes.
+pub const ENV_PQ_ENVELOPE_KEY: &str = "RON_STORAGE_PQ_ENVELOPE_KEY";
+
 /// Default wallet URL used for local dev wiring.
 pub const DEFAULT_WALLET_BASE_URL: &str = "http://127.0.0.1:8088";
 
@@ -321,7 +370,152 @@ pub fn accounting_export_timeout_from_env() -> Duration {
     Duration::from_millis(millis)
 }
 
+/// Return the erasure-coding shape, or `None` when erasure coding is off (the default).
+pub fn erasure_config_from_env() -> anyhow::Result<Option<ErasureConfig>> {
+    let enabled = std::env::var(ENV_EC)
+        .ok()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .is_some_and(|value| matches!(value.as_str(), "1" | "true" | "on" | "yes"));
+    if !enabled {
+        return Ok(None);
+    }
+
+    let shards = |name: &str, default: usize| -> anyhow::Result<usize> {
+        match std::env::var(name) {
+            Ok(value) => value
+                .trim()
+                .parse::<usize>()
+                .with_context(|| format!("invalid {name}: {value}")),
+            Err(_) => Ok(default),
+        }
+    };
+    let cfg = ErasureConfig {
+        data_shards: shards(ENV_EC_DATA_SHARDS, DEFAULT_DATA_SHARDS)?,
+        parity_shards: shards(ENV_EC_PARITY_SHARDS, DEFAULT_PARITY_SHARDS)?,
+    };
+    if cfg.data_shards == 0 || cfg.parity_shards == 0 || cfg.total_shards() > 256 {
+        bail!(
+            "invalid erasure shape {}+{}: need data ≥ 1, parity ≥ 1, total ≤ 256",
+            cfg.data_shards,
+            cfg.parity_shards
+        );
+    }
+    Ok(Some(cfg))
+}
+
+/// Return the configured shard roots; empty means shards live under the data dir.
+#[must_use]
+pub fn erasure_roots_from_env() -> Vec<PathBuf> {
+    std::env::var(ENV_EC_ROOTS)
+        .ok()
+        .map(|value| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|root| !root.is_empty())
+                .map(PathBuf::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// One replication peer from `RON_STORAGE_PEERS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplicationPeerConfig {
+    pub peer: Peer,
+    pub base_url: String,
+}
+
+/// Return the configured replication peers; empty means replication is off (the default).
+pub fn replication_peers_from_env() -> anyhow::Result<Vec<ReplicationPeerConfig>> {
+    let Ok(value) = std::env::var(ENV_PEERS) else {
+        return Ok(Vec::new());
+    };
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (id, rest) = entry.split_once('@').with_context(|| {
+                format!("invalid {ENV_PEERS} entry {entry}: want id@region=url")
+            })?;
+            let (region, base_url) = rest.split_once('=').with_context(|| {
+                format!("invalid {ENV_PEERS} entry {entry}: want id@region=url")
+            })?;
+            if id.is_empty() || base_url.is_empty() {
+                bail!("invalid {ENV_PEERS} entry {entry}: want id@region=url");
+            }
+            Ok(ReplicationPeerConfig {
+                peer: Peer::new(id, region),
+                base_url: base_url.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Delay between replication rounds.
+#[must_use]
+pub fn replication_interval_from_env() -> Duration {
+    let secs = std::env::var(ENV_REPLICATION_INTERVAL_SECS)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(DEFAULT_REPLICATION_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Return the at-rest compression policy, or `None` when compression is off (the default).
+pub fn compression_config_from_env() -> anyhow::Result<Option<CompressionConfig>> {
+    let enabled = std::env::var(ENV_COMPRESSION)
+        .ok()
+        .map(|value| value.trim().to_ascii_lowercase())
+        .is_some_and(|value| matches!(value.as_str(), "1" | "true" | "on" | "yes" | "zstd"));
+    if !enabled {
+        return Ok(None);
+    }
+
+    fn parse<T: FromStr>(name: &str, default: T) -> anyhow::Result<T> {
+        match std::env::var(name) {
+            Ok(value) => value
+                .trim()
+                .parse::<T>()
+                .map_err(|_| anyhow::anyhow!("invalid {name}: {value}")),
+            Err(_) => Ok(default),
+        }
+    }
+    let cfg = CompressionConfig {
+        level: parse(ENV_COMPRESSION_LEVEL, DEFAULT_LEVEL)?,
+        min_bytes: parse(ENV_COMPRESSION_MIN_BYTES, DEFAULT_MIN_BYTES)?,
+        chunk_size: parse(ENV_COMPRESSION_CHUNK_BYTES, DEFAULT_CHUNK_SIZE)?,
+    };
+    if !zstd::compression_level_range().contains(&cfg.level) {
+        bail!(
+            "invalid {ENV_COMPRESSION_LEVEL}: {} out of range",
+            cfg.level
+        );
+    }
+    if cfg.chunk_size == 0 || cfg.chunk_size > MAX_CHUNK_SIZE {
+        bail!(
+            "invalid {ENV_COMPRESSION_CHUNK_BYTES}: {} (need 1..={MAX_CHUNK_SIZE})",
+            cfg.chunk_size
+        );
+    }
+    Ok(Some(cfg))
+}
+
+/// Sealed-envelope key file path; `None` leaves sealing off (seal requests are refused).
+pub fn pq_envelope_key_from_env() -> Option<PathBuf> {
+    std::env::var(ENV_PQ_ENVELOPE_KEY)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
 /// Runtime config for svc-storage.
+///
+/// Filesystem-backend knobs (erasure, compression, sealed envelopes) are not part of it;
+/// `main` reads them through the `*_from_env` helpers above.
 #[derive(Debug, Clone)]
 pub struct Config {
     /// HTTP bind address.
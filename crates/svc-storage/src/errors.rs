@@ -21,4 +21,16 @@ pub enum StorageError {
 
     #[error("integrity check failed")]
     IntegrityFailed,
+
+    #[error("erasure coding error: {0}")]
+    Erasure(String),
+
+    #[error("replication error: {0}")]
+    Replication(String),
+
+    #[error("compression error: {0}")]
+    Compression(String),
+
+    #[error("envelope error: {0}")]
+    Envelope(String),
 }
@@ -14,6 +14,10 @@ pub fn into_response(err: StorageError) -> impl IntoResponse {
         }
         StorageError::CapacityExceeded => (StatusCode::PAYLOAD_TOO_LARGE, "capacity_exceeded"),
         StorageError::IntegrityFailed => (StatusCode::BAD_REQUEST, "integrity_failed"),
+        StorageError::Erasure(_) => (StatusCode::INTERNAL_SERVER_ERROR, "erasure_error"),
+        StorageError::Replication(_) => (StatusCode::BAD_GATEWAY, "replication_error"),
+        StorageError::Compression(_) => (StatusCode::INTERNAL_SERVER_ERROR, "compression_error"),
+        StorageError::Envelope(_) => (StatusCode::INTERNAL_SERVER_ERROR, "envelope_error"),
         StorageError::Io(_) => (StatusCode::INTERNAL_SERVER_ERROR, "io_error"),
     };
 
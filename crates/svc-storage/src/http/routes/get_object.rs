@@ -4,7 +4,9 @@ use axum::{
     response::{IntoResponse, Response},
 };
 
-use crate::http::extractors::AppState;
+use crate::auth::require_token;
+use crate::errors::StorageError;
+use crate::http::{error, extractors::AppState};
 
 /// b3:<64 lowercase hex>
 #[inline]
@@ -89,6 +91,14 @@ pub async fn handler(
         Err(_) => return (StatusCode::NOT_FOUND, ()).into_response(),
     };
 
+    // Sealed objects are never served without a valid macaroon, even in dev mode; this gate
+    // covers full and range reads, and an unknown status (store error) counts as sealed.
+    if !matches!(app.store.sealed(&cid).await, Ok(false)) {
+        if let Err(reject) = require_token(&headers_in) {
+            return reject.into_response();
+        }
+    }
+
     // Range?
     if let Some(hv) = headers_in.get(axum::http::header::RANGE) {
         if let Ok(hs) = hv.to_str() {
@@ -118,17 +128,11 @@ pub async fn handler(
                         );
                         return (StatusCode::PARTIAL_CONTENT, headers, chunk).into_response();
                     }
-                    Err(_) => return (StatusCode::RANGE_NOT_SATISFIABLE, ()).into_response(),
+                    Err(StorageError::RangeNotSatisfiable) => return unsatisfiable(meta.len),
+                    Err(e) => return error::into_response(e).into_response(),
                 }
             } else {
-                // 416 must include Content-Range: */<len>
-                let mut headers = HeaderMap::new();
-                headers.insert(
-                    axum::http::header::CONTENT_RANGE,
-                    HeaderValue::from_str(&format!("*/{}", meta.len))
-                        .expect("content range should be a valid header value"),
-                );
-                return (StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response();
+                return unsatisfiable(meta.len);
             }
         }
     }
@@ -152,3 +156,14 @@ pub async fn handler(
         Err(_) => (StatusCode::NOT_FOUND, ()).into_response(),
     }
 }
+
+/// 416 must include Content-Range: */<len>
+fn unsatisfiable(len: u64) -> Response {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        axum::http::header::CONTENT_RANGE,
+        HeaderValue::from_str(&format!("*/{len}"))
+            .expect("content range should be a valid header value"),
+    );
+    (StatusCode::RANGE_NOT_SATISFIABLE, headers).into_response()
+}
@@ -4,7 +4,9 @@ use axum::{
     response::IntoResponse,
 };
 
+use crate::auth::require_token;
 use crate::http::extractors::AppState;
+use crate::storage::SEALED_HEADER;
 
 /// b3:<64 lowercase hex>
 #[inline]
@@ -29,7 +31,11 @@ fn ensure_quoted_etag(etag: &str) -> String {
     }
 }
 
-pub async fn handler(State(app): State<AppState>, Path(cid): Path<String>) -> impl IntoResponse {
+pub async fn handler(
+    State(app): State<AppState>,
+    Path(cid): Path<String>,
+    headers_in: HeaderMap,
+) -> impl IntoResponse {
     // Malformed CID → 400
     if !is_valid_cid(&cid) {
         return StatusCode::BAD_REQUEST.into_response();
@@ -49,6 +55,14 @@ pub async fn handler(State(app): State<AppState>, Path(cid): Path<String>) -> im
                 HeaderValue::from_str(&meta.len.to_string()).unwrap(),
             );
 
+            // Sealed objects need a macaroon for HEAD too; unknown status counts as sealed.
+            if !matches!(app.store.sealed(&cid).await, Ok(false)) {
+                if let Err(reject) = require_token(&headers_in) {
+                    return reject.into_response();
+                }
+                headers.insert(SEALED_HEADER, HeaderValue::from_static("true"));
+            }
+
             (StatusCode::OK, headers).into_response()
         }
         // Well-formed but unknown → 404
@@ -40,6 +40,7 @@ use crate::{
             WalletSettlementHttpClient,
         },
     },
+    storage::PutOptions,
 };
 
 const H_TENANT: &str = "x-ron-tenant";
@@ -216,7 +217,11 @@ pub async fn handler(
         }
     };
 
-    if let Err(err) = app.store.put(&cid, body).await {
+    if let Err(err) = app
+        .store
+        .put_with(&cid, body, PutOptions::from_headers(&headers))
+        .await
+    {
         if let (Some(client), Some(plan)) = (&settlement_client, &settlement_plan) {
             let _ = client.release_failed_paid_storage(plan).await;
         }
@@ -1,11 +1,16 @@
 use crate::http::extractors::AppState;
 use axum::{
     extract::State,
+    http::HeaderMap,
     response::{IntoResponse, Response},
 };
 
-pub async fn handler(State(app): State<AppState>, body: bytes::Bytes) -> Response {
-    super::put_object::handler(State(app), body)
+pub async fn handler(
+    State(app): State<AppState>,
+    headers: HeaderMap,
+    body: bytes::Bytes,
+) -> Response {
+    super::put_object::handler(State(app), headers, body)
         .await
         .into_response()
 }
@@ -1,21 +1,30 @@
-use axum::{extract::State, response::IntoResponse, Json};
+use axum::{extract::State, http::HeaderMap, response::IntoResponse, Json};
 use blake3;
 use serde::Serialize;
 
 use crate::http::extractors::AppState;
+use crate::storage::PutOptions;
 
 #[derive(Serialize)]
 struct PutResp {
     cid: String,
 }
 
-pub async fn handler(State(app): State<AppState>, body: bytes::Bytes) -> impl IntoResponse {
+pub async fn handler(
+    State(app): State<AppState>,
+    headers: HeaderMap,
+    body: bytes::Bytes,
+) -> impl IntoResponse {
     // Compute b3 content id from the body (exactly what your script expects)
     let digest = blake3::hash(&body).to_hex().to_string();
     let cid = format!("b3:{digest}");
 
-    // Store full body
-    if let Err(e) = app.store.put(&cid, body).await {
+    // Store full body; Content-Type only steers at-rest compression, x-ron-seal asks for an envelope.
+    if let Err(e) = app
+        .store
+        .put_with(&cid, body, PutOptions::from_headers(&headers))
+        .await
+    {
         return (
             axum::http::StatusCode::INTERNAL_SERVER_ERROR,
             format!("put failed: {e}"),
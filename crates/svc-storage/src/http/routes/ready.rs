@@ -1,10 +1,18 @@
-use axum::{extract::State, response::IntoResponse};
+use std::sync::Arc;
+
+use axum::{extract::State, response::IntoResponse, Extension};
 use http::StatusCode;
 
 use crate::http::extractors::AppState;
+use crate::readiness::Readiness;
 
-pub async fn handler(State(_app): State<AppState>) -> impl IntoResponse {
-    // If the HTTP server is bound and AppState is wired, report ready.
-    // Deeper readiness gates (quotas, residency, etc.) can be added later.
-    StatusCode::OK
+pub async fn handler(
+    State(_app): State<AppState>,
+    readiness: Option<Extension<Arc<Readiness>>>,
+) -> impl IntoResponse {
+    // Without a readiness handle (tests, embedded routers), a bound server with AppState is ready.
+    match readiness {
+        Some(Extension(readiness)) if !readiness.all_ready() => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::OK,
+    }
 }
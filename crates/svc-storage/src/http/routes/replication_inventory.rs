@@ -0,0 +1,24 @@
+//! RO:WHAT — /replication/inventory handler: list every cid this node holds.
+//! RO:WHY  — Peers pull objects placement assigns to them (storage::replication::HttpPeer).
+//! RO:SECURITY — the full cid list is not public: a valid bearer macaroon is always required (no dev bypass).
+
+use axum::{extract::State, http::HeaderMap, response::IntoResponse, Json};
+use serde::Serialize;
+
+use crate::auth::require_token;
+use crate::http::{error, extractors::AppState};
+
+#[derive(Serialize)]
+struct InventoryDto {
+    cids: Vec<String>,
+}
+
+pub async fn handler(State(app): State<AppState>, headers: HeaderMap) -> axum::response::Response {
+    if let Err(reject) = require_token(&headers) {
+        return reject.into_response();
+    }
+    match app.store.list().await {
+        Ok(cids) => Json(InventoryDto { cids }).into_response(),
+        Err(e) => error::into_response(e).into_response(),
+    }
+}
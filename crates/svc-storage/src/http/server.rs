@@ -8,11 +8,11 @@
 //! RO:SECURITY — paid estimate is read-only; paid write enforces verifier and settlement modes.
 //! RO:TEST — http_blackbox, paid_write_estimate, web3_paid_storage_loop.
 
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 
 use axum::{
     routing::{get, head, put},
-    Router,
+    Extension, Router,
 };
 use tracing::{error, info};
 
@@ -20,7 +20,8 @@ use crate::http::extractors::AppState;
 #[cfg(feature = "metrics")]
 use crate::http::routes::metrics;
 use crate::http::routes::{get_object, head_object, paid_estimate, paid_object, put_object};
-use crate::http::routes::{health, ready, version};
+use crate::http::routes::{health, ready, replication_inventory, version};
+use crate::readiness::Readiness;
 
 /// Build a router whose state type is **AppState**.
 pub fn build_router() -> Router<AppState> {
@@ -38,6 +39,11 @@ pub fn build_router() -> Router<AppState> {
             "/paid/o",
             put(paid_object::handler).post(paid_object::handler),
         )
+        // Replication inventory for peer pull rounds.
+        .route(
+            "/replication/inventory",
+            get(replication_inventory::handler),
+        )
         // Observability & version.
         .route("/version", get(version::handler))
         .route("/healthz", get(health::handler))
@@ -49,7 +55,7 @@ pub fn build_router() -> Router<AppState> {
     let app = Router::new().merge(api);
 
     info!(
-        "mount: POST/PUT /o; GET /paid/o/estimate; POST/PUT /paid/o; HEAD/GET /o/:cid; GET /version; GET /healthz; GET /readyz{}",
+        "mount: POST/PUT /o; GET /paid/o/estimate; POST/PUT /paid/o; HEAD/GET /o/:cid; GET /replication/inventory; GET /version; GET /healthz; GET /readyz{}",
         {
             #[cfg(feature = "metrics")]
             {
@@ -65,11 +71,17 @@ pub fn build_router() -> Router<AppState> {
     app
 }
 
-pub async fn serve_http(addr: SocketAddr, state: AppState) -> anyhow::Result<()> {
+/// Bind and serve until ctrl-c; `/readyz` reports `readiness`.
+pub async fn serve_http(
+    addr: SocketAddr,
+    state: AppState,
+    readiness: Arc<Readiness>,
+) -> anyhow::Result<()> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
     info!("svc-storage listening on {addr}");
+    readiness.set_listeners_bound(true);
 
-    let app = build_router().with_state(state);
+    let app = build_router().with_state(state).layer(Extension(readiness));
     let make_svc = app.into_make_service();
 
     axum::serve(listener, make_svc)
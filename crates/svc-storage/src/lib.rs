@@ -11,6 +11,7 @@
 #![forbid(unsafe_code)]
 
 pub mod accounting;
+pub mod auth;
 pub mod config;
 pub mod errors;
 #[cfg(feature = "metrics")]
@@ -35,6 +36,7 @@ pub mod http {
         pub mod post_object;
         pub mod put_object;
         pub mod ready;
+        pub mod replication_inventory;
         pub mod version;
     }
     pub mod server;
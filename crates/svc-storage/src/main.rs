@@ -1,7 +1,19 @@
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
+use anyhow::Context;
+use svc_storage::config::{
+    compression_config_from_env, erasure_config_from_env, erasure_roots_from_env,
+    replication_interval_from_env, replication_peers_from_env, ENV_NODE_ID, ENV_PEER_TOKEN,
+};
 use svc_storage::http::{extractors::AppState, server::serve_http};
-use svc_storage::storage::{MemoryStorage, Storage};
+use svc_storage::policy::residency::ENV_REGION;
+use svc_storage::readiness::Readiness;
+use svc_storage::storage::hedged::{HedgeConfig, HedgedStorage};
+use svc_storage::storage::placement::{Peer, PlacementEngine, PlacementPolicy};
+use svc_storage::storage::repair::{RepairConfig, RepairWorker, StorageSource};
+use svc_storage::storage::replication::{HttpPeer, PeerStorage, ReplicaPeer, Replicator};
+use svc_storage::storage::{DynStorage, FsStorage, MemoryStorage};
+use tokio::sync::watch;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -9,12 +21,99 @@ async fn main() -> anyhow::Result<()> {
         .unwrap_or_else(|_| "127.0.0.1:5303".to_string())
         .parse()?;
 
-    // In-memory store for smoke tests; pluggable later.
-    let store: Arc<dyn Storage> = Arc::new(MemoryStorage::default());
-    let state = AppState { store };
+    let readiness = Arc::new(Readiness::new());
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let mut workers = Vec::new();
+
+    // In-memory store for smoke tests unless a data dir is set explicitly.
+    let mut local_fs = None;
+    let store: DynStorage = match std::env::var("RON_STORAGE_DATA_DIR") {
+        Ok(dir) => {
+            let mut fs = FsStorage::new(PathBuf::from(dir)).await?;
+            if let Some(ec) = erasure_config_from_env()? {
+                fs = fs.with_erasure(ec)?;
+                let roots = erasure_roots_from_env();
+                if !roots.is_empty() {
+                    fs = fs.with_shard_roots(roots).await?;
+                }
+                if !fs.survives_shard_root_loss() {
+                    eprintln!(
+                        "warning: losing one shard root loses more than {} shards; \
+                         set RON_STORAGE_EC_ROOTS to more disks",
+                        ec.parity_shards
+                    );
+                }
+            }
+            if let Some(zc) = compression_config_from_env()? {
+                fs = fs.with_compression(zc);
+            }
+            #[cfg(feature = "pq-envelope")]
+            if let Some(key) = svc_storage::config::pq_envelope_key_from_env() {
+                let envelope = svc_storage::storage::pq_envelope::Envelope::from_key_file(&key)?;
+                fs = fs.with_envelope(envelope);
+            }
+            let fs = Arc::new(fs);
+            local_fs = Some(Arc::clone(&fs));
+            fs
+        }
+        Err(_) => Arc::new(MemoryStorage::default()),
+    };
+
+    // Replicate to the configured peers and hedge reads against them;
+    // without RON_STORAGE_PEERS this node stands alone.
+    let mut served_store = Arc::clone(&store);
+    let mut replicas: Vec<DynStorage> = Vec::new();
+    let peers = replication_peers_from_env()?;
+    if !peers.is_empty() {
+        let node_id = std::env::var(ENV_NODE_ID)
+            .with_context(|| format!("{ENV_NODE_ID} is required when peers are configured"))?;
+        let region = std::env::var(ENV_REGION).unwrap_or_default();
+        let engine = Arc::new(PlacementEngine::new(PlacementPolicy::from_env()?));
+        let replicator = Replicator::new(Peer::new(node_id, region), Arc::clone(&store), engine);
+        let token = std::env::var(ENV_PEER_TOKEN).ok();
+        for cfg in peers {
+            let mut transport = HttpPeer::new(cfg.base_url, Duration::from_secs(30))?;
+            if let Some(token) = &token {
+                transport = transport.with_token(token.clone());
+            }
+            let transport: Arc<dyn ReplicaPeer> = Arc::new(transport);
+            replicas.push(Arc::new(PeerStorage(Arc::clone(&transport))));
+            replicator.add_peer(cfg.peer, transport);
+        }
+        served_store = Arc::new(HedgedStorage::new(
+            Arc::clone(&store),
+            replicas.clone(),
+            HedgeConfig::default(),
+        ));
+        workers
+            .push(Arc::new(replicator).spawn(replication_interval_from_env(), shutdown_rx.clone()));
+    }
+
+    // Scrub and repair in the background, refetching from the peers when shards cannot
+    // rebuild an object; a large unrepaired backlog degrades /readyz.
+    if let Some(fs) = local_fs {
+        let mut repair =
+            RepairWorker::new(fs, RepairConfig::default()).with_readiness(Arc::clone(&readiness));
+        for replica in replicas {
+            repair = repair.with_source(Arc::new(StorageSource(replica)));
+        }
+        workers.push(Arc::new(repair).spawn(shutdown_rx.clone()));
+    }
+
+    let state = AppState {
+        store: served_store,
+    };
+    readiness.set_config_loaded(true);
+
+    let served = serve_http(addr, state, readiness).await;
+
+    let _ = shutdown_tx.send(true);
+    for worker in workers {
+        let _ = worker.await;
+    }
 
     // Handle the Result so clippy’s unused_must_use stays green.
-    if let Err(e) = serve_http(addr, state).await {
+    if let Err(e) = served {
         eprintln!("server error: {e:#}");
         std::process::exit(1);
     }
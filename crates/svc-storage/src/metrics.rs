@@ -2,13 +2,16 @@
 //! RO:WHY — Observability contract; paid storage must surface admission, settlement, and accounting export outcomes.
 //! RO:INTERACTS — /metrics route via prometheus::gather(), paid_object handler, accounting exporter.
 //! RO:INVARIANTS — no account IDs, CIDs, receipt hashes, or private labels in metrics.
-//! RO:METRICS — storage_paid_write_total, storage_paid_write_bytes_total, storage_accounting_export_total.
+//! RO:METRICS — storage_paid_write_total, storage_paid_write_bytes_total, storage_accounting_export_total, storage_repair_*, storage_replication_total, storage_hedged_read_total, storage_compression_total.
 //! RO:CONFIG — enabled by the `metrics` feature, default-on for svc-storage.
 //! RO:SECURITY — labels are low-cardinality machine statuses only.
 //! RO:TEST — paid_write_policy, paid_write_accounting_export, and web3_paid_storage_loop assert metric paths.
 
 use once_cell::sync::Lazy;
-use prometheus::{register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec};
+use prometheus::{
+    register_int_counter, register_int_counter_vec, register_int_gauge, IntCounter, IntCounterVec,
+    IntGauge,
+};
 
 static PAID_WRITE_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -44,6 +47,50 @@ static ACCOUNTING_EXPORT_EVENTS_TOTAL: Lazy<IntCounter> = Lazy::new(|| {
     .expect("storage_accounting_export_events_total registration should succeed")
 });
 
+static REPAIR_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "storage_repair_total",
+        "Total repair worker outcomes (scanned, corrupt, repaired, error).",
+        &["outcome"]
+    )
+    .expect("storage_repair_total registration should succeed")
+});
+
+static REPAIR_BACKLOG: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "storage_repair_backlog",
+        "Corrupt or missing objects awaiting a clean copy."
+    )
+    .expect("storage_repair_backlog registration should succeed")
+});
+
+static REPLICATION_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "storage_replication_total",
+        "Total replication transfers by outcome (pushed, pulled, failed).",
+        &["outcome"]
+    )
+    .expect("storage_replication_total registration should succeed")
+});
+
+static HEDGED_READ_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "storage_hedged_read_total",
+        "Total hedged reads by winning path (primary, hedge, fallback, failed).",
+        &["outcome"]
+    )
+    .expect("storage_hedged_read_total registration should succeed")
+});
+
+static COMPRESSION_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "storage_compression_total",
+        "Total objects written by the fs backend, by at-rest encoding (compressed, raw).",
+        &["outcome"]
+    )
+    .expect("storage_compression_total registration should succeed")
+});
+
 /// Record one paid-write admission result.
 pub fn observe_paid_write(status: &'static str, bytes_stored: u64) {
     PAID_WRITE_TOTAL.with_label_values(&[status]).inc();
@@ -62,6 +109,31 @@ pub fn observe_accounting_export(status: &'static str, event_count: u64) {
     }
 }
 
+/// Record one repair worker outcome (`scanned`, `corrupt`, `repaired`, or `error`).
+pub fn observe_repair(outcome: &'static str) {
+    REPAIR_TOTAL.with_label_values(&[outcome]).inc();
+}
+
+/// Publish the current repair backlog size.
+pub fn set_repair_backlog(backlog: i64) {
+    REPAIR_BACKLOG.set(backlog);
+}
+
+/// Record one replication transfer outcome (`pushed`, `pulled`, or `failed`).
+pub fn observe_replication(outcome: &'static str) {
+    REPLICATION_TOTAL.with_label_values(&[outcome]).inc();
+}
+
+/// Record which path served a hedged read.
+pub fn observe_hedged_read(outcome: &'static str) {
+    HEDGED_READ_TOTAL.with_label_values(&[outcome]).inc();
+}
+
+/// Record whether an object was stored compressed or raw.
+pub fn observe_compression(outcome: &'static str) {
+    COMPRESSION_TOTAL.with_label_values(&[outcome]).inc();
+}
+
 /// Ensure paid-write and accounting-export metric families exist before traffic arrives.
 ///
 /// The `/metrics` route calls this so fresh dev runs and dashboards show the
@@ -79,6 +151,23 @@ pub fn register_paid_write_metrics() {
     ACCOUNTING_EXPORT_TOTAL.with_label_values(&["exported"]);
     ACCOUNTING_EXPORT_TOTAL.with_label_values(&["failed"]);
 
+    REPAIR_TOTAL.with_label_values(&["scanned"]);
+    REPAIR_TOTAL.with_label_values(&["corrupt"]);
+    REPAIR_TOTAL.with_label_values(&["repaired"]);
+    REPAIR_TOTAL.with_label_values(&["error"]);
+
+    REPLICATION_TOTAL.with_label_values(&["pushed"]);
+    REPLICATION_TOTAL.with_label_values(&["pulled"]);
+    REPLICATION_TOTAL.with_label_values(&["failed"]);
+
+    for outcome in ["primary", "hedge", "fallback", "failed"] {
+        HEDGED_READ_TOTAL.with_label_values(&[outcome]);
+    }
+
+    COMPRESSION_TOTAL.with_label_values(&["compressed"]);
+    COMPRESSION_TOTAL.with_label_values(&["raw"]);
+
     Lazy::force(&PAID_WRITE_BYTES_TOTAL);
+    Lazy::force(&REPAIR_BACKLOG);
     Lazy::force(&ACCOUNTING_EXPORT_EVENTS_TOTAL);
 }
@@ -1 +1,124 @@
-// Policy module: residency (scaffold)
+//! RO:WHAT — Region residency policy for object placement.
+//! RO:WHY — GOVERNANCE [I-S4]; objects may only land in allowed regions, and strict tenants never leave home.
+//! RO:INTERACTS — storage::placement (filters and orders candidate peers), config (RON_STORAGE_REGION / RON_STORAGE_RESIDENCY).
+//! RO:INVARIANTS — strict-region never permits a foreign peer; an allowlist, when set, is always enforced.
+//! RO:METRICS — none here; placement shortfalls surface through replication reports.
+//! RO:CONFIG — RON_STORAGE_RESIDENCY (none|region|strict-region), RON_STORAGE_REGION, RON_STORAGE_ALLOWED_REGIONS.
+//! RO:SECURITY — residency is a legal constraint; fail closed (fewer replicas) rather than place out of region.
+//! RO:TEST — tests/storage_replication.rs.
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::bail;
+
+/// Environment variable for this node's region label.
+pub const ENV_REGION: &str = "RON_STORAGE_REGION";
+
+/// Environment variable selecting the residency mode.
+pub const ENV_RESIDENCY: &str = "RON_STORAGE_RESIDENCY";
+
+/// Environment variable with a comma-separated region allowlist (empty = any).
+pub const ENV_ALLOWED_REGIONS: &str = "RON_STORAGE_ALLOWED_REGIONS";
+
+/// How strictly replicas must stay in the home region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResidencyMode {
+    /// Any (allowed) region.
+    #[default]
+    None,
+    /// Any (allowed) region, but home-region peers are filled first.
+    Region,
+    /// Home region only.
+    StrictRegion,
+}
+
+impl ResidencyMode {
+    /// Stable config spelling.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Region => "region",
+            Self::StrictRegion => "strict-region",
+        }
+    }
+}
+
+impl fmt::Display for ResidencyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ResidencyMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "none" | "off" => Ok(Self::None),
+            "region" | "prefer-region" => Ok(Self::Region),
+            "strict-region" | "strict_region" | "strict" => Ok(Self::StrictRegion),
+            other => {
+                bail!("invalid {ENV_RESIDENCY}: {other}; expected none, region, or strict-region")
+            }
+        }
+    }
+}
+
+/// Residency rules applied to one node's placement decisions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResidencyPolicy {
+    pub mode: ResidencyMode,
+    /// Region label of the home (writing) node.
+    pub home_region: String,
+    /// Regions replicas may be placed in; empty means any.
+    pub allowed_regions: Vec<String>,
+}
+
+impl ResidencyPolicy {
+    /// Parse mode, home region, and allowlist from the environment.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let mode = match std::env::var(ENV_RESIDENCY) {
+            Ok(value) => value.parse()?,
+            Err(_) => ResidencyMode::default(),
+        };
+        let home_region = std::env::var(ENV_REGION)
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default();
+        if mode == ResidencyMode::StrictRegion && home_region.is_empty() {
+            bail!("{ENV_RESIDENCY}=strict-region requires {ENV_REGION}");
+        }
+        let allowed_regions = std::env::var(ENV_ALLOWED_REGIONS)
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|region| !region.is_empty())
+            .map(str::to_string)
+            .collect();
+        Ok(Self {
+            mode,
+            home_region,
+            allowed_regions,
+        })
+    }
+
+    /// Whether a replica may be placed on a peer in `region`.
+    #[must_use]
+    pub fn permits(&self, region: &str) -> bool {
+        if !self.allowed_regions.is_empty() && !self.allowed_regions.iter().any(|r| r == region) {
+            return false;
+        }
+        match self.mode {
+            ResidencyMode::None | ResidencyMode::Region => true,
+            ResidencyMode::StrictRegion => region == self.home_region,
+        }
+    }
+
+    /// Whether peers in `region` should be filled before others.
+    #[must_use]
+    pub fn prefers(&self, region: &str) -> bool {
+        self.mode != ResidencyMode::None && region == self.home_region
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 pub struct Readiness {
     config_loaded: AtomicBool,
     listeners_bound: AtomicBool,
+    repair_degraded: AtomicBool,
 }
 
 impl Readiness {
@@ -19,14 +20,25 @@ impl Readiness {
         self.listeners_bound.store(v, Ordering::Relaxed);
     }
 
+    /// Set by the repair worker while its corrupt-object backlog is over threshold.
+    pub fn set_repair_degraded(&self, v: bool) {
+        self.repair_degraded.store(v, Ordering::Relaxed);
+    }
+
+    pub fn repair_degraded(&self) -> bool {
+        self.repair_degraded.load(Ordering::Relaxed)
+    }
+
     /// Minimal health: all invariants that should be up even when not "ready".
     pub fn health_ok(&self) -> bool {
         // For now, "healthy" if the process is running; later include store checks.
         true
     }
 
-    /// Ready when config is loaded and listeners are bound.
+    /// Ready when config is loaded, listeners are bound, and repair is not degraded.
     pub fn all_ready(&self) -> bool {
-        self.config_loaded.load(Ordering::Relaxed) && self.listeners_bound.load(Ordering::Relaxed)
+        self.config_loaded.load(Ordering::Relaxed)
+            && self.listeners_bound.load(Ordering::Relaxed)
+            && !self.repair_degraded()
     }
 }
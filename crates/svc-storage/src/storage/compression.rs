@@ -1 +1,313 @@
-// Storage module: compression (scaffold)
+//! RO:WHAT — Per-object zstd compression at rest: policy (content-type + size threshold) and the framed blob format.
+//! RO:WHY — Text-heavy site bundles shrink several-fold; independent frames keep Range reads from inflating whole objects.
+//! RO:INTERACTS — storage::fs (encode on put, header probe + frame decode on head/get/range), storage::repair (re-hash).
+//! RO:INVARIANTS — the b3 address and ETag are over the uncompressed bytes; a header only counts if it embeds the cid digest.
+//! RO:METRICS — storage_compression_total{outcome=compressed|raw}.
+//! RO:CONFIG — CompressionConfig { level, min_bytes, chunk_size }; RON_STORAGE_COMPRESSION*.
+//! RO:SECURITY — each frame inflates into a buffer capped at chunk_size; oversized or short frames are IntegrityFailed.
+//! RO:TEST — tests/storage_compression.rs.
+//!
+//! On-disk layout of a compressed blob (all integers little-endian):
+//!
+//! ```text
+//! magic "RONZ" | version u8 | codec u8 | b3 digest [32] | chunk_size u32 | len u64 | frames u32
+//! | frame_len u32 × frames | zstd frame × frames
+//! ```
+//!
+//! Frame `i` holds uncompressed bytes `[i * chunk_size, (i + 1) * chunk_size)`.
+//! Blobs without a matching header are stored raw, exactly as before.
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::errors::StorageError;
+
+/// Leading bytes of every compressed blob.
+pub const MAGIC: &[u8; 4] = b"RONZ";
+
+/// Header format version written to disk.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Codec id for zstd frames.
+pub const CODEC_ZSTD: u8 = 1;
+
+/// Length of the fixed part of the header (before the frame table).
+pub const HEADER_FIXED_LEN: usize = 4 + 1 + 1 + 32 + 4 + 8 + 4;
+
+/// Default zstd level; cheap enough for the write path.
+pub const DEFAULT_LEVEL: i32 = 3;
+
+/// Default minimum object size worth compressing.
+pub const DEFAULT_MIN_BYTES: usize = 4 * 1024;
+
+/// Default uncompressed bytes per frame (matches the 64 KiB read chunk).
+pub const DEFAULT_CHUNK_SIZE: u32 = 64 * 1024;
+
+/// Largest frame size accepted from disk.
+pub const MAX_CHUNK_SIZE: u32 = 4 * 1024 * 1024;
+
+/// Content types that are already compressed; never worth another pass.
+const INCOMPRESSIBLE: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "image/avif",
+    "audio/",
+    "video/",
+    "application/zip",
+    "application/gzip",
+    "application/x-gzip",
+    "application/zstd",
+    "application/x-7z-compressed",
+    "application/x-xz",
+    "application/x-bzip2",
+    "font/woff",
+    "font/woff2",
+];
+
+/// When and how hard to compress new objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionConfig {
+    pub level: i32,
+    /// Objects smaller than this are stored raw.
+    pub min_bytes: usize,
+    /// Uncompressed bytes per independently decodable frame.
+    pub chunk_size: u32,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: DEFAULT_LEVEL,
+            min_bytes: DEFAULT_MIN_BYTES,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+}
+
+impl CompressionConfig {
+    /// Whether an object of this type and size should be tried at all.
+    ///
+    /// Unknown content types are tried; `compress` still stores them raw when
+    /// zstd does not win.
+    #[must_use]
+    pub fn should_compress(&self, content_type: Option<&str>, len: usize) -> bool {
+        if len < self.min_bytes {
+            return false;
+        }
+        let Some(ct) = content_type else {
+            return true;
+        };
+        let essence = ct
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        !INCOMPRESSIBLE.iter().any(|p| essence.starts_with(p))
+    }
+
+    /// Encode `data` as a framed blob, or `None` when compression saves less than 1/8.
+    pub fn compress(&self, cid: &str, data: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        let digest = cid_digest(cid).ok_or(StorageError::BadAddress)?;
+        let chunk = self.chunk_size.clamp(1, MAX_CHUNK_SIZE) as usize;
+
+        let mut frames = Vec::with_capacity(data.len().div_ceil(chunk));
+        for piece in data.chunks(chunk) {
+            let frame = zstd::bulk::compress(piece, self.level)
+                .map_err(|e| StorageError::Compression(format!("zstd compress failed: {e}")))?;
+            frames.push(frame);
+        }
+
+        let body: usize = frames.iter().map(Vec::len).sum();
+        let total = HEADER_FIXED_LEN + frames.len() * 4 + body;
+        if total > data.len() - data.len() / 8 {
+            return Ok(None);
+        }
+
+        let mut out = Vec::with_capacity(total);
+        out.extend_from_slice(MAGIC);
+        out.push(FORMAT_VERSION);
+        out.push(CODEC_ZSTD);
+        out.extend_from_slice(&digest);
+        out.extend_from_slice(&(chunk as u32).to_le_bytes());
+        out.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        out.extend_from_slice(&(frames.len() as u32).to_le_bytes());
+        for frame in &frames {
+            out.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+        }
+        for frame in &frames {
+            out.extend_from_slice(frame);
+        }
+        Ok(Some(out))
+    }
+}
+
+/// Parsed header of a compressed blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobHeader {
+    /// Uncompressed object length.
+    pub len: u64,
+    pub chunk_size: u32,
+    /// Compressed length of each frame, in order.
+    pub frame_lens: Vec<u32>,
+}
+
+impl BlobHeader {
+    /// Frame count announced by the fixed header, if `fixed` is a header for `cid`.
+    ///
+    /// The embedded digest must equal the cid, so a raw blob can never be
+    /// mistaken for a compressed one (it would have to contain its own hash).
+    #[must_use]
+    pub fn probe(cid: &str, fixed: &[u8]) -> Option<usize> {
+        if fixed.len() < HEADER_FIXED_LEN
+            || &fixed[..4] != MAGIC
+            || fixed[4] != FORMAT_VERSION
+            || fixed[5] != CODEC_ZSTD
+            || fixed[6..38] != cid_digest(cid)?
+        {
+            return None;
+        }
+        Some(read_u32(fixed, 50) as usize)
+    }
+
+    /// Decode the fixed header plus frame table; the table must match the announced shape.
+    pub fn decode(fixed: &[u8], table: &[u8]) -> Result<Self, StorageError> {
+        let chunk_size = read_u32(fixed, 38);
+        let len = u64::from_le_bytes(fixed[42..50].try_into().unwrap_or_default());
+        let frames = read_u32(fixed, 50) as usize;
+
+        if chunk_size == 0
+            || chunk_size > MAX_CHUNK_SIZE
+            || table.len() != frames * 4
+            || len.div_ceil(u64::from(chunk_size)) != frames as u64
+        {
+            return Err(StorageError::IntegrityFailed);
+        }
+        let frame_lens = (0..frames).map(|i| read_u32(table, i * 4)).collect();
+        Ok(Self {
+            len,
+            chunk_size,
+            frame_lens,
+        })
+    }
+
+    /// Total header length on disk (fixed part + frame table).
+    #[must_use]
+    pub fn encoded_len(&self) -> u64 {
+        (HEADER_FIXED_LEN + self.frame_lens.len() * 4) as u64
+    }
+
+    /// Frames `first..=last` covering uncompressed `[start, end_inclusive]`,
+    /// with their byte offset from the start of the blob and total length.
+    #[must_use]
+    pub fn frame_span(&self, start: u64, end_inclusive: u64) -> (usize, usize, u64, u64) {
+        let chunk = u64::from(self.chunk_size);
+        let first = (start / chunk) as usize;
+        let last = (end_inclusive / chunk) as usize;
+        let before: u64 = self.frame_lens[..first].iter().map(|&n| u64::from(n)).sum();
+        let span: u64 = self.frame_lens[first..=last]
+            .iter()
+            .map(|&n| u64::from(n))
+            .sum();
+        (first, last, self.encoded_len() + before, span)
+    }
+
+    /// Uncompressed length of frame `index`.
+    fn frame_out_len(&self, index: usize) -> usize {
+        let chunk = u64::from(self.chunk_size);
+        let start = index as u64 * chunk;
+        (self.len - start).min(chunk) as usize
+    }
+
+    /// Inflate consecutive frames starting at `first` from `body`, appending to `out`.
+    pub fn inflate_frames(
+        &self,
+        first: usize,
+        body: &[u8],
+        out: &mut Vec<u8>,
+    ) -> Result<(), StorageError> {
+        let mut offset = 0usize;
+        let mut index = first;
+        while offset < body.len() {
+            let frame_len = *self
+                .frame_lens
+                .get(index)
+                .ok_or(StorageError::IntegrityFailed)? as usize;
+            let frame = body
+                .get(offset..offset + frame_len)
+                .ok_or(StorageError::IntegrityFailed)?;
+            let expect = self.frame_out_len(index);
+            // Capacity caps the output, so a hostile frame cannot balloon memory.
+            let plain =
+                zstd::bulk::decompress(frame, expect).map_err(|_| StorageError::IntegrityFailed)?;
+            if plain.len() != expect {
+                return Err(StorageError::IntegrityFailed);
+            }
+            out.extend_from_slice(&plain);
+            offset += frame_len;
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Decode a whole blob read from disk: `Some(bytes)` if it was compressed, `None` if raw.
+pub fn inflate(cid: &str, blob: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+    let Some(frames) = BlobHeader::probe(cid, blob) else {
+        return Ok(None);
+    };
+    let table_end = HEADER_FIXED_LEN + frames * 4;
+    let table = blob
+        .get(HEADER_FIXED_LEN..table_end)
+        .ok_or(StorageError::IntegrityFailed)?;
+    let header = BlobHeader::decode(&blob[..HEADER_FIXED_LEN], table)?;
+
+    let body = &blob[table_end..];
+    let expected: u64 = header.frame_lens.iter().map(|&n| u64::from(n)).sum();
+    if body.len() as u64 != expected {
+        return Err(StorageError::IntegrityFailed);
+    }
+    let mut out = Vec::with_capacity(blob.len());
+    header.inflate_frames(0, body, &mut out)?;
+    Ok(Some(out))
+}
+
+/// Read and decode the header at the reader's position; `None` if the blob is raw.
+///
+/// The reader is left somewhere inside the header either way; seek before reading on.
+pub async fn read_header<R: AsyncRead + Unpin>(
+    cid: &str,
+    r: &mut R,
+) -> Result<Option<BlobHeader>, StorageError> {
+    let mut fixed = Vec::with_capacity(HEADER_FIXED_LEN);
+    (&mut *r)
+        .take(HEADER_FIXED_LEN as u64)
+        .read_to_end(&mut fixed)
+        .await?;
+    let Some(frames) = BlobHeader::probe(cid, &fixed) else {
+        return Ok(None);
+    };
+    let mut table = Vec::with_capacity(frames.min(1 << 16) * 4);
+    (&mut *r)
+        .take(frames as u64 * 4)
+        .read_to_end(&mut table)
+        .await?;
+    BlobHeader::decode(&fixed, &table).map(Some)
+}
+
+/// Record whether a new object was stored compressed or raw.
+pub(crate) fn observe(_outcome: &'static str) {
+    #[cfg(feature = "metrics")]
+    crate::metrics::observe_compression(_outcome);
+}
+
+fn cid_digest(cid: &str) -> Option<[u8; 32]> {
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(cid.strip_prefix("b3:")?, &mut out).ok()?;
+    Some(out)
+}
+
+fn read_u32(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(buf[at..at + 4].try_into().unwrap_or_default())
+}
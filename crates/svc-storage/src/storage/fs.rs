@@ -1,4 +1,19 @@
 //! Filesystem-backed storage for svc-storage.
+//! RO:WHAT — CAS blobs under `root/<cid>`, or (with erasure coding) shards only, spread over shard roots as
+//!           `<shard_root>/<cid>.shards/NNN` (shard i on root i mod m).
+//! RO:WHY — Durable tier-0 backend; with one shard root per disk, losing a disk costs at most its shards.
+//! RO:INTERACTS — storage::Storage trait, storage::erasure (encode on put, reconstruct on read), storage::compression,
+//!                 storage::pq_envelope (seal on put when asked, open on read).
+//! RO:INVARIANTS — cid is b3:<64 hex>; blobs and shard sets are written, fsynced and renamed into place before anything
+//!                 they replace is deleted; reads never return bytes that fail the cid hash;
+//!                 lengths, ETags and ranges are always over the uncompressed plaintext; sealing is sticky;
+//!                 an object is stored as a blob or as shards, never both.
+//! RO:METRICS — storage_compression_total (via storage::compression).
+//! RO:CONFIG — root dir (RON_STORAGE_DATA_DIR), ErasureConfig + shard roots (RON_STORAGE_EC_*), CompressionConfig (RON_STORAGE_COMPRESSION*),
+//!             Envelope (RON_STORAGE_PQ_ENVELOPE_KEY).
+//! RO:SECURITY — paths are derived only from validated cids, never from raw input; sealed objects are never compressed
+//!               and their shards only ever hold ciphertext.
+//! RO:TEST — tests/storage_erasure.rs, tests/storage_compression.rs, tests/storage_pq_envelope.rs.
 
 use std::path::{Path, PathBuf};
 
@@ -6,11 +21,48 @@ use axum::body::Bytes;
 use tokio::fs;
 use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 
-use crate::{errors::StorageError, types::HeadMeta};
+use super::compression::{self, BlobHeader, CompressionConfig};
+use super::erasure::{shard_id, ErasureCoder, ErasureConfig, ShardManifest};
+#[cfg(feature = "pq-envelope")]
+use super::pq_envelope::{self, Envelope, EnvelopeHeader};
+use super::{HeadMeta, PutOptions, Result, Storage};
+use crate::errors::StorageError;
+
+/// Name of the manifest file inside each of an object's shard directories.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Directory under the root where corrupt blobs are moved aside.
+pub const QUARANTINE_DIR: &str = ".quarantine";
+
+/// How a blob file is encoded on disk.
+enum Layout {
+    Raw(u64),
+    Compressed(BlobHeader),
+    #[cfg(feature = "pq-envelope")]
+    Sealed(EnvelopeHeader),
+}
+
+impl Layout {
+    /// Plaintext object length.
+    fn len(&self) -> u64 {
+        match self {
+            Self::Raw(len) => *len,
+            Self::Compressed(header) => header.len,
+            #[cfg(feature = "pq-envelope")]
+            Self::Sealed(header) => header.len,
+        }
+    }
+}
 
 /// Simple filesystem store rooted at `root/`.
 pub struct FsStorage {
     root: PathBuf,
+    /// Where erasure shards live; defaults to `[root]`.
+    shard_roots: Vec<PathBuf>,
+    erasure: Option<ErasureCoder>,
+    compression: Option<CompressionConfig>,
+    #[cfg(feature = "pq-envelope")]
+    envelope: Option<Envelope>,
 }
 
 impl FsStorage {
@@ -18,7 +70,72 @@ impl FsStorage {
         if !root.exists() {
             fs::create_dir_all(&root).await?;
         }
-        Ok(Self { root })
+        Ok(Self {
+            shard_roots: vec![root.clone()],
+            root,
+            erasure: None,
+            compression: None,
+            #[cfg(feature = "pq-envelope")]
+            envelope: None,
+        })
+    }
+
+    /// Store every new object as erasure shards instead of a blob.
+    pub fn with_erasure(mut self, cfg: ErasureConfig) -> Result<Self> {
+        self.erasure = Some(ErasureCoder::new(cfg)?);
+        Ok(self)
+    }
+
+    /// Spread shards over `roots` (one per disk); shard `i` goes to `roots[i % roots.len()]`.
+    pub async fn with_shard_roots(mut self, roots: Vec<PathBuf>) -> anyhow::Result<Self> {
+        if roots.is_empty() {
+            anyhow::bail!("at least one shard root is required");
+        }
+        for root in &roots {
+            fs::create_dir_all(root).await?;
+        }
+        self.shard_roots = roots;
+        Ok(self)
+    }
+
+    /// Whether losing any single shard root still leaves enough shards to rebuild every new object.
+    #[must_use]
+    pub fn survives_shard_root_loss(&self) -> bool {
+        self.erasure.as_ref().is_some_and(|coder| {
+            let cfg = coder.config();
+            cfg.total_shards().div_ceil(self.shard_roots.len()) <= cfg.parity_shards
+        })
+    }
+
+    /// Compress new objects at rest when `cfg` says they are worth it.
+    pub fn with_compression(mut self, cfg: CompressionConfig) -> Self {
+        self.compression = Some(cfg);
+        self
+    }
+
+    /// Seal objects written with `PutOptions::seal`, and open sealed objects on read.
+    #[cfg(feature = "pq-envelope")]
+    pub fn with_envelope(mut self, envelope: Envelope) -> Self {
+        self.envelope = Some(envelope);
+        self
+    }
+
+    /// Root directory of this store.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether erasure coding is applied to new objects.
+    #[must_use]
+    pub fn erasure_config(&self) -> Option<ErasureConfig> {
+        self.erasure.as_ref().map(ErasureCoder::config)
+    }
+
+    /// Compression policy applied to new objects, if any.
+    #[must_use]
+    pub fn compression_config(&self) -> Option<CompressionConfig> {
+        self.compression
     }
 
     fn is_valid_b3_cid(cid: &str) -> bool {
@@ -30,108 +147,709 @@ impl FsStorage {
         }
     }
 
-    fn path_for(&self, cid: &str) -> Result<PathBuf, StorageError> {
+    fn path_for(&self, cid: &str) -> Result<PathBuf> {
         if !Self::is_valid_b3_cid(cid) {
-            return Err(StorageError::BadRequest("invalid cid".into()));
+            return Err(StorageError::BadAddress);
         }
         Ok(self.root.join(cid))
     }
 
+    /// Shard directory for `cid` on the first shard root.
+    pub fn shard_dir_for(&self, cid: &str) -> Result<PathBuf> {
+        self.shard_dir_at(0, cid)
+    }
+
+    /// Shard directory for `cid` on shard root `root`.
+    pub fn shard_dir_at(&self, root: usize, cid: &str) -> Result<PathBuf> {
+        self.shard_dir_named(root, cid, "shards")
+    }
+
+    /// Where the next shard set of `cid` is written before it is swapped into place.
+    fn staged_shard_dir(&self, root: usize, cid: &str) -> Result<PathBuf> {
+        self.shard_dir_named(root, cid, "shards.tmp")
+    }
+
+    /// Where the previous shard set of `cid` is kept until the new one is in place everywhere.
+    fn retired_shard_dir(&self, root: usize, cid: &str) -> Result<PathBuf> {
+        self.shard_dir_named(root, cid, "shards.old")
+    }
+
+    fn shard_dir_named(&self, root: usize, cid: &str, suffix: &str) -> Result<PathBuf> {
+        if !Self::is_valid_b3_cid(cid) {
+            return Err(StorageError::BadAddress);
+        }
+        let root = self.shard_roots.get(root).ok_or(StorageError::BadAddress)?;
+        Ok(root.join(format!("{cid}.{suffix}")))
+    }
+
+    fn shard_path(&self, cid: &str, index: usize) -> Result<PathBuf> {
+        Ok(self
+            .shard_dir_at(index % self.shard_roots.len(), cid)?
+            .join(format!("{index:03}")))
+    }
+
+    fn etag_for(cid: &str) -> String {
+        // Strong ETag (content hash), same shape as MemoryStorage.
+        format!("\"{}\"", &cid[3..])
+    }
+
+    fn not_found_or_io(e: std::io::Error) -> StorageError {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            StorageError::NotFound
+        } else {
+            StorageError::Io(e)
+        }
+    }
+
     async fn write_all_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
-        let tmp = path.with_extension("tmp");
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
         {
             let mut f = fs::File::create(&tmp).await?;
             f.write_all(data).await?;
             f.flush().await?;
+            f.sync_all().await?;
         }
         // Replace temp with final
         // On all platforms tokio::fs::rename overwrites if allowed by OS.
         fs::rename(&tmp, path).await?;
+        if let Some(dir) = path.parent() {
+            Self::sync_dir(dir).await?;
+        }
         Ok(())
     }
-}
 
-#[async_trait::async_trait]
-impl crate::storage::Storage for FsStorage {
-    async fn put_bytes(&self, bytes: Bytes) -> Result<HeadMeta, StorageError> {
-        // Compute BLAKE3 CID
-        let hash = blake3::hash(&bytes);
-        let cid = format!("b3:{}", hash.to_hex());
-        let len = bytes.len() as u64;
+    /// Flush a directory's entries (creates, renames) to disk.
+    async fn sync_dir(dir: &Path) -> std::io::Result<()> {
+        #[cfg(unix)]
+        fs::File::open(dir).await?.sync_all().await?;
+        #[cfg(not(unix))]
+        let _ = dir;
+        Ok(())
+    }
 
-        let path = self.path_for(&cid)?;
-        if !path.exists() {
-            Self::write_all_atomic(&path, &bytes)
-                .await
-                .map_err(StorageError::Io)?;
+    async fn remove_dir_if_present(dir: &Path) -> Result<()> {
+        match fs::remove_dir_all(dir).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(StorageError::Io(e)),
         }
+    }
 
-        Ok(HeadMeta { cid, len })
+    /// On-disk form of a plaintext object: compressed when the policy accepts it and zstd wins.
+    fn encode_blob(
+        &self,
+        cid: &str,
+        data: &[u8],
+        content_type: Option<&str>,
+    ) -> Result<Option<Vec<u8>>> {
+        let packed = match &self.compression {
+            Some(cfg) if cfg.should_compress(content_type, data.len()) => {
+                cfg.compress(cid, data)?
+            }
+            _ => None,
+        };
+        if self.compression.is_some() {
+            compression::observe(if packed.is_some() {
+                "compressed"
+            } else {
+                "raw"
+            });
+        }
+        Ok(packed)
     }
 
-    async fn has(&self, cid: &str) -> Result<bool, StorageError> {
-        let path = self.path_for(cid)?;
-        Ok(path.exists())
+    /// Replace the shard set of `cid` with `shards` without ever leaving it with no complete set.
+    ///
+    /// The new set (manifest last) is written and fsynced into a staging directory on each shard
+    /// root, then renamed into place root by root; the previous set is moved aside first and only
+    /// deleted once every root holds the new one. A swap interrupted between the two renames is
+    /// rolled back by `read_manifest`.
+    async fn write_shards(&self, manifest: &ShardManifest, shards: &[Bytes]) -> Result<()> {
+        let cid = manifest.cid.as_str();
+        let roots = self.shard_roots.len().min(shards.len());
+        for root in 0..roots {
+            let staged = self.staged_shard_dir(root, cid)?;
+            Self::remove_dir_if_present(&staged).await?;
+            fs::create_dir_all(&staged).await?;
+        }
+        for (i, shard) in shards.iter().enumerate() {
+            let path = self
+                .staged_shard_dir(i % self.shard_roots.len(), cid)?
+                .join(format!("{i:03}"));
+            Self::write_all_atomic(&path, shard).await?;
+        }
+        // Manifest last: its presence means every shard was written.
+        let json = Self::manifest_json(manifest)?;
+        for root in 0..roots {
+            let staged = self.staged_shard_dir(root, cid)?;
+            Self::write_all_atomic(&staged.join(MANIFEST_FILE), &json).await?;
+        }
+
+        for root in 0..roots {
+            let live = self.shard_dir_at(root, cid)?;
+            let retired = self.retired_shard_dir(root, cid)?;
+            Self::remove_dir_if_present(&retired).await?;
+            match fs::rename(&live, &retired).await {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(StorageError::Io(e)),
+            }
+            fs::rename(self.staged_shard_dir(root, cid)?, &live).await?;
+            Self::sync_dir(&self.shard_roots[root]).await?;
+        }
+
+        // Only now drop the previous set, and any left on roots the new layout no longer uses.
+        for root in 0..self.shard_roots.len() {
+            Self::remove_dir_if_present(&self.retired_shard_dir(root, cid)?).await?;
+            if root >= roots {
+                Self::remove_dir_if_present(&self.shard_dir_at(root, cid)?).await?;
+            }
+        }
+        Ok(())
     }
 
-    async fn head(&self, cid: &str) -> Result<HeadMeta, StorageError> {
-        let path = self.path_for(cid)?;
-        let meta = fs::metadata(&path).await.map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                StorageError::NotFound
-            } else {
-                StorageError::Io(e)
+    /// Put back the shard set a swap moved aside on `root` if the new one never arrived.
+    async fn reinstate_retired_shards(&self, root: usize, cid: &str) -> Result<bool> {
+        let retired = self.retired_shard_dir(root, cid)?;
+        let live = self.shard_dir_at(root, cid)?;
+        if live.exists() || !retired.exists() {
+            return Ok(false);
+        }
+        fs::rename(&retired, &live).await?;
+        tracing::warn!(%cid, root, "restored shard set left aside by an interrupted put");
+        Ok(true)
+    }
+
+    fn manifest_json(manifest: &ShardManifest) -> Result<Vec<u8>> {
+        serde_json::to_vec_pretty(manifest)
+            .map_err(|e| StorageError::Erasure(format!("manifest encode failed: {e}")))
+    }
+
+    /// Copy `manifest` into every shard root that holds one of its shards.
+    async fn write_manifests(&self, manifest: &ShardManifest) -> Result<()> {
+        let json = Self::manifest_json(manifest)?;
+        for root in 0..self.shard_roots.len().min(manifest.shards.len()) {
+            let dir = self.shard_dir_at(root, &manifest.cid)?;
+            fs::create_dir_all(&dir).await?;
+            Self::write_all_atomic(&dir.join(MANIFEST_FILE), &json).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether every shard file named by `manifest` exists (digests are checked on read).
+    fn shards_present(&self, manifest: &ShardManifest) -> Result<bool> {
+        for i in 0..manifest.shards.len() {
+            if !self.shard_path(&manifest.cid, i)?.exists() {
+                return Ok(false);
             }
-        })?;
-        Ok(HeadMeta {
-            cid: cid.to_string(),
-            len: meta.len(),
-        })
+        }
+        Ok(true)
+    }
+
+    /// Read each shard named by `manifest`; unreadable shards are `None`.
+    async fn read_shard_files(&self, manifest: &ShardManifest) -> Result<Vec<Option<Vec<u8>>>> {
+        let mut shards = Vec::with_capacity(manifest.shards.len());
+        for i in 0..manifest.shards.len() {
+            shards.push(fs::read(self.shard_path(&manifest.cid, i)?).await.ok());
+        }
+        Ok(shards)
     }
 
-    async fn get_full(&self, cid: &str) -> Result<Bytes, StorageError> {
+    /// Drop every shard directory of `cid`, on all shard roots.
+    async fn remove_shards(&self, cid: &str) -> Result<()> {
+        for root in 0..self.shard_roots.len() {
+            Self::remove_dir_if_present(&self.shard_dir_at(root, cid)?).await?;
+        }
+        Ok(())
+    }
+
+    /// Encrypt `data` into a sealed blob with the configured envelope key.
+    fn seal_blob(&self, cid: &str, data: &[u8]) -> Result<Vec<u8>> {
+        #[cfg(feature = "pq-envelope")]
+        {
+            self.envelope()?.seal(cid, data)
+        }
+        #[cfg(not(feature = "pq-envelope"))]
+        {
+            let _ = (cid, data);
+            Err(StorageError::Envelope(
+                "sealing requires the pq-envelope feature".into(),
+            ))
+        }
+    }
+
+    #[cfg(feature = "pq-envelope")]
+    fn envelope(&self) -> Result<&Envelope> {
+        self.envelope
+            .as_ref()
+            .ok_or_else(|| StorageError::Envelope("no envelope key configured".into()))
+    }
+
+    /// Open a whole sealed blob and check the plaintext against `cid`.
+    #[cfg(feature = "pq-envelope")]
+    fn open_sealed(&self, cid: &str, blob: &[u8]) -> Result<Bytes> {
+        let plain = self
+            .envelope()?
+            .open(cid, blob)?
+            .ok_or(StorageError::IntegrityFailed)?;
+        if blake3::hash(&plain).to_hex().as_str() != &cid[3..] {
+            return Err(StorageError::IntegrityFailed);
+        }
+        Ok(Bytes::from(plain))
+    }
+
+    #[cfg(not(feature = "pq-envelope"))]
+    fn open_sealed(&self, _cid: &str, _blob: &[u8]) -> Result<Bytes> {
+        Err(StorageError::Envelope(
+            "sealed object but built without pq-envelope".into(),
+        ))
+    }
+
+    /// Open the blob for `cid` and work out its on-disk layout; `None` if missing.
+    async fn open_blob(&self, cid: &str) -> Result<Option<(fs::File, Layout)>> {
         let path = self.path_for(cid)?;
-        let mut f = fs::File::open(&path).await.map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                StorageError::NotFound
-            } else {
-                StorageError::Io(e)
+        let mut f = match fs::File::open(&path).await {
+            Ok(f) => f,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(StorageError::Io(e)),
+        };
+        #[cfg(feature = "pq-envelope")]
+        {
+            if let Some(header) = pq_envelope::read_header(cid, &mut f).await? {
+                return Ok(Some((f, Layout::Sealed(header))));
+            }
+            f.seek(std::io::SeekFrom::Start(0)).await?;
+        }
+        let layout = match compression::read_header(cid, &mut f).await? {
+            Some(header) => Layout::Compressed(header),
+            None => Layout::Raw(f.metadata().await?.len()),
+        };
+        Ok(Some((f, layout)))
+    }
+
+    /// Whether the blob file for `cid` is a sealed envelope.
+    async fn blob_sealed(&self, cid: &str) -> Result<bool> {
+        #[cfg(feature = "pq-envelope")]
+        {
+            let Ok(mut f) = fs::File::open(self.path_for(cid)?).await else {
+                return Ok(false);
+            };
+            Ok(pq_envelope::read_header(cid, &mut f).await?.is_some())
+        }
+        #[cfg(not(feature = "pq-envelope"))]
+        {
+            let _ = cid;
+            Ok(false)
+        }
+    }
+
+    /// Load the shard manifest for `cid`, if the object was erasure-coded.
+    ///
+    /// Every shard root holding a shard has a copy; the first intact one wins.
+    pub async fn read_manifest(&self, cid: &str) -> Result<Option<ShardManifest>> {
+        let mut last_err = None;
+        for root in 0..self.shard_roots.len() {
+            let path = self.shard_dir_at(root, cid)?.join(MANIFEST_FILE);
+            let read = match fs::read(&path).await {
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::NotFound
+                        && self.reinstate_retired_shards(root, cid).await? =>
+                {
+                    fs::read(&path).await
+                }
+                read => read,
+            };
+            match read {
+                Ok(raw) => match serde_json::from_slice::<ShardManifest>(&raw) {
+                    Ok(manifest) if manifest.cid == cid => return Ok(Some(manifest)),
+                    Ok(_) => last_err = Some(StorageError::IntegrityFailed),
+                    Err(e) => {
+                        last_err = Some(StorageError::Erasure(format!(
+                            "manifest decode failed: {e}"
+                        )))
+                    }
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => last_err = Some(StorageError::Io(e)),
+            }
+        }
+        last_err.map_or(Ok(None), Err)
+    }
+
+    /// Check each shard of `cid` against its manifest digest.
+    ///
+    /// Returns `(bytes read, damaged shard indices)`, or `None` if the object has no shards.
+    pub async fn shard_damage(&self, cid: &str) -> Result<Option<(u64, Vec<usize>)>> {
+        let Some(manifest) = self.read_manifest(cid).await? else {
+            return Ok(None);
+        };
+        let mut read = 0u64;
+        let mut damaged = Vec::new();
+        for (i, want) in manifest.shards.iter().enumerate() {
+            match fs::read(self.shard_path(cid, i)?).await {
+                Ok(shard) => {
+                    read += shard.len() as u64;
+                    if shard_id(&shard) != *want {
+                        damaged.push(i);
+                    }
+                }
+                Err(_) => damaged.push(i),
+            }
+        }
+        Ok(Some((read, damaged)))
+    }
+
+    /// Rebuild `cid` from its surviving shards and rewrite any missing or corrupt shard.
+    pub async fn rebuild_from_shards(&self, cid: &str) -> Result<Bytes> {
+        let manifest = self
+            .read_manifest(cid)
+            .await?
+            .ok_or(StorageError::NotFound)?;
+
+        let mut shards = Vec::with_capacity(manifest.shards.len());
+        let mut damaged = Vec::new();
+        for (i, want) in manifest.shards.iter().enumerate() {
+            let shard = fs::read(self.shard_path(cid, i)?).await.ok();
+            let intact = matches!(&shard, Some(s) if shard_id(s) == *want);
+            if !intact {
+                damaged.push(i);
             }
-        })?;
+            shards.push(shard);
+        }
+
+        let coder = ErasureCoder::new(manifest.config())?;
+        let stored = coder.reconstruct(&manifest, shards)?;
 
-        let mut buf = Vec::new();
-        f.read_to_end(&mut buf).await.map_err(StorageError::Io)?;
-        Ok(Bytes::from(buf))
+        // Heal before decoding: the surviving shards matched their digests, and sealed
+        // shards must be healable even on a node without the envelope key.
+        if !damaged.is_empty() {
+            // Encoding is deterministic, so re-encoding yields the shards the manifest names.
+            let (_, fresh) = coder.encode(cid, &stored)?;
+            for &i in &damaged {
+                let path = self.shard_path(cid, i)?;
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).await?;
+                }
+                Self::write_all_atomic(&path, &fresh[i]).await?;
+            }
+            // A lost shard root also took its manifest copy.
+            self.write_manifests(&manifest).await?;
+            tracing::warn!(%cid, healed = damaged.len(), "rebuilt object from erasure shards");
+        }
+
+        if manifest.sealed {
+            return self.open_sealed(cid, &stored);
+        }
+        self.decode_stored(cid, stored.to_vec())?
+            .ok_or(StorageError::IntegrityFailed)
     }
 
-    async fn get_range(
+    /// Re-store `cid` from verified bytes (repair), replacing whatever shards are left.
+    ///
+    /// The content type recorded at put is kept unless `opts` names one.
+    pub async fn restore(&self, cid: &str, data: Bytes, opts: PutOptions<'_>) -> Result<()> {
+        let recorded = self.content_type(cid).await.ok().flatten();
+        let opts = PutOptions {
+            content_type: opts.content_type.or(recorded.as_deref()),
+            ..opts
+        };
+        self.store_object(cid, data, opts, true).await
+    }
+
+    /// Content type recorded when `cid` was stored as shards.
+    pub async fn content_type(&self, cid: &str) -> Result<Option<String>> {
+        Ok(self.read_manifest(cid).await?.and_then(|m| m.content_type))
+    }
+
+    /// List every object with a blob under the root or shards under any shard root.
+    pub async fn list_cids(&self) -> Result<Vec<String>> {
+        let mut out = Vec::new();
+        let mut roots = vec![&self.root];
+        roots.extend(self.shard_roots.iter().filter(|r| **r != self.root));
+        for root in roots {
+            let mut dir = match fs::read_dir(root).await {
+                Ok(dir) => dir,
+                // A lost shard root; its objects still list from the others.
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(StorageError::Io(e)),
+            };
+            while let Some(entry) = dir.next_entry().await? {
+                let name = entry.file_name();
+                let Some(name) = name.to_str() else { continue };
+                // A set moved aside by an interrupted put is reinstated on first read.
+                let cid = name
+                    .strip_suffix(".shards")
+                    .or_else(|| name.strip_suffix(".shards.old"))
+                    .unwrap_or(name);
+                if Self::is_valid_b3_cid(cid) {
+                    out.push(cid.to_string());
+                }
+            }
+        }
+        out.sort();
+        out.dedup();
+        Ok(out)
+    }
+
+    /// Move the blob for `cid` aside into the quarantine dir; returns the new path.
+    pub async fn quarantine(&self, cid: &str) -> Result<PathBuf> {
+        let path = self.path_for(cid)?;
+        let qdir = self.root.join(QUARANTINE_DIR);
+        fs::create_dir_all(&qdir).await?;
+        let stamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let dest = qdir.join(format!("{cid}.{stamp}"));
+        fs::rename(&path, &dest)
+            .await
+            .map_err(Self::not_found_or_io)?;
+        Ok(dest)
+    }
+
+    /// Read the blob, open or inflate it, and verify it against its address;
+    /// `None` if missing or corrupt.
+    ///
+    /// Sealed blobs need the envelope key; without one this is an `Envelope` error.
+    pub async fn read_verified_blob(&self, cid: &str) -> Result<Option<Bytes>> {
+        let path = self.path_for(cid)?;
+        match fs::read(&path).await {
+            Ok(buf) => self.decode_stored(cid, buf),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(StorageError::Io(e)),
+        }
+    }
+
+    /// Open or inflate a stored blob (from disk or rebuilt from shards) and check it
+    /// against `cid`; `None` if it does not verify.
+    /// Write `cid` in its stored form; `replace` rewrites it even when a complete copy is present.
+    ///
+    /// The new copy is durable and in place before anything it replaces is deleted.
+    async fn store_object(
         &self,
         cid: &str,
-        start: u64,
-        end_inclusive: u64,
-    ) -> Result<(Bytes, u64), StorageError> {
+        data: Bytes,
+        opts: PutOptions<'_>,
+        replace: bool,
+    ) -> Result<()> {
         let path = self.path_for(cid)?;
-        let meta = fs::metadata(&path).await.map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                StorageError::NotFound
+        let blob_exists = path.exists();
+        let manifest = self.read_manifest(cid).await?;
+        // A replacement is built from the verified bytes alone, never from what is left on disk.
+        let sealed = !replace
+            && ((blob_exists && self.blob_sealed(cid).await?)
+                || manifest.as_ref().is_some_and(|m| m.sealed));
+        let sealing = opts.seal && !sealed;
+
+        let present = match (&self.erasure, &manifest) {
+            (Some(_), Some(m)) => self.shards_present(m)?,
+            (Some(_), None) => false,
+            (None, _) => blob_exists,
+        };
+        if present && !sealing && !replace {
+            return Ok(());
+        }
+
+        // A re-put without a type keeps the one recorded at first put.
+        let content_type = opts
+            .content_type
+            .map(str::to_string)
+            .or_else(|| manifest.as_ref().and_then(|m| m.content_type.clone()));
+
+        // Stored form: a fresh envelope, the existing one (sealing is sticky), or the
+        // plaintext possibly compressed. Sealing an existing plaintext object replaces it.
+        let (stored, compressed) = if sealing {
+            (self.seal_blob(cid, &data)?, false)
+        } else if sealed {
+            let blob = if blob_exists {
+                fs::read(&path).await?
             } else {
-                StorageError::Io(e)
+                let m = manifest.as_ref().ok_or(StorageError::NotFound)?;
+                let shards = self.read_shard_files(m).await?;
+                ErasureCoder::new(m.config())?
+                    .reconstruct(m, shards)?
+                    .to_vec()
+            };
+            (blob, false)
+        } else {
+            match self.encode_blob(cid, &data, content_type.as_deref())? {
+                Some(packed) => (packed, true),
+                None => (data.to_vec(), false),
+            }
+        };
+
+        match &self.erasure {
+            Some(coder) => {
+                let (mut m, shards) = coder.encode(cid, &stored)?;
+                m.sealed = sealed || sealing;
+                m.compressed = compressed;
+                m.object_len = Some(data.len() as u64);
+                m.content_type = content_type;
+                self.write_shards(&m, &shards).await?;
+                if blob_exists {
+                    // Shards replace the blob.
+                    fs::remove_file(&path).await?;
+                }
+            }
+            None => {
+                Self::write_all_atomic(&path, &stored).await?;
+                // The blob replaces shards left from an earlier erasure-coded put.
+                self.remove_shards(cid).await?;
             }
-        })?;
-        let total = meta.len();
-        if start > end_inclusive || end_inclusive >= total {
-            return Err(StorageError::BadRequest("invalid range".into()));
         }
+        Ok(())
+    }
 
-        let mut f = fs::File::open(&path).await.map_err(StorageError::Io)?;
+    fn decode_stored(&self, cid: &str, buf: Vec<u8>) -> Result<Option<Bytes>> {
+        #[cfg(feature = "pq-envelope")]
+        if pq_envelope::EnvelopeHeader::probe(cid, &buf).is_some() {
+            return match self.open_sealed(cid, &buf) {
+                Ok(plain) => Ok(Some(plain)),
+                Err(StorageError::IntegrityFailed) => Ok(None),
+                Err(e) => Err(e),
+            };
+        }
+        let buf = match compression::inflate(cid, &buf) {
+            Ok(Some(plain)) => plain,
+            Ok(None) => buf,
+            Err(StorageError::IntegrityFailed) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if blake3::hash(&buf).to_hex().as_str() == &cid[3..] {
+            Ok(Some(Bytes::from(buf)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for FsStorage {
+    async fn put(&self, cid: &str, data: Bytes) -> Result<()> {
+        self.put_with(cid, data, PutOptions::default()).await
+    }
+
+    async fn put_with(&self, cid: &str, data: Bytes, opts: PutOptions<'_>) -> Result<()> {
+        self.store_object(cid, data, opts, false).await
+    }
+
+    async fn sealed(&self, cid: &str) -> Result<bool> {
+        if self.blob_sealed(cid).await? {
+            return Ok(true);
+        }
+        Ok(self.read_manifest(cid).await?.is_some_and(|m| m.sealed))
+    }
+
+    async fn exists(&self, cid: &str) -> Result<bool> {
+        if self.path_for(cid)?.exists() {
+            return Ok(true);
+        }
+        for root in 0..self.shard_roots.len() {
+            if self.shard_dir_at(root, cid)?.join(MANIFEST_FILE).exists() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        let len = match self.open_blob(cid).await? {
+            Some((_, layout)) => layout.len(),
+            None => {
+                let manifest = self
+                    .read_manifest(cid)
+                    .await?
+                    .ok_or(StorageError::NotFound)?;
+                match manifest.object_len {
+                    Some(len) => len,
+                    // Older sealed manifest: its length is the envelope's; rebuild to learn the plaintext length.
+                    None if manifest.sealed => self.rebuild_from_shards(cid).await?.len() as u64,
+                    None => manifest.len,
+                }
+            }
+        };
+        Ok(HeadMeta {
+            len,
+            etag: Self::etag_for(cid),
+        })
+    }
+
+    async fn get_full(&self, cid: &str) -> Result<Bytes> {
+        if let Some(bytes) = self.read_verified_blob(cid).await? {
+            return Ok(bytes);
+        }
+        let blob_exists = self.path_for(cid)?.exists();
+        match self.read_manifest(cid).await? {
+            Some(_) => {
+                let bytes = self.rebuild_from_shards(cid).await?;
+                if blob_exists {
+                    // A corrupt blob next to intact shards (older layout): move it aside.
+                    let dest = self.quarantine(cid).await?;
+                    tracing::warn!(%cid, dest = %dest.display(), "quarantined corrupt blob");
+                }
+                Ok(bytes)
+            }
+            None if blob_exists => Err(StorageError::IntegrityFailed),
+            None => Err(StorageError::NotFound),
+        }
+    }
+
+    async fn get_range(&self, cid: &str, start: u64, end_inclusive: u64) -> Result<(Bytes, u64)> {
+        let Some((mut f, layout)) = self.open_blob(cid).await? else {
+            // Shards only (or blob lost): rebuild, then serve the slice from memory.
+            let full = self.get_full(cid).await?;
+            let total = full.len() as u64;
+            if start > end_inclusive || end_inclusive >= total {
+                return Err(StorageError::RangeNotSatisfiable);
+            }
+            return Ok((full.slice(start as usize..=end_inclusive as usize), total));
+        };
+
+        let total = layout.len();
+        if start > end_inclusive || end_inclusive >= total {
+            return Err(StorageError::RangeNotSatisfiable);
+        }
         let span = (end_inclusive - start + 1) as usize;
-        let mut buf = vec![0u8; span];
 
-        f.seek(std::io::SeekFrom::Start(start))
-            .await
-            .map_err(StorageError::Io)?;
-        f.read_exact(&mut buf).await.map_err(StorageError::Io)?;
+        match layout {
+            Layout::Raw(_) => {
+                let mut buf = vec![0u8; span];
+                f.seek(std::io::SeekFrom::Start(start)).await?;
+                f.read_exact(&mut buf).await?;
+                Ok((Bytes::from(buf), total))
+            }
+            Layout::Compressed(header) => {
+                // Inflate only the frames that overlap the range.
+                let (first, _, offset, packed_len) = header.frame_span(start, end_inclusive);
+                let mut packed = vec![0u8; packed_len as usize];
+                f.seek(std::io::SeekFrom::Start(offset)).await?;
+                f.read_exact(&mut packed).await?;
+
+                let mut plain = Vec::new();
+                header.inflate_frames(first, &packed, &mut plain)?;
+                let skip = (start - first as u64 * u64::from(header.chunk_size)) as usize;
+                Ok((Bytes::from(plain).slice(skip..skip + span), total))
+            }
+            #[cfg(feature = "pq-envelope")]
+            Layout::Sealed(header) => {
+                // Decrypt only the chunks that overlap the range.
+                let (first, offset, sealed_len) = header.chunk_span(start, end_inclusive);
+                let mut sealed = vec![0u8; sealed_len as usize];
+                f.seek(std::io::SeekFrom::Start(offset)).await?;
+                f.read_exact(&mut sealed).await?;
+
+                let mut plain = Vec::new();
+                self.envelope()?
+                    .open_chunks(&header, first, &sealed, &mut plain)?;
+                let skip = (start - first * u64::from(header.chunk_size)) as usize;
+                Ok((Bytes::from(plain).slice(skip..skip + span), total))
+            }
+        }
+    }
 
-        Ok((Bytes::from(buf), total))
+    async fn list(&self) -> Result<Vec<String>> {
+        self.list_cids().await
     }
 }
@@ -1 +1,368 @@
-// Storage module: hedged (scaffold)
+//! RO:WHAT — Hedged reads: a `Storage` wrapper that fires a second read at a replica once the primary is slower than its pXX.
+//! RO:WHY — Cold-read p99 is dominated by a single slow disk; racing a replica cuts the tail.
+//! RO:INVARIANTS — at most two reads in flight per call; replica bytes are only used after they hash to the b3 cid;
+//!                 an unsatisfiable range is answered by the primary, never by fanning out to replicas;
+//!                 an object the primary lacks counts as sealed unless every replica holding it says otherwise.
+//! RO:INTERACTS — any primary `Storage` (usually FsStorage), replica stores (replication::PeerStorage), http::routes::get_object via AppState (main).
+//! RO:METRICS — storage_hedged_read_total{outcome=primary|hedge|fallback|failed}.
+//! RO:CONFIG — HedgeConfig { percentile, min/default delay, window, max_range_hedge_bytes } (docs/CONFIG.MD placement.hedge_*).
+//! RO:SECURITY — replica content is untrusted; a replica range read fetches the whole object so it can be verified.
+//! RO:TEST — tests/storage_hedged.rs.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use parking_lot::Mutex;
+
+use super::{DynStorage, HeadMeta, PutOptions, Result, Storage};
+use crate::errors::StorageError;
+
+type ReadFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Hedge timing knobs.
+#[derive(Debug, Clone)]
+pub struct HedgeConfig {
+    /// Primary latency percentile (0.0–1.0) after which the hedge fires.
+    pub percentile: f64,
+    /// Floor for the hedge delay.
+    pub min_delay: Duration,
+    /// Delay used until `min_samples` primary reads have been observed.
+    pub default_delay: Duration,
+    /// Samples needed before the percentile is trusted.
+    pub min_samples: usize,
+    /// Number of recent primary latencies kept.
+    pub window: usize,
+    /// Range reads on objects larger than this are not hedged (the replica must send the whole object);
+    /// once the primary has failed, replicas serve the range whatever the size.
+    pub max_range_hedge_bytes: u64,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            percentile: 0.95,
+            min_delay: Duration::from_millis(5),
+            // docs/CONFIG.MD `placement.hedge_delay` default.
+            default_delay: Duration::from_millis(30),
+            min_samples: 32,
+            window: 512,
+            max_range_hedge_bytes: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Sliding window of primary read latencies.
+#[derive(Debug)]
+pub struct LatencyTracker {
+    window: usize,
+    samples: Mutex<VecDeque<Duration>>,
+}
+
+impl LatencyTracker {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn record(&self, d: Duration) {
+        let mut samples = self.samples.lock();
+        if samples.len() == self.window {
+            samples.pop_front();
+        }
+        samples.push_back(d);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.samples.lock().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Nearest-rank percentile of the recorded samples.
+    #[must_use]
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut sorted: Vec<Duration> = self.samples.lock().iter().copied().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+        let rank = ((p.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize).max(1);
+        Some(sorted[rank - 1])
+    }
+}
+
+/// `Storage` that hedges reads from `primary` to one of `replicas`.
+pub struct HedgedStorage {
+    primary: DynStorage,
+    replicas: Vec<DynStorage>,
+    cfg: HedgeConfig,
+    latency: LatencyTracker,
+    next_replica: AtomicUsize,
+}
+
+impl HedgedStorage {
+    pub fn new(primary: DynStorage, replicas: Vec<DynStorage>, cfg: HedgeConfig) -> Self {
+        Self {
+            primary,
+            replicas,
+            latency: LatencyTracker::new(cfg.window),
+            cfg,
+            next_replica: AtomicUsize::new(0),
+        }
+    }
+
+    /// How long the primary gets before the hedge fires.
+    #[must_use]
+    pub fn hedge_delay(&self) -> Duration {
+        if self.latency.len() < self.cfg.min_samples {
+            return self.cfg.default_delay;
+        }
+        self.latency
+            .percentile(self.cfg.percentile)
+            .unwrap_or(self.cfg.default_delay)
+            .max(self.cfg.min_delay)
+    }
+
+    #[must_use]
+    pub fn latency(&self) -> &LatencyTracker {
+        &self.latency
+    }
+
+    /// Replicas in round-robin order, starting at the next one due.
+    fn replica_order(&self) -> Vec<DynStorage> {
+        if self.replicas.is_empty() {
+            return Vec::new();
+        }
+        let start = self.next_replica.fetch_add(1, Ordering::Relaxed) % self.replicas.len();
+        self.replicas[start..]
+            .iter()
+            .chain(&self.replicas[..start])
+            .cloned()
+            .collect()
+    }
+
+    /// Verified full read from a replica.
+    async fn replica_full(replica: DynStorage, cid: String) -> Result<Bytes> {
+        let bytes = replica.get_full(&cid).await?;
+        if !content_matches(&cid, &bytes) {
+            tracing::warn!(%cid, "hedged read: replica bytes failed the cid check");
+            return Err(StorageError::IntegrityFailed);
+        }
+        Ok(bytes)
+    }
+
+    /// Race `primary` against `hedge(replica, true)` fired after the hedge delay; fall back
+    /// to the remaining replicas in turn (`hedge(replica, false)`) if both fail.
+    async fn race<'a, T: Send + 'a>(
+        &'a self,
+        primary: ReadFuture<'a, T>,
+        hedge: impl Fn(DynStorage, bool) -> ReadFuture<'a, T>,
+    ) -> Result<T> {
+        let mut replicas = self.replica_order().into_iter();
+        let started = Instant::now();
+        let mut primary = primary;
+
+        let first_replica = match replicas.next() {
+            None => return self.timed_primary(primary, started).await,
+            Some(r) => r,
+        };
+
+        tokio::select! {
+            res = &mut primary => {
+                self.latency.record(started.elapsed());
+                match res {
+                    Ok(v) => {
+                        observe("primary");
+                        return Ok(v);
+                    }
+                    Err(e) if is_request_error(&e) => return Err(e),
+                    Err(e) => {
+                        tracing::debug!(error = %e, "hedged read: primary failed before hedge");
+                        return self.fallback(std::iter::once(first_replica).chain(replicas), &hedge).await;
+                    }
+                }
+            }
+            _ = tokio::time::sleep(self.hedge_delay()) => {}
+        }
+
+        let mut backup = hedge(first_replica, true);
+        let mut primary_done = false;
+        let mut backup_done = false;
+        while !(primary_done && backup_done) {
+            tokio::select! {
+                res = &mut primary, if !primary_done => {
+                    primary_done = true;
+                    self.latency.record(started.elapsed());
+                    match res {
+                        Ok(v) => {
+                            observe("primary");
+                            return Ok(v);
+                        }
+                        // A replica cannot do better with a bad request; stop the hedge.
+                        Err(e) if is_request_error(&e) => return Err(e),
+                        Err(_) => {}
+                    }
+                }
+                res = &mut backup, if !backup_done => {
+                    backup_done = true;
+                    if let Ok(v) = res {
+                        if !primary_done {
+                            // Primary is at least this slow; keep the tail visible to the tracker.
+                            self.latency.record(started.elapsed());
+                        }
+                        observe("hedge");
+                        return Ok(v);
+                    }
+                }
+            }
+        }
+        self.fallback(replicas, &hedge).await
+    }
+
+    async fn timed_primary<T>(&self, primary: ReadFuture<'_, T>, started: Instant) -> Result<T> {
+        let res = primary.await;
+        self.latency.record(started.elapsed());
+        res
+    }
+
+    async fn fallback<'a, T: Send + 'a>(
+        &'a self,
+        replicas: impl Iterator<Item = DynStorage>,
+        hedge: &impl Fn(DynStorage, bool) -> ReadFuture<'a, T>,
+    ) -> Result<T> {
+        let mut last = StorageError::NotFound;
+        for replica in replicas {
+            match hedge(replica, false).await {
+                Ok(v) => {
+                    observe("fallback");
+                    return Ok(v);
+                }
+                Err(e) => last = e,
+            }
+        }
+        observe("failed");
+        Err(last)
+    }
+}
+
+#[async_trait::async_trait]
+impl Storage for HedgedStorage {
+    async fn put(&self, cid: &str, data: Bytes) -> Result<()> {
+        self.primary.put(cid, data).await
+    }
+
+    async fn put_with(&self, cid: &str, data: Bytes, opts: PutOptions<'_>) -> Result<()> {
+        self.primary.put_with(cid, data, opts).await
+    }
+
+    /// Reads may be served by a replica, so a local miss must not open the auth gate.
+    async fn sealed(&self, cid: &str) -> Result<bool> {
+        if self.primary.exists(cid).await? {
+            return self.primary.sealed(cid).await;
+        }
+        let mut plain = false;
+        for replica in self.replica_order() {
+            match replica.exists(cid).await {
+                Ok(false) => continue,
+                Ok(true) if matches!(replica.sealed(cid).await, Ok(false)) => plain = true,
+                // Sealed, or a replica that cannot say: fail closed.
+                _ => return Ok(true),
+            }
+        }
+        Ok(!plain)
+    }
+
+    async fn exists(&self, cid: &str) -> Result<bool> {
+        self.primary.exists(cid).await
+    }
+
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        match self.primary.head(cid).await {
+            // Replica metadata is unverifiable on its own; derive it from verified bytes.
+            Err(StorageError::NotFound) => {
+                for replica in self.replica_order() {
+                    if let Ok(bytes) = Self::replica_full(replica, cid.to_string()).await {
+                        return Ok(HeadMeta {
+                            len: bytes.len() as u64,
+                            etag: format!("\"{}\"", &cid[3..]),
+                        });
+                    }
+                }
+                Err(StorageError::NotFound)
+            }
+            other => other,
+        }
+    }
+
+    async fn get_full(&self, cid: &str) -> Result<Bytes> {
+        let primary = Box::pin(async move {
+            let bytes = self.primary.get_full(cid).await?;
+            if !content_matches(cid, &bytes) {
+                return Err(StorageError::IntegrityFailed);
+            }
+            Ok(bytes)
+        });
+        self.race(primary, |replica, _| {
+            Box::pin(Self::replica_full(replica, cid.to_string()))
+        })
+        .await
+    }
+
+    async fn get_range(&self, cid: &str, start: u64, end_inclusive: u64) -> Result<(Bytes, u64)> {
+        if start > end_inclusive {
+            return Err(StorageError::RangeNotSatisfiable);
+        }
+        let primary = Box::pin(self.primary.get_range(cid, start, end_inclusive));
+        let max_hedge = self.cfg.max_range_hedge_bytes;
+        self.race(primary, |replica, hedging| {
+            let cid = cid.to_string();
+            Box::pin(async move {
+                // Only race a whole-object replica read while it stays cheap.
+                if hedging && replica.head(&cid).await?.len > max_hedge {
+                    return Err(StorageError::Replication(
+                        "object too large to hedge a range".into(),
+                    ));
+                }
+                let full = Self::replica_full(replica, cid).await?;
+                let total = full.len() as u64;
+                if start > end_inclusive || end_inclusive >= total {
+                    return Err(StorageError::RangeNotSatisfiable);
+                }
+                Ok((full.slice(start as usize..=end_inclusive as usize), total))
+            })
+        })
+        .await
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        self.primary.list().await
+    }
+}
+
+/// Errors about the request itself; every replica would answer the same.
+fn is_request_error(e: &StorageError) -> bool {
+    matches!(
+        e,
+        StorageError::RangeNotSatisfiable | StorageError::BadAddress
+    )
+}
+
+fn content_matches(cid: &str, bytes: &[u8]) -> bool {
+    cid.strip_prefix("b3:")
+        .is_some_and(|hex| blake3::hash(bytes).to_hex().as_str() == hex)
+}
+
+fn observe(_outcome: &'static str) {
+    #[cfg(feature = "metrics")]
+    crate::metrics::observe_hedged_read(_outcome);
+}
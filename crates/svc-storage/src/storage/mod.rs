@@ -9,6 +9,51 @@ use parking_lot::RwLock;
 
 use crate::errors::StorageError;
 
+pub mod compression;
+pub mod erasure;
+pub mod fs;
+pub mod hedged;
+pub mod placement;
+#[cfg(feature = "pq-envelope")]
+pub mod pq_envelope;
+pub mod repair;
+pub mod replication;
+
+pub use fs::FsStorage;
+
+/// Request header asking for an object to be sealed at rest (`x-ron-seal: 1`).
+pub const SEAL_HEADER: &str = "x-ron-seal";
+
+/// Response header marking a sealed object on HEAD.
+pub const SEALED_HEADER: &str = "x-ron-sealed";
+
+/// Per-write hints; none of them change the object's address.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PutOptions<'a> {
+    /// Client Content-Type; steers at-rest compression.
+    pub content_type: Option<&'a str>,
+    /// Store the object in a sealed envelope (requires the `pq-envelope` feature and a key).
+    pub seal: bool,
+}
+
+impl<'a> PutOptions<'a> {
+    /// Options from a request's headers (`Content-Type`, `x-ron-seal`).
+    #[must_use]
+    pub fn from_headers(headers: &'a http::HeaderMap) -> Self {
+        let seal = headers
+            .get(SEAL_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().to_ascii_lowercase())
+            .is_some_and(|v| matches!(v.as_str(), "1" | "true" | "yes" | "pq-hybrid"));
+        Self {
+            content_type: headers
+                .get(http::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok()),
+            seal,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct HeadMeta {
     pub len: u64,
@@ -21,6 +66,21 @@ pub type Result<T, E = StorageError> = std::result::Result<T, E>;
 pub trait Storage: Send + Sync + 'static {
     async fn put(&self, cid: &str, data: Bytes) -> Result<()>;
 
+    /// Like `put`, with per-write hints. Backends that cannot seal refuse `seal` rather than store plaintext.
+    async fn put_with(&self, cid: &str, data: Bytes, opts: PutOptions<'_>) -> Result<()> {
+        if opts.seal {
+            return Err(StorageError::Envelope(
+                "sealing not supported by this backend".into(),
+            ));
+        }
+        self.put(cid, data).await
+    }
+
+    /// Whether `cid` is stored in a sealed envelope (reads then require authorization).
+    async fn sealed(&self, _cid: &str) -> Result<bool> {
+        Ok(false)
+    }
+
     #[allow(dead_code)]
     async fn exists(&self, cid: &str) -> Result<bool>;
 
@@ -31,6 +91,9 @@ pub trait Storage: Send + Sync + 'static {
 
     /// Returns (bytes, total_len). Caller provides inclusive range.
     async fn get_range(&self, cid: &str, start: u64, end_inclusive: u64) -> Result<(Bytes, u64)>;
+
+    /// Every cid held locally, sorted (replication inventory).
+    async fn list(&self) -> Result<Vec<String>>;
 }
 
 /// A simple in-memory storage for smoke tests and local development.
@@ -96,6 +159,12 @@ impl Storage for MemoryStorage {
 
         Ok((v.slice(s..=e), total_len))
     }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        let mut cids: Vec<String> = self.inner.read().keys().cloned().collect();
+        cids.sort();
+        Ok(cids)
+    }
 }
 
 // Convenience so other modules can hold Arc<dyn Storage>.
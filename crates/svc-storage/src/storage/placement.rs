@@ -1 +1,173 @@
-// Storage module: placement (scaffold)
+//! RO:WHAT — Placement engine: choose the replication factor's worth of target peers for each object.
+//! RO:WHY — IDB [P-4] policy-first placement; rendezvous (HRW) hashing keeps placements stable as peers come and go.
+//! RO:INTERACTS — policy::residency (filter + preference), storage::replication (push/pull targets).
+//! RO:INVARIANTS — deterministic for a given (cid, peer set, policy); never returns a peer residency forbids.
+//! RO:METRICS — none here; shortfall is reported to callers.
+//! RO:CONFIG — PlacementPolicy { replication_factor, residency } (RON_STORAGE_RF + residency env).
+//! RO:SECURITY — peer ids are opaque labels; scores are BLAKE3 so no peer can bias selection without changing its id.
+//! RO:TEST — tests/storage_replication.rs.
+
+use std::collections::BTreeMap;
+
+use parking_lot::RwLock;
+
+use crate::policy::residency::ResidencyPolicy;
+
+/// Environment variable for the target replication factor.
+pub const ENV_REPLICATION_FACTOR: &str = "RON_STORAGE_RF";
+
+/// Default replication factor (docs/CONFIG.MD `durability.replication_factor`).
+pub const DEFAULT_REPLICATION_FACTOR: usize = 2;
+
+/// A storage node that can hold replicas.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Peer {
+    pub id: String,
+    pub region: String,
+}
+
+impl Peer {
+    pub fn new(id: impl Into<String>, region: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            region: region.into(),
+        }
+    }
+}
+
+/// How many replicas to keep and where they may live.
+#[derive(Debug, Clone)]
+pub struct PlacementPolicy {
+    pub replication_factor: usize,
+    pub residency: ResidencyPolicy,
+}
+
+impl Default for PlacementPolicy {
+    fn default() -> Self {
+        Self {
+            replication_factor: DEFAULT_REPLICATION_FACTOR,
+            residency: ResidencyPolicy::default(),
+        }
+    }
+}
+
+impl PlacementPolicy {
+    /// Read RF and residency from the environment.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let replication_factor = match std::env::var(ENV_REPLICATION_FACTOR) {
+            Ok(value) => value
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|rf| *rf >= 1)
+                .ok_or_else(|| anyhow::anyhow!("invalid {ENV_REPLICATION_FACTOR}: {value}"))?,
+            Err(_) => DEFAULT_REPLICATION_FACTOR,
+        };
+        Ok(Self {
+            replication_factor,
+            residency: ResidencyPolicy::from_env()?,
+        })
+    }
+}
+
+/// Where one object should live.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacementDecision {
+    pub cid: String,
+    /// Target peers, best first.
+    pub targets: Vec<Peer>,
+    /// Replicas missing because too few peers are eligible.
+    pub shortfall: usize,
+}
+
+impl PlacementDecision {
+    /// Whether `peer_id` is one of the targets.
+    #[must_use]
+    pub fn includes(&self, peer_id: &str) -> bool {
+        self.targets.iter().any(|p| p.id == peer_id)
+    }
+}
+
+/// Rendezvous-hashing placement over a mutable peer set.
+pub struct PlacementEngine {
+    policy: PlacementPolicy,
+    peers: RwLock<BTreeMap<String, Peer>>,
+}
+
+impl PlacementEngine {
+    pub fn new(policy: PlacementPolicy) -> Self {
+        Self {
+            policy,
+            peers: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn policy(&self) -> &PlacementPolicy {
+        &self.policy
+    }
+
+    /// Add or update a peer.
+    pub fn upsert_peer(&self, peer: Peer) {
+        self.peers.write().insert(peer.id.clone(), peer);
+    }
+
+    /// Remove a peer; its objects move to the next-highest scorers.
+    pub fn remove_peer(&self, id: &str) {
+        self.peers.write().remove(id);
+    }
+
+    /// Replace the whole peer set.
+    pub fn set_peers(&self, peers: impl IntoIterator<Item = Peer>) {
+        *self.peers.write() = peers.into_iter().map(|p| (p.id.clone(), p)).collect();
+    }
+
+    #[must_use]
+    pub fn peers(&self) -> Vec<Peer> {
+        self.peers.read().values().cloned().collect()
+    }
+
+    /// Pick the targets for `cid`.
+    ///
+    /// Eligible peers are ranked by residency preference, then by their
+    /// rendezvous score; the top `replication_factor` win.
+    #[must_use]
+    pub fn place(&self, cid: &str) -> PlacementDecision {
+        let residency = &self.policy.residency;
+        let mut ranked: Vec<(bool, u64, Peer)> = self
+            .peers
+            .read()
+            .values()
+            .filter(|p| residency.permits(&p.region))
+            .map(|p| {
+                (
+                    residency.prefers(&p.region),
+                    rendezvous_score(cid, &p.id),
+                    p.clone(),
+                )
+            })
+            .collect();
+        ranked.sort_by_key(|(preferred, score, _)| std::cmp::Reverse((*preferred, *score)));
+
+        let rf = self.policy.replication_factor;
+        let targets: Vec<Peer> = ranked.into_iter().take(rf).map(|(_, _, p)| p).collect();
+        PlacementDecision {
+            cid: cid.to_string(),
+            shortfall: rf - targets.len(),
+            targets,
+        }
+    }
+}
+
+/// Highest-random-weight score of `peer_id` for `cid`.
+#[must_use]
+pub fn rendezvous_score(cid: &str, peer_id: &str) -> u64 {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(cid.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(peer_id.as_bytes());
+    let digest = hasher.finalize();
+    let mut head = [0u8; 8];
+    head.copy_from_slice(&digest.as_bytes()[..8]);
+    u64::from_be_bytes(head)
+}
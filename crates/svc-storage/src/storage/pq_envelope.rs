@@ -1 +1,317 @@
-// Storage module: pq_envelope (scaffold)
+//! RO:WHAT — Optional sealed envelope at rest: ChaCha20-Poly1305 chunks under a per-object DEK wrapped by a ron-kms hybrid key.
+//! RO:WHY — Tenants with confidentiality needs get server-side encryption without giving up Range reads.
+//! RO:INTERACTS — ron_kms::traits::Hybrid (X25519+ML-KEM DEK wrap), storage::fs (seal on put, open on get/range), http GET auth.
+//! RO:INVARIANTS — the b3 address stays over the plaintext; every chunk's AAD binds cid, length, chunk size and index.
+//! RO:METRICS — none here; reads surface through the normal storage paths.
+//! RO:CONFIG — feature `pq-envelope`; RON_STORAGE_PQ_ENVELOPE_KEY (hybrid secret key file).
+//! RO:SECURITY — DEKs never touch disk unwrapped; a wrong key or flipped bit is IntegrityFailed, never plaintext.
+//! RO:TEST — tests/storage_pq_envelope.rs.
+//!
+//! On-disk layout of a sealed blob (integers little-endian):
+//!
+//! ```text
+//! magic "RONE" | version u8 | b3 digest [32] | chunk_size u32 | len u64 | wrapped_len u32
+//! | wrapped DEK | (ciphertext ‖ tag) × ceil(len / chunk_size)
+//! ```
+//!
+//! Every chunk but the last holds exactly `chunk_size` plaintext bytes, so chunk
+//! offsets are computed, not stored. Nonces are the chunk index; the DEK is
+//! fresh per object, so a (key, nonce) pair is never reused.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use ron_kms::pq::HybridKeypair;
+use ron_kms::traits::Hybrid;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::errors::StorageError;
+
+/// Leading bytes of every sealed blob.
+pub const MAGIC: &[u8; 4] = b"RONE";
+
+/// Envelope format version written to disk.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Length of the fixed header (before the wrapped DEK).
+pub const HEADER_FIXED_LEN: usize = 4 + 1 + 32 + 4 + 8 + 4;
+
+/// Default plaintext bytes per sealed chunk.
+pub const DEFAULT_CHUNK_SIZE: u32 = 64 * 1024;
+
+/// Largest chunk size accepted from disk.
+pub const MAX_CHUNK_SIZE: u32 = 4 * 1024 * 1024;
+
+/// Largest wrapped DEK accepted from disk (hybrid KEM ciphertext + sealed DEK is ~1.2 KiB).
+const MAX_WRAPPED_LEN: usize = 16 * 1024;
+
+/// Poly1305 tag length.
+const TAG_LEN: u64 = 16;
+
+/// Seals and opens objects under DEKs wrapped by a ron-kms key.
+#[derive(Clone)]
+pub struct Envelope {
+    kek: Arc<dyn Hybrid + Send + Sync>,
+    chunk_size: u32,
+}
+
+impl Envelope {
+    pub fn new(kek: Arc<dyn Hybrid + Send + Sync>) -> Self {
+        Self {
+            kek,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// Load a hybrid X25519+ML-KEM secret key written by `HybridKeypair::secret_bytes`.
+    pub fn from_key_file(path: &Path) -> Result<Self, StorageError> {
+        let raw = zeroize::Zeroizing::new(std::fs::read(path)?);
+        let kp = HybridKeypair::from_secret_bytes(&raw)
+            .map_err(|e| StorageError::Envelope(format!("bad key file: {e}")))?;
+        Ok(Self::new(Arc::new(kp)))
+    }
+
+    /// Plaintext bytes per chunk for newly sealed objects.
+    #[must_use]
+    pub fn with_chunk_size(mut self, chunk_size: u32) -> Self {
+        self.chunk_size = chunk_size.clamp(1, MAX_CHUNK_SIZE);
+        self
+    }
+
+    /// Encrypt `data` under a fresh DEK and wrap the DEK under the KEK.
+    pub fn seal(&self, cid: &str, data: &[u8]) -> Result<Vec<u8>, StorageError> {
+        let digest = cid_digest(cid).ok_or(StorageError::BadAddress)?;
+        let dek: zeroize::Zeroizing<[u8; 32]> =
+            zeroize::Zeroizing::new(ChaCha20Poly1305::generate_key(&mut OsRng).into());
+        let wrapped = self
+            .kek
+            .wrap(dek.as_ref())
+            .map_err(|e| StorageError::Envelope(format!("DEK wrap failed: {e}")))?;
+
+        let header = EnvelopeHeader {
+            digest,
+            len: data.len() as u64,
+            chunk_size: self.chunk_size,
+            wrapped_dek: wrapped,
+        };
+        let chunks = data.len().div_ceil(self.chunk_size as usize);
+        let mut out = Vec::with_capacity(
+            header.encoded_len() as usize + data.len() + chunks * TAG_LEN as usize,
+        );
+        header.encode_into(&mut out);
+
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(dek.as_ref()));
+        for (i, chunk) in data.chunks(self.chunk_size as usize).enumerate() {
+            let aad = header.chunk_aad(i as u64);
+            let ct = cipher
+                .encrypt(
+                    &nonce(i as u64),
+                    Payload {
+                        msg: chunk,
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| StorageError::Envelope("chunk encrypt failed".into()))?;
+            out.extend_from_slice(&ct);
+        }
+        Ok(out)
+    }
+
+    /// Decrypt a whole sealed blob: `Some(plaintext)` if it was sealed, `None` if not.
+    pub fn open(&self, cid: &str, blob: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        let Some(wrapped_len) = EnvelopeHeader::probe(cid, blob) else {
+            return Ok(None);
+        };
+        let body_at = HEADER_FIXED_LEN + wrapped_len;
+        let wrapped = blob
+            .get(HEADER_FIXED_LEN..body_at)
+            .ok_or(StorageError::IntegrityFailed)?;
+        let header = EnvelopeHeader::decode(&blob[..HEADER_FIXED_LEN], wrapped)?;
+
+        let body = &blob[body_at..];
+        if body.len() as u64 != header.sealed_body_len() {
+            return Err(StorageError::IntegrityFailed);
+        }
+        let mut out = Vec::with_capacity(body.len());
+        self.open_chunks(&header, 0, body, &mut out)?;
+        Ok(Some(out))
+    }
+
+    /// Decrypt consecutive chunks starting at `first` from `body`, appending to `out`.
+    pub fn open_chunks(
+        &self,
+        header: &EnvelopeHeader,
+        first: u64,
+        body: &[u8],
+        out: &mut Vec<u8>,
+    ) -> Result<(), StorageError> {
+        let dek = zeroize::Zeroizing::new(
+            self.kek
+                .unwrap_(&header.wrapped_dek)
+                .map_err(|_| StorageError::IntegrityFailed)?,
+        );
+        if dek.len() != 32 {
+            return Err(StorageError::IntegrityFailed);
+        }
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&dek));
+
+        let mut offset = 0usize;
+        let mut index = first;
+        while offset < body.len() {
+            let sealed_len = (header.chunk_plain_len(index) + TAG_LEN) as usize;
+            let ct = body
+                .get(offset..offset + sealed_len)
+                .ok_or(StorageError::IntegrityFailed)?;
+            let aad = header.chunk_aad(index);
+            let pt = cipher
+                .decrypt(&nonce(index), Payload { msg: ct, aad: &aad })
+                .map_err(|_| StorageError::IntegrityFailed)?;
+            out.extend_from_slice(&pt);
+            offset += sealed_len;
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Parsed header of a sealed blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvelopeHeader {
+    digest: [u8; 32],
+    /// Plaintext object length.
+    pub len: u64,
+    pub chunk_size: u32,
+    /// DEK wrapped under the KEK (opaque to storage).
+    pub wrapped_dek: Vec<u8>,
+}
+
+impl EnvelopeHeader {
+    /// Wrapped-DEK length announced by the fixed header, if `fixed` seals `cid`.
+    ///
+    /// The embedded digest must equal the cid, so plaintext or compressed blobs
+    /// are never mistaken for sealed ones.
+    #[must_use]
+    pub fn probe(cid: &str, fixed: &[u8]) -> Option<usize> {
+        if fixed.len() < HEADER_FIXED_LEN
+            || &fixed[..4] != MAGIC
+            || fixed[4] != FORMAT_VERSION
+            || fixed[5..37] != cid_digest(cid)?
+        {
+            return None;
+        }
+        Some(read_u32(fixed, 49) as usize)
+    }
+
+    /// Decode the fixed header plus wrapped DEK.
+    pub fn decode(fixed: &[u8], wrapped: &[u8]) -> Result<Self, StorageError> {
+        let mut digest = [0u8; 32];
+        digest.copy_from_slice(&fixed[5..37]);
+        let chunk_size = read_u32(fixed, 37);
+        let len = u64::from_le_bytes(fixed[41..49].try_into().unwrap_or_default());
+        if chunk_size == 0
+            || chunk_size > MAX_CHUNK_SIZE
+            || wrapped.len() != read_u32(fixed, 49) as usize
+        {
+            return Err(StorageError::IntegrityFailed);
+        }
+        Ok(Self {
+            digest,
+            len,
+            chunk_size,
+            wrapped_dek: wrapped.to_vec(),
+        })
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(MAGIC);
+        out.push(FORMAT_VERSION);
+        out.extend_from_slice(&self.digest);
+        out.extend_from_slice(&self.chunk_size.to_le_bytes());
+        out.extend_from_slice(&self.len.to_le_bytes());
+        out.extend_from_slice(&(self.wrapped_dek.len() as u32).to_le_bytes());
+        out.extend_from_slice(&self.wrapped_dek);
+    }
+
+    /// Total header length on disk (fixed part + wrapped DEK).
+    #[must_use]
+    pub fn encoded_len(&self) -> u64 {
+        (HEADER_FIXED_LEN + self.wrapped_dek.len()) as u64
+    }
+
+    /// Length of all sealed chunks together.
+    #[must_use]
+    pub fn sealed_body_len(&self) -> u64 {
+        self.len + self.len.div_ceil(u64::from(self.chunk_size)) * TAG_LEN
+    }
+
+    /// Chunks covering plaintext `[start, end_inclusive]`: first index, blob offset, sealed length.
+    #[must_use]
+    pub fn chunk_span(&self, start: u64, end_inclusive: u64) -> (u64, u64, u64) {
+        let chunk = u64::from(self.chunk_size);
+        let first = start / chunk;
+        let last = end_inclusive / chunk;
+        let offset = self.encoded_len() + first * (chunk + TAG_LEN);
+        let span: u64 = (first..=last)
+            .map(|i| self.chunk_plain_len(i) + TAG_LEN)
+            .sum();
+        (first, offset, span)
+    }
+
+    fn chunk_plain_len(&self, index: u64) -> u64 {
+        let chunk = u64::from(self.chunk_size);
+        self.len.saturating_sub(index * chunk).min(chunk)
+    }
+
+    fn chunk_aad(&self, index: u64) -> [u8; 52] {
+        let mut aad = [0u8; 52];
+        aad[..32].copy_from_slice(&self.digest);
+        aad[32..40].copy_from_slice(&self.len.to_le_bytes());
+        aad[40..44].copy_from_slice(&self.chunk_size.to_le_bytes());
+        aad[44..].copy_from_slice(&index.to_le_bytes());
+        aad
+    }
+}
+
+/// Read and decode an envelope header at the reader's position; `None` if not sealed.
+///
+/// The reader is left somewhere inside the header either way; seek before reading on.
+pub async fn read_header<R: AsyncRead + Unpin>(
+    cid: &str,
+    r: &mut R,
+) -> Result<Option<EnvelopeHeader>, StorageError> {
+    let mut fixed = Vec::with_capacity(HEADER_FIXED_LEN);
+    (&mut *r)
+        .take(HEADER_FIXED_LEN as u64)
+        .read_to_end(&mut fixed)
+        .await?;
+    let Some(wrapped_len) = EnvelopeHeader::probe(cid, &fixed) else {
+        return Ok(None);
+    };
+    if wrapped_len > MAX_WRAPPED_LEN {
+        return Err(StorageError::IntegrityFailed);
+    }
+    let mut wrapped = Vec::with_capacity(wrapped_len);
+    (&mut *r)
+        .take(wrapped_len as u64)
+        .read_to_end(&mut wrapped)
+        .await?;
+    EnvelopeHeader::decode(&fixed, &wrapped).map(Some)
+}
+
+fn nonce(index: u64) -> Nonce {
+    let mut n = [0u8; 12];
+    n[..8].copy_from_slice(&index.to_le_bytes());
+    Nonce::from(n)
+}
+
+fn cid_digest(cid: &str) -> Option<[u8; 32]> {
+    let mut out = [0u8; 32];
+    hex::decode_to_slice(cid.strip_prefix("b3:")?, &mut out).ok()?;
+    Some(out)
+}
+
+fn read_u32(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(buf[at..at + 4].try_into().unwrap_or_default())
+}
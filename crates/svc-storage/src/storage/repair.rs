@@ -1 +1,406 @@
-// Storage module: repair (scaffold)
+//! RO:WHAT — Background repair worker: re-hash local CAS blobs and shards, quarantine bit-rot, rebuild from shards or replicas.
+//! RO:WHY — Silent corruption on long-lived nodes; a blob whose BLAKE3 no longer matches its b3 address must never be served.
+//! RO:INTERACTS — storage::FsStorage (list/quarantine/rebuild), RepairSource (replica fetch), readiness::Readiness, metrics.
+//! RO:INVARIANTS — only bytes that hash to the cid (or shards that match their manifest digest) are written back;
+//!                 corrupt blobs are moved aside, never deleted.
+//! RO:METRICS — storage_repair_total{outcome=scanned|corrupt|repaired|error}, storage_repair_backlog.
+//! RO:CONFIG — RepairConfig { interval, max_bytes_per_sec, max_objects_per_pass, degrade_backlog }; partial passes
+//!             resume after the last cid examined, so every object is eventually scanned.
+//! RO:SECURITY — replica bytes are untrusted until their digest matches the address; sealed blobs are only
+//!               checked when the envelope key is loaded.
+//! RO:TEST — tests/storage_repair.rs.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::Bytes;
+use parking_lot::Mutex;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use super::compression;
+use super::{DynStorage, FsStorage, PutOptions, Result, Storage};
+use crate::errors::StorageError;
+use crate::readiness::Readiness;
+
+/// Read buffer used while re-hashing blobs.
+const SCAN_CHUNK: usize = 64 * 1024;
+
+/// Pause before the next pass after a pass panicked or failed outright.
+const FAILED_PASS_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Pacing and degrade knobs for the repair worker.
+#[derive(Debug, Clone)]
+pub struct RepairConfig {
+    /// Delay between full scan passes.
+    pub interval: Duration,
+    /// Re-hash bandwidth cap (0 = unpaced).
+    pub max_bytes_per_sec: u64,
+    /// Objects examined per pass (0 = whole store).
+    pub max_objects_per_pass: usize,
+    /// Readiness degrades once this many objects are corrupt and unrepaired.
+    pub degrade_backlog: usize,
+}
+
+impl Default for RepairConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(3600),
+            // docs/CONFIG.MD `durability.repair_pacing` default.
+            max_bytes_per_sec: 50 * 1024 * 1024,
+            max_objects_per_pass: 0,
+            degrade_backlog: 16,
+        }
+    }
+}
+
+/// Somewhere a clean copy of an object can be fetched from (peer replica, remote node).
+#[async_trait::async_trait]
+pub trait RepairSource: Send + Sync + 'static {
+    async fn fetch(&self, cid: &str) -> Option<Bytes>;
+}
+
+/// Use any `Storage` (e.g. another node's store) as a repair source.
+pub struct StorageSource(pub DynStorage);
+
+#[async_trait::async_trait]
+impl RepairSource for StorageSource {
+    async fn fetch(&self, cid: &str) -> Option<Bytes> {
+        self.0.get_full(cid).await.ok()
+    }
+}
+
+/// Counts for a single scan pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PassReport {
+    pub scanned: u64,
+    pub corrupt: u64,
+    pub repaired: u64,
+    /// Objects that could not be checked or quarantined this pass (I/O errors); retried next time round.
+    pub errors: u64,
+    /// Corrupt objects still waiting for a clean copy after this pass.
+    pub backlog: usize,
+}
+
+/// Scans a `FsStorage` and repairs what it finds.
+pub struct RepairWorker {
+    store: Arc<FsStorage>,
+    cfg: RepairConfig,
+    sources: Vec<Arc<dyn RepairSource>>,
+    readiness: Option<Arc<Readiness>>,
+    backlog: Mutex<BTreeSet<String>>,
+    /// Last cid examined by a partial pass; the next pass starts after it.
+    cursor: Mutex<Option<String>>,
+}
+
+impl RepairWorker {
+    pub fn new(store: Arc<FsStorage>, cfg: RepairConfig) -> Self {
+        Self {
+            store,
+            cfg,
+            sources: Vec::new(),
+            readiness: None,
+            backlog: Mutex::new(BTreeSet::new()),
+            cursor: Mutex::new(None),
+        }
+    }
+
+    /// Add a replica to fetch clean copies from when shards cannot rebuild an object.
+    pub fn with_source(mut self, source: Arc<dyn RepairSource>) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Degrade this readiness handle while the corrupt backlog is too large.
+    pub fn with_readiness(mut self, readiness: Arc<Readiness>) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    /// Objects found corrupt or missing that no source could repair yet.
+    #[must_use]
+    pub fn backlog(&self) -> Vec<String> {
+        self.backlog.lock().iter().cloned().collect()
+    }
+
+    /// Run one scan + repair pass over the store.
+    pub async fn run_pass(&self) -> Result<PassReport> {
+        let mut report = PassReport::default();
+        let mut pacer = Pacer::new(self.cfg.max_bytes_per_sec);
+
+        // Retry earlier failures first so the backlog drains even on partial passes.
+        for cid in self.backlog() {
+            if self.repair(&cid, &mut pacer).await {
+                report.repaired += 1;
+            }
+        }
+
+        let mut cids = self.store.list_cids().await?;
+        if self.cfg.max_objects_per_pass > 0 {
+            // Resume after the cursor and wrap around, so a capped pass still reaches every object.
+            let start = match self.cursor.lock().as_deref() {
+                Some(cursor) => cids.partition_point(|cid| cid.as_str() <= cursor),
+                None => 0,
+            };
+            cids.rotate_left(start);
+            cids.truncate(self.cfg.max_objects_per_pass);
+            *self.cursor.lock() = cids.last().cloned();
+        }
+
+        for cid in cids {
+            if self.backlog.lock().contains(&cid) {
+                // Already counted as corrupt; retried above.
+                continue;
+            }
+            report.scanned += 1;
+            observe("scanned");
+
+            match self.check(&cid, &mut pacer).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    report.corrupt += 1;
+                    observe("corrupt");
+                    self.backlog.lock().insert(cid.clone());
+                    if self.repair(&cid, &mut pacer).await {
+                        report.repaired += 1;
+                    }
+                }
+                Err(e) => {
+                    // One unreadable object must not stop the pass.
+                    report.errors += 1;
+                    observe("error");
+                    tracing::warn!(%cid, error = %e, "repair: could not check object");
+                }
+            }
+        }
+
+        report.backlog = self.backlog.lock().len();
+        self.publish_backlog(report.backlog);
+        Ok(report)
+    }
+
+    /// Check one object; a corrupt blob is quarantined before returning `false`.
+    async fn check(&self, cid: &str, pacer: &mut Pacer) -> Result<bool> {
+        if self.healthy(cid, pacer).await? {
+            return Ok(true);
+        }
+
+        tracing::warn!(%cid, "repair: blob failed BLAKE3 check or shards missing/corrupt");
+        let blob = self.store.root().join(cid);
+        if blob.exists() {
+            let dest = self.store.quarantine(cid).await?;
+            tracing::warn!(%cid, dest = %dest.display(), "repair: quarantined corrupt blob");
+        }
+        Ok(false)
+    }
+
+    /// Whether the local blob hashes to `cid`, or its shards are all intact.
+    async fn healthy(&self, cid: &str, pacer: &mut Pacer) -> Result<bool> {
+        let blob = self.store.root().join(cid);
+        let healthy = if blob.exists() {
+            match self.hash_matches(cid, pacer).await {
+                Ok(ok) => ok,
+                // Raced with a delete or quarantine.
+                Err(StorageError::NotFound) => false,
+                Err(e) => return Err(e),
+            }
+        } else {
+            match self.store.shard_damage(cid).await? {
+                Some((read, damaged)) => {
+                    pacer.consume(read).await;
+                    damaged.is_empty()
+                }
+                None => false,
+            }
+        };
+        Ok(healthy)
+    }
+
+    /// Spawn the worker loop; it stops when `shutdown` flips to `true`.
+    ///
+    /// Each pass runs in its own task so a panic is logged and retried after a
+    /// backoff instead of killing the worker.
+    pub fn spawn(self: Arc<Self>, mut shutdown: watch::Receiver<bool>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                let worker = Arc::clone(&self);
+                let delay = match tokio::spawn(async move { worker.run_pass().await }).await {
+                    Ok(Ok(report)) => {
+                        tracing::info!(?report, "repair pass complete");
+                        self.cfg.interval
+                    }
+                    Ok(Err(e)) => {
+                        tracing::error!(error = %e, "repair pass failed");
+                        FAILED_PASS_BACKOFF
+                    }
+                    Err(e) => {
+                        tracing::error!(error = %e, "repair pass panicked; restarting");
+                        FAILED_PASS_BACKOFF
+                    }
+                };
+
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    changed = shutdown.changed() => {
+                        if changed.is_err() || *shutdown.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Stream the blob through BLAKE3 and compare against its address.
+    ///
+    /// Compressed blobs are inflated first; the address is over the uncompressed bytes.
+    async fn hash_matches(&self, cid: &str, pacer: &mut Pacer) -> Result<bool> {
+        let path = self.store.root().join(cid);
+        let mut f = tokio::fs::File::open(&path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                StorageError::NotFound
+            } else {
+                StorageError::Io(e)
+            }
+        })?;
+
+        if self.store.sealed(cid).await? {
+            pacer.consume(f.metadata().await?.len()).await;
+            return match self.store.read_verified_blob(cid).await {
+                Ok(plain) => Ok(plain.is_some()),
+                // Without the envelope key a sealed blob cannot be checked; leave it be.
+                Err(StorageError::Envelope(e)) => {
+                    tracing::debug!(%cid, error = %e, "repair: skipping sealed blob");
+                    Ok(true)
+                }
+                Err(e) => Err(e),
+            };
+        }
+
+        let header = match compression::read_header(cid, &mut f).await {
+            Ok(header) => header,
+            Err(StorageError::IntegrityFailed) => return Ok(false),
+            Err(e) => return Err(e),
+        };
+        if let Some(header) = header {
+            let mut packed = Vec::new();
+            f.seek(std::io::SeekFrom::Start(header.encoded_len()))
+                .await?;
+            f.read_to_end(&mut packed).await?;
+            pacer.consume(packed.len() as u64).await;
+            let mut plain = Vec::new();
+            if header.inflate_frames(0, &packed, &mut plain).is_err()
+                || plain.len() as u64 != header.len
+            {
+                return Ok(false);
+            }
+            return Ok(blake3::hash(&plain).to_hex().as_str() == &cid[3..]);
+        }
+        f.seek(std::io::SeekFrom::Start(0)).await?;
+
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = vec![0u8; SCAN_CHUNK];
+        loop {
+            let n = f.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            pacer.consume(n as u64).await;
+        }
+        Ok(hasher.finalize().to_hex().as_str() == &cid[3..])
+    }
+
+    /// Try shards first (healing any damaged ones), then each replica; true once a verified copy is back in place.
+    ///
+    /// A copy that became healthy since it was queued (re-stored by a client or pulled by
+    /// replication) clears the entry without fetching anything.
+    async fn repair(&self, cid: &str, pacer: &mut Pacer) -> bool {
+        if matches!(self.healthy(cid, pacer).await, Ok(true)) {
+            if self.backlog.lock().remove(cid) {
+                tracing::info!(%cid, "repair: object healthy again; dropped from backlog");
+            }
+            return true;
+        }
+        let restored = if matches!(self.store.read_manifest(cid).await, Ok(Some(_)))
+            && self.store.rebuild_from_shards(cid).await.is_ok()
+        {
+            true
+        } else {
+            self.fetch_from_sources(cid).await
+        };
+
+        if restored {
+            self.backlog.lock().remove(cid);
+            observe("repaired");
+            tracing::info!(%cid, "repair: object restored");
+        }
+        restored
+    }
+
+    async fn fetch_from_sources(&self, cid: &str) -> bool {
+        for source in &self.sources {
+            let Some(bytes) = source.fetch(cid).await else {
+                continue;
+            };
+            if blake3::hash(&bytes).to_hex().as_str() != &cid[3..] {
+                tracing::warn!(%cid, "repair: replica returned bytes that fail the cid check");
+                continue;
+            }
+            // An object sealed here stays sealed when restored from a replica.
+            let opts = PutOptions {
+                seal: self.store.sealed(cid).await.unwrap_or(false),
+                ..PutOptions::default()
+            };
+            if self.store.restore(cid, bytes, opts).await.is_ok() {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn publish_backlog(&self, backlog: usize) {
+        #[cfg(feature = "metrics")]
+        crate::metrics::set_repair_backlog(backlog as i64);
+
+        if let Some(readiness) = &self.readiness {
+            readiness.set_repair_degraded(backlog >= self.cfg.degrade_backlog);
+        }
+    }
+}
+
+fn observe(_outcome: &'static str) {
+    #[cfg(feature = "metrics")]
+    crate::metrics::observe_repair(_outcome);
+}
+
+/// Simple byte-rate pacer: sleeps whenever reads get ahead of the budget.
+struct Pacer {
+    max_bytes_per_sec: u64,
+    started: Instant,
+    bytes: u64,
+}
+
+impl Pacer {
+    fn new(max_bytes_per_sec: u64) -> Self {
+        Self {
+            max_bytes_per_sec,
+            started: Instant::now(),
+            bytes: 0,
+        }
+    }
+
+    async fn consume(&mut self, n: u64) {
+        if self.max_bytes_per_sec == 0 {
+            return;
+        }
+        self.bytes += n;
+        let due = Duration::from_secs_f64(self.bytes as f64 / self.max_bytes_per_sec as f64);
+        let elapsed = self.started.elapsed();
+        if due > elapsed {
+            tokio::time::sleep(due - elapsed).await;
+        }
+    }
+}
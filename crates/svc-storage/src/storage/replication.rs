@@ -1 +1,495 @@
-// Storage module: replication (scaffold)
+//! RO:WHAT — Replication push/pull protocol between svc-storage instances.
+//! RO:WHY — Objects must live on RF peers chosen by placement, not only on the node that received them.
+//! RO:INTERACTS — storage::placement (targets), Storage (local CAS), ReplicaPeer (LocalPeer in-process, HttpPeer over /o + /replication/inventory).
+//! RO:INVARIANTS — every pulled/pushed body is checked against its b3 cid; replication never deletes; residency filters targets;
+//!                 one object failing never ends a round.
+//! RO:METRICS — storage_replication_total{outcome=pushed|pulled|failed}.
+//! RO:CONFIG — PlacementPolicy (RON_STORAGE_RF, residency env); peer set from the embedding node (main: RON_STORAGE_PEERS).
+//! RO:SECURITY — peers are untrusted byte sources; mismatched bodies are dropped and counted as failures;
+//!               sealed objects are pushed with `x-ron-seal` so they are never stored as plaintext downstream.
+//! RO:TEST — tests/storage_replication.rs.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use parking_lot::RwLock;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use super::placement::{Peer, PlacementEngine};
+use super::{DynStorage, HeadMeta, PutOptions, Result, Storage, SEALED_HEADER, SEAL_HEADER};
+use crate::errors::StorageError;
+
+/// Transport to one remote replica.
+#[async_trait::async_trait]
+pub trait ReplicaPeer: Send + Sync + 'static {
+    async fn has(&self, cid: &str) -> Result<bool>;
+    async fn push(&self, cid: &str, bytes: Bytes, opts: PutOptions<'_>) -> Result<()>;
+    async fn pull(&self, cid: &str) -> Result<Bytes>;
+    /// Length and ETag as the peer reports them (unverified; no body transfer where possible).
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        let bytes = self.pull(cid).await?;
+        Ok(HeadMeta {
+            len: bytes.len() as u64,
+            etag: format!("\"{}\"", cid.trim_start_matches("b3:")),
+        })
+    }
+    /// Whether the peer holds `cid` sealed; pulled copies are sealed locally too.
+    async fn sealed(&self, _cid: &str) -> Result<bool> {
+        Ok(false)
+    }
+    /// Every cid the peer holds.
+    async fn inventory(&self) -> Result<Vec<String>>;
+}
+
+/// In-process peer over another `Storage` (tests, single-binary clusters).
+pub struct LocalPeer {
+    store: DynStorage,
+}
+
+impl LocalPeer {
+    pub fn new(store: DynStorage) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl ReplicaPeer for LocalPeer {
+    async fn has(&self, cid: &str) -> Result<bool> {
+        self.store.exists(cid).await
+    }
+
+    async fn push(&self, cid: &str, bytes: Bytes, opts: PutOptions<'_>) -> Result<()> {
+        self.store.put_with(cid, bytes, opts).await
+    }
+
+    async fn pull(&self, cid: &str) -> Result<Bytes> {
+        self.store.get_full(cid).await
+    }
+
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        self.store.head(cid).await
+    }
+
+    async fn sealed(&self, cid: &str) -> Result<bool> {
+        self.store.sealed(cid).await
+    }
+
+    async fn inventory(&self) -> Result<Vec<String>> {
+        self.store.list().await
+    }
+}
+
+/// Remote svc-storage instance reached over its HTTP object routes.
+pub struct HttpPeer {
+    base_url: String,
+    client: reqwest::Client,
+    token: Option<String>,
+}
+
+impl HttpPeer {
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(http_err)?;
+        Ok(Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            client,
+            token: None,
+        })
+    }
+
+    /// Bearer macaroon sent on every request (needed to pull sealed objects).
+    #[must_use]
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let req = self
+            .client
+            .request(method, format!("{}{path}", self.base_url));
+        match &self.token {
+            Some(token) => req.bearer_auth(token),
+            None => req,
+        }
+    }
+}
+
+fn http_err(e: reqwest::Error) -> StorageError {
+    StorageError::Replication(e.to_string())
+}
+
+#[async_trait::async_trait]
+impl ReplicaPeer for HttpPeer {
+    async fn has(&self, cid: &str) -> Result<bool> {
+        let resp = self
+            .request(reqwest::Method::HEAD, &format!("/o/{cid}"))
+            .send()
+            .await
+            .map_err(http_err)?;
+        match resp.status() {
+            s if s.is_success() => Ok(true),
+            reqwest::StatusCode::NOT_FOUND => Ok(false),
+            s => Err(StorageError::Replication(format!("HEAD returned {s}"))),
+        }
+    }
+
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        let resp = self
+            .request(reqwest::Method::HEAD, &format!("/o/{cid}"))
+            .send()
+            .await
+            .map_err(http_err)?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(StorageError::NotFound);
+        }
+        let resp = resp.error_for_status().map_err(http_err)?;
+        let header = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_owned)
+        };
+        let len = header(reqwest::header::CONTENT_LENGTH)
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| StorageError::Replication("HEAD without Content-Length".into()))?;
+        let etag = header(reqwest::header::ETAG)
+            .unwrap_or_else(|| format!("\"{}\"", cid.trim_start_matches("b3:")));
+        Ok(HeadMeta { len, etag })
+    }
+
+    async fn sealed(&self, cid: &str) -> Result<bool> {
+        let resp = self
+            .request(reqwest::Method::HEAD, &format!("/o/{cid}"))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(http_err)?;
+        Ok(resp.headers().contains_key(SEALED_HEADER))
+    }
+
+    async fn push(&self, cid: &str, bytes: Bytes, opts: PutOptions<'_>) -> Result<()> {
+        #[derive(serde::Deserialize)]
+        struct PutResp {
+            cid: String,
+        }
+
+        let mut req = self.request(reqwest::Method::PUT, "/o").body(bytes);
+        if opts.seal {
+            req = req.header(SEAL_HEADER, "1");
+        }
+        let resp = req
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(http_err)?;
+        let stored: PutResp = resp.json().await.map_err(http_err)?;
+        if stored.cid != cid {
+            return Err(StorageError::IntegrityFailed);
+        }
+        Ok(())
+    }
+
+    async fn pull(&self, cid: &str) -> Result<Bytes> {
+        let resp = self
+            .request(reqwest::Method::GET, &format!("/o/{cid}"))
+            .send()
+            .await
+            .map_err(http_err)?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(StorageError::NotFound);
+        }
+        resp.error_for_status()
+            .map_err(http_err)?
+            .bytes()
+            .await
+            .map_err(http_err)
+    }
+
+    async fn inventory(&self) -> Result<Vec<String>> {
+        #[derive(serde::Deserialize)]
+        struct Inventory {
+            cids: Vec<String>,
+        }
+
+        let inv: Inventory = self
+            .request(reqwest::Method::GET, "/replication/inventory")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(http_err)?
+            .json()
+            .await
+            .map_err(http_err)?;
+        Ok(inv.cids)
+    }
+}
+
+/// Read-only `Storage` view of a peer, used as a hedged-read replica.
+///
+/// Writes are refused: replicas are filled by the `Replicator`, not by serving traffic.
+pub struct PeerStorage(pub Arc<dyn ReplicaPeer>);
+
+#[async_trait::async_trait]
+impl Storage for PeerStorage {
+    async fn put(&self, _cid: &str, _data: Bytes) -> Result<()> {
+        Err(StorageError::Replication(
+            "peer replica is read-only".into(),
+        ))
+    }
+
+    async fn sealed(&self, cid: &str) -> Result<bool> {
+        self.0.sealed(cid).await
+    }
+
+    async fn exists(&self, cid: &str) -> Result<bool> {
+        self.0.has(cid).await
+    }
+
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        self.0.head(cid).await
+    }
+
+    async fn get_full(&self, cid: &str) -> Result<Bytes> {
+        self.0.pull(cid).await
+    }
+
+    async fn get_range(&self, cid: &str, start: u64, end_inclusive: u64) -> Result<(Bytes, u64)> {
+        let full = self.0.pull(cid).await?;
+        let total = full.len() as u64;
+        if start > end_inclusive || end_inclusive >= total {
+            return Err(StorageError::RangeNotSatisfiable);
+        }
+        Ok((full.slice(start as usize..=end_inclusive as usize), total))
+    }
+
+    async fn list(&self) -> Result<Vec<String>> {
+        self.0.inventory().await
+    }
+}
+
+/// Outcome counts for one replicate/sync call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    pub pushed: u64,
+    pub pulled: u64,
+    pub failed: u64,
+    /// Objects with fewer eligible targets than the replication factor.
+    pub under_replicated: u64,
+}
+
+impl SyncReport {
+    fn merge(&mut self, other: SyncReport) {
+        self.pushed += other.pushed;
+        self.pulled += other.pulled;
+        self.failed += other.failed;
+        self.under_replicated += other.under_replicated;
+    }
+}
+
+/// Registered remote peers keyed by id, with their transports.
+type PeerTable = BTreeMap<String, (Peer, Arc<dyn ReplicaPeer>)>;
+
+/// Drives replication for one node: push what it holds to the targets, pull what it should hold.
+pub struct Replicator {
+    local: Peer,
+    store: DynStorage,
+    engine: Arc<PlacementEngine>,
+    peers: RwLock<PeerTable>,
+}
+
+impl Replicator {
+    /// `local` is this node's identity; it is added to the engine's peer set.
+    pub fn new(local: Peer, store: DynStorage, engine: Arc<PlacementEngine>) -> Self {
+        engine.upsert_peer(local.clone());
+        Self {
+            local,
+            store,
+            engine,
+            peers: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    #[must_use]
+    pub fn local(&self) -> &Peer {
+        &self.local
+    }
+
+    /// Register a remote peer with placement and a transport to reach it.
+    pub fn add_peer(&self, peer: Peer, transport: Arc<dyn ReplicaPeer>) {
+        self.peers
+            .write()
+            .insert(peer.id.clone(), (peer.clone(), transport));
+        self.engine.upsert_peer(peer);
+    }
+
+    pub fn remove_peer(&self, id: &str) {
+        self.peers.write().remove(id);
+        self.engine.remove_peer(id);
+    }
+
+    fn transport(&self, id: &str) -> Option<Arc<dyn ReplicaPeer>> {
+        self.peers.read().get(id).map(|(_, t)| Arc::clone(t))
+    }
+
+    /// Push a locally held object to each placement target that lacks it.
+    pub async fn replicate(&self, cid: &str) -> Result<SyncReport> {
+        let mut report = SyncReport::default();
+        let decision = self.engine.place(cid);
+        if decision.shortfall > 0 {
+            report.under_replicated += 1;
+        }
+
+        let opts = PutOptions {
+            seal: self.store.sealed(cid).await?,
+            ..PutOptions::default()
+        };
+        let mut body: Option<Bytes> = None;
+        for target in decision.targets.iter().filter(|p| p.id != self.local.id) {
+            let Some(transport) = self.transport(&target.id) else {
+                report.failed += 1;
+                continue;
+            };
+            match transport.has(cid).await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!(%cid, peer = %target.id, error = %e, "replication: has() failed");
+                    report.failed += 1;
+                    observe("failed");
+                    continue;
+                }
+            }
+            let bytes = match &body {
+                Some(b) => b.clone(),
+                None => body.insert(self.store.get_full(cid).await?).clone(),
+            };
+            match transport.push(cid, bytes, opts).await {
+                Ok(()) => {
+                    report.pushed += 1;
+                    observe("pushed");
+                }
+                Err(e) => {
+                    tracing::warn!(%cid, peer = %target.id, error = %e, "replication: push failed");
+                    report.failed += 1;
+                    observe("failed");
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// One anti-entropy round: push every local object to its targets, then pull
+    /// anything peers hold that placement assigns to this node.
+    ///
+    /// Pulls only come from peers in regions this node's residency permits, so
+    /// a strict-region object never crosses into another region by pull.
+    pub async fn sync_round(&self) -> Result<SyncReport> {
+        let mut report = SyncReport::default();
+
+        for cid in self.store.list().await? {
+            match self.replicate(&cid).await {
+                Ok(r) => report.merge(r),
+                Err(e) => {
+                    tracing::warn!(%cid, error = %e, "replication: replicate failed");
+                    report.failed += 1;
+                    observe("failed");
+                }
+            }
+        }
+
+        let residency = &self.engine.policy().residency;
+        let peers: Vec<(String, Arc<dyn ReplicaPeer>)> = self
+            .peers
+            .read()
+            .values()
+            .filter(|(peer, _)| residency.permits(&peer.region))
+            .map(|(peer, t)| (peer.id.clone(), Arc::clone(t)))
+            .collect();
+        for (peer_id, transport) in peers {
+            let inventory = match transport.inventory().await {
+                Ok(inv) => inv,
+                Err(e) => {
+                    tracing::warn!(peer = %peer_id, error = %e, "replication: inventory failed");
+                    report.failed += 1;
+                    observe("failed");
+                    continue;
+                }
+            };
+            for cid in inventory {
+                if !self.engine.place(&cid).includes(&self.local.id) {
+                    continue;
+                }
+                match self.store.exists(&cid).await {
+                    Ok(false) => {}
+                    Ok(true) => continue,
+                    // A malformed cid from the peer, or a local read error.
+                    Err(e) => {
+                        tracing::warn!(%cid, peer = %peer_id, error = %e, "replication: exists() failed");
+                        report.failed += 1;
+                        observe("failed");
+                        continue;
+                    }
+                }
+                match self.pull_from(transport.as_ref(), &cid).await {
+                    Ok(()) => {
+                        report.pulled += 1;
+                        observe("pulled");
+                    }
+                    Err(e) => {
+                        tracing::warn!(%cid, peer = %peer_id, error = %e, "replication: pull failed");
+                        report.failed += 1;
+                        observe("failed");
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Run a sync round every `interval` until `shutdown` flips to `true`.
+    pub fn spawn(
+        self: Arc<Self>,
+        interval: Duration,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.sync_round().await {
+                    Ok(report) => tracing::info!(?report, "replication round complete"),
+                    Err(e) => tracing::error!(error = %e, "replication round failed"),
+                }
+                tokio::select! {
+                    _ = tokio::time::sleep(interval) => {}
+                    changed = shutdown.changed() => {
+                        if changed.is_err() || *shutdown.borrow() {
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    async fn pull_from(&self, transport: &dyn ReplicaPeer, cid: &str) -> Result<()> {
+        let bytes = transport.pull(cid).await?;
+        if format!("b3:{}", blake3::hash(&bytes).to_hex()) != cid {
+            return Err(StorageError::IntegrityFailed);
+        }
+        let opts = PutOptions {
+            seal: transport.sealed(cid).await?,
+            ..PutOptions::default()
+        };
+        self.store.put_with(cid, bytes, opts).await
+    }
+}
+
+fn observe(_outcome: &'static str) {
+    #[cfg(feature = "metrics")]
+    crate::metrics::observe_replication(_outcome);
+}
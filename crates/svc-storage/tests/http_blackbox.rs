@@ -12,11 +12,12 @@ use std::sync::Arc;
 use axum::{
     body::{to_bytes, Body, Bytes},
     http::{header, HeaderMap, Method, Request, StatusCode},
-    Router,
+    Extension, Router,
 };
 use serde_json::Value;
 use svc_storage::{
     http::{extractors::AppState, server::build_router},
+    readiness::Readiness,
     storage::{MemoryStorage, Storage},
 };
 use tower::ServiceExt;
@@ -219,3 +220,23 @@ async fn blackbox_put_head_get_range() {
         );
     }
 }
+
+#[tokio::test]
+async fn readyz_follows_readiness_when_wired() {
+    let readiness = Arc::new(Readiness::new());
+    readiness.set_config_loaded(true);
+    readiness.set_listeners_bound(true);
+    let router = app().layer(Extension(Arc::clone(&readiness)));
+
+    let (status, _, _) = send(
+        router.clone(),
+        request(Method::GET, "/readyz", Body::empty()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The repair worker degrades readiness while its backlog is too large.
+    readiness.set_repair_degraded(true);
+    let (status, _, _) = send(router, request(Method::GET, "/readyz", Body::empty())).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
@@ -0,0 +1,221 @@
+//! RO:WHAT — At-rest compression tests for storage::compression and the FsStorage blob format.
+//! RO:WHY — Compressed blobs must stay invisible to clients: same cid, length, ETag and Range bytes.
+//! RO:INTERACTS — storage::compression::{CompressionConfig, BlobHeader}, storage::FsStorage, storage::repair.
+//! RO:INVARIANTS — cid over uncompressed bytes; ranges decode only overlapping frames; tampered frames fail closed.
+//! RO:METRICS — storage_compression_total is exercised indirectly.
+//! RO:CONFIG — temp data dirs; small chunk sizes so ranges span several frames.
+//! RO:SECURITY — a raw blob is never mistaken for a compressed one; frame output is capped.
+//! RO:TEST — cargo test -p svc-storage --test storage_compression.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use svc_storage::errors::StorageError;
+use svc_storage::storage::compression::{self, CompressionConfig, MAGIC};
+use svc_storage::storage::repair::{RepairConfig, RepairWorker};
+use svc_storage::storage::{FsStorage, PutOptions, Storage};
+
+fn cid_of(bytes: &[u8]) -> String {
+    format!("b3:{}", blake3::hash(bytes).to_hex())
+}
+
+/// Repetitive, text-like payload that zstd shrinks well.
+fn bundle(len: usize) -> Vec<u8> {
+    b"<div class=\"card\"><p>hello rustyonions</p></div>\n"
+        .iter()
+        .copied()
+        .cycle()
+        .take(len)
+        .collect()
+}
+
+fn small_frames() -> CompressionConfig {
+    CompressionConfig {
+        chunk_size: 1024,
+        ..CompressionConfig::default()
+    }
+}
+
+fn text_html() -> PutOptions<'static> {
+    PutOptions {
+        content_type: Some("text/html"),
+        ..PutOptions::default()
+    }
+}
+
+async fn compressed_store(dir: &tempfile::TempDir) -> FsStorage {
+    FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage")
+        .with_compression(small_frames())
+}
+
+#[test]
+fn policy_respects_threshold_and_content_type() {
+    let cfg = CompressionConfig::default();
+    assert!(!cfg.should_compress(Some("text/html"), cfg.min_bytes - 1));
+    assert!(cfg.should_compress(Some("text/html; charset=utf-8"), cfg.min_bytes));
+    assert!(cfg.should_compress(None, cfg.min_bytes));
+    assert!(!cfg.should_compress(Some("image/JPEG"), 1 << 20));
+    assert!(!cfg.should_compress(Some("video/mp4"), 1 << 20));
+}
+
+#[test]
+fn compress_round_trips_and_skips_incompressible() {
+    let cfg = small_frames();
+    let data = bundle(10_000);
+    let cid = cid_of(&data);
+
+    let packed = cfg.compress(&cid, &data).expect("compress").expect("wins");
+    assert!(packed.len() * 4 < data.len(), "{} bytes", packed.len());
+    assert_eq!(&packed[..4], MAGIC);
+    assert_eq!(
+        compression::inflate(&cid, &packed).expect("inflate"),
+        Some(data)
+    );
+
+    // Pseudo-random bytes do not shrink: stored raw.
+    let mut noise = vec![0u8; 10_000];
+    blake3::Hasher::new()
+        .update(b"noise")
+        .finalize_xof()
+        .fill(&mut noise);
+    assert!(cfg
+        .compress(&cid_of(&noise), &noise)
+        .expect("compress")
+        .is_none());
+}
+
+#[test]
+fn header_is_bound_to_its_cid() {
+    let data = bundle(8_192);
+    let cid = cid_of(&data);
+    let packed = small_frames()
+        .compress(&cid, &data)
+        .expect("compress")
+        .expect("wins");
+
+    // Same bytes read back under a different address are treated as raw.
+    let other = cid_of(b"other");
+    assert_eq!(
+        compression::inflate(&other, &packed).expect("inflate"),
+        None
+    );
+}
+
+#[tokio::test]
+async fn fs_storage_serves_compressed_objects_transparently() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = compressed_store(&dir).await;
+
+    let data = bundle(100_000);
+    let cid = cid_of(&data);
+    store
+        .put_with(&cid, Bytes::from(data.clone()), text_html())
+        .await
+        .expect("put");
+
+    let on_disk = std::fs::metadata(dir.path().join(&cid))
+        .expect("blob")
+        .len();
+    assert!(on_disk * 4 < data.len() as u64, "{on_disk} bytes on disk");
+
+    let head = store.head(&cid).await.expect("head");
+    assert_eq!(head.len, data.len() as u64);
+    assert_eq!(head.etag, format!("\"{}\"", &cid[3..]));
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+
+    // Within one frame, across frame boundaries, and the final partial frame.
+    for (start, end) in [
+        (0, 9),
+        (1000, 1100),
+        (2047, 2048),
+        (5000, 9999),
+        (99_000, 99_999),
+    ] {
+        let (slice, total) = store.get_range(&cid, start, end).await.expect("range");
+        assert_eq!(total, data.len() as u64);
+        assert_eq!(slice.as_ref(), &data[start as usize..=end as usize]);
+    }
+    assert!(matches!(
+        store.get_range(&cid, 99_999, 100_000).await,
+        Err(StorageError::RangeNotSatisfiable)
+    ));
+}
+
+#[tokio::test]
+async fn fs_storage_keeps_media_and_small_objects_raw() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = compressed_store(&dir).await;
+
+    let media = bundle(50_000);
+    let media_cid = cid_of(&media);
+    store
+        .put_with(
+            &media_cid,
+            Bytes::from(media.clone()),
+            PutOptions {
+                content_type: Some("image/png"),
+                ..PutOptions::default()
+            },
+        )
+        .await
+        .expect("put media");
+    assert_eq!(
+        std::fs::read(dir.path().join(&media_cid)).expect("blob"),
+        media
+    );
+
+    let tiny = bundle(100);
+    let tiny_cid = cid_of(&tiny);
+    store
+        .put(&tiny_cid, Bytes::from(tiny.clone()))
+        .await
+        .expect("put tiny");
+    assert_eq!(
+        std::fs::read(dir.path().join(&tiny_cid)).expect("blob"),
+        tiny
+    );
+}
+
+#[tokio::test]
+async fn tampered_compressed_blob_fails_closed_and_is_quarantined() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(compressed_store(&dir).await);
+
+    let data = bundle(20_000);
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+
+    let path = dir.path().join(&cid);
+    let mut blob = std::fs::read(&path).expect("blob");
+    let last = blob.len() - 1;
+    blob[last] ^= 0xff;
+    std::fs::write(&path, &blob).expect("tamper");
+
+    assert!(matches!(
+        store.get_full(&cid).await,
+        Err(StorageError::IntegrityFailed)
+    ));
+
+    let worker = RepairWorker::new(
+        Arc::clone(&store),
+        RepairConfig {
+            interval: Duration::from_millis(10),
+            max_bytes_per_sec: 0,
+            max_objects_per_pass: 0,
+            degrade_backlog: 1,
+        },
+    );
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!(report.corrupt, 1);
+    assert!(!path.exists(), "corrupt blob moved aside");
+    assert_eq!(worker.backlog(), vec![cid]);
+}
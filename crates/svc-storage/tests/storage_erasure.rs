@@ -0,0 +1,279 @@
+//! RO:WHAT — Erasure-coding tests for storage::erasure and the FsStorage shard layout.
+//! RO:WHY — Disk-loss durability; any k of n shards must rebuild the exact object bytes.
+//! RO:INTERACTS — storage::erasure::ErasureCoder, storage::FsStorage, Storage trait.
+//! RO:INVARIANTS — rebuilt bytes hash back to the b3 cid; corrupt shards count as missing; < k shards fails closed.
+//! RO:METRICS — none.
+//! RO:CONFIG — temp data dirs only; 4+2 and 6+3 shapes.
+//! RO:SECURITY — tampered shards are never used for reconstruction.
+//! RO:TEST — cargo test -p svc-storage --test storage_erasure.
+
+use axum::body::Bytes;
+use svc_storage::errors::StorageError;
+use svc_storage::storage::erasure::{ErasureCoder, ErasureConfig};
+use svc_storage::storage::fs::MANIFEST_FILE;
+use svc_storage::storage::{FsStorage, Storage};
+
+fn cid_of(bytes: &[u8]) -> String {
+    format!("b3:{}", blake3::hash(bytes).to_hex())
+}
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+fn four_plus_two() -> ErasureConfig {
+    ErasureConfig {
+        data_shards: 4,
+        parity_shards: 2,
+    }
+}
+
+#[test]
+fn encode_then_reconstruct_from_any_k_shards() {
+    let coder = ErasureCoder::new(four_plus_two()).expect("coder");
+    let data = payload(10_001);
+    let cid = cid_of(&data);
+
+    let (manifest, shards) = coder.encode(&cid, &data).expect("encode");
+    assert_eq!(manifest.shards.len(), 6);
+    assert_eq!(manifest.len, data.len() as u64);
+
+    // Drop every pair of shards in turn; 4 of 6 always suffice.
+    for a in 0..6 {
+        for b in (a + 1)..6 {
+            let survivors = shards
+                .iter()
+                .enumerate()
+                .map(|(i, s)| (i != a && i != b).then(|| s.to_vec()))
+                .collect();
+            let rebuilt = coder.reconstruct(&manifest, survivors).expect("rebuild");
+            assert_eq!(rebuilt.as_ref(), data.as_slice(), "lost shards {a},{b}");
+        }
+    }
+}
+
+#[test]
+fn too_few_or_corrupt_shards_fail_closed() {
+    let coder = ErasureCoder::new(four_plus_two()).expect("coder");
+    let data = payload(4096);
+    let cid = cid_of(&data);
+    let (manifest, shards) = coder.encode(&cid, &data).expect("encode");
+
+    let mut survivors: Vec<Option<Vec<u8>>> = shards.iter().map(|s| Some(s.to_vec())).collect();
+    survivors[0] = None;
+    survivors[1] = None;
+    // Flip a byte in a third shard: it must be treated as missing, leaving only 3.
+    if let Some(shard) = survivors[2].as_mut() {
+        shard[0] ^= 0xff;
+    }
+
+    let err = coder
+        .reconstruct(&manifest, survivors)
+        .expect_err("3 of 6 shards must not rebuild");
+    assert!(matches!(err, StorageError::IntegrityFailed));
+}
+
+#[test]
+fn empty_object_round_trips() {
+    let coder = ErasureCoder::new(ErasureConfig::default()).expect("coder");
+    let cid = cid_of(b"");
+    let (manifest, shards) = coder.encode(&cid, b"").expect("encode");
+    let survivors = shards.iter().map(|s| Some(s.to_vec())).collect();
+    assert!(coder
+        .reconstruct(&manifest, survivors)
+        .expect("rebuild")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn fs_storage_keeps_shards_only_and_heals_lost_shards() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage")
+        .with_erasure(four_plus_two())
+        .expect("erasure");
+
+    let data = payload(50_000);
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+
+    let shard_dir = store.shard_dir_for(&cid).expect("shard dir");
+    assert!(shard_dir.join(MANIFEST_FILE).exists());
+    assert!(!dir.path().join(&cid).exists(), "no blob next to shards");
+
+    // Lose two shards (one data, one parity).
+    let lost = std::fs::read(shard_dir.join("001")).expect("shard");
+    std::fs::remove_file(shard_dir.join("001")).expect("remove shard");
+    std::fs::remove_file(shard_dir.join("005")).expect("remove shard");
+
+    assert!(store.exists(&cid).await.expect("exists"));
+    assert_eq!(store.head(&cid).await.expect("head").len, data.len() as u64);
+
+    let (slice, total) = store.get_range(&cid, 100, 199).await.expect("range");
+    assert_eq!(total, data.len() as u64);
+    assert_eq!(slice.as_ref(), &data[100..200]);
+
+    // The range read rewrote the lost shards, and still no blob.
+    assert_eq!(std::fs::read(shard_dir.join("001")).expect("healed"), lost);
+    assert!(shard_dir.join("005").exists());
+    assert!(!dir.path().join(&cid).exists());
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+}
+
+#[tokio::test]
+async fn fs_storage_survives_losing_a_shard_root() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let roots: Vec<_> = (0..3)
+        .map(|i| dir.path().join(format!("disk{i}")))
+        .collect();
+    let store = FsStorage::new(dir.path().join("data"))
+        .await
+        .expect("fs storage")
+        .with_erasure(four_plus_two())
+        .expect("erasure")
+        .with_shard_roots(roots.clone())
+        .await
+        .expect("shard roots");
+    assert!(store.survives_shard_root_loss());
+
+    let data = payload(30_000);
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+
+    // Six shards over three roots: two each, plus a manifest copy.
+    for root in &roots {
+        let shards = std::fs::read_dir(root.join(format!("{cid}.shards")))
+            .expect("shard dir")
+            .count();
+        assert_eq!(shards, 3);
+    }
+
+    std::fs::remove_dir_all(&roots[0]).expect("lose disk");
+    assert_eq!(store.list_cids().await.expect("list"), vec![cid.clone()]);
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+
+    // The rebuild put the lost root's shards and manifest back.
+    let healed = roots[0].join(format!("{cid}.shards"));
+    assert!(healed.join("000").exists());
+    assert!(healed.join("003").exists());
+    assert!(healed.join(MANIFEST_FILE).exists());
+}
+
+#[tokio::test]
+async fn fs_storage_rebuilds_corrupt_blob() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage")
+        .with_erasure(ErasureConfig::default())
+        .expect("erasure");
+
+    let data = payload(9_999);
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+
+    // A stray blob (e.g. from before erasure was enabled) that rotted.
+    std::fs::write(dir.path().join(&cid), b"bit rot").expect("corrupt blob");
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+    assert!(!dir.path().join(&cid).exists(), "corrupt blob moved aside");
+}
+
+#[tokio::test]
+async fn fs_storage_without_erasure_reports_corruption() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage");
+
+    let data = payload(128);
+    let cid = cid_of(&data);
+    store.put(&cid, Bytes::from(data)).await.expect("put");
+    assert!(!store.shard_dir_for(&cid).expect("shard dir").exists());
+
+    std::fs::write(dir.path().join(&cid), b"bit rot").expect("corrupt blob");
+    let err = store.get_full(&cid).await.expect_err("corrupt blob");
+    assert!(matches!(err, StorageError::IntegrityFailed));
+
+    let missing = cid_of(b"never stored");
+    assert!(matches!(
+        store.get_full(&missing).await,
+        Err(StorageError::NotFound)
+    ));
+    assert!(matches!(
+        store.head("b3:not-hex").await,
+        Err(StorageError::BadAddress)
+    ));
+}
+
+#[tokio::test]
+async fn interrupted_shard_swap_keeps_the_previous_set() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let data = payload(20_000);
+    let cid = cid_of(&data);
+
+    // A blob from before erasure was enabled is only removed once its shards are in place.
+    let plain = FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage");
+    plain
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    let store = FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage")
+        .with_erasure(four_plus_two())
+        .expect("erasure");
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    let live = store.shard_dir_for(&cid).expect("shard dir");
+    let staged = dir.path().join(format!("{cid}.shards.tmp"));
+    let retired = dir.path().join(format!("{cid}.shards.old"));
+    assert!(live.join(MANIFEST_FILE).exists());
+    assert!(!dir.path().join(&cid).exists());
+    assert!(!staged.exists() && !retired.exists());
+
+    // Crash after the live set was moved aside, with the new set half written.
+    std::fs::rename(&live, &retired).expect("move aside");
+    std::fs::create_dir_all(&staged).expect("staging dir");
+    std::fs::write(staged.join("000"), b"partial").expect("partial shard");
+
+    assert_eq!(store.list_cids().await.expect("list"), vec![cid.clone()]);
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+    assert!(live.join(MANIFEST_FILE).exists() && !retired.exists());
+
+    // The next rewrite clears the leftovers.
+    store
+        .restore(&cid, Bytes::from(data.clone()), Default::default())
+        .await
+        .expect("restore");
+    assert!(!staged.exists() && !retired.exists());
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+}
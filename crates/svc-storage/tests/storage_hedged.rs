@@ -0,0 +1,312 @@
+//! RO:WHAT — Hedged read tests: slow primary loses to a replica, bad replica bytes are ignored, Range GETs hedge too.
+//! RO:WHY — Tail latency; one slow disk must not set p99, and hedging must never serve unverified bytes.
+//! RO:INTERACTS — storage::hedged::{HedgedStorage, LatencyTracker}, MemoryStorage, GET /o/:cid.
+//! RO:INVARIANTS — winner hashes to its cid; at most one hedge per call; missing primary falls back to replicas.
+//! RO:METRICS — storage_hedged_read_total exercised indirectly.
+//! RO:CONFIG — short hedge delays so tests run fast.
+//! RO:SECURITY — a replica returning forged bytes never wins.
+//! RO:TEST — cargo test -p svc-storage --test storage_hedged.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    http::{header, Method, Request, StatusCode},
+};
+use svc_storage::errors::StorageError;
+use svc_storage::http::{extractors::AppState, server::build_router};
+use svc_storage::storage::hedged::{HedgeConfig, HedgedStorage, LatencyTracker};
+use svc_storage::storage::{DynStorage, HeadMeta, MemoryStorage, Result, Storage};
+use tower::ServiceExt;
+
+const BODY: &[u8] = b"hedged read payload, long enough to slice a range out of";
+
+fn cid_of(bytes: &[u8]) -> String {
+    format!("b3:{}", blake3::hash(bytes).to_hex())
+}
+
+/// Wraps a store and delays every read (and HEAD by `head_delay`).
+struct Slow {
+    inner: MemoryStorage,
+    delay: Duration,
+    head_delay: Duration,
+}
+
+#[async_trait::async_trait]
+impl Storage for Slow {
+    async fn put(&self, cid: &str, data: Bytes) -> Result<()> {
+        self.inner.put(cid, data).await
+    }
+    async fn exists(&self, cid: &str) -> Result<bool> {
+        self.inner.exists(cid).await
+    }
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        tokio::time::sleep(self.head_delay).await;
+        self.inner.head(cid).await
+    }
+    async fn get_full(&self, cid: &str) -> Result<Bytes> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.get_full(cid).await
+    }
+    async fn get_range(&self, cid: &str, start: u64, end_inclusive: u64) -> Result<(Bytes, u64)> {
+        tokio::time::sleep(self.delay).await;
+        self.inner.get_range(cid, start, end_inclusive).await
+    }
+    async fn list(&self) -> Result<Vec<String>> {
+        self.inner.list().await
+    }
+}
+
+async fn slow_with_body(delay: Duration) -> DynStorage {
+    let inner = MemoryStorage::default();
+    inner
+        .put(&cid_of(BODY), Bytes::from_static(BODY))
+        .await
+        .expect("put");
+    Arc::new(Slow {
+        inner,
+        delay,
+        head_delay: Duration::ZERO,
+    })
+}
+
+/// A replica that holds `BODY` sealed.
+struct SealedReplica(MemoryStorage);
+
+#[async_trait::async_trait]
+impl Storage for SealedReplica {
+    async fn put(&self, cid: &str, data: Bytes) -> Result<()> {
+        self.0.put(cid, data).await
+    }
+    async fn sealed(&self, _cid: &str) -> Result<bool> {
+        Ok(true)
+    }
+    async fn exists(&self, cid: &str) -> Result<bool> {
+        self.0.exists(cid).await
+    }
+    async fn head(&self, cid: &str) -> Result<HeadMeta> {
+        self.0.head(cid).await
+    }
+    async fn get_full(&self, cid: &str) -> Result<Bytes> {
+        self.0.get_full(cid).await
+    }
+    async fn get_range(&self, cid: &str, start: u64, end_inclusive: u64) -> Result<(Bytes, u64)> {
+        self.0.get_range(cid, start, end_inclusive).await
+    }
+    async fn list(&self) -> Result<Vec<String>> {
+        self.0.list().await
+    }
+}
+
+async fn forged_replica() -> DynStorage {
+    // Stores bytes under a cid they do not hash to.
+    let store = MemoryStorage::default();
+    store
+        .put(&cid_of(BODY), Bytes::from_static(b"forged"))
+        .await
+        .expect("put");
+    Arc::new(store)
+}
+
+fn quick() -> HedgeConfig {
+    HedgeConfig {
+        default_delay: Duration::from_millis(10),
+        ..HedgeConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn slow_primary_loses_to_replica() {
+    let primary = slow_with_body(Duration::from_millis(500)).await;
+    let replica = slow_with_body(Duration::ZERO).await;
+    let hedged = HedgedStorage::new(primary, vec![replica], quick());
+
+    let started = Instant::now();
+    let bytes = hedged.get_full(&cid_of(BODY)).await.expect("get");
+    assert_eq!(bytes.as_ref(), BODY);
+    assert!(started.elapsed() < Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn forged_replica_bytes_never_win() {
+    let primary = slow_with_body(Duration::from_millis(50)).await;
+    let hedged = HedgedStorage::new(primary, vec![forged_replica().await], quick());
+
+    let bytes = hedged.get_full(&cid_of(BODY)).await.expect("get");
+    assert_eq!(bytes.as_ref(), BODY);
+}
+
+#[tokio::test]
+async fn missing_primary_falls_back_to_replicas() {
+    let primary: DynStorage = Arc::new(MemoryStorage::default());
+    let good = slow_with_body(Duration::ZERO).await;
+    let hedged = HedgedStorage::new(primary, vec![forged_replica().await, good], quick());
+
+    // Whichever replica is tried first, the verified one serves the read.
+    for _ in 0..2 {
+        let bytes = hedged.get_full(&cid_of(BODY)).await.expect("get");
+        assert_eq!(bytes.as_ref(), BODY);
+    }
+    assert_eq!(
+        hedged.head(&cid_of(BODY)).await.expect("head").len,
+        BODY.len() as u64
+    );
+}
+
+#[tokio::test]
+async fn range_get_route_is_hedged() {
+    let primary = slow_with_body(Duration::from_millis(500)).await;
+    let replica = slow_with_body(Duration::ZERO).await;
+    let store: DynStorage = Arc::new(HedgedStorage::new(primary, vec![replica], quick()));
+    let app = build_router().with_state(AppState { store });
+
+    let started = Instant::now();
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!("/o/{}", cid_of(BODY)))
+                .header(header::RANGE, "bytes=7-10")
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(resp.status(), StatusCode::PARTIAL_CONTENT);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.expect("body");
+    assert_eq!(body.as_ref(), &BODY[7..=10]);
+    assert!(started.elapsed() < Duration::from_millis(400));
+}
+
+#[test]
+fn latency_tracker_percentile_uses_recent_window() {
+    let tracker = LatencyTracker::new(100);
+    assert!(tracker.percentile(0.95).is_none());
+    for ms in 1..=200u64 {
+        tracker.record(Duration::from_millis(ms));
+    }
+    assert_eq!(tracker.len(), 100);
+    assert_eq!(tracker.percentile(0.95), Some(Duration::from_millis(195)));
+    assert_eq!(tracker.percentile(0.5), Some(Duration::from_millis(150)));
+}
+
+#[tokio::test]
+async fn hedge_delay_tracks_primary_percentile() {
+    let primary = slow_with_body(Duration::ZERO).await;
+    let cfg = HedgeConfig {
+        min_samples: 4,
+        min_delay: Duration::from_millis(1),
+        ..quick()
+    };
+    let hedged = HedgedStorage::new(primary, Vec::new(), cfg);
+    assert_eq!(hedged.hedge_delay(), Duration::from_millis(10));
+
+    for ms in [2u64, 3, 4, 40] {
+        hedged.latency().record(Duration::from_millis(ms));
+    }
+    assert_eq!(hedged.hedge_delay(), Duration::from_millis(40));
+}
+
+#[tokio::test]
+async fn unsatisfiable_range_is_not_fanned_out() {
+    let primary = slow_with_body(Duration::ZERO).await;
+    // Any replica read would blow the time budget below.
+    let replica = slow_with_body(Duration::from_secs(5)).await;
+    let store = HedgedStorage::new(primary, vec![replica], quick());
+    let len = BODY.len() as u64;
+
+    let started = Instant::now();
+    for (start, end) in [(len, len + 10), (10, 5)] {
+        assert!(matches!(
+            store.get_range(&cid_of(BODY), start, end).await,
+            Err(StorageError::RangeNotSatisfiable)
+        ));
+    }
+    assert!(started.elapsed() < Duration::from_secs(1));
+
+    let app = build_router().with_state(AppState {
+        store: Arc::new(store),
+    });
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method(Method::GET)
+                .uri(format!("/o/{}", cid_of(BODY)))
+                .header(header::RANGE, format!("bytes={len}-"))
+                .body(Body::empty())
+                .expect("request"),
+        )
+        .await
+        .expect("response");
+    assert_eq!(resp.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(resp.headers()[header::CONTENT_RANGE], format!("*/{len}"));
+}
+
+#[tokio::test]
+async fn range_reads_do_not_wait_for_primary_metadata() {
+    let inner = MemoryStorage::default();
+    inner
+        .put(&cid_of(BODY), Bytes::from_static(BODY))
+        .await
+        .expect("put");
+    let primary: DynStorage = Arc::new(Slow {
+        inner,
+        delay: Duration::ZERO,
+        head_delay: Duration::from_secs(5),
+    });
+    let replica = slow_with_body(Duration::ZERO).await;
+    let store = HedgedStorage::new(primary, vec![replica], quick());
+
+    let started = Instant::now();
+    let (chunk, total) = store.get_range(&cid_of(BODY), 7, 10).await.expect("range");
+    assert_eq!(chunk.as_ref(), &BODY[7..=10]);
+    assert_eq!(total, BODY.len() as u64);
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn objects_only_on_a_sealed_replica_still_need_a_token() {
+    let replica = MemoryStorage::default();
+    replica
+        .put(&cid_of(BODY), Bytes::from_static(BODY))
+        .await
+        .expect("put");
+    let primary: DynStorage = Arc::new(MemoryStorage::default());
+    let store = HedgedStorage::new(primary, vec![Arc::new(SealedReplica(replica))], quick());
+    assert!(store.sealed(&cid_of(BODY)).await.expect("sealed"));
+
+    let app = build_router().with_state(AppState {
+        store: Arc::new(store),
+    });
+    let uri = format!("/o/{}", cid_of(BODY));
+    for req in [
+        Request::get(&uri).body(Body::empty()),
+        Request::get(&uri)
+            .header(header::RANGE, "bytes=0-3")
+            .body(Body::empty()),
+        Request::head(&uri).body(Body::empty()),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(req.expect("request"))
+            .await
+            .expect("response");
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
+#[tokio::test]
+async fn plain_replica_objects_stay_anonymous_and_unknown_ones_fail_closed() {
+    let primary: DynStorage = Arc::new(MemoryStorage::default());
+    let plain = HedgedStorage::new(
+        Arc::clone(&primary),
+        vec![slow_with_body(Duration::ZERO).await],
+        quick(),
+    );
+    assert!(!plain.sealed(&cid_of(BODY)).await.expect("sealed"));
+
+    // No replica vouches for the object, so its status is unknown.
+    let nobody = HedgedStorage::new(primary, vec![forged_replica().await], quick());
+    assert!(nobody.sealed(&cid_of(b"elsewhere")).await.expect("sealed"));
+}
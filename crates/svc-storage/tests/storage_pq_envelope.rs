@@ -0,0 +1,289 @@
+//! RO:WHAT — Sealed-envelope tests for storage::pq_envelope and the FsStorage sealed blob path.
+//! RO:WHY — Sealed objects must read back exactly like plain ones for key holders and never leak plaintext at rest.
+//! RO:INTERACTS — storage::pq_envelope::Envelope, storage::FsStorage (+ erasure), ron_kms::pq::HybridKeypair, GET/HEAD routes.
+//! RO:INVARIANTS — cid over plaintext; ranges decrypt only overlapping chunks; wrong key or flipped bit is IntegrityFailed.
+//! RO:METRICS — none.
+//! RO:CONFIG — feature `pq-envelope`; temp data dirs and key files; small chunk sizes so ranges span several chunks.
+//! RO:SECURITY — on-disk blobs and shards never contain the plaintext; sealed GETs need a macaroon even in dev mode.
+//! RO:TEST — cargo test -p svc-storage --features pq-envelope --test storage_pq_envelope.
+
+#![cfg(feature = "pq-envelope")]
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    http::{header, Method, Request, StatusCode},
+};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as B64;
+use base64::Engine;
+use ron_kms::pq::HybridKeypair;
+use svc_storage::errors::StorageError;
+use svc_storage::http::{extractors::AppState, server::build_router};
+use svc_storage::storage::erasure::ErasureConfig;
+use svc_storage::storage::pq_envelope::{Envelope, MAGIC};
+use svc_storage::storage::{
+    FsStorage, MemoryStorage, PutOptions, Storage, SEALED_HEADER, SEAL_HEADER,
+};
+use tower::ServiceExt;
+
+fn cid_of(bytes: &[u8]) -> String {
+    format!("b3:{}", blake3::hash(bytes).to_hex())
+}
+
+fn payload(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+fn sealed() -> PutOptions<'static> {
+    PutOptions {
+        seal: true,
+        ..PutOptions::default()
+    }
+}
+
+fn envelope(kp: HybridKeypair) -> Envelope {
+    Envelope::new(Arc::new(kp)).with_chunk_size(1024)
+}
+
+async fn sealed_store(dir: &tempfile::TempDir, kp: HybridKeypair) -> FsStorage {
+    FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage")
+        .with_envelope(envelope(kp))
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[test]
+fn seal_round_trips_and_wrong_key_fails_closed() {
+    let kp = HybridKeypair::generate();
+    let data = payload(5_000);
+    let cid = cid_of(&data);
+
+    let sealer = envelope(HybridKeypair::from_secret_bytes(&kp.secret_bytes()).expect("key"));
+    let blob = sealer.seal(&cid, &data).expect("seal");
+    assert_eq!(&blob[..4], MAGIC);
+    assert!(!contains(&blob, &data[..64]));
+    assert_eq!(sealer.open(&cid, &blob).expect("open"), Some(data.clone()));
+
+    // Plain bytes are not an envelope; an envelope under another cid is not either.
+    assert_eq!(sealer.open(&cid, &data).expect("open plain"), None);
+    assert_eq!(sealer.open(&cid_of(b"x"), &blob).expect("open other"), None);
+
+    let stranger = envelope(HybridKeypair::generate());
+    assert!(matches!(
+        stranger.open(&cid, &blob),
+        Err(StorageError::IntegrityFailed)
+    ));
+
+    let mut flipped = blob.clone();
+    let last = flipped.len() - 1;
+    flipped[last] ^= 1;
+    assert!(matches!(
+        sealer.open(&cid, &flipped),
+        Err(StorageError::IntegrityFailed)
+    ));
+}
+
+#[tokio::test]
+async fn fs_storage_serves_sealed_objects_and_ranges() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = sealed_store(&dir, HybridKeypair::generate()).await;
+
+    let data = payload(10_000);
+    let cid = cid_of(&data);
+    store
+        .put_with(&cid, Bytes::from(data.clone()), sealed())
+        .await
+        .expect("put");
+
+    let on_disk = std::fs::read(dir.path().join(&cid)).expect("blob");
+    assert_eq!(&on_disk[..4], MAGIC);
+    assert!(!contains(&on_disk, &data[..64]));
+    assert!(store.sealed(&cid).await.expect("sealed"));
+
+    let head = store.head(&cid).await.expect("head");
+    assert_eq!(head.len, data.len() as u64);
+    assert_eq!(head.etag, format!("\"{}\"", &cid[3..]));
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+
+    for (start, end) in [(0, 9), (1000, 1100), (1023, 1024), (3000, 9999)] {
+        let (slice, total) = store.get_range(&cid, start, end).await.expect("range");
+        assert_eq!(total, data.len() as u64);
+        assert_eq!(slice.as_ref(), &data[start as usize..=end as usize]);
+    }
+    assert!(matches!(
+        store.get_range(&cid, 9_999, 10_000).await,
+        Err(StorageError::RangeNotSatisfiable)
+    ));
+
+    // A later plain put of the same bytes does not unseal the object.
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put plain");
+    assert!(store.sealed(&cid).await.expect("still sealed"));
+}
+
+#[tokio::test]
+async fn seal_requests_are_refused_without_a_key() {
+    let data = Bytes::from_static(b"confidential");
+    let cid = cid_of(&data);
+
+    let memory = MemoryStorage::new();
+    assert!(matches!(
+        memory.put_with(&cid, data.clone(), sealed()).await,
+        Err(StorageError::Envelope(_))
+    ));
+    assert!(matches!(
+        memory.get_full(&cid).await,
+        Err(StorageError::NotFound)
+    ));
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let keyless = FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage");
+    assert!(matches!(
+        keyless.put_with(&cid, data, sealed()).await,
+        Err(StorageError::Envelope(_))
+    ));
+    assert!(!dir.path().join(&cid).exists());
+}
+
+#[tokio::test]
+async fn sealed_object_rebuilds_from_ciphertext_shards() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = sealed_store(&dir, HybridKeypair::generate())
+        .await
+        .with_erasure(ErasureConfig {
+            data_shards: 4,
+            parity_shards: 2,
+        })
+        .expect("erasure");
+
+    let data = payload(8_000);
+    let cid = cid_of(&data);
+    store
+        .put_with(&cid, Bytes::from(data.clone()), sealed())
+        .await
+        .expect("put");
+
+    let shard_dir = store.shard_dir_for(&cid).expect("shard dir");
+    for i in 0..4 {
+        let shard = std::fs::read(shard_dir.join(format!("{i:03}"))).expect("shard");
+        assert!(!contains(&shard, &data[..64]), "shard {i} leaks plaintext");
+    }
+
+    assert!(!dir.path().join(&cid).exists(), "shards only");
+    let first = std::fs::read(shard_dir.join("000")).expect("shard");
+    assert_eq!(&first[..4], MAGIC);
+    std::fs::remove_file(shard_dir.join("001")).expect("drop shard");
+
+    assert_eq!(store.head(&cid).await.expect("head").len, data.len() as u64);
+    assert_eq!(
+        store.get_full(&cid).await.expect("rebuild").as_ref(),
+        data.as_slice()
+    );
+    let healed = std::fs::read(shard_dir.join("001")).expect("healed shard");
+    assert!(!contains(&healed, &data[..64]));
+    assert!(store.sealed(&cid).await.expect("sealed"));
+}
+
+#[tokio::test]
+async fn key_file_round_trips() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let kp = HybridKeypair::generate();
+    let key_path = dir.path().join("envelope.key");
+    std::fs::write(&key_path, kp.secret_bytes().as_slice()).expect("write key");
+
+    let data = payload(3_000);
+    let cid = cid_of(&data);
+    let blob = envelope(kp).seal(&cid, &data).expect("seal");
+    let loaded = Envelope::from_key_file(&key_path).expect("load key");
+    assert_eq!(loaded.open(&cid, &blob).expect("open"), Some(data));
+
+    std::fs::write(&key_path, b"short").expect("truncate key");
+    assert!(matches!(
+        Envelope::from_key_file(&key_path),
+        Err(StorageError::Envelope(_))
+    ));
+}
+
+fn mint_token(secret: &[u8; 32]) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_secs();
+    let (ts, exp) = (now, now + 300);
+    let sig = blake3::keyed_hash(secret, format!("v=1|ts={ts}|exp={exp}").as_bytes());
+    B64.encode(format!("v=1;ts={ts};exp={exp};sig={}", sig.to_hex()))
+}
+
+#[tokio::test]
+async fn sealed_get_requires_a_macaroon() {
+    let secret = [7u8; 32];
+    std::env::set_var("RON_STORAGE_MACAROON_SECRET", B64.encode(secret));
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store: Arc<dyn Storage> = Arc::new(sealed_store(&dir, HybridKeypair::generate()).await);
+    let router = build_router().with_state(AppState { store });
+
+    let data = payload(2_000);
+    let cid = cid_of(&data);
+    let put = Request::builder()
+        .method(Method::PUT)
+        .uri("/o")
+        .header(SEAL_HEADER, "1")
+        .body(Body::from(data.clone()))
+        .expect("put request");
+    let resp = router.clone().oneshot(put).await.expect("put");
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let head = Request::builder()
+        .method(Method::HEAD)
+        .uri(format!("/o/{cid}"))
+        .body(Body::empty())
+        .expect("head request");
+    let resp = router.clone().oneshot(head).await.expect("head");
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let head = Request::builder()
+        .method(Method::HEAD)
+        .uri(format!("/o/{cid}"))
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", mint_token(&secret)),
+        )
+        .body(Body::empty())
+        .expect("head request");
+    let resp = router.clone().oneshot(head).await.expect("head");
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()[SEALED_HEADER], "true");
+
+    let anonymous = Request::builder()
+        .uri(format!("/o/{cid}"))
+        .body(Body::empty())
+        .expect("get request");
+    let resp = router.clone().oneshot(anonymous).await.expect("get");
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let authorized = Request::builder()
+        .uri(format!("/o/{cid}"))
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", mint_token(&secret)),
+        )
+        .body(Body::empty())
+        .expect("get request");
+    let resp = router.oneshot(authorized).await.expect("get");
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.expect("body");
+    assert_eq!(body.as_ref(), data.as_slice());
+}
@@ -0,0 +1,392 @@
+//! RO:WHAT — Repair worker tests: bit-rot detection, quarantine, rebuild from shards or replicas, readiness degrade.
+//! RO:WHY — Durability; a corrupt blob must be moved aside and restored only from bytes that hash to its address.
+//! RO:INTERACTS — storage::repair::RepairWorker, storage::FsStorage, readiness::Readiness.
+//! RO:INVARIANTS — healthy blobs untouched; quarantined blobs kept; unrepairable objects stay in the backlog.
+//! RO:METRICS — storage_repair_total / storage_repair_backlog are exercised indirectly.
+//! RO:CONFIG — temp data dirs; unpaced worker config.
+//! RO:SECURITY — replica bytes that fail the cid check are rejected.
+//! RO:TEST — cargo test -p svc-storage --test storage_repair.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use svc_storage::readiness::Readiness;
+use svc_storage::storage::compression::CompressionConfig;
+use svc_storage::storage::erasure::ErasureConfig;
+use svc_storage::storage::fs::QUARANTINE_DIR;
+use svc_storage::storage::repair::{RepairConfig, RepairSource, RepairWorker, StorageSource};
+use svc_storage::storage::replication::{LocalPeer, PeerStorage};
+use svc_storage::storage::{DynStorage, FsStorage, PutOptions, Storage};
+
+fn cid_of(bytes: &[u8]) -> String {
+    format!("b3:{}", blake3::hash(bytes).to_hex())
+}
+
+fn unpaced() -> RepairConfig {
+    RepairConfig {
+        interval: Duration::from_millis(10),
+        max_bytes_per_sec: 0,
+        max_objects_per_pass: 0,
+        degrade_backlog: 1,
+    }
+}
+
+async fn store_in(dir: &tempfile::TempDir) -> FsStorage {
+    FsStorage::new(dir.path().to_path_buf())
+        .await
+        .expect("fs storage")
+}
+
+struct LyingReplica;
+
+#[async_trait::async_trait]
+impl RepairSource for LyingReplica {
+    async fn fetch(&self, _cid: &str) -> Option<Bytes> {
+        Some(Bytes::from_static(b"definitely not the object"))
+    }
+}
+
+#[tokio::test]
+async fn healthy_store_scans_clean() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(store_in(&dir).await);
+    for body in [&b"one"[..], b"two", b"three"] {
+        store
+            .put(&cid_of(body), Bytes::copy_from_slice(body))
+            .await
+            .expect("put");
+    }
+
+    let worker = RepairWorker::new(store, unpaced());
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!(report.scanned, 3);
+    assert_eq!(report.corrupt, 0);
+    assert_eq!(report.backlog, 0);
+}
+
+#[tokio::test]
+async fn corrupt_blob_is_quarantined_and_rebuilt_from_shards() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(
+        store_in(&dir)
+            .await
+            .with_erasure(ErasureConfig::default())
+            .expect("erasure"),
+    );
+    let data = vec![7u8; 20_000];
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    std::fs::write(dir.path().join(&cid), b"flipped bits").expect("corrupt");
+
+    let worker = RepairWorker::new(Arc::clone(&store), unpaced());
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!(report.corrupt, 1);
+    assert_eq!(report.repaired, 1);
+    assert_eq!(report.backlog, 0);
+
+    assert!(!dir.path().join(&cid).exists(), "shards only after repair");
+    assert_eq!(store.get_full(&cid).await.expect("get").as_ref(), &data[..]);
+    let quarantined = std::fs::read_dir(dir.path().join(QUARANTINE_DIR))
+        .expect("quarantine dir")
+        .count();
+    assert_eq!(quarantined, 1);
+}
+
+#[tokio::test]
+async fn corrupt_blob_is_refetched_from_replica() {
+    let local_dir = tempfile::tempdir().expect("tempdir");
+    let replica_dir = tempfile::tempdir().expect("tempdir");
+    let local = Arc::new(store_in(&local_dir).await);
+    let replica: Arc<dyn Storage> = Arc::new(store_in(&replica_dir).await);
+
+    let data = b"replicated object".to_vec();
+    let cid = cid_of(&data);
+    local
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    replica
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    std::fs::write(local_dir.path().join(&cid), b"rot").expect("corrupt");
+
+    let worker = RepairWorker::new(Arc::clone(&local), unpaced())
+        .with_source(Arc::new(LyingReplica))
+        .with_source(Arc::new(StorageSource(replica)));
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!((report.corrupt, report.repaired), (1, 1));
+    assert_eq!(
+        local.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+}
+
+#[tokio::test]
+async fn capped_passes_resume_and_cover_every_object() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(store_in(&dir).await);
+    let mut cids = Vec::new();
+    for i in 0..5u8 {
+        let body = vec![i; 16];
+        let cid = cid_of(&body);
+        store.put(&cid, Bytes::from(body)).await.expect("put");
+        cids.push(cid);
+    }
+    cids.sort();
+    // Rot the last object in scan order; a pass that always restarts at the top never sees it.
+    std::fs::write(dir.path().join(&cids[4]), b"rot").expect("corrupt");
+
+    let cfg = RepairConfig {
+        max_objects_per_pass: 2,
+        ..unpaced()
+    };
+    let worker = RepairWorker::new(Arc::clone(&store), cfg);
+    let mut scanned = 0;
+    let mut corrupt = 0;
+    for _ in 0..3 {
+        let report = worker.run_pass().await.expect("pass");
+        scanned += report.scanned;
+        corrupt += report.corrupt;
+    }
+    // Passes cover [0, 1], [2, 3], then [4, 0]: every object, wrapping at the end.
+    assert_eq!(scanned, 6);
+    assert_eq!(corrupt, 1);
+    assert_eq!(worker.backlog(), vec![cids[4].clone()]);
+}
+
+#[tokio::test]
+async fn unreadable_object_is_counted_and_the_pass_continues() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(store_in(&dir).await);
+    let good = b"still scanned".to_vec();
+    store
+        .put(&cid_of(&good), Bytes::from(good.clone()))
+        .await
+        .expect("put");
+    // A directory where a blob should be: opening works, reading fails with an I/O error.
+    std::fs::create_dir(dir.path().join(cid_of(b"unreadable"))).expect("mkdir");
+
+    let worker = RepairWorker::new(Arc::clone(&store), unpaced());
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!(report.scanned, 2);
+    assert_eq!(report.errors, 1);
+    assert_eq!(report.corrupt, 0);
+    assert!(worker.backlog().is_empty());
+}
+
+#[tokio::test]
+async fn unrepairable_backlog_degrades_readiness_until_drained() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let replica_dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(store_in(&dir).await);
+    let replica = Arc::new(store_in(&replica_dir).await);
+
+    let data = b"only copy".to_vec();
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    std::fs::write(dir.path().join(&cid), b"rot").expect("corrupt");
+
+    let readiness = Arc::new(Readiness::new());
+    readiness.set_config_loaded(true);
+    readiness.set_listeners_bound(true);
+
+    let worker = RepairWorker::new(Arc::clone(&store), unpaced())
+        .with_source(Arc::new(LyingReplica))
+        .with_source(Arc::new(StorageSource(replica.clone())))
+        .with_readiness(Arc::clone(&readiness));
+
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!((report.corrupt, report.repaired, report.backlog), (1, 0, 1));
+    assert_eq!(worker.backlog(), vec![cid.clone()]);
+    assert!(!readiness.all_ready());
+    assert!(matches!(
+        store.get_full(&cid).await,
+        Err(svc_storage::errors::StorageError::NotFound)
+    ));
+
+    // A replica gains the object; the next pass drains the backlog.
+    replica
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!((report.repaired, report.backlog), (1, 0));
+    assert!(readiness.all_ready());
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+}
+
+#[tokio::test]
+async fn peer_replica_repairs_and_readiness_recovers() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let peer_dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(store_in(&dir).await);
+    let peer: DynStorage = Arc::new(store_in(&peer_dir).await);
+
+    let data = b"replicated copy".to_vec();
+    let cid = cid_of(&data);
+    for s in [&*store as &dyn Storage, &*peer] {
+        s.put(&cid, Bytes::from(data.clone())).await.expect("put");
+    }
+    std::fs::write(dir.path().join(&cid), b"rot").expect("corrupt");
+
+    let readiness = Arc::new(Readiness::new());
+    readiness.set_config_loaded(true);
+    readiness.set_listeners_bound(true);
+
+    // Wired the way main wires replication peers in.
+    let source = PeerStorage(Arc::new(LocalPeer::new(peer)));
+    let worker = RepairWorker::new(Arc::clone(&store), unpaced())
+        .with_source(Arc::new(StorageSource(Arc::new(source))))
+        .with_readiness(Arc::clone(&readiness));
+
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!((report.corrupt, report.repaired, report.backlog), (1, 1, 0));
+    assert!(readiness.all_ready());
+    assert_eq!(
+        store.get_full(&cid).await.expect("get").as_ref(),
+        data.as_slice()
+    );
+}
+
+#[tokio::test]
+async fn backlog_entry_clears_once_the_local_copy_is_healthy_again() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(store_in(&dir).await);
+
+    let data = b"re-uploaded later".to_vec();
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    std::fs::write(dir.path().join(&cid), b"rot").expect("corrupt");
+
+    let readiness = Arc::new(Readiness::new());
+    readiness.set_config_loaded(true);
+    readiness.set_listeners_bound(true);
+
+    // No sources: nothing but a fresh local copy can clear the entry.
+    let worker =
+        RepairWorker::new(Arc::clone(&store), unpaced()).with_readiness(Arc::clone(&readiness));
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!(report.backlog, 1);
+    assert!(!readiness.all_ready());
+
+    // A client stores the object again.
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!(report.backlog, 0);
+    assert!(worker.backlog().is_empty());
+    assert!(readiness.all_ready());
+}
+
+#[tokio::test]
+async fn spawned_worker_repairs_and_stops_on_shutdown() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(
+        store_in(&dir)
+            .await
+            .with_erasure(ErasureConfig {
+                data_shards: 2,
+                parity_shards: 1,
+            })
+            .expect("erasure"),
+    );
+    let data = b"background repair".to_vec();
+    let cid = cid_of(&data);
+    store
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+    let shard = store.shard_dir_for(&cid).expect("shard dir").join("000");
+    let original = std::fs::read(&shard).expect("shard");
+    std::fs::write(&shard, b"rot").expect("corrupt shard");
+
+    let (tx, rx) = tokio::sync::watch::channel(false);
+    let handle = Arc::new(RepairWorker::new(Arc::clone(&store), unpaced())).spawn(rx);
+
+    for _ in 0..200 {
+        if std::fs::read(&shard).is_ok_and(|s| s == original) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(std::fs::read(&shard).expect("healed"), original);
+    assert_eq!(store.get_full(&cid).await.expect("get").as_ref(), &data[..]);
+
+    tx.send(true).expect("shutdown");
+    tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("worker stops")
+        .expect("worker task");
+}
+
+#[tokio::test]
+async fn restored_object_keeps_its_content_type() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let replica_dir = tempfile::tempdir().expect("tempdir");
+    let store = Arc::new(
+        store_in(&dir)
+            .await
+            .with_erasure(ErasureConfig {
+                data_shards: 2,
+                parity_shards: 1,
+            })
+            .expect("erasure")
+            .with_compression(CompressionConfig {
+                min_bytes: 0,
+                ..CompressionConfig::default()
+            }),
+    );
+    let replica: Arc<dyn Storage> = Arc::new(store_in(&replica_dir).await);
+
+    // Compressible bytes, but the type says media: stored raw.
+    let data = vec![b'a'; 64 * 1024];
+    let cid = cid_of(&data);
+    let video = PutOptions {
+        content_type: Some("video/mp4"),
+        ..PutOptions::default()
+    };
+    store
+        .put_with(&cid, Bytes::from(data.clone()), video)
+        .await
+        .expect("put");
+    replica
+        .put(&cid, Bytes::from(data.clone()))
+        .await
+        .expect("put");
+
+    // Lose more shards than parity covers; only the replica can restore it.
+    let shard_dir = store.shard_dir_for(&cid).expect("shard dir");
+    for i in 0..2 {
+        std::fs::remove_file(shard_dir.join(format!("{i:03}"))).expect("drop shard");
+    }
+
+    let worker = RepairWorker::new(Arc::clone(&store), unpaced())
+        .with_source(Arc::new(StorageSource(replica)));
+    let report = worker.run_pass().await.expect("pass");
+    assert_eq!((report.corrupt, report.repaired), (1, 1));
+
+    let manifest = store
+        .read_manifest(&cid)
+        .await
+        .expect("manifest")
+        .expect("shards");
+    assert_eq!(manifest.content_type.as_deref(), Some("video/mp4"));
+    assert!(!manifest.compressed, "media stays raw after repair");
+    assert_eq!(store.get_full(&cid).await.expect("get").as_ref(), &data[..]);
+}
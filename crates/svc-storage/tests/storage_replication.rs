@@ -0,0 +1,357 @@
+//! RO:WHAT — Placement + replication tests: rendezvous targets, residency filtering, multi-node convergence.
+//! RO:WHY — Every object must end up on its RF placement targets, never in a region residency forbids.
+//! RO:INTERACTS — storage::placement::PlacementEngine, storage::replication::{Replicator, LocalPeer}, FsStorage, /replication/inventory.
+//! RO:INVARIANTS — placement deterministic; strict-region never leaves home; sync rounds converge and verify bytes.
+//! RO:METRICS — storage_replication_total exercised indirectly.
+//! RO:CONFIG — in-process cluster of FsStorage roots in temp dirs.
+//! RO:SECURITY — a peer serving bytes that fail the cid check is not trusted; inventory needs a bearer macaroon.
+//! RO:TEST — cargo test -p svc-storage --test storage_replication.
+
+use std::sync::Arc;
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    http::{header, Method, Request, StatusCode},
+};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as B64;
+use base64::Engine;
+use svc_storage::http::{extractors::AppState, server::build_router};
+use svc_storage::policy::residency::{ResidencyMode, ResidencyPolicy};
+use svc_storage::storage::hedged::{HedgeConfig, HedgedStorage};
+use svc_storage::storage::placement::{Peer, PlacementEngine, PlacementPolicy};
+use svc_storage::storage::replication::{HttpPeer, LocalPeer, PeerStorage, Replicator};
+use svc_storage::storage::{DynStorage, FsStorage, MemoryStorage, Storage};
+use tower::ServiceExt;
+
+fn cid_of(bytes: &[u8]) -> String {
+    format!("b3:{}", blake3::hash(bytes).to_hex())
+}
+
+fn policy(rf: usize, mode: ResidencyMode, home: &str) -> PlacementPolicy {
+    PlacementPolicy {
+        replication_factor: rf,
+        residency: ResidencyPolicy {
+            mode,
+            home_region: home.to_string(),
+            allowed_regions: Vec::new(),
+        },
+    }
+}
+
+fn cluster_peers() -> Vec<Peer> {
+    vec![
+        Peer::new("n1", "us-east-1"),
+        Peer::new("n2", "us-east-1"),
+        Peer::new("n3", "eu-west-1"),
+        Peer::new("n4", "eu-west-1"),
+    ]
+}
+
+#[test]
+fn placement_is_deterministic_and_stable_under_peer_loss() {
+    let engine = PlacementEngine::new(policy(2, ResidencyMode::None, ""));
+    engine.set_peers(cluster_peers());
+
+    for i in 0..64 {
+        let cid = cid_of(format!("object-{i}").as_bytes());
+        let first = engine.place(&cid);
+        assert_eq!(first, engine.place(&cid));
+        assert_eq!(first.targets.len(), 2);
+        assert_eq!(first.shortfall, 0);
+
+        // Rendezvous property: dropping a non-target peer leaves placement unchanged.
+        let bystander = cluster_peers()
+            .into_iter()
+            .find(|p| !first.includes(&p.id))
+            .expect("bystander");
+        engine.remove_peer(&bystander.id);
+        assert_eq!(engine.place(&cid).targets, first.targets);
+        engine.upsert_peer(bystander);
+    }
+}
+
+#[test]
+fn residency_filters_and_prefers_home_region() {
+    let strict = PlacementEngine::new(policy(3, ResidencyMode::StrictRegion, "eu-west-1"));
+    strict.set_peers(cluster_peers());
+    let decision = strict.place(&cid_of(b"strict"));
+    assert!(decision.targets.iter().all(|p| p.region == "eu-west-1"));
+    assert_eq!((decision.targets.len(), decision.shortfall), (2, 1));
+
+    let preferred = PlacementEngine::new(policy(3, ResidencyMode::Region, "us-east-1"));
+    preferred.set_peers(cluster_peers());
+    let decision = preferred.place(&cid_of(b"prefer"));
+    assert_eq!(decision.targets.len(), 3);
+    assert!(decision.targets[..2]
+        .iter()
+        .all(|p| p.region == "us-east-1"));
+    assert_eq!(decision.targets[2].region, "eu-west-1");
+
+    let mut allow = policy(4, ResidencyMode::None, "");
+    allow.residency.allowed_regions = vec!["us-east-1".into()];
+    let allowlisted = PlacementEngine::new(allow);
+    allowlisted.set_peers(cluster_peers());
+    let decision = allowlisted.place(&cid_of(b"allowlist"));
+    assert!(decision.targets.iter().all(|p| p.region == "us-east-1"));
+    assert_eq!(decision.shortfall, 2);
+}
+
+struct Node {
+    _dir: tempfile::TempDir,
+    store: DynStorage,
+    replicator: Replicator,
+}
+
+async fn cluster(rf: usize, mode: ResidencyMode) -> Vec<Node> {
+    let peers = cluster_peers();
+    let mut nodes = Vec::new();
+    for peer in &peers {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store: DynStorage = Arc::new(
+            FsStorage::new(dir.path().to_path_buf())
+                .await
+                .expect("fs storage"),
+        );
+        let engine = Arc::new(PlacementEngine::new(policy(rf, mode, &peer.region)));
+        let replicator = Replicator::new(peer.clone(), Arc::clone(&store), engine);
+        nodes.push(Node {
+            _dir: dir,
+            store,
+            replicator,
+        });
+    }
+    for node in &nodes {
+        for (peer, other) in peers.iter().zip(&nodes) {
+            if peer.id != node.replicator.local().id {
+                node.replicator.add_peer(
+                    peer.clone(),
+                    Arc::new(LocalPeer::new(Arc::clone(&other.store))),
+                );
+            }
+        }
+    }
+    nodes
+}
+
+#[tokio::test]
+async fn sync_rounds_converge_on_placement_targets() {
+    let nodes = cluster(2, ResidencyMode::None).await;
+
+    // Each node ingests its own objects.
+    let mut objects = Vec::new();
+    for (i, node) in nodes.iter().enumerate() {
+        for j in 0..5 {
+            let body = format!("node {i} object {j}").into_bytes();
+            let cid = cid_of(&body);
+            node.store.put(&cid, Bytes::from(body)).await.expect("put");
+            objects.push(cid);
+        }
+    }
+
+    for node in &nodes {
+        node.replicator.sync_round().await.expect("sync");
+    }
+
+    let engine = PlacementEngine::new(policy(2, ResidencyMode::None, ""));
+    engine.set_peers(cluster_peers());
+    for cid in &objects {
+        let decision = engine.place(cid);
+        for (peer, node) in cluster_peers().iter().zip(&nodes) {
+            if decision.includes(&peer.id) {
+                assert!(
+                    node.store.exists(cid).await.expect("exists"),
+                    "{cid} missing on target {}",
+                    peer.id
+                );
+            }
+        }
+    }
+
+    // Converged: another round moves nothing.
+    for node in &nodes {
+        let report = node.replicator.sync_round().await.expect("sync");
+        assert_eq!((report.pushed, report.pulled, report.failed), (0, 0, 0));
+    }
+}
+
+#[tokio::test]
+async fn pull_restores_object_lost_by_a_target() {
+    let nodes = cluster(3, ResidencyMode::None).await;
+    let body = b"pull me back".to_vec();
+    let cid = cid_of(&body);
+    nodes[0]
+        .store
+        .put(&cid, Bytes::from(body.clone()))
+        .await
+        .expect("put");
+    nodes[0]
+        .replicator
+        .replicate(&cid)
+        .await
+        .expect("replicate");
+
+    let engine = PlacementEngine::new(policy(3, ResidencyMode::None, ""));
+    engine.set_peers(cluster_peers());
+    let decision = engine.place(&cid);
+    let (idx, _) = cluster_peers()
+        .iter()
+        .enumerate()
+        .find(|(i, p)| *i != 0 && decision.includes(&p.id))
+        .expect("remote target");
+
+    // Simulate a lost disk on that target, then let it pull.
+    let fresh = tempfile::tempdir().expect("tempdir");
+    let empty: DynStorage = Arc::new(
+        FsStorage::new(fresh.path().to_path_buf())
+            .await
+            .expect("fs storage"),
+    );
+    let engine = Arc::new(PlacementEngine::new(policy(3, ResidencyMode::None, "")));
+    let replacement = Replicator::new(cluster_peers()[idx].clone(), Arc::clone(&empty), engine);
+    for (i, peer) in cluster_peers().into_iter().enumerate() {
+        if i != idx {
+            replacement.add_peer(peer, Arc::new(LocalPeer::new(Arc::clone(&nodes[i].store))));
+        }
+    }
+    let report = replacement.sync_round().await.expect("sync");
+    assert_eq!(report.pulled, 1);
+    assert_eq!(
+        empty.get_full(&cid).await.expect("get").as_ref(),
+        body.as_slice()
+    );
+}
+
+#[tokio::test]
+async fn strict_residency_never_replicates_abroad() {
+    let nodes = cluster(4, ResidencyMode::StrictRegion).await;
+    let body = b"stays in us-east-1".to_vec();
+    let cid = cid_of(&body);
+    nodes[0]
+        .store
+        .put(&cid, Bytes::from(body))
+        .await
+        .expect("put");
+
+    for node in &nodes {
+        node.replicator.sync_round().await.expect("sync");
+    }
+    assert!(nodes[1].store.exists(&cid).await.expect("exists"));
+    assert!(!nodes[2].store.exists(&cid).await.expect("exists"));
+    assert!(!nodes[3].store.exists(&cid).await.expect("exists"));
+}
+
+#[tokio::test]
+async fn unreadable_object_does_not_abort_the_round() {
+    let nodes = cluster(4, ResidencyMode::None).await;
+    let bad = b"rotted before it replicated".to_vec();
+    let good = b"replicates anyway".to_vec();
+    for body in [&bad, &good] {
+        nodes[0]
+            .store
+            .put(&cid_of(body), Bytes::from(body.clone()))
+            .await
+            .expect("put");
+    }
+    std::fs::write(nodes[0]._dir.path().join(cid_of(&bad)), b"rot").expect("corrupt");
+
+    let report = nodes[0].replicator.sync_round().await.expect("sync");
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.pushed, 3);
+    for node in &nodes[1..] {
+        assert!(node.store.exists(&cid_of(&good)).await.expect("exists"));
+        assert!(!node.store.exists(&cid_of(&bad)).await.expect("exists"));
+    }
+}
+
+fn mint_token(secret: &[u8; 32]) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("clock")
+        .as_secs();
+    let (ts, exp) = (now, now + 300);
+    let sig = blake3::keyed_hash(secret, format!("v=1|ts={ts}|exp={exp}").as_bytes());
+    B64.encode(format!("v=1;ts={ts};exp={exp};sig={}", sig.to_hex()))
+}
+
+#[tokio::test]
+async fn inventory_route_requires_a_bearer_token() {
+    let secret = [9u8; 32];
+    std::env::set_var("RON_STORAGE_MACAROON_SECRET", B64.encode(secret));
+
+    let store: Arc<dyn Storage> = Arc::new(MemoryStorage::default());
+    let cid = cid_of(b"inventory");
+    store
+        .put(&cid, Bytes::from_static(b"inventory"))
+        .await
+        .expect("put");
+    let app = build_router().with_state(AppState { store });
+
+    let anonymous = Request::builder()
+        .method(Method::GET)
+        .uri("/replication/inventory")
+        .body(Body::empty())
+        .expect("request");
+    let resp = app.clone().oneshot(anonymous).await.expect("response");
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let authorized = Request::builder()
+        .method(Method::GET)
+        .uri("/replication/inventory")
+        .header(
+            header::AUTHORIZATION,
+            format!("Bearer {}", mint_token(&secret)),
+        )
+        .body(Body::empty())
+        .expect("request");
+    let resp = app.oneshot(authorized).await.expect("response");
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = to_bytes(resp.into_body(), usize::MAX).await.expect("body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("json");
+    assert_eq!(json["cids"], serde_json::json!([cid]));
+}
+
+#[tokio::test]
+async fn peers_serve_as_hedged_read_replicas() {
+    let body = b"only on the peer".to_vec();
+    let cid = cid_of(&body);
+    let peer_store: DynStorage = Arc::new(MemoryStorage::default());
+    peer_store
+        .put(&cid, Bytes::from(body.clone()))
+        .await
+        .expect("put");
+    let replica: DynStorage = Arc::new(PeerStorage(Arc::new(LocalPeer::new(peer_store))));
+    assert!(replica.put(&cid, Bytes::new()).await.is_err(), "read-only");
+
+    let local: DynStorage = Arc::new(MemoryStorage::default());
+    let store = HedgedStorage::new(local, vec![replica], HedgeConfig::default());
+    assert_eq!(store.get_full(&cid).await.expect("get").as_ref(), &body[..]);
+    assert_eq!(store.head(&cid).await.expect("head").len, body.len() as u64);
+}
+
+#[tokio::test]
+async fn http_peer_head_reads_metadata_without_the_body() {
+    let body = b"metadata only".to_vec();
+    let cid = cid_of(&body);
+    let store: DynStorage = Arc::new(MemoryStorage::default());
+    store
+        .put(&cid, Bytes::from(body.clone()))
+        .await
+        .expect("put");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    let app = build_router().with_state(AppState { store });
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let peer =
+        HttpPeer::new(format!("http://{addr}"), std::time::Duration::from_secs(5)).expect("peer");
+    let replica = PeerStorage(Arc::new(peer));
+    let meta = replica.head(&cid).await.expect("head");
+    assert_eq!(meta.len, body.len() as u64);
+    assert_eq!(meta.etag, format!("\"{}\"", blake3::hash(&body).to_hex()));
+    assert!(matches!(
+        replica.head(&cid_of(b"absent")).await,
+        Err(svc_storage::errors::StorageError::NotFound)
+    ));
+}
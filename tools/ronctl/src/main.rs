@@ -2,12 +2,11 @@
 #![forbid(unsafe_code)]
 
 use std::env;
-use std::os::unix::net::UnixStream;
 use std::process::ExitCode;
 
 use clap::{Parser, Subcommand};
-use ron_bus::api::{Envelope, IndexReq, IndexResp};
-use ron_bus::uds::{recv, send};
+use ron_bus::api::{IndexReq, IndexResp, SVC_INDEX};
+use ron_bus::uds::{Client, UdsError};
 
 /// Default UDS path for svc-index if RON_INDEX_SOCK is not set.
 const DEFAULT_INDEX_SOCK: &str = "/tmp/ron/svc-index.sock";
@@ -64,47 +63,24 @@ fn main() -> ExitCode {
 }
 
 fn run(sock: &str, cmd: Command) -> anyhow::Result<ExitCode> {
+    // Error frames from svc-index surface as `UdsError::Remote` and exit 1 via `main`;
+    // undecodable replies keep their per-command exit codes (3/7/11).
+    let mut client = Client::connect(sock, SVC_INDEX)?;
     match cmd {
-        Command::Health => {
-            let mut s = UnixStream::connect(sock)?;
-            let env = Envelope {
-                service: "svc.index".into(),
-                method: "v1.health".into(),
-                corr_id: 1,
-                token: vec![],
-                payload: rmp_serde::to_vec(&IndexReq::Health)?,
-            };
-            send(&mut s, &env)?;
-            let reply = recv(&mut s)?;
-
-            match rmp_serde::from_slice::<IndexResp>(&reply.payload) {
-                Ok(IndexResp::HealthOk) => {
-                    println!("index: OK");
-                    Ok(ExitCode::SUCCESS)
-                }
-                Ok(other) => {
-                    eprintln!("unexpected index response: {:?}", other);
-                    Ok(ExitCode::from(2))
-                }
-                Err(e) => {
-                    eprintln!("decode error: {e}");
-                    Ok(ExitCode::from(3))
-                }
+        Command::Health => match call(&mut client, "v1.health", &IndexReq::Health, 3)? {
+            Err(code) => Ok(code),
+            Ok(IndexResp::HealthOk) => {
+                println!("index: OK");
+                Ok(ExitCode::SUCCESS)
             }
-        }
+            Ok(other) => {
+                eprintln!("unexpected index response: {:?}", other);
+                Ok(ExitCode::from(2))
+            }
+        },
         Command::Resolve { addr } => {
-            let mut s = UnixStream::connect(sock)?;
-            let env = Envelope {
-                service: "svc.index".into(),
-                method: "v1.resolve".into(),
-                corr_id: 2,
-                token: vec![],
-                payload: rmp_serde::to_vec(&IndexReq::Resolve { addr: addr.clone() })?,
-            };
-            send(&mut s, &env)?;
-            let reply = recv(&mut s)?;
-
-            match rmp_serde::from_slice::<IndexResp>(&reply.payload) {
+            match call(&mut client, "v1.resolve", &IndexReq::Resolve { addr }, 7)? {
+                Err(code) => Ok(code),
                 Ok(IndexResp::Resolved { dir }) => {
                     println!("{dir}");
                     Ok(ExitCode::SUCCESS)
@@ -121,28 +97,16 @@ fn run(sock: &str, cmd: Command) -> anyhow::Result<ExitCode> {
                     eprintln!("unexpected index response: {:?}", other);
                     Ok(ExitCode::from(6))
                 }
-                Err(e) => {
-                    eprintln!("decode error: {e}");
-                    Ok(ExitCode::from(7))
-                }
             }
         }
         Command::PutAddress { addr, dir } => {
-            let mut s = UnixStream::connect(sock)?;
-            let env = Envelope {
-                service: "svc.index".into(),
-                method: "v1.put_address".into(),
-                corr_id: 3,
-                token: vec![],
-                payload: rmp_serde::to_vec(&IndexReq::PutAddress {
-                    addr: addr.clone(),
-                    dir: dir.clone(),
-                })?,
-            };
-            send(&mut s, &env)?;
-            let reply = recv(&mut s)?;
-
-            match rmp_serde::from_slice::<IndexResp>(&reply.payload) {
+            match call(
+                &mut client,
+                "v1.put_address",
+                &IndexReq::PutAddress { addr, dir },
+                11,
+            )? {
+                Err(code) => Ok(code),
                 Ok(IndexResp::PutOk) => {
                     println!("ok");
                     Ok(ExitCode::SUCCESS)
@@ -160,11 +124,24 @@ fn run(sock: &str, cmd: Command) -> anyhow::Result<ExitCode> {
                     eprintln!("unexpected index response: {:?}", other);
                     Ok(ExitCode::from(10))
                 }
-                Err(e) => {
-                    eprintln!("decode error: {e}");
-                    Ok(ExitCode::from(11))
-                }
             }
         }
     }
 }
+
+/// One index call; an undecodable reply becomes `Err(decode_exit)` instead of an error.
+fn call(
+    client: &mut Client,
+    method: &str,
+    req: &IndexReq,
+    decode_exit: u8,
+) -> anyhow::Result<Result<IndexResp, ExitCode>> {
+    match client.call::<_, IndexResp>(method, req) {
+        Ok(resp) => Ok(Ok(resp)),
+        Err(UdsError::Decode(e)) => {
+            eprintln!("decode error: {e}");
+            Ok(Err(ExitCode::from(decode_exit)))
+        }
+        Err(e) => Err(e.into()),
+    }
+}